@@ -0,0 +1,5 @@
+-- Versioned hashes cannot be converted back; tokens migrated to them must be reissued
+DELETE FROM auth_tokens WHERE token_prefix IS NOT NULL;
+DROP INDEX IF EXISTS idx_auth_tokens_prefix;
+ALTER TABLE auth_tokens DROP COLUMN token_prefix;
+ALTER TABLE auth_tokens ALTER COLUMN token_hash TYPE VARCHAR(64);
@@ -0,0 +1,7 @@
+-- Versioned token hashes: argon2id / hmac-sha256 strings replace bare SHA-256 hex.
+-- token_prefix is a non-secret selector used to find candidate rows before verifying;
+-- it is NULL for legacy hashes until they are migrated on first use.
+ALTER TABLE auth_tokens ALTER COLUMN token_hash TYPE TEXT;
+ALTER TABLE auth_tokens ADD COLUMN token_prefix VARCHAR(12);
+
+CREATE INDEX idx_auth_tokens_prefix ON auth_tokens(token_prefix);
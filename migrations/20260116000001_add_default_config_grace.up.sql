@@ -0,0 +1,2 @@
+-- Grace period (ms) Vouch waits for builder bids, per default config
+ALTER TABLE vouch_default_configs ADD COLUMN grace TEXT;
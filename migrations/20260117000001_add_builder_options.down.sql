@@ -0,0 +1,11 @@
+ALTER TABLE vouch_proposer_patterns
+    DROP COLUMN IF EXISTS builder_boost_factor,
+    DROP COLUMN IF EXISTS builder_enabled;
+
+ALTER TABLE vouch_proposers
+    DROP COLUMN IF EXISTS builder_boost_factor,
+    DROP COLUMN IF EXISTS builder_enabled;
+
+ALTER TABLE vouch_default_configs
+    DROP COLUMN IF EXISTS builder_boost_factor,
+    DROP COLUMN IF EXISTS builder_enabled;
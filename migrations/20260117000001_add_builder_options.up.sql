@@ -0,0 +1,12 @@
+-- Builder options passed through to Vouch execution configs
+ALTER TABLE vouch_default_configs
+    ADD COLUMN builder_enabled BOOLEAN,
+    ADD COLUMN builder_boost_factor TEXT;
+
+ALTER TABLE vouch_proposers
+    ADD COLUMN builder_enabled BOOLEAN,
+    ADD COLUMN builder_boost_factor TEXT;
+
+ALTER TABLE vouch_proposer_patterns
+    ADD COLUMN builder_enabled BOOLEAN,
+    ADD COLUMN builder_boost_factor TEXT;
@@ -0,0 +1,10 @@
+-- Latest validator registration each relay has seen for a proposer
+CREATE TABLE vouch_validator_registrations (
+    public_key TEXT NOT NULL REFERENCES vouch_proposers(public_key) ON DELETE CASCADE,
+    relay_url TEXT NOT NULL,
+    fee_recipient TEXT NOT NULL,
+    gas_limit TEXT NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (public_key, relay_url)
+);
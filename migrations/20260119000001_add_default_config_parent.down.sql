@@ -0,0 +1 @@
+ALTER TABLE vouch_default_configs DROP COLUMN IF EXISTS parent;
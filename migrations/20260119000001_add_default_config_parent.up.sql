@@ -0,0 +1,3 @@
+-- Optional parent config; the execution config merges the chain, child values win
+ALTER TABLE vouch_default_configs
+    ADD COLUMN parent TEXT REFERENCES vouch_default_configs(name) ON UPDATE CASCADE;
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_vouch_proposer_patterns_labels;
+DROP INDEX IF EXISTS idx_vouch_proposers_labels;
+ALTER TABLE vouch_proposer_patterns DROP COLUMN IF EXISTS labels, DROP COLUMN IF EXISTS note;
+ALTER TABLE vouch_proposers DROP COLUMN IF EXISTS labels, DROP COLUMN IF EXISTS note;
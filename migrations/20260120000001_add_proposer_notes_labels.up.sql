@@ -0,0 +1,12 @@
+-- Free-form note and key/value labels, e.g. the customer or operator a validator belongs to
+ALTER TABLE vouch_proposers
+    ADD COLUMN note TEXT,
+    ADD COLUMN labels JSONB NOT NULL DEFAULT '{}';
+
+ALTER TABLE vouch_proposer_patterns
+    ADD COLUMN note TEXT,
+    ADD COLUMN labels JSONB NOT NULL DEFAULT '{}';
+
+-- Supports the `labels @> ...` containment filters
+CREATE INDEX idx_vouch_proposers_labels ON vouch_proposers USING GIN (labels);
+CREATE INDEX idx_vouch_proposer_patterns_labels ON vouch_proposer_patterns USING GIN (labels);
@@ -0,0 +1,5 @@
+-- Relay URLs switched off across every config, e.g. during a relay incident
+CREATE TABLE disabled_relays (
+    url TEXT PRIMARY KEY,
+    disabled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,11 @@
+DROP TRIGGER IF EXISTS commit_boost_mux_keys_version ON commit_boost_mux_keys;
+DROP TRIGGER IF EXISTS commit_boost_mux_configs_version ON commit_boost_mux_configs;
+DROP TRIGGER IF EXISTS disabled_relays_version ON disabled_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_pattern_relays_version ON vouch_proposer_pattern_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_patterns_version ON vouch_proposer_patterns;
+DROP TRIGGER IF EXISTS vouch_proposer_relays_version ON vouch_proposer_relays;
+DROP TRIGGER IF EXISTS vouch_proposers_version ON vouch_proposers;
+DROP TRIGGER IF EXISTS vouch_default_relays_version ON vouch_default_relays;
+DROP TRIGGER IF EXISTS vouch_default_configs_version ON vouch_default_configs;
+DROP FUNCTION IF EXISTS bump_config_version();
+DROP TABLE IF EXISTS config_versions;
@@ -0,0 +1,46 @@
+-- Counters bumped by every change to the data behind the public endpoints.
+-- Replicas compare them to decide whether cached responses and ETags are still current.
+CREATE TABLE config_versions (
+    scope TEXT PRIMARY KEY,
+    version BIGINT NOT NULL DEFAULT 1,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO config_versions (scope) VALUES ('vouch'), ('commit_boost');
+
+CREATE FUNCTION bump_config_version() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE config_versions
+    SET version = version + 1, updated_at = NOW()
+    WHERE scope = TG_ARGV[0];
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER vouch_default_configs_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_default_configs
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_default_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_default_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposers_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposers
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposer_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposer_patterns_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_patterns
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposer_pattern_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_pattern_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER disabled_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON disabled_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER commit_boost_mux_configs_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON commit_boost_mux_configs
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
+CREATE TRIGGER commit_boost_mux_keys_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON commit_boost_mux_keys
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS vouch_config_usage;
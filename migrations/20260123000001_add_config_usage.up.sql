@@ -0,0 +1,13 @@
+-- How often each consumer fetches each default config, to spot configs nothing polls anymore.
+-- Not covered by the config_versions triggers: usage is not part of any response.
+CREATE TABLE vouch_config_usage (
+    config_name TEXT NOT NULL REFERENCES vouch_default_configs(name) ON DELETE CASCADE ON UPDATE CASCADE,
+    -- Signing consumer name, or client IP for unsigned requests
+    consumer TEXT NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    last_key_count INTEGER NOT NULL DEFAULT 0,
+    max_key_count INTEGER NOT NULL DEFAULT 0,
+    first_requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (config_name, consumer)
+);
@@ -0,0 +1 @@
+ALTER TABLE vouch_proposers DROP COLUMN IF EXISTS decommissioned_at;
@@ -0,0 +1,2 @@
+-- Decommissioned proposers keep their row for history but are left out of execution configs
+ALTER TABLE vouch_proposers ADD COLUMN decommissioned_at TIMESTAMPTZ;
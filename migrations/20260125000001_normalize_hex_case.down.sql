@@ -0,0 +1,9 @@
+-- Lowercased values are kept; only the foreign keys go back to their previous form
+ALTER TABLE vouch_validator_registrations
+    DROP CONSTRAINT vouch_validator_registrations_public_key_fkey,
+    ADD CONSTRAINT vouch_validator_registrations_public_key_fkey
+        FOREIGN KEY (public_key) REFERENCES vouch_proposers(public_key) ON DELETE CASCADE;
+ALTER TABLE vouch_proposer_relays
+    DROP CONSTRAINT vouch_proposer_relays_proposer_public_key_fkey,
+    ADD CONSTRAINT vouch_proposer_relays_proposer_public_key_fkey
+        FOREIGN KEY (proposer_public_key) REFERENCES vouch_proposers(public_key) ON DELETE CASCADE;
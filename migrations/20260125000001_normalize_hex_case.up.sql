@@ -0,0 +1,58 @@
+-- Hex keys and addresses are compared as text, so store them in one canonical (lowercase) form.
+
+-- Lowercasing a proposer key must carry its relays and registrations along
+ALTER TABLE vouch_proposer_relays
+    DROP CONSTRAINT vouch_proposer_relays_proposer_public_key_fkey,
+    ADD CONSTRAINT vouch_proposer_relays_proposer_public_key_fkey
+        FOREIGN KEY (proposer_public_key) REFERENCES vouch_proposers(public_key)
+        ON DELETE CASCADE ON UPDATE CASCADE;
+ALTER TABLE vouch_validator_registrations
+    DROP CONSTRAINT vouch_validator_registrations_public_key_fkey,
+    ADD CONSTRAINT vouch_validator_registrations_public_key_fkey
+        FOREIGN KEY (public_key) REFERENCES vouch_proposers(public_key)
+        ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- Of proposers differing only in case keep one: the lowercase row (the only one
+-- execution configs ever looked up), else the most recently updated
+DELETE FROM vouch_proposers p
+USING vouch_proposers q
+WHERE lower(q.public_key) = lower(p.public_key)
+  AND q.public_key <> p.public_key
+  AND (q.public_key = lower(q.public_key), q.updated_at, q.public_key)
+    > (p.public_key = lower(p.public_key), p.updated_at, p.public_key);
+
+UPDATE vouch_proposers
+SET public_key = lower(public_key), fee_recipient = lower(fee_recipient)
+WHERE public_key <> lower(public_key) OR fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_proposer_relays
+SET public_key = lower(public_key), fee_recipient = lower(fee_recipient)
+WHERE public_key <> lower(public_key) OR fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_default_configs SET fee_recipient = lower(fee_recipient)
+WHERE fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_default_relays
+SET public_key = lower(public_key), fee_recipient = lower(fee_recipient)
+WHERE public_key <> lower(public_key) OR fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_proposer_patterns SET fee_recipient = lower(fee_recipient)
+WHERE fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_proposer_pattern_relays
+SET public_key = lower(public_key), fee_recipient = lower(fee_recipient)
+WHERE public_key <> lower(public_key) OR fee_recipient <> lower(fee_recipient);
+
+UPDATE vouch_validator_registrations SET fee_recipient = lower(fee_recipient)
+WHERE fee_recipient <> lower(fee_recipient);
+
+-- Same for mux keys, which are unique per mux
+DELETE FROM commit_boost_mux_keys k
+USING commit_boost_mux_keys o
+WHERE o.mux_name = k.mux_name
+  AND lower(o.public_key) = lower(k.public_key)
+  AND o.public_key <> k.public_key
+  AND (o.public_key = lower(o.public_key), o.id) > (k.public_key = lower(k.public_key), k.id);
+
+UPDATE commit_boost_mux_keys SET public_key = lower(public_key)
+WHERE public_key <> lower(public_key);
@@ -0,0 +1,25 @@
+-- Back to ETH amounts, without trailing zeros
+
+UPDATE vouch_default_configs
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
+
+UPDATE vouch_default_relays
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
+
+UPDATE vouch_proposers
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
+
+UPDATE vouch_proposer_relays
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
+
+UPDATE vouch_proposer_patterns
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
+
+UPDATE vouch_proposer_pattern_relays
+SET min_value = trim_scale(min_value::numeric * 0.000000000000000001)::text
+WHERE min_value ~ '^[0-9]+$';
@@ -0,0 +1,27 @@
+-- min_value is stored as an integer amount of wei. Until now it was stored as
+-- written, in ETH as Vouch reads it (e.g. "0.1", or "1" for one ETH), so every
+-- numeric value is converted; anything else is left as it is.
+
+UPDATE vouch_default_configs
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
+
+UPDATE vouch_default_relays
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
+
+UPDATE vouch_proposers
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
+
+UPDATE vouch_proposer_relays
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
+
+UPDATE vouch_proposer_patterns
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
+
+UPDATE vouch_proposer_pattern_relays
+SET min_value = trunc(min_value::numeric * 1000000000000000000)::text
+WHERE min_value ~ '^([0-9]+(\.[0-9]*)?|\.[0-9]+)$';
@@ -0,0 +1,2 @@
+-- Scopes granted by each API token; every token keeps full admin access
+ALTER TABLE auth_tokens ADD COLUMN scopes TEXT[] NOT NULL DEFAULT '{admin}';
@@ -0,0 +1,5 @@
+ALTER TABLE vouch_proposer_patterns DROP COLUMN IF EXISTS relay_sets;
+ALTER TABLE vouch_proposers DROP COLUMN IF EXISTS relay_sets;
+ALTER TABLE vouch_default_configs DROP COLUMN IF EXISTS relay_sets;
+DROP TABLE IF EXISTS vouch_relay_set_relays;
+DROP TABLE IF EXISTS vouch_relay_sets;
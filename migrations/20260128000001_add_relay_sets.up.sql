@@ -0,0 +1,37 @@
+-- Named relay collections shared by default configs, proposers and patterns
+CREATE TABLE vouch_relay_sets (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE vouch_relay_set_relays (
+    id SERIAL PRIMARY KEY,
+    set_name TEXT NOT NULL REFERENCES vouch_relay_sets(name) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    fee_recipient TEXT,
+    gas_limit TEXT,
+    min_value TEXT,
+    UNIQUE(set_name, url)
+);
+
+CREATE INDEX idx_vouch_relay_set_relays_set_name ON vouch_relay_set_relays(set_name);
+
+CREATE TRIGGER vouch_relay_sets_updated_at
+    BEFORE UPDATE ON vouch_relay_sets
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER vouch_relay_sets_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_relay_sets
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_relay_set_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_relay_set_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+
+-- Sets are expanded in order at read time; later sets and inline relays win by URL.
+-- Names are checked on write and sets in use cannot be deleted.
+ALTER TABLE vouch_default_configs ADD COLUMN relay_sets TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE vouch_proposers ADD COLUMN relay_sets TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE vouch_proposer_patterns ADD COLUMN relay_sets TEXT[] NOT NULL DEFAULT '{}';
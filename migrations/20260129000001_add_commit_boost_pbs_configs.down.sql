@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS commit_boost_pbs_muxes;
+DROP TABLE IF EXISTS commit_boost_pbs_relays;
+DROP TABLE IF EXISTS commit_boost_pbs_configs;
@@ -0,0 +1,51 @@
+-- Complete Commit-Boost PBS configs rendered as cb-config.toml
+CREATE TABLE commit_boost_pbs_configs (
+    name TEXT PRIMARY KEY,
+    chain TEXT NOT NULL,
+    -- [pbs] section, passed through as-is
+    pbs JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Default relays, also available to the config's muxes by relay_id
+CREATE TABLE commit_boost_pbs_relays (
+    id SERIAL PRIMARY KEY,
+    config_name TEXT NOT NULL REFERENCES commit_boost_pbs_configs(name) ON DELETE CASCADE,
+    relay_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    UNIQUE(config_name, relay_id)
+);
+
+-- Muxes take their keys either from a mux config or from a registry
+CREATE TABLE commit_boost_pbs_muxes (
+    id SERIAL PRIMARY KEY,
+    config_name TEXT NOT NULL REFERENCES commit_boost_pbs_configs(name) ON DELETE CASCADE,
+    mux_id TEXT NOT NULL,
+    mux_name TEXT REFERENCES commit_boost_mux_configs(name),
+    registry TEXT CHECK (registry IN ('lido', 'ssv')),
+    node_operator_id BIGINT,
+    relay_ids TEXT[] NOT NULL DEFAULT '{}',
+    UNIQUE(config_name, mux_id),
+    CHECK ((mux_name IS NULL) <> (registry IS NULL)),
+    CHECK ((registry IS NULL) = (node_operator_id IS NULL))
+);
+
+CREATE INDEX idx_commit_boost_pbs_relays_config_name ON commit_boost_pbs_relays(config_name);
+CREATE INDEX idx_commit_boost_pbs_muxes_config_name ON commit_boost_pbs_muxes(config_name);
+CREATE INDEX idx_commit_boost_pbs_muxes_mux_name ON commit_boost_pbs_muxes(mux_name);
+
+CREATE TRIGGER commit_boost_pbs_configs_updated_at
+    BEFORE UPDATE ON commit_boost_pbs_configs
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER commit_boost_pbs_configs_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON commit_boost_pbs_configs
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
+CREATE TRIGGER commit_boost_pbs_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON commit_boost_pbs_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
+CREATE TRIGGER commit_boost_pbs_muxes_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON commit_boost_pbs_muxes
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
@@ -0,0 +1,11 @@
+-- Audit events kept in the database when `audit_database` is enabled,
+-- so the history of a single resource can be queried over the API.
+CREATE TABLE audit_events (
+    id BIGSERIAL PRIMARY KEY,
+    event JSONB NOT NULL,
+    resource_type TEXT GENERATED ALWAYS AS (event->>'resource_type') STORED,
+    resource_id TEXT GENERATED ALWAYS AS (event->>'resource_id') STORED,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_events_resource ON audit_events(resource_type, resource_id, id);
@@ -0,0 +1,6 @@
+ALTER TABLE commit_boost_pbs_configs DROP COLUMN id;
+ALTER TABLE commit_boost_mux_configs DROP COLUMN id;
+ALTER TABLE vouch_relay_sets DROP COLUMN id;
+ALTER TABLE vouch_proposer_patterns DROP COLUMN id;
+ALTER TABLE vouch_proposers DROP COLUMN id;
+ALTER TABLE vouch_default_configs DROP COLUMN id;
@@ -0,0 +1,8 @@
+-- Stable ids that survive renames, for external tooling that tracks resources
+-- (Terraform and the like). Natural keys stay the primary keys.
+ALTER TABLE vouch_default_configs ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
+ALTER TABLE vouch_proposers ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
+ALTER TABLE vouch_proposer_patterns ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
+ALTER TABLE vouch_relay_sets ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
+ALTER TABLE commit_boost_mux_configs ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
+ALTER TABLE commit_boost_pbs_configs ADD COLUMN id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid();
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS vouch_proposer_group_relays;
+DROP TABLE IF EXISTS vouch_proposer_group_keys;
+DROP TABLE IF EXISTS vouch_proposer_groups;
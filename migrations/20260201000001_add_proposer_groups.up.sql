@@ -0,0 +1,53 @@
+-- Settings shared by a batch of proposers. Members are listed keys and/or keys
+-- matching `pattern`; a key in several groups takes the one with the highest
+-- priority (then the first by name). Proposer settings override the group's.
+CREATE TABLE vouch_proposer_groups (
+    name TEXT PRIMARY KEY,
+    id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid(),
+    pattern TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    fee_recipient TEXT,
+    gas_limit TEXT,
+    min_value TEXT,
+    builder_enabled BOOLEAN,
+    builder_boost_factor TEXT,
+    reset_relays BOOLEAN NOT NULL DEFAULT FALSE,
+    note TEXT,
+    relay_sets TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE vouch_proposer_group_keys (
+    group_name TEXT NOT NULL REFERENCES vouch_proposer_groups(name) ON DELETE CASCADE,
+    public_key TEXT NOT NULL,
+    PRIMARY KEY (group_name, public_key)
+);
+
+CREATE TABLE vouch_proposer_group_relays (
+    id SERIAL PRIMARY KEY,
+    group_name TEXT NOT NULL REFERENCES vouch_proposer_groups(name) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    fee_recipient TEXT,
+    gas_limit TEXT,
+    min_value TEXT,
+    UNIQUE(group_name, url)
+);
+
+CREATE INDEX idx_vouch_proposer_group_keys_public_key ON vouch_proposer_group_keys(public_key);
+CREATE INDEX idx_vouch_proposer_group_relays_group_name ON vouch_proposer_group_relays(group_name);
+
+CREATE TRIGGER vouch_proposer_groups_updated_at
+    BEFORE UPDATE ON vouch_proposer_groups
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER vouch_proposer_groups_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_groups
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposer_group_keys_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_group_keys
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
+CREATE TRIGGER vouch_proposer_group_relays_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON vouch_proposer_group_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('vouch');
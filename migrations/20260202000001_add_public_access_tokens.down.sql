@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS commit_boost_mux_configs_access_tokens ON commit_boost_mux_configs;
+DROP TRIGGER IF EXISTS vouch_default_configs_access_tokens ON vouch_default_configs;
+DROP FUNCTION IF EXISTS delete_public_access_tokens();
+DROP TABLE IF EXISTS public_access_tokens;
@@ -0,0 +1,31 @@
+-- Optional bearer tokens guarding the public endpoint of one default config or
+-- mux config. A resource without tokens stays public.
+CREATE TABLE public_access_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    resource_type TEXT NOT NULL CHECK (resource_type IN ('vouch_default_config', 'commit_boost_mux')),
+    resource_name TEXT NOT NULL,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    token_prefix TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_public_access_tokens_resource ON public_access_tokens(resource_type, resource_name);
+
+-- Tokens go away with their resource; TG_ARGV[0] is the resource type
+CREATE OR REPLACE FUNCTION delete_public_access_tokens()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM public_access_tokens
+    WHERE resource_type = TG_ARGV[0] AND resource_name = OLD.name;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER vouch_default_configs_access_tokens
+    AFTER DELETE ON vouch_default_configs
+    FOR EACH ROW EXECUTE FUNCTION delete_public_access_tokens('vouch_default_config');
+CREATE TRIGGER commit_boost_mux_configs_access_tokens
+    AFTER DELETE ON commit_boost_mux_configs
+    FOR EACH ROW EXECUTE FUNCTION delete_public_access_tokens('commit_boost_mux');
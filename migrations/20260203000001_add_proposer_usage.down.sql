@@ -0,0 +1 @@
+DROP TABLE IF EXISTS vouch_proposer_usage;
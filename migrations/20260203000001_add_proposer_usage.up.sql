@@ -0,0 +1,7 @@
+-- When each proposer was last asked for by an execution-config request, so the
+-- cleanup job can find proposers of churned customers. Like vouch_config_usage,
+-- not covered by the config_versions triggers: usage is not part of any response.
+CREATE TABLE vouch_proposer_usage (
+    public_key TEXT PRIMARY KEY REFERENCES vouch_proposers(public_key) ON DELETE CASCADE ON UPDATE CASCADE,
+    last_requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
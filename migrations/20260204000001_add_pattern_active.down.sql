@@ -0,0 +1 @@
+ALTER TABLE vouch_proposer_patterns DROP COLUMN IF EXISTS active;
@@ -0,0 +1,2 @@
+-- Inactive patterns keep their settings and relays but are left out of execution configs
+ALTER TABLE vouch_proposer_patterns ADD COLUMN active BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,28 @@
+DROP TRIGGER IF EXISTS commit_boost_pbs_muxes_touch_delete ON commit_boost_pbs_muxes;
+DROP TRIGGER IF EXISTS commit_boost_pbs_muxes_touch_update ON commit_boost_pbs_muxes;
+DROP TRIGGER IF EXISTS commit_boost_pbs_muxes_touch_insert ON commit_boost_pbs_muxes;
+DROP TRIGGER IF EXISTS commit_boost_pbs_relays_touch_delete ON commit_boost_pbs_relays;
+DROP TRIGGER IF EXISTS commit_boost_pbs_relays_touch_update ON commit_boost_pbs_relays;
+DROP TRIGGER IF EXISTS commit_boost_pbs_relays_touch_insert ON commit_boost_pbs_relays;
+DROP TRIGGER IF EXISTS commit_boost_mux_keys_touch_delete ON commit_boost_mux_keys;
+DROP TRIGGER IF EXISTS commit_boost_mux_keys_touch_update ON commit_boost_mux_keys;
+DROP TRIGGER IF EXISTS commit_boost_mux_keys_touch_insert ON commit_boost_mux_keys;
+DROP TRIGGER IF EXISTS vouch_proposer_group_relays_touch_delete ON vouch_proposer_group_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_group_relays_touch_update ON vouch_proposer_group_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_group_relays_touch_insert ON vouch_proposer_group_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_group_keys_touch_delete ON vouch_proposer_group_keys;
+DROP TRIGGER IF EXISTS vouch_proposer_group_keys_touch_update ON vouch_proposer_group_keys;
+DROP TRIGGER IF EXISTS vouch_proposer_group_keys_touch_insert ON vouch_proposer_group_keys;
+DROP TRIGGER IF EXISTS vouch_relay_set_relays_touch_delete ON vouch_relay_set_relays;
+DROP TRIGGER IF EXISTS vouch_relay_set_relays_touch_update ON vouch_relay_set_relays;
+DROP TRIGGER IF EXISTS vouch_relay_set_relays_touch_insert ON vouch_relay_set_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_pattern_relays_touch_delete ON vouch_proposer_pattern_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_pattern_relays_touch_update ON vouch_proposer_pattern_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_pattern_relays_touch_insert ON vouch_proposer_pattern_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_relays_touch_delete ON vouch_proposer_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_relays_touch_update ON vouch_proposer_relays;
+DROP TRIGGER IF EXISTS vouch_proposer_relays_touch_insert ON vouch_proposer_relays;
+DROP TRIGGER IF EXISTS vouch_default_relays_touch_delete ON vouch_default_relays;
+DROP TRIGGER IF EXISTS vouch_default_relays_touch_update ON vouch_default_relays;
+DROP TRIGGER IF EXISTS vouch_default_relays_touch_insert ON vouch_default_relays;
+DROP FUNCTION IF EXISTS touch_parent_updated_at();
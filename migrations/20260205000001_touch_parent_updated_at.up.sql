@@ -0,0 +1,104 @@
+-- Relays and keys live in their own tables, so changing only them would leave the
+-- parent's updated_at behind. These statement-level triggers touch every parent whose
+-- rows were inserted, updated or deleted (the parent's own updated_at trigger sets NOW()).
+-- Arguments: parent table, parent key column, child foreign key column.
+CREATE FUNCTION touch_parent_updated_at() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        EXECUTE format(
+            'UPDATE %I SET updated_at = NOW() WHERE %I IN (SELECT %I FROM new_rows)',
+            TG_ARGV[0], TG_ARGV[1], TG_ARGV[2]
+        );
+    END IF;
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        EXECUTE format(
+            'UPDATE %I SET updated_at = NOW() WHERE %I IN (SELECT %I FROM old_rows)',
+            TG_ARGV[0], TG_ARGV[1], TG_ARGV[2]
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Transition tables allow a single event per trigger
+CREATE TRIGGER vouch_default_relays_touch_insert
+    AFTER INSERT ON vouch_default_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_default_configs', 'name', 'config_name');
+CREATE TRIGGER vouch_default_relays_touch_update
+    AFTER UPDATE ON vouch_default_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_default_configs', 'name', 'config_name');
+CREATE TRIGGER vouch_default_relays_touch_delete
+    AFTER DELETE ON vouch_default_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_default_configs', 'name', 'config_name');
+CREATE TRIGGER vouch_proposer_relays_touch_insert
+    AFTER INSERT ON vouch_proposer_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposers', 'public_key', 'proposer_public_key');
+CREATE TRIGGER vouch_proposer_relays_touch_update
+    AFTER UPDATE ON vouch_proposer_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposers', 'public_key', 'proposer_public_key');
+CREATE TRIGGER vouch_proposer_relays_touch_delete
+    AFTER DELETE ON vouch_proposer_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposers', 'public_key', 'proposer_public_key');
+CREATE TRIGGER vouch_proposer_pattern_relays_touch_insert
+    AFTER INSERT ON vouch_proposer_pattern_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_patterns', 'name', 'pattern_name');
+CREATE TRIGGER vouch_proposer_pattern_relays_touch_update
+    AFTER UPDATE ON vouch_proposer_pattern_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_patterns', 'name', 'pattern_name');
+CREATE TRIGGER vouch_proposer_pattern_relays_touch_delete
+    AFTER DELETE ON vouch_proposer_pattern_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_patterns', 'name', 'pattern_name');
+CREATE TRIGGER vouch_relay_set_relays_touch_insert
+    AFTER INSERT ON vouch_relay_set_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_relay_sets', 'name', 'set_name');
+CREATE TRIGGER vouch_relay_set_relays_touch_update
+    AFTER UPDATE ON vouch_relay_set_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_relay_sets', 'name', 'set_name');
+CREATE TRIGGER vouch_relay_set_relays_touch_delete
+    AFTER DELETE ON vouch_relay_set_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_relay_sets', 'name', 'set_name');
+CREATE TRIGGER vouch_proposer_group_keys_touch_insert
+    AFTER INSERT ON vouch_proposer_group_keys REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER vouch_proposer_group_keys_touch_update
+    AFTER UPDATE ON vouch_proposer_group_keys REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER vouch_proposer_group_keys_touch_delete
+    AFTER DELETE ON vouch_proposer_group_keys REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER vouch_proposer_group_relays_touch_insert
+    AFTER INSERT ON vouch_proposer_group_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER vouch_proposer_group_relays_touch_update
+    AFTER UPDATE ON vouch_proposer_group_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER vouch_proposer_group_relays_touch_delete
+    AFTER DELETE ON vouch_proposer_group_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('vouch_proposer_groups', 'name', 'group_name');
+CREATE TRIGGER commit_boost_mux_keys_touch_insert
+    AFTER INSERT ON commit_boost_mux_keys REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_mux_configs', 'name', 'mux_name');
+CREATE TRIGGER commit_boost_mux_keys_touch_update
+    AFTER UPDATE ON commit_boost_mux_keys REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_mux_configs', 'name', 'mux_name');
+CREATE TRIGGER commit_boost_mux_keys_touch_delete
+    AFTER DELETE ON commit_boost_mux_keys REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_mux_configs', 'name', 'mux_name');
+CREATE TRIGGER commit_boost_pbs_relays_touch_insert
+    AFTER INSERT ON commit_boost_pbs_relays REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
+CREATE TRIGGER commit_boost_pbs_relays_touch_update
+    AFTER UPDATE ON commit_boost_pbs_relays REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
+CREATE TRIGGER commit_boost_pbs_relays_touch_delete
+    AFTER DELETE ON commit_boost_pbs_relays REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
+CREATE TRIGGER commit_boost_pbs_muxes_touch_insert
+    AFTER INSERT ON commit_boost_pbs_muxes REFERENCING NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
+CREATE TRIGGER commit_boost_pbs_muxes_touch_update
+    AFTER UPDATE ON commit_boost_pbs_muxes REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
+CREATE TRIGGER commit_boost_pbs_muxes_touch_delete
+    AFTER DELETE ON commit_boost_pbs_muxes REFERENCING OLD TABLE AS old_rows
+    FOR EACH STATEMENT EXECUTE FUNCTION touch_parent_updated_at('commit_boost_pbs_configs', 'name', 'config_name');
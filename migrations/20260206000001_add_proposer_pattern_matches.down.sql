@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS vouch_pattern_match_state;
+DROP TABLE IF EXISTS vouch_proposer_pattern_matches;
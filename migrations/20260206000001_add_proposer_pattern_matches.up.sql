@@ -0,0 +1,19 @@
+-- Proposers each pattern's regex matches, computed by the pattern match job.
+-- No foreign keys: the job replaces all rows at once, and rows are only read
+-- while vouch_pattern_match_state.version equals the 'vouch' config version.
+CREATE TABLE vouch_proposer_pattern_matches (
+    pattern_name TEXT NOT NULL,
+    public_key TEXT NOT NULL,
+    PRIMARY KEY (pattern_name, public_key)
+);
+
+CREATE INDEX idx_vouch_proposer_pattern_matches_public_key ON vouch_proposer_pattern_matches(public_key);
+
+-- The 'vouch' config version the matches were computed at
+CREATE TABLE vouch_pattern_match_state (
+    singleton BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    version BIGINT NOT NULL DEFAULT 0,
+    refreshed_at TIMESTAMPTZ
+);
+
+INSERT INTO vouch_pattern_match_state DEFAULT VALUES;
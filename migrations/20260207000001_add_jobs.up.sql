@@ -0,0 +1,16 @@
+-- Long-running requests accepted with 202, run by the replica that accepted them.
+-- The runner bumps heartbeat_at while it works; a running job whose heartbeat
+-- stopped died with its replica.
+CREATE TABLE jobs (
+    id UUID PRIMARY KEY,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'succeeded', 'failed')),
+    actor TEXT,
+    result JSONB,
+    error JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_jobs_created_at ON jobs(created_at);
@@ -0,0 +1,12 @@
+-- Scheduled snapshots written to the export storage bucket. The unique
+-- scheduled_for lets one replica claim each run of the schedule.
+CREATE TABLE backups (
+    id UUID PRIMARY KEY,
+    scheduled_for TIMESTAMPTZ NOT NULL UNIQUE,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'succeeded', 'failed')),
+    object_key TEXT,
+    size BIGINT,
+    error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
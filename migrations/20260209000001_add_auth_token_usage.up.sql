@@ -0,0 +1,12 @@
+-- Requests per API token and route, to see what still depends on a token
+-- before deactivating it. Routes are the matched path templates, so there is
+-- one row per endpoint, not per resource.
+CREATE TABLE auth_token_usage (
+    token_id UUID NOT NULL REFERENCES auth_tokens(id) ON DELETE CASCADE,
+    method TEXT NOT NULL,
+    route TEXT NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    first_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (token_id, method, route)
+);
@@ -0,0 +1 @@
+DROP TRIGGER IF EXISTS disabled_relays_commit_boost_version ON disabled_relays;
@@ -0,0 +1,5 @@
+-- Rendered PBS configs leave globally disabled relays out, so disabling or
+-- enabling a relay also invalidates cached Commit-Boost responses
+CREATE TRIGGER disabled_relays_commit_boost_version
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON disabled_relays
+    FOR EACH STATEMENT EXECUTE FUNCTION bump_config_version('commit_boost');
@@ -0,0 +1,2 @@
+-- The retention sweep deletes by age
+CREATE INDEX idx_audit_events_created_at ON audit_events(created_at);
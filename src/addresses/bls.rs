@@ -12,12 +12,12 @@ use std::fmt;
 
 /// BLS public key (48 bytes, hex-encoded with 0x prefix)
 #[derive(PartialEq, Eq, Clone, Hash, ToSchema)]
-#[schema(as = String, example = "0x8021cef5a095e4a4476d5a56d0e63e7ddb5e66ba5349fe1bc341f94f0f07a0dbfd19755b9820022e6b9153e28fce90e8")]
+#[schema(value_type = String, pattern = "^0[xX][0-9a-fA-F]{96}$", example = "0x8021cef5a095e4a4476d5a56d0e63e7ddb5e66ba5349fe1bc341f94f0f07a0dbfd19755b9820022e6b9153e28fce90e8")]
 pub struct BlsPubkey(pub [u8; 48]);
 
 impl fmt::Display for BlsPubkey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
+        let hex = format!("0x{}", hex::encode(self.0));
         write!(f, "{}", hex)
     }
 }
@@ -30,7 +30,7 @@ impl Default for BlsPubkey {
 
 impl fmt::Debug for BlsPubkey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
+        let hex = format!("0x{}", hex::encode(self.0));
         write!(f, "{}", hex)
     }
 }
@@ -40,8 +40,8 @@ impl Serialize for BlsPubkey {
     where
         S: Serializer,
     {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
-        serializer.serialize_str(&hex.as_str())
+        let hex = format!("0x{}", hex::encode(self.0));
+        serializer.serialize_str(hex.as_str())
     }
 }
 
@@ -76,11 +76,13 @@ impl std::str::FromStr for BlsPubkey {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(stripped) = s.strip_prefix("0x") {
-            let bytes = <[u8; 48]>::from_hex(stripped).map_err(|e| e.to_string())?;
+        // Mixed-case input is accepted; Display always renders lowercase
+        if let Some(stripped) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let bytes = <[u8; 48]>::from_hex(stripped)
+                .map_err(|e| format!("Invalid BLS public key '{}': {}", s, e))?;
             Ok(Self(bytes))
         } else {
-            Err("Must start with 0x".to_string())
+            Err(format!("Invalid BLS public key '{}': Must start with 0x", s))
         }
     }
 }
@@ -149,6 +151,16 @@ mod tests {
         assert_eq!(deserialized, addr);
     }
 
+    #[test]
+    fn bls_pubkey_mixed_case_is_lowercased() {
+        let lower = format!("0x{}ab", "0a".repeat(47));
+        let mixed = format!("0X{}AB", "0A".repeat(47));
+        let addr: BlsPubkey = mixed.parse().unwrap();
+
+        assert_eq!(addr, lower.parse::<BlsPubkey>().unwrap());
+        assert_eq!(addr.to_string(), lower);
+    }
+
     #[test]
     #[should_panic(expected = "Odd number of digits")]
     fn bls_pubkey_wrong1() {
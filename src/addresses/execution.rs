@@ -11,19 +11,19 @@ use std::fmt;
 
 /// Ethereum address (20 bytes, hex-encoded with 0x prefix)
 #[derive(PartialEq, Eq, Clone, Default, ToSchema)]
-#[schema(as = String, example = "0x1234567890abcdef1234567890abcdef12345678")]
+#[schema(value_type = String, pattern = "^0[xX][0-9a-fA-F]{40}$", example = "0x1234567890abcdef1234567890abcdef12345678")]
 pub struct EthAddress(pub [u8; 20]);
 
 impl fmt::Display for EthAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
+        let hex = format!("0x{}", hex::encode(self.0));
         write!(f, "{}", hex)
     }
 }
 
 impl fmt::Debug for EthAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
+        let hex = format!("0x{}", hex::encode(self.0));
         write!(f, "{}", hex)
     }
 }
@@ -33,8 +33,8 @@ impl Serialize for EthAddress {
     where
         S: Serializer,
     {
-        let hex = format!("0x{}", hex::encode(self.0.to_vec()));
-        serializer.serialize_str(&hex.as_str())
+        let hex = format!("0x{}", hex::encode(self.0));
+        serializer.serialize_str(hex.as_str())
     }
 }
 
@@ -69,11 +69,13 @@ impl std::str::FromStr for EthAddress {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(stripped) = s.strip_prefix("0x") {
-            let bytes = <[u8; 20]>::from_hex(stripped).map_err(|e| e.to_string())?;
+        // Mixed-case input is accepted; Display always renders lowercase
+        if let Some(stripped) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            let bytes = <[u8; 20]>::from_hex(stripped)
+                .map_err(|e| format!("Invalid address '{}': {}", s, e))?;
             Ok(Self(bytes))
         } else {
-            Err("Must start with 0x".to_string())
+            Err(format!("Invalid address '{}': Must start with 0x", s))
         }
     }
 }
@@ -136,6 +138,12 @@ mod tests {
         assert_eq!(deserialized, addr);
     }
 
+    #[test]
+    fn execution_address_mixed_case_is_lowercased() {
+        let addr: EthAddress = "0xAbCdEf0123456789aBcDeF0123456789ABCDEF01".parse().unwrap();
+        assert_eq!(addr.to_string(), "0xabcdef0123456789abcdef0123456789abcdef01");
+    }
+
     #[test]
     #[should_panic(expected = "Odd number of digits")]
     fn execution_address_wrong1() {
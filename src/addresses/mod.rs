@@ -3,3 +3,10 @@ mod execution;
 
 pub use bls::BlsPubkey;
 pub use execution::EthAddress;
+
+/// Canonical form of a hex key or address given as a plain string (path
+/// segment, filter value): trimmed and lowercase, matching how the typed
+/// values are stored
+pub fn normalize_hex(s: &str) -> String {
+    s.trim().to_ascii_lowercase()
+}
@@ -0,0 +1,122 @@
+//! Lightweight events recording who fetched public configs and key lists
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::net::IpAddr;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{RequestContext, AUDIT_SCHEMA_VERSION, AUDIT_WRITER};
+
+/// Public endpoints whose reads can be recorded
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicRead {
+    ExecutionConfig,
+    ExecutionConfigV1,
+    MuxKeys,
+    PbsConfig,
+}
+
+impl PublicRead {
+    /// Name used in access events and metric labels
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PublicRead::ExecutionConfig => "execution_config",
+            PublicRead::ExecutionConfigV1 => "execution_config_v1",
+            PublicRead::MuxKeys => "mux_keys",
+            PublicRead::PbsConfig => "pbs_config",
+        }
+    }
+}
+
+/// One read of a public endpoint, written next to the audit events
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccessEvent {
+    /// Always "access"
+    #[serde(rename = "type")]
+    #[schema(value_type = String)]
+    pub event_type: &'static str,
+    /// Format version of the event, shared with audit events
+    pub schema_version: u32,
+    pub timestamp: DateTime<Utc>,
+    pub request_id: Uuid,
+    pub read: PublicRead,
+    /// Default config, mux or PBS config name
+    pub resource_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub client_ip: Option<IpAddr>,
+    /// Validator keys sent by the client or returned to it
+    pub key_count: usize,
+    /// Proposer entries in an execution config response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposer_count: Option<usize>,
+}
+
+impl AccessEvent {
+    pub fn new(
+        ctx: &RequestContext,
+        read: PublicRead,
+        resource_id: impl Into<String>,
+        key_count: usize,
+    ) -> Self {
+        Self {
+            event_type: "access",
+            schema_version: AUDIT_SCHEMA_VERSION,
+            timestamp: Utc::now(),
+            request_id: ctx.request_id,
+            read,
+            resource_id: resource_id.into(),
+            client_ip: ctx.client_ip,
+            key_count,
+            proposer_count: None,
+        }
+    }
+
+    pub fn with_proposer_count(mut self, count: usize) -> Self {
+        self.proposer_count = Some(count);
+        self
+    }
+
+    /// Queue this event for the configured audit output
+    pub async fn log(self) {
+        if let Some(writer) = AUDIT_WRITER.get() {
+            writer.send(serde_json::to_string(&self).unwrap_or_default()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::ActorInfo;
+
+    #[test]
+    fn serializes_as_access_event() {
+        let ctx = RequestContext {
+            request_id: Uuid::nil(),
+            actor: ActorInfo::default(),
+            client_ip: Some("10.0.0.7".parse().unwrap()),
+            override_requested: false,
+        };
+        let event = AccessEvent::new(&ctx, PublicRead::ExecutionConfig, "main", 3)
+            .with_proposer_count(2);
+
+        let mut value = serde_json::to_value(&event).unwrap();
+        value.as_object_mut().unwrap().remove("timestamp");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "type": "access",
+                "schema_version": AUDIT_SCHEMA_VERSION,
+                "request_id": Uuid::nil(),
+                "read": "execution_config",
+                "resource_id": "main",
+                "client_ip": "10.0.0.7",
+                "key_count": 3,
+                "proposer_count": 2,
+            })
+        );
+    }
+}
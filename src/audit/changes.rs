@@ -0,0 +1,263 @@
+//! Before/after values recorded with audit events
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::{
+    Labels, VouchDefaultConfig, VouchProposer, VouchProposerGroup, VouchProposerPattern,
+};
+
+/// Previous and new value of a field (`None` when the field was unset or the
+/// resource did not exist)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Change<T> {
+    pub from: Option<T>,
+    pub to: Option<T>,
+}
+
+impl<T: Clone + PartialEq> Change<T> {
+    /// The change between two values, or `None` when they are equal
+    pub fn between(from: &Option<T>, to: &Option<T>) -> Option<Self> {
+        (from != to).then(|| Self {
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
+
+    /// A field set on a resource that did not exist before
+    pub fn set(to: Option<T>) -> Option<Self> {
+        Self::between(&None, &to)
+    }
+}
+
+/// Audited field values of a resource at one point in time
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditValues {
+    pub fee_recipient: Option<String>,
+    pub min_value: Option<String>,
+    pub gas_limit: Option<String>,
+    pub grace: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub active: Option<bool>,
+    pub reset_relays: Option<bool>,
+    pub pattern: Option<String>,
+    pub priority: Option<i32>,
+    pub tags: Option<Vec<String>>,
+    pub note: Option<String>,
+    pub labels: Option<Labels>,
+    pub key_count: Option<i64>,
+    pub name: Option<String>,
+    pub parent: Option<String>,
+    pub relays_count: Option<i64>,
+    pub relay_sets: Option<Vec<String>>,
+    pub chain: Option<String>,
+    pub muxes: Option<Vec<String>>,
+    pub disabled: Option<bool>,
+    pub decommissioned: Option<bool>,
+}
+
+impl From<&VouchDefaultConfig> for AuditValues {
+    fn from(config: &VouchDefaultConfig) -> Self {
+        Self {
+            fee_recipient: config.fee_recipient.as_ref().map(|a| a.to_string()),
+            min_value: config.min_value.clone(),
+            gas_limit: config.gas_limit.clone(),
+            grace: config.grace.clone(),
+            builder_enabled: config.builder_enabled,
+            builder_boost_factor: config.builder_boost_factor.clone(),
+            active: Some(config.active),
+            parent: config.parent.clone(),
+            relay_sets: Some(config.relay_sets.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&VouchProposer> for AuditValues {
+    fn from(proposer: &VouchProposer) -> Self {
+        Self {
+            fee_recipient: proposer.fee_recipient.as_ref().map(|a| a.to_string()),
+            min_value: proposer.min_value.clone(),
+            gas_limit: proposer.gas_limit.clone(),
+            builder_enabled: proposer.builder_enabled,
+            builder_boost_factor: proposer.builder_boost_factor.clone(),
+            reset_relays: Some(proposer.reset_relays),
+            note: proposer.note.clone(),
+            labels: Some(proposer.labels.0.clone()),
+            relay_sets: Some(proposer.relay_sets.clone()),
+            decommissioned: Some(proposer.decommissioned_at.is_some()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&VouchProposerPattern> for AuditValues {
+    fn from(pattern: &VouchProposerPattern) -> Self {
+        Self {
+            fee_recipient: pattern.fee_recipient.as_ref().map(|a| a.to_string()),
+            min_value: pattern.min_value.clone(),
+            gas_limit: pattern.gas_limit.clone(),
+            builder_enabled: pattern.builder_enabled,
+            builder_boost_factor: pattern.builder_boost_factor.clone(),
+            reset_relays: Some(pattern.reset_relays),
+            active: Some(pattern.active),
+            pattern: Some(pattern.pattern.clone()),
+            tags: Some(pattern.tags.clone()),
+            note: pattern.note.clone(),
+            labels: Some(pattern.labels.0.clone()),
+            relay_sets: Some(pattern.relay_sets.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&VouchProposerGroup> for AuditValues {
+    fn from(group: &VouchProposerGroup) -> Self {
+        Self {
+            fee_recipient: group.fee_recipient.as_ref().map(|a| a.to_string()),
+            min_value: group.min_value.clone(),
+            gas_limit: group.gas_limit.clone(),
+            builder_enabled: group.builder_enabled,
+            builder_boost_factor: group.builder_boost_factor.clone(),
+            reset_relays: Some(group.reset_relays),
+            pattern: group.pattern.clone(),
+            priority: Some(group.priority),
+            note: group.note.clone(),
+            relay_sets: Some(group.relay_sets.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Changed fields as `{from, to}` pairs; unchanged fields are omitted
+#[derive(Debug, Clone, Serialize, Default, ToSchema)]
+pub struct AuditChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<Change<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<Change<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_relays: Option<Change<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Change<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Change<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Change<Labels>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_count: Option<Change<i64>>,
+    /// Key count of the mux keys were copied or moved to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_key_count: Option<Change<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relays_count: Option<Change<i64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relay_sets: Option<Change<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<Change<String>>,
+    /// Mux ids of a PBS config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub muxes: Option<Change<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<Change<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decommissioned: Option<Change<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Change<Vec<String>>>,
+    /// Log filter directives
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<Change<String>>,
+    /// Relay the change applies to (context, not a changed value)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relay_url: Option<String>,
+    /// Mux keys were copied or moved to (context, not a changed value)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl AuditChanges {
+    /// Field-by-field difference between two snapshots of a resource
+    pub fn diff(before: &AuditValues, after: &AuditValues) -> Self {
+        Self {
+            fee_recipient: Change::between(&before.fee_recipient, &after.fee_recipient),
+            min_value: Change::between(&before.min_value, &after.min_value),
+            gas_limit: Change::between(&before.gas_limit, &after.gas_limit),
+            grace: Change::between(&before.grace, &after.grace),
+            builder_enabled: Change::between(&before.builder_enabled, &after.builder_enabled),
+            builder_boost_factor: Change::between(
+                &before.builder_boost_factor,
+                &after.builder_boost_factor,
+            ),
+            active: Change::between(&before.active, &after.active),
+            reset_relays: Change::between(&before.reset_relays, &after.reset_relays),
+            pattern: Change::between(&before.pattern, &after.pattern),
+            priority: Change::between(&before.priority, &after.priority),
+            tags: Change::between(&before.tags, &after.tags),
+            note: Change::between(&before.note, &after.note),
+            labels: Change::between(&before.labels, &after.labels),
+            key_count: Change::between(&before.key_count, &after.key_count),
+            target_key_count: None,
+            name: Change::between(&before.name, &after.name),
+            parent: Change::between(&before.parent, &after.parent),
+            relays_count: Change::between(&before.relays_count, &after.relays_count),
+            relay_sets: Change::between(&before.relay_sets, &after.relay_sets),
+            chain: Change::between(&before.chain, &after.chain),
+            muxes: Change::between(&before.muxes, &after.muxes),
+            disabled: Change::between(&before.disabled, &after.disabled),
+            decommissioned: Change::between(&before.decommissioned, &after.decommissioned),
+            scopes: None,
+            log_level: None,
+            relay_url: None,
+            target: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_keeps_only_changed_fields() {
+        let before = AuditValues {
+            fee_recipient: Some("0xaa".to_string()),
+            gas_limit: Some("30000000".to_string()),
+            relays_count: Some(2),
+            ..Default::default()
+        };
+        let after = AuditValues {
+            fee_recipient: Some("0xbb".to_string()),
+            gas_limit: Some("30000000".to_string()),
+            relays_count: None,
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(AuditChanges::diff(&before, &after)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "fee_recipient": { "from": "0xaa", "to": "0xbb" },
+                "relays_count": { "from": 2, "to": null },
+            })
+        );
+    }
+}
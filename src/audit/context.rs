@@ -2,10 +2,13 @@
 
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
+use std::net::IpAddr;
 use uuid::Uuid;
 
 use super::ActorInfo;
 use crate::errors::ApiError;
+use crate::handlers::ClientIp;
+use crate::validation::OVERRIDE_HEADER;
 
 /// Request context containing actor info and request ID
 /// Extracted by handlers that need to perform audit logging
@@ -13,6 +16,10 @@ use crate::errors::ApiError;
 pub struct RequestContext {
     pub request_id: Uuid,
     pub actor: ActorInfo,
+    /// Client address (see `listen.trusted_proxies`), absent on unix socket connections
+    pub client_ip: Option<IpAddr>,
+    /// The request asked to bypass the `validation` policy (`X-Fee-Manager-Override`)
+    pub override_requested: bool,
 }
 
 impl<S> FromRequestParts<S> for RequestContext
@@ -34,6 +41,22 @@ where
             .cloned()
             .unwrap_or_default();
 
-        Ok(RequestContext { request_id, actor })
+        let client_ip = parts
+            .extensions
+            .get::<ClientIp>()
+            .map(|ClientIp(ip)| *ip);
+
+        let override_requested = parts
+            .headers
+            .get(OVERRIDE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+
+        Ok(RequestContext {
+            request_id,
+            actor,
+            client_ip,
+            override_requested,
+        })
     }
 }
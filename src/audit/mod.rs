@@ -1,64 +1,139 @@
 //! Audit trail logging for admin operations
 
+mod access;
+mod changes;
 mod context;
+mod rotation;
+mod schema;
+mod sink;
+mod store;
+mod writer;
 
+pub use access::{AccessEvent, PublicRead};
+pub use changes::{AuditChanges, AuditValues, Change};
 pub use context::RequestContext;
+pub use rotation::RotatingFileWriter;
+pub use schema::{audit_event_schema, AUDIT_SCHEMA_VERSION};
+pub use store::{history, spawn_retention, sweep};
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
-use std::fs::OpenOptions;
+use sqlx::postgres::PgConnectOptions;
 use std::io::{stderr, stdout, Write};
-use std::sync::{Mutex, OnceLock};
+use std::sync::OnceLock;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::config::{AuditQueueConfig, AuditRotationConfig, AuditSinkConfig};
+use sink::BrokerSink;
+use store::DatabaseStore;
+use writer::AuditWriter;
+
 /// Global audit writer
 static AUDIT_WRITER: OnceLock<AuditWriter> = OnceLock::new();
 
-/// Writer for audit events
-struct AuditWriter {
-    writer: Mutex<Box<dyn Write + Send>>,
-}
-
-impl std::fmt::Debug for AuditWriter {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("AuditWriter").finish_non_exhaustive()
-    }
-}
-
 /// Initialize the audit writer with the specified output destination.
 /// Must be called once at startup before any audit logging.
 ///
+/// Events are written by a background thread, so a slow output does not
+/// hold up request handlers.
+///
 /// # Arguments
 /// * `output` - "stdout", "stderr", or a file path
-pub fn init_audit_writer(output: &str) {
-    let writer: Box<dyn Write + Send> = match output {
+/// * `rotation` - Rotation policy, only applied when `output` is a file path
+/// * `queue` - Size of the event queue and what to do when it is full
+/// * `sink` - Message broker every event is also published to
+/// * `database` - Database every event is also stored in, for [`history`]
+pub fn init_audit_writer(
+    output: &str,
+    rotation: &AuditRotationConfig,
+    queue: &AuditQueueConfig,
+    sink: Option<&AuditSinkConfig>,
+    database: Option<PgConnectOptions>,
+) {
+    let output: Box<dyn Write + Send> = match output {
         "stdout" => Box::new(stdout()),
         "stderr" => Box::new(stderr()),
         path => Box::new(
-            OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(path)
-                .expect("Failed to open audit log file"),
+            RotatingFileWriter::open(path, rotation).expect("Failed to open audit log file"),
         ),
     };
+    let sink = sink.map(|config| BrokerSink::spawn(config).expect("Failed to start audit sink"));
+    let store = database.map(|options| {
+        DatabaseStore::spawn(options, queue.capacity).expect("Failed to start audit store")
+    });
+    let writer =
+        AuditWriter::spawn(output, sink, store, queue).expect("Failed to start audit writer");
 
     AUDIT_WRITER
-        .set(AuditWriter {
-            writer: Mutex::new(writer),
-        })
+        .set(writer)
         .expect("Audit writer already initialized");
 }
 
+/// Write out queued audit events and stop the writer; call once on shutdown.
+/// Events logged afterwards are discarded.
+pub async fn shutdown_audit_writer() {
+    if let Some(writer) = AUDIT_WRITER.get() {
+        writer.shutdown().await;
+    }
+}
+
+/// State of the audit writer, for `/health?verbose=true`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuditStatus {
+    /// Whether the background writer thread is alive
+    pub writer_running: bool,
+    /// Events dropped because the queue was full, since startup
+    pub dropped: u64,
+    /// Message broker sink, when `audit_sink` is configured
+    pub sink: Option<AuditSinkStatus>,
+    /// Database store, when `audit_database` is enabled
+    pub database: Option<AuditSinkStatus>,
+}
+
+/// State of the audit message broker sink or database store
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuditSinkStatus {
+    /// "kafka", "nats" or "database"
+    pub kind: String,
+    /// Whether the last connect or publish succeeded
+    pub connected: bool,
+    /// Events waiting to be published
+    pub buffered: usize,
+    /// Events published since startup
+    pub published: u64,
+    /// Events dropped because the buffer was full, since startup
+    pub dropped: u64,
+}
+
+/// Current audit writer state, or `None` when the writer was never started
+pub fn audit_status() -> Option<AuditStatus> {
+    AUDIT_WRITER.get().map(AuditWriter::status)
+}
+
+/// Whether audit events are stored in the database, so [`history`] has them
+pub fn stores_history() -> bool {
+    AUDIT_WRITER.get().is_some_and(AuditWriter::stores_events)
+}
+
 /// Information about the actor performing an action
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize, Default, ToSchema)]
 pub struct ActorInfo {
     pub token_id: Uuid,
     pub token_name: String,
+    /// Scopes of the token or OIDC identity; not written to the audit trail
+    #[serde(skip)]
+    pub scopes: Vec<String>,
+}
+
+impl ActorInfo {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
 }
 
 /// Types of audit actions
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditAction {
     Create,
@@ -66,49 +141,64 @@ pub enum AuditAction {
     Delete,
     AddKeys,
     RemoveKeys,
+    CopyKeys,
+    MoveKeys,
+    DisableRelay,
+    EnableRelay,
+    Decommission,
+    Reactivate,
+    AuthLockout,
+    Import,
+    Restore,
 }
 
 /// Resource types that can be audited
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ResourceType {
     VouchDefaultConfig,
     VouchProposer,
     VouchProposerPattern,
+    VouchRelaySet,
+    VouchProposerGroup,
     CommitBoostMux,
+    CommitBoostPbsConfig,
     AuthToken,
+    PublicAccessToken,
+    Relay,
+    LogLevel,
+    Backup,
 }
 
-/// Key field changes to track
-#[derive(Debug, Clone, Serialize, Default)]
-pub struct AuditChanges {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_recipient: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub active: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reset_relays: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub pattern: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tags: Option<Vec<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub key_count: Option<i64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub relays_count: Option<usize>,
+impl ResourceType {
+    /// Name used in audit events
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResourceType::VouchDefaultConfig => "vouch_default_config",
+            ResourceType::VouchProposer => "vouch_proposer",
+            ResourceType::VouchProposerPattern => "vouch_proposer_pattern",
+            ResourceType::VouchRelaySet => "vouch_relay_set",
+            ResourceType::VouchProposerGroup => "vouch_proposer_group",
+            ResourceType::CommitBoostMux => "commit_boost_mux",
+            ResourceType::CommitBoostPbsConfig => "commit_boost_pbs_config",
+            ResourceType::AuthToken => "auth_token",
+            ResourceType::PublicAccessToken => "public_access_token",
+            ResourceType::Relay => "relay",
+            ResourceType::LogLevel => "log_level",
+            ResourceType::Backup => "backup",
+        }
+    }
 }
 
 /// Complete audit event
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct AuditEvent {
+    /// Always "audit"
     #[serde(rename = "type")]
+    #[schema(value_type = String)]
     pub event_type: &'static str,
+    /// Format version of the event, see [`AUDIT_SCHEMA_VERSION`]
+    pub schema_version: u32,
     pub timestamp: DateTime<Utc>,
     pub request_id: Uuid,
     pub actor: ActorInfo,
@@ -133,6 +223,7 @@ impl AuditEvent {
     ) -> Self {
         Self {
             event_type: "audit",
+            schema_version: AUDIT_SCHEMA_VERSION,
             timestamp: Utc::now(),
             request_id,
             actor,
@@ -145,19 +236,32 @@ impl AuditEvent {
         }
     }
 
+    /// Create a new failed audit event
+    pub fn failure(
+        request_id: Uuid,
+        actor: ActorInfo,
+        action: AuditAction,
+        resource_type: ResourceType,
+        resource_id: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            success: false,
+            error: Some(error.into()),
+            ..Self::success(request_id, actor, action, resource_type, resource_id)
+        }
+    }
+
     /// Add changes to the event
     pub fn with_changes(mut self, changes: AuditChanges) -> Self {
         self.changes = Some(changes);
         self
     }
 
-    /// Log this audit event to the configured output
-    pub fn log(self) {
+    /// Queue this audit event for the configured output
+    pub async fn log(self) {
         if let Some(writer) = AUDIT_WRITER.get() {
-            let json = serde_json::to_string(&self).unwrap_or_default();
-            if let Ok(mut w) = writer.writer.lock() {
-                let _ = writeln!(w, "{}", json);
-            }
+            writer.send(serde_json::to_string(&self).unwrap_or_default()).await;
         }
     }
 }
@@ -174,6 +278,7 @@ macro_rules! audit_log {
             $resource_id,
         )
         .log()
+        .await
     };
     ($ctx:expr, $action:expr, $resource_type:expr, $resource_id:expr, $changes:expr) => {
         $crate::audit::AuditEvent::success(
@@ -185,5 +290,31 @@ macro_rules! audit_log {
         )
         .with_changes($changes)
         .log()
+        .await
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_type_names_match_serialized_events() {
+        let all = [
+            ResourceType::VouchDefaultConfig,
+            ResourceType::VouchProposer,
+            ResourceType::VouchProposerPattern,
+            ResourceType::VouchRelaySet,
+            ResourceType::VouchProposerGroup,
+            ResourceType::CommitBoostMux,
+            ResourceType::CommitBoostPbsConfig,
+            ResourceType::AuthToken,
+            ResourceType::PublicAccessToken,
+            ResourceType::Relay,
+            ResourceType::LogLevel,
+        ];
+        for resource_type in all {
+            assert_eq!(serde_json::to_value(resource_type).unwrap(), resource_type.as_str());
+        }
+    }
+}
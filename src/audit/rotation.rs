@@ -0,0 +1,213 @@
+//! Size and time based rotation for file audit output
+
+use chrono::{DateTime, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::AuditRotationConfig;
+
+/// File writer that rotates the audit log when it grows too large or too old.
+///
+/// Rotated files are renamed to `<path>.<timestamp>` and only the newest
+/// `retention` rotated files are kept.
+pub struct RotatingFileWriter {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    opened_at: DateTime<Utc>,
+    max_size_bytes: u64,
+    max_age: Option<chrono::Duration>,
+    retention: usize,
+}
+
+impl RotatingFileWriter {
+    /// Open (or create) the audit file at `path` with the given rotation policy
+    pub fn open(path: impl AsRef<Path>, rotation: &AuditRotationConfig) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            size,
+            opened_at: Utc::now(),
+            max_size_bytes: rotation.max_size_mb * 1024 * 1024,
+            max_age: (rotation.max_age_hours > 0)
+                .then(|| chrono::Duration::hours(rotation.max_age_hours as i64)),
+            retention: rotation.retention,
+        })
+    }
+
+    fn should_rotate(&self, incoming: usize) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_big =
+            self.max_size_bytes > 0 && self.size + incoming as u64 > self.max_size_bytes;
+        let too_old = self
+            .max_age
+            .is_some_and(|max_age| Utc::now() - self.opened_at >= max_age);
+        too_big || too_old
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        let timestamp = Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+        let rotated = PathBuf::from(format!("{}.{}", self.path.display(), timestamp));
+        fs::rename(&self.path, &rotated)?;
+
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        self.opened_at = Utc::now();
+
+        self.prune()
+    }
+
+    /// Delete the oldest rotated files beyond the retention limit
+    fn prune(&self) -> io::Result<()> {
+        let mut rotated = self.rotated_files()?;
+        if rotated.len() <= self.retention {
+            return Ok(());
+        }
+        // Timestamp suffixes sort chronologically
+        rotated.sort();
+        let excess = rotated.len() - self.retention;
+        for old in rotated.into_iter().take(excess) {
+            fs::remove_file(old)?;
+        }
+        Ok(())
+    }
+
+    fn rotated_files(&self) -> io::Result<Vec<PathBuf>> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let prefix = match self.path.file_name() {
+            Some(name) => format!("{}.", name.to_string_lossy()),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                files.push(entry.path());
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.should_rotate(buf.len()) {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fee-manager-audit-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn count_rotated(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter(|e| {
+                e.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("audit.log.")
+            })
+            .count()
+    }
+
+    #[test]
+    fn rotates_when_size_exceeded() {
+        let dir = temp_dir("size");
+        let path = dir.join("audit.log");
+        let rotation = AuditRotationConfig {
+            max_size_mb: 1,
+            max_age_hours: 0,
+            retention: 10,
+        };
+        let mut writer = RotatingFileWriter::open(&path, &rotation).unwrap();
+
+        let line = vec![b'x'; 600 * 1024];
+        writer.write_all(&line).unwrap();
+        writer.write_all(&line).unwrap();
+
+        assert_eq!(count_rotated(&dir), 1);
+        assert_eq!(fs::metadata(&path).unwrap().len(), line.len() as u64);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_only_retained_files() {
+        let dir = temp_dir("retention");
+        let path = dir.join("audit.log");
+        let rotation = AuditRotationConfig {
+            max_size_mb: 1,
+            max_age_hours: 0,
+            retention: 2,
+        };
+        let mut writer = RotatingFileWriter::open(&path, &rotation).unwrap();
+
+        let line = vec![b'x'; 700 * 1024];
+        for _ in 0..5 {
+            writer.write_all(&line).unwrap();
+        }
+
+        assert_eq!(count_rotated(&dir), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn no_rotation_when_disabled() {
+        let dir = temp_dir("disabled");
+        let path = dir.join("audit.log");
+        let rotation = AuditRotationConfig {
+            max_size_mb: 0,
+            max_age_hours: 0,
+            retention: 2,
+        };
+        let mut writer = RotatingFileWriter::open(&path, &rotation).unwrap();
+
+        let line = vec![b'x'; 700 * 1024];
+        for _ in 0..3 {
+            writer.write_all(&line).unwrap();
+        }
+
+        assert_eq!(count_rotated(&dir), 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+//! JSON Schema of the events written to the audit trail
+//!
+//! Within one `schema_version` the event format only grows: fields are added as
+//! optional, never removed, renamed or retyped, and enums only gain values.
+//! Anything else bumps [`AUDIT_SCHEMA_VERSION`].
+
+use serde_json::{json, Value};
+use utoipa::OpenApi;
+
+use super::{AccessEvent, AuditEvent};
+
+/// Version written into every event as `schema_version`
+pub const AUDIT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(OpenApi)]
+#[openapi(components(schemas(AuditEvent, AccessEvent)))]
+struct AuditSchemaDoc;
+
+/// JSON Schema (draft 2020-12) of one line of the audit output
+pub fn audit_event_schema() -> Value {
+    let components = AuditSchemaDoc::openapi()
+        .components
+        .map(|components| components.schemas)
+        .unwrap_or_default();
+    let defs = serde_json::to_string(&components)
+        .expect("schemas serialize")
+        .replace("#/components/schemas/", "#/$defs/");
+    let mut defs: Value = serde_json::from_str(&defs).expect("schemas are JSON");
+
+    // `type` tells the two event kinds apart
+    for (name, event_type) in [("AuditEvent", "audit"), ("AccessEvent", "access")] {
+        defs[name]["properties"]["type"] = json!({ "type": "string", "const": event_type });
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("urn:fee-manager:audit-event:v{}", AUDIT_SCHEMA_VERSION),
+        "title": "Fee Manager audit event",
+        "description": format!(
+            "One line of the audit output (schema_version {}). Fields are only ever added; \
+             consumers should ignore unknown fields and enum values.",
+            AUDIT_SCHEMA_VERSION
+        ),
+        "oneOf": [
+            { "$ref": "#/$defs/AuditEvent" },
+            { "$ref": "#/$defs/AccessEvent" }
+        ],
+        "$defs": defs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{ActorInfo, AuditAction, AuditChanges, Change, PublicRead, RequestContext, ResourceType};
+    use uuid::Uuid;
+
+    /// Fields of schema_version 1; they must stay, with the same requiredness
+    const AUDIT_EVENT_V1: &[(&str, bool)] = &[
+        ("type", true),
+        ("schema_version", true),
+        ("timestamp", true),
+        ("request_id", true),
+        ("actor", true),
+        ("action", true),
+        ("resource_type", true),
+        ("resource_id", true),
+        ("success", true),
+        ("error", false),
+        ("changes", false),
+    ];
+    const ACCESS_EVENT_V1: &[(&str, bool)] = &[
+        ("type", true),
+        ("schema_version", true),
+        ("timestamp", true),
+        ("request_id", true),
+        ("read", true),
+        ("resource_id", true),
+        ("client_ip", false),
+        ("key_count", true),
+        ("proposer_count", false),
+    ];
+
+    fn assert_keeps_fields(schema: &Value, name: &str, fields: &[(&str, bool)]) {
+        let def = &schema["$defs"][name];
+        let required: Vec<&str> = def["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field.as_str().unwrap())
+            .collect();
+        for (field, is_required) in fields {
+            assert!(def["properties"].get(*field).is_some(), "{}.{} was removed", name, field);
+            assert_eq!(required.contains(field), *is_required, "{}.{} changed requiredness", name, field);
+        }
+        // New fields must be optional
+        for field in required {
+            assert!(fields.iter().any(|(known, _)| *known == field), "{}.{} is a new required field", name, field);
+        }
+    }
+
+    #[test]
+    fn schema_keeps_v1_fields() {
+        let schema = audit_event_schema();
+        assert_keeps_fields(&schema, "AuditEvent", AUDIT_EVENT_V1);
+        assert_keeps_fields(&schema, "AccessEvent", ACCESS_EVENT_V1);
+        assert_eq!(schema["$defs"]["AuditEvent"]["properties"]["type"]["const"], "audit");
+    }
+
+    #[test]
+    fn schema_describes_every_serialized_field() {
+        let schema = audit_event_schema();
+        let event = AuditEvent::failure(
+            Uuid::nil(),
+            ActorInfo::default(),
+            AuditAction::Update,
+            ResourceType::VouchProposer,
+            "0x",
+            "failed",
+        )
+        .with_changes(AuditChanges {
+            gas_limit: Change::set(Some("30000000".to_string())),
+            ..Default::default()
+        });
+        let ctx = RequestContext {
+            request_id: Uuid::nil(),
+            actor: ActorInfo::default(),
+            client_ip: Some("10.0.0.7".parse().unwrap()),
+            override_requested: false,
+        };
+        let access = AccessEvent::new(&ctx, PublicRead::MuxKeys, "pool-1", 1).with_proposer_count(1);
+
+        for (name, value) in [
+            ("AuditEvent", serde_json::to_value(&event).unwrap()),
+            ("AccessEvent", serde_json::to_value(&access).unwrap()),
+        ] {
+            assert_eq!(value["schema_version"], AUDIT_SCHEMA_VERSION);
+            for field in value.as_object().unwrap().keys() {
+                assert!(
+                    schema["$defs"][name]["properties"].get(field).is_some(),
+                    "{}.{} is not in the schema",
+                    name,
+                    field
+                );
+            }
+        }
+    }
+}
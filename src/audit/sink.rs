@@ -0,0 +1,346 @@
+//! Publishes audit events to Kafka or NATS, so they reach a SIEM without tailing files
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+
+use super::AuditSinkStatus;
+use crate::config::AuditSinkConfig;
+
+/// Buffers serialized audit events for a dedicated publisher thread
+pub(crate) struct BrokerSink {
+    sender: Sender<String>,
+    dropped: Arc<AtomicU64>,
+    stats: Arc<SinkStats>,
+    handle: JoinHandle<()>,
+    shutdown_timeout: Duration,
+}
+
+/// Counters shared with the health endpoint
+#[derive(Debug)]
+pub(crate) struct SinkStats {
+    kind: &'static str,
+    pub(super) connected: AtomicBool,
+    pub(super) buffered: AtomicUsize,
+    pub(super) published: AtomicU64,
+    pub(super) dropped: AtomicU64,
+}
+
+impl SinkStats {
+    pub(super) fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            connected: AtomicBool::new(false),
+            buffered: AtomicUsize::new(0),
+            published: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn status(&self) -> AuditSinkStatus {
+        AuditSinkStatus {
+            kind: self.kind.to_string(),
+            connected: self.connected.load(Ordering::Relaxed),
+            buffered: self.buffered.load(Ordering::Relaxed),
+            published: self.published.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl BrokerSink {
+    /// Start the publisher thread; it connects in the background and keeps retrying
+    pub(crate) fn spawn(config: &AuditSinkConfig) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel(config.buffer);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let stats = Arc::new(SinkStats::new(config.kind.as_str()));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let handle = std::thread::Builder::new()
+            .name("audit-sink".to_string())
+            .spawn({
+                let config = config.clone();
+                let dropped = dropped.clone();
+                let stats = stats.clone();
+                move || runtime.block_on(run(config, receiver, dropped, stats))
+            })?;
+
+        Ok(Self {
+            sender,
+            dropped,
+            stats,
+            handle,
+            shutdown_timeout: Duration::from_secs(config.shutdown_timeout_secs),
+        })
+    }
+
+    /// Buffer one event; when the buffer is full the event is dropped and counted
+    pub(crate) fn publish(&self, line: &str) {
+        // Counted before sending so the publisher never takes out more than was put in
+        self.stats.buffered.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = self.sender.try_send(line.to_string()) {
+            self.stats.buffered.fetch_sub(1, Ordering::Relaxed);
+            if let TrySendError::Full(_) = e {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn stats(&self) -> Arc<SinkStats> {
+        self.stats.clone()
+    }
+
+    /// Publish what is buffered and stop, giving up after `shutdown_timeout_secs`
+    pub(crate) fn shutdown(self) {
+        drop(self.sender);
+        let deadline = Instant::now() + self.shutdown_timeout;
+        while !self.handle.is_finished() {
+            if Instant::now() >= deadline {
+                tracing::warn!("Audit sink shutdown timed out, buffered events were not published");
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let _ = self.handle.join();
+    }
+}
+
+async fn run(
+    config: AuditSinkConfig,
+    mut receiver: Receiver<String>,
+    dropped: Arc<AtomicU64>,
+    stats: Arc<SinkStats>,
+) {
+    let mut publisher: Option<Publisher> = None;
+    let mut backoff = Backoff::new(&config);
+
+    while let Some(line) = receiver.recv().await {
+        stats.buffered.fetch_sub(1, Ordering::Relaxed);
+        report_dropped(&dropped);
+        // Hold on to the event until the broker takes it; newer events wait in the buffer
+        loop {
+            let result = match &publisher {
+                Some(p) => p.publish(&config.topic, &line).await,
+                None => match Publisher::connect(&config).await {
+                    Ok(p) => {
+                        publisher = Some(p);
+                        stats.connected.store(true, Ordering::Relaxed);
+                        continue;
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+            match result {
+                Ok(()) => {
+                    backoff.reset();
+                    stats.connected.store(true, Ordering::Relaxed);
+                    stats.published.fetch_add(1, Ordering::Relaxed);
+                    break;
+                }
+                Err(e) => {
+                    stats.connected.store(false, Ordering::Relaxed);
+                    let delay = backoff.next();
+                    tracing::warn!(
+                        kind = ?config.kind,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Failed to publish audit event: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    report_dropped(&dropped);
+    if let Some(publisher) = publisher {
+        publisher.flush().await;
+    }
+}
+
+/// Exponential backoff between retries, reset after a successful publish
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    fn new(config: &AuditSinkConfig) -> Self {
+        Self {
+            initial: Duration::from_millis(config.retry_initial_ms),
+            max: Duration::from_millis(config.retry_max_ms),
+            attempt: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    fn next(&mut self) -> Duration {
+        let delay = self.initial.saturating_mul(2u32.saturating_pow(self.attempt));
+        self.attempt = self.attempt.saturating_add(1);
+        delay.min(self.max)
+    }
+}
+
+fn report_dropped(dropped: &AtomicU64) {
+    let count = dropped.swap(0, Ordering::Relaxed);
+    if count > 0 {
+        tracing::warn!(dropped = count, "Audit sink buffer full, events were not published");
+    }
+}
+
+/// Connected client of the configured broker
+enum Publisher {
+    #[cfg(feature = "audit-kafka")]
+    Kafka(rdkafka::producer::FutureProducer),
+    #[cfg(feature = "audit-nats")]
+    Nats(async_nats::Client),
+}
+
+impl Publisher {
+    async fn connect(config: &AuditSinkConfig) -> Result<Self, String> {
+        match config.kind {
+            #[cfg(feature = "audit-kafka")]
+            crate::config::AuditSinkKind::Kafka => kafka_producer(config).map(Publisher::Kafka),
+            #[cfg(feature = "audit-nats")]
+            crate::config::AuditSinkKind::Nats => nats_client(config).await.map(Publisher::Nats),
+            // Config validation rejects kinds that are not compiled in
+            #[allow(unreachable_patterns)]
+            kind => Err(format!("built without the {} feature", kind.feature())),
+        }
+    }
+
+    async fn publish(&self, topic: &str, line: &str) -> Result<(), String> {
+        match self {
+            #[cfg(feature = "audit-kafka")]
+            Publisher::Kafka(producer) => {
+                let record = rdkafka::producer::FutureRecord::<(), _>::to(topic).payload(line);
+                producer
+                    .send(record, Duration::from_secs(5))
+                    .await
+                    .map(|_| ())
+                    .map_err(|(e, _)| e.to_string())
+            }
+            #[cfg(feature = "audit-nats")]
+            Publisher::Nats(client) => client
+                .publish(topic.to_string(), line.to_string().into())
+                .await
+                .map_err(|e| e.to_string()),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (topic, line);
+                Err("no audit sink compiled in".to_string())
+            }
+        }
+    }
+
+    /// Hand off anything the client still holds before the process exits
+    async fn flush(&self) {
+        match self {
+            #[cfg(feature = "audit-kafka")]
+            Publisher::Kafka(producer) => {
+                use rdkafka::producer::Producer;
+                let _ = producer.flush(Duration::from_secs(5));
+            }
+            #[cfg(feature = "audit-nats")]
+            Publisher::Nats(client) => {
+                let _ = client.flush().await;
+            }
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "audit-kafka")]
+fn kafka_producer(config: &AuditSinkConfig) -> Result<rdkafka::producer::FutureProducer, String> {
+    let mut client = rdkafka::ClientConfig::new();
+    client
+        .set("bootstrap.servers", config.brokers.join(","))
+        .set("message.timeout.ms", "30000");
+    let protocol = match (config.username.is_some(), config.tls) {
+        (true, true) => "sasl_ssl",
+        (true, false) => "sasl_plaintext",
+        (false, true) => "ssl",
+        (false, false) => "plaintext",
+    };
+    client.set("security.protocol", protocol);
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        client
+            .set("sasl.mechanism", &config.sasl_mechanism)
+            .set("sasl.username", username)
+            .set("sasl.password", password);
+    }
+    client.create().map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "audit-nats")]
+async fn nats_client(config: &AuditSinkConfig) -> Result<async_nats::Client, String> {
+    let mut options = async_nats::ConnectOptions::new()
+        .name("fee-manager-audit")
+        .require_tls(config.tls);
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        options = options.user_and_password(username.clone(), password.clone());
+    }
+    if let Some(token) = &config.token {
+        options = options.token(token.clone());
+    }
+    options
+        .connect(config.brokers.as_slice())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuditSinkKind;
+
+    fn config() -> AuditSinkConfig {
+        AuditSinkConfig {
+            kind: AuditSinkKind::Nats,
+            brokers: vec!["nats://localhost:4222".to_string()],
+            topic: "audit".to_string(),
+            username: None,
+            password: None,
+            password_file: None,
+            token: None,
+            sasl_mechanism: "PLAIN".to_string(),
+            tls: false,
+            buffer: 1,
+            retry_initial_ms: 100,
+            retry_max_ms: 350,
+            shutdown_timeout_secs: 1,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let mut backoff = Backoff::new(&config());
+        let delays: Vec<u128> = (0..4).map(|_| backoff.next().as_millis()).collect();
+        assert_eq!(delays, [100, 200, 350, 350]);
+    }
+
+    #[test]
+    fn shutdown_gives_up_on_unreachable_broker() {
+        let sink = BrokerSink::spawn(&AuditSinkConfig {
+            brokers: vec!["nats://127.0.0.1:1".to_string()],
+            ..config()
+        })
+        .unwrap();
+        sink.publish("{\"n\":1}");
+
+        let started = Instant::now();
+        sink.shutdown();
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,212 @@
+//! Stores audit events in the database, so the history of a resource can be queried
+
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::PgPool;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use tracing::{error, info, warn};
+
+use super::sink::SinkStats;
+use super::ResourceType;
+
+const RETRY_INITIAL: Duration = Duration::from_millis(100);
+const RETRY_MAX: Duration = Duration::from_secs(5);
+/// Attempts to store one event before it is dropped, so a bad event cannot stall the queue
+const MAX_ATTEMPTS: u32 = 5;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time between retention sweeps
+const SWEEP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Buffers serialized audit events for a dedicated thread that inserts them
+pub(crate) struct DatabaseStore {
+    sender: Sender<String>,
+    dropped: Arc<AtomicU64>,
+    stats: Arc<SinkStats>,
+    handle: JoinHandle<()>,
+}
+
+impl DatabaseStore {
+    /// Start the store thread with its own connection; it retries each event a few times before dropping it
+    pub(crate) fn spawn(options: PgConnectOptions, buffer: usize) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel(buffer);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let stats = Arc::new(SinkStats::new("database"));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let handle = std::thread::Builder::new()
+            .name("audit-store".to_string())
+            .spawn({
+                let dropped = dropped.clone();
+                let stats = stats.clone();
+                move || runtime.block_on(run(options, receiver, dropped, stats))
+            })?;
+
+        Ok(Self {
+            sender,
+            dropped,
+            stats,
+            handle,
+        })
+    }
+
+    /// Buffer one event; when the buffer is full the event is dropped and counted
+    pub(crate) fn publish(&self, line: &str) {
+        self.stats.buffered.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = self.sender.try_send(line.to_string()) {
+            self.stats.buffered.fetch_sub(1, Ordering::Relaxed);
+            if let TrySendError::Full(_) = e {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(crate) fn stats(&self) -> Arc<SinkStats> {
+        self.stats.clone()
+    }
+
+    /// Insert what is buffered and stop, giving up after ten seconds
+    pub(crate) fn shutdown(self) {
+        drop(self.sender);
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while !self.handle.is_finished() {
+            if Instant::now() >= deadline {
+                warn!("Audit store shutdown timed out, buffered events were not stored");
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let _ = self.handle.join();
+    }
+}
+
+async fn run(
+    options: PgConnectOptions,
+    mut receiver: Receiver<String>,
+    dropped: Arc<AtomicU64>,
+    stats: Arc<SinkStats>,
+) {
+    // A pool of its own: the application's pool belongs to another runtime
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with(options);
+
+    while let Some(line) = receiver.recv().await {
+        stats.buffered.fetch_sub(1, Ordering::Relaxed);
+        report_dropped(&dropped);
+        let mut delay = RETRY_INITIAL;
+        let mut attempt = 1;
+        loop {
+            // Access events are high volume and belong to no resource
+            let result = sqlx::query(
+                "INSERT INTO audit_events (event)
+                 SELECT event FROM (SELECT $1::jsonb AS event) e WHERE event->>'type' = 'audit'",
+            )
+            .bind(&line)
+            .execute(&pool)
+            .await;
+            match result {
+                Ok(_) => {
+                    stats.connected.store(true, Ordering::Relaxed);
+                    stats.published.fetch_add(1, Ordering::Relaxed);
+                    break;
+                }
+                Err(e) if attempt >= MAX_ATTEMPTS => {
+                    stats.connected.store(false, Ordering::Relaxed);
+                    stats.dropped.fetch_add(1, Ordering::Relaxed);
+                    // The event goes to the log instead, so it is not lost entirely
+                    error!(
+                        attempts = attempt,
+                        event = %line,
+                        "Dropping audit event that could not be stored: {}",
+                        e
+                    );
+                    break;
+                }
+                Err(e) => {
+                    stats.connected.store(false, Ordering::Relaxed);
+                    warn!(
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Failed to store audit event: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(RETRY_MAX);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    report_dropped(&dropped);
+    pool.close().await;
+}
+
+fn report_dropped(dropped: &AtomicU64) {
+    let count = dropped.swap(0, Ordering::Relaxed);
+    if count > 0 {
+        warn!(dropped = count, "Audit store buffer full, events were not stored");
+    }
+}
+
+/// A page of the stored audit events of one resource, oldest first, and how many there are
+pub async fn history(
+    pool: &PgPool,
+    resource_type: ResourceType,
+    resource_id: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<serde_json::Value>, i64), sqlx::Error> {
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_events WHERE resource_type = $1 AND resource_id = $2",
+    )
+    .bind(resource_type.as_str())
+    .bind(resource_id)
+    .fetch_one(pool)
+    .await?;
+    let events = sqlx::query_scalar(
+        "SELECT event FROM audit_events
+         WHERE resource_type = $1 AND resource_id = $2
+         ORDER BY id
+         LIMIT $3 OFFSET $4",
+    )
+    .bind(resource_type.as_str())
+    .bind(resource_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+    Ok((events, total))
+}
+
+/// Delete the stored audit events older than `retention_days`, returning how many
+pub async fn sweep(pool: &PgPool, retention_days: u32) -> Result<u64, sqlx::Error> {
+    Ok(
+        sqlx::query("DELETE FROM audit_events WHERE created_at < NOW() - make_interval(days => $1)")
+            .bind(i32::try_from(retention_days).unwrap_or(i32::MAX))
+            .execute(pool)
+            .await?
+            .rows_affected(),
+    )
+}
+
+/// Delete expired audit events every hour in the background (`audit_retention_days`)
+pub fn spawn_retention(pool: PgPool, retention_days: u32) {
+    let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+            match sweep(&pool, retention_days).await {
+                Ok(0) => {}
+                Ok(deleted) => info!(deleted, retention_days, "Expired audit events deleted"),
+                Err(e) => warn!("Audit retention sweep failed: {}", e),
+            }
+        }
+    });
+}
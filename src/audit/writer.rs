@@ -0,0 +1,251 @@
+//! Background writer that keeps audit output IO off the request path
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+
+use super::sink::{BrokerSink, SinkStats};
+use super::store::DatabaseStore;
+use super::AuditStatus;
+use crate::config::{AuditQueueConfig, AuditQueuePolicy};
+
+enum Message {
+    Line(String),
+    Shutdown,
+}
+
+/// Queues serialized audit events for a dedicated writer thread
+pub(crate) struct AuditWriter {
+    sender: Sender<Message>,
+    policy: AuditQueuePolicy,
+    dropped: Arc<AtomicU64>,
+    /// Events dropped since startup; `dropped` is reset whenever it is reported
+    dropped_total: AtomicU64,
+    sink: Option<Arc<SinkStats>>,
+    store: Option<Arc<SinkStats>>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl std::fmt::Debug for AuditWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditWriter")
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuditWriter {
+    /// Start the writer thread with a queue of `queue.capacity` events; every
+    /// written event is also handed to `sink` and `store` when configured
+    pub(crate) fn spawn(
+        output: Box<dyn Write + Send>,
+        sink: Option<BrokerSink>,
+        store: Option<DatabaseStore>,
+        queue: &AuditQueueConfig,
+    ) -> io::Result<Self> {
+        let (sender, receiver) = mpsc::channel(queue.capacity);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let sink_stats = sink.as_ref().map(BrokerSink::stats);
+        let store_stats = store.as_ref().map(DatabaseStore::stats);
+        let handle = std::thread::Builder::new()
+            .name("audit-writer".to_string())
+            .spawn({
+                let dropped = dropped.clone();
+                move || run(output, sink, store, receiver, &dropped)
+            })?;
+
+        Ok(Self {
+            sender,
+            policy: queue.when_full,
+            dropped,
+            dropped_total: AtomicU64::new(0),
+            sink: sink_stats,
+            store: store_stats,
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+
+    /// Queue one line; when the queue is full it is dropped or waits, depending on
+    /// the policy. Waiting yields to the runtime rather than parking its worker.
+    pub(crate) async fn send(&self, line: String) {
+        match self.policy {
+            AuditQueuePolicy::Block => {
+                let _ = self.sender.send(Message::Line(line)).await;
+            }
+            AuditQueuePolicy::Drop => {
+                if let Err(TrySendError::Full(_)) = self.sender.try_send(Message::Line(line)) {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    self.dropped_total.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Whether events are also stored in the database
+    pub(crate) fn stores_events(&self) -> bool {
+        self.store.is_some()
+    }
+
+    /// Whether the writer thread is alive, and what was lost so far
+    pub(crate) fn status(&self) -> AuditStatus {
+        let running = match self.handle.lock() {
+            Ok(handle) => handle.as_ref().is_some_and(|h| !h.is_finished()),
+            Err(_) => false,
+        };
+        AuditStatus {
+            writer_running: running,
+            dropped: self.dropped_total.load(Ordering::Relaxed),
+            sink: self.sink.as_ref().map(|stats| stats.status()),
+            database: self.store.as_ref().map(|stats| stats.status()),
+        }
+    }
+
+    /// Write out everything queued so far and stop the writer thread
+    pub(crate) async fn shutdown(&self) {
+        let handle = match self.handle.lock() {
+            Ok(mut handle) => handle.take(),
+            Err(_) => None,
+        };
+        let Some(handle) = handle else {
+            return;
+        };
+        // Waits for room rather than dropping, the queue drains in order up to this message
+        let _ = self.sender.send(Message::Shutdown).await;
+        let _ = tokio::task::spawn_blocking(move || handle.join()).await;
+    }
+}
+
+fn run(
+    mut output: Box<dyn Write + Send>,
+    sink: Option<BrokerSink>,
+    store: Option<DatabaseStore>,
+    mut receiver: Receiver<Message>,
+    dropped: &AtomicU64,
+) {
+    while let Some(mut message) = receiver.blocking_recv() {
+        // Write whatever is queued, then flush once
+        loop {
+            match message {
+                Message::Line(line) => {
+                    if let Err(e) = writeln!(output, "{}", line) {
+                        tracing::error!("Failed to write audit event: {}", e);
+                    }
+                    if let Some(sink) = &sink {
+                        sink.publish(&line);
+                    }
+                    if let Some(store) = &store {
+                        store.publish(&line);
+                    }
+                }
+                Message::Shutdown => {
+                    report_dropped(dropped);
+                    let _ = output.flush();
+                    if let Some(sink) = sink {
+                        sink.shutdown();
+                    }
+                    if let Some(store) = store {
+                        store.shutdown();
+                    }
+                    return;
+                }
+            }
+            match receiver.try_recv() {
+                Ok(next) => message = next,
+                Err(_) => break,
+            }
+        }
+        report_dropped(dropped);
+        if let Err(e) = output.flush() {
+            tracing::error!("Failed to flush audit output: {}", e);
+        }
+    }
+}
+
+fn report_dropped(dropped: &AtomicU64) {
+    let count = dropped.swap(0, Ordering::Relaxed);
+    if count > 0 {
+        tracing::warn!(dropped = count, "Audit queue full, events were dropped");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Condvar;
+
+    /// Collects output; writes wait while paused so tests can stall the writer
+    #[derive(Clone, Default)]
+    struct SharedOutput {
+        buf: Arc<Mutex<Vec<u8>>>,
+        paused: Arc<(Mutex<bool>, Condvar)>,
+    }
+
+    impl SharedOutput {
+        fn pause(&self, paused: bool) {
+            let (lock, resumed) = &*self.paused;
+            *lock.lock().unwrap() = paused;
+            resumed.notify_all();
+        }
+
+        fn lines(&self) -> usize {
+            String::from_utf8(self.buf.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .count()
+        }
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let (lock, resumed) = &*self.paused;
+            drop(resumed.wait_while(lock.lock().unwrap(), |paused| *paused).unwrap());
+            self.buf.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_writes_all_queued_events() {
+        let output = SharedOutput::default();
+        let queue = AuditQueueConfig {
+            capacity: 4,
+            when_full: AuditQueuePolicy::Block,
+        };
+        let writer = AuditWriter::spawn(Box::new(output.clone()), None, None, &queue).unwrap();
+
+        for i in 0..100 {
+            writer.send(format!("{{\"n\":{}}}", i)).await;
+        }
+        writer.shutdown().await;
+
+        assert_eq!(output.lines(), 100);
+    }
+
+    #[tokio::test]
+    async fn drops_events_when_queue_is_full() {
+        let output = SharedOutput::default();
+        let queue = AuditQueueConfig {
+            capacity: 2,
+            when_full: AuditQueuePolicy::Drop,
+        };
+        let writer = AuditWriter::spawn(Box::new(output.clone()), None, None, &queue).unwrap();
+
+        // A stalled output must not block senders
+        output.pause(true);
+        for i in 0..10 {
+            writer.send(format!("{{\"n\":{}}}", i)).await;
+        }
+        output.pause(false);
+        writer.shutdown().await;
+
+        // The queue holds two events, plus at most one the thread already took
+        let written = output.lines();
+        assert!((2..=3).contains(&written), "wrote {} events", written);
+    }
+}
@@ -0,0 +1,170 @@
+//! Public access tokens: optional bearer tokens guarding the public endpoint of
+//! a single default config or mux config. A resource without tokens stays public.
+
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::hashing::find_verified;
+use super::service::{generate_token, token_prefix};
+use crate::errors::ApiError;
+
+/// Resource whose public endpoint a token guards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessResource {
+    DefaultConfig,
+    Mux,
+}
+
+impl AccessResource {
+    /// Value stored in `public_access_tokens.resource_type`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccessResource::DefaultConfig => "vouch_default_config",
+            AccessResource::Mux => "commit_boost_mux",
+        }
+    }
+}
+
+/// Database model for public access tokens
+#[derive(Debug, Clone, FromRow)]
+pub struct AccessToken {
+    pub id: Uuid,
+    pub resource_type: String,
+    pub resource_name: String,
+    pub name: String,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// API response for an access token (excludes hash)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessTokenInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<AccessToken> for AccessTokenInfo {
+    fn from(token: AccessToken) -> Self {
+        Self {
+            id: token.id,
+            name: token.name,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+        }
+    }
+}
+
+const TOKEN_COLUMNS: &str = "id, resource_type, resource_name, name, token_hash, created_at, last_used_at";
+
+/// Create a token for a resource, returning it with the plaintext token
+pub async fn create_access_token(
+    pool: &PgPool,
+    resource: AccessResource,
+    resource_name: &str,
+    name: &str,
+) -> Result<(AccessToken, String), ApiError> {
+    let (plaintext, hash) = generate_token().await;
+    let token = sqlx::query_as::<_, AccessToken>(&format!(
+        "INSERT INTO public_access_tokens (resource_type, resource_name, name, token_hash, token_prefix)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING {}",
+        TOKEN_COLUMNS
+    ))
+    .bind(resource.as_str())
+    .bind(resource_name)
+    .bind(name)
+    .bind(&hash)
+    .bind(token_prefix(&plaintext))
+    .fetch_one(pool)
+    .await?;
+    Ok((token, plaintext))
+}
+
+/// Tokens of a resource, oldest first
+pub async fn list_access_tokens(
+    pool: &PgPool,
+    resource: AccessResource,
+    resource_name: &str,
+) -> Result<Vec<AccessToken>, ApiError> {
+    let tokens = sqlx::query_as::<_, AccessToken>(&format!(
+        "SELECT {} FROM public_access_tokens
+         WHERE resource_type = $1 AND resource_name = $2
+         ORDER BY created_at, id",
+        TOKEN_COLUMNS
+    ))
+    .bind(resource.as_str())
+    .bind(resource_name)
+    .fetch_all(pool)
+    .await?;
+    Ok(tokens)
+}
+
+/// Delete one token of a resource; false if the resource has no such token
+pub async fn delete_access_token(
+    pool: &PgPool,
+    resource: AccessResource,
+    resource_name: &str,
+    id: Uuid,
+) -> Result<bool, ApiError> {
+    let result = sqlx::query(
+        "DELETE FROM public_access_tokens WHERE id = $1 AND resource_type = $2 AND resource_name = $3",
+    )
+    .bind(id)
+    .bind(resource.as_str())
+    .bind(resource_name)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Let a public request through if the resource has no tokens, or the request
+/// carries one of them as `Authorization: Bearer <token>`
+pub async fn require_access(
+    pool: &PgPool,
+    resource: AccessResource,
+    resource_name: &str,
+    headers: &HeaderMap,
+) -> Result<(), ApiError> {
+    let candidates: Vec<(Uuid, String, String)> = sqlx::query_as(
+        "SELECT id, token_prefix, token_hash FROM public_access_tokens
+         WHERE resource_type = $1 AND resource_name = $2",
+    )
+    .bind(resource.as_str())
+    .bind(resource_name)
+    .fetch_all(pool)
+    .await?;
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+    let prefix = token_prefix(token);
+    let candidates: Vec<(Uuid, String)> = candidates
+        .into_iter()
+        .filter(|(_, candidate_prefix, _)| candidate_prefix == prefix)
+        .map(|(id, _, hash)| (id, hash))
+        .collect();
+    let hashes = candidates.iter().map(|(_, hash)| hash.clone()).collect();
+    let Some(index) = find_verified(token, hashes).await else {
+        return Err(ApiError::Unauthorized);
+    };
+    let id = candidates[index].0;
+
+    sqlx::query("UPDATE public_access_tokens SET last_used_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
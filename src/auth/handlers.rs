@@ -3,17 +3,21 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
-    routing::{delete, get},
-    Json, Router,
+    extract::{Path, Query, State},
+    routing::{get, patch},
+    Extension, Json, Router,
 };
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use uuid::Uuid;
 
-use super::{service, TokenInfo};
-use crate::audit::{AuditAction, AuditChanges, RequestContext, ResourceType};
+use super::oidc::OidcIdentity;
+use super::usage::{self, TokenRouteUsage};
+use super::TokenInfo;
+use crate::audit::{AuditAction, RequestContext, ResourceType};
 use crate::audit_log;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::services::tokens::{self, TokenRepository};
 use crate::{errors::ApiError, AppState};
 
 /// Request body for creating a new token
@@ -23,6 +27,9 @@ pub struct CreateTokenRequest {
     pub name: String,
     /// Optional longer description
     pub description: Option<String>,
+    /// Extra scopes such as `override`; `admin` is always granted
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
 
 /// Response when a token is created (includes plaintext token)
@@ -32,15 +39,95 @@ pub struct CreateTokenResponse {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    pub scopes: Vec<String>,
     /// The plaintext token - shown only once!
     pub token: String,
 }
 
+/// Request body for updating a token; omitted fields are left unchanged
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTokenRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Inactive tokens are rejected by the auth middleware until reactivated
+    pub active: Option<bool>,
+    /// Replaces the token's scopes; `admin` is always kept
+    pub scopes: Option<Vec<String>>,
+}
+
+/// The token authenticating the current request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WhoamiResponse {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// OIDC issuer when the request used a JWT; `id` is then nil and `name` is the subject
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+}
+
+/// What a token has been used for, to tell whether anything still depends on it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenUsageResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Requests over all routes
+    pub request_count: i64,
+    /// Fraction of requests recorded (`auth.usage.sample_rate`); below 1 the counts are estimates
+    pub sample_rate: f64,
+    /// Routes the token called, most recently used first
+    pub routes: Vec<TokenRouteUsage>,
+}
+
+/// Sorting parameters for the token list
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TokenListQuery {
+    /// Sort column: name, created_at, last_used_at (default: created_at desc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "name"),
+        ("created_at", "created_at"),
+        ("last_used_at", "last_used_at"),
+    ],
+    default: ("created_at", SortOrder::Desc),
+    tiebreak: "id",
+};
+
+/// OpenAPI paths and schemas for the token routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_tokens, create_token, update_token, delete_token, token_usage, whoami),
+    components(schemas(
+        TokenInfo,
+        CreateTokenRequest,
+        CreateTokenResponse,
+        UpdateTokenRequest,
+        TokenUsageResponse,
+        TokenRouteUsage,
+        WhoamiResponse
+    ))
+)]
+pub struct AuthApi;
+
 /// Create token routes
 pub fn token_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/", get(list_tokens).post(create_token))
-        .route("/{id}", delete(delete_token))
+        .route("/{id}", patch(update_token).delete(delete_token))
+        .route("/{id}/usage", get(token_usage))
 }
 
 /// List all tokens
@@ -48,16 +135,20 @@ pub fn token_routes() -> Router<Arc<AppState>> {
     get,
     path = "/api/admin/tokens",
     tag = "Auth",
+    params(TokenListQuery),
     responses(
         (status = 200, description = "List of tokens", body = Vec<TokenInfo>),
+        (status = 400, description = "Invalid sort column"),
         (status = 401, description = "Unauthorized")
     ),
     security(("bearer_auth" = []))
 )]
 pub async fn list_tokens(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<TokenListQuery>,
 ) -> Result<Json<Vec<TokenInfo>>, ApiError> {
-    let tokens = service::list_tokens(&state.pool).await?;
+    let order_by = SORT_COLUMNS.order_by(query.sort.as_deref(), query.order)?;
+    let tokens = state.pool.list(&order_by).await?;
     let token_infos: Vec<TokenInfo> = tokens.into_iter().map(TokenInfo::from).collect();
     Ok(Json(token_infos))
 }
@@ -70,6 +161,7 @@ pub async fn list_tokens(
     request_body = CreateTokenRequest,
     responses(
         (status = 201, description = "Token created", body = CreateTokenResponse),
+        (status = 400, description = "Unknown scope"),
         (status = 401, description = "Unauthorized")
     ),
     security(("bearer_auth" = []))
@@ -79,15 +171,10 @@ pub async fn create_token(
     ctx: RequestContext,
     Json(request): Json<CreateTokenRequest>,
 ) -> Result<Json<CreateTokenResponse>, ApiError> {
-    let (token, plaintext) =
-        service::create_token(&state.pool, &request.name, request.description.as_deref()).await?;
+    let (token, plaintext, changes) = tokens::create(&state.pool, &request).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            name: Some(token.name.clone()),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Create, ResourceType::AuthToken, token.id.to_string(), changes);
     }
 
@@ -95,10 +182,44 @@ pub async fn create_token(
         id: token.id,
         name: token.name,
         description: token.description,
+        scopes: token.scopes,
         token: plaintext,
     }))
 }
 
+/// Update a token's name, description, active flag or scopes
+#[utoipa::path(
+    patch,
+    path = "/api/admin/tokens/{id}",
+    tag = "Auth",
+    params(
+        ("id" = Uuid, Path, description = "Token ID to update")
+    ),
+    request_body = UpdateTokenRequest,
+    responses(
+        (status = 200, description = "Token updated", body = TokenInfo),
+        (status = 400, description = "Invalid name, unknown scope or deactivating the calling token"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Token not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_token(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateTokenRequest>,
+) -> Result<Json<TokenInfo>, ApiError> {
+    let (token, changes) = tokens::update(&state.pool, ctx.actor.token_id, id, &request).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::AuthToken, id.to_string(), changes);
+    }
+
+    Ok(Json(TokenInfo::from(token)))
+}
+
 /// Delete a token by ID
 #[utoipa::path(
     delete,
@@ -119,15 +240,92 @@ pub async fn delete_token(
     ctx: RequestContext,
     Path(id): Path<Uuid>,
 ) -> Result<axum::http::StatusCode, ApiError> {
-    let deleted = service::delete_token(&state.pool, id).await?;
-
-    if deleted {
-        // Audit log
-        if state.config.audit_enabled {
-            audit_log!(ctx, AuditAction::Delete, ResourceType::AuthToken, id.to_string());
-        }
-        Ok(axum::http::StatusCode::NO_CONTENT)
-    } else {
-        Err(ApiError::NotFound(format!("Token {} not found", id)))
+    tokens::delete(&state.pool, id).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::AuthToken, id.to_string());
+    }
+    Ok(axum::http::StatusCode::NO_CONTENT)
+}
+
+/// Requests a token made, by route
+#[utoipa::path(
+    get,
+    path = "/api/admin/tokens/{id}/usage",
+    tag = "Auth",
+    params(
+        ("id" = Uuid, Path, description = "Token ID")
+    ),
+    responses(
+        (status = 200, description = "Request counts and last use per route", body = TokenUsageResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Token not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn token_usage(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TokenUsageResponse>, ApiError> {
+    let token = tokens::get(&state.pool, id).await?;
+    let routes = usage::routes(&state.pool, id).await?;
+
+    Ok(Json(TokenUsageResponse {
+        id: token.id,
+        name: token.name,
+        active: token.active,
+        last_used_at: token.last_used_at,
+        request_count: routes.iter().map(|route| route.request_count).sum(),
+        sample_rate: state.config.auth.usage.sample_rate,
+        routes,
+    }))
+}
+
+/// Describe the token used for this request
+#[utoipa::path(
+    get,
+    path = "/api/admin/whoami",
+    tag = "Auth",
+    responses(
+        (status = 200, description = "Token used for this request", body = WhoamiResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Authentication is disabled, no token in use")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn whoami(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    identity: Option<Extension<OidcIdentity>>,
+) -> Result<Json<WhoamiResponse>, ApiError> {
+    if let Some(Extension(identity)) = identity {
+        return Ok(Json(WhoamiResponse {
+            id: Uuid::nil(),
+            name: identity.subject,
+            description: None,
+            scopes: identity.scopes,
+            created_at: identity.issued_at.unwrap_or_else(chrono::Utc::now),
+            last_used_at: None,
+            issuer: Some(identity.issuer),
+        }));
     }
+
+    if ctx.actor.token_id.is_nil() {
+        return Err(ApiError::NotFound(
+            "Authentication is disabled, no token in use".to_string(),
+        ));
+    }
+
+    let token = tokens::get(&state.pool, ctx.actor.token_id).await?;
+
+    Ok(Json(WhoamiResponse {
+        id: token.id,
+        name: token.name,
+        description: token.description,
+        scopes: token.scopes,
+        created_at: token.created_at,
+        last_used_at: token.last_used_at,
+        issuer: None,
+    }))
 }
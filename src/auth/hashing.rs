@@ -0,0 +1,234 @@
+//! Versioned token hashing
+//!
+//! Stored hashes carry their scheme so old hashes keep verifying after the
+//! configured scheme changes:
+//!
+//! - legacy: bare 64-char hex SHA-256 (read-only, migrated on first use)
+//! - `hmac-sha256$<hex>`: HMAC-SHA256 keyed with the server pepper
+//! - `$argon2id$...`: Argon2id PHC string (pepper used as the Argon2 secret)
+
+use std::sync::OnceLock;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::AuthConfig;
+
+const HMAC_PREFIX: &str = "hmac-sha256$";
+const ARGON2ID_PREFIX: &str = "$argon2id$";
+
+static TOKEN_HASHER: OnceLock<TokenHasher> = OnceLock::new();
+
+/// Scheme used for newly stored token hashes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum TokenHashScheme {
+    #[default]
+    #[serde(rename = "argon2id")]
+    Argon2id,
+    #[serde(rename = "hmac-sha256")]
+    HmacSha256,
+}
+
+/// Scheme a stored hash was produced with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StoredScheme {
+    LegacySha256,
+    HmacSha256,
+    Argon2id,
+}
+
+impl StoredScheme {
+    fn detect(stored: &str) -> Option<Self> {
+        if stored.starts_with(ARGON2ID_PREFIX) {
+            Some(Self::Argon2id)
+        } else if stored.starts_with(HMAC_PREFIX) {
+            Some(Self::HmacSha256)
+        } else if stored.len() == 64 && stored.bytes().all(|b| b.is_ascii_hexdigit()) {
+            Some(Self::LegacySha256)
+        } else {
+            None
+        }
+    }
+}
+
+/// Hashes and verifies API tokens with the configured scheme
+#[derive(Debug, Clone)]
+pub struct TokenHasher {
+    scheme: TokenHashScheme,
+    pepper: Option<Vec<u8>>,
+}
+
+impl Default for TokenHasher {
+    fn default() -> Self {
+        Self {
+            scheme: TokenHashScheme::Argon2id,
+            pepper: None,
+        }
+    }
+}
+
+impl TokenHasher {
+    pub fn new(scheme: TokenHashScheme, pepper: Option<&str>) -> Result<Self, String> {
+        let pepper = pepper.filter(|p| !p.is_empty()).map(|p| p.as_bytes().to_vec());
+        if scheme == TokenHashScheme::HmacSha256 && pepper.is_none() {
+            return Err("auth.token_pepper is required for the hmac-sha256 scheme".to_string());
+        }
+        Ok(Self { scheme, pepper })
+    }
+
+    /// Hash a token with the configured scheme
+    pub fn hash(&self, token: &str) -> String {
+        match self.scheme {
+            TokenHashScheme::Argon2id => {
+                let salt_bytes: [u8; 16] = rand::rng().random();
+                let salt = SaltString::encode_b64(&salt_bytes).expect("valid salt length");
+                self.argon2()
+                    .hash_password(token.as_bytes(), &salt)
+                    .expect("argon2 hashing with default params")
+                    .to_string()
+            }
+            TokenHashScheme::HmacSha256 => format!("{}{}", HMAC_PREFIX, self.hmac_hex(token)),
+        }
+    }
+
+    /// Check a token against a stored hash of any supported scheme
+    pub fn verify(&self, token: &str, stored: &str) -> bool {
+        match StoredScheme::detect(stored) {
+            Some(StoredScheme::LegacySha256) => {
+                constant_time_eq(legacy_sha256(token).as_bytes(), stored.as_bytes())
+            }
+            Some(StoredScheme::HmacSha256) => {
+                self.pepper.is_some()
+                    && constant_time_eq(
+                        self.hmac_hex(token).as_bytes(),
+                        &stored.as_bytes()[HMAC_PREFIX.len()..],
+                    )
+            }
+            Some(StoredScheme::Argon2id) => PasswordHash::new(stored)
+                .map(|hash| self.argon2().verify_password(token.as_bytes(), &hash).is_ok())
+                .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    /// Verify against a throwaway hash so unknown tokens take as long as known ones
+    pub fn verify_dummy(&self, token: &str) {
+        static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+        let dummy = DUMMY_HASH.get_or_init(|| self.hash(""));
+        let _ = self.verify(token, dummy);
+    }
+
+    /// Whether a stored hash should be replaced with one from the configured scheme
+    pub fn needs_rehash(&self, stored: &str) -> bool {
+        let wanted = match self.scheme {
+            TokenHashScheme::Argon2id => StoredScheme::Argon2id,
+            TokenHashScheme::HmacSha256 => StoredScheme::HmacSha256,
+        };
+        StoredScheme::detect(stored) != Some(wanted)
+    }
+
+    fn argon2(&self) -> Argon2<'_> {
+        match &self.pepper {
+            Some(pepper) => {
+                Argon2::new_with_secret(pepper, Algorithm::Argon2id, Version::V0x13, Params::default())
+                    .expect("valid argon2 secret")
+            }
+            None => Argon2::default(),
+        }
+    }
+
+    fn hmac_hex(&self, token: &str) -> String {
+        let key = self.pepper.as_deref().unwrap_or_default();
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(token.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+/// Unsalted SHA-256 hex digest used by the original token scheme
+pub fn legacy_sha256(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Initialize the global token hasher from auth config
+pub fn init_token_hasher(config: &AuthConfig) -> Result<(), String> {
+    let hasher = TokenHasher::new(config.hash_scheme, config.token_pepper.as_deref())?;
+    let _ = TOKEN_HASHER.set(hasher);
+    Ok(())
+}
+
+/// Get the global token hasher (Argon2id without pepper if not initialized)
+pub fn token_hasher() -> &'static TokenHasher {
+    TOKEN_HASHER.get_or_init(TokenHasher::default)
+}
+
+/// Index of the first stored hash the token verifies against. Argon2id takes
+/// tens of milliseconds, so this runs on the blocking pool rather than stalling
+/// a runtime worker. Without a match a dummy hash is verified as well, so
+/// unknown tokens take as long as known ones.
+pub async fn find_verified(token: &str, stored: Vec<String>) -> Option<usize> {
+    let token = token.to_string();
+    tokio::task::spawn_blocking(move || {
+        let hasher = token_hasher();
+        let found = stored.iter().position(|hash| hasher.verify(&token, hash));
+        if found.is_none() {
+            hasher.verify_dummy(&token);
+        }
+        found
+    })
+    .await
+    .expect("token verification panicked")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn argon2id_roundtrip() {
+        let hasher = TokenHasher::default();
+        let hash = hasher.hash("secret");
+        assert!(hash.starts_with(ARGON2ID_PREFIX));
+        assert!(hasher.verify("secret", &hash));
+        assert!(!hasher.verify("other", &hash));
+        assert!(!hasher.needs_rehash(&hash));
+    }
+
+    #[test]
+    fn hmac_requires_pepper() {
+        assert!(TokenHasher::new(TokenHashScheme::HmacSha256, None).is_err());
+
+        let hasher = TokenHasher::new(TokenHashScheme::HmacSha256, Some("pepper")).unwrap();
+        let hash = hasher.hash("secret");
+        assert!(hash.starts_with(HMAC_PREFIX));
+        assert!(hasher.verify("secret", &hash));
+
+        let other = TokenHasher::new(TokenHashScheme::HmacSha256, Some("other")).unwrap();
+        assert!(!other.verify("secret", &hash));
+    }
+
+    #[test]
+    fn legacy_hash_verifies_and_needs_rehash() {
+        let hasher = TokenHasher::default();
+        let legacy = legacy_sha256("secret");
+        assert!(hasher.verify("secret", &legacy));
+        assert!(!hasher.verify("other", &legacy));
+        assert!(hasher.needs_rehash(&legacy));
+    }
+
+    #[tokio::test]
+    async fn find_verified_picks_matching_hash() {
+        let hashes = vec![legacy_sha256("other"), token_hasher().hash("secret")];
+        assert_eq!(find_verified("secret", hashes.clone()).await, Some(1));
+        assert_eq!(find_verified("unknown", hashes).await, None);
+        assert_eq!(find_verified("secret", Vec::new()).await, None);
+    }
+}
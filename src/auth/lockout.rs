@@ -0,0 +1,154 @@
+//! Failure tracking and temporary lockout for admin authentication
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::config::LockoutConfig;
+
+/// Entries are pruned once the map grows past this size
+const PRUNE_THRESHOLD: usize = 1024;
+
+static LOCKOUT_TRACKER: OnceLock<LockoutTracker> = OnceLock::new();
+
+#[derive(Debug)]
+struct FailureEntry {
+    failures: u32,
+    window_start: Instant,
+    locked_until: Option<Instant>,
+}
+
+/// Counts authentication failures per key (client IP or token prefix)
+#[derive(Debug)]
+pub struct LockoutTracker {
+    window: Duration,
+    lockout: Duration,
+    entries: Mutex<HashMap<String, FailureEntry>>,
+}
+
+impl LockoutTracker {
+    pub fn new(config: &LockoutConfig) -> Self {
+        Self {
+            window: Duration::from_secs(config.window_secs),
+            lockout: Duration::from_secs(config.lockout_secs),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remaining lockout time for a key, if it is locked
+    pub fn locked_for(&self, key: &str) -> Option<Duration> {
+        let entries = self.entries.lock().ok()?;
+        let locked_until = entries.get(key)?.locked_until?;
+        locked_until.checked_duration_since(Instant::now())
+    }
+
+    /// Record a failed attempt. Returns true when this failure starts a lockout.
+    pub fn record_failure(&self, key: &str, max_failures: u32) -> bool {
+        if max_failures == 0 {
+            return false;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return false;
+        };
+        let now = Instant::now();
+
+        if entries.len() >= PRUNE_THRESHOLD {
+            let window = self.window;
+            entries.retain(|_, e| {
+                e.locked_until.is_some_and(|until| until > now)
+                    || now.duration_since(e.window_start) < window
+            });
+        }
+
+        let entry = entries.entry(key.to_string()).or_insert(FailureEntry {
+            failures: 0,
+            window_start: now,
+            locked_until: None,
+        });
+
+        let lock_expired = entry.locked_until.is_some_and(|until| until <= now);
+        if lock_expired || now.duration_since(entry.window_start) >= self.window {
+            entry.failures = 0;
+            entry.window_start = now;
+            entry.locked_until = None;
+        }
+
+        entry.failures += 1;
+        if entry.failures >= max_failures && entry.locked_until.is_none() {
+            entry.locked_until = Some(now + self.lockout);
+            return true;
+        }
+        false
+    }
+
+    /// Clear failures for a key after a successful authentication
+    pub fn record_success(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(key);
+        }
+    }
+}
+
+/// Get the global lockout tracker, created from config on first use
+pub fn lockout_tracker(config: &LockoutConfig) -> &'static LockoutTracker {
+    LOCKOUT_TRACKER.get_or_init(|| LockoutTracker::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(lockout_secs: u64) -> LockoutConfig {
+        LockoutConfig {
+            max_failures_per_ip: 3,
+            max_failures_per_token: 3,
+            window_secs: 300,
+            lockout_secs,
+        }
+    }
+
+    #[test]
+    fn locks_after_max_failures() {
+        let tracker = LockoutTracker::new(&config(60));
+
+        assert!(!tracker.record_failure("ip:1.2.3.4", 3));
+        assert!(!tracker.record_failure("ip:1.2.3.4", 3));
+        assert!(tracker.locked_for("ip:1.2.3.4").is_none());
+        assert!(tracker.record_failure("ip:1.2.3.4", 3));
+        assert!(tracker.locked_for("ip:1.2.3.4").is_some());
+
+        // Other keys are unaffected
+        assert!(tracker.locked_for("ip:5.6.7.8").is_none());
+    }
+
+    #[test]
+    fn success_clears_failures() {
+        let tracker = LockoutTracker::new(&config(60));
+
+        tracker.record_failure("token:abc", 3);
+        tracker.record_failure("token:abc", 3);
+        tracker.record_success("token:abc");
+        assert!(!tracker.record_failure("token:abc", 3));
+        assert!(tracker.locked_for("token:abc").is_none());
+    }
+
+    #[test]
+    fn lockout_expires() {
+        let tracker = LockoutTracker::new(&config(0));
+
+        for _ in 0..3 {
+            tracker.record_failure("ip:1.2.3.4", 3);
+        }
+        assert!(tracker.locked_for("ip:1.2.3.4").is_none());
+    }
+
+    #[test]
+    fn zero_limit_disables() {
+        let tracker = LockoutTracker::new(&config(60));
+
+        for _ in 0..10 {
+            assert!(!tracker.record_failure("ip:1.2.3.4", 0));
+        }
+        assert!(tracker.locked_for("ip:1.2.3.4").is_none());
+    }
+}
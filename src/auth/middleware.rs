@@ -4,14 +4,23 @@ use std::sync::Arc;
 
 use axum::{
     body::Body,
-    extract::State,
+    extract::{MatchedPath, State},
     http::{header::AUTHORIZATION, Request},
     middleware::Next,
     response::Response,
 };
+use uuid::Uuid;
 
-use super::service::{get_token_by_hash, update_last_used};
-use crate::{audit::ActorInfo, errors::ApiError, AppState};
+use super::lockout::{lockout_tracker, LockoutTracker};
+use super::oidc::{looks_like_jwt, oidc_verifier};
+use super::service::{find_token, token_prefix, update_last_used};
+use super::usage;
+use crate::{
+    audit::{ActorInfo, AuditAction, AuditEvent, ResourceType},
+    errors::ApiError,
+    handlers::ClientIp,
+    AppState,
+};
 
 /// Middleware that requires authentication via Bearer token
 pub async fn require_auth(
@@ -32,23 +41,124 @@ pub async fn require_auth(
         .and_then(|value| value.strip_prefix("Bearer "))
         .ok_or(ApiError::Unauthorized)?;
 
+    let lockout = &state.config.auth.lockout;
+    let tracker = lockout_tracker(lockout);
+    let ip_key = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| format!("ip:{}", ip));
+    let token_key = format!("token:{}", token_prefix(token));
+
+    // Reject locked out clients before touching the database
+    let locked = ip_key
+        .iter()
+        .chain(std::iter::once(&token_key))
+        .find_map(|key| tracker.locked_for(key));
+    if let Some(remaining) = locked {
+        return Err(ApiError::TooManyRequests(format!(
+            "Too many failed authentication attempts, retry in {}s",
+            remaining.as_secs().max(1)
+        )));
+    }
+
+    // JWTs from the identity provider; JWT prefixes are shared, so only the IP counts failures
+    if state.config.auth.oidc.enabled && looks_like_jwt(token) {
+        let identity = match oidc_verifier(&state.config.auth.oidc).verify(token).await {
+            Ok(identity) => identity,
+            Err(ApiError::Unauthorized) => {
+                if let Some(key) = &ip_key {
+                    let request_id = request.extensions().get::<Uuid>().copied();
+                    record_failure(&state, tracker, key, lockout.max_failures_per_ip, request_id).await;
+                }
+                return Err(ApiError::Unauthorized);
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(key) = &ip_key {
+            tracker.record_success(key);
+        }
+
+        let actor = ActorInfo {
+            token_id: Uuid::nil(),
+            token_name: identity.subject.clone(),
+            scopes: identity.scopes.clone(),
+        };
+        request.extensions_mut().insert(actor.clone());
+        request.extensions_mut().insert(identity);
+
+        let mut response = next.run(request).await;
+        response.extensions_mut().insert(actor);
+        return Ok(response);
+    }
+
     // Validate and get token info
-    let token_info = get_token_by_hash(&state.pool, token)
-        .await?
-        .ok_or(ApiError::Unauthorized)?;
+    let token_info = match find_token(&state.pool, token).await? {
+        Some(info) if info.active => info,
+        _ => {
+            let request_id = request.extensions().get::<Uuid>().copied();
+            if let Some(key) = &ip_key {
+                record_failure(&state, tracker, key, lockout.max_failures_per_ip, request_id).await;
+            }
+            record_failure(&state, tracker, &token_key, lockout.max_failures_per_token, request_id).await;
+            return Err(ApiError::Unauthorized);
+        }
+    };
 
-    if !token_info.active {
-        return Err(ApiError::Unauthorized);
+    if let Some(key) = &ip_key {
+        tracker.record_success(key);
     }
+    tracker.record_success(&token_key);
 
     // Update last_used_at
     update_last_used(&state.pool, token_info.id).await?;
+    // Counted by route template, so a token's usage has one row per endpoint
+    if let Some(route) = request.extensions().get::<MatchedPath>() {
+        usage::record(
+            &state.pool,
+            &state.config.auth.usage,
+            token_info.id,
+            request.method().as_str(),
+            route.as_str(),
+        );
+    }
 
     // Insert actor info into request extensions for audit logging
-    request.extensions_mut().insert(ActorInfo {
+    let actor = ActorInfo {
         token_id: token_info.id,
         token_name: token_info.name,
-    });
+        scopes: token_info.scopes,
+    };
+    request.extensions_mut().insert(actor.clone());
+
+    // Expose the actor on the response for the access log
+    let mut response = next.run(request).await;
+    response.extensions_mut().insert(actor);
+    Ok(response)
+}
 
-    Ok(next.run(request).await)
+/// Count a failed attempt and audit the start of a lockout
+async fn record_failure(
+    state: &AppState,
+    tracker: &LockoutTracker,
+    key: &str,
+    max_failures: u32,
+    request_id: Option<Uuid>,
+) {
+    if !tracker.record_failure(key, max_failures) {
+        return;
+    }
+
+    tracing::warn!("Authentication locked out for {} after {} failures", key, max_failures);
+    if state.config.audit_enabled {
+        AuditEvent::failure(
+            request_id.unwrap_or_else(Uuid::new_v4),
+            ActorInfo::default(),
+            AuditAction::AuthLockout,
+            ResourceType::AuthToken,
+            key,
+            format!("{} failed authentication attempts", max_failures),
+        )
+        .log()
+        .await;
+    }
 }
@@ -1,8 +1,14 @@
 // Auth module for API token authentication
 
+pub mod access;
 pub mod handlers;
+pub mod hashing;
+pub mod lockout;
 pub mod middleware;
+pub mod oidc;
 pub mod service;
+pub mod signing;
+pub mod usage;
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -10,6 +16,19 @@ use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Scope granting access to the admin API; every token carries it
+pub const ADMIN_SCOPE: &str = "admin";
+
+/// Scope allowing a request to bypass the `validation` policy bounds
+/// (sent with the `X-Fee-Manager-Override` header)
+pub const OVERRIDE_SCOPE: &str = "override";
+
+/// Scope allowing the log level to be changed at runtime (`PUT /api/admin/log-level`)
+pub const LOGGING_SCOPE: &str = "logging";
+
+/// Scopes tokens and OIDC scope mappings may grant
+pub const KNOWN_SCOPES: &[&str] = &[ADMIN_SCOPE, OVERRIDE_SCOPE, LOGGING_SCOPE];
+
 /// Database model for auth tokens
 #[derive(Debug, Clone, FromRow)]
 pub struct AuthToken {
@@ -20,6 +39,7 @@ pub struct AuthToken {
     pub created_at: DateTime<Utc>,
     pub last_used_at: Option<DateTime<Utc>>,
     pub active: bool,
+    pub scopes: Vec<String>,
 }
 
 /// API response for token info (excludes hash)
@@ -33,6 +53,7 @@ pub struct TokenInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_used_at: Option<DateTime<Utc>>,
     pub active: bool,
+    pub scopes: Vec<String>,
 }
 
 impl From<AuthToken> for TokenInfo {
@@ -44,6 +65,7 @@ impl From<AuthToken> for TokenInfo {
             created_at: token.created_at,
             last_used_at: token.last_used_at,
             active: token.active,
+            scopes: token.scopes,
         }
     }
 }
@@ -0,0 +1,266 @@
+//! Validation of OIDC-issued JWTs for the admin API
+//!
+//! Signing keys come from the provider's JWKS and are cached for
+//! `auth.oidc.jwks_refresh_secs`; a token signed with an unknown `kid` triggers
+//! an early refresh (at most once per `MIN_REFRESH_INTERVAL`) so key rotation
+//! does not lock users out.
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use super::ADMIN_SCOPE;
+use crate::config::OidcConfig;
+use crate::errors::ApiError;
+
+/// Shortest time between two JWKS fetches triggered by unknown key IDs
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+static OIDC_VERIFIER: OnceLock<OidcVerifier> = OnceLock::new();
+
+/// Caller identity taken from a validated JWT
+#[derive(Debug, Clone)]
+pub struct OidcIdentity {
+    pub subject: String,
+    pub issuer: String,
+    /// Fee-manager scopes mapped from the scopes claim
+    pub scopes: Vec<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct CachedKeys {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+/// Validates JWTs against the configured issuer, audience and JWKS
+#[derive(Debug)]
+pub struct OidcVerifier {
+    config: OidcConfig,
+    http: reqwest::Client,
+    cache: RwLock<Option<CachedKeys>>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    iss: String,
+    #[serde(default)]
+    iat: Option<i64>,
+    #[serde(flatten)]
+    other: serde_json::Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct Discovery {
+    jwks_uri: String,
+}
+
+/// Whether a bearer credential has the three-part shape of a JWT
+pub fn looks_like_jwt(token: &str) -> bool {
+    token.split('.').count() == 3
+}
+
+impl OidcVerifier {
+    pub fn new(config: &OidcConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+        Self {
+            config: config.clone(),
+            http,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Validate a JWT; `Unauthorized` for tokens that should count as failed logins
+    pub async fn verify(&self, token: &str) -> Result<OidcIdentity, ApiError> {
+        let header = decode_header(token).map_err(|_| ApiError::Unauthorized)?;
+        // Only asymmetric algorithms: the JWKS is public, so HMAC keys from it prove nothing
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err(ApiError::Unauthorized);
+        }
+        let jwk = self.signing_key(header.kid.as_deref()).await?;
+        let key = DecodingKey::from_jwk(&jwk).map_err(|_| ApiError::Unauthorized)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = self.config.leeway_secs;
+
+        let claims = decode::<Claims>(token, &key, &validation)
+            .map_err(|_| ApiError::Unauthorized)?
+            .claims;
+
+        // Other scopes only add to admin access, they do not grant it
+        let scopes = self.mapped_scopes(&claims);
+        if !scopes.iter().any(|scope| scope == ADMIN_SCOPE) {
+            return Err(ApiError::Unauthorized);
+        }
+
+        Ok(OidcIdentity {
+            subject: claims.sub,
+            issuer: claims.iss,
+            scopes,
+            issued_at: claims.iat.and_then(|iat| DateTime::from_timestamp(iat, 0)),
+        })
+    }
+
+    /// Fee-manager scopes granted by the configured claim, which may be a
+    /// space-separated string (`scope`) or an array (`groups`, `roles`)
+    fn mapped_scopes(&self, claims: &Claims) -> Vec<String> {
+        let values: Vec<&str> = match claims.other.get(&self.config.scopes_claim) {
+            Some(Value::String(s)) => s.split_whitespace().collect(),
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        values
+            .into_iter()
+            .filter_map(|value| self.config.scope_mapping.get(value).cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    async fn signing_key(&self, kid: Option<&str>) -> Result<Jwk, ApiError> {
+        let refresh = Duration::from_secs(self.config.jwks_refresh_secs);
+        {
+            let cache = self.cache.read().await;
+            if let Some(cached) = cache.as_ref() {
+                let fresh = cached.fetched_at.elapsed() < refresh;
+                if let Some(jwk) = find_key(&cached.keys, kid) {
+                    if fresh {
+                        return Ok(jwk.clone());
+                    }
+                } else if fresh && cached.fetched_at.elapsed() < MIN_REFRESH_INTERVAL {
+                    return Err(ApiError::Unauthorized);
+                }
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // Another request may have refreshed the keys while we waited for the lock
+        if let Some(cached) = cache.as_ref() {
+            if cached.fetched_at.elapsed() < MIN_REFRESH_INTERVAL {
+                return find_key(&cached.keys, kid)
+                    .cloned()
+                    .ok_or(ApiError::Unauthorized);
+            }
+        }
+        let keys = match self.fetch_keys().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                // Keep accepting known keys while the provider is unreachable
+                if let Some(jwk) = cache.as_ref().and_then(|c| find_key(&c.keys, kid)) {
+                    warn!("Using cached JWKS after refresh failed: {}", e);
+                    return Ok(jwk.clone());
+                }
+                return Err(e);
+            }
+        };
+        let jwk = find_key(&keys, kid).cloned();
+        *cache = Some(CachedKeys {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        jwk.ok_or(ApiError::Unauthorized)
+    }
+
+    async fn fetch_keys(&self) -> Result<JwkSet, ApiError> {
+        let jwks_url = match &self.config.jwks_url {
+            Some(url) => url.clone(),
+            None => {
+                let discovery_url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim_end_matches('/')
+                );
+                self.get_json::<Discovery>(&discovery_url).await?.jwks_uri
+            }
+        };
+        self.get_json(&jwks_url).await
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ApiError> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                ApiError::InternalError(format!("Cannot reach identity provider at {}: {}", url, e))
+            })?;
+        response.json().await.map_err(|e| {
+            ApiError::InternalError(format!("Invalid response from identity provider at {}: {}", url, e))
+        })
+    }
+}
+
+/// Key with the given ID, or the only key when the token names none
+fn find_key<'a>(keys: &'a JwkSet, kid: Option<&str>) -> Option<&'a Jwk> {
+    match kid {
+        Some(kid) => keys.find(kid),
+        None if keys.keys.len() == 1 => keys.keys.first(),
+        None => None,
+    }
+}
+
+/// Get the global OIDC verifier, created from config on first use
+pub fn oidc_verifier(config: &OidcConfig) -> &'static OidcVerifier {
+    OIDC_VERIFIER.get_or_init(|| OidcVerifier::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verifier(scopes_claim: &str) -> OidcVerifier {
+        let config = OidcConfig {
+            scopes_claim: scopes_claim.to_string(),
+            scope_mapping: [("fee-manager:admin".to_string(), "admin".to_string())].into(),
+            ..OidcConfig::default()
+        };
+        OidcVerifier::new(&config)
+    }
+
+    fn claims(json: Value) -> Claims {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn scopes_from_string_or_array_claims() {
+        let scope = claims(serde_json::json!({
+            "sub": "alice", "iss": "https://sso", "scope": "openid fee-manager:admin"
+        }));
+        assert_eq!(verifier("scope").mapped_scopes(&scope), vec!["admin"]);
+
+        let groups = claims(serde_json::json!({
+            "sub": "alice", "iss": "https://sso", "groups": ["ops", "fee-manager:admin"]
+        }));
+        assert_eq!(verifier("groups").mapped_scopes(&groups), vec!["admin"]);
+
+        let unmapped = claims(serde_json::json!({
+            "sub": "bob", "iss": "https://sso", "scope": "openid profile"
+        }));
+        assert!(verifier("scope").mapped_scopes(&unmapped).is_empty());
+    }
+
+    #[test]
+    fn jwt_shape() {
+        assert!(looks_like_jwt("eyJhbGciOi.eyJzdWIiOi.c2lnbmF0dXJl"));
+        assert!(!looks_like_jwt("0123456789abcdef0123456789abcdef"));
+    }
+}
@@ -1,78 +1,104 @@
 // Token service: generation, validation, and CRUD operations
 
 use rand::Rng;
-use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use super::AuthToken;
+use super::hashing::{find_verified, legacy_sha256, token_hasher};
+use super::{AuthToken, ADMIN_SCOPE};
 use crate::errors::ApiError;
 
 const TOKEN_LENGTH: usize = 32;
 
+/// Number of leading token characters stored in clear to select candidate rows
+const TOKEN_PREFIX_LENGTH: usize = 12;
+
 /// Generate a new random token and its hash
-pub fn generate_token() -> (String, String) {
-    let mut rng = rand::rng();
-    let token_bytes: [u8; TOKEN_LENGTH] = rng.random();
+pub async fn generate_token() -> (String, String) {
+    let token_bytes: [u8; TOKEN_LENGTH] = rand::rng().random();
     let token = hex::encode(token_bytes);
-    let hash = hash_token(&token);
+    let hash = hash_token(&token).await;
     (token, hash)
 }
 
-/// Hash a token using SHA-256
-pub fn hash_token(token: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(token.as_bytes());
-    hex::encode(hasher.finalize())
+/// Hash a token with the configured versioned scheme, on the blocking pool
+pub async fn hash_token(token: &str) -> String {
+    let token = token.to_string();
+    tokio::task::spawn_blocking(move || token_hasher().hash(&token))
+        .await
+        .expect("token hashing panicked")
 }
 
-/// Validate a token against the database
-pub async fn validate_token(pool: &PgPool, token: &str) -> Result<bool, ApiError> {
-    let hash = hash_token(token);
+/// Non-secret lookup prefix of a token
+pub(crate) fn token_prefix(token: &str) -> &str {
+    token.get(..TOKEN_PREFIX_LENGTH).unwrap_or(token)
+}
+
+/// Find the token matching a plaintext bearer token.
+///
+/// Legacy SHA-256 hashes (and hashes from a previously configured scheme) are
+/// rewritten with the current scheme on first successful use.
+pub async fn find_token(pool: &PgPool, token: &str) -> Result<Option<AuthToken>, ApiError> {
+    let prefix = token_prefix(token);
 
-    let result = sqlx::query_scalar!(
+    let mut candidates = sqlx::query_as!(
+        AuthToken,
         r#"
-        SELECT EXISTS(
-            SELECT 1 FROM auth_tokens
-            WHERE token_hash = $1 AND active = true
-        ) as "exists!"
+        SELECT id, name, description, token_hash, created_at, last_used_at, active, scopes
+        FROM auth_tokens
+        WHERE token_prefix = $1
         "#,
-        hash
+        prefix
     )
-    .fetch_one(pool)
+    .fetch_all(pool)
     .await?;
 
-    if result {
-        // Update last_used_at
-        sqlx::query!(
-            "UPDATE auth_tokens SET last_used_at = NOW() WHERE token_hash = $1",
-            hash
+    let hashes = candidates.iter().map(|candidate| candidate.token_hash.clone()).collect();
+    let mut found = find_verified(token, hashes)
+        .await
+        .map(|index| candidates.swap_remove(index));
+
+    if found.is_none() {
+        found = sqlx::query_as!(
+            AuthToken,
+            r#"
+            SELECT id, name, description, token_hash, created_at, last_used_at, active, scopes
+            FROM auth_tokens
+            WHERE token_prefix IS NULL AND token_hash = $1
+            "#,
+            legacy_sha256(token)
         )
-        .execute(pool)
+        .fetch_optional(pool)
         .await?;
     }
 
-    Ok(result)
-}
-
-/// Get token info by hash (for audit logging)
-/// Returns the token with updated last_used_at
-pub async fn get_token_by_hash(pool: &PgPool, token: &str) -> Result<Option<AuthToken>, ApiError> {
-    let hash = hash_token(token);
+    if let Some(found) = found.as_mut() {
+        if token_hasher().needs_rehash(&found.token_hash) {
+            let hash = hash_token(token).await;
+            sqlx::query!(
+                "UPDATE auth_tokens SET token_hash = $2, token_prefix = $3 WHERE id = $1",
+                found.id,
+                hash,
+                prefix
+            )
+            .execute(pool)
+            .await?;
+            found.token_hash = hash;
+        }
+    }
 
-    let token = sqlx::query_as!(
-        AuthToken,
-        r#"
-        SELECT id, name, description, token_hash, created_at, last_used_at, active
-        FROM auth_tokens
-        WHERE token_hash = $1
-        "#,
-        hash
-    )
-    .fetch_optional(pool)
-    .await?;
+    Ok(found)
+}
 
-    Ok(token)
+/// Validate a token against the database
+pub async fn validate_token(pool: &PgPool, token: &str) -> Result<bool, ApiError> {
+    match find_token(pool, token).await? {
+        Some(found) if found.active => {
+            update_last_used(pool, found.id).await?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
 }
 
 /// Update last_used_at for a token
@@ -88,19 +114,22 @@ pub async fn create_token(
     pool: &PgPool,
     name: &str,
     description: Option<&str>,
+    scopes: &[String],
 ) -> Result<(AuthToken, String), ApiError> {
-    let (plaintext, hash) = generate_token();
+    let (plaintext, hash) = generate_token().await;
 
     let token = sqlx::query_as!(
         AuthToken,
         r#"
-        INSERT INTO auth_tokens (name, description, token_hash)
-        VALUES ($1, $2, $3)
-        RETURNING id, name, description, token_hash, created_at, last_used_at, active
+        INSERT INTO auth_tokens (name, description, token_hash, token_prefix, scopes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, name, description, token_hash, created_at, last_used_at, active, scopes
         "#,
         name,
         description,
-        hash
+        hash,
+        token_prefix(&plaintext),
+        scopes
     )
     .fetch_one(pool)
     .await?;
@@ -108,18 +137,15 @@ pub async fn create_token(
     Ok((token, plaintext))
 }
 
-/// List all tokens (without hashes)
-pub async fn list_tokens(pool: &PgPool) -> Result<Vec<AuthToken>, ApiError> {
-    let tokens = sqlx::query_as!(
-        AuthToken,
-        r#"
-        SELECT id, name, description, token_hash, created_at, last_used_at, active
-        FROM auth_tokens
-        ORDER BY created_at DESC
-        "#
-    )
-    .fetch_all(pool)
-    .await?;
+/// List all tokens (without hashes), ordered by a whitelisted ORDER BY clause
+pub async fn list_tokens(pool: &PgPool, order_by: &str) -> Result<Vec<AuthToken>, ApiError> {
+    let sql = format!(
+        "SELECT id, name, description, token_hash, created_at, last_used_at, active, scopes
+         FROM auth_tokens
+         {}",
+        order_by
+    );
+    let tokens = sqlx::query_as::<_, AuthToken>(&sql).fetch_all(pool).await?;
 
     Ok(tokens)
 }
@@ -129,7 +155,7 @@ pub async fn get_token(pool: &PgPool, id: Uuid) -> Result<Option<AuthToken>, Api
     let token = sqlx::query_as!(
         AuthToken,
         r#"
-        SELECT id, name, description, token_hash, created_at, last_used_at, active
+        SELECT id, name, description, token_hash, created_at, last_used_at, active, scopes
         FROM auth_tokens
         WHERE id = $1
         "#,
@@ -141,6 +167,38 @@ pub async fn get_token(pool: &PgPool, id: Uuid) -> Result<Option<AuthToken>, Api
     Ok(token)
 }
 
+/// Update name, description, active flag and/or scopes; `None` leaves a field unchanged
+pub async fn update_token(
+    pool: &PgPool,
+    id: Uuid,
+    name: Option<&str>,
+    description: Option<&str>,
+    active: Option<bool>,
+    scopes: Option<&[String]>,
+) -> Result<Option<AuthToken>, ApiError> {
+    let token = sqlx::query_as!(
+        AuthToken,
+        r#"
+        UPDATE auth_tokens
+        SET name = COALESCE($2, name),
+            description = COALESCE($3, description),
+            active = COALESCE($4, active),
+            scopes = COALESCE($5, scopes)
+        WHERE id = $1
+        RETURNING id, name, description, token_hash, created_at, last_used_at, active, scopes
+        "#,
+        id,
+        name,
+        description,
+        active,
+        scopes
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(token)
+}
+
 /// Delete a token by ID
 pub async fn delete_token(pool: &PgPool, id: Uuid) -> Result<bool, ApiError> {
     let result = sqlx::query!("DELETE FROM auth_tokens WHERE id = $1", id)
@@ -163,7 +221,8 @@ pub async fn ensure_default_token(pool: &PgPool) -> Result<Option<String>, ApiEr
     }
 
     // Create default token
-    let (_, plaintext) = create_token(pool, "default", Some("Auto-generated initial token")).await?;
+    let scopes = [ADMIN_SCOPE.to_string()];
+    let (_, plaintext) = create_token(pool, "default", Some("Auto-generated initial token"), &scopes).await?;
 
     Ok(Some(plaintext))
 }
@@ -0,0 +1,130 @@
+//! HMAC request signing for the public endpoints
+//!
+//! Each consumer shares a secret with the server and sends three headers:
+//!
+//! - `X-Fee-Manager-Consumer`: consumer name from `auth.public_signing.consumers`
+//! - `X-Fee-Manager-Timestamp`: Unix time in seconds
+//! - `X-Fee-Manager-Signature`: hex HMAC-SHA256 over
+//!   `<timestamp>\n<METHOD>\n<path and query>\n<body>`
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{audit::ActorInfo, errors::ApiError, AppState};
+
+pub const CONSUMER_HEADER: &str = "x-fee-manager-consumer";
+pub const TIMESTAMP_HEADER: &str = "x-fee-manager-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-fee-manager-signature";
+
+fn request_mac(
+    secret: &str,
+    timestamp: i64,
+    method: &str,
+    path_and_query: &str,
+    body: &[u8],
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}\n{}\n{}\n", timestamp, method, path_and_query).as_bytes());
+    mac.update(body);
+    mac
+}
+
+/// Hex HMAC-SHA256 signature of a request
+pub fn sign(secret: &str, timestamp: i64, method: &str, path_and_query: &str, body: &[u8]) -> String {
+    hex::encode(request_mac(secret, timestamp, method, path_and_query, body).finalize().into_bytes())
+}
+
+/// Check a hex signature in constant time
+fn verify(
+    secret: &str,
+    signature: &str,
+    timestamp: i64,
+    method: &str,
+    path_and_query: &str,
+    body: &[u8],
+) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    request_mac(secret, timestamp, method, path_and_query, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+/// Middleware that requires a valid consumer signature on public routes
+pub async fn require_signature(
+    State(state): State<Arc<AppState>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let signing = &state.config.auth.public_signing;
+    let (parts, body) = request.into_parts();
+    let header = |name: &str| parts.headers.get(name).and_then(|value| value.to_str().ok());
+    let consumer_name = header(CONSUMER_HEADER).ok_or(ApiError::Unauthorized)?.to_string();
+    let signature = header(SIGNATURE_HEADER).ok_or(ApiError::Unauthorized)?.to_string();
+    let timestamp: i64 = header(TIMESTAMP_HEADER)
+        .and_then(|value| value.parse().ok())
+        .ok_or(ApiError::Unauthorized)?;
+
+    let skew = (chrono::Utc::now().timestamp() - timestamp).unsigned_abs();
+    if skew > signing.max_skew_secs {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let secret = signing
+        .consumers
+        .iter()
+        .find(|c| c.name == consumer_name)
+        .and_then(|c| c.secret.as_deref())
+        .ok_or(ApiError::Unauthorized)?;
+
+    // The body is signed too, so it is buffered up to auth.public_signing.max_body_bytes
+    let body = to_bytes(body, signing.max_body_bytes).await.map_err(|_| {
+        ApiError::PayloadTooLarge(format!("Signed request bodies are limited to {} bytes", signing.max_body_bytes))
+    })?;
+    let path_and_query = parts.uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    if !verify(secret, &signature, timestamp, parts.method.as_str(), path_and_query, &body) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // The consumer stands in for the token name in the access log
+    let actor = ActorInfo {
+        token_id: Uuid::nil(),
+        token_name: consumer_name,
+        scopes: Vec::new(),
+    };
+    let mut request = Request::from_parts(parts, Body::from(body));
+    request.extensions_mut().insert(actor.clone());
+
+    let mut response = next.run(request).await;
+    response.extensions_mut().insert(actor);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_request_and_body() {
+        let signature = sign("secret", 1700000000, "POST", "/vouch/v2/execution-config/main", b"{}");
+        assert!(verify("secret", &signature, 1700000000, "POST", "/vouch/v2/execution-config/main", b"{}"));
+
+        assert!(!verify("other", &signature, 1700000000, "POST", "/vouch/v2/execution-config/main", b"{}"));
+        assert!(!verify("secret", &signature, 1700000001, "POST", "/vouch/v2/execution-config/main", b"{}"));
+        assert!(!verify("secret", &signature, 1700000000, "POST", "/vouch/v2/execution-config/other", b"{}"));
+        assert!(!verify("secret", &signature, 1700000000, "POST", "/vouch/v2/execution-config/main", b"[]"));
+        assert!(!verify("secret", "not-hex", 1700000000, "POST", "/vouch/v2/execution-config/main", b"{}"));
+    }
+}
@@ -0,0 +1,67 @@
+// Per-token request counts by route
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::config::TokenUsageConfig;
+
+/// Requests a token made to one route
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenRouteUsage {
+    #[schema(example = "GET")]
+    pub method: String,
+    /// Matched route template
+    #[schema(example = "/api/admin/vouch/proposers/{public_key}")]
+    pub route: String,
+    /// Estimated from the sampled requests when `auth.usage.sample_rate` is below 1
+    pub request_count: i64,
+    pub first_used_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+}
+
+/// Count a request of `token_id` to a route without holding up the response
+pub fn record(pool: &PgPool, config: &TokenUsageConfig, token_id: Uuid, method: &str, route: &str) {
+    if !config.enabled || rand::rng().random::<f64>() >= config.sample_rate {
+        return;
+    }
+    // Each sampled request stands for the ones skipped
+    let weight = (1.0 / config.sample_rate).round() as i64;
+    let pool = pool.clone();
+    let method = method.to_string();
+    let route = route.to_string();
+    tokio::spawn(async move {
+        let result = sqlx::query!(
+            "INSERT INTO auth_token_usage (token_id, method, route, request_count)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (token_id, method, route) DO UPDATE SET
+                request_count = auth_token_usage.request_count + $4,
+                last_used_at = NOW()",
+            token_id,
+            method,
+            route,
+            weight
+        )
+        .execute(&pool)
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to record usage of token {}: {}", token_id, e);
+        }
+    });
+}
+
+/// Routes a token has called, most recently used first
+pub async fn routes(pool: &PgPool, token_id: Uuid) -> Result<Vec<TokenRouteUsage>, sqlx::Error> {
+    sqlx::query_as!(
+        TokenRouteUsage,
+        "SELECT method, route, request_count, first_used_at, last_used_at
+         FROM auth_token_usage WHERE token_id = $1
+         ORDER BY last_used_at DESC, method, route",
+        token_id
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,319 @@
+//! Scheduled backups
+//!
+//! Writes a snapshot of every configuration table to `export_storage` on the
+//! `backups.schedule` cron schedule, then deletes the snapshots the retention
+//! settings no longer keep. Each run of the schedule is claimed by inserting
+//! its time into `backups`, so of several replicas only one takes it.
+//! [`restore`] puts a snapshot back.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::config::{BackupRetentionConfig, BackupsConfig, ExportStorageConfig};
+use crate::cron::CronSchedule;
+use crate::errors::ApiError;
+use crate::object_storage::{export_name, ObjectStorage};
+use crate::schema::{BackupResponse, BackupRestoreResponse, BackupSnapshot, JobStatus, StoredExport};
+
+/// A run still going after this long died with its replica
+const INTERRUPTED_AFTER_SECS: f64 = 3600.0;
+
+/// Tables a snapshot holds, parents before children. Usage, registrations,
+/// pattern matches, audit events, jobs and admin tokens are history or derived
+/// and not part of it; a restore drops the usage and registrations of the
+/// proposers and configs it replaces.
+const TABLES: &[&str] = &[
+    "vouch_default_configs",
+    "vouch_default_relays",
+    "vouch_relay_sets",
+    "vouch_relay_set_relays",
+    "vouch_proposers",
+    "vouch_proposer_relays",
+    "vouch_proposer_patterns",
+    "vouch_proposer_pattern_relays",
+    "vouch_proposer_groups",
+    "vouch_proposer_group_keys",
+    "vouch_proposer_group_relays",
+    "commit_boost_mux_configs",
+    "commit_boost_mux_keys",
+    "commit_boost_pbs_configs",
+    "commit_boost_pbs_relays",
+    "commit_boost_pbs_muxes",
+    "disabled_relays",
+    "public_access_tokens",
+];
+
+/// Take the backup for the run of the schedule at `scheduled_for` and prune
+/// old snapshots; `None` when another replica already claimed the run
+pub async fn run(
+    pool: &PgPool,
+    storage: &ExportStorageConfig,
+    retention: &BackupRetentionConfig,
+    scheduled_for: DateTime<Utc>,
+) -> Result<Option<BackupResponse>, sqlx::Error> {
+    let id = Uuid::new_v4();
+    let claimed = sqlx::query!(
+        "INSERT INTO backups (id, scheduled_for) VALUES ($1, $2) ON CONFLICT (scheduled_for) DO NOTHING",
+        id,
+        scheduled_for
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if claimed == 0 {
+        return Ok(None);
+    }
+
+    let storage = ObjectStorage::new(storage).map_err(|e| format!("Export storage client: {}", e));
+    let outcome = match &storage {
+        Ok(storage) => write_snapshot(pool, storage, scheduled_for).await,
+        Err(e) => Err(e.clone()),
+    };
+    let (status, key, size, error) = match &outcome {
+        Ok(stored) => (JobStatus::Succeeded, Some(stored.key.clone()), Some(stored.size as i64), None),
+        Err(e) => (JobStatus::Failed, None, None, Some(e.clone())),
+    };
+    let backup = sqlx::query!(
+        r#"UPDATE backups SET status = $2, object_key = $3, size = $4, error = $5, finished_at = NOW()
+           WHERE id = $1 RETURNING started_at, finished_at"#,
+        id,
+        status as _,
+        key,
+        size,
+        error
+    )
+    .fetch_one(pool)
+    .await?;
+
+    // Only a new snapshot may push old ones out, so failing runs keep what there is
+    if let (Ok(_), Ok(storage)) = (&outcome, &storage) {
+        if let Err(e) = prune(pool, storage, retention).await {
+            warn!("Pruning old backups failed: {}", e);
+        }
+    }
+
+    Ok(Some(BackupResponse {
+        id,
+        scheduled_for,
+        status,
+        key,
+        size,
+        error,
+        started_at: backup.started_at,
+        finished_at: backup.finished_at,
+    }))
+}
+
+async fn write_snapshot(
+    pool: &PgPool,
+    storage: &ObjectStorage,
+    scheduled_for: DateTime<Utc>,
+) -> Result<StoredExport, String> {
+    let snapshot = snapshot(pool).await.map_err(|e| e.to_string())?;
+    storage
+        .put_json(&export_name("backups", scheduled_for), &snapshot, false)
+        .await
+        .map_err(|e| format!("Export storage: {}", e))
+}
+
+/// Read every configuration table in one transaction, so the snapshot is
+/// consistent while writes go on
+pub async fn snapshot(pool: &PgPool) -> Result<BackupSnapshot, ApiError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    let taken_at = sqlx::query_scalar!(r#"SELECT NOW() AS "now!""#)
+        .fetch_one(&mut *tx)
+        .await?;
+    let schema_version = schema_version(&mut tx).await?;
+    let mut tables = BTreeMap::new();
+    for table in TABLES {
+        let rows: Vec<serde_json::Value> = sqlx::query_scalar(&format!("SELECT to_jsonb(t) FROM {} t", table))
+            .fetch_all(&mut *tx)
+            .await?;
+        tables.insert(table.to_string(), rows);
+    }
+    tx.commit().await?;
+
+    Ok(BackupSnapshot {
+        taken_at,
+        schema_version,
+        tables,
+    })
+}
+
+/// Replace the contents of every configuration table with a snapshot, in one
+/// transaction. Restored rows keep their ids and creation times; parents get
+/// the time of the restore as `updated_at`.
+pub async fn restore(pool: &PgPool, snapshot: &BackupSnapshot) -> Result<BackupRestoreResponse, ApiError> {
+    if let Some(table) = snapshot.tables.keys().find(|table| !TABLES.contains(&table.as_str())) {
+        return Err(ApiError::InvalidData(format!("Unknown table in snapshot: {}", table)));
+    }
+    if let Some(table) = TABLES.iter().find(|table| !snapshot.tables.contains_key(**table)) {
+        return Err(ApiError::InvalidData(format!("Snapshot lacks table {}", table)));
+    }
+
+    let mut tx = pool.begin().await?;
+    let schema_version = schema_version(&mut tx).await?;
+    if snapshot.schema_version != schema_version {
+        return Err(ApiError::Conflict(format!(
+            "Snapshot was taken at schema version {}, the database is at {}",
+            snapshot.schema_version, schema_version
+        )));
+    }
+
+    // Children first; deleting configs also deletes their access tokens
+    for table in TABLES.iter().rev() {
+        sqlx::query(&format!("DELETE FROM {}", table)).execute(&mut *tx).await?;
+    }
+    let mut restored = BTreeMap::new();
+    for table in TABLES {
+        let rows = &snapshot.tables[*table];
+        sqlx::query(&format!(
+            "INSERT INTO {0} SELECT * FROM jsonb_populate_recordset(NULL::{0}, $1)",
+            table
+        ))
+        .bind(Json(rows))
+        .execute(&mut *tx)
+        .await?;
+        restore_sequences(&mut tx, table).await?;
+        restored.insert(table.to_string(), rows.len());
+    }
+    tx.commit().await?;
+
+    Ok(BackupRestoreResponse {
+        taken_at: snapshot.taken_at,
+        tables: restored,
+    })
+}
+
+/// Latest migration applied
+async fn schema_version(conn: &mut PgConnection) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(r#"SELECT COALESCE(MAX(version), 0) AS "version!" FROM _sqlx_migrations WHERE success"#)
+        .fetch_one(conn)
+        .await
+}
+
+/// Move the serial sequences of a table past the restored ids
+async fn restore_sequences(conn: &mut PgConnection, table: &str) -> Result<(), sqlx::Error> {
+    let columns = sqlx::query!(
+        r#"SELECT column_name AS "column_name!", pg_get_serial_sequence(table_name, column_name) AS "sequence!"
+           FROM information_schema.columns
+           WHERE table_schema = current_schema() AND table_name = $1
+             AND pg_get_serial_sequence(table_name, column_name) IS NOT NULL"#,
+        table
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    for column in columns {
+        sqlx::query(&format!(
+            "SELECT setval($1, COALESCE((SELECT MAX({}) FROM {}), 0) + 1, false)",
+            column.column_name, table
+        ))
+        .bind(&column.sequence)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Delete the snapshots retention no longer keeps, and the failed runs older
+/// than every snapshot left
+async fn prune(pool: &PgPool, storage: &ObjectStorage, retention: &BackupRetentionConfig) -> Result<(), sqlx::Error> {
+    let expired = sqlx::query!(
+        r#"SELECT id, object_key AS "object_key!" FROM backups
+           WHERE status = 'succeeded' AND (
+               ($1::INT > 0 AND id NOT IN (SELECT id FROM backups WHERE status = 'succeeded' ORDER BY scheduled_for DESC LIMIT $1))
+               OR ($2::INT > 0 AND scheduled_for < NOW() - make_interval(days => $2)))"#,
+        i32::try_from(retention.keep_last).unwrap_or(i32::MAX),
+        i32::try_from(retention.max_age_days).unwrap_or(i32::MAX)
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for backup in expired {
+        // The row stays while its object does, so a failed delete is retried next run
+        if let Err(e) = storage.delete(&backup.object_key).await {
+            warn!(key = %backup.object_key, "Deleting old backup failed: {}", e);
+            continue;
+        }
+        sqlx::query!("DELETE FROM backups WHERE id = $1", backup.id)
+            .execute(pool)
+            .await?;
+        info!(key = %backup.object_key, "Old backup deleted");
+    }
+
+    sqlx::query!(
+        "DELETE FROM backups WHERE status = 'failed'
+         AND scheduled_for < (SELECT MIN(scheduled_for) FROM backups WHERE status = 'succeeded')"
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Every recorded run, newest first, failing those interrupted by their replica stopping
+pub async fn list(pool: &PgPool) -> Result<Vec<BackupResponse>, sqlx::Error> {
+    sqlx::query!(
+        "UPDATE backups SET status = 'failed', error = 'Interrupted by its replica stopping', finished_at = NOW()
+         WHERE status = 'running' AND started_at < NOW() - make_interval(secs => $1)",
+        INTERRUPTED_AFTER_SECS
+    )
+    .execute(pool)
+    .await?;
+
+    let backups = sqlx::query!(
+        r#"SELECT id, scheduled_for, status AS "status: JobStatus", object_key, size, error, started_at, finished_at
+           FROM backups ORDER BY scheduled_for DESC"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(backups
+        .into_iter()
+        .map(|backup| BackupResponse {
+            id: backup.id,
+            scheduled_for: backup.scheduled_for,
+            status: backup.status,
+            key: backup.object_key,
+            size: backup.size,
+            error: backup.error,
+            started_at: backup.started_at,
+            finished_at: backup.finished_at,
+        })
+        .collect())
+}
+
+/// Take backups on the configured schedule in the background
+pub fn spawn(pool: PgPool, config: &BackupsConfig, storage: &ExportStorageConfig) {
+    let config = config.clone();
+    let storage = storage.clone();
+    let schedule = CronSchedule::parse(&config.schedule).expect("backups.schedule is checked by validate");
+    tokio::spawn(async move {
+        let mut after = Utc::now();
+        loop {
+            // Never before the last run, should the clock be behind the timer
+            let Some(next) = schedule.next_after(after.max(Utc::now())) else {
+                warn!(schedule = %config.schedule, "Backup schedule never runs");
+                return;
+            };
+            tokio::time::sleep((next - Utc::now()).to_std().unwrap_or_default()).await;
+            after = next;
+
+            match run(&pool, &storage, &config.retention, next).await {
+                Ok(Some(backup)) if backup.status == JobStatus::Succeeded => {
+                    info!(key = backup.key.as_deref().unwrap_or_default(), size = backup.size, "Backup completed")
+                }
+                Ok(Some(backup)) => warn!("Backup failed: {}", backup.error.unwrap_or_default()),
+                Ok(None) => {}
+                Err(e) => warn!("Backup failed: {}", e),
+            }
+        }
+    });
+}
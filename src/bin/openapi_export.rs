@@ -0,0 +1,10 @@
+// bin/openapi_export.rs - Print the OpenAPI spec for client generation
+use fee_manager::openapi::ApiDoc;
+use utoipa::OpenApi;
+
+fn main() {
+    let spec = ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("Failed to serialize OpenAPI spec");
+    println!("{}", spec);
+}
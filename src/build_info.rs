@@ -0,0 +1,27 @@
+//! Build information recorded by build.rs
+
+use chrono::{DateTime, Utc};
+
+/// Crate version
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Git commit the binary was built from, if known
+pub fn commit() -> Option<&'static str> {
+    Some(env!("FEE_MANAGER_GIT_COMMIT")).filter(|commit| !commit.is_empty())
+}
+
+/// When the binary was built (`SOURCE_DATE_EPOCH` when set)
+pub fn built_at() -> Option<DateTime<Utc>> {
+    env!("FEE_MANAGER_BUILD_TIMESTAMP")
+        .parse()
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+}
+
+/// Cargo features compiled in
+pub fn features() -> Vec<&'static str> {
+    env!("FEE_MANAGER_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect()
+}
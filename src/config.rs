@@ -1,76 +1,1819 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
 use config::{Config, Environment, File};
-use serde::Deserialize;
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use utoipa::ToSchema;
+
+use crate::addresses::BlsPubkey;
+use crate::auth::hashing::{TokenHashScheme, TokenHasher};
+use crate::auth::{ADMIN_SCOPE, KNOWN_SCOPES};
+use crate::cron::CronSchedule;
 
 #[derive(Clone, Deserialize, Debug)]
 pub struct AppConfig {
+    #[serde(default)]
     pub database: DatabaseConfig,
+    #[serde(default)]
     pub auth: AuthConfig,
+    #[serde(default)]
+    pub vouch: VouchConfig,
+    #[serde(default)]
+    pub commit_boost: CommitBoostConfig,
+    /// Policy bounds checked on every write
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    /// Admin API request bounds
+    #[serde(default)]
+    pub api: ApiConfig,
+    /// S3-compatible bucket exports can be written to with `?destination=storage`
+    #[serde(default)]
+    pub export_storage: Option<ExportStorageConfig>,
+    /// Scheduled snapshots into `export_storage`
+    #[serde(default)]
+    pub backups: BackupsConfig,
+    /// Pass over the database for damage left by manual edits
+    #[serde(default)]
+    pub consistency_check: ConsistencyCheckConfig,
+    /// Tracing filter directive (default: "info")
+    #[serde(default = "default_log_level")]
     pub log_level: String,
     /// Log format: "text" (default) or "json"
     #[serde(default = "default_log_format")]
     pub log_format: String,
+    /// Log one line per HTTP request with status and latency (default: true)
+    #[serde(default = "default_access_log")]
+    pub access_log: bool,
+    /// Serve the embedded admin UI under /ui (default: true)
+    #[serde(default = "default_ui_enabled")]
+    pub ui_enabled: bool,
+    /// Serve the read-only GraphQL API under /api/admin/graphql (default: false)
+    #[serde(default)]
+    pub graphql_enabled: bool,
+    /// Serve Prometheus metrics under /metrics, next to the admin API (default: false)
+    #[serde(default)]
+    pub metrics_enabled: bool,
     /// Enable audit trail logging (default: true)
     #[serde(default = "default_audit_enabled")]
     pub audit_enabled: bool,
+    /// Also record reads of the public execution-config and mux key endpoints (default: false)
+    #[serde(default)]
+    pub audit_public_reads: bool,
     /// Audit output destination: "stdout", "stderr", or file path (default: "stderr")
     #[serde(default = "default_audit_output")]
     pub audit_output: String,
+    /// Rotation policy used when audit_output is a file path
+    #[serde(default)]
+    pub audit_rotation: AuditRotationConfig,
+    /// Queue between request handlers and the audit writer
+    #[serde(default)]
+    pub audit_queue: AuditQueueConfig,
+    /// Also publish audit events to Kafka or NATS (needs the matching cargo feature)
+    #[serde(default)]
+    pub audit_sink: Option<AuditSinkConfig>,
+    /// Also store audit events in the database, for the per-resource `/audit` endpoints (default: false)
+    #[serde(default)]
+    pub audit_database: bool,
+    /// Days stored audit events are kept before an hourly sweep deletes them (0 keeps them, default: 0)
+    #[serde(default)]
+    pub audit_retention_days: u32,
+    /// Public responses cached per replica until their data changes (0 disables, default: 1000)
+    #[serde(default = "default_response_cache_size")]
+    pub response_cache_size: usize,
+    /// Bound on concurrent public requests, shedding the excess with 503
+    #[serde(default)]
+    pub public_concurrency: PublicConcurrencyConfig,
+    /// Which sockets to serve on
+    #[serde(default)]
+    pub listen: ListenConfig,
+    /// Listen address (default: "0.0.0.0")
+    #[serde(default = "default_host")]
     pub host: String,
+    /// Listen port (default: 3000)
+    #[serde(default = "default_port")]
     pub port: u16,
 }
 
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_host() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_port() -> u16 {
+    3000
+}
+
 fn default_log_format() -> String {
     "text".to_string()
 }
 
+fn default_access_log() -> bool {
+    true
+}
+
+fn default_ui_enabled() -> bool {
+    true
+}
+
 fn default_audit_enabled() -> bool {
     true
 }
 
+fn default_response_cache_size() -> usize {
+    1000
+}
+
 fn default_audit_output() -> String {
     "stderr".to_string()
 }
 
+#[derive(Clone, Deserialize, Debug)]
+pub struct VouchConfig {
+    #[serde(default)]
+    pub registrations: RegistrationsConfig,
+    /// Count execution-config fetches per config and consumer (default: true)
+    #[serde(default = "default_track_usage")]
+    pub track_usage: bool,
+    #[serde(default)]
+    pub cleanup: ProposerCleanupConfig,
+    #[serde(default)]
+    pub pattern_matches: PatternMatchesConfig,
+    /// When the v1 execution config was deprecated; unset serves it without
+    /// deprecation headers (default)
+    #[serde(default)]
+    pub v1_deprecated_at: Option<DateTime<Utc>>,
+}
+
+impl Default for VouchConfig {
+    fn default() -> Self {
+        Self {
+            registrations: RegistrationsConfig::default(),
+            track_usage: default_track_usage(),
+            cleanup: ProposerCleanupConfig::default(),
+            pattern_matches: PatternMatchesConfig::default(),
+            v1_deprecated_at: None,
+        }
+    }
+}
+
+fn default_track_usage() -> bool {
+    true
+}
+
+/// Background job that records the validator registrations relays have seen
+#[derive(Clone, Deserialize, Debug)]
+pub struct RegistrationsConfig {
+    /// Run the ingestion job (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between runs (default: 3600)
+    #[serde(default = "default_registrations_interval_secs")]
+    pub interval_secs: u64,
+    /// Per-request timeout in seconds (default: 10)
+    #[serde(default = "default_registrations_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Relays to query; empty means every relay URL configured in the database
+    #[serde(default)]
+    pub relays: Vec<String>,
+}
+
+impl Default for RegistrationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_registrations_interval_secs(),
+            timeout_secs: default_registrations_timeout_secs(),
+            relays: Vec::new(),
+        }
+    }
+}
+
+fn default_registrations_interval_secs() -> u64 {
+    3600
+}
+
+fn default_registrations_timeout_secs() -> u64 {
+    10
+}
+
+/// What the proposer cleanup does with proposers nobody requests any more
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupAction {
+    /// Set `decommissioned_at`, keeping the row
+    #[default]
+    Decommission,
+    /// Delete the proposer with its relays
+    Delete,
+}
+
+/// Background job that decommissions or deletes proposers no execution-config
+/// request has asked for in `inactive_days` (needs `track_usage`)
+#[derive(Clone, Deserialize, Debug)]
+pub struct ProposerCleanupConfig {
+    /// Run the cleanup job (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between runs (default: 86400)
+    #[serde(default = "default_cleanup_interval_secs")]
+    pub interval_secs: u64,
+    /// Days without a request before a proposer is cleaned up (default: 90)
+    #[serde(default = "default_cleanup_inactive_days")]
+    pub inactive_days: u32,
+    #[serde(default)]
+    pub action: CleanupAction,
+}
+
+impl Default for ProposerCleanupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_cleanup_interval_secs(),
+            inactive_days: default_cleanup_inactive_days(),
+            action: CleanupAction::default(),
+        }
+    }
+}
+
+fn default_cleanup_interval_secs() -> u64 {
+    86400
+}
+
+fn default_cleanup_inactive_days() -> u32 {
+    90
+}
+
+/// Background job that keeps the proposer pattern match table current
+#[derive(Clone, Deserialize, Debug)]
+pub struct PatternMatchesConfig {
+    /// Run the refresh job (default: true); reads refresh stale matches either way
+    #[serde(default = "default_pattern_matches_enabled")]
+    pub enabled: bool,
+    /// Seconds between checks for pattern or proposer changes (default: 30)
+    #[serde(default = "default_pattern_matches_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for PatternMatchesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_pattern_matches_enabled(),
+            interval_secs: default_pattern_matches_interval_secs(),
+        }
+    }
+}
+
+fn default_pattern_matches_enabled() -> bool {
+    true
+}
+
+fn default_pattern_matches_interval_secs() -> u64 {
+    30
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct CommitBoostConfig {
+    /// Reject adding a key that already belongs to another mux config (default: false)
+    #[serde(default)]
+    pub strict_unique_keys: bool,
+}
+
+/// Policy for written values. The gas_limit bounds may be exceeded with the
+/// `override` scope and the `X-Fee-Manager-Override: true` header; the relay
+/// registry may not
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct ValidationConfig {
+    /// Smallest gas_limit accepted for configs, proposers, patterns and relays (default: unbounded)
+    #[serde(default)]
+    pub gas_limit_min: Option<u64>,
+    /// Largest gas_limit accepted for configs, proposers, patterns and relays (default: unbounded)
+    #[serde(default)]
+    pub gas_limit_max: Option<u64>,
+    /// Registry of relay public keys; a relay written with a listed URL must
+    /// use its public key (default: none)
+    #[serde(default)]
+    pub relay_keys: Vec<RelayKeyConfig>,
+    /// Also reject relays whose URL is not in `relay_keys` (default: false)
+    #[serde(default)]
+    pub require_known_relays: bool,
+}
+
+/// Public key a relay is known by; URLs match regardless of case and a trailing slash
+#[derive(Clone, Deserialize, Debug)]
+pub struct RelayKeyConfig {
+    pub url: String,
+    pub public_key: BlsPubkey,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct ApiConfig {
+    /// Largest `limit` a list request may ask for; larger values are rejected with 422 (default: 1000)
+    #[serde(default = "default_max_page_size")]
+    pub max_page_size: i64,
+    /// Keys the mux key endpoints return when the request sets no `limit`, so an
+    /// unpaged request stays bounded (default: 10000)
+    #[serde(default = "default_keys_page_size")]
+    pub keys_page_size: i64,
+    /// Imports of more items than this run as a background job answered with 202 (default: 1000)
+    #[serde(default = "default_async_job_threshold")]
+    pub async_job_threshold: usize,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            max_page_size: default_max_page_size(),
+            keys_page_size: default_keys_page_size(),
+            async_job_threshold: default_async_job_threshold(),
+        }
+    }
+}
+
+fn default_max_page_size() -> i64 {
+    1000
+}
+
+fn default_keys_page_size() -> i64 {
+    10_000
+}
+
+fn default_async_job_threshold() -> usize {
+    1000
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct ExportStorageConfig {
+    /// S3 API endpoint, e.g. `https://s3.eu-west-1.amazonaws.com` or `http://minio:9000`
+    pub endpoint: url::Url,
+    pub bucket: String,
+    /// Signing region (default: "us-east-1", which most S3-compatible stores accept)
+    #[serde(default = "default_export_storage_region")]
+    pub region: String,
+    /// Prepended to every object key, e.g. `fee-manager/prod/` (default: none)
+    #[serde(default)]
+    pub prefix: String,
+    pub access_key_id: String,
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// File to read secret_access_key from (e.g. a mounted secret)
+    #[serde(default)]
+    pub secret_access_key_file: Option<String>,
+    /// Address the bucket as `endpoint/bucket` instead of `bucket.endpoint`, as MinIO needs (default: false)
+    #[serde(default)]
+    pub path_style: bool,
+    /// Timeout of each request to the store in seconds (default: 30)
+    #[serde(default = "default_export_storage_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_export_storage_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_export_storage_timeout_secs() -> u64 {
+    30
+}
+
+/// Background job writing a snapshot of the Vouch configuration to
+/// `export_storage` on a cron schedule
+#[derive(Clone, Deserialize, Debug)]
+pub struct BackupsConfig {
+    /// Run the backup job (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cron expression in UTC: minute, hour, day of month, month, day of week (default: "0 3 * * *")
+    #[serde(default = "default_backups_schedule")]
+    pub schedule: String,
+    #[serde(default)]
+    pub retention: BackupRetentionConfig,
+}
+
+impl Default for BackupsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: default_backups_schedule(),
+            retention: BackupRetentionConfig::default(),
+        }
+    }
+}
+
+fn default_backups_schedule() -> String {
+    "0 3 * * *".to_string()
+}
+
+/// Which snapshots survive a backup run; the others are deleted from the bucket
+#[derive(Clone, Deserialize, Debug)]
+pub struct BackupRetentionConfig {
+    /// Keep this many newest snapshots (0 keeps any number, default: 7)
+    #[serde(default = "default_backups_keep_last")]
+    pub keep_last: u32,
+    /// Delete snapshots older than this many days (0 keeps them, default: 30)
+    #[serde(default = "default_backups_max_age_days")]
+    pub max_age_days: u32,
+}
+
+impl Default for BackupRetentionConfig {
+    fn default() -> Self {
+        Self {
+            keep_last: default_backups_keep_last(),
+            max_age_days: default_backups_max_age_days(),
+        }
+    }
+}
+
+fn default_backups_keep_last() -> u32 {
+    7
+}
+
+fn default_backups_max_age_days() -> u32 {
+    30
+}
+
+/// Consistency check run before serving; `POST /api/admin/consistency-check`
+/// runs it on demand regardless
 #[derive(Clone, Deserialize, Debug, Default)]
+pub struct ConsistencyCheckConfig {
+    /// Check the database on startup and log the findings (default: false)
+    #[serde(default)]
+    pub on_startup: bool,
+    /// Repair the fixable findings of the startup check (default: false)
+    #[serde(default)]
+    pub fix: bool,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct AuditRotationConfig {
+    /// Rotate when the file would exceed this size in MiB (0 disables, default: 100)
+    #[serde(default = "default_audit_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Rotate when the file is older than this many hours (0 disables, default: 0)
+    #[serde(default)]
+    pub max_age_hours: u64,
+    /// Number of rotated files to keep (default: 10)
+    #[serde(default = "default_audit_retention")]
+    pub retention: usize,
+}
+
+impl Default for AuditRotationConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: default_audit_max_size_mb(),
+            max_age_hours: 0,
+            retention: default_audit_retention(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct ListenConfig {
+    /// Serve on host:port over TCP (default: true)
+    #[serde(default = "default_listen_tcp")]
+    pub tcp: bool,
+    /// Also serve on this unix domain socket, e.g. for a reverse proxy on the same host
+    #[serde(default)]
+    pub unix_socket_path: Option<String>,
+    /// Serve `/api/admin`, Swagger and the admin UI on a separate listener instead
+    #[serde(default)]
+    pub admin: Option<AdminListenConfig>,
+    /// Proxies and load balancers whose `X-Forwarded-For` header is believed, as IPs or CIDRs
+    #[serde(default)]
+    pub trusted_proxies: TrustedProxies,
+}
+
+impl Default for ListenConfig {
+    fn default() -> Self {
+        Self {
+            tcp: default_listen_tcp(),
+            unix_socket_path: None,
+            admin: None,
+            trusted_proxies: TrustedProxies::default(),
+        }
+    }
+}
+
+fn default_listen_tcp() -> bool {
+    true
+}
+
+/// Networks allowed to report the client address; a bare IP is a single-host network
+#[derive(Clone, Deserialize, Debug, Default)]
+#[serde(try_from = "Vec<String>")]
+pub struct TrustedProxies(Vec<IpNet>);
+
+impl TryFrom<Vec<String>> for TrustedProxies {
+    type Error = String;
+
+    fn try_from(entries: Vec<String>) -> Result<Self, Self::Error> {
+        entries
+            .iter()
+            .map(|entry| {
+                let entry = entry.trim();
+                entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| format!("'{}' is not an IP address or CIDR", entry))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl TrustedProxies {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Address of the client behind `peer`. The header is only believed when `peer` is
+    /// trusted, and is read right to left up to the first hop that is not a trusted proxy,
+    /// since everything before that hop could have been sent by the client itself.
+    pub fn client_ip(&self, peer: IpAddr, forwarded_for: Option<&str>) -> IpAddr {
+        let Some(forwarded_for) = forwarded_for else {
+            return peer;
+        };
+        let mut client = peer;
+        for hop in forwarded_for.rsplit(',') {
+            if !self.contains(client) {
+                break;
+            }
+            match parse_forwarded_hop(hop.trim()) {
+                Some(ip) => client = ip,
+                None => break,
+            }
+        }
+        client
+    }
+}
+
+/// One `X-Forwarded-For` entry: an IP, optionally with a port (`[::1]:80` for IPv6)
+fn parse_forwarded_hop(hop: &str) -> Option<IpAddr> {
+    hop.parse::<IpAddr>()
+        .ok()
+        .or_else(|| hop.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct AdminListenConfig {
+    /// Admin listen address, usually an internal interface (default: "127.0.0.1")
+    #[serde(default = "default_admin_host")]
+    pub host: String,
+    /// Admin listen port
+    pub port: u16,
+}
+
+impl AdminListenConfig {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+fn default_admin_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+/// What `AuditEvent::log` does when the audit queue is full
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditQueuePolicy {
+    /// Discard the event and report the count as a warning (default)
+    #[default]
+    Drop,
+    /// Wait for room, slowing requests down to the speed of the audit output
+    Block,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct AuditQueueConfig {
+    /// Events buffered ahead of the writer (default: 1024)
+    #[serde(default = "default_audit_queue_capacity")]
+    pub capacity: usize,
+    /// Policy when the queue is full: "drop" (default) or "block"
+    #[serde(default)]
+    pub when_full: AuditQueuePolicy,
+}
+
+impl Default for AuditQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_audit_queue_capacity(),
+            when_full: AuditQueuePolicy::default(),
+        }
+    }
+}
+
+fn default_audit_queue_capacity() -> usize {
+    1024
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct PublicConcurrencyConfig {
+    /// Public requests served at once; 0 disables the limit (default: 0)
+    #[serde(default)]
+    pub max_in_flight: usize,
+    /// Requests waiting for a slot before new ones are shed right away (default: 100)
+    #[serde(default = "default_public_max_queued")]
+    pub max_queued: usize,
+    /// How long a request waits for a slot before it is shed (default: 2000)
+    #[serde(default = "default_public_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+}
+
+impl Default for PublicConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 0,
+            max_queued: default_public_max_queued(),
+            queue_timeout_ms: default_public_queue_timeout_ms(),
+        }
+    }
+}
+
+fn default_public_max_queued() -> usize {
+    100
+}
+
+fn default_public_queue_timeout_ms() -> u64 {
+    2000
+}
+
+/// Message broker an audit sink publishes to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSinkKind {
+    Kafka,
+    Nats,
+}
+
+impl AuditSinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditSinkKind::Kafka => "kafka",
+            AuditSinkKind::Nats => "nats",
+        }
+    }
+
+    /// Cargo feature that compiles in support for this broker
+    pub fn feature(self) -> &'static str {
+        match self {
+            AuditSinkKind::Kafka => "audit-kafka",
+            AuditSinkKind::Nats => "audit-nats",
+        }
+    }
+
+    fn compiled_in(self) -> bool {
+        match self {
+            AuditSinkKind::Kafka => cfg!(feature = "audit-kafka"),
+            AuditSinkKind::Nats => cfg!(feature = "audit-nats"),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct AuditSinkConfig {
+    /// Broker to publish to: "kafka" or "nats"
+    pub kind: AuditSinkKind,
+    /// Kafka bootstrap servers or NATS server URLs
+    pub brokers: Vec<String>,
+    /// Kafka topic or NATS subject
+    pub topic: String,
+    /// SASL username (Kafka) or user name (NATS)
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password for `username`
+    #[serde(default)]
+    pub password: Option<String>,
+    /// File to read password from (e.g. a mounted secret)
+    #[serde(default)]
+    pub password_file: Option<String>,
+    /// Authentication token (NATS only)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// SASL mechanism used with `username` (Kafka only, default: "PLAIN")
+    #[serde(default = "default_audit_sink_sasl_mechanism")]
+    pub sasl_mechanism: String,
+    /// Connect over TLS (default: false)
+    #[serde(default)]
+    pub tls: bool,
+    /// Events held while the broker is slow or unreachable; newer events are dropped beyond this (default: 10000)
+    #[serde(default = "default_audit_sink_buffer")]
+    pub buffer: usize,
+    /// First delay before retrying a failed publish, doubled up to retry_max_ms (default: 100)
+    #[serde(default = "default_audit_sink_retry_initial_ms")]
+    pub retry_initial_ms: u64,
+    /// Longest delay between retries (default: 30000)
+    #[serde(default = "default_audit_sink_retry_max_ms")]
+    pub retry_max_ms: u64,
+    /// How long shutdown waits for buffered events to be published (default: 10)
+    #[serde(default = "default_audit_sink_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+}
+
+fn default_audit_sink_sasl_mechanism() -> String {
+    "PLAIN".to_string()
+}
+
+fn default_audit_sink_buffer() -> usize {
+    10_000
+}
+
+fn default_audit_sink_retry_initial_ms() -> u64 {
+    100
+}
+
+fn default_audit_sink_retry_max_ms() -> u64 {
+    30_000
+}
+
+fn default_audit_sink_shutdown_timeout_secs() -> u64 {
+    10
+}
+
+fn default_audit_max_size_mb() -> u64 {
+    100
+}
+
+fn default_audit_retention() -> usize {
+    10
+}
+
+#[derive(Clone, Deserialize, Debug)]
 pub struct AuthConfig {
     /// Enable authentication for admin routes (default: true)
     #[serde(default = "default_auth_enabled")]
     pub enabled: bool,
+    /// Scheme for newly stored token hashes: "argon2id" (default) or "hmac-sha256"
+    #[serde(default)]
+    pub hash_scheme: TokenHashScheme,
+    /// Server-side secret mixed into token hashes (required for "hmac-sha256")
+    #[serde(default)]
+    pub token_pepper: Option<String>,
+    /// File to read token_pepper from (e.g. a mounted secret)
+    #[serde(default)]
+    pub token_pepper_file: Option<String>,
+    /// Temporary lockout after repeated authentication failures
+    #[serde(default)]
+    pub lockout: LockoutConfig,
+    /// HMAC request signing for the public Vouch and Commit-Boost endpoints
+    #[serde(default)]
+    pub public_signing: PublicSigningConfig,
+    /// Accept JWTs from an OIDC provider alongside static tokens
+    #[serde(default)]
+    pub oidc: OidcConfig,
+    /// Per-token request counts by route, for `GET /api/admin/tokens/{id}/usage`
+    #[serde(default)]
+    pub usage: TokenUsageConfig,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_auth_enabled(),
+            hash_scheme: TokenHashScheme::default(),
+            token_pepper: None,
+            token_pepper_file: None,
+            lockout: LockoutConfig::default(),
+            public_signing: PublicSigningConfig::default(),
+            oidc: OidcConfig::default(),
+            usage: TokenUsageConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct LockoutConfig {
+    /// Failed attempts from one client IP before lockout (0 disables, default: 20)
+    #[serde(default = "default_max_failures_per_ip")]
+    pub max_failures_per_ip: u32,
+    /// Failed attempts against one token prefix before lockout (0 disables, default: 5)
+    #[serde(default = "default_max_failures_per_token")]
+    pub max_failures_per_token: u32,
+    /// Window in seconds in which failures are counted (default: 300)
+    #[serde(default = "default_lockout_window_secs")]
+    pub window_secs: u64,
+    /// Lockout duration in seconds (default: 900)
+    #[serde(default = "default_lockout_secs")]
+    pub lockout_secs: u64,
+}
+
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self {
+            max_failures_per_ip: default_max_failures_per_ip(),
+            max_failures_per_token: default_max_failures_per_token(),
+            window_secs: default_lockout_window_secs(),
+            lockout_secs: default_lockout_secs(),
+        }
+    }
+}
+
+fn default_max_failures_per_ip() -> u32 {
+    20
+}
+
+fn default_max_failures_per_token() -> u32 {
+    5
+}
+
+fn default_lockout_window_secs() -> u64 {
+    300
+}
+
+fn default_lockout_secs() -> u64 {
+    900
 }
 
 fn default_auth_enabled() -> bool {
     true
 }
 
+#[derive(Clone, Deserialize, Debug)]
+pub struct TokenUsageConfig {
+    /// Record which routes each token calls (default: true)
+    #[serde(default = "default_token_usage_enabled")]
+    pub enabled: bool,
+    /// Fraction of requests recorded, each counting for 1/sample_rate requests (default: 1.0)
+    #[serde(default = "default_token_usage_sample_rate")]
+    pub sample_rate: f64,
+}
+
+impl Default for TokenUsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_token_usage_enabled(),
+            sample_rate: default_token_usage_sample_rate(),
+        }
+    }
+}
+
+fn default_token_usage_enabled() -> bool {
+    true
+}
+
+fn default_token_usage_sample_rate() -> f64 {
+    1.0
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct PublicSigningConfig {
+    /// Require signed requests on the public endpoints (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Accepted difference between the signed timestamp and server time (default: 300)
+    #[serde(default = "default_max_skew_secs")]
+    pub max_skew_secs: u64,
+    /// Largest body buffered to verify its signature; larger ones get 413.
+    /// Raise it for signed text/plain or multipart key uploads (default: 2 MiB)
+    #[serde(default = "default_max_signed_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Consumers allowed to call the public endpoints, each with its own secret
+    #[serde(default)]
+    pub consumers: Vec<SigningConsumer>,
+}
+
+impl Default for PublicSigningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_skew_secs: default_max_skew_secs(),
+            max_body_bytes: default_max_signed_body_bytes(),
+            consumers: Vec::new(),
+        }
+    }
+}
+
+fn default_max_skew_secs() -> u64 {
+    300
+}
+
+fn default_max_signed_body_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct OidcConfig {
+    /// Validate Bearer JWTs issued by `issuer` (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Expected `iss` claim; also used to discover the JWKS URL
+    #[serde(default)]
+    pub issuer: String,
+    /// Expected `aud` claim
+    #[serde(default)]
+    pub audience: String,
+    /// JWKS URL (default: `jwks_uri` from the issuer's openid-configuration)
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+    /// Seconds before cached signing keys are fetched again (default: 3600)
+    #[serde(default = "default_jwks_refresh_secs")]
+    pub jwks_refresh_secs: u64,
+    /// Allowed clock skew for `exp` and `nbf` in seconds (default: 60)
+    #[serde(default = "default_oidc_leeway_secs")]
+    pub leeway_secs: u64,
+    /// Claim holding the caller's scopes or groups, as a space-separated string or array (default: "scope")
+    #[serde(default = "default_scopes_claim")]
+    pub scopes_claim: String,
+    /// Claim value → fee-manager scope; a JWT needs at least one mapped scope
+    #[serde(default)]
+    pub scope_mapping: HashMap<String, String>,
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer: String::new(),
+            audience: String::new(),
+            jwks_url: None,
+            jwks_refresh_secs: default_jwks_refresh_secs(),
+            leeway_secs: default_oidc_leeway_secs(),
+            scopes_claim: default_scopes_claim(),
+            scope_mapping: HashMap::new(),
+        }
+    }
+}
+
+fn default_jwks_refresh_secs() -> u64 {
+    3600
+}
+
+fn default_oidc_leeway_secs() -> u64 {
+    60
+}
+
+fn default_scopes_claim() -> String {
+    "scope".to_string()
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct SigningConsumer {
+    /// Sent by the consumer in the X-Fee-Manager-Consumer header
+    pub name: String,
+    /// Shared HMAC-SHA256 secret
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// File to read the secret from (e.g. a mounted secret)
+    #[serde(default)]
+    pub secret_file: Option<String>,
+}
+
 impl AppConfig {
     pub fn address(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    /// Check settings that deserialize fine but would fail at startup.
+    /// All problems are collected so they can be fixed in one go.
+    pub fn validate(&self) -> Result<(), ConfigErrors> {
+        let mut errors = Vec::new();
+
+        if self.port == 0 {
+            errors.push("port: must be between 1 and 65535".to_string());
+        }
+        if !self.listen.tcp && self.listen.unix_socket_path.is_none() {
+            errors.push("listen: enable tcp or set unix_socket_path".to_string());
+        }
+        if let Some(path) = &self.listen.unix_socket_path {
+            if let Err(e) = check_unix_socket_path(path) {
+                errors.push(format!("listen.unix_socket_path: {}", e));
+            }
+        }
+        if self.host.trim().is_empty() {
+            errors.push("host: must not be empty".to_string());
+        }
+        if let Some(admin) = &self.listen.admin {
+            if admin.port == 0 {
+                errors.push("listen.admin.port: must be between 1 and 65535".to_string());
+            }
+            if admin.host.trim().is_empty() {
+                errors.push("listen.admin.host: must not be empty".to_string());
+            }
+            let wildcard = |host: &str| matches!(host, "0.0.0.0" | "::" | "[::]");
+            let overlaps = admin.host == self.host || wildcard(&admin.host) || wildcard(&self.host);
+            if self.listen.tcp && admin.port == self.port && overlaps {
+                errors.push(format!(
+                    "listen.admin.port: {} is already used by the main listener",
+                    admin.port
+                ));
+            }
+        }
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.log_level) {
+            errors.push(format!("log_level: invalid filter '{}': {}", self.log_level, e));
+        }
+        if !matches!(self.log_format.as_str(), "text" | "json") {
+            errors.push(format!(
+                "log_format: expected \"text\" or \"json\", got '{}'",
+                self.log_format
+            ));
+        }
+        if self.audit_enabled {
+            if let Err(e) = check_audit_output(&self.audit_output) {
+                errors.push(format!("audit_output: {}", e));
+            }
+            if self.audit_queue.capacity == 0 {
+                errors.push("audit_queue.capacity: must be greater than 0".to_string());
+            }
+            if let Some(sink) = &self.audit_sink {
+                check_audit_sink(sink, &mut errors);
+            }
+        }
+        if let Err(e) = TokenHasher::new(self.auth.hash_scheme, self.auth.token_pepper.as_deref()) {
+            errors.push(e);
+        }
+        let sample_rate = self.auth.usage.sample_rate;
+        if sample_rate.is_nan() || sample_rate <= 0.0 || sample_rate > 1.0 {
+            errors.push(format!("auth.usage.sample_rate: {} is not in (0, 1]", sample_rate));
+        }
+        if self.auth.lockout.window_secs == 0 {
+            errors.push("auth.lockout.window_secs: must be greater than 0".to_string());
+        }
+        let signing = &self.auth.public_signing;
+        if signing.enabled {
+            if signing.consumers.is_empty() {
+                errors.push("auth.public_signing.consumers: at least one consumer is required".to_string());
+            }
+            if signing.max_skew_secs == 0 {
+                errors.push("auth.public_signing.max_skew_secs: must be greater than 0".to_string());
+            }
+            if signing.max_body_bytes == 0 {
+                errors.push("auth.public_signing.max_body_bytes: must be greater than 0".to_string());
+            }
+        }
+        let oidc = &self.auth.oidc;
+        if oidc.enabled {
+            if oidc.issuer.trim().is_empty() {
+                errors.push("auth.oidc.issuer: must not be empty".to_string());
+            }
+            if oidc.audience.trim().is_empty() {
+                errors.push("auth.oidc.audience: must not be empty".to_string());
+            }
+            if let Some(url) = &oidc.jwks_url {
+                if let Err(e) = url::Url::parse(url) {
+                    errors.push(format!("auth.oidc.jwks_url: {}", e));
+                }
+            } else if !oidc.issuer.trim().is_empty() {
+                if let Err(e) = url::Url::parse(&oidc.issuer) {
+                    errors.push(format!("auth.oidc.issuer: set jwks_url or use a URL issuer: {}", e));
+                }
+            }
+            for scope in oidc.scope_mapping.values() {
+                if !KNOWN_SCOPES.contains(&scope.as_str()) {
+                    errors.push(format!(
+                        "auth.oidc.scope_mapping: unknown scope '{}', expected one of: {}",
+                        scope,
+                        KNOWN_SCOPES.join(", ")
+                    ));
+                }
+            }
+            if !oidc.scope_mapping.values().any(|scope| scope == ADMIN_SCOPE) {
+                errors.push(format!(
+                    "auth.oidc.scope_mapping: map at least one claim value to '{}'",
+                    ADMIN_SCOPE
+                ));
+            }
+        }
+        let mut consumer_names = std::collections::HashSet::new();
+        for consumer in &signing.consumers {
+            if consumer.name.trim().is_empty() {
+                errors.push("auth.public_signing.consumers: name must not be empty".to_string());
+            } else if !consumer_names.insert(consumer.name.as_str()) {
+                errors.push(format!(
+                    "auth.public_signing.consumers: duplicate name '{}'",
+                    consumer.name
+                ));
+            }
+            if consumer.secret.as_deref().is_none_or(str::is_empty) {
+                errors.push(format!(
+                    "auth.public_signing.consumers: '{}' needs a secret or secret_file",
+                    consumer.name
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.validation.gas_limit_min, self.validation.gas_limit_max) {
+            if min > max {
+                errors.push(format!(
+                    "validation.gas_limit_min: {} is greater than gas_limit_max {}",
+                    min, max
+                ));
+            }
+        }
+        for relay in &self.validation.relay_keys {
+            if let Err(e) = url::Url::parse(&relay.url) {
+                errors.push(format!("validation.relay_keys: '{}': {}", relay.url, e));
+            }
+        }
+        if self.validation.require_known_relays && self.validation.relay_keys.is_empty() {
+            errors.push("validation.require_known_relays: needs relays listed in validation.relay_keys".to_string());
+        }
+        if self.api.max_page_size < 1 {
+            errors.push("api.max_page_size: must be greater than 0".to_string());
+        }
+        if self.api.keys_page_size < 1 {
+            errors.push("api.keys_page_size: must be greater than 0".to_string());
+        }
+        if self.api.async_job_threshold == 0 {
+            errors.push("api.async_job_threshold: must be greater than 0".to_string());
+        }
+        if let Some(storage) = &self.export_storage {
+            check_export_storage(storage, &mut errors);
+        }
+        if let Err(e) = CronSchedule::parse(&self.backups.schedule) {
+            errors.push(format!("backups.schedule: {}", e));
+        }
+        if self.backups.enabled && self.export_storage.is_none() {
+            errors.push("backups.enabled: needs export_storage to write the snapshots to".to_string());
+        }
+        let registrations = &self.vouch.registrations;
+        if registrations.enabled && registrations.interval_secs == 0 {
+            errors.push("vouch.registrations.interval_secs: must be greater than 0".to_string());
+        }
+        if registrations.timeout_secs == 0 {
+            errors.push("vouch.registrations.timeout_secs: must be greater than 0".to_string());
+        }
+        for relay in &registrations.relays {
+            if let Err(e) = url::Url::parse(relay) {
+                errors.push(format!("vouch.registrations.relays: '{}': {}", relay, e));
+            }
+        }
+        let cleanup = &self.vouch.cleanup;
+        if cleanup.enabled {
+            if cleanup.interval_secs == 0 {
+                errors.push("vouch.cleanup.interval_secs: must be greater than 0".to_string());
+            }
+            if cleanup.inactive_days == 0 {
+                errors.push("vouch.cleanup.inactive_days: must be greater than 0".to_string());
+            }
+            if !self.vouch.track_usage {
+                errors.push("vouch.cleanup.enabled: needs vouch.track_usage to see which proposers are requested".to_string());
+            }
+        }
+        let pattern_matches = &self.vouch.pattern_matches;
+        if pattern_matches.enabled && pattern_matches.interval_secs == 0 {
+            errors.push("vouch.pattern_matches.interval_secs: must be greater than 0".to_string());
+        }
+        if let Some(url) = &self.database.url {
+            match url::Url::parse(url) {
+                Ok(u) if matches!(u.scheme(), "postgres" | "postgresql") => {}
+                Ok(u) => errors.push(format!(
+                    "database.url: expected a postgres:// URL, got scheme '{}'",
+                    u.scheme()
+                )),
+                Err(e) => errors.push(format!("database.url: {}", e)),
+            }
+        } else {
+            if self.database.port == 0 {
+                errors.push("database.port: must be between 1 and 65535".to_string());
+            }
+            if self.database.host.trim().is_empty() {
+                errors.push("database.host: must not be empty".to_string());
+            }
+            if self.database.dbname.trim().is_empty() {
+                errors.push("database.dbname: must not be empty".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(errors))
+        }
+    }
+}
+
+/// Problems found while validating the configuration
+#[derive(Debug)]
+pub struct ConfigErrors(pub Vec<String>);
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration:")?;
+        for error in &self.0 {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// The socket's directory must exist, and the path must be free or a stale socket
+fn check_unix_socket_path(path: &str) -> Result<(), String> {
+    if !cfg!(unix) {
+        return Err("unix sockets are not supported on this platform".to_string());
+    }
+    let path = Path::new(path);
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() && !dir.is_dir() => {
+            return Err(format!("directory '{}' does not exist", dir.display()));
+        }
+        _ => {}
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(format!("'{}' exists and is not a socket", path.display()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_audit_sink(sink: &AuditSinkConfig, errors: &mut Vec<String>) {
+    if !sink.kind.compiled_in() {
+        errors.push(format!(
+            "audit_sink.kind: this build has no {:?} support, rebuild with `--features {}`",
+            sink.kind,
+            sink.kind.feature()
+        ));
+    }
+    if sink.brokers.iter().all(|b| b.trim().is_empty()) {
+        errors.push("audit_sink.brokers: at least one broker is required".to_string());
+    }
+    if sink.topic.trim().is_empty() {
+        errors.push("audit_sink.topic: must not be empty".to_string());
+    }
+    if sink.username.is_some() != sink.password.is_some() {
+        errors.push("audit_sink.password: username and password must be set together".to_string());
+    }
+    if sink.token.is_some() && sink.kind != AuditSinkKind::Nats {
+        errors.push("audit_sink.token: only supported for nats".to_string());
+    }
+    if sink.buffer == 0 {
+        errors.push("audit_sink.buffer: must be greater than 0".to_string());
+    }
+    if sink.retry_initial_ms == 0 || sink.retry_initial_ms > sink.retry_max_ms {
+        errors.push("audit_sink.retry_initial_ms: must be between 1 and retry_max_ms".to_string());
+    }
+}
+
+fn check_export_storage(storage: &ExportStorageConfig, errors: &mut Vec<String>) {
+    if !matches!(storage.endpoint.scheme(), "http" | "https") || storage.endpoint.host_str().is_none() {
+        errors.push("export_storage.endpoint: must be an http(s) URL with a host".to_string());
+    }
+    if storage.bucket.trim().is_empty() {
+        errors.push("export_storage.bucket: must not be empty".to_string());
+    }
+    if storage.access_key_id.trim().is_empty() {
+        errors.push("export_storage.access_key_id: must not be empty".to_string());
+    }
+    if storage.secret_access_key.is_none() {
+        errors.push("export_storage.secret_access_key: needs a secret_access_key or secret_access_key_file".to_string());
+    }
+    if storage.timeout_secs == 0 {
+        errors.push("export_storage.timeout_secs: must be greater than 0".to_string());
+    }
+}
+
+/// Audit output must be stdout, stderr, or a file we can append to
+fn check_audit_output(output: &str) -> Result<(), String> {
+    if matches!(output, "stdout" | "stderr") {
+        return Ok(());
+    }
+    let path = Path::new(output);
+    if path.exists() {
+        return OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map(|_| ())
+            .map_err(|e| format!("cannot write to '{}': {}", output, e));
+    }
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    match parent.metadata() {
+        Ok(meta) if !meta.is_dir() => Err(format!("'{}' is not a directory", parent.display())),
+        Ok(meta) if meta.permissions().readonly() => {
+            Err(format!("directory '{}' is read-only", parent.display()))
+        }
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("cannot access directory '{}': {}", parent.display(), e)),
+    }
 }
 
 #[derive(Clone, Deserialize, Debug)]
+#[serde(default)]
 pub struct DatabaseConfig {
+    /// Full connection URL; takes precedence over the individual fields
+    /// (also read from `DATABASE_URL`)
+    pub url: Option<String>,
+    /// File to read url from
+    pub url_file: Option<String>,
     pub host: String,
     pub port: u16,
     pub username: String,
     pub password: String,
+    /// File to read password from (e.g. a mounted secret)
+    pub password_file: Option<String>,
     pub dbname: String,
+    /// Connecting through PgBouncer in transaction pooling mode: no statement cache
+    /// and no session-level migration lock (default: false)
+    pub pgbouncer_mode: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            url_file: None,
+            host: "localhost".to_string(),
+            port: 5432,
+            username: "postgres".to_string(),
+            password: String::new(),
+            password_file: None,
+            dbname: "fee_manager".to_string(),
+            pgbouncer_mode: false,
+        }
+    }
 }
 
 impl DatabaseConfig {
     pub fn database_url(&self) -> String {
+        if let Some(url) = &self.url {
+            return url.clone();
+        }
         format!(
             "postgres://{}:{}@{}:{}/{}",
             self.username, self.password, self.host, self.port, self.dbname
         )
     }
+
+    /// Connection options for every pool the service opens. In `pgbouncer_mode` the
+    /// statement cache is off, so a statement prepared on one server connection is
+    /// never executed again after PgBouncer moved the client to another.
+    pub fn connect_options(&self) -> Result<PgConnectOptions, sqlx::Error> {
+        let options: PgConnectOptions = self.database_url().parse()?;
+        Ok(if self.pgbouncer_mode {
+            options.statement_cache_capacity(0)
+        } else {
+            options
+        })
+    }
+}
+
+/// Try to connect to the configured database
+pub async fn check_database(config: &DatabaseConfig) -> Result<(), sqlx::Error> {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect_with(config.connect_options()?)
+        .await?;
+    sqlx::query("SELECT 1").execute(&pool).await?;
+    pool.close().await;
+    Ok(())
 }
 
+/// Load `config.yaml` (optional) with `FEE_MANAGER_*` environment overrides.
+/// Nested keys use `__`, e.g. `FEE_MANAGER_DATABASE__HOST`.
 pub fn load_config() -> Result<AppConfig, config::ConfigError> {
-    let config = Config::builder()
-        .add_source(File::with_name("config.yaml"))
-        .add_source(Environment::with_prefix("FEE_MANAGER"))
-        .build()?;
+    load_config_from("config.yaml", None)
+}
+
+/// `env` replaces the process environment when set
+fn load_config_from(
+    path: &str,
+    env: Option<HashMap<String, String>>,
+) -> Result<AppConfig, config::ConfigError> {
+    let database_url = match &env {
+        Some(vars) => vars.get("DATABASE_URL").cloned(),
+        None => std::env::var("DATABASE_URL").ok(),
+    };
+
+    let mut builder = Config::builder()
+        .add_source(File::with_name(path).required(false))
+        .add_source(
+            Environment::with_prefix("FEE_MANAGER")
+                .prefix_separator("_")
+                .separator("__")
+                .source(env),
+        );
+    if let Some(url) = database_url.filter(|url| !url.is_empty()) {
+        builder = builder.set_override("database.url", url)?;
+    }
+
+    let mut config: AppConfig = builder.build()?.try_deserialize()?;
+    config.load_secret_files()?;
+    Ok(config)
+}
+
+impl AppConfig {
+    /// Replace `*_file` settings with the contents of the referenced files
+    fn load_secret_files(&mut self) -> Result<(), config::ConfigError> {
+        if let Some(path) = &self.database.password_file {
+            if !self.database.password.is_empty() {
+                return Err(both_set("database.password"));
+            }
+            self.database.password = read_secret_file("database.password_file", path)?;
+        }
+        if let Some(path) = &self.database.url_file {
+            if self.database.url.is_some() {
+                return Err(both_set("database.url"));
+            }
+            self.database.url = Some(read_secret_file("database.url_file", path)?);
+        }
+        if let Some(path) = &self.auth.token_pepper_file {
+            if self.auth.token_pepper.is_some() {
+                return Err(both_set("auth.token_pepper"));
+            }
+            self.auth.token_pepper = Some(read_secret_file("auth.token_pepper_file", path)?);
+        }
+        if let Some(sink) = &mut self.audit_sink {
+            if let Some(path) = &sink.password_file {
+                if sink.password.is_some() {
+                    return Err(both_set("audit_sink.password"));
+                }
+                sink.password = Some(read_secret_file("audit_sink.password_file", path)?);
+            }
+        }
+        if let Some(storage) = &mut self.export_storage {
+            if let Some(path) = &storage.secret_access_key_file {
+                if storage.secret_access_key.is_some() {
+                    return Err(both_set("export_storage.secret_access_key"));
+                }
+                storage.secret_access_key = Some(read_secret_file("export_storage.secret_access_key_file", path)?);
+            }
+        }
+        for consumer in &mut self.auth.public_signing.consumers {
+            if let Some(path) = &consumer.secret_file {
+                if consumer.secret.is_some() {
+                    return Err(both_set("auth.public_signing.consumers[].secret"));
+                }
+                consumer.secret = Some(read_secret_file("auth.public_signing.consumers[].secret_file", path)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn both_set(key: &str) -> config::ConfigError {
+    config::ConfigError::Message(format!("set either {key} or {key}_file, not both"))
+}
+
+/// Read a secret, ignoring the trailing newline most secret mounts add
+fn read_secret_file(key: &str, path: &str) -> Result<String, config::ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        config::ConfigError::Message(format!("{}: cannot read '{}': {}", key, path, e))
+    })?;
+    let secret = contents.trim_end_matches(['\n', '\r']);
+    if secret.is_empty() {
+        return Err(config::ConfigError::Message(format!("{}: '{}' is empty", key, path)));
+    }
+    Ok(secret.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::FileFormat;
+
+    fn parse(yaml: &str) -> AppConfig {
+        Config::builder()
+            .add_source(File::from_str(yaml, FileFormat::Yaml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    const VALID: &str = r#"
+host: 0.0.0.0
+port: 3000
+log_level: info
+audit_output: stderr
+auth: {}
+database: { host: localhost, port: 5432, username: u, password: p, dbname: db }
+"#;
+
+    #[test]
+    fn valid_config_passes() {
+        assert!(parse(VALID).validate().is_ok());
+    }
+
+    #[test]
+    fn errors_are_aggregated() {
+        let yaml = VALID
+            .replace("port: 3000", "port: 0")
+            .replace("log_level: info", "log_level: \"info,[bad\"")
+            .replace("audit_output: stderr", "audit_output: /nonexistent-dir/audit.log")
+            .replace("auth: {}", "auth: { hash_scheme: hmac-sha256 }");
+
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 4, "{}", errors);
+        assert!(errors.0[0].starts_with("port:"));
+        assert!(errors.0[1].starts_with("log_level:"));
+        assert!(errors.0[2].starts_with("audit_output:"));
+        assert!(errors.0[3].contains("token_pepper"));
+    }
+
+    #[test]
+    fn admin_listener_needs_its_own_port() {
+        let yaml = format!("{}listen: {{ admin: {{ port: 3000 }} }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("listen.admin.port:"), "{}", errors);
+
+        let yaml = format!("{}listen: {{ admin: {{ port: 9000 }} }}\n", VALID);
+        let config = parse(&yaml);
+        config.validate().unwrap();
+        assert_eq!(config.listen.admin.unwrap().address(), "127.0.0.1:9000");
+    }
+
+    #[test]
+    fn audit_sink_needs_brokers_topic_and_feature() {
+        let yaml = format!("{}audit_sink: {{ kind: nats, brokers: [], topic: \"\", username: u }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        let expected = if cfg!(feature = "audit-nats") { 3 } else { 4 };
+        assert_eq!(errors.0.len(), expected, "{}", errors);
+        assert!(errors.0.iter().any(|e| e.starts_with("audit_sink.brokers:")));
+        assert!(errors.0.iter().any(|e| e.starts_with("audit_sink.topic:")));
+        assert!(errors.0.iter().any(|e| e.starts_with("audit_sink.password:")));
+
+        let yaml = format!(
+            "{}audit_sink: {{ kind: kafka, brokers: [\"kafka:9092\"], topic: audit }}\n",
+            VALID
+        );
+        let config = parse(&yaml);
+        assert_eq!(config.validate().is_ok(), cfg!(feature = "audit-kafka"));
+        let sink = config.audit_sink.unwrap();
+        assert_eq!(sink.buffer, 10_000);
+        assert_eq!(sink.sasl_mechanism, "PLAIN");
+    }
+
+    #[test]
+    fn public_signing_needs_consumers_with_secrets() {
+        let yaml = VALID.replace("auth: {}", "auth: { public_signing: { enabled: true } }");
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("auth.public_signing.consumers:"), "{}", errors);
+
+        let yaml = VALID.replace(
+            "auth: {}",
+            "auth: { public_signing: { enabled: true, consumers: [{ name: a, secret: s }, { name: a }] } }",
+        );
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 2, "{}", errors);
+
+        let yaml = VALID.replace(
+            "auth: {}",
+            "auth: { public_signing: { enabled: true, consumers: [{ name: a, secret: s }] } }",
+        );
+        parse(&yaml).validate().unwrap();
+
+        let yaml = VALID.replace(
+            "auth: {}",
+            "auth: { public_signing: { enabled: true, max_body_bytes: 0, consumers: [{ name: a, secret: s }] } }",
+        );
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("auth.public_signing.max_body_bytes:"), "{}", errors);
+    }
+
+    #[test]
+    fn oidc_needs_issuer_audience_and_admin_mapping() {
+        let yaml = VALID.replace("auth: {}", "auth: { oidc: { enabled: true } }");
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 3, "{}", errors);
+
+        let yaml = VALID.replace(
+            "auth: {}",
+            "auth: { oidc: { enabled: true, issuer: https://sso.example.com, audience: fee-manager, scope_mapping: { ops: root } } }",
+        );
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("auth.oidc.scope_mapping: unknown scope"), "{}", errors);
+
+        let yaml = VALID.replace(
+            "auth: {}",
+            "auth: { oidc: { enabled: true, issuer: https://sso.example.com, audience: fee-manager, scope_mapping: { ops: admin } } }",
+        );
+        parse(&yaml).validate().unwrap();
+    }
+
+    #[test]
+    fn gas_limit_bounds_must_be_ordered() {
+        let yaml = format!("{}validation: {{ gas_limit_min: 60000000, gas_limit_max: 1000000 }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("validation.gas_limit_min:"), "{}", errors);
+
+        let yaml = format!("{}validation: {{ gas_limit_min: 1000000 }}\n", VALID);
+        parse(&yaml).validate().unwrap();
+    }
+
+    #[test]
+    fn relay_keys_need_urls() {
+        let key = format!("0x{}", "ab".repeat(48));
+        let yaml = format!(
+            "{}validation: {{ relay_keys: [{{ url: relay.example.com, public_key: '{}' }}], require_known_relays: true }}\n",
+            VALID, key
+        );
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("validation.relay_keys: 'relay.example.com':"), "{}", errors);
+
+        let errors = parse(&format!("{}validation: {{ require_known_relays: true }}\n", VALID))
+            .validate()
+            .unwrap_err();
+        assert!(errors.0[0].starts_with("validation.require_known_relays:"), "{}", errors);
+
+        let yaml = format!(
+            "{}validation: {{ relay_keys: [{{ url: 'https://relay.example.com', public_key: '{}' }}] }}\n",
+            VALID, key
+        );
+        parse(&yaml).validate().unwrap();
+    }
+
+    #[test]
+    fn max_page_size_must_be_positive() {
+        assert_eq!(parse(VALID).api.max_page_size, 1000);
+
+        let yaml = format!("{}api: {{ max_page_size: 0 }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("api.max_page_size:"), "{}", errors);
+    }
+
+    #[test]
+    fn keys_page_size_must_be_positive() {
+        assert_eq!(parse(VALID).api.keys_page_size, 10_000);
+
+        let yaml = format!("{}api: {{ keys_page_size: 0 }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("api.keys_page_size:"), "{}", errors);
+    }
+
+    #[test]
+    fn export_storage_needs_endpoint_bucket_and_secret() {
+        let yaml = format!("{}export_storage: {{ endpoint: 'ftp://files', bucket: '', access_key_id: id }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 3, "{}", errors);
+        assert!(errors.0[0].starts_with("export_storage.endpoint:"));
+        assert!(errors.0[2].starts_with("export_storage.secret_access_key:"));
+
+        let yaml = format!(
+            "{}export_storage: {{ endpoint: 'http://minio:9000', bucket: backups, access_key_id: id, secret_access_key: secret, path_style: true }}\n",
+            VALID
+        );
+        let config = parse(&yaml);
+        config.validate().unwrap();
+        assert_eq!(config.export_storage.unwrap().region, "us-east-1");
+    }
+
+    #[test]
+    fn token_usage_sample_rate_is_a_fraction() {
+        for rate in ["0", "1.5", "-0.1"] {
+            let yaml = VALID.replace("auth: {}", &format!("auth: {{ usage: {{ sample_rate: {} }} }}", rate));
+            let errors = parse(&yaml).validate().unwrap_err();
+            assert!(errors.0[0].starts_with("auth.usage.sample_rate:"), "{}", errors);
+        }
+        let yaml = VALID.replace("auth: {}", "auth: { usage: { sample_rate: 0.1 } }");
+        assert!(parse(&yaml).validate().is_ok());
+    }
+
+    #[test]
+    fn backups_need_schedule_and_storage() {
+        let yaml = format!("{}backups: {{ enabled: true, schedule: '0 25 * * *' }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 2, "{}", errors);
+        assert!(errors.0[0].starts_with("backups.schedule:"));
+        assert!(errors.0[1].starts_with("backups.enabled:"));
 
-    config.try_deserialize()
+        let config = parse(VALID);
+        assert!(!config.backups.enabled);
+        assert_eq!(config.backups.schedule, "0 3 * * *");
+        assert_eq!(config.backups.retention.keep_last, 7);
+    }
+
+    #[test]
+    fn proposer_cleanup_needs_usage_tracking() {
+        let yaml = format!("{}vouch: {{ track_usage: false, cleanup: {{ enabled: true, inactive_days: 0 }} }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert_eq!(errors.0.len(), 2, "{}", errors);
+        assert!(errors.0[0].starts_with("vouch.cleanup.inactive_days:"));
+        assert!(errors.0[1].starts_with("vouch.cleanup.enabled:"));
+
+        let yaml = format!("{}vouch: {{ cleanup: {{ enabled: true, action: delete }} }}\n", VALID);
+        let config = parse(&yaml);
+        config.validate().unwrap();
+        assert_eq!(config.vouch.cleanup.action, CleanupAction::Delete);
+        assert_eq!(config.vouch.cleanup.inactive_days, 90);
+    }
+
+    #[test]
+    fn listen_needs_a_usable_socket() {
+        let yaml = format!("{}listen: {{ tcp: false }}\n", VALID);
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("listen:"), "{}", errors);
+
+        let yaml = format!(
+            "{}listen: {{ unix_socket_path: /nonexistent-dir/fee-manager.sock }}\n",
+            VALID
+        );
+        let errors = parse(&yaml).validate().unwrap_err();
+        assert!(errors.0[0].starts_with("listen.unix_socket_path:"), "{}", errors);
+    }
+
+    #[test]
+    fn loads_from_env_without_file() {
+        let env = HashMap::from([
+            ("FEE_MANAGER_PORT".to_string(), "8080".to_string()),
+            ("FEE_MANAGER_LOG_LEVEL".to_string(), "debug".to_string()),
+            ("FEE_MANAGER_DATABASE__HOST".to_string(), "db.internal".to_string()),
+            ("FEE_MANAGER_AUTH__LOCKOUT__LOCKOUT_SECS".to_string(), "60".to_string()),
+        ]);
+        let config = load_config_from("/nonexistent/config.yaml", Some(env)).unwrap();
+
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.host, "0.0.0.0");
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.database.host, "db.internal");
+        assert_eq!(config.auth.lockout.lockout_secs, 60);
+        assert!(config.auth.enabled);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn database_url_takes_precedence() {
+        let env = HashMap::from([
+            ("DATABASE_URL".to_string(), "postgres://u:p@db:5433/fees".to_string()),
+            ("FEE_MANAGER_DATABASE__HOST".to_string(), "ignored".to_string()),
+        ]);
+        let config = load_config_from("/nonexistent/config.yaml", Some(env)).unwrap();
+
+        assert_eq!(config.database.database_url(), "postgres://u:p@db:5433/fees");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn secrets_from_files() {
+        let dir = std::env::temp_dir().join(format!("fee-manager-secrets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let password = dir.join("password");
+        let pepper = dir.join("pepper");
+        std::fs::write(&password, "s3cret\n").unwrap();
+        std::fs::write(&pepper, "pepper").unwrap();
+
+        let env = HashMap::from([
+            ("FEE_MANAGER_DATABASE__PASSWORD_FILE".to_string(), password.display().to_string()),
+            ("FEE_MANAGER_AUTH__TOKEN_PEPPER_FILE".to_string(), pepper.display().to_string()),
+        ]);
+        let config = load_config_from("/nonexistent/config.yaml", Some(env)).unwrap();
+        assert_eq!(config.database.password, "s3cret");
+        assert_eq!(config.auth.token_pepper.as_deref(), Some("pepper"));
+
+        // Inline value and file together are ambiguous
+        let env = HashMap::from([
+            ("FEE_MANAGER_AUTH__TOKEN_PEPPER".to_string(), "inline".to_string()),
+            ("FEE_MANAGER_AUTH__TOKEN_PEPPER_FILE".to_string(), pepper.display().to_string()),
+        ]);
+        let err = load_config_from("/nonexistent/config.yaml", Some(env)).unwrap_err();
+        assert!(err.to_string().contains("not both"), "{}", err);
+
+        let env = HashMap::from([(
+            "FEE_MANAGER_DATABASE__PASSWORD_FILE".to_string(),
+            dir.join("missing").display().to_string(),
+        )]);
+        let err = load_config_from("/nonexistent/config.yaml", Some(env)).unwrap_err();
+        assert!(err.to_string().contains("database.password_file"), "{}", err);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn trusted_proxies_resolve_client_ip() {
+        let config = parse(&format!(
+            "{}listen:\n  trusted_proxies: [\"10.0.0.0/8\", \"192.0.2.1\"]\n",
+            VALID
+        ));
+        let proxies = &config.listen.trusted_proxies;
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+
+        // Untrusted peers cannot claim another address
+        assert_eq!(proxies.client_ip(ip("198.51.100.9"), Some("203.0.113.7")), ip("198.51.100.9"));
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), None), ip("10.1.2.3"));
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), Some("203.0.113.7")), ip("203.0.113.7"));
+        // Trusted hops are skipped, a spoofed leftmost entry is not reached
+        assert_eq!(
+            proxies.client_ip(ip("10.1.2.3"), Some("6.6.6.6, 203.0.113.7:5123, 192.0.2.1")),
+            ip("203.0.113.7")
+        );
+        assert_eq!(proxies.client_ip(ip("192.0.2.1"), Some("[2001:db8::1]:443")), ip("2001:db8::1"));
+        // Garbage stops the walk at the last proxy
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), Some("nonsense, 10.0.0.1")), ip("10.0.0.1"));
+
+        let err = Config::builder()
+            .add_source(File::from_str(
+                &format!("{}listen:\n  trusted_proxies: [\"not-an-ip\"]\n", VALID),
+                FileFormat::Yaml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("not-an-ip"), "{}", err);
+    }
 }
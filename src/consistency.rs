@@ -0,0 +1,254 @@
+//! Database consistency checks
+//!
+//! Looks for rows the API never writes but manual database surgery can leave
+//! behind: relays and mux keys whose owner is gone, references to relay sets
+//! that do not exist, malformed or uppercase hex values, and relays listed
+//! twice under URLs differing only in case or a trailing slash. With `fix`
+//! each finding with an unambiguous repair is repaired: orphans and the older
+//! of duplicate relays are deleted, missing relay sets dropped from the list
+//! and uppercase hex lowercased. Malformed hex is only reported.
+
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::config::ConsistencyCheckConfig;
+use crate::schema::{ConsistencyFinding, ConsistencyIssue, ConsistencyReport};
+
+/// Rows belonging to another: table, owner column, owner table, owner key
+const OWNED_ROWS: &[(&str, &str, &str, &str, ConsistencyIssue)] = &[
+    ("vouch_default_relays", "config_name", "vouch_default_configs", "name", ConsistencyIssue::OrphanedRelay),
+    ("vouch_proposer_relays", "proposer_public_key", "vouch_proposers", "public_key", ConsistencyIssue::OrphanedRelay),
+    ("vouch_proposer_pattern_relays", "pattern_name", "vouch_proposer_patterns", "name", ConsistencyIssue::OrphanedRelay),
+    ("vouch_proposer_group_relays", "group_name", "vouch_proposer_groups", "name", ConsistencyIssue::OrphanedRelay),
+    ("vouch_relay_set_relays", "set_name", "vouch_relay_sets", "name", ConsistencyIssue::OrphanedRelay),
+    ("commit_boost_pbs_relays", "config_name", "commit_boost_pbs_configs", "name", ConsistencyIssue::OrphanedRelay),
+    ("commit_boost_mux_keys", "mux_name", "commit_boost_mux_configs", "name", ConsistencyIssue::MissingMux),
+];
+
+/// Vouch relay tables and the column naming the owner of each relay
+const RELAY_TABLES: &[(&str, &str)] = &[
+    ("vouch_default_relays", "config_name"),
+    ("vouch_proposer_relays", "proposer_public_key"),
+    ("vouch_proposer_pattern_relays", "pattern_name"),
+    ("vouch_proposer_group_relays", "group_name"),
+    ("vouch_relay_set_relays", "set_name"),
+];
+
+/// Tables listing relay sets by name, with their key column
+const RELAY_SET_USERS: &[(&str, &str)] = &[
+    ("vouch_default_configs", "name"),
+    ("vouch_proposers", "public_key"),
+    ("vouch_proposer_patterns", "name"),
+    ("vouch_proposer_groups", "name"),
+];
+
+const PUBLIC_KEY: &str = "^0x[0-9a-f]{96}$";
+const ADDRESS: &str = "^0x[0-9a-f]{40}$";
+
+/// Stored hex values in their canonical form: table, row key expression, column, pattern
+const HEX_COLUMNS: &[(&str, &str, &str, &str)] = &[
+    ("vouch_default_configs", "name", "fee_recipient", ADDRESS),
+    ("vouch_proposers", "public_key", "public_key", PUBLIC_KEY),
+    ("vouch_proposers", "public_key", "fee_recipient", ADDRESS),
+    ("vouch_proposer_patterns", "name", "fee_recipient", ADDRESS),
+    ("vouch_proposer_groups", "name", "fee_recipient", ADDRESS),
+    ("vouch_proposer_group_keys", "group_name || '/' || public_key", "public_key", PUBLIC_KEY),
+    ("vouch_default_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("vouch_default_relays", "id::TEXT", "fee_recipient", ADDRESS),
+    ("vouch_proposer_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("vouch_proposer_relays", "id::TEXT", "fee_recipient", ADDRESS),
+    ("vouch_proposer_pattern_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("vouch_proposer_pattern_relays", "id::TEXT", "fee_recipient", ADDRESS),
+    ("vouch_proposer_group_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("vouch_proposer_group_relays", "id::TEXT", "fee_recipient", ADDRESS),
+    ("vouch_relay_set_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("vouch_relay_set_relays", "id::TEXT", "fee_recipient", ADDRESS),
+    ("commit_boost_pbs_relays", "id::TEXT", "public_key", PUBLIC_KEY),
+    ("commit_boost_mux_keys", "id::TEXT", "public_key", PUBLIC_KEY),
+];
+
+/// Statement repairing one finding, with its text parameters
+struct Repair {
+    sql: String,
+    binds: Vec<String>,
+}
+
+type Found = (ConsistencyFinding, Option<Repair>);
+
+fn finding(issue: ConsistencyIssue, table: &str, row: String, detail: String, repair: Option<Repair>) -> Found {
+    let finding = ConsistencyFinding {
+        issue,
+        table: table.to_string(),
+        row,
+        detail,
+        fixable: repair.is_some(),
+        fixed: false,
+    };
+    (finding, repair)
+}
+
+/// Check the database and, with `fix`, repair what can be repaired
+pub async fn run(pool: &PgPool, fix: bool) -> Result<ConsistencyReport, sqlx::Error> {
+    let mut found = missing_owners(pool).await?;
+    found.extend(missing_relay_sets(pool).await?);
+    found.extend(invalid_hex(pool).await?);
+    found.extend(duplicate_urls(pool).await?);
+
+    let mut findings = Vec::with_capacity(found.len());
+    for (mut finding, repair) in found {
+        if let (true, Some(repair)) = (fix, repair) {
+            finding.fixed = apply(pool, &repair).await?;
+        }
+        findings.push(finding);
+    }
+    let fixed = findings.iter().filter(|finding| finding.fixed).count();
+    Ok(ConsistencyReport {
+        fix,
+        count: findings.len(),
+        fixed,
+        findings,
+    })
+}
+
+/// Run a repair; false when the row is already gone or fixing it would
+/// collide with another row (e.g. a proposer key also stored in lowercase)
+async fn apply(pool: &PgPool, repair: &Repair) -> Result<bool, sqlx::Error> {
+    let mut query = sqlx::query(&repair.sql);
+    for bind in &repair.binds {
+        query = query.bind(bind);
+    }
+    match query.execute(pool).await {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+async fn missing_owners(pool: &PgPool) -> Result<Vec<Found>, sqlx::Error> {
+    let mut found = Vec::new();
+    for &(table, owner_column, owner_table, owner_key, issue) in OWNED_ROWS {
+        let rows: Vec<(String, String)> = sqlx::query_as(&format!(
+            "SELECT c.id::TEXT, c.{1} FROM {0} c
+             WHERE NOT EXISTS (SELECT 1 FROM {2} o WHERE o.{3} = c.{1})
+             ORDER BY c.id",
+            table, owner_column, owner_table, owner_key
+        ))
+        .fetch_all(pool)
+        .await?;
+        for (id, owner) in rows {
+            let repair = Repair {
+                sql: format!("DELETE FROM {} WHERE id::TEXT = $1", table),
+                binds: vec![id.clone()],
+            };
+            let detail = format!("{} '{}' does not exist in {}", owner_column, owner, owner_table);
+            found.push(finding(issue, table, id, detail, Some(repair)));
+        }
+    }
+    Ok(found)
+}
+
+async fn missing_relay_sets(pool: &PgPool) -> Result<Vec<Found>, sqlx::Error> {
+    let mut found = Vec::new();
+    for &(table, key) in RELAY_SET_USERS {
+        let rows: Vec<(String, String)> = sqlx::query_as(&format!(
+            "SELECT t.{1}, s.name FROM {0} t CROSS JOIN LATERAL unnest(t.relay_sets) AS s(name)
+             WHERE NOT EXISTS (SELECT 1 FROM vouch_relay_sets r WHERE r.name = s.name)
+             ORDER BY 1, 2",
+            table, key
+        ))
+        .fetch_all(pool)
+        .await?;
+        for (row, set) in rows {
+            let repair = Repair {
+                sql: format!(
+                    "UPDATE {} SET relay_sets = array_remove(relay_sets, $2) WHERE {} = $1",
+                    table, key
+                ),
+                binds: vec![row.clone(), set.clone()],
+            };
+            let detail = format!("Relay set '{}' does not exist", set);
+            found.push(finding(ConsistencyIssue::MissingRelaySet, table, row, detail, Some(repair)));
+        }
+    }
+    Ok(found)
+}
+
+async fn invalid_hex(pool: &PgPool) -> Result<Vec<Found>, sqlx::Error> {
+    let mut found = Vec::new();
+    for &(table, key, column, pattern) in HEX_COLUMNS {
+        let rows: Vec<(String, String, bool)> = sqlx::query_as(&format!(
+            "SELECT {1}, {2}, lower({2}) ~ $1 FROM {0} WHERE {2} !~ $1 ORDER BY 1",
+            table, key, column
+        ))
+        .bind(pattern)
+        .fetch_all(pool)
+        .await?;
+        for (row, value, lowercase_is_valid) in rows {
+            let (detail, repair) = if lowercase_is_valid {
+                let repair = Repair {
+                    sql: format!("UPDATE {0} SET {2} = lower({2}) WHERE {1} = $1", table, key, column),
+                    binds: vec![row.clone()],
+                };
+                (format!("{} '{}' is not lowercase", column, value), Some(repair))
+            } else {
+                let kind = if pattern == PUBLIC_KEY { "public key" } else { "address" };
+                (format!("{} '{}' is not a valid {}", column, value, kind), None)
+            };
+            found.push(finding(ConsistencyIssue::InvalidHex, table, row, detail, repair));
+        }
+    }
+    Ok(found)
+}
+
+/// Relays of one owner whose URLs differ only in case or a trailing slash;
+/// the newest row is the one the owner was last saved with, so it stays
+async fn duplicate_urls(pool: &PgPool) -> Result<Vec<Found>, sqlx::Error> {
+    let mut found = Vec::new();
+    for &(table, owner_column) in RELAY_TABLES {
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(&format!(
+            "SELECT c.id::TEXT, c.{1}, c.url, n.url FROM {0} c
+             JOIN LATERAL (
+                 SELECT o.url FROM {0} o
+                 WHERE o.{1} = c.{1} AND o.id > c.id AND rtrim(lower(o.url), '/') = rtrim(lower(c.url), '/')
+                 ORDER BY o.id DESC LIMIT 1
+             ) n ON true
+             ORDER BY c.id",
+            table, owner_column
+        ))
+        .fetch_all(pool)
+        .await?;
+        for (id, owner, url, newer) in rows {
+            let repair = Repair {
+                sql: format!("DELETE FROM {} WHERE id::TEXT = $1", table),
+                binds: vec![id.clone()],
+            };
+            let detail = format!(
+                "'{}' repeats '{}' of {} '{}'; the newer row is kept",
+                url, newer, owner_column, owner
+            );
+            found.push(finding(ConsistencyIssue::DuplicateUrl, table, id, detail, Some(repair)));
+        }
+    }
+    Ok(found)
+}
+
+/// Check (and with `consistency_check.fix` repair) the database before
+/// serving, logging each finding
+pub async fn run_on_startup(pool: &PgPool, config: &ConsistencyCheckConfig) {
+    match run(pool, config.fix).await {
+        Ok(report) => {
+            for finding in &report.findings {
+                warn!(
+                    issue = ?finding.issue,
+                    table = %finding.table,
+                    row = %finding.row,
+                    fixed = finding.fixed,
+                    "Consistency check: {}",
+                    finding.detail
+                );
+            }
+            info!(findings = report.count, fixed = report.fixed, "Consistency check completed");
+        }
+        Err(e) => warn!("Consistency check failed: {}", e),
+    }
+}
@@ -0,0 +1,167 @@
+//! Cron schedules
+//!
+//! The classic five fields (minute, hour, day of month, month, day of week)
+//! with `*`, lists, ranges and steps, evaluated in UTC. As in cron, a day
+//! matches when either day field does if both are restricted.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Timelike, Utc};
+
+/// Days searched for the next run; Feb 29 on a given weekday recurs within 28 years
+const SEARCH_DAYS: i64 = 366 * 28;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Whether the day fields are `*`, which changes how they combine
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Parse e.g. `0 3 * * *` or `*/15 8-18 * * 1-5`
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!("'{}' needs 5 fields, got {}", expression, fields.len()));
+        };
+        let mut days_of_week = parse_field(day_of_week, "day of week", 0, 7)?;
+        // 7 is another name for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days_of_month: parse_field(day_of_month, "day of month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            days_of_week,
+            any_day_of_month: day_of_month == "*",
+            any_day_of_week: day_of_week == "*",
+        })
+    }
+
+    /// First run strictly after `after`, or `None` for a schedule that never fires (e.g. Feb 30)
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after + Duration::minutes(1);
+        let start_date = start.date_naive();
+        for offset in 0..SEARCH_DAYS {
+            let date = start_date + Duration::days(offset);
+            if !self.matches_day(date.month(), date.day(), date.weekday().num_days_from_sunday()) {
+                continue;
+            }
+            let (first_hour, first_minute) = if offset == 0 { (start.hour(), start.minute()) } else { (0, 0) };
+            for hour in first_hour..24 {
+                if !has(self.hours, hour) {
+                    continue;
+                }
+                let from = if hour == first_hour { first_minute } else { 0 };
+                if let Some(minute) = (from..60).find(|&minute| has(self.minutes, minute)) {
+                    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                    return Some(date.and_time(time).and_utc());
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, month: u32, day: u32, weekday: u32) -> bool {
+        if !has(self.months, month) {
+            return false;
+        }
+        let by_month_day = has(self.days_of_month, day);
+        let by_weekday = has(self.days_of_week, weekday);
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => by_weekday,
+            (false, true) => by_month_day,
+            (false, false) => by_month_day || by_weekday,
+        }
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Bit set of the values a field allows
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|&step| step > 0)
+                    .ok_or_else(|| format!("{}: invalid step in '{}'", name, part))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else if let Some((first, last)) = range.split_once('-') {
+            (parse_value(first, name, min, max)?, parse_value(last, name, min, max)?)
+        } else {
+            let value = parse_value(range, name, min, max)?;
+            // `5/10` runs from 5 to the end, as in Vixie cron
+            (value, if step > 1 { max } else { value })
+        };
+        if first > last {
+            return Err(format!("{}: range '{}' is backwards", name, range));
+        }
+        for value in (first..=last).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+fn parse_value(value: &str, name: &str, min: u32, max: u32) -> Result<u32, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| format!("{}: '{}' is not between {} and {}", name, value, min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn next_run_times() {
+        let nightly = CronSchedule::parse("0 3 * * *").unwrap();
+        assert_eq!(nightly.next_after(at(2025, 1, 10, 2, 59)), Some(at(2025, 1, 10, 3, 0)));
+        assert_eq!(nightly.next_after(at(2025, 1, 10, 3, 0)), Some(at(2025, 1, 11, 3, 0)));
+
+        let quarter_hours = CronSchedule::parse("*/15 8-18 * * 1-5").unwrap();
+        // Friday evening to Monday morning
+        assert_eq!(quarter_hours.next_after(at(2025, 1, 10, 18, 45)), Some(at(2025, 1, 13, 8, 0)));
+        assert_eq!(quarter_hours.next_after(at(2025, 1, 13, 8, 1)), Some(at(2025, 1, 13, 8, 15)));
+
+        // Either day field matches when both are set: the 1st, or a Sunday (7)
+        let either = CronSchedule::parse("30 0 1 * 7").unwrap();
+        assert_eq!(either.next_after(at(2025, 1, 1, 1, 0)), Some(at(2025, 1, 5, 0, 30)));
+
+        let leap_day = CronSchedule::parse("0 0 29 2 *").unwrap();
+        assert_eq!(leap_day.next_after(at(2025, 1, 1, 0, 0)), Some(at(2028, 2, 29, 0, 0)));
+        assert_eq!(CronSchedule::parse("0 0 30 2 *").unwrap().next_after(at(2025, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for expression in ["0 3 * *", "60 * * * *", "0 3 * * 8", "*/0 * * * *", "5-1 * * * *", "a * * * *"] {
+            assert!(CronSchedule::parse(expression).is_err(), "{}", expression);
+        }
+    }
+}
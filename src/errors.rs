@@ -1,7 +1,10 @@
 // errors.rs
+//! API errors rendered as RFC 7807 `application/problem+json` documents
+
 use axum::{
     body::Body,
-    http::{Response, StatusCode},
+    extract::rejection::{JsonRejection, PathRejection},
+    http::{header, HeaderValue, Response, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -9,34 +12,141 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::error;
 use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Media type of error response bodies
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+tokio::task_local! {
+    /// ID of the request being handled, set by the request ID middleware so
+    /// errors can report it without threading it through every handler
+    pub static REQUEST_ID: Uuid;
+}
+
+/// Postgres SQLSTATE for unique constraint violations
+const UNIQUE_VIOLATION: &str = "23505";
+/// Postgres SQLSTATE for foreign key violations
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// Stable, machine-readable error codes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The requested resource does not exist
+    NotFound,
+    /// The request conflicts with existing state (duplicate name, key owned elsewhere)
+    Conflict,
+    /// The request body or parameters are invalid
+    ValidationFailed,
+    /// Missing or invalid credentials
+    Unauthorized,
+    /// The credentials lack the scope the operation needs
+    Forbidden,
+    /// The requested API version is not served
+    NotAcceptable,
+    /// The request body is larger than the server buffers
+    PayloadTooLarge,
+    /// The client is temporarily locked out
+    TooManyRequests,
+    /// The server is overloaded and shed the request; retry later
+    ServiceUnavailable,
+    /// A service the request depends on (e.g. export storage) failed
+    UpstreamFailed,
+    /// Unexpected server-side failure
+    InternalError,
+}
 
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct ErrorResponse {
-    pub error: ErrorDetail,
+/// RFC 7807 problem details
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    /// Problem type URI (`about:blank`; use `code` to tell problems apart)
+    #[serde(rename = "type")]
+    #[schema(example = "about:blank")]
+    pub problem_type: String,
+    /// Short summary of the HTTP status
+    #[schema(example = "Not Found")]
+    pub title: String,
+    /// HTTP status code
+    #[schema(example = 404)]
+    pub status: u16,
+    /// Human-readable explanation of this occurrence
+    #[schema(example = "Config 'main' not found")]
+    pub detail: String,
+    /// Stable error code
+    pub code: ErrorCode,
+    /// ID of the failed request, also sent as `x-request-id`; quote it when reporting a problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<Uuid>,
 }
 
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct ErrorDetail {
-    pub code: String,
-    pub message: String,
+impl ProblemDetails {
+    pub fn new(status: StatusCode, code: ErrorCode, detail: impl Into<String>) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: detail.into(),
+            code,
+            request_id: REQUEST_ID.try_with(|id| *id).ok(),
+        }
+    }
 }
 
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response<Body> {
+        let status =
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let request_id = self.request_id;
+        let mut response = (status, Json(self)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        if let Some(id) = request_id {
+            if let Ok(value) = HeaderValue::from_str(&id.to_string()) {
+                headers.insert("x-request-id", value);
+            }
+        }
+        response
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum ApiError {
     #[error("Resource not found: {0}")]
     NotFound(String),
 
-    #[allow(dead_code)]
     #[error("Internal server error: {0}")]
     InternalError(String),
 
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Unprocessable: {0}")]
+    Unprocessable(String),
+
     #[error("Unauthorized")]
     Unauthorized,
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Not acceptable: {0}")]
+    NotAcceptable(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    #[error("Bad gateway: {0}")]
+    BadGateway(String),
+
     #[error("Database error: {0}")]
     DatabaseError(#[from] sqlx::Error),
 
@@ -44,82 +154,161 @@ pub enum ApiError {
     JsonError(#[from] serde_json::Error),
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response<Body> {
-        let (status, error_response) = match &self {
-            ApiError::NotFound(msg) => (
-                StatusCode::NOT_FOUND,
-                ErrorResponse {
-                    error: ErrorDetail {
-                        code: "NOT_FOUND".to_string(),
-                        message: msg.to_string(),
-                    },
-                },
-            ),
-            ApiError::InternalError(msg) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                ErrorResponse {
-                    error: ErrorDetail {
-                        code: "INTERNAL_ERROR".to_string(),
-                        message: msg.to_string(),
-                    },
-                },
-            ),
-            ApiError::InvalidData(msg) => (
+impl ApiError {
+    /// Problem details sent for this error (also kept for failed jobs)
+    pub fn problem(&self) -> ProblemDetails {
+        match self {
+            ApiError::NotFound(msg) => {
+                ProblemDetails::new(StatusCode::NOT_FOUND, ErrorCode::NotFound, msg.as_str())
+            }
+            ApiError::InternalError(msg) => {
+                error!("Internal error: {}", msg);
+                ProblemDetails::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ErrorCode::InternalError,
+                    msg.as_str(),
+                )
+            }
+            ApiError::InvalidData(msg) => ProblemDetails::new(
                 StatusCode::BAD_REQUEST,
-                ErrorResponse {
-                    error: ErrorDetail {
-                        code: "INVALID_DATA".to_string(),
-                        message: msg.to_string(),
-                    },
-                },
+                ErrorCode::ValidationFailed,
+                msg.as_str(),
             ),
-            ApiError::Unauthorized => (
+            ApiError::Conflict(msg) => {
+                ProblemDetails::new(StatusCode::CONFLICT, ErrorCode::Conflict, msg.as_str())
+            }
+            ApiError::Unprocessable(msg) => ProblemDetails::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCode::ValidationFailed,
+                msg.as_str(),
+            ),
+            ApiError::Unauthorized => ProblemDetails::new(
                 StatusCode::UNAUTHORIZED,
-                ErrorResponse {
-                    error: ErrorDetail {
-                        code: "UNAUTHORIZED".to_string(),
-                        message: "Authentication required".to_string(),
-                    },
-                },
+                ErrorCode::Unauthorized,
+                "Authentication required",
+            ),
+            ApiError::Forbidden(msg) => {
+                ProblemDetails::new(StatusCode::FORBIDDEN, ErrorCode::Forbidden, msg.as_str())
+            }
+            ApiError::NotAcceptable(msg) => ProblemDetails::new(
+                StatusCode::NOT_ACCEPTABLE,
+                ErrorCode::NotAcceptable,
+                msg.as_str(),
             ),
-            ApiError::DatabaseError(e) => match e {
-                sqlx::Error::RowNotFound => (
-                    StatusCode::NOT_FOUND,
-                    ErrorResponse {
-                        error: ErrorDetail {
-                            code: "NOT_FOUND".to_string(),
-                            message: "Resource not found".to_string(),
-                        },
-                    },
-                ),
-                _ => {
-                    error!("Database error: {:?}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        ErrorResponse {
-                            error: ErrorDetail {
-                                code: "DATABASE_ERROR".to_string(),
-                                message: "Internal server error".to_string(),
-                            },
-                        },
-                    )
-                }
-            },
+            ApiError::PayloadTooLarge(msg) => ProblemDetails::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ErrorCode::PayloadTooLarge,
+                msg.as_str(),
+            ),
+            ApiError::TooManyRequests(msg) => ProblemDetails::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorCode::TooManyRequests,
+                msg.as_str(),
+            ),
+            ApiError::ServiceUnavailable(msg) => ProblemDetails::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorCode::ServiceUnavailable,
+                msg.as_str(),
+            ),
+            ApiError::BadGateway(msg) => {
+                error!("Upstream failure: {}", msg);
+                ProblemDetails::new(StatusCode::BAD_GATEWAY, ErrorCode::UpstreamFailed, msg.as_str())
+            }
+            ApiError::DatabaseError(e) => database_problem(e),
             ApiError::JsonError(e) => {
                 error!("JSON error: {:?}", e);
-                (
+                ProblemDetails::new(
                     StatusCode::BAD_REQUEST,
-                    ErrorResponse {
-                        error: ErrorDetail {
-                            code: "INVALID_JSON".to_string(),
-                            message: "Invalid JSON format".to_string(),
-                        },
-                    },
+                    ErrorCode::ValidationFailed,
+                    "Invalid JSON format",
+                )
+            }
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(e) => ApiError::InvalidData(e.kind().to_string()),
+            other => ApiError::InternalError(other.body_text()),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::InvalidData(rejection.body_text())
+    }
+}
+
+fn database_problem(e: &sqlx::Error) -> ProblemDetails {
+    if let sqlx::Error::RowNotFound = e {
+        return ProblemDetails::new(StatusCode::NOT_FOUND, ErrorCode::NotFound, "Resource not found");
+    }
+    if let Some(db) = e.as_database_error() {
+        match db.code().as_deref() {
+            Some(UNIQUE_VIOLATION) => {
+                return ProblemDetails::new(
+                    StatusCode::CONFLICT,
+                    ErrorCode::Conflict,
+                    "Resource already exists",
                 )
             }
-        };
+            Some(FOREIGN_KEY_VIOLATION) => {
+                return ProblemDetails::new(
+                    StatusCode::BAD_REQUEST,
+                    ErrorCode::ValidationFailed,
+                    "Referenced resource does not exist",
+                )
+            }
+            _ => {}
+        }
+    }
+    error!("Database error: {:?}", e);
+    ProblemDetails::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        ErrorCode::InternalError,
+        "Internal server error",
+    )
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response<Body> {
+        self.problem().into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_serialize_as_screaming_snake_case() {
+        let problem = ApiError::InvalidData("bad".to_string()).problem();
+        let value = serde_json::to_value(&problem).unwrap();
+        assert_eq!(value["type"], "about:blank");
+        assert_eq!(value["title"], "Bad Request");
+        assert_eq!(value["status"], 400);
+        assert_eq!(value["detail"], "bad");
+        assert_eq!(value["code"], "VALIDATION_FAILED");
+
+        let conflict = ApiError::Conflict("dup".to_string()).problem();
+        assert_eq!(conflict.status, 409);
+        assert_eq!(serde_json::to_value(conflict.code).unwrap(), "CONFLICT");
+        assert!(value.get("request_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn request_id_is_reported_in_body_and_header() {
+        let id = Uuid::new_v4();
+        let response = REQUEST_ID
+            .scope(id, async { ApiError::NotFound("gone".to_string()).into_response() })
+            .await;
+        assert_eq!(response.headers()["x-request-id"], id.to_string().as_str());
 
-        (status, Json(error_response)).into_response()
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["request_id"], id.to_string());
     }
 }
@@ -0,0 +1,277 @@
+// handlers/access_tokens.rs - Public access tokens of default configs and mux configs
+use crate::audit::{AuditAction, AuditChanges, Change, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::auth::access::{self, AccessResource, AccessTokenInfo};
+use crate::errors::ApiError;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+/// Request body for creating an access token
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAccessTokenRequest {
+    /// Short identifier of the consumer using the token
+    pub name: String,
+}
+
+/// Response when an access token is created (includes plaintext token)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateAccessTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// The plaintext token - shown only once!
+    pub token: String,
+}
+
+/// OpenAPI paths and schemas for the access token routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        list_default_config_access_tokens,
+        create_default_config_access_token,
+        delete_default_config_access_token,
+        list_mux_access_tokens,
+        create_mux_access_token,
+        delete_mux_access_token
+    ),
+    components(schemas(AccessTokenInfo, CreateAccessTokenRequest, CreateAccessTokenResponse))
+)]
+pub struct AccessTokensApi;
+
+/// Admin routes for access tokens, next to the resources they guard (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/vouch/configs/default/{name}/access-tokens",
+            get(list_default_config_access_tokens).post(create_default_config_access_token),
+        )
+        .route(
+            "/vouch/configs/default/{name}/access-tokens/{id}",
+            delete(delete_default_config_access_token),
+        )
+        .route(
+            "/commit-boost/mux/{name}/access-tokens",
+            get(list_mux_access_tokens).post(create_mux_access_token),
+        )
+        .route("/commit-boost/mux/{name}/access-tokens/{id}", delete(delete_mux_access_token))
+}
+
+/// 404 unless the guarded resource exists
+async fn ensure_resource_exists(pool: &PgPool, resource: AccessResource, name: &str) -> Result<(), ApiError> {
+    let (sql, label) = match resource {
+        AccessResource::DefaultConfig => (
+            "SELECT EXISTS(SELECT 1 FROM vouch_default_configs WHERE name = $1)",
+            "Default config",
+        ),
+        AccessResource::Mux => (
+            "SELECT EXISTS(SELECT 1 FROM commit_boost_mux_configs WHERE name = $1)",
+            "Mux config",
+        ),
+    };
+    let exists: bool = sqlx::query_scalar(sql).bind(name).fetch_one(pool).await?;
+    if exists {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(format!("{} '{}' not found", label, name)))
+    }
+}
+
+async fn list_tokens(
+    state: &AppState,
+    resource: AccessResource,
+    name: String,
+) -> Result<Json<Vec<AccessTokenInfo>>, ApiError> {
+    info!("Listing access tokens of {} '{}'", resource.as_str(), name);
+    ensure_resource_exists(&state.pool, resource, &name).await?;
+    let tokens = access::list_access_tokens(&state.pool, resource, &name).await?;
+    Ok(Json(tokens.into_iter().map(AccessTokenInfo::from).collect()))
+}
+
+async fn create_token(
+    state: &AppState,
+    ctx: &RequestContext,
+    resource: AccessResource,
+    name: String,
+    req: CreateAccessTokenRequest,
+) -> Result<(StatusCode, Json<CreateAccessTokenResponse>), ApiError> {
+    info!("Creating access token '{}' for {} '{}'", req.name, resource.as_str(), name);
+    if req.name.trim().is_empty() {
+        return Err(ApiError::InvalidData("Token name must not be empty".to_string()));
+    }
+    ensure_resource_exists(&state.pool, resource, &name).await?;
+    let (token, plaintext) = access::create_access_token(&state.pool, resource, &name, &req.name).await?;
+
+    if state.config.audit_enabled {
+        let changes = AuditChanges {
+            name: Change::set(Some(token.name.clone())),
+            ..Default::default()
+        };
+        audit_log!(ctx, AuditAction::Create, ResourceType::PublicAccessToken, token.id.to_string(), changes);
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateAccessTokenResponse {
+            id: token.id,
+            name: token.name,
+            token: plaintext,
+        }),
+    ))
+}
+
+async fn delete_token(
+    state: &AppState,
+    ctx: &RequestContext,
+    resource: AccessResource,
+    name: String,
+    id: Uuid,
+) -> Result<StatusCode, ApiError> {
+    info!("Deleting access token {} of {} '{}'", id, resource.as_str(), name);
+    if !access::delete_access_token(&state.pool, resource, &name, id).await? {
+        return Err(ApiError::NotFound(format!("Access token {} not found", id)));
+    }
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::PublicAccessToken, id.to_string());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/access-tokens",
+    params(("name" = String, Path, description = "Default config name")),
+    responses(
+        (status = 200, description = "Access tokens guarding the config's execution config", body = Vec<AccessTokenInfo>),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_default_config_access_tokens(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<AccessTokenInfo>>, ApiError> {
+    list_tokens(&state, AccessResource::DefaultConfig, name).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/configs/default/{name}/access-tokens",
+    params(("name" = String, Path, description = "Default config name")),
+    request_body = CreateAccessTokenRequest,
+    responses(
+        (status = 201, description = "Access token created", body = CreateAccessTokenResponse),
+        (status = 400, description = "Empty token name"),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, req))]
+pub async fn create_default_config_access_token(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(req): Json<CreateAccessTokenRequest>,
+) -> Result<(StatusCode, Json<CreateAccessTokenResponse>), ApiError> {
+    create_token(&state, &ctx, AccessResource::DefaultConfig, name, req).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/vouch/configs/default/{name}/access-tokens/{id}",
+    params(
+        ("name" = String, Path, description = "Default config name"),
+        ("id" = Uuid, Path, description = "Access token ID")
+    ),
+    responses(
+        (status = 204, description = "Access token deleted"),
+        (status = 404, description = "Access token not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_default_config_access_token(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    delete_token(&state, &ctx, AccessResource::DefaultConfig, name, id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/mux/{name}/access-tokens",
+    params(("name" = String, Path, description = "Mux config name")),
+    responses(
+        (status = 200, description = "Access tokens guarding the mux's public key list", body = Vec<AccessTokenInfo>),
+        (status = 404, description = "Mux config not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_mux_access_tokens(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<AccessTokenInfo>>, ApiError> {
+    list_tokens(&state, AccessResource::Mux, name).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/commit-boost/mux/{name}/access-tokens",
+    params(("name" = String, Path, description = "Mux config name")),
+    request_body = CreateAccessTokenRequest,
+    responses(
+        (status = 201, description = "Access token created", body = CreateAccessTokenResponse),
+        (status = 400, description = "Empty token name"),
+        (status = 404, description = "Mux config not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, req))]
+pub async fn create_mux_access_token(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(req): Json<CreateAccessTokenRequest>,
+) -> Result<(StatusCode, Json<CreateAccessTokenResponse>), ApiError> {
+    create_token(&state, &ctx, AccessResource::Mux, name, req).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/commit-boost/mux/{name}/access-tokens/{id}",
+    params(
+        ("name" = String, Path, description = "Mux config name"),
+        ("id" = Uuid, Path, description = "Access token ID")
+    ),
+    responses(
+        (status = 204, description = "Access token deleted"),
+        (status = 404, description = "Access token not found")
+    ),
+    tag = "Auth",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_mux_access_token(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    delete_token(&state, &ctx, AccessResource::Mux, name, id).await
+}
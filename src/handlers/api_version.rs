@@ -0,0 +1,65 @@
+// handlers/api_version.rs - Admin API versioning
+//
+// The admin API is served under `/api/v1/admin`. The unversioned `/api/admin`
+// prefix stays as a deprecated alias of version 1, so existing clients keep
+// working while breaking changes go into a new prefix. Clients may name the
+// version they were written against in `X-Fee-Manager-Api-Version`; a prefix
+// that serves another version answers 406 instead of guessing, and every
+// response names the version that served it.
+use crate::errors::ApiError;
+use crate::handlers::deprecation::Deprecation;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::DateTime;
+
+/// Version a client expects in requests, and the version served in responses
+pub const API_VERSION_HEADER: &str = "x-fee-manager-api-version";
+
+/// Current admin API version
+pub const ADMIN_API_VERSION: &str = "1";
+
+/// Prefix of the current admin API version
+pub const ADMIN_API_PREFIX: &str = "/api/v1/admin";
+
+/// Unversioned alias of version 1, kept for existing clients
+pub const UNVERSIONED_ADMIN_PREFIX: &str = "/api/admin";
+
+/// When the unversioned prefix was deprecated (2026-02-10)
+const UNVERSIONED_DEPRECATED_AT: i64 = 1_770_681_600;
+
+/// Deprecation of the unversioned prefix, linking each route to its versioned path
+pub fn unversioned_deprecation() -> Deprecation {
+    Deprecation::new(DateTime::from_timestamp(UNVERSIONED_DEPRECATED_AT, 0).unwrap_or_default())
+        .moved(UNVERSIONED_ADMIN_PREFIX, ADMIN_API_PREFIX)
+}
+
+/// Middleware rejecting requests for a version other than the one the prefix
+/// serves and naming the served version in the response
+pub async fn negotiate_version(
+    State(version): State<&'static str>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if let Some(requested) = request.headers().get(API_VERSION_HEADER) {
+        let requested = requested.to_str().unwrap_or_default().trim();
+        if requested != version {
+            return ApiError::NotAcceptable(format!(
+                "API version '{}' is not served; this path serves version {}",
+                requested, version
+            ))
+            .into_response();
+        }
+    }
+
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(
+        HeaderName::from_static(API_VERSION_HEADER),
+        HeaderValue::from_static(version),
+    );
+    response
+}
@@ -0,0 +1,280 @@
+// handlers/audit_history.rs - Stored audit trail of a single resource
+use crate::addresses::BlsPubkey;
+use crate::audit::{self, ResourceType};
+use crate::errors::ApiError;
+use crate::handlers::extract::ApiPath;
+use crate::handlers::pagination::check_limit;
+use crate::schema::AuditHistoryResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::{IntoParams, OpenApi};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditHistoryQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// OpenAPI paths and schemas for the audit history routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_proposer_audit,
+        get_default_config_audit,
+        get_proposer_pattern_audit,
+        get_proposer_group_audit,
+        get_relay_set_audit,
+        get_mux_audit,
+        get_pbs_config_audit,
+        get_token_audit
+    ),
+    components(schemas(AuditHistoryResponse))
+)]
+pub struct AuditHistoryApi;
+
+/// Admin routes for audit history, next to the resources they describe (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/vouch/proposers/{public_key}/audit", get(get_proposer_audit))
+        .route("/vouch/configs/default/{name}/audit", get(get_default_config_audit))
+        .route("/vouch/proposer-patterns/{name}/audit", get(get_proposer_pattern_audit))
+        .route("/vouch/proposer-groups/{name}/audit", get(get_proposer_group_audit))
+        .route("/vouch/relay-sets/{name}/audit", get(get_relay_set_audit))
+        .route("/commit-boost/mux/{name}/audit", get(get_mux_audit))
+        .route("/commit-boost/pbs/{name}/audit", get(get_pbs_config_audit))
+        .route("/tokens/{id}/audit", get(get_token_audit))
+}
+
+/// Events are kept even after the resource is deleted, so its existence is not checked
+async fn history(
+    state: &AppState,
+    resource_type: ResourceType,
+    resource_id: String,
+    query: AuditHistoryQuery,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    if !audit::stores_history() {
+        return Err(ApiError::NotFound(
+            "Audit history is not stored; enable audit_database".to_string(),
+        ));
+    }
+    check_limit(query.limit, state.config.api.max_page_size)?;
+    info!("Getting audit history of {} '{}'", resource_type.as_str(), resource_id);
+
+    let offset = query.offset.max(0);
+    let (events, total) = audit::history(&state.pool, resource_type, &resource_id, query.limit, offset).await?;
+    Ok(Json(AuditHistoryResponse {
+        resource_type: resource_type.as_str().to_string(),
+        resource_id,
+        has_more: offset + (events.len() as i64) < total,
+        events,
+        total,
+        limit: query.limit,
+        offset,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposers/{public_key}/audit",
+    params(
+        ("public_key" = String, Path, description = "Proposer BLS public key"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the proposer, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_audit(
+    State(state): State<Arc<AppState>>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::VouchProposer, public_key.to_string(), query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/audit",
+    params(
+        ("name" = String, Path, description = "Default config name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the default config, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_default_config_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::VouchDefaultConfig, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/{name}/audit",
+    params(
+        ("name" = String, Path, description = "Proposer pattern name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the proposer pattern, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_pattern_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::VouchProposerPattern, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-groups/{name}/audit",
+    params(
+        ("name" = String, Path, description = "Proposer group name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the proposer group, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_group_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::VouchProposerGroup, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/relay-sets/{name}/audit",
+    params(
+        ("name" = String, Path, description = "Relay set name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the relay set, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_relay_set_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::VouchRelaySet, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/mux/{name}/audit",
+    params(
+        ("name" = String, Path, description = "Mux config name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the mux config, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_mux_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::CommitBoostMux, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/pbs/{name}/audit",
+    params(
+        ("name" = String, Path, description = "PBS config name"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the PBS config, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_pbs_config_audit(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::CommitBoostPbsConfig, name, query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/tokens/{id}/audit",
+    params(
+        ("id" = Uuid, Path, description = "Token ID"),
+        AuditHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Audit events of the token, oldest first", body = AuditHistoryResponse),
+        (status = 404, description = "Audit history is not stored"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Audit",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_token_audit(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<AuditHistoryQuery>,
+) -> Result<Json<AuditHistoryResponse>, ApiError> {
+    history(&state, ResourceType::AuthToken, id.to_string(), query).await
+}
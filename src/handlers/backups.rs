@@ -0,0 +1,99 @@
+// handlers/backups.rs - Status of scheduled backups and restoring a snapshot
+use crate::audit::{AuditAction, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::cron::CronSchedule;
+use crate::errors::ApiError;
+use crate::schema::{
+    BackupResponse, BackupRestoreResponse, BackupRetention, BackupSnapshot, BackupsResponse, JobStatus,
+};
+use crate::AppState;
+use axum::{
+    extract::{DefaultBodyLimit, State},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::OpenApi;
+
+/// OpenAPI paths and schemas for the backup routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_backups, restore_backup),
+    components(schemas(
+        BackupsResponse,
+        BackupResponse,
+        BackupRetention,
+        JobStatus,
+        BackupSnapshot,
+        BackupRestoreResponse
+    ))
+)]
+pub struct BackupsApi;
+
+/// Admin routes for the backup status and restore (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(list_backups)).route(
+        "/restore",
+        // Snapshots of large fleets exceed the default 2 MiB body limit
+        post(restore_backup).layer(DefaultBodyLimit::disable()),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/backups",
+    responses(
+        (status = 200, description = "Backup schedule, next run, and the recorded runs", body = BackupsResponse)
+    ),
+    tag = "Backups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_backups(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<BackupsResponse>, ApiError> {
+    let config = &state.config.backups;
+    let next_run_at = CronSchedule::parse(&config.schedule)
+        .ok()
+        .filter(|_| config.enabled)
+        .and_then(|schedule| schedule.next_after(Utc::now()));
+    Ok(Json(BackupsResponse {
+        enabled: config.enabled,
+        schedule: config.schedule.clone(),
+        next_run_at,
+        retention: BackupRetention {
+            keep_last: config.retention.keep_last,
+            max_age_days: config.retention.max_age_days,
+        },
+        backups: crate::backups::list(&state.pool).await?,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/backups/restore",
+    request_body(content = BackupSnapshot, description = "A snapshot as written to the bucket"),
+    responses(
+        (status = 200, description = "Every configuration table replaced by the snapshot", body = BackupRestoreResponse),
+        (status = 400, description = "Not a snapshot, or it lacks or adds tables"),
+        (status = 409, description = "Snapshot taken at another schema version")
+    ),
+    tag = "Backups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, snapshot))]
+pub async fn restore_backup(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(snapshot): Json<BackupSnapshot>,
+) -> Result<Json<BackupRestoreResponse>, ApiError> {
+    info!("Restoring the snapshot taken at {}", snapshot.taken_at);
+    let restored = crate::backups::restore(&state.pool, &snapshot).await?;
+
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Restore, ResourceType::Backup, snapshot.taken_at.to_rfc3339());
+    }
+    Ok(Json(restored))
+}
@@ -0,0 +1,163 @@
+// handlers/commit_boost/key_digest.rs - Membership-only representations of mux keys
+use crate::addresses::BlsPubkey;
+use crate::schema::{MuxKeyBloomResponse, MuxKeyHashesResponse};
+use axum::http::{header, HeaderMap};
+use sha2::{Digest, Sha256};
+
+/// Media type of the salted key hash list
+pub(crate) const KEY_HASHES_MEDIA_TYPE: &str = "application/vnd.fee-manager.key-hashes+json";
+
+/// Media type of the Bloom filter over the salted key hashes
+pub(crate) const KEY_BLOOM_MEDIA_TYPE: &str = "application/vnd.fee-manager.key-bloom+json";
+
+/// False positive rate the Bloom filter is sized for
+const BLOOM_FALSE_POSITIVE_RATE: f64 = 0.001;
+
+const ALGORITHM: &str = "sha256";
+
+/// How the public endpoint represents a mux config's keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyRepresentation {
+    /// Plain JSON array of public keys
+    Plain,
+    /// Salted SHA-256 hash per key
+    Hashes,
+    /// Bloom filter over the salted hashes
+    Bloom,
+}
+
+impl KeyRepresentation {
+    /// Pick a representation from the `Accept` header; anything else gets the plain list
+    pub(crate) fn negotiate(headers: &HeaderMap) -> Self {
+        let accepted: Vec<&str> = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|media_type| media_type.split(';').next().unwrap_or_default().trim())
+            .collect();
+        if accepted.iter().any(|media_type| media_type.eq_ignore_ascii_case(KEY_BLOOM_MEDIA_TYPE)) {
+            KeyRepresentation::Bloom
+        } else if accepted.iter().any(|media_type| media_type.eq_ignore_ascii_case(KEY_HASHES_MEDIA_TYPE)) {
+            KeyRepresentation::Hashes
+        } else {
+            KeyRepresentation::Plain
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            KeyRepresentation::Plain => "plain",
+            KeyRepresentation::Hashes => "hashes",
+            KeyRepresentation::Bloom => "bloom",
+        }
+    }
+}
+
+/// SHA-256 over the salt followed by the raw key bytes
+fn key_hash(salt: &[u8], key: &BlsPubkey) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(key.0);
+    hasher.finalize().into()
+}
+
+pub(crate) fn key_hashes(salt: &[u8], keys: &[BlsPubkey]) -> MuxKeyHashesResponse {
+    // Sorted, so the order does not reveal when keys were added
+    let mut hashes: Vec<String> = keys.iter().map(|key| hex::encode(key_hash(salt, key))).collect();
+    hashes.sort_unstable();
+    MuxKeyHashesResponse {
+        algorithm: ALGORITHM.to_string(),
+        salt: hex::encode(salt),
+        hashes,
+    }
+}
+
+/// Bit positions of one key hash in a filter of `bits` bits (double hashing)
+fn bloom_positions(hash: &[u8; 32], bits: u64, hashes: u32) -> impl Iterator<Item = u64> {
+    let h1 = u64::from_be_bytes(hash[..8].try_into().expect("8 bytes"));
+    let h2 = u64::from_be_bytes(hash[8..16].try_into().expect("8 bytes"));
+    (0..u64::from(hashes)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bits)
+}
+
+pub(crate) fn key_bloom(salt: &[u8], keys: &[BlsPubkey]) -> MuxKeyBloomResponse {
+    // Optimal size and hash count for the key count, in whole bytes
+    let n = keys.len().max(1) as f64;
+    let ln2 = std::f64::consts::LN_2;
+    let bits = ((-n * BLOOM_FALSE_POSITIVE_RATE.ln() / (ln2 * ln2)).ceil() as u64).div_ceil(8) * 8;
+    let hashes = ((bits as f64 / n) * ln2).round().max(1.0) as u32;
+
+    let mut filter = vec![0u8; (bits / 8) as usize];
+    for key in keys {
+        for position in bloom_positions(&key_hash(salt, key), bits, hashes) {
+            filter[(position / 8) as usize] |= 1 << (position % 8);
+        }
+    }
+    MuxKeyBloomResponse {
+        algorithm: ALGORITHM.to_string(),
+        salt: hex::encode(salt),
+        bits,
+        hashes,
+        filter: hex::encode(filter),
+        false_positive_rate: BLOOM_FALSE_POSITIVE_RATE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn key(byte: u8) -> BlsPubkey {
+        BlsPubkey([byte; 48])
+    }
+
+    fn bloom_contains(bloom: &MuxKeyBloomResponse, salt: &[u8], key: &BlsPubkey) -> bool {
+        let filter = hex::decode(&bloom.filter).unwrap();
+        bloom_positions(&key_hash(salt, key), bloom.bits, bloom.hashes)
+            .all(|position| filter[(position / 8) as usize] & (1 << (position % 8)) != 0)
+    }
+
+    #[test]
+    fn negotiates_representation_from_accept() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(KeyRepresentation::negotiate(&headers), KeyRepresentation::Plain);
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+        assert_eq!(KeyRepresentation::negotiate(&headers), KeyRepresentation::Plain);
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("application/json;q=0.5, application/vnd.fee-manager.key-hashes+json"),
+        );
+        assert_eq!(KeyRepresentation::negotiate(&headers), KeyRepresentation::Hashes);
+        headers.insert(header::ACCEPT, HeaderValue::from_static(KEY_BLOOM_MEDIA_TYPE));
+        assert_eq!(KeyRepresentation::negotiate(&headers), KeyRepresentation::Bloom);
+    }
+
+    #[test]
+    fn key_hashes_are_salted_and_sorted() {
+        let keys = [key(1), key(2), key(3)];
+        let hashes = key_hashes(b"salt", &keys);
+        assert_eq!(hashes.hashes.len(), 3);
+        assert!(hashes.hashes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(hashes.hashes.contains(&hex::encode(key_hash(b"salt", &key(2)))));
+        assert_ne!(key_hashes(b"other", &keys).hashes, hashes.hashes);
+    }
+
+    #[test]
+    fn bloom_contains_every_key() {
+        let keys: Vec<BlsPubkey> = (0..200).map(key).collect();
+        let bloom = key_bloom(b"salt", &keys);
+        assert_eq!(bloom.bits % 8, 0);
+        assert!(bloom.hashes >= 1);
+        assert!(keys.iter().all(|k| bloom_contains(&bloom, b"salt", k)));
+        let false_positives = (200..=255).filter(|&b| bloom_contains(&bloom, b"salt", &key(b))).count();
+        assert!(false_positives < 5, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn empty_bloom_matches_nothing() {
+        let bloom = key_bloom(b"salt", &[]);
+        assert!(bloom.bits > 0);
+        assert!(!bloom_contains(&bloom, b"salt", &key(1)));
+    }
+}
@@ -2,12 +2,72 @@
 use crate::AppState;
 use axum::{routing::{get, post}, Router};
 use std::sync::Arc;
+use utoipa::OpenApi;
 
+pub(crate) mod key_digest;
 pub mod mux;
+pub mod pbs;
+
+/// OpenAPI paths and schemas for the Commit-Boost routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        // Public
+        mux::get_mux_keys_public,
+        pbs::get_pbs_config_public,
+        // Mux Admin
+        mux::list_mux_configs,
+        mux::get_mux_config,
+        mux::head_mux_config,
+        mux::create_mux_config,
+        mux::update_mux_config,
+        mux::delete_mux_config,
+        mux::get_mux_config_dependents,
+        mux::add_mux_keys,
+        mux::remove_mux_keys,
+        mux::copy_mux_keys,
+        mux::move_mux_keys,
+        mux::diff_mux_keys,
+        mux::list_duplicate_keys,
+        // PBS Admin
+        pbs::list_pbs_configs,
+        pbs::get_pbs_config,
+        pbs::head_pbs_config,
+        pbs::create_pbs_config,
+        pbs::update_pbs_config,
+        pbs::delete_pbs_config,
+    ),
+    components(schemas(
+        crate::schema::PaginatedResponse<crate::schema::MuxConfigListItem>,
+        crate::schema::PaginatedResponse<crate::schema::DuplicateMuxKey>,
+        crate::schema::DuplicateMuxKey,
+        crate::schema::MuxConfigResponse,
+        crate::schema::MuxConfigListItem,
+        crate::schema::CreateMuxConfigRequest,
+        crate::schema::UpdateMuxConfigRequest,
+        crate::schema::MuxKeysRequest,
+        crate::schema::MuxKeysResponse,
+        crate::schema::MuxKeysTransferRequest,
+        crate::schema::MuxKeysTransferResponse,
+        crate::schema::MuxKeysDiffResponse,
+        crate::schema::MuxKeyHashesResponse,
+        crate::schema::MuxKeyBloomResponse,
+        crate::schema::PaginatedResponse<crate::schema::PbsConfigResponse>,
+        crate::schema::PbsConfigResponse,
+        crate::schema::CreatePbsConfigRequest,
+        crate::schema::UpdatePbsConfigRequest,
+        crate::schema::PbsRelay,
+        crate::schema::PbsMux,
+        crate::schema::MuxRegistry,
+    ))
+)]
+pub struct CommitBoostApi;
 
 /// Public routes for Commit-Boost (no authentication)
 pub fn public_routes() -> Router<Arc<AppState>> {
-    Router::new().route("/v1/mux/{name}", get(mux::get_mux_keys_public))
+    Router::new()
+        .route("/v1/mux/{name}", get(mux::get_mux_keys_public))
+        .route("/v1/pbs/{name}", get(pbs::get_pbs_config_public))
 }
 
 /// Admin routes for Commit-Boost (authentication required)
@@ -17,11 +77,25 @@ pub fn admin_routes() -> Router<Arc<AppState>> {
         .route(
             "/mux/{name}",
             get(mux::get_mux_config)
+                .head(mux::head_mux_config)
                 .put(mux::update_mux_config)
                 .delete(mux::delete_mux_config),
         )
+        .route("/mux/{name}/dependents", get(mux::get_mux_config_dependents))
         .route(
             "/mux/{name}/keys",
             post(mux::add_mux_keys).delete(mux::remove_mux_keys),
         )
+        .route("/mux/{name}/keys/copy", post(mux::copy_mux_keys))
+        .route("/mux/{name}/keys/move", post(mux::move_mux_keys))
+        .route("/mux/{name}/keys/diff", post(mux::diff_mux_keys))
+        .route("/keys/duplicates", get(mux::list_duplicate_keys))
+        .route("/pbs", get(pbs::list_pbs_configs).post(pbs::create_pbs_config))
+        .route(
+            "/pbs/{name}",
+            get(pbs::get_pbs_config)
+                .head(pbs::head_pbs_config)
+                .put(pbs::update_pbs_config)
+                .delete(pbs::delete_pbs_config),
+        )
 }
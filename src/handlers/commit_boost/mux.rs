@@ -1,17 +1,32 @@
 // handlers/commit_boost/mux.rs - Mux config CRUD handlers
 use crate::addresses::BlsPubkey;
-use crate::audit::{AuditAction, AuditChanges, RequestContext, ResourceType};
+use crate::audit::{AccessEvent, AuditAction, PublicRead, RequestContext, ResourceType};
 use crate::audit_log;
+use crate::auth::access::{require_access, AccessResource};
+use crate::config::ApiConfig;
 use crate::errors::ApiError;
+use crate::handlers::commit_boost::key_digest::{
+    key_bloom, key_hashes, KeyRepresentation, KEY_BLOOM_MEDIA_TYPE, KEY_HASHES_MEDIA_TYPE,
+};
+use crate::handlers::extract::ApiJson;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::handlers::streaming::{json_body, JsonStream};
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::metrics;
+use crate::response_cache::{VersionScope, VersionedRequest};
 use crate::schema::{
-    CreateMuxConfigRequest, MuxConfigListItem, MuxConfigResponse, MuxKeysRequest, MuxKeysResponse,
-    PaginatedResponse, UpdateMuxConfigRequest,
+    CreateMuxConfigRequest, DeletionPreview, DuplicateMuxKey, MuxConfigListItem, MuxConfigResponse, MuxKeyBloomResponse,
+    MuxKeyHashesResponse, MuxKeysDiffResponse, MuxKeysRequest, MuxKeysResponse, MuxKeysTransferRequest,
+    MuxKeysTransferResponse, PaginatedResponse, UpdateMuxConfigRequest,
 };
+use crate::services::mux;
+use crate::services::PageRequest;
 use crate::AppState;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::Deserialize;
@@ -21,6 +36,10 @@ use utoipa::IntoParams;
 
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct MuxConfigFilters {
+    /// Sort column: name, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
@@ -31,6 +50,51 @@ fn default_limit() -> i64 {
     100
 }
 
+/// Header carrying the total key count on the public endpoint
+const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DuplicateKeysQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MuxKeysDiffQuery {
+    /// Reconcile the mux config to the desired keys instead of only previewing (default: false)
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MuxKeysQuery {
+    /// Maximum number of keys to return (at most `api.max_page_size`; default: `api.keys_page_size`)
+    pub limit: Option<i64>,
+    /// Number of keys to skip
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl MuxKeysQuery {
+    /// The requested page size, rejected with 422 above `api.max_page_size`,
+    /// or `api.keys_page_size` when the client does not page
+    fn limit(&self, api: &ApiConfig) -> Result<i64, ApiError> {
+        match self.limit {
+            Some(limit) => {
+                check_limit(limit, api.max_page_size)?;
+                Ok(limit)
+            }
+            None => Ok(api.keys_page_size),
+        }
+    }
+
+    fn offset(&self) -> i64 {
+        self.offset.max(0)
+    }
+}
+
 // ============================================================================
 // Public Endpoint
 // ============================================================================
@@ -39,44 +103,110 @@ fn default_limit() -> i64 {
     get,
     path = "/commit-boost/v1/mux/{name}",
     params(
-        ("name" = String, Path, description = "Mux config name")
+        ("name" = String, Path, description = "Mux config name"),
+        MuxKeysQuery
     ),
     responses(
-        (status = 200, description = "List of validator public keys", body = Vec<BlsPubkey>),
-        (status = 404, description = "Mux config not found")
+        (status = 200, description = "List of validator public keys", body = Vec<BlsPubkey>,
+            headers(("x-total-count" = i64, description = "Total number of keys in the mux config"))),
+        (status = 200, description = "Salted hashes of every key, for membership checks only (ignores paging)",
+            body = MuxKeyHashesResponse, content_type = "application/vnd.fee-manager.key-hashes+json"),
+        (status = 200, description = "Bloom filter over every key's salted hash, for membership checks only (ignores paging)",
+            body = MuxKeyBloomResponse, content_type = "application/vnd.fee-manager.key-bloom+json"),
+        (status = 404, description = "Mux config not found"),
+        (status = 401, description = "The mux has access tokens and the request carries none of them"),
+        (status = 422, description = "limit exceeds api.max_page_size")
     ),
     tag = "Commit-Boost - Public"
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, ctx))]
 pub async fn get_mux_keys_public(
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(name): Path<String>,
-) -> Result<Json<Vec<BlsPubkey>>, ApiError> {
-    info!("Getting mux keys (public): {}", name);
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM commit_boost_mux_configs WHERE name = $1",
+    Query(page): Query<MuxKeysQuery>,
+    request_headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let representation = KeyRepresentation::negotiate(&request_headers);
+    info!("Getting mux keys (public, {}): {}", representation.as_str(), name);
+    require_access(&state.pool, AccessResource::Mux, &name, &request_headers).await?;
+    let limit = page.limit(&state.config.api)?;
+
+    let versioned = VersionedRequest::new(
+        &state.pool,
+        VersionScope::CommitBoost,
+        &format!("mux|{}|{}|{}|{}", name, representation.as_str(), limit, page.offset()),
+        state.config.response_cache_size,
     )
-    .bind(&name)
-    .fetch_one(&state.pool)
     .await?;
+    if versioned.not_modified(&request_headers) {
+        metrics::count_public_request(PublicRead::MuxKeys, &name);
+        if state.config.audit_enabled && state.config.audit_public_reads {
+            AccessEvent::new(&ctx, PublicRead::MuxKeys, &name, 0).log().await;
+        }
+        return Ok(versioned.not_modified_response());
+    }
 
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Mux config '{}' not found",
-            name
-        )));
+    // Membership-only representations cover every key, so paging does not apply
+    let digest = match representation {
+        KeyRepresentation::Plain => None,
+        KeyRepresentation::Hashes => Some(match versioned.cached::<(MuxKeyHashesResponse, i64)>() {
+            Some(digest) => digest_response(&digest.0, digest.1, KEY_HASHES_MEDIA_TYPE),
+            None => {
+                let (salt, keys) = mux::digest_keys(&state.pool, &name).await?;
+                let total = keys.len() as i64;
+                let digest = versioned.store((key_hashes(&salt, &keys), total));
+                digest_response(&digest.0, total, KEY_HASHES_MEDIA_TYPE)
+            }
+        }),
+        KeyRepresentation::Bloom => Some(match versioned.cached::<(MuxKeyBloomResponse, i64)>() {
+            Some(digest) => digest_response(&digest.0, digest.1, KEY_BLOOM_MEDIA_TYPE),
+            None => {
+                let (salt, keys) = mux::digest_keys(&state.pool, &name).await?;
+                let total = keys.len() as i64;
+                let digest = versioned.store((key_bloom(&salt, &keys), total));
+                digest_response(&digest.0, total, KEY_BLOOM_MEDIA_TYPE)
+            }
+        }),
+    };
+    if let Some((response, total)) = digest {
+        metrics::count_public_request(PublicRead::MuxKeys, &name);
+        if state.config.audit_enabled && state.config.audit_public_reads {
+            AccessEvent::new(&ctx, PublicRead::MuxKeys, &name, total as usize).log().await;
+        }
+        let vary = [(header::VARY, HeaderValue::from_static("accept"))];
+        return Ok((versioned.etag_header(), vary, response).into_response());
     }
 
-    let keys = sqlx::query_scalar::<_, BlsPubkey>(
-        "SELECT public_key FROM commit_boost_mux_keys WHERE mux_name = $1 ORDER BY id",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
+    let page_data = match versioned.cached::<(Vec<BlsPubkey>, i64)>() {
+        Some(page_data) => page_data,
+        None => versioned.store(mux::key_page(&state.pool, &name, limit, page.offset()).await?),
+    };
+    let (keys, total) = &*page_data;
+    metrics::count_public_request(PublicRead::MuxKeys, &name);
+    if state.config.audit_enabled && state.config.audit_public_reads {
+        AccessEvent::new(&ctx, PublicRead::MuxKeys, &name, keys.len()).log().await;
+    }
+
+    // The body stays a plain array for Commit-Boost, so the total goes in a header
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(*total));
+    headers.insert(header::VARY, HeaderValue::from_static("accept"));
+
+    // Serialized from the cached page as it is sent
+    let body = json_body(move |writer| serde_json::to_writer(writer, &page_data.0));
+    Ok((headers, versioned.etag_header(), body).into_response())
+}
 
-    Ok(Json(keys))
+/// A membership-only body with its media type and the total key count
+fn digest_response<T: serde::Serialize>(body: &T, total: i64, media_type: &'static str) -> (Response, i64) {
+    let headers = [
+        (header::CONTENT_TYPE, HeaderValue::from_static(media_type)),
+        (HeaderName::from_static(TOTAL_COUNT_HEADER), HeaderValue::from(total)),
+    ];
+    let body = serde_json::to_vec(body).expect("digest serializes");
+    ((headers, body).into_response(), total)
 }
 
 // ============================================================================
@@ -86,101 +216,84 @@ pub async fn get_mux_keys_public(
 #[utoipa::path(
     get,
     path = "/api/admin/commit-boost/mux",
-    params(MuxConfigFilters),
+    params(MuxConfigFilters, TimeRangeFilters),
     responses(
-        (status = 200, description = "List of mux configs", body = PaginatedResponse<MuxConfigListItem>)
+        (status = 200, description = "List of mux configs", body = PaginatedResponse<MuxConfigListItem>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, uri))]
 pub async fn list_mux_configs(
     State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
     Query(filters): Query<MuxConfigFilters>,
-) -> Result<Json<PaginatedResponse<MuxConfigListItem>>, ApiError> {
+    Query(time_range): Query<TimeRangeFilters>,
+) -> Result<Page<MuxConfigListItem>, ApiError> {
     info!("Listing mux configs");
+    check_limit(filters.limit, state.config.api.max_page_size)?;
 
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM commit_boost_mux_configs")
-        .fetch_one(&state.pool)
-        .await?;
-
-    let configs = sqlx::query_as::<_, crate::models::CommitBoostMuxConfig>(
-        "SELECT name, created_at, updated_at
-         FROM commit_boost_mux_configs
-         ORDER BY name ASC
-         LIMIT $1 OFFSET $2",
-    )
-    .bind(filters.limit)
-    .bind(filters.offset)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let mut data = Vec::new();
-    for config in configs {
-        let key_count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM commit_boost_mux_keys WHERE mux_name = $1",
-        )
-        .bind(&config.name)
-        .fetch_one(&state.pool)
-        .await?;
-
-        data.push(MuxConfigListItem {
-            name: config.name,
-            key_count,
-            created_at: config.created_at,
-            updated_at: config.updated_at,
-        });
-    }
-
-    Ok(Json(PaginatedResponse {
-        data,
-        total,
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
         limit: filters.limit,
         offset: filters.offset,
-    }))
+    };
+    Ok(Page::new(uri, mux::list(&state.pool, &time_range, page).await?))
 }
 
 #[utoipa::path(
-    get,
+    head,
     path = "/api/admin/commit-boost/mux/{name}",
     params(
         ("name" = String, Path, description = "Mux config name")
     ),
     responses(
-        (status = 200, description = "Mux config details", body = MuxConfigResponse),
+        (status = 200, description = "Mux config exists"),
         (status = 404, description = "Mux config not found")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
 )]
 #[instrument(skip(state))]
+pub async fn head_mux_config(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    mux::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/mux/{name}",
+    params(
+        ("name" = String, Path, description = "Mux config name"),
+        MuxKeysQuery
+    ),
+    responses(
+        (status = 200, description = "Mux config details", body = MuxConfigResponse),
+        (status = 404, description = "Mux config not found"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
 pub async fn get_mux_config(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
-) -> Result<Json<MuxConfigResponse>, ApiError> {
+    Query(page): Query<MuxKeysQuery>,
+) -> Result<JsonStream<MuxConfigResponse>, ApiError> {
     info!("Getting mux config: {}", name);
 
-    let config = sqlx::query_as::<_, crate::models::CommitBoostMuxConfig>(
-        "SELECT name, created_at, updated_at FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_optional(&state.pool)
-    .await?
-    .ok_or_else(|| ApiError::NotFound(format!("Mux config '{}' not found", name)))?;
-
-    let keys = sqlx::query_scalar::<_, BlsPubkey>(
-        "SELECT public_key FROM commit_boost_mux_keys WHERE mux_name = $1 ORDER BY id",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
+    let limit = page.limit(&state.config.api)?;
 
-    Ok(Json(MuxConfigResponse {
-        name: config.name,
-        keys,
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    }))
+    // A page of keys can be large, so it is serialized as it is sent like the public one
+    Ok(JsonStream(mux::get(&state.pool, &name, Some(limit), page.offset()).await?))
 }
 
 #[utoipa::path(
@@ -189,7 +302,7 @@ pub async fn get_mux_config(
     request_body = CreateMuxConfigRequest,
     responses(
         (status = 201, description = "Mux config created", body = MuxConfigListItem),
-        (status = 409, description = "Mux config already exists")
+        (status = 409, description = "Mux config already exists, or a key belongs to another mux config (strict_unique_keys)")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
@@ -202,61 +315,14 @@ pub async fn create_mux_config(
 ) -> Result<impl IntoResponse, ApiError> {
     info!("Creating mux config: {}", req.name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing > 0 {
-        return Err(ApiError::InvalidData(format!(
-            "Mux config '{}' already exists",
-            req.name
-        )));
-    }
-
-    sqlx::query("INSERT INTO commit_boost_mux_configs (name) VALUES ($1)")
-        .bind(&req.name)
-        .execute(&mut *tx)
-        .await?;
-
-    for key in &req.keys {
-        sqlx::query("INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)")
-            .bind(&req.name)
-            .bind(key)
-            .execute(&mut *tx)
-            .await?;
-    }
-
-    tx.commit().await?;
+    let strict = state.config.commit_boost.strict_unique_keys;
+    let (response, changes) = mux::create(&state.pool, &req, strict).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            key_count: Some(req.keys.len() as i64),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Create, ResourceType::CommitBoostMux, &req.name, changes);
     }
 
-    let config = sqlx::query_as::<_, crate::models::CommitBoostMuxConfig>(
-        "SELECT name, created_at, updated_at FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let response = MuxConfigListItem {
-        name: config.name,
-        key_count: req.keys.len() as i64,
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    };
-
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -269,7 +335,8 @@ pub async fn create_mux_config(
     request_body = UpdateMuxConfigRequest,
     responses(
         (status = 200, description = "Mux config updated", body = MuxConfigResponse),
-        (status = 404, description = "Mux config not found")
+        (status = 404, description = "Mux config not found"),
+        (status = 409, description = "A key already belongs to another mux config (strict_unique_keys)")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
@@ -283,67 +350,15 @@ pub async fn update_mux_config(
 ) -> Result<Json<MuxConfigResponse>, ApiError> {
     info!("Updating mux config: {}", name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Mux config '{}' not found",
-            name
-        )));
-    }
-
-    // Replace all keys
-    sqlx::query("DELETE FROM commit_boost_mux_keys WHERE mux_name = $1")
-        .bind(&name)
-        .execute(&mut *tx)
-        .await?;
-
-    for key in &req.keys {
-        sqlx::query("INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)")
-            .bind(&name)
-            .bind(key)
-            .execute(&mut *tx)
-            .await?;
-    }
-
-    // Touch updated_at
-    sqlx::query("UPDATE commit_boost_mux_configs SET updated_at = NOW() WHERE name = $1")
-        .bind(&name)
-        .execute(&mut *tx)
-        .await?;
-
-    tx.commit().await?;
+    let strict = state.config.commit_boost.strict_unique_keys;
+    let (response, changes) = mux::update(&state.pool, &name, &req.keys, strict).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            key_count: Some(req.keys.len() as i64),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Update, ResourceType::CommitBoostMux, &name, changes);
     }
 
-    let config = sqlx::query_as::<_, crate::models::CommitBoostMuxConfig>(
-        "SELECT name, created_at, updated_at FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    Ok(Json(MuxConfigResponse {
-        name: config.name,
-        keys: req.keys,
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    }))
+    Ok(Json(response))
 }
 
 #[utoipa::path(
@@ -354,7 +369,8 @@ pub async fn update_mux_config(
     ),
     responses(
         (status = 204, description = "Mux config deleted"),
-        (status = 404, description = "Mux config not found")
+        (status = 404, description = "Mux config not found"),
+        (status = 409, description = "Mux config is used by a PBS config")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
@@ -367,26 +383,39 @@ pub async fn delete_mux_config(
 ) -> Result<impl IntoResponse, ApiError> {
     info!("Deleting mux config: {}", name);
 
-    let result = sqlx::query("DELETE FROM commit_boost_mux_configs WHERE name = $1")
-        .bind(&name)
-        .execute(&state.pool)
-        .await?;
-
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Mux config '{}' not found",
-            name
-        )));
-    }
+    let changes = mux::delete(&state.pool, &name).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        audit_log!(ctx, AuditAction::Delete, ResourceType::CommitBoostMux, &name);
+        audit_log!(ctx, AuditAction::Delete, ResourceType::CommitBoostMux, &name, changes);
     }
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/mux/{name}/dependents",
+    params(
+        ("name" = String, Path, description = "Mux config name")
+    ),
+    responses(
+        (status = 200, description = "What deleting the mux config would remove or be blocked by", body = DeletionPreview),
+        (status = 404, description = "Mux config not found")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_mux_config_dependents(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<DeletionPreview>, ApiError> {
+    info!("Previewing deletion of mux config: {}", name);
+
+    Ok(Json(mux::dependents(&state.pool, &name).await?))
+}
+
 #[utoipa::path(
     post,
     path = "/api/admin/commit-boost/mux/{name}/keys",
@@ -396,7 +425,9 @@ pub async fn delete_mux_config(
     request_body = MuxKeysRequest,
     responses(
         (status = 200, description = "Keys added", body = MuxKeysResponse),
-        (status = 404, description = "Mux config not found")
+        (status = 400, description = "Malformed public key in request body"),
+        (status = 404, description = "Mux config not found"),
+        (status = 409, description = "A key already belongs to another mux config (strict_unique_keys)")
     ),
     tag = "Commit-Boost - Mux",
     security(("bearer_auth" = []))
@@ -406,69 +437,19 @@ pub async fn add_mux_keys(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
     Path(name): Path<String>,
-    Json(req): Json<MuxKeysRequest>,
+    ApiJson(req): ApiJson<MuxKeysRequest>,
 ) -> Result<Json<MuxKeysResponse>, ApiError> {
     info!("Adding keys to mux config: {}", name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Mux config '{}' not found",
-            name
-        )));
-    }
-
-    let mut added = 0i64;
-    for key in &req.keys {
-        // Use ON CONFLICT to avoid duplicates
-        let result = sqlx::query(
-            "INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)
-             ON CONFLICT (mux_name, public_key) DO NOTHING",
-        )
-        .bind(&name)
-        .bind(key)
-        .execute(&mut *tx)
-        .await?;
-        added += result.rows_affected() as i64;
-    }
-
-    // Touch updated_at
-    sqlx::query("UPDATE commit_boost_mux_configs SET updated_at = NOW() WHERE name = $1")
-        .bind(&name)
-        .execute(&mut *tx)
-        .await?;
-
-    tx.commit().await?;
-
-    let total_keys: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM commit_boost_mux_keys WHERE mux_name = $1")
-            .bind(&name)
-            .fetch_one(&state.pool)
-            .await?;
+    let strict = state.config.commit_boost.strict_unique_keys;
+    let (response, changes) = mux::add_keys(&state.pool, &name, &req.keys, strict).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            key_count: Some(added),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::AddKeys, ResourceType::CommitBoostMux, &name, changes);
     }
 
-    Ok(Json(MuxKeysResponse {
-        added: Some(added),
-        removed: None,
-        total_keys,
-    }))
+    Ok(Json(response))
 }
 
 #[utoipa::path(
@@ -480,6 +461,7 @@ pub async fn add_mux_keys(
     request_body = MuxKeysRequest,
     responses(
         (status = 200, description = "Keys removed", body = MuxKeysResponse),
+        (status = 400, description = "Malformed public key in request body"),
         (status = 404, description = "Mux config not found")
     ),
     tag = "Commit-Boost - Mux",
@@ -490,63 +472,156 @@ pub async fn remove_mux_keys(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
     Path(name): Path<String>,
-    Json(req): Json<MuxKeysRequest>,
+    ApiJson(req): ApiJson<MuxKeysRequest>,
 ) -> Result<Json<MuxKeysResponse>, ApiError> {
     info!("Removing keys from mux config: {}", name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM commit_boost_mux_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&mut *tx)
-    .await?;
+    let (response, changes) = mux::remove_keys(&state.pool, &name, &req.keys).await?;
 
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Mux config '{}' not found",
-            name
-        )));
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::RemoveKeys, ResourceType::CommitBoostMux, &name, changes);
     }
 
-    let result = sqlx::query(
-        "DELETE FROM commit_boost_mux_keys WHERE mux_name = $1 AND public_key = ANY($2)",
-    )
-    .bind(&name)
-    .bind(&req.keys)
-    .execute(&mut *tx)
-    .await?;
-
-    let removed = result.rows_affected() as i64;
+    Ok(Json(response))
+}
 
-    // Touch updated_at
-    sqlx::query("UPDATE commit_boost_mux_configs SET updated_at = NOW() WHERE name = $1")
-        .bind(&name)
-        .execute(&mut *tx)
-        .await?;
+#[utoipa::path(
+    post,
+    path = "/api/admin/commit-boost/mux/{name}/keys/copy",
+    params(
+        ("name" = String, Path, description = "Source mux config name")
+    ),
+    request_body = MuxKeysTransferRequest,
+    responses(
+        (status = 200, description = "Keys copied", body = MuxKeysTransferResponse),
+        (status = 400, description = "Source and target are the same, or a key is malformed"),
+        (status = 404, description = "Source or target mux config not found"),
+        (status = 409, description = "A key already belongs to another mux config (strict_unique_keys)")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn copy_mux_keys(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    ApiJson(req): ApiJson<MuxKeysTransferRequest>,
+) -> Result<Json<MuxKeysTransferResponse>, ApiError> {
+    info!("Copying keys from mux config {} to {}", name, req.target);
+    transfer_mux_keys(&state, &ctx, &name, req, false).await.map(Json)
+}
 
-    tx.commit().await?;
+#[utoipa::path(
+    post,
+    path = "/api/admin/commit-boost/mux/{name}/keys/move",
+    params(
+        ("name" = String, Path, description = "Source mux config name")
+    ),
+    request_body = MuxKeysTransferRequest,
+    responses(
+        (status = 200, description = "Keys moved", body = MuxKeysTransferResponse),
+        (status = 400, description = "Source and target are the same, or a key is malformed"),
+        (status = 404, description = "Source or target mux config not found"),
+        (status = 409, description = "A key already belongs to another mux config (strict_unique_keys)")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn move_mux_keys(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    ApiJson(req): ApiJson<MuxKeysTransferRequest>,
+) -> Result<Json<MuxKeysTransferResponse>, ApiError> {
+    info!("Moving keys from mux config {} to {}", name, req.target);
+    transfer_mux_keys(&state, &ctx, &name, req, true).await.map(Json)
+}
 
-    let total_keys: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM commit_boost_mux_keys WHERE mux_name = $1")
-            .bind(&name)
-            .fetch_one(&state.pool)
-            .await?;
+/// Copy or move keys from `source` to `req.target` and audit it on the source
+async fn transfer_mux_keys(
+    state: &AppState,
+    ctx: &RequestContext,
+    source: &str,
+    req: MuxKeysTransferRequest,
+    remove_from_source: bool,
+) -> Result<MuxKeysTransferResponse, ApiError> {
+    let strict = state.config.commit_boost.strict_unique_keys;
+    let (response, changes) = mux::transfer(&state.pool, source, req, remove_from_source, strict).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            key_count: Some(removed),
-            ..Default::default()
+        let action = if remove_from_source {
+            AuditAction::MoveKeys
+        } else {
+            AuditAction::CopyKeys
         };
-        audit_log!(ctx, AuditAction::RemoveKeys, ResourceType::CommitBoostMux, &name, changes);
+        audit_log!(ctx, action, ResourceType::CommitBoostMux, source, changes);
     }
 
-    Ok(Json(MuxKeysResponse {
-        added: None,
-        removed: Some(removed),
-        total_keys,
-    }))
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/commit-boost/mux/{name}/keys/diff",
+    params(
+        ("name" = String, Path, description = "Mux config name"),
+        MuxKeysDiffQuery
+    ),
+    request_body(content = MuxKeysRequest, description = "The complete desired key list"),
+    responses(
+        (status = 200, description = "Keys to add, remove and keep; applied with apply=true", body = MuxKeysDiffResponse),
+        (status = 400, description = "Malformed public key in request body"),
+        (status = 404, description = "Mux config not found"),
+        (status = 409, description = "A key to add already belongs to another mux config (strict_unique_keys)")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, req))]
+pub async fn diff_mux_keys(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Query(query): Query<MuxKeysDiffQuery>,
+    ApiJson(req): ApiJson<MuxKeysRequest>,
+) -> Result<Json<MuxKeysDiffResponse>, ApiError> {
+    info!("Diffing keys of mux config {} (apply: {})", name, query.apply);
+
+    let strict = state.config.commit_boost.strict_unique_keys;
+    let (response, changes) = mux::diff(&state.pool, &name, &req.keys, query.apply, strict).await?;
+
+    // Audit log
+    if let (true, Some(changes)) = (state.config.audit_enabled, changes) {
+        audit_log!(ctx, AuditAction::Update, ResourceType::CommitBoostMux, &name, changes);
+    }
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/keys/duplicates",
+    params(DuplicateKeysQuery),
+    responses(
+        (status = 200, description = "Keys that belong to more than one mux config", body = PaginatedResponse<DuplicateMuxKey>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Commit-Boost - Mux",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, uri))]
+pub async fn list_duplicate_keys(
+    State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<DuplicateKeysQuery>,
+) -> Result<Page<DuplicateMuxKey>, ApiError> {
+    info!("Listing keys present in more than one mux config");
+    check_limit(query.limit, state.config.api.max_page_size)?;
+
+    Ok(Page::new(uri, mux::duplicates(&state.pool, query.limit, query.offset).await?))
 }
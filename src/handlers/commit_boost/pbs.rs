@@ -0,0 +1,284 @@
+// handlers/commit_boost/pbs.rs - PBS config CRUD and the public cb-config.toml endpoint
+use crate::audit::{AccessEvent, AuditAction, PublicRead, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::auth::access::{require_access, AccessResource};
+use crate::errors::ApiError;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::relays::load_disabled_relays;
+use crate::handlers::sort::SortOrder;
+use crate::metrics;
+use crate::response_cache::{VersionScope, VersionedRequest};
+use crate::schema::{CreatePbsConfigRequest, PaginatedResponse, PbsConfigResponse, UpdatePbsConfigRequest};
+use crate::services::pbs;
+use crate::services::PageRequest;
+use crate::AppState;
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PbsConfigFilters {
+    /// Sort column: name, chain, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// Content type of the rendered config
+const TOML_CONTENT_TYPE: &str = "application/toml";
+
+// ============================================================================
+// Public Endpoint
+// ============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/commit-boost/v1/pbs/{name}",
+    params(
+        ("name" = String, Path, description = "PBS config name")
+    ),
+    responses(
+        (status = 200, description = "cb-config.toml with chain, [pbs], [[relays]] and [[mux]] sections", body = String, content_type = "application/toml"),
+        (status = 404, description = "PBS config not found"),
+        (status = 401, description = "A referenced mux has access tokens and the request carries none of them")
+    ),
+    tag = "Commit-Boost - Public"
+)]
+#[instrument(skip(state, ctx))]
+pub async fn get_pbs_config_public(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    request_headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    info!("Rendering PBS config (public): {}", name);
+    // The keys of referenced muxes are inlined, so their tokens guard the config too
+    for mux_name in pbs::referenced_muxes(&state.pool, &name).await? {
+        require_access(&state.pool, AccessResource::Mux, &mux_name, &request_headers).await?;
+    }
+
+    let versioned = VersionedRequest::new(
+        &state.pool,
+        VersionScope::CommitBoost,
+        &format!("pbs|{}", name),
+        state.config.response_cache_size,
+    )
+    .await?;
+    if versioned.not_modified(&request_headers) {
+        metrics::count_public_request(PublicRead::PbsConfig, &name);
+        if state.config.audit_enabled && state.config.audit_public_reads {
+            AccessEvent::new(&ctx, PublicRead::PbsConfig, &name, 0).log().await;
+        }
+        return Ok(versioned.not_modified_response());
+    }
+
+    let rendered = match versioned.cached::<(String, usize)>() {
+        Some(rendered) => rendered,
+        None => {
+            let globally_disabled = load_disabled_relays(&state.pool).await?;
+            versioned.store(pbs::render(&state.pool, &name, &globally_disabled).await?)
+        }
+    };
+    let (toml, key_count) = &*rendered;
+    metrics::count_public_request(PublicRead::PbsConfig, &name);
+    if state.config.audit_enabled && state.config.audit_public_reads {
+        AccessEvent::new(&ctx, PublicRead::PbsConfig, &name, *key_count).log().await;
+    }
+
+    Ok((
+        [(header::CONTENT_TYPE, HeaderValue::from_static(TOML_CONTENT_TYPE))],
+        versioned.etag_header(),
+        toml.clone(),
+    )
+        .into_response())
+}
+
+// ============================================================================
+// Admin Endpoints
+// ============================================================================
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/pbs",
+    params(PbsConfigFilters),
+    responses(
+        (status = 200, description = "List of PBS configs", body = PaginatedResponse<PbsConfigResponse>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, uri))]
+pub async fn list_pbs_configs(
+    State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
+    Query(filters): Query<PbsConfigFilters>,
+) -> Result<Page<PbsConfigResponse>, ApiError> {
+    info!("Listing PBS configs");
+    check_limit(filters.limit, state.config.api.max_page_size)?;
+
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
+        limit: filters.limit,
+        offset: filters.offset,
+    };
+    Ok(Page::new(uri, pbs::list(&state.pool, page).await?))
+}
+
+#[utoipa::path(
+    head,
+    path = "/api/admin/commit-boost/pbs/{name}",
+    params(
+        ("name" = String, Path, description = "PBS config name")
+    ),
+    responses(
+        (status = 200, description = "PBS config exists"),
+        (status = 404, description = "PBS config not found")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_pbs_config(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    pbs::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/commit-boost/pbs/{name}",
+    params(
+        ("name" = String, Path, description = "PBS config name")
+    ),
+    responses(
+        (status = 200, description = "PBS config details", body = PbsConfigResponse),
+        (status = 404, description = "PBS config not found")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_pbs_config(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<PbsConfigResponse>, ApiError> {
+    info!("Getting PBS config: {}", name);
+
+    Ok(Json(pbs::get(&state.pool, &name).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/commit-boost/pbs",
+    request_body = CreatePbsConfigRequest,
+    responses(
+        (status = 201, description = "PBS config created", body = PbsConfigResponse),
+        (status = 400, description = "Invalid settings, relay or mux (e.g. unknown mux config or relay id)"),
+        (status = 409, description = "PBS config already exists")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn create_pbs_config(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(req): Json<CreatePbsConfigRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Creating PBS config: {}", req.name);
+
+    let (response, changes) = pbs::create(&state.pool, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Create, ResourceType::CommitBoostPbsConfig, &req.name, changes);
+    }
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/commit-boost/pbs/{name}",
+    params(
+        ("name" = String, Path, description = "PBS config name")
+    ),
+    request_body = UpdatePbsConfigRequest,
+    responses(
+        (status = 200, description = "PBS config replaced", body = PbsConfigResponse),
+        (status = 400, description = "Invalid settings, relay or mux (e.g. unknown mux config or relay id)"),
+        (status = 404, description = "PBS config not found")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn update_pbs_config(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(req): Json<UpdatePbsConfigRequest>,
+) -> Result<Json<PbsConfigResponse>, ApiError> {
+    info!("Updating PBS config: {}", name);
+
+    let (response, changes) = pbs::update(&state.pool, &name, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::CommitBoostPbsConfig, &name, changes);
+    }
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/commit-boost/pbs/{name}",
+    params(
+        ("name" = String, Path, description = "PBS config name")
+    ),
+    responses(
+        (status = 204, description = "PBS config deleted"),
+        (status = 404, description = "PBS config not found")
+    ),
+    tag = "Commit-Boost - PBS",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_pbs_config(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Deleting PBS config: {}", name);
+
+    let changes = pbs::delete(&state.pool, &name).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::CommitBoostPbsConfig, &name, changes);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,106 @@
+// handlers/concurrency.rs - Concurrency limit and load shedding for the public endpoints
+use crate::config::PublicConcurrencyConfig;
+use crate::errors::ApiError;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Process-wide load of the public endpoints, reported by `/metrics`
+static IN_FLIGHT: AtomicI64 = AtomicI64::new(0);
+static QUEUED: AtomicI64 = AtomicI64::new(0);
+static SHED: AtomicU64 = AtomicU64::new(0);
+
+/// Snapshot of the public endpoint load
+pub struct PublicLoad {
+    pub in_flight: i64,
+    pub queued: i64,
+    pub shed: u64,
+}
+
+pub fn public_load() -> PublicLoad {
+    PublicLoad {
+        in_flight: IN_FLIGHT.load(Ordering::Relaxed),
+        queued: QUEUED.load(Ordering::Relaxed),
+        shed: SHED.load(Ordering::Relaxed),
+    }
+}
+
+/// Slots shared by every listener serving the public router
+pub(crate) struct ConcurrencyLimit {
+    slots: Semaphore,
+    queued: AtomicI64,
+    max_queued: i64,
+    queue_timeout: Duration,
+}
+
+impl ConcurrencyLimit {
+    /// `None` when `max_in_flight` is 0, so the layer is not installed at all
+    pub(crate) fn new(config: &PublicConcurrencyConfig) -> Option<Arc<Self>> {
+        (config.max_in_flight > 0).then(|| {
+            Arc::new(Self {
+                slots: Semaphore::new(config.max_in_flight),
+                queued: AtomicI64::new(0),
+                max_queued: i64::try_from(config.max_queued).unwrap_or(i64::MAX),
+                queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+            })
+        })
+    }
+}
+
+/// Decrements a gauge when the request finishes, or when the client goes away mid-wait
+struct Gauge<'a>(&'a AtomicI64);
+
+impl<'a> Gauge<'a> {
+    fn enter(gauge: &'a AtomicI64) -> Self {
+        gauge.fetch_add(1, Ordering::Relaxed);
+        Self(gauge)
+    }
+}
+
+impl Drop for Gauge<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Middleware that serves at most `max_in_flight` public requests at once. Excess requests
+/// wait up to `queue_timeout_ms` in a queue of `max_queued`; the rest get a 503 right away,
+/// which is cheaper for everyone than piling up on the database pool.
+pub(crate) async fn limit_concurrency(
+    State(limit): State<Arc<ConcurrencyLimit>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let permit = match limit.slots.try_acquire() {
+        Ok(permit) => Some(permit),
+        Err(_) if limit.queued.load(Ordering::Relaxed) >= limit.max_queued => None,
+        Err(_) => {
+            let _queued = (Gauge::enter(&limit.queued), Gauge::enter(&QUEUED));
+            tokio::time::timeout(limit.queue_timeout, limit.slots.acquire())
+                .await
+                .ok()
+                .and_then(Result::ok)
+        }
+    };
+    let Some(_permit) = permit else {
+        SHED.fetch_add(1, Ordering::Relaxed);
+        let mut response =
+            ApiError::ServiceUnavailable("Server is overloaded, retry later".to_string())
+                .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+        return response;
+    };
+
+    let _in_flight = Gauge::enter(&IN_FLIGHT);
+    next.run(request).await
+}
@@ -0,0 +1,60 @@
+// handlers/consistency.rs - On-demand database consistency check
+use crate::errors::ApiError;
+use crate::schema::{ConsistencyFinding, ConsistencyIssue, ConsistencyReport};
+use crate::AppState;
+use axum::{
+    extract::{Query, State},
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::{IntoParams, OpenApi};
+
+/// OpenAPI paths and schemas for the consistency check route
+#[derive(OpenApi)]
+#[openapi(
+    paths(check_consistency),
+    components(schemas(ConsistencyReport, ConsistencyFinding, ConsistencyIssue))
+)]
+pub struct ConsistencyApi;
+
+/// Admin routes for the consistency check (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", post(check_consistency))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ConsistencyCheckQuery {
+    /// Repair the findings that can be repaired (default: false)
+    #[serde(default)]
+    pub fix: bool,
+}
+
+/// Look for orphaned relays and mux keys, missing relay sets, invalid hex
+/// values and duplicate relay URLs
+#[utoipa::path(
+    post,
+    path = "/api/admin/consistency-check",
+    params(ConsistencyCheckQuery),
+    responses(
+        (status = 200, description = "Findings and which of them were repaired", body = ConsistencyReport)
+    ),
+    tag = "Consistency",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn check_consistency(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ConsistencyCheckQuery>,
+) -> Result<Json<ConsistencyReport>, ApiError> {
+    let report = crate::consistency::run(&state.pool, query.fix).await?;
+    info!(
+        findings = report.count,
+        fixed = report.fixed,
+        "Consistency check completed (fix: {})",
+        query.fix
+    );
+    Ok(Json(report))
+}
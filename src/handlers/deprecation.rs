@@ -0,0 +1,166 @@
+// handlers/deprecation.rs - Deprecation and Sunset headers for routes being retired
+//
+// Attach to a route with
+// `.route_layer(middleware::from_fn_with_state(Deprecation::new(since).sunset(at), deprecated))`;
+// responses then carry `Deprecation` (RFC 9745) and `Sunset` (RFC 8594) headers,
+// plus a `Link` to the successor, and each request is counted in
+// `fee_manager_deprecated_requests_total` so the route can be removed once
+// nothing calls it anymore.
+use crate::metrics;
+use axum::{
+    body::Body,
+    extract::{MatchedPath, OriginalUri, State},
+    http::{header, HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+
+/// When the route was deprecated, as `@<unix time>` (RFC 9745)
+pub const DEPRECATION_HEADER: &str = "deprecation";
+/// When the route will be removed, as an HTTP date (RFC 8594)
+pub const SUNSET_HEADER: &str = "sunset";
+
+/// When a route was deprecated and when it goes away
+#[derive(Debug, Clone)]
+pub struct Deprecation {
+    since: DateTime<Utc>,
+    sunset: Option<DateTime<Utc>>,
+    successor: Option<Successor>,
+}
+
+#[derive(Debug, Clone)]
+enum Successor {
+    /// One route replacing this one
+    Path(&'static str),
+    /// The same route under another prefix
+    Prefix { from: &'static str, to: &'static str },
+}
+
+impl Deprecation {
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            sunset: None,
+            successor: None,
+        }
+    }
+
+    /// Announce when the route will be removed
+    pub fn sunset(mut self, at: DateTime<Utc>) -> Self {
+        self.sunset = Some(at);
+        self
+    }
+
+    /// Point clients at the route replacing this one
+    pub fn successor(mut self, path: &'static str) -> Self {
+        self.successor = Some(Successor::Path(path));
+        self
+    }
+
+    /// Point clients at the same path under another prefix, e.g. a versioned one
+    pub fn moved(mut self, from: &'static str, to: &'static str) -> Self {
+        self.successor = Some(Successor::Prefix { from, to });
+        self
+    }
+
+    /// Headers to add to every response of the route requested at `path`
+    fn headers(&self, path: &str) -> Vec<(HeaderName, String)> {
+        let mut headers = vec![(
+            HeaderName::from_static(DEPRECATION_HEADER),
+            format!("@{}", self.since.timestamp()),
+        )];
+        if let Some(sunset) = self.sunset {
+            headers.push((
+                HeaderName::from_static(SUNSET_HEADER),
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        let successor = match self.successor {
+            Some(Successor::Path(successor)) => Some(successor.to_string()),
+            Some(Successor::Prefix { from, to }) => {
+                path.strip_prefix(from).map(|rest| format!("{}{}", to, rest))
+            }
+            None => None,
+        };
+        if let Some(successor) = successor {
+            headers.push((header::LINK, format!("<{}>; rel=\"successor-version\"", successor)));
+        }
+        headers
+    }
+}
+
+/// Middleware marking the responses of a deprecated route and counting its use
+pub async fn deprecated(State(deprecation): State<Deprecation>, request: Request<Body>, next: Next) -> Response {
+    // Route layers only run for matched routes, so the label set stays bounded
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    metrics::count_deprecated_request(&route);
+    // Nested routers see the path without their prefix
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let mut response = next.run(request).await;
+    for (name, value) in deprecation.headers(&path) {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().append(name, value);
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn header_formats() {
+        let deprecation = Deprecation::new(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap())
+            .sunset(Utc.with_ymd_and_hms(2025, 6, 30, 23, 59, 59).unwrap())
+            .successor("/api/admin/vouch/configs/default/{name}");
+
+        let headers = deprecation.headers("/api/admin/vouch/configs/default");
+        assert_eq!(
+            headers[0],
+            (HeaderName::from_static(DEPRECATION_HEADER), "@1735689600".to_string())
+        );
+        assert_eq!(
+            headers[1],
+            (
+                HeaderName::from_static(SUNSET_HEADER),
+                "Mon, 30 Jun 2025 23:59:59 GMT".to_string()
+            )
+        );
+        assert_eq!(
+            headers[2],
+            (
+                header::LINK,
+                "</api/admin/vouch/configs/default/{name}>; rel=\"successor-version\"".to_string()
+            )
+        );
+
+        assert_eq!(Deprecation::new(Utc.timestamp_opt(0, 0).unwrap()).headers("/").len(), 1);
+    }
+
+    #[test]
+    fn moved_links_the_same_path() {
+        let deprecation =
+            Deprecation::new(Utc.timestamp_opt(0, 0).unwrap()).moved("/api/admin", "/api/v1/admin");
+
+        assert_eq!(
+            deprecation.headers("/api/admin/vouch/proposers/0xab")[1],
+            (
+                header::LINK,
+                "</api/v1/admin/vouch/proposers/0xab>; rel=\"successor-version\"".to_string()
+            )
+        );
+        assert_eq!(deprecation.headers("/metrics").len(), 1);
+    }
+}
@@ -0,0 +1,50 @@
+// handlers/export_destination.rs - Export endpoints writing to the export bucket instead of the response
+use crate::config::AppConfig;
+use crate::errors::ApiError;
+use crate::object_storage::{export_name, ObjectStorage};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Where an export goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportDestination {
+    /// The response body
+    #[default]
+    Response,
+    /// An object in the `export_storage` bucket; the response says which
+    Storage,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ExportDestinationQuery {
+    /// `response` (default) or `storage` for the `export_storage` bucket
+    #[serde(default)]
+    #[param(inline)]
+    pub destination: ExportDestination,
+}
+
+/// Write an export to the bucket as `<kind>/<timestamp>.json`, answering `201 Created`
+pub async fn store_export(
+    config: &AppConfig,
+    kind: &str,
+    value: &impl Serialize,
+    pretty: bool,
+) -> Result<Response, ApiError> {
+    let storage = config
+        .export_storage
+        .as_ref()
+        .ok_or_else(|| ApiError::InvalidData("No export_storage is configured".to_string()))?;
+    let stored = ObjectStorage::new(storage)
+        .map_err(|e| ApiError::InternalError(format!("Export storage client: {}", e)))?
+        .put_json(&export_name(kind, Utc::now()), value, pretty)
+        .await
+        .map_err(|e| ApiError::BadGateway(format!("Export storage: {}", e)))?;
+    Ok((StatusCode::CREATED, Json(stored)).into_response())
+}
@@ -0,0 +1,192 @@
+// handlers/extract.rs - Extractors that report malformed input as problem documents
+use crate::addresses::BlsPubkey;
+use crate::errors::ApiError;
+use axum::{
+    extract::{FromRequest, FromRequestParts, Path, Request},
+    http::{header, request::Parts},
+    Json,
+};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+
+/// Most keys one request body may carry
+const MAX_KEYS: usize = 1_000_000;
+/// Longest line of a key upload; a key takes 98 characters
+const MAX_KEY_LINE: usize = 256;
+
+/// [`Path`] that answers unparsable segments (e.g. a malformed public key)
+/// with `400 VALIDATION_FAILED` instead of axum's plain-text rejection
+pub struct ApiPath<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request_parts(parts, state).await?;
+        Ok(Self(value))
+    }
+}
+
+/// [`Json`] that answers bodies it cannot deserialize with `400 VALIDATION_FAILED`
+pub struct ApiJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
+
+/// Public keys of a request body: a JSON array, or for very large sets a
+/// `text/plain` or `multipart/form-data` upload with one key per line, parsed
+/// as the body streams in instead of buffering it
+pub struct KeyList(pub Vec<BlsPubkey>);
+
+impl<S> FromRequest<S> for KeyList
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+        let mut lines = KeyLines::default();
+        match mime.as_str() {
+            "text/plain" => {
+                let mut body = req.into_body().into_data_stream();
+                while let Some(chunk) = body.next().await {
+                    let chunk = chunk.map_err(|e| ApiError::InvalidData(format!("Failed to read body: {}", e)))?;
+                    lines.push(&chunk)?;
+                }
+            }
+            "multipart/form-data" => {
+                let boundary = multer::parse_boundary(&content_type).map_err(multipart_error)?;
+                let mut multipart = multer::Multipart::new(req.into_body().into_data_stream(), boundary);
+                while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+                    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+                        lines.push(&chunk)?;
+                    }
+                    lines.end_part()?;
+                }
+            }
+            _ => {
+                let ApiJson(keys) = ApiJson::<Vec<BlsPubkey>>::from_request(req, state).await?;
+                return Ok(Self(keys));
+            }
+        }
+        lines.end_part()?;
+        Ok(Self(lines.keys))
+    }
+}
+
+fn multipart_error(e: multer::Error) -> ApiError {
+    ApiError::InvalidData(format!("Invalid multipart body: {}", e))
+}
+
+/// Splits body chunks into lines and parses each as a public key, skipping
+/// blank lines and `#` comments
+#[derive(Default)]
+struct KeyLines {
+    partial: Vec<u8>,
+    line: usize,
+    keys: Vec<BlsPubkey>,
+}
+
+impl KeyLines {
+    fn push(&mut self, mut chunk: &[u8]) -> Result<(), ApiError> {
+        while let Some(end) = chunk.iter().position(|b| *b == b'\n') {
+            self.partial.extend_from_slice(&chunk[..end]);
+            self.end_line()?;
+            chunk = &chunk[end + 1..];
+        }
+        self.partial.extend_from_slice(chunk);
+        if self.partial.len() > MAX_KEY_LINE {
+            return Err(ApiError::InvalidData(format!(
+                "Line {} is longer than {} bytes",
+                self.line + 1,
+                MAX_KEY_LINE
+            )));
+        }
+        Ok(())
+    }
+
+    /// End of the body or of a multipart field: the last line needs no newline
+    fn end_part(&mut self) -> Result<(), ApiError> {
+        if self.partial.is_empty() {
+            Ok(())
+        } else {
+            self.end_line()
+        }
+    }
+
+    fn end_line(&mut self) -> Result<(), ApiError> {
+        self.line += 1;
+        let line = std::mem::take(&mut self.partial);
+        let text = std::str::from_utf8(&line)
+            .map_err(|_| ApiError::InvalidData(format!("Line {} is not valid UTF-8", self.line)))?
+            .trim();
+        if text.is_empty() || text.starts_with('#') {
+            return Ok(());
+        }
+        if self.keys.len() == MAX_KEYS {
+            return Err(ApiError::InvalidData(format!("At most {} keys are allowed", MAX_KEYS)));
+        }
+        let key = text
+            .parse()
+            .map_err(|e| ApiError::InvalidData(format!("Line {}: {}", self.line, e)))?;
+        self.keys.push(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0x8021b6d3d8e0e9e2b7c4a2f6e1d9a7c3b5e8f0a2c4d6e8f0a1b3c5d7e9f1a3b5c7d9e1f3a5b7c9d1e3f5a7b9c1d3e5f7";
+
+    fn parse(chunks: &[&str]) -> Result<Vec<BlsPubkey>, ApiError> {
+        let mut lines = KeyLines::default();
+        for chunk in chunks {
+            lines.push(chunk.as_bytes())?;
+        }
+        lines.end_part()?;
+        Ok(lines.keys)
+    }
+
+    #[test]
+    fn keys_may_span_chunks() {
+        let body = format!("# validators\r\n{}\n\n{}", KEY, KEY.to_uppercase().replace("0X", "0x"));
+        let (head, tail) = body.split_at(40);
+        let keys = parse(&[head, tail]).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[0].to_string(), KEY);
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let err = parse(&[&format!("{}\n0x1234\n", KEY)]).unwrap_err();
+        assert!(err.to_string().contains("Line 2"), "{}", err);
+
+        let err = parse(&[&"0".repeat(MAX_KEY_LINE + 1)]).unwrap_err();
+        assert!(err.to_string().contains("longer than"), "{}", err);
+    }
+}
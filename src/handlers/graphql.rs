@@ -0,0 +1,510 @@
+// handlers/graphql.rs - Read-only GraphQL API over proposers, patterns, configs and muxes
+use crate::addresses::normalize_hex;
+use crate::models::{
+    CommitBoostMuxConfig, Labels, VouchDefaultConfig, VouchProposer, VouchProposerPattern,
+};
+use crate::units::{min_value_in, ValueUnits};
+use crate::AppState;
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, InputObject, Object, Schema,
+    SimpleObject, ID,
+};
+use axum::{routing::post, Json, Router};
+use chrono::{DateTime, Utc};
+use sqlx::types::Json as SqlJson;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::error;
+
+/// Page size used when a list query does not pass `limit`
+const DEFAULT_LIMIT: i64 = 100;
+/// Largest page a single list field returns
+const MAX_LIMIT: i64 = 1000;
+/// Deepest selection accepted; enough for proposer → patterns → relays
+const MAX_DEPTH: usize = 8;
+
+pub type FeeManagerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Schema with the database pool attached for the resolvers
+pub fn build_schema(pool: PgPool) -> FeeManagerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(pool)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// Admin route for the GraphQL endpoint (authentication required)
+pub fn routes(pool: PgPool) -> Router<Arc<AppState>> {
+    let schema = build_schema(pool);
+    Router::new().route(
+        "/",
+        post(move |Json(request): Json<async_graphql::Request>| async move {
+            Json(schema.execute(request).await)
+        }),
+    )
+}
+
+/// Log a database error and hide its details from the client, as the REST API does
+fn db_error(e: sqlx::Error) -> async_graphql::Error {
+    error!("Database error: {:?}", e);
+    async_graphql::Error::new("Internal server error")
+}
+
+/// Relay rows with `min_value` in ETH, as the REST API shows it
+fn in_eth(mut relays: Vec<Relay>) -> Vec<Relay> {
+    for relay in &mut relays {
+        relay.min_value = min_value_in(relay.min_value.take(), ValueUnits::Eth);
+    }
+    relays
+}
+
+fn page(limit: Option<i64>, offset: Option<i64>) -> async_graphql::Result<(i64, i64)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = offset.unwrap_or(0);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(format!("limit must be between 1 and {}", MAX_LIMIT).into());
+    }
+    if offset < 0 {
+        return Err("offset must not be negative".into());
+    }
+    Ok((limit, offset))
+}
+
+fn labels_from(filters: Option<Vec<LabelInput>>) -> Labels {
+    filters
+        .unwrap_or_default()
+        .into_iter()
+        .map(|l| (l.key, l.value))
+        .collect()
+}
+
+fn labels_to(labels: Labels) -> Vec<Label> {
+    labels
+        .into_iter()
+        .map(|(key, value)| Label { key, value })
+        .collect()
+}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(SimpleObject)]
+pub struct Label {
+    pub key: String,
+    pub value: String,
+}
+
+/// Label a row must carry to match a filter
+#[derive(InputObject)]
+pub struct LabelInput {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(SimpleObject, sqlx::FromRow)]
+pub struct Relay {
+    pub url: String,
+    pub public_key: String,
+    pub fee_recipient: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub disabled: bool,
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Proposer {
+    pub public_key: String,
+    pub id: ID,
+    pub fee_recipient: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub reset_relays: bool,
+    pub note: Option<String>,
+    pub labels: Vec<Label>,
+    /// Set while the proposer is left out of execution configs
+    pub decommissioned_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<VouchProposer> for Proposer {
+    fn from(p: VouchProposer) -> Self {
+        Self {
+            public_key: p.public_key.to_string(),
+            id: p.id.into(),
+            fee_recipient: p.fee_recipient.map(|a| a.to_string()),
+            gas_limit: p.gas_limit,
+            min_value: min_value_in(p.min_value, ValueUnits::Eth),
+            builder_enabled: p.builder_enabled,
+            builder_boost_factor: p.builder_boost_factor,
+            reset_relays: p.reset_relays,
+            note: p.note,
+            labels: labels_to(p.labels.0),
+            decommissioned_at: p.decommissioned_at,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Proposer {
+    async fn relays(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Relay>> {
+        sqlx::query_as::<_, Relay>(
+            "SELECT url, public_key, fee_recipient, gas_limit, min_value, disabled
+             FROM vouch_proposer_relays WHERE proposer_public_key = $1 ORDER BY url",
+        )
+        .bind(&self.public_key)
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map(in_eth)
+        .map_err(db_error)
+    }
+
+    /// Proposer patterns whose regex matches this public key
+    async fn matching_patterns(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ProposerPattern>> {
+        let patterns = fetch_patterns(ctx.data::<PgPool>()?, None, &Labels::new()).await?;
+        Ok(patterns
+            .into_iter()
+            .filter(|p| {
+                regex::Regex::new(&p.pattern)
+                    .map(|re| re.is_match(&self.public_key))
+                    .unwrap_or(false)
+            })
+            .collect())
+    }
+
+    /// Names of the mux configs that contain this public key
+    async fn muxes(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<String>> {
+        sqlx::query_scalar(
+            "SELECT mux_name FROM commit_boost_mux_keys WHERE public_key = $1 ORDER BY mux_name",
+        )
+        .bind(&self.public_key)
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map_err(db_error)
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ProposerPattern {
+    pub name: String,
+    pub id: ID,
+    pub pattern: String,
+    pub tags: Vec<String>,
+    pub fee_recipient: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub reset_relays: bool,
+    pub active: bool,
+    pub note: Option<String>,
+    pub labels: Vec<Label>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<VouchProposerPattern> for ProposerPattern {
+    fn from(p: VouchProposerPattern) -> Self {
+        Self {
+            name: p.name,
+            id: p.id.into(),
+            pattern: p.pattern,
+            tags: p.tags,
+            fee_recipient: p.fee_recipient.map(|a| a.to_string()),
+            gas_limit: p.gas_limit,
+            min_value: min_value_in(p.min_value, ValueUnits::Eth),
+            builder_enabled: p.builder_enabled,
+            builder_boost_factor: p.builder_boost_factor,
+            reset_relays: p.reset_relays,
+            active: p.active,
+            note: p.note,
+            labels: labels_to(p.labels.0),
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl ProposerPattern {
+    async fn relays(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Relay>> {
+        sqlx::query_as::<_, Relay>(
+            "SELECT url, public_key, fee_recipient, gas_limit, min_value, disabled
+             FROM vouch_proposer_pattern_relays WHERE pattern_name = $1 ORDER BY url",
+        )
+        .bind(&self.name)
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map(in_eth)
+        .map_err(db_error)
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct DefaultConfig {
+    pub name: String,
+    pub id: ID,
+    pub fee_recipient: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub grace: Option<String>,
+    pub active: bool,
+    pub parent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<VouchDefaultConfig> for DefaultConfig {
+    fn from(c: VouchDefaultConfig) -> Self {
+        Self {
+            name: c.name,
+            id: c.id.into(),
+            fee_recipient: c.fee_recipient.map(|a| a.to_string()),
+            gas_limit: c.gas_limit,
+            min_value: min_value_in(c.min_value, ValueUnits::Eth),
+            builder_enabled: c.builder_enabled,
+            builder_boost_factor: c.builder_boost_factor,
+            grace: c.grace,
+            active: c.active,
+            parent: c.parent,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl DefaultConfig {
+    /// Relays set on this config itself, without those inherited from parents
+    async fn relays(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Relay>> {
+        sqlx::query_as::<_, Relay>(
+            "SELECT url, public_key, fee_recipient, gas_limit, min_value, false AS disabled
+             FROM vouch_default_relays WHERE config_name = $1 ORDER BY url",
+        )
+        .bind(&self.name)
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map(in_eth)
+        .map_err(db_error)
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Mux {
+    pub name: String,
+    pub id: ID,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CommitBoostMuxConfig> for Mux {
+    fn from(m: CommitBoostMuxConfig) -> Self {
+        Self {
+            name: m.name,
+            id: m.id.into(),
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Mux {
+    async fn key_count(&self, ctx: &Context<'_>) -> async_graphql::Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM commit_boost_mux_keys WHERE mux_name = $1")
+            .bind(&self.name)
+            .fetch_one(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)
+    }
+
+    async fn keys(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> async_graphql::Result<Vec<String>> {
+        let (limit, offset) = page(limit, offset)?;
+        sqlx::query_scalar(
+            "SELECT public_key FROM commit_boost_mux_keys WHERE mux_name = $1
+             ORDER BY id LIMIT $2 OFFSET $3",
+        )
+        .bind(&self.name)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map_err(db_error)
+    }
+}
+
+// ============================================================================
+// Queries
+// ============================================================================
+
+const PROPOSER_COLUMNS: &str = "public_key, fee_recipient, gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels, relay_sets, decommissioned_at, id, created_at, updated_at";
+const PATTERN_COLUMNS: &str = "name, pattern, tags, fee_recipient, gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, active, note, labels, relay_sets, id, created_at, updated_at";
+const CONFIG_COLUMNS: &str = "name, fee_recipient, gas_limit, min_value, grace, builder_enabled, builder_boost_factor, active, parent, relay_sets, id, created_at, updated_at";
+
+async fn fetch_patterns(
+    pool: &PgPool,
+    tag: Option<&str>,
+    labels: &Labels,
+) -> async_graphql::Result<Vec<ProposerPattern>> {
+    let sql = format!(
+        "SELECT {} FROM vouch_proposer_patterns
+         WHERE ($1::text IS NULL OR $1 = ANY(tags)) AND labels @> $2::jsonb
+         ORDER BY name",
+        PATTERN_COLUMNS
+    );
+    let patterns = sqlx::query_as::<_, VouchProposerPattern>(&sql)
+        .bind(tag)
+        .bind(SqlJson(labels))
+        .fetch_all(pool)
+        .await
+        .map_err(db_error)?;
+    Ok(patterns.into_iter().map(Into::into).collect())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Proposers ordered by public key, optionally filtered
+    async fn proposers(
+        &self,
+        ctx: &Context<'_>,
+        public_key_prefix: Option<String>,
+        fee_recipient: Option<String>,
+        labels: Option<Vec<LabelInput>>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> async_graphql::Result<Vec<Proposer>> {
+        let (limit, offset) = page(limit, offset)?;
+        let sql = format!(
+            "SELECT {} FROM vouch_proposers
+             WHERE ($1::text IS NULL OR starts_with(public_key, $1))
+               AND ($2::text IS NULL OR lower(fee_recipient) = lower($2))
+               AND labels @> $3::jsonb
+             ORDER BY public_key LIMIT $4 OFFSET $5",
+            PROPOSER_COLUMNS
+        );
+        let proposers = sqlx::query_as::<_, VouchProposer>(&sql)
+            .bind(public_key_prefix.as_deref().map(normalize_hex))
+            .bind(fee_recipient)
+            .bind(SqlJson(labels_from(labels)))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)?;
+        Ok(proposers.into_iter().map(Into::into).collect())
+    }
+
+    async fn proposer(
+        &self,
+        ctx: &Context<'_>,
+        public_key: String,
+    ) -> async_graphql::Result<Option<Proposer>> {
+        let sql = format!("SELECT {} FROM vouch_proposers WHERE public_key = $1", PROPOSER_COLUMNS);
+        let proposer = sqlx::query_as::<_, VouchProposer>(&sql)
+            .bind(normalize_hex(&public_key))
+            .fetch_optional(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)?;
+        Ok(proposer.map(Into::into))
+    }
+
+    /// Proposer patterns ordered by name, optionally filtered by tag and labels
+    async fn proposer_patterns(
+        &self,
+        ctx: &Context<'_>,
+        tag: Option<String>,
+        labels: Option<Vec<LabelInput>>,
+    ) -> async_graphql::Result<Vec<ProposerPattern>> {
+        fetch_patterns(ctx.data::<PgPool>()?, tag.as_deref(), &labels_from(labels)).await
+    }
+
+    async fn proposer_pattern(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Option<ProposerPattern>> {
+        let sql = format!("SELECT {} FROM vouch_proposer_patterns WHERE name = $1", PATTERN_COLUMNS);
+        let pattern = sqlx::query_as::<_, VouchProposerPattern>(&sql)
+            .bind(name)
+            .fetch_optional(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)?;
+        Ok(pattern.map(Into::into))
+    }
+
+    /// Default configs ordered by name, optionally filtered by `active`
+    async fn default_configs(
+        &self,
+        ctx: &Context<'_>,
+        active: Option<bool>,
+    ) -> async_graphql::Result<Vec<DefaultConfig>> {
+        let sql = format!(
+            "SELECT {} FROM vouch_default_configs
+             WHERE ($1::boolean IS NULL OR active = $1) ORDER BY name",
+            CONFIG_COLUMNS
+        );
+        let configs = sqlx::query_as::<_, VouchDefaultConfig>(&sql)
+            .bind(active)
+            .fetch_all(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)?;
+        Ok(configs.into_iter().map(Into::into).collect())
+    }
+
+    async fn default_config(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Option<DefaultConfig>> {
+        let sql = format!("SELECT {} FROM vouch_default_configs WHERE name = $1", CONFIG_COLUMNS);
+        let config = sqlx::query_as::<_, VouchDefaultConfig>(&sql)
+            .bind(name)
+            .fetch_optional(ctx.data::<PgPool>()?)
+            .await
+            .map_err(db_error)?;
+        Ok(config.map(Into::into))
+    }
+
+    /// Mux configs ordered by name
+    async fn muxes(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Mux>> {
+        let muxes = sqlx::query_as::<_, CommitBoostMuxConfig>(
+            "SELECT name, id, created_at, updated_at FROM commit_boost_mux_configs ORDER BY name",
+        )
+        .fetch_all(ctx.data::<PgPool>()?)
+        .await
+        .map_err(db_error)?;
+        Ok(muxes.into_iter().map(Into::into).collect())
+    }
+
+    async fn mux(&self, ctx: &Context<'_>, name: String) -> async_graphql::Result<Option<Mux>> {
+        let mux = sqlx::query_as::<_, CommitBoostMuxConfig>(
+            "SELECT name, id, created_at, updated_at FROM commit_boost_mux_configs WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(ctx.data::<PgPool>()?)
+        .await
+        .map_err(db_error)?;
+        Ok(mux.map(Into::into))
+    }
+}
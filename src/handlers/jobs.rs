@@ -0,0 +1,62 @@
+// handlers/jobs.rs - Status of background jobs
+use crate::errors::ApiError;
+use crate::handlers::api_version;
+use crate::schema::{JobAccepted, JobKind, JobResponse, JobStatus};
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use std::sync::Arc;
+use tracing::instrument;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+/// OpenAPI paths and schemas for the job routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_job),
+    components(schemas(JobResponse, JobAccepted, JobKind, JobStatus))
+)]
+pub struct JobsApi;
+
+/// Admin routes for job status (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/{id}", get(get_job))
+}
+
+/// `202 Accepted` pointing at a submitted job
+pub fn accepted(id: Uuid) -> Response {
+    let url = format!("{}/jobs/{}", api_version::ADMIN_API_PREFIX, id);
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, url.clone())],
+        Json(JobAccepted { id, url }),
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/jobs/{id}",
+    params(("id" = Uuid, Path, description = "Job ID from the 202 answer")),
+    responses(
+        (status = 200, description = "Job status, with the result or error once finished", body = JobResponse),
+        (status = 404, description = "Job not found")
+    ),
+    tag = "Jobs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<JobResponse>, ApiError> {
+    crate::jobs::get(&state.pool, id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Job '{}' not found", id)))
+}
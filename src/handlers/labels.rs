@@ -0,0 +1,66 @@
+// handlers/labels.rs - Label metadata validation and `label.<key>` filters
+use crate::errors::ApiError;
+use crate::models::Labels;
+
+const MAX_LABELS: usize = 32;
+const MAX_KEY_LEN: usize = 63;
+const MAX_VALUE_LEN: usize = 255;
+
+/// Query parameter prefix selecting a label, as in `?label.team=alpha`
+const FILTER_PREFIX: &str = "label.";
+
+/// Reject label maps that are too large or use keys that cannot be filtered on
+pub fn validate_labels(labels: &Labels) -> Result<(), ApiError> {
+    if labels.len() > MAX_LABELS {
+        return Err(ApiError::InvalidData(format!(
+            "At most {} labels are allowed",
+            MAX_LABELS
+        )));
+    }
+    for (key, value) in labels {
+        validate_key(key)?;
+        if value.len() > MAX_VALUE_LEN {
+            return Err(ApiError::InvalidData(format!(
+                "Label '{}' value must be at most {} characters",
+                key, MAX_VALUE_LEN
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_key(key: &str) -> Result<(), ApiError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'));
+    if !valid {
+        return Err(ApiError::InvalidData(format!(
+            "Invalid label key '{}': use 1-{} characters from A-Z, a-z, 0-9, '_', '-', '.', '/'",
+            key, MAX_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Collect `label.<key>=<value>` query parameters into the labels a row must have
+pub fn label_filters(params: &[(String, String)]) -> Result<Labels, ApiError> {
+    let mut labels = Labels::new();
+    for (name, value) in params {
+        if let Some(key) = name.strip_prefix(FILTER_PREFIX) {
+            validate_key(key)?;
+            labels.insert(key.to_string(), value.clone());
+        }
+    }
+    Ok(labels)
+}
+
+/// SQL condition requiring `column` to contain all `labels` (none when empty)
+pub fn labels_condition(column: &str, labels: &Labels) -> Option<String> {
+    if labels.is_empty() {
+        return None;
+    }
+    let json = serde_json::to_string(labels).unwrap_or_default();
+    Some(format!("{} @> '{}'::jsonb", column, json.replace('\'', "''")))
+}
@@ -0,0 +1,98 @@
+// handlers/log_level.rs - Runtime log level changes without a restart
+use crate::audit::{AuditAction, AuditChanges, Change, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::auth::LOGGING_SCOPE;
+use crate::errors::ApiError;
+use crate::logging;
+use crate::schema::{LogLevelRequest, LogLevelResponse};
+use crate::AppState;
+use axum::{extract::State, routing::get, Json, Router};
+use std::sync::Arc;
+use tracing::{instrument, warn};
+use tracing_subscriber::EnvFilter;
+use utoipa::OpenApi;
+
+/// OpenAPI paths and schemas for the log level routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_log_level, set_log_level),
+    components(schemas(LogLevelRequest, LogLevelResponse))
+)]
+pub struct LogLevelApi;
+
+/// Admin routes for the runtime log level (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(get_log_level).put(set_log_level))
+}
+
+fn unavailable() -> ApiError {
+    ApiError::InternalError("Runtime log level changes are not available".to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/log-level",
+    responses(
+        (status = 200, description = "Active log filter", body = LogLevelResponse)
+    ),
+    tag = "Logging",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn get_log_level() -> Result<Json<LogLevelResponse>, ApiError> {
+    let level = logging::log_level().ok_or_else(unavailable)?;
+    Ok(Json(LogLevelResponse { level }))
+}
+
+/// Replace the log filter until the next change or restart
+#[utoipa::path(
+    put,
+    path = "/api/admin/log-level",
+    request_body = LogLevelRequest,
+    responses(
+        (status = 200, description = "Log filter replaced", body = LogLevelResponse),
+        (status = 400, description = "Invalid filter directives"),
+        (status = 403, description = "Token lacks the `logging` scope")
+    ),
+    tag = "Logging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn set_log_level(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(request): Json<LogLevelRequest>,
+) -> Result<Json<LogLevelResponse>, ApiError> {
+    // Without authentication there is no token to carry the scope
+    if state.config.auth.enabled && !ctx.actor.has_scope(LOGGING_SCOPE) {
+        return Err(ApiError::Forbidden(format!(
+            "Changing the log level requires a token with the '{}' scope",
+            LOGGING_SCOPE
+        )));
+    }
+
+    let level = request.level.trim();
+    if level.is_empty() {
+        return Err(ApiError::InvalidData("level must not be empty".to_string()));
+    }
+    let filter = EnvFilter::try_new(level)
+        .map_err(|e| ApiError::InvalidData(format!("Invalid log level '{}': {}", level, e)))?;
+    let level = filter.to_string();
+
+    let previous = logging::set_log_level(filter).map_err(|e| {
+        warn!("Failed to change log level: {}", e);
+        unavailable()
+    })?;
+    warn!("{} changed log level from '{}' to '{}'", ctx.actor.token_name, previous, level);
+
+    // Audit log
+    if state.config.audit_enabled {
+        let changes = AuditChanges {
+            log_level: Change::between(&Some(previous), &Some(level.clone())),
+            ..Default::default()
+        };
+        audit_log!(ctx, AuditAction::Update, ResourceType::LogLevel, "log_level", changes);
+    }
+
+    Ok(Json(LogLevelResponse { level }))
+}
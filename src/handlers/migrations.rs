@@ -0,0 +1,44 @@
+// handlers/migrations.rs - Schema state of the connected database
+use crate::errors::ApiError;
+use crate::schema::{MigrationInfo, MigrationState, MigrationsResponse};
+use crate::AppState;
+use axum::{extract::State, routing::get, Json, Router};
+use std::sync::Arc;
+use tracing::instrument;
+use utoipa::OpenApi;
+
+/// OpenAPI paths and schemas for the migration routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_migrations),
+    components(schemas(MigrationsResponse, MigrationInfo, MigrationState))
+)]
+pub struct MigrationsApi;
+
+/// Admin routes for the migration status (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(list_migrations))
+}
+
+/// Compare the migrations embedded in this binary with what the database has run
+#[utoipa::path(
+    get,
+    path = "/api/admin/migrations",
+    responses(
+        (status = 200, description = "Applied, pending, failed and unknown migrations", body = MigrationsResponse)
+    ),
+    tag = "Migrations",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_migrations(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<MigrationsResponse>, ApiError> {
+    let migrations = crate::list_migrations(&state.pool).await?;
+    let count = |state| migrations.iter().filter(|m| m.state == state).count();
+    Ok(Json(MigrationsResponse {
+        applied: count(MigrationState::Applied),
+        pending: count(MigrationState::Pending),
+        migrations,
+    }))
+}
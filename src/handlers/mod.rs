@@ -1,27 +1,117 @@
 // handlers/mod.rs - Main router and health endpoints
 use crate::auth;
+use crate::build_info;
+use crate::errors::ApiError;
+use crate::metrics;
 use crate::openapi;
 use crate::AppState;
+use crate::audit::{self, ActorInfo, AuditStatus};
+use crate::config::TrustedProxies;
 use axum::{
-    body::Body, http::Request, middleware, response::IntoResponse, routing::get, Json, Router,
+    body::Body,
+    extract::{ConnectInfo, Query, State},
+    http::{header, Request},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
 };
-use serde::Serialize;
-use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
-use tracing::instrument;
-use utoipa::OpenApi;
-use utoipa::ToSchema;
+use tracing::{info, instrument};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
+pub mod access_tokens;
+pub mod api_version;
+pub mod audit_history;
+pub mod backups;
 pub mod commit_boost;
+pub(crate) mod concurrency;
+pub mod consistency;
+pub mod deprecation;
+pub mod export_destination;
+pub mod extract;
+pub mod graphql;
+pub mod jobs;
+pub mod labels;
+pub mod log_level;
+pub mod migrations;
+pub mod pagination;
+pub(crate) mod partial_update;
+pub mod relays;
+pub mod search;
+pub mod sort;
+pub mod streaming;
+pub mod time_range;
+pub mod ui;
 pub mod vouch;
 
+/// When the first router was built, for the uptime in verbose health
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+
 #[derive(Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: String,
 }
 
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct HealthQuery {
+    /// Include pool, migration, build and audit details (requires an admin token)
+    #[serde(default)]
+    pub verbose: bool,
+}
+
+/// `/health?verbose=true`: details for incident triage
+#[derive(Serialize, ToSchema)]
+pub struct HealthDetailResponse {
+    /// "healthy", or "degraded" when the database cannot be queried or migrations are pending
+    pub status: String,
+    /// Crate version of the running binary
+    pub version: String,
+    /// Git commit the binary was built from
+    pub commit: Option<String>,
+    pub uptime_secs: u64,
+    pub pool: PoolStatus,
+    /// Unset when the database could not be queried
+    pub migrations: Option<MigrationStatus>,
+    /// Why the database could not be queried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_error: Option<String>,
+    pub audit: AuditHealth,
+}
+
+/// Database connection pool usage
+#[derive(Serialize, ToSchema)]
+pub struct PoolStatus {
+    /// Open connections
+    pub size: u32,
+    pub idle: usize,
+    pub in_use: usize,
+    pub max_connections: u32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MigrationStatus {
+    pub applied: usize,
+    /// Versions of migrations this binary knows about but the database has not run
+    pub pending: Vec<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AuditHealth {
+    /// `audit_enabled` setting
+    pub enabled: bool,
+    /// Writer state; unset when the writer was not started
+    #[serde(flatten)]
+    pub status: Option<AuditStatus>,
+}
+
 #[utoipa::path(
     get,
     path = "/ready",
@@ -40,18 +130,144 @@ pub async fn get_ready() -> impl IntoResponse {
 #[utoipa::path(
     get,
     path = "/health",
+    params(HealthQuery),
+    responses(
+        (status = 200, description = "Service healthy; `HealthDetailResponse` with `verbose=true`", body = HealthResponse),
+        (status = 401, description = "`verbose=true` without a valid admin token")
+    ),
+    tag = "Health"
+)]
+#[instrument(skip(state))]
+pub async fn get_health(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HealthQuery>,
+) -> Response {
+    if !query.verbose {
+        return Json(HealthResponse {
+            status: "healthy".to_string(),
+        })
+        .into_response();
+    }
+
+    let pool = &state.pool;
+    let idle = pool.num_idle();
+    let (migrations, database_error) = match crate::migration_status(pool).await {
+        Ok((applied, pending)) => (Some(MigrationStatus { applied, pending }), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let healthy = migrations.as_ref().is_some_and(|m| m.pending.is_empty());
+
+    Json(HealthDetailResponse {
+        status: if healthy { "healthy" } else { "degraded" }.to_string(),
+        version: build_info::VERSION.to_string(),
+        commit: build_info::commit().map(str::to_string),
+        uptime_secs: STARTED_AT.get_or_init(Instant::now).elapsed().as_secs(),
+        pool: PoolStatus {
+            size: pool.size(),
+            idle,
+            in_use: (pool.size() as usize).saturating_sub(idle),
+            max_connections: pool.options().get_max_connections(),
+        },
+        migrations,
+        database_error,
+        audit: AuditHealth {
+            enabled: state.config.audit_enabled,
+            status: crate::audit::audit_status(),
+        },
+    })
+    .into_response()
+}
+
+/// Build information for fleet-wide version inventory
+#[derive(Serialize, ToSchema)]
+pub struct VersionResponse {
+    pub version: String,
+    /// Git commit the binary was built from
+    pub commit: Option<String>,
+    /// When the binary was built
+    pub built_at: Option<DateTime<Utc>>,
+    /// Cargo features compiled in, e.g. `audit-kafka`
+    pub features: Vec<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/version",
     responses(
-        (status = 200, description = "Service healthy", body = HealthResponse)
+        (status = 200, description = "Build information", body = VersionResponse)
     ),
     tag = "Health"
 )]
 #[instrument]
-pub async fn get_health() -> impl IntoResponse {
-    Json(HealthResponse {
-        status: "healthy".to_string(),
+pub async fn get_version() -> impl IntoResponse {
+    Json(VersionResponse {
+        version: build_info::VERSION.to_string(),
+        commit: build_info::commit().map(str::to_string),
+        built_at: build_info::built_at(),
+        features: build_info::features().into_iter().map(str::to_string).collect(),
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+        (status = 404, description = "`metrics_enabled` is off")
+    ),
+    tag = "Health"
+)]
+#[instrument(skip(state))]
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> Result<Response, ApiError> {
+    if !state.config.metrics_enabled {
+        return Err(ApiError::NotFound("Metrics are disabled; enable metrics_enabled".to_string()));
+    }
+    let body = metrics::render(&state.pool).await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        body,
+    )
+        .into_response())
+}
+
+/// JSON Schema of the audit output, for SIEM parsers
+async fn get_audit_schema() -> Json<serde_json::Value> {
+    Json(audit::audit_event_schema())
+}
+
+/// `verbose=true` exposes internals, so it takes the same credentials as the admin API
+async fn require_auth_for_verbose(
+    state: State<Arc<AppState>>,
+    request: Request<Body>,
+    next: axum::middleware::Next,
+) -> Result<Response, ApiError> {
+    let verbose = Query::<HealthQuery>::try_from_uri(request.uri())
+        .map(|Query(query)| query.verbose)
+        .unwrap_or(false);
+    if verbose {
+        auth::middleware::require_auth(state, request, next).await
+    } else {
+        Ok(next.run(request).await)
+    }
+}
+
+/// OpenAPI paths and schemas for the health routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_ready, get_health, get_version, get_metrics),
+    components(schemas(
+        HealthResponse,
+        VersionResponse,
+        HealthDetailResponse,
+        PoolStatus,
+        MigrationStatus,
+        AuditHealth,
+        AuditStatus,
+        crate::audit::AuditSinkStatus,
+    ))
+)]
+pub struct HealthApi;
+
 /// Middleware to inject request ID into extensions for handlers
 async fn inject_request_id(
     mut request: Request<Body>,
@@ -66,33 +282,176 @@ async fn inject_request_id(
         .unwrap_or_else(Uuid::new_v4);
 
     request.extensions_mut().insert(request_id);
+    // Error responses pick the ID up from here
+    crate::errors::REQUEST_ID.scope(request_id, next.run(request)).await
+}
+
+/// Address of the client, resolved through `listen.trusted_proxies`.
+/// Absent on unix socket connections.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub IpAddr);
+
+/// Middleware that resolves the client address before authentication and handlers see it
+async fn resolve_client_ip(
+    State(proxies): State<Arc<TrustedProxies>>,
+    mut request: Request<Body>,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    if let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        let forwarded_for = request
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok());
+        let client_ip = proxies.client_ip(peer.ip(), forwarded_for);
+        request.extensions_mut().insert(ClientIp(client_ip));
+    }
     next.run(request).await
 }
 
+/// Middleware that logs one line per request with status, latency and token name
+async fn log_request(
+    request: Request<Body>,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let request_id = request.extensions().get::<Uuid>().copied();
+
+    let response = next.run(request).await;
+
+    let token = response
+        .extensions()
+        .get::<ActorInfo>()
+        .map(|actor| actor.token_name.as_str());
+    info!(
+        target: "access_log",
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms = started.elapsed().as_secs_f64() * 1000.0,
+        request_id = request_id.map(|id| id.to_string()),
+        token,
+        "request completed"
+    );
+    response
+}
+
+/// Router serving every endpoint on a single listener
 pub fn create_router(state: Arc<AppState>) -> Router {
-    let vouch_public = vouch::public_routes();
-    let commit_boost_public = commit_boost::public_routes();
-
-    // Admin routes protected by authentication middleware
-    let admin_routes = Router::new()
-        .nest("/vouch", vouch::admin_routes())
-        .nest("/commit-boost", commit_boost::admin_routes())
-        .nest("/tokens", auth::handlers::token_routes())
-        .layer(middleware::from_fn_with_state(
-            state.clone(),
-            auth::middleware::require_auth,
-        ));
+    build_router(state, true, true)
+}
+
+/// Router for the main listener when admin routes are served separately:
+/// Vouch and Commit-Boost endpoints plus health checks
+pub fn create_public_router(state: Arc<AppState>) -> Router {
+    build_router(state, true, false)
+}
 
-    Router::new()
+/// Router for the admin listener: `/api/v1/admin` (and the deprecated `/api/admin`), Swagger, the admin UI and health checks
+pub fn create_admin_router(state: Arc<AppState>) -> Router {
+    build_router(state, false, true)
+}
+
+fn build_router(state: Arc<AppState>, public: bool, admin: bool) -> Router {
+    let access_log = state.config.access_log;
+    let trusted_proxies = Arc::new(state.config.listen.trusted_proxies.clone());
+    STARTED_AT.get_or_init(Instant::now);
+
+    let mut router = Router::new()
         .route("/ready", get(get_ready))
-        .route("/health", get(get_health))
-        .nest("/vouch", vouch_public)
-        .nest("/commit-boost", commit_boost_public)
-        .nest("/api/admin", admin_routes)
-        .with_state(state)
-        .merge(
-            SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", openapi::ApiDoc::openapi()),
-        )
+        .route("/version", get(get_version))
+        .route(
+            "/health",
+            get(get_health).layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_auth_for_verbose,
+            )),
+        );
+
+    if public {
+        let mut public_routes = Router::new()
+            .nest("/vouch", vouch::public_routes(&state.config.vouch))
+            .nest("/commit-boost", commit_boost::public_routes());
+        if state.config.auth.public_signing.enabled {
+            public_routes = public_routes.layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth::signing::require_signature,
+            ));
+        }
+        // Outermost, so shed requests cost no signature check
+        if let Some(limit) = concurrency::ConcurrencyLimit::new(&state.config.public_concurrency) {
+            public_routes = public_routes.layer(middleware::from_fn_with_state(
+                limit,
+                concurrency::limit_concurrency,
+            ));
+        }
+        router = router.merge(public_routes);
+    }
+
+    if admin {
+        // Admin routes protected by authentication middleware
+        let mut admin_routes = Router::new()
+            .nest("/vouch", vouch::admin_routes())
+            .nest("/commit-boost", commit_boost::admin_routes())
+            .nest("/tokens", auth::handlers::token_routes())
+            .nest("/relays", relays::routes())
+            .nest("/log-level", log_level::routes())
+            .nest("/migrations", migrations::routes())
+            .nest("/jobs", jobs::routes())
+            .nest("/backups", backups::routes())
+            .nest("/consistency-check", consistency::routes())
+            .merge(audit_history::routes())
+            .merge(access_tokens::routes())
+            .route("/whoami", get(auth::handlers::whoami))
+            .route("/search", get(search::search))
+            .route("/resources/{id}", get(search::get_resource));
+        if state.config.graphql_enabled {
+            admin_routes = admin_routes.nest("/graphql", graphql::routes(state.pool.clone()));
+        }
+        let admin_routes = admin_routes
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth::middleware::require_auth,
+            ))
+            .layer(middleware::from_fn_with_state(
+                api_version::ADMIN_API_VERSION,
+                api_version::negotiate_version,
+            ));
+        // The unversioned prefix serves version 1 too, marked deprecated
+        let unversioned_routes = admin_routes.clone().route_layer(middleware::from_fn_with_state(
+            api_version::unversioned_deprecation(),
+            deprecation::deprecated,
+        ));
+        router = router
+            .nest(api_version::ADMIN_API_PREFIX, admin_routes)
+            .nest(api_version::UNVERSIONED_ADMIN_PREFIX, unversioned_routes);
+        if state.config.ui_enabled {
+            router = router.merge(ui::routes());
+        }
+        router = router.route("/metrics", get(get_metrics));
+    }
+
+    let router = router.with_state(state);
+    let router = if admin {
+        router
+            .merge(
+                SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", openapi::ApiDoc::openapi()),
+            )
+            .route("/api-doc/audit-schema.json", get(get_audit_schema))
+    } else {
+        router
+    };
+
+    // Access log runs inside the request ID middleware so it can report the ID
+    let router = if access_log {
+        router.layer(middleware::from_fn(log_request))
+    } else {
+        router
+    };
+
+    router
+        .layer(middleware::from_fn_with_state(trusted_proxies, resolve_client_ip))
         // Add request ID middleware
         .layer(middleware::from_fn(inject_request_id))
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
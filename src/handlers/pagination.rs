@@ -0,0 +1,140 @@
+// handlers/pagination.rs - Paginated list responses with RFC 5988 `Link` headers
+use crate::errors::ApiError;
+use crate::schema::PaginatedResponse;
+use axum::{
+    http::{header, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// A page of a list endpoint, answered with `Link` headers pointing at the
+/// neighbouring pages of the same request
+pub struct Page<T> {
+    uri: Uri,
+    body: PaginatedResponse<T>,
+}
+
+impl<T> Page<T> {
+    /// `uri` is the request's `OriginalUri`, so links keep the router prefix and every filter
+    pub fn new(uri: Uri, body: PaginatedResponse<T>) -> Self {
+        Self { uri, body }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let link = link_header(&self.uri, &self.body);
+        let mut response = Json(self.body).into_response();
+        if let Some(link) = link.and_then(|link| HeaderValue::try_from(link).ok()) {
+            response.headers_mut().insert(header::LINK, link);
+        }
+        response
+    }
+}
+
+/// Reject a `limit` outside `0..=max` (`api.max_page_size`) with 422
+pub fn check_limit(limit: i64, max: i64) -> Result<(), ApiError> {
+    if (0..=max).contains(&limit) {
+        Ok(())
+    } else {
+        Err(ApiError::Unprocessable(format!(
+            "limit must be between 0 and {}, got {}",
+            max, limit
+        )))
+    }
+}
+
+/// `<...>; rel="next", <...>; rel="prev"`, `None` on a single page
+fn link_header<T>(uri: &Uri, page: &PaginatedResponse<T>) -> Option<String> {
+    // A zero limit never advances, so there is nothing to link to
+    if page.limit <= 0 {
+        return None;
+    }
+
+    let mut links = Vec::new();
+    if page.has_more {
+        links.push(format!("<{}>; rel=\"next\"", with_offset(uri, page.offset + page.limit)));
+    }
+    if page.offset > 0 {
+        let prev = (page.offset - page.limit).max(0);
+        links.push(format!("<{}>; rel=\"prev\"", with_offset(uri, prev)));
+    }
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
+/// The request path and query with `offset` replaced, other parameters kept in order
+fn with_offset(uri: &Uri, offset: i64) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+        if key != "offset" {
+            query.append_pair(&key, &value);
+        }
+    }
+    query.append_pair("offset", &offset.to_string());
+    format!("{}?{}", uri.path(), query.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(total: i64, limit: i64, offset: i64) -> PaginatedResponse<()> {
+        let rows = (total - offset).clamp(0, limit) as usize;
+        PaginatedResponse::new(vec![(); rows], total, limit, offset)
+    }
+
+    fn link(uri: &str, page: PaginatedResponse<()>) -> Option<String> {
+        link_header(&uri.parse().unwrap(), &page)
+    }
+
+    #[test]
+    fn has_more_until_the_last_row() {
+        assert!(page(5, 2, 0).has_more);
+        assert!(page(5, 2, 2).has_more);
+        assert!(!page(5, 2, 4).has_more);
+        assert!(!page(4, 2, 2).has_more);
+        assert!(!page(0, 2, 0).has_more);
+    }
+
+    #[test]
+    fn links_neighbouring_pages() {
+        assert_eq!(
+            link("/api/admin/vouch/proposers?limit=2", page(5, 2, 0)).as_deref(),
+            Some("</api/admin/vouch/proposers?limit=2&offset=2>; rel=\"next\"")
+        );
+        assert_eq!(
+            link("/api/admin/vouch/proposers?offset=2&limit=2", page(5, 2, 2)).as_deref(),
+            Some(
+                "</api/admin/vouch/proposers?limit=2&offset=4>; rel=\"next\", \
+                 </api/admin/vouch/proposers?limit=2&offset=0>; rel=\"prev\""
+            )
+        );
+        assert_eq!(
+            link("/api/admin/vouch/proposers?limit=2&offset=4", page(5, 2, 4)).as_deref(),
+            Some("</api/admin/vouch/proposers?limit=2&offset=2>; rel=\"prev\"")
+        );
+    }
+
+    #[test]
+    fn keeps_filters_and_clamps_prev_to_the_start() {
+        assert_eq!(
+            link("/api/admin/vouch/proposers?label.team=a%20b&limit=10&offset=3", page(5, 10, 3)).as_deref(),
+            Some("</api/admin/vouch/proposers?label.team=a+b&limit=10&offset=0>; rel=\"prev\"")
+        );
+    }
+
+    #[test]
+    fn single_page_has_no_links() {
+        assert_eq!(link("/api/admin/vouch/proposers", page(3, 100, 0)), None);
+        assert_eq!(link("/api/admin/vouch/proposers?limit=0", page(3, 0, 0)), None);
+    }
+
+    #[test]
+    fn limit_is_bounded_by_max_page_size() {
+        assert!(check_limit(0, 1000).is_ok());
+        assert!(check_limit(1000, 1000).is_ok());
+        assert!(matches!(check_limit(1001, 1000), Err(ApiError::Unprocessable(_))));
+        assert!(matches!(check_limit(-1, 1000), Err(ApiError::Unprocessable(_))));
+    }
+}
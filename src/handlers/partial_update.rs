@@ -0,0 +1,99 @@
+// handlers/partial_update.rs - UPDATE statements covering only the fields a request sets
+use sqlx::{Encode, PgConnection, Postgres, QueryBuilder, Type};
+
+/// Builds `UPDATE <table> SET <column> = $1, ... WHERE <key> = $n`, numbering
+/// placeholders in the order values are bound, so any subset of fields lines up
+pub(crate) struct PartialUpdate<'args> {
+    query: QueryBuilder<'args, Postgres>,
+    columns: usize,
+}
+
+impl<'args> PartialUpdate<'args> {
+    pub(crate) fn new(table: &str) -> Self {
+        Self {
+            query: QueryBuilder::new(format!("UPDATE {} SET ", table)),
+            columns: 0,
+        }
+    }
+
+    /// Set `column` when the request provides a value; `None` leaves it unchanged
+    pub(crate) fn set<T>(&mut self, column: &str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        if let Some(value) = value {
+            if self.columns > 0 {
+                self.query.push(", ");
+            }
+            self.query.push(column).push(" = ").push_bind(value);
+            self.columns += 1;
+        }
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.columns == 0
+    }
+
+    fn finish<K>(&mut self, key_column: &str, key: K)
+    where
+        K: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        self.query.push(" WHERE ").push(key_column).push(" = ").push_bind(key);
+    }
+
+    /// Update the row where `key_column = key`; does nothing when no field is set.
+    /// Returns the number of rows updated.
+    pub(crate) async fn execute<K>(
+        mut self,
+        conn: &mut PgConnection,
+        key_column: &str,
+        key: K,
+    ) -> Result<u64, sqlx::Error>
+    where
+        K: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        if self.is_empty() {
+            return Ok(0);
+        }
+        self.finish(key_column, key);
+        let result = self.query.build().execute(conn).await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [&str; 4] = ["fee_recipient", "gas_limit", "active", "labels"];
+
+    #[test]
+    fn placeholders_follow_the_fields_set() {
+        // Every subset of columns, including none
+        for mask in 0u32..(1 << COLUMNS.len()) {
+            let mut update = PartialUpdate::new("t");
+            let mut expected = Vec::new();
+            for (i, column) in COLUMNS.iter().enumerate() {
+                let value = (mask & (1 << i) != 0).then_some("v");
+                update.set(column, value);
+                if value.is_some() {
+                    expected.push(format!("{} = ${}", column, expected.len() + 1));
+                }
+            }
+            assert_eq!(update.is_empty(), expected.is_empty());
+            update.finish("name", "n");
+
+            assert_eq!(
+                update.query.sql(),
+                format!(
+                    "UPDATE t SET {} WHERE name = ${}",
+                    expected.join(", "),
+                    expected.len() + 1
+                ),
+                "mask {:04b}",
+                mask
+            );
+        }
+    }
+}
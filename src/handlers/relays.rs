@@ -0,0 +1,152 @@
+// handlers/relays.rs - Global relay switches applied across all configs
+use crate::audit::{AuditAction, AuditChanges, Change, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::errors::ApiError;
+use crate::schema::DisabledRelayResponse;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::OpenApi;
+
+/// OpenAPI paths and schemas for the relay routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_disabled_relays, disable_relay, enable_relay),
+    components(schemas(DisabledRelayResponse))
+)]
+pub struct RelaysApi;
+
+/// Admin routes for global relay switches (authentication required)
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/disabled", get(list_disabled_relays))
+        .route("/{url}/disable", post(disable_relay))
+        .route("/{url}/enable", post(enable_relay))
+}
+
+/// URLs of all globally disabled relays
+pub(crate) async fn load_disabled_relays(pool: &PgPool) -> Result<HashSet<String>, ApiError> {
+    let urls: Vec<String> = sqlx::query_scalar("SELECT url FROM disabled_relays")
+        .fetch_all(pool)
+        .await?;
+    Ok(urls.into_iter().collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/relays/disabled",
+    responses(
+        (status = 200, description = "Globally disabled relays", body = Vec<DisabledRelayResponse>)
+    ),
+    tag = "Relays",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_disabled_relays(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<DisabledRelayResponse>>, ApiError> {
+    let relays = sqlx::query_as::<_, crate::models::DisabledRelay>(
+        "SELECT url, disabled_at FROM disabled_relays ORDER BY url",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(relays.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/relays/{url}/disable",
+    params(
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    responses(
+        (status = 200, description = "Relay disabled in all configs", body = DisabledRelayResponse)
+    ),
+    tag = "Relays",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn disable_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(url): Path<String>,
+) -> Result<Json<DisabledRelayResponse>, ApiError> {
+    info!("Disabling relay globally: {}", url);
+
+    // Disabling an already disabled relay keeps the original timestamp
+    let inserted = sqlx::query("INSERT INTO disabled_relays (url) VALUES ($1) ON CONFLICT (url) DO NOTHING")
+        .bind(&url)
+        .execute(&state.pool)
+        .await?
+        .rows_affected()
+        > 0;
+
+    let relay = sqlx::query_as::<_, crate::models::DisabledRelay>(
+        "SELECT url, disabled_at FROM disabled_relays WHERE url = $1",
+    )
+    .bind(&url)
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Audit log
+    if state.config.audit_enabled && inserted {
+        let changes = AuditChanges {
+            disabled: Change::between(&Some(false), &Some(true)),
+            ..Default::default()
+        };
+        audit_log!(ctx, AuditAction::DisableRelay, ResourceType::Relay, &url, changes);
+    }
+
+    Ok(Json(relay.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/relays/{url}/enable",
+    params(
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    responses(
+        (status = 204, description = "Relay enabled again"),
+        (status = 404, description = "Relay is not globally disabled")
+    ),
+    tag = "Relays",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn enable_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(url): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    info!("Enabling relay globally: {}", url);
+
+    let deleted = sqlx::query("DELETE FROM disabled_relays WHERE url = $1")
+        .bind(&url)
+        .execute(&state.pool)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+        return Err(ApiError::NotFound(format!("Relay '{}' is not disabled", url)));
+    }
+
+    // Audit log
+    if state.config.audit_enabled {
+        let changes = AuditChanges {
+            disabled: Change::between(&Some(true), &Some(false)),
+            ..Default::default()
+        };
+        audit_log!(ctx, AuditAction::EnableRelay, ResourceType::Relay, &url, changes);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
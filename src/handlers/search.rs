@@ -0,0 +1,258 @@
+// handlers/search.rs - Cross-resource search endpoint
+use crate::errors::ApiError;
+use crate::handlers::pagination::check_limit;
+use crate::schema::{ResourceLookupResponse, SearchHit, SearchResourceType, SearchResponse};
+use crate::AppState;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::{IntoParams, OpenApi};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    /// Search term: pubkey / fee recipient prefix, or name / relay URL substring
+    pub q: String,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+/// OpenAPI paths and schemas for the search route
+#[derive(OpenApi)]
+#[openapi(
+    paths(search, get_resource),
+    components(schemas(SearchResourceType, SearchHit, SearchResponse, ResourceLookupResponse))
+)]
+pub struct SearchApi;
+
+/// Raw search row before the resource type is parsed
+#[derive(sqlx::FromRow)]
+struct SearchRow {
+    resource_type: String,
+    resource_id: String,
+    field: String,
+    value: String,
+}
+
+/// Hex values ($1) are prefix matched; names and relay URLs ($2) are case-insensitive substring matches
+const SEARCH_SQL: &str = "
+    SELECT 'vouch_proposer' AS resource_type, public_key AS resource_id, 'public_key' AS field, public_key AS value
+        FROM vouch_proposers WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer', public_key, 'fee_recipient', fee_recipient
+        FROM vouch_proposers WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer', proposer_public_key, 'relay_url', url
+        FROM vouch_proposer_relays WHERE url ILIKE $2
+    UNION ALL
+    SELECT 'vouch_proposer', proposer_public_key, 'relay_public_key', public_key
+        FROM vouch_proposer_relays WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer', proposer_public_key, 'relay_fee_recipient', fee_recipient
+        FROM vouch_proposer_relays WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_default_config', name, 'name', name
+        FROM vouch_default_configs WHERE name ILIKE $2
+    UNION ALL
+    SELECT 'vouch_default_config', name, 'fee_recipient', fee_recipient
+        FROM vouch_default_configs WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_default_config', config_name, 'relay_url', url
+        FROM vouch_default_relays WHERE url ILIKE $2
+    UNION ALL
+    SELECT 'vouch_default_config', config_name, 'relay_public_key', public_key
+        FROM vouch_default_relays WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_default_config', config_name, 'relay_fee_recipient', fee_recipient
+        FROM vouch_default_relays WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', name, 'name', name
+        FROM vouch_proposer_patterns WHERE name ILIKE $2
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', name, 'fee_recipient', fee_recipient
+        FROM vouch_proposer_patterns WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', pattern_name, 'relay_url', url
+        FROM vouch_proposer_pattern_relays WHERE url ILIKE $2
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', pattern_name, 'relay_public_key', public_key
+        FROM vouch_proposer_pattern_relays WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', pattern_name, 'relay_fee_recipient', fee_recipient
+        FROM vouch_proposer_pattern_relays WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_group', name, 'name', name
+        FROM vouch_proposer_groups WHERE name ILIKE $2
+    UNION ALL
+    SELECT 'vouch_proposer_group', name, 'fee_recipient', fee_recipient
+        FROM vouch_proposer_groups WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_group', group_name, 'public_key', public_key
+        FROM vouch_proposer_group_keys WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_group', group_name, 'relay_url', url
+        FROM vouch_proposer_group_relays WHERE url ILIKE $2
+    UNION ALL
+    SELECT 'vouch_proposer_group', group_name, 'relay_public_key', public_key
+        FROM vouch_proposer_group_relays WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_proposer_group', group_name, 'relay_fee_recipient', fee_recipient
+        FROM vouch_proposer_group_relays WHERE fee_recipient LIKE $1
+    UNION ALL
+    SELECT 'vouch_relay_set', name, 'name', name
+        FROM vouch_relay_sets WHERE name ILIKE $2
+    UNION ALL
+    SELECT 'vouch_relay_set', set_name, 'relay_url', url
+        FROM vouch_relay_set_relays WHERE url ILIKE $2
+    UNION ALL
+    SELECT 'vouch_relay_set', set_name, 'relay_public_key', public_key
+        FROM vouch_relay_set_relays WHERE public_key LIKE $1
+    UNION ALL
+    SELECT 'vouch_relay_set', set_name, 'relay_fee_recipient', fee_recipient
+        FROM vouch_relay_set_relays WHERE fee_recipient LIKE $1    UNION ALL
+    SELECT 'commit_boost_mux', name, 'name', name
+        FROM commit_boost_mux_configs WHERE name ILIKE $2
+    UNION ALL
+    SELECT 'commit_boost_mux', mux_name, 'public_key', public_key
+        FROM commit_boost_mux_keys WHERE public_key LIKE $1
+    ORDER BY resource_type, resource_id, field
+    LIMIT $3";
+
+/// Every resource with a stable id; ids are random UUIDs, so at most one row matches
+const RESOURCE_BY_ID_SQL: &str = "
+    SELECT 'vouch_proposer', public_key FROM vouch_proposers WHERE id = $1
+    UNION ALL
+    SELECT 'vouch_default_config', name FROM vouch_default_configs WHERE id = $1
+    UNION ALL
+    SELECT 'vouch_proposer_pattern', name FROM vouch_proposer_patterns WHERE id = $1
+    UNION ALL
+    SELECT 'vouch_proposer_group', name FROM vouch_proposer_groups WHERE id = $1
+    UNION ALL
+    SELECT 'vouch_relay_set', name FROM vouch_relay_sets WHERE id = $1
+    UNION ALL
+    SELECT 'commit_boost_mux', name FROM commit_boost_mux_configs WHERE id = $1
+    UNION ALL
+    SELECT 'commit_boost_pbs_config', name FROM commit_boost_pbs_configs WHERE id = $1";
+
+fn parse_resource_type(resource_type: &str) -> Option<SearchResourceType> {
+    match resource_type {
+        "vouch_proposer" => Some(SearchResourceType::VouchProposer),
+        "vouch_default_config" => Some(SearchResourceType::VouchDefaultConfig),
+        "vouch_proposer_pattern" => Some(SearchResourceType::VouchProposerPattern),
+        "vouch_proposer_group" => Some(SearchResourceType::VouchProposerGroup),
+        "vouch_relay_set" => Some(SearchResourceType::VouchRelaySet),
+        "commit_boost_mux" => Some(SearchResourceType::CommitBoostMux),
+        "commit_boost_pbs_config" => Some(SearchResourceType::CommitBoostPbsConfig),
+        _ => None,
+    }
+}
+
+/// Escape LIKE wildcards so the search term is matched literally
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Search hits across all resources", body = SearchResponse),
+        (status = 400, description = "Empty search term"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Search",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    info!("Searching for: {}", query.q);
+    check_limit(query.limit, state.config.api.max_page_size)?;
+
+    let term = query.q.trim();
+    if term.is_empty() {
+        return Err(ApiError::InvalidData(
+            "Search term 'q' must not be empty".to_string(),
+        ));
+    }
+
+    // Hex values are stored lowercase
+    let escaped = escape_like(term);
+    let prefix = format!("{}%", escaped.to_lowercase());
+    let substring = format!("%{}%", escaped);
+
+    let rows = sqlx::query_as::<_, SearchRow>(SEARCH_SQL)
+        .bind(&prefix)
+        .bind(&substring)
+        .bind(query.limit)
+        .fetch_all(&state.pool)
+        .await?;
+
+    let hits = rows
+        .into_iter()
+        .filter_map(|row| {
+            let resource_type = parse_resource_type(&row.resource_type)?;
+            Some(SearchHit {
+                resource_type,
+                resource_id: row.resource_id,
+                field: row.field,
+                value: row.value,
+            })
+        })
+        .collect();
+
+    Ok(Json(SearchResponse {
+        query: term.to_string(),
+        hits,
+    }))
+}
+
+/// Resolve a stable resource id to the resource type and natural key, for tooling
+/// that tracks resources by id
+#[utoipa::path(
+    get,
+    path = "/api/admin/resources/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Stable resource id, as returned in the `id` field")
+    ),
+    responses(
+        (status = 200, description = "Resource with this id", body = ResourceLookupResponse),
+        (status = 404, description = "No resource has this id")
+    ),
+    tag = "Search",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_resource(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ResourceLookupResponse>, ApiError> {
+    info!("Looking up resource: {}", id);
+
+    let row: Option<(String, String)> = sqlx::query_as(RESOURCE_BY_ID_SQL)
+        .bind(id)
+        .fetch_optional(&state.pool)
+        .await?;
+    let (resource_type, resource_id) = row
+        .and_then(|(resource_type, resource_id)| Some((parse_resource_type(&resource_type)?, resource_id)))
+        .ok_or_else(|| ApiError::NotFound(format!("No resource with id '{}'", id)))?;
+
+    Ok(Json(ResourceLookupResponse {
+        id,
+        resource_type,
+        resource_id,
+    }))
+}
@@ -0,0 +1,61 @@
+// handlers/sort.rs - Shared sorting parameters for list endpoints
+use crate::errors::ApiError;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+/// Sort direction for list endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn as_sql(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// Sortable columns of a list endpoint: API name -> SQL column, plus the default
+pub struct SortColumns {
+    pub columns: &'static [(&'static str, &'static str)],
+    pub default: (&'static str, SortOrder),
+    /// Unique column appended so pagination stays stable on ties
+    pub tiebreak: &'static str,
+}
+
+impl SortColumns {
+    /// Build an ORDER BY clause, rejecting columns that are not whitelisted
+    pub fn order_by(&self, sort: Option<&str>, order: Option<SortOrder>) -> Result<String, ApiError> {
+        let (column, default_order) = match sort {
+            Some(sort) => {
+                let column = self
+                    .columns
+                    .iter()
+                    .find(|(name, _)| *name == sort)
+                    .map(|(_, column)| *column)
+                    .ok_or_else(|| {
+                        let allowed: Vec<&str> = self.columns.iter().map(|(name, _)| *name).collect();
+                        ApiError::InvalidData(format!(
+                            "Invalid sort column '{}', expected one of: {}",
+                            sort,
+                            allowed.join(", ")
+                        ))
+                    })?;
+                (column, SortOrder::Asc)
+            }
+            None => self.default,
+        };
+        let direction = order.unwrap_or(default_order).as_sql();
+
+        if column == self.tiebreak {
+            Ok(format!("ORDER BY {} {}", column, direction))
+        } else {
+            Ok(format!("ORDER BY {} {}, {} ASC", column, direction, self.tiebreak))
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// handlers/streaming.rs - JSON bodies serialized while they are sent
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use futures_util::stream;
+use serde::Serialize;
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Bytes handed to the body per chunk
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks serialized ahead of a slow client
+const CHUNKS_AHEAD: usize = 4;
+
+/// Serialize a large response on a blocking thread into a chunked body, so
+/// besides the value only a few chunks are held in memory. `write` serializes
+/// into the sink it is given; a client that disconnects stops it.
+pub fn json_body<F>(write: F) -> Body
+where
+    F: FnOnce(&mut ChunkWriter) -> serde_json::Result<()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHUNKS_AHEAD);
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkWriter {
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+            tx,
+        };
+        let result = write(&mut writer).map_err(io::Error::from).and_then(|()| writer.flush());
+        if let Err(e) = result {
+            if e.kind() != io::ErrorKind::BrokenPipe {
+                warn!("Streaming response failed: {}", e);
+            }
+            // Ends the body with an error, so the client sees a truncated response
+            let _ = writer.tx.blocking_send(Err(e));
+        }
+    });
+    Body::from_stream(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }))
+}
+
+/// [`axum::Json`] for large values: serialized chunk by chunk as the client reads
+pub struct JsonStream<T>(pub T);
+
+impl<T: Serialize + Send + 'static> IntoResponse for JsonStream<T> {
+    fn into_response(self) -> Response {
+        let value = self.0;
+        (
+            [(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            json_body(move |writer| serde_json::to_writer(writer, &value)),
+        )
+            .into_response()
+    }
+}
+
+/// Sink of [`json_body`], passing full chunks on to the response body
+pub struct ChunkWriter {
+    buffer: Vec<u8>,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl ChunkWriter {
+    fn send(&mut self) -> io::Result<()> {
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.tx
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            self.send()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn large_values_arrive_in_chunks() {
+        let keys: Vec<String> = (0..10_000).map(|i| format!("0x{:096x}", i)).collect();
+        let expected = serde_json::to_vec(&keys).unwrap();
+
+        let mut body = JsonStream(keys).into_response().into_body().into_data_stream();
+        let mut chunks = 0;
+        let mut received = Vec::new();
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.unwrap();
+            assert!(chunk.len() <= CHUNK_SIZE + 100);
+            received.extend_from_slice(&chunk);
+            chunks += 1;
+        }
+        assert!(chunks > 1);
+        assert_eq!(received, expected);
+    }
+}
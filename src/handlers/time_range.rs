@@ -0,0 +1,32 @@
+// handlers/time_range.rs - Shared created_at/updated_at range filters for list endpoints
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+/// `?created_after=`, `?created_before=` and `?updated_after=` of list endpoints
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct TimeRangeFilters {
+    /// Only rows created after this RFC 3339 timestamp
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only rows created before this RFC 3339 timestamp
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only rows changed after this RFC 3339 timestamp, relay and key changes included
+    pub updated_after: Option<DateTime<Utc>>,
+}
+
+impl TimeRangeFilters {
+    /// SQL conditions over `alias.created_at` / `alias.updated_at` (bounds are exclusive)
+    pub fn conditions(&self, alias: &str) -> Vec<String> {
+        let bounds = [
+            ("created_at", ">", self.created_after),
+            ("created_at", "<", self.created_before),
+            ("updated_at", ">", self.updated_after),
+        ];
+        bounds
+            .into_iter()
+            .filter_map(|(column, op, bound)| {
+                bound.map(|at| format!("{}.{} {} '{}'::timestamptz", alias, column, op, at.to_rfc3339()))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,53 @@
+// handlers/ui.rs - Embedded admin UI
+//!
+//! Static single-page app under `/ui`. It has no access of its own: the
+//! browser asks for an API token and calls the admin API with it.
+
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{header, Response, StatusCode},
+    response::{IntoResponse, Redirect},
+    routing::get,
+    Router,
+};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "ui/"]
+struct UiAssets;
+
+const INDEX: &str = "index.html";
+
+/// Routes for `/ui`, `/ui/` and everything below it
+pub fn routes<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    Router::new()
+        .route("/ui", get(|| async { Redirect::permanent("/ui/") }))
+        .route("/ui/", get(|| async { serve(INDEX) }))
+        .route("/ui/{*path}", get(asset))
+}
+
+/// Serve an embedded asset; unknown extensionless paths fall back to the app shell
+async fn asset(Path(path): Path<String>) -> Response<Body> {
+    if UiAssets::get(&path).is_some() {
+        return serve(&path);
+    }
+    if !path.rsplit('/').next().unwrap_or_default().contains('.') {
+        return serve(INDEX);
+    }
+    StatusCode::NOT_FOUND.into_response()
+}
+
+fn serve(path: &str) -> Response<Body> {
+    match UiAssets::get(path) {
+        Some(file) => (
+            [
+                (header::CONTENT_TYPE, file.metadata.mimetype().to_string()),
+                (header::CACHE_CONTROL, "no-cache".to_string()),
+            ],
+            file.data,
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -1,14 +1,21 @@
 // handlers/vouch/default_configs.rs - Default Config CRUD handlers
-use crate::audit::{AuditAction, AuditChanges, RequestContext, ResourceType};
+use crate::audit::{AuditAction, RequestContext, ResourceType};
 use crate::audit_log;
 use crate::errors::ApiError;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::handlers::time_range::TimeRangeFilters;
 use crate::schema::{
-    CreateDefaultConfigRequest, DefaultConfigListItem, DefaultConfigResponse, PaginatedResponse,
-    RelayConfig, UpdateDefaultConfigRequest,
+    ConfigUsageResponse, CreateDefaultConfigRequest, DefaultConfigListItem, DefaultConfigResponse, DeletionPreview,
+    PaginatedResponse, RelayConfig, UpdateDefaultConfigRequest,
 };
+use crate::services::configs::{self, ConfigFilters};
+use crate::services::PageRequest;
+use crate::units::{MinValues, UnitsQuery, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_key, check_relay_keys};
 use crate::AppState;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{OriginalUri, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -30,6 +37,10 @@ pub struct DefaultConfigFilters {
     pub relay_url: Option<String>,
     /// Filter by relay min_value (exact match)
     pub relay_min_value: Option<String>,
+    /// Sort column: name, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
@@ -43,130 +54,78 @@ fn default_limit() -> i64 {
 #[utoipa::path(
     get,
     path = "/api/admin/vouch/configs/default",
-    params(DefaultConfigFilters),
+    params(DefaultConfigFilters, TimeRangeFilters, UnitsQuery),
     responses(
-        (status = 200, description = "List of default configs", body = PaginatedResponse<DefaultConfigListItem>)
+        (status = 200, description = "List of default configs", body = PaginatedResponse<DefaultConfigListItem>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
     ),
     tag = "Vouch - Default Configs",
     security(("bearer_auth" = []))
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, uri))]
 pub async fn list_default_configs(
     State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
     Query(filters): Query<DefaultConfigFilters>,
-) -> Result<Json<PaginatedResponse<DefaultConfigListItem>>, ApiError> {
+    Query(time_range): Query<TimeRangeFilters>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Page<DefaultConfigListItem>, ApiError> {
     info!("Listing default configs with filters: {:?}", filters);
-
-    let mut conditions = Vec::new();
-
-    if let Some(ref name) = filters.name {
-        conditions.push(format!("c.name LIKE '{}%'", name.replace('\'', "''")));
-    }
-    if let Some(ref fr) = filters.fee_recipient {
-        conditions.push(format!("c.fee_recipient = '{}'", fr.replace('\'', "''")));
-    }
-    if let Some(ref gl) = filters.gas_limit {
-        conditions.push(format!("c.gas_limit = '{}'", gl.replace('\'', "''")));
-    }
-    if let Some(ref mv) = filters.min_value {
-        conditions.push(format!("c.min_value = '{}'", mv.replace('\'', "''")));
-    }
-    if let Some(active) = filters.active {
-        conditions.push(format!("c.active = {}", if active { "true" } else { "false" }));
-    }
-    // Relay filters using EXISTS subquery
-    if let Some(ref relay_url) = filters.relay_url {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_default_relays r WHERE r.config_name = c.name AND r.url LIKE '{}%')",
-            relay_url.replace('\'', "''")
-        ));
-    }
-    if let Some(ref relay_min_value) = filters.relay_min_value {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_default_relays r WHERE r.config_name = c.name AND r.min_value = '{}')",
-            relay_min_value.replace('\'', "''")
-        ));
-    }
-
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
+    check_limit(filters.limit, state.config.api.max_page_size)?;
+
+    let config_filters = ConfigFilters {
+        name: filters.name,
+        fee_recipient: filters.fee_recipient,
+        gas_limit: filters.gas_limit,
+        min_value: filters.min_value,
+        active: filters.active,
+        relay_url: filters.relay_url,
+        relay_min_value: filters.relay_min_value,
+        time_range,
     };
-
-    let count_sql = format!(
-        "SELECT COUNT(*) as count FROM vouch_default_configs c {}",
-        where_clause
-    );
-    let total: i64 = sqlx::query_scalar(&count_sql)
-        .fetch_one(&state.pool)
-        .await?;
-
-    let data_sql = format!(
-        "SELECT c.name, c.fee_recipient, c.gas_limit, c.min_value, c.active, c.created_at, c.updated_at
-         FROM vouch_default_configs c {}
-         ORDER BY c.name ASC
-         LIMIT {} OFFSET {}",
-        where_clause, filters.limit, filters.offset
-    );
-
-    let configs = sqlx::query_as::<_, crate::models::VouchDefaultConfig>(&data_sql)
-        .fetch_all(&state.pool)
-        .await?;
-
-    // Fetch relays for all configs in the result
-    let config_names: Vec<&str> = configs.iter().map(|c| c.name.as_str()).collect();
-    let relays_map = if !config_names.is_empty() {
-        let placeholders: Vec<String> = config_names.iter().enumerate()
-            .map(|(i, _)| format!("${}", i + 1))
-            .collect();
-        let relays_sql = format!(
-            "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
-             FROM vouch_default_relays WHERE config_name IN ({})",
-            placeholders.join(", ")
-        );
-        let mut query = sqlx::query_as::<_, crate::models::VouchDefaultRelay>(&relays_sql);
-        for name in &config_names {
-            query = query.bind(*name);
-        }
-        let all_relays = query.fetch_all(&state.pool).await?;
-
-        // Group relays by config_name
-        let mut map: HashMap<String, HashMap<String, RelayConfig>> = HashMap::new();
-        for relay in all_relays {
-            map.entry(relay.config_name.clone())
-                .or_default()
-                .insert(relay.url.clone(), relay.into());
-        }
-        map
-    } else {
-        HashMap::new()
-    };
-
-    let data: Vec<DefaultConfigListItem> = configs
-        .into_iter()
-        .map(|c| {
-            let relays = relays_map.get(&c.name).cloned();
-            let mut item: DefaultConfigListItem = c.into();
-            item.relays = relays;
-            item
-        })
-        .collect();
-
-    Ok(Json(PaginatedResponse {
-        data,
-        total,
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
         limit: filters.limit,
         offset: filters.offset,
-    }))
+    };
+    let mut response = configs::list(&state.pool, &config_filters, page).await?;
+    response.data.display_min_values(units);
+
+    Ok(Page::new(uri, response))
 }
 
 #[utoipa::path(
-    get,
+    head,
     path = "/api/admin/vouch/configs/default/{name}",
     params(
         ("name" = String, Path, description = "Config name")
     ),
+    responses(
+        (status = 200, description = "Config exists"),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_default_config(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    configs::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}",
+    params(
+        ("name" = String, Path, description = "Config name"),
+        UnitsQuery
+    ),
     responses(
         (status = 200, description = "Default config details", body = DefaultConfigResponse),
         (status = 404, description = "Config not found")
@@ -178,45 +137,36 @@ pub async fn list_default_configs(
 pub async fn get_default_config(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
 ) -> Result<Json<DefaultConfigResponse>, ApiError> {
     info!("Getting default config: {}", name);
 
-    let config = sqlx::query_as::<_, crate::models::VouchDefaultConfig>(
-        "SELECT name, fee_recipient, gas_limit, min_value, active, created_at, updated_at
-         FROM vouch_default_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_optional(&state.pool)
-    .await?
-    .ok_or_else(|| ApiError::NotFound(format!("Default config '{}' not found", name)))?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchDefaultRelay>(
-        "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
-         FROM vouch_default_relays WHERE config_name = $1",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, RelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    Ok(Json(DefaultConfigResponse {
-        name: config.name,
-        fee_recipient: config.fee_recipient,
-        gas_limit: config.gas_limit,
-        min_value: config.min_value,
-        active: config.active,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    }))
+    let mut response = configs::get(&state.pool, &name).await?;
+    response.display_min_values(units);
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/usage",
+    params(
+        ("name" = String, Path, description = "Config name")
+    ),
+    responses(
+        (status = 200, description = "Execution-config fetches per consumer", body = ConfigUsageResponse),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_default_config_usage(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<ConfigUsageResponse>, ApiError> {
+    info!("Getting usage of default config: {}", name);
+
+    Ok(Json(configs::usage(&state.pool, &name).await?))
 }
 
 #[utoipa::path(
@@ -225,6 +175,7 @@ pub async fn get_default_config(
     request_body = CreateDefaultConfigRequest,
     responses(
         (status = 201, description = "Config created", body = DefaultConfigResponse),
+        (status = 400, description = "Unknown parent config or relay set"),
         (status = 409, description = "Config already exists")
     ),
     tag = "Vouch - Default Configs",
@@ -234,109 +185,21 @@ pub async fn get_default_config(
 pub async fn create_default_config(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
-    Json(req): Json<CreateDefaultConfigRequest>,
+    Json(mut req): Json<CreateDefaultConfigRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
     info!("Creating default config: {}", req.name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config already exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM vouch_default_configs WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing > 0 {
-        return Err(ApiError::InvalidData(format!(
-            "Config '{}' already exists",
-            req.name
-        )));
-    }
-
-    sqlx::query(
-        "INSERT INTO vouch_default_configs (name, fee_recipient, gas_limit, min_value, active)
-         VALUES ($1, $2, $3, $4, $5)",
-    )
-    .bind(&req.name)
-    .bind(&req.fee_recipient)
-    .bind(&req.gas_limit)
-    .bind(&req.min_value)
-    .bind(req.active)
-    .execute(&mut *tx)
-    .await?;
-
-    if let Some(relays) = &req.relays {
-        for (url, relay) in relays {
-            sqlx::query(
-                "INSERT INTO vouch_default_relays
-                 (config_name, url, public_key, fee_recipient, gas_limit, min_value)
-                 VALUES ($1, $2, $3, $4, $5, $6)",
-            )
-            .bind(&req.name)
-            .bind(url)
-            .bind(&relay.public_key)
-            .bind(&relay.fee_recipient)
-            .bind(&relay.gas_limit)
-            .bind(&relay.min_value)
-            .execute(&mut *tx)
-            .await?;
-        }
-    }
-
-    tx.commit().await?;
+    let (mut response, changes) = configs::create(&state.pool, &req).await?;
+    response.display_min_values(ValueUnits::Eth);
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            fee_recipient: req.fee_recipient.as_ref().map(|a| a.to_string()),
-            min_value: req.min_value.clone(),
-            gas_limit: req.gas_limit.clone(),
-            active: Some(req.active),
-            relays_count: req.relays.as_ref().map(|r| r.len()),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Create, ResourceType::VouchDefaultConfig, &req.name, changes);
     }
 
-    // Fetch the created config
-    let config = sqlx::query_as::<_, crate::models::VouchDefaultConfig>(
-        "SELECT name, fee_recipient, gas_limit, min_value, active, created_at, updated_at
-         FROM vouch_default_configs WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchDefaultRelay>(
-        "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
-         FROM vouch_default_relays WHERE config_name = $1",
-    )
-    .bind(&req.name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, RelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    let response = DefaultConfigResponse {
-        name: config.name,
-        fee_recipient: config.fee_recipient,
-        gas_limit: config.gas_limit,
-        min_value: config.min_value,
-        active: config.active,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    };
-
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -349,6 +212,7 @@ pub async fn create_default_config(
     request_body = UpdateDefaultConfigRequest,
     responses(
         (status = 200, description = "Config updated", body = DefaultConfigResponse),
+        (status = 400, description = "Unknown parent config or relay set, or parent cycle"),
         (status = 404, description = "Config not found")
     ),
     tag = "Vouch - Default Configs",
@@ -359,131 +223,22 @@ pub async fn update_default_config(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
     Path(name): Path<String>,
-    Json(req): Json<UpdateDefaultConfigRequest>,
+    Json(mut req): Json<UpdateDefaultConfigRequest>,
 ) -> Result<Json<DefaultConfigResponse>, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
     info!("Updating default config: {}", name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if config exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM vouch_default_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Default config '{}' not found",
-            name
-        )));
-    }
-
-    // Build update query dynamically
-    let mut updates = Vec::new();
-    if req.fee_recipient.is_some() {
-        updates.push("fee_recipient = $2");
-    }
-    if req.gas_limit.is_some() {
-        updates.push("gas_limit = $3");
-    }
-    if req.min_value.is_some() {
-        updates.push("min_value = $4");
-    }
-    if req.active.is_some() {
-        updates.push("active = $5");
-    }
-
-    if !updates.is_empty() {
-        sqlx::query(&format!(
-            "UPDATE vouch_default_configs SET {} WHERE name = $1",
-            updates.join(", ")
-        ))
-        .bind(&name)
-        .bind(&req.fee_recipient)
-        .bind(&req.gas_limit)
-        .bind(&req.min_value)
-        .bind(&req.active)
-        .execute(&mut *tx)
-        .await?;
-    }
-
-    // Handle relays if provided
-    if let Some(relays) = &req.relays {
-        sqlx::query("DELETE FROM vouch_default_relays WHERE config_name = $1")
-            .bind(&name)
-            .execute(&mut *tx)
-            .await?;
-
-        for (url, relay) in relays {
-            sqlx::query(
-                "INSERT INTO vouch_default_relays
-                 (config_name, url, public_key, fee_recipient, gas_limit, min_value)
-                 VALUES ($1, $2, $3, $4, $5, $6)",
-            )
-            .bind(&name)
-            .bind(url)
-            .bind(&relay.public_key)
-            .bind(&relay.fee_recipient)
-            .bind(&relay.gas_limit)
-            .bind(&relay.min_value)
-            .execute(&mut *tx)
-            .await?;
-        }
-    }
-
-    tx.commit().await?;
+    let (mut response, changes) = configs::update(&state.pool, &name, &req).await?;
+    response.display_min_values(ValueUnits::Eth);
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            fee_recipient: req.fee_recipient.as_ref().map(|a| a.to_string()),
-            min_value: req.min_value.clone(),
-            gas_limit: req.gas_limit.clone(),
-            active: req.active,
-            relays_count: req.relays.as_ref().map(|r| r.len()),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Update, ResourceType::VouchDefaultConfig, &name, changes);
     }
 
-    // Fetch updated config
-    let config = sqlx::query_as::<_, crate::models::VouchDefaultConfig>(
-        "SELECT name, fee_recipient, gas_limit, min_value, active, created_at, updated_at
-         FROM vouch_default_configs WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchDefaultRelay>(
-        "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
-         FROM vouch_default_relays WHERE config_name = $1",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, RelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    Ok(Json(DefaultConfigResponse {
-        name: config.name,
-        fee_recipient: config.fee_recipient,
-        gas_limit: config.gas_limit,
-        min_value: config.min_value,
-        active: config.active,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: config.created_at,
-        updated_at: config.updated_at,
-    }))
+    Ok(Json(response))
 }
 
 #[utoipa::path(
@@ -494,7 +249,8 @@ pub async fn update_default_config(
     ),
     responses(
         (status = 204, description = "Config deleted"),
-        (status = 404, description = "Config not found")
+        (status = 404, description = "Config not found"),
+        (status = 409, description = "Config is the parent of other configs")
     ),
     tag = "Vouch - Default Configs",
     security(("bearer_auth" = []))
@@ -507,21 +263,130 @@ pub async fn delete_default_config(
 ) -> Result<impl IntoResponse, ApiError> {
     info!("Deleting default config: {}", name);
 
-    let result = sqlx::query("DELETE FROM vouch_default_configs WHERE name = $1")
-        .bind(&name)
-        .execute(&state.pool)
-        .await?;
+    let changes = configs::delete(&state.pool, &name).await?;
 
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Default config '{}' not found",
-            name
-        )));
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchDefaultConfig, &name, changes);
     }
 
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/dependents",
+    params(
+        ("name" = String, Path, description = "Config name")
+    ),
+    responses(
+        (status = 200, description = "What deleting the config would remove or be blocked by", body = DeletionPreview),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_default_config_dependents(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<DeletionPreview>, ApiError> {
+    info!("Previewing deletion of default config: {}", name);
+
+    Ok(Json(configs::dependents(&state.pool, &name).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/relays",
+    params(
+        ("name" = String, Path, description = "Config name"),
+        UnitsQuery
+    ),
+    responses(
+        (status = 200, description = "Relays of the config keyed by URL", body = HashMap<String, RelayConfig>),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_default_config_relays(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Json<HashMap<String, RelayConfig>>, ApiError> {
+    let mut relays = configs::relays(&state.pool, &name).await?;
+    relays.display_min_values(units);
+    Ok(Json(relays))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/vouch/configs/default/{name}/relays/{url}",
+    params(
+        ("name" = String, Path, description = "Config name"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    request_body = RelayConfig,
+    responses(
+        (status = 200, description = "Relay added or replaced; all relays of the config keyed by URL", body = HashMap<String, RelayConfig>),
+        (status = 400, description = "Invalid relay settings"),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn put_default_config_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, url)): Path<(String, String)>,
+    Json(mut relay): Json<RelayConfig>,
+) -> Result<Json<HashMap<String, RelayConfig>>, ApiError> {
+    relay.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut relay)?;
+    check_relay_key(&state.config.validation, &url, &relay.public_key)?;
+    info!("Setting relay {} for default config: {}", url, name);
+
+    let (mut relays, changes) = configs::put_relay(&state.pool, &name, &url, &relay).await?;
+    relays.display_min_values(ValueUnits::Eth);
+
     // Audit log
     if state.config.audit_enabled {
-        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchDefaultConfig, &name);
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchDefaultConfig, &name, changes);
+    }
+
+    Ok(Json(relays))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/vouch/configs/default/{name}/relays/{url}",
+    params(
+        ("name" = String, Path, description = "Config name"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    responses(
+        (status = 204, description = "Relay removed from the config"),
+        (status = 404, description = "Config or relay not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_default_config_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, url)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Removing relay {} from default config: {}", url, name);
+
+    let changes = configs::delete_relay(&state.pool, &name, &url).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchDefaultConfig, &name, changes);
     }
 
     Ok(StatusCode::NO_CONTENT)
@@ -1,20 +1,70 @@
 // handlers/vouch/execution_config.rs - Public execution config endpoint
 use crate::addresses::BlsPubkey;
+use crate::audit::{AccessEvent, PublicRead, RequestContext};
+use crate::auth::access::{require_access, AccessResource};
 use crate::errors::ApiError;
-use crate::schema::{ExecutionConfigResponse, ProposerEntry, RelayConfig};
+use crate::handlers::deprecation::Deprecation;
+use crate::handlers::export_destination::{store_export, ExportDestination};
+use crate::handlers::extract::KeyList;
+use crate::handlers::relays::load_disabled_relays;
+use crate::handlers::streaming::json_body;
+use crate::metrics;
+use crate::models::VouchDefaultConfig;
+use crate::schema::{
+    BatchExecutionConfigRequest, BuilderConfigV1, ConfigSourceKind, ExecutionConfigExplanation,
+    ExecutionConfigResponse, ExecutionConfigV1Response, ExplainedValue, KeyExplanation,
+    ProposerConfigV1, ProposerEntry, RelayConfig, StoredExport,
+};
 use crate::AppState;
+use crate::response_cache::{VersionScope, VersionedRequest};
+use crate::services::configs::{load_config_chain, merge_config_chain};
+use crate::services::groups::{group_for, load_groups, ResolvedGroup};
+use crate::services::relay_sets::{expand_relay_sets, load_relay_sets};
+use crate::units::{MinValues, ValueUnits};
 use axum::{
     extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::Deserialize;
-use std::collections::HashMap;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize)]
 pub struct ExecutionConfigQuery {
     pub tags: Option<String>,
+    #[serde(default)]
+    pub tags_match: TagsMatch,
+}
+
+impl ExecutionConfigQuery {
+    /// Request parameters that shape the response, for ETags and the response cache
+    fn fingerprint(&self, keys: &[BlsPubkey]) -> String {
+        let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+        format!(
+            "{}|{:?}|{}",
+            self.tags.as_deref().unwrap_or_default(),
+            self.tags_match,
+            keys.join(",")
+        )
+    }
+}
+
+/// How requested tags select proposer patterns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TagsMatch {
+    /// Patterns carrying at least one of the tags
+    #[default]
+    Any,
+    /// Patterns carrying every one of the tags
+    All,
 }
 
 #[utoipa::path(
@@ -22,97 +72,990 @@ pub struct ExecutionConfigQuery {
     path = "/vouch/v2/execution-config/{config}",
     params(
         ("config" = String, Path, description = "Default config name"),
-        ("tags" = Option<String>, Query, description = "Comma-separated list of tags")
+        ("tags" = Option<String>, Query, description = "Comma-separated list of tags"),
+        ("tags_match" = Option<TagsMatch>, Query, description = "Select patterns with any (default) or all of the tags")
+    ),
+    request_body(
+        description = "Proposer public keys: a JSON array, or one key per line as `text/plain` or in the files of a `multipart/form-data` upload (blank lines and `#` comments are skipped)",
+        content(
+            (Vec<BlsPubkey> = "application/json"),
+            (String = "text/plain"),
+            (String = "multipart/form-data")
+        )
     ),
-    request_body = Vec<BlsPubkey>,
     responses(
         (status = 200, description = "Execution configuration", body = ExecutionConfigResponse),
-        (status = 404, description = "Config not found")
+        (status = 404, description = "Config not found"),
+        (status = 401, description = "The config has access tokens and the request carries none of them")
     ),
     tag = "Vouch - Public"
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, ctx))]
 pub async fn get_execution_config(
     State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
     Path(config_name): Path<String>,
     Query(query): Query<ExecutionConfigQuery>,
-    Json(keys): Json<Vec<BlsPubkey>>,
-) -> Result<Json<ExecutionConfigResponse>, ApiError> {
+    headers: HeaderMap,
+    KeyList(keys): KeyList,
+) -> Result<Response, ApiError> {
     info!(
-        "Getting execution config: {} with tags: {:?}, keys: {}",
+        "Getting execution config: {} with tags: {:?} ({:?}), keys: {}",
         config_name,
         query.tags,
+        query.tags_match,
         keys.len()
     );
 
-    // 1. Load default config
-    let default_config = sqlx::query_as::<_, crate::models::VouchDefaultConfig>(
-        "SELECT name, fee_recipient, gas_limit, min_value, active, created_at, updated_at
-         FROM vouch_default_configs WHERE name = $1 AND active = true",
+    require_access(&state.pool, AccessResource::DefaultConfig, &config_name, &headers).await?;
+
+    let fingerprint = format!("v2|{}|{}", config_name, query.fingerprint(&keys));
+    let versioned = VersionedRequest::new(
+        &state.pool,
+        VersionScope::Vouch,
+        &fingerprint,
+        state.config.response_cache_size,
+    )
+    .await?;
+    if versioned.not_modified(&headers) {
+        if state.config.audit_enabled && state.config.audit_public_reads {
+            AccessEvent::new(&ctx, PublicRead::ExecutionConfig, &config_name, keys.len()).log().await;
+        }
+        metrics::count_public_request(PublicRead::ExecutionConfig, &config_name);
+        record_usage(&state, &ctx, &config_name, Some(keys.len()));
+        record_key_usage(&state, &keys);
+        return Ok(versioned.not_modified_response());
+    }
+
+    let response = match versioned.cached::<ExecutionConfigResponse>() {
+        Some(response) => response,
+        None => versioned.store(
+            build_execution_config(
+                &state.pool,
+                &config_name,
+                query.tags.as_deref(),
+                query.tags_match,
+                &keys,
+            )
+            .await?,
+        ),
+    };
+
+    if state.config.audit_enabled && state.config.audit_public_reads {
+        AccessEvent::new(&ctx, PublicRead::ExecutionConfig, &config_name, keys.len())
+            .with_proposer_count(response.proposers.as_ref().map_or(0, Vec::len))
+            .log()
+            .await;
+    }
+    metrics::count_public_request(PublicRead::ExecutionConfig, &config_name);
+    record_usage(&state, &ctx, &config_name, Some(keys.len()));
+    record_key_usage(&state, &keys);
+    Ok((versioned.etag_header(), Json(&*response)).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/vouch/v2/execution-config:batch",
+    params(
+        ("tags" = Option<String>, Query, description = "Comma-separated list of tags"),
+        ("tags_match" = Option<TagsMatch>, Query, description = "Select patterns with any (default) or all of the tags")
+    ),
+    request_body = BatchExecutionConfigRequest,
+    responses(
+        (status = 200, description = "Execution configuration per config name", body = HashMap<String, ExecutionConfigResponse>),
+        (status = 404, description = "One of the configs was not found"),
+        (status = 401, description = "The one of the configs has access tokens and the request carries none of them")
+    ),
+    tag = "Vouch - Public"
+)]
+#[instrument(skip(state, ctx, req))]
+pub async fn get_execution_configs_batch(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(query): Query<ExecutionConfigQuery>,
+    headers: HeaderMap,
+    Json(req): Json<BatchExecutionConfigRequest>,
+) -> Result<Response, ApiError> {
+    info!(
+        "Getting batch execution config: {:?} with tags: {:?} ({:?}), keys: {}",
+        req.configs,
+        query.tags,
+        query.tags_match,
+        req.keys.len()
+    );
+
+    for config_name in &req.configs {
+        require_access(&state.pool, AccessResource::DefaultConfig, config_name, &headers).await?;
+    }
+
+    let fingerprint = format!(
+        "batch|{}|{}",
+        req.configs.join(","),
+        query.fingerprint(&req.keys)
+    );
+    let versioned = VersionedRequest::new(
+        &state.pool,
+        VersionScope::Vouch,
+        &fingerprint,
+        state.config.response_cache_size,
+    )
+    .await?;
+    // Counts the reads and returns the audit events to log for them
+    let count_reads = |responses: Option<&HashMap<String, ExecutionConfigResponse>>| {
+        record_key_usage(&state, &req.keys);
+        let mut events = Vec::new();
+        let mut logged = HashSet::new();
+        for config_name in req.configs.iter().filter(|name| logged.insert(*name)) {
+            metrics::count_public_request(PublicRead::ExecutionConfig, config_name);
+            record_usage(&state, &ctx, config_name, Some(req.keys.len()));
+            if !(state.config.audit_enabled && state.config.audit_public_reads) {
+                continue;
+            }
+            let mut event =
+                AccessEvent::new(&ctx, PublicRead::ExecutionConfig, config_name, req.keys.len());
+            if let Some(response) = responses.and_then(|r| r.get(config_name)) {
+                event = event.with_proposer_count(response.proposers.as_ref().map_or(0, Vec::len));
+            }
+            events.push(event);
+        }
+        events
+    };
+    if versioned.not_modified(&headers) {
+        for event in count_reads(None) {
+            event.log().await;
+        }
+        return Ok(versioned.not_modified_response());
+    }
+
+    let responses = match versioned.cached::<HashMap<String, ExecutionConfigResponse>>() {
+        Some(responses) => responses,
+        None => {
+            let mut responses = HashMap::with_capacity(req.configs.len());
+            for config_name in &req.configs {
+                if responses.contains_key(config_name) {
+                    continue;
+                }
+                let response = build_execution_config(
+                    &state.pool,
+                    config_name,
+                    query.tags.as_deref(),
+                    query.tags_match,
+                    &req.keys,
+                )
+                .await?;
+                responses.insert(config_name.clone(), response);
+            }
+            versioned.store(responses)
+        }
+    };
+
+    for event in count_reads(Some(&responses)) {
+        event.log().await;
+    }
+    Ok((versioned.etag_header(), Json(&*responses)).into_response())
+}
+
+/// Deprecation of the legacy v1 endpoint as of `vouch.v1_deprecated_at`, linking the
+/// v2 endpoint of the same config (a POST taking the keys, not a drop-in GET)
+pub fn v1_deprecation(since: DateTime<Utc>) -> Deprecation {
+    Deprecation::new(since).moved("/vouch/v1/execution-config/", "/vouch/v2/execution-config/")
+}
+
+/// Query parameters of the v1 execution config
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExecutionConfigV1Query {
+    /// List the relays left out because they are disabled in `disabled_relays` of each builder config
+    #[serde(default)]
+    pub include_disabled: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/vouch/v1/execution-config/{config}",
+    params(
+        ("config" = String, Path, description = "Default config name"),
+        ExecutionConfigV1Query
+    ),
+    responses(
+        (status = 200, description = "Execution configuration in the legacy v1 format", body = ExecutionConfigV1Response),
+        (status = 404, description = "Config not found"),
+        (status = 401, description = "The config has access tokens and the request carries none of them")
+    ),
+    tag = "Vouch - Public"
+)]
+#[instrument(skip(state, ctx))]
+pub async fn get_execution_config_v1(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(config_name): Path<String>,
+    Query(query): Query<ExecutionConfigV1Query>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    info!(
+        "Getting v1 execution config: {} (include_disabled: {})",
+        config_name, query.include_disabled
+    );
+
+    require_access(&state.pool, AccessResource::DefaultConfig, &config_name, &headers).await?;
+
+    let versioned = VersionedRequest::new(
+        &state.pool,
+        VersionScope::Vouch,
+        &format!("v1|{}|{}", config_name, query.include_disabled),
+        state.config.response_cache_size,
+    )
+    .await?;
+    if versioned.not_modified(&headers) {
+        if state.config.audit_enabled && state.config.audit_public_reads {
+            AccessEvent::new(&ctx, PublicRead::ExecutionConfigV1, &config_name, 0).log().await;
+        }
+        metrics::count_public_request(PublicRead::ExecutionConfigV1, &config_name);
+        record_usage(&state, &ctx, &config_name, None);
+        return Ok(versioned.not_modified_response());
+    }
+
+    let response = match versioned.cached::<ExecutionConfigV1Response>() {
+        Some(response) => response,
+        None => versioned.store(
+            build_execution_config_v1(&state.pool, &config_name, query.include_disabled).await?,
+        ),
+    };
+
+    if state.config.audit_enabled && state.config.audit_public_reads {
+        let count = response.proposer_config.len();
+        AccessEvent::new(&ctx, PublicRead::ExecutionConfigV1, &config_name, count)
+            .with_proposer_count(count)
+            .log()
+            .await;
+    }
+    metrics::count_public_request(PublicRead::ExecutionConfigV1, &config_name);
+    record_usage(&state, &ctx, &config_name, Some(response.proposer_config.len()));
+    Ok((versioned.etag_header(), Json(&*response)).into_response())
+}
+
+/// File formats a default config can be exported in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+pub enum ExportFormat {
+    /// Vouch static `executionconfig` JSON (version 2)
+    #[default]
+    #[serde(rename = "vouch-json")]
+    VouchJson,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    /// Output format (default: vouch-json)
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Comma-separated tags selecting the proposer patterns to include
+    pub tags: Option<String>,
+    /// Select patterns with any (default) or all of the tags
+    #[serde(default)]
+    pub tags_match: TagsMatch,
+    /// `response` (default) or `storage` for the `export_storage` bucket
+    #[serde(default)]
+    #[param(inline)]
+    pub destination: ExportDestination,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/configs/default/{name}/export",
+    params(
+        ("name" = String, Path, description = "Config name"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "Execution config file covering every active proposer", body = ExecutionConfigResponse),
+        (status = 201, description = "Written to the export_storage bucket", body = StoredExport),
+        (status = 400, description = "Unknown format, or destination=storage without export_storage"),
+        (status = 502, description = "The export_storage bucket rejected the file"),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn export_execution_config(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    info!("Exporting execution config: {} as {:?}", name, query.format);
+
+    let config = export_config(&state.pool, &name, query.tags.as_deref(), query.tags_match).await?;
+    if query.destination == ExportDestination::Storage {
+        return store_export(&state.config, &format!("execution-config/{}", name), &config, true).await;
+    }
+
+    // Every proposer makes this the largest response, so it is serialized as it is sent
+    let body = match query.format {
+        ExportFormat::VouchJson => json_body(move |writer| serde_json::to_writer_pretty(writer, &config)),
+    };
+
+    let disposition = format!("attachment; filename=\"{}-execution-config.json\"", name);
+    Ok((
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&disposition)
+                    .map_err(|_| ApiError::InvalidData(format!("Config name '{}' cannot be used as a file name", name)))?,
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Execution config file of a default config, covering every active proposer
+pub(crate) async fn export_config(
+    pool: &PgPool,
+    name: &str,
+    tags: Option<&str>,
+    tags_match: TagsMatch,
+) -> Result<ExecutionConfigResponse, ApiError> {
+    // A static file cannot ask for keys, so it carries every proposer
+    let keys: Vec<BlsPubkey> = sqlx::query_scalar!(
+        r#"SELECT public_key as "public_key: BlsPubkey" FROM vouch_proposers WHERE decommissioned_at IS NULL ORDER BY public_key"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    build_execution_config(pool, name, tags, tags_match, &keys).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/execution-config/{config}/explain",
+    params(
+        ("config" = String, Path, description = "Default config name"),
+        ("tags" = Option<String>, Query, description = "Comma-separated list of tags"),
+        ("tags_match" = Option<TagsMatch>, Query, description = "Select patterns with any (default) or all of the tags")
+    ),
+    request_body = Vec<BlsPubkey>,
+    responses(
+        (status = 200, description = "Merged values per key with the resource that set each", body = ExecutionConfigExplanation),
+        (status = 404, description = "Config not found")
+    ),
+    tag = "Vouch - Default Configs",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, keys))]
+pub async fn explain_execution_config(
+    State(state): State<Arc<AppState>>,
+    Path(config_name): Path<String>,
+    Query(query): Query<ExecutionConfigQuery>,
+    Json(keys): Json<Vec<BlsPubkey>>,
+) -> Result<Json<ExecutionConfigExplanation>, ApiError> {
+    info!("Explaining execution config: {} for {} keys", config_name, keys.len());
+
+    let (mut response, default_origins, origins) = build_traced_execution_config(
+        &state.pool,
+        &config_name,
+        query.tags.as_deref(),
+        query.tags_match,
+        &keys,
     )
-    .bind(&config_name)
-    .fetch_optional(&state.pool)
-    .await?
-    .ok_or_else(|| ApiError::NotFound(format!("Default config '{}' not found", config_name)))?;
-
-    // 2. Load default relays
-    let default_relays = sqlx::query_as::<_, crate::models::VouchDefaultRelay>(
-        "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
-         FROM vouch_default_relays WHERE config_name = $1",
+    .await?;
+    response.display_min_values(ValueUnits::Eth);
+    let entries = response.proposers.as_deref().unwrap_or_default();
+    // Vouch matches pattern entries as (unanchored) regexes
+    let patterns: Vec<Option<Regex>> = entries
+        .iter()
+        .zip(&origins)
+        .map(|(entry, origin)| match origin {
+            EntryOrigin::Pattern(_) => Regex::new(&entry.proposer).ok(),
+            _ => None,
+        })
+        .collect();
+
+    let keys = keys
+        .into_iter()
+        .map(|public_key| {
+            let key = public_key.to_string();
+            let entry = entries.iter().zip(&origins).zip(&patterns).find_map(|((entry, origin), pattern)| {
+                let matches = match pattern {
+                    Some(regex) => regex.is_match(&key),
+                    None => entry.proposer == key,
+                };
+                matches.then_some((entry, origin))
+            });
+            explain_key(public_key, &key, &response, &default_origins, entry)
+        })
+        .collect();
+
+    Ok(Json(ExecutionConfigExplanation {
+        config: config_name,
+        keys,
+    }))
+}
+
+/// A merged value: the entry's if it sets one, else the default config's
+fn explained<T: Clone>(
+    key: &str,
+    field: &'static str,
+    entry: Option<(&Option<T>, &EntryOrigin)>,
+    default: &Option<T>,
+    default_origins: &DefaultOrigins,
+) -> Option<ExplainedValue<T>> {
+    if let Some((Some(value), origin)) = entry {
+        let (source, name) = origin.source(key, origin.sets_field(field));
+        return Some(ExplainedValue { value: value.clone(), source, name });
+    }
+    default.clone().map(|value| ExplainedValue {
+        value,
+        source: ConfigSourceKind::Default,
+        name: default_origins.fields.get(field).cloned().unwrap_or_default(),
+    })
+}
+
+/// What Vouch makes of the defaults and the entry matching one key
+fn explain_key(
+    public_key: BlsPubkey,
+    key: &str,
+    response: &ExecutionConfigResponse,
+    default_origins: &DefaultOrigins,
+    entry: Option<(&ProposerEntry, &EntryOrigin)>,
+) -> KeyExplanation {
+    let reset_relays = entry.is_some_and(|(entry, _)| entry.reset_relays == Some(true));
+    let mut relays = HashMap::new();
+    if !reset_relays {
+        for (url, relay) in response.relays.iter().flatten() {
+            let value = ExplainedValue {
+                value: relay.clone(),
+                source: ConfigSourceKind::Default,
+                name: default_origins.relays.get(url).cloned().unwrap_or_default(),
+            };
+            relays.insert(url.clone(), value);
+        }
+    }
+    if let Some((entry, origin)) = entry {
+        for (url, relay) in entry.relays.iter().flatten() {
+            let (source, name) = origin.source(key, origin.sets_relay(url));
+            relays.insert(url.clone(), ExplainedValue { value: relay.clone(), source, name });
+        }
+    }
+
+    KeyExplanation {
+        entry: entry.map(|(entry, _)| entry.proposer.clone()),
+        fee_recipient: explained(
+            key,
+            "fee_recipient",
+            entry.map(|(e, o)| (&e.fee_recipient, o)),
+            &response.fee_recipient,
+            default_origins,
+        ),
+        gas_limit: explained(key, "gas_limit", entry.map(|(e, o)| (&e.gas_limit, o)), &response.gas_limit, default_origins),
+        min_value: explained(key, "min_value", entry.map(|(e, o)| (&e.min_value, o)), &response.min_value, default_origins),
+        builder_enabled: explained(
+            key,
+            "builder_enabled",
+            entry.map(|(e, o)| (&e.builder_enabled, o)),
+            &response.builder_enabled,
+            default_origins,
+        ),
+        builder_boost_factor: explained(
+            key,
+            "builder_boost_factor",
+            entry.map(|(e, o)| (&e.builder_boost_factor, o)),
+            &response.builder_boost_factor,
+            default_origins,
+        ),
+        grace: explained(key, "grace", None, &response.grace, default_origins),
+        reset_relays,
+        relays,
+        public_key,
+    }
+}
+
+/// Count a served config towards its usage stats without holding up the response.
+/// `key_count` is `None` when the request does not say how many keys it covers.
+fn record_usage(state: &AppState, ctx: &RequestContext, config_name: &str, key_count: Option<usize>) {
+    if !state.config.vouch.track_usage {
+        return;
+    }
+    // Signed requests name their consumer; everything else is told apart by address
+    let consumer = if !ctx.actor.token_name.is_empty() {
+        ctx.actor.token_name.clone()
+    } else {
+        ctx.client_ip
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    let key_count = key_count.map(|count| i32::try_from(count).unwrap_or(i32::MAX));
+    let pool = state.pool.clone();
+    let config_name = config_name.to_string();
+    tokio::spawn(async move {
+        let result = sqlx::query!(
+            "INSERT INTO vouch_config_usage (config_name, consumer, request_count, last_key_count, max_key_count)
+             SELECT name, $2, 1, COALESCE($3, 0), COALESCE($3, 0)
+             FROM vouch_default_configs WHERE name = $1
+             ON CONFLICT (config_name, consumer) DO UPDATE SET
+                request_count = vouch_config_usage.request_count + 1,
+                last_key_count = COALESCE($3, vouch_config_usage.last_key_count),
+                max_key_count = GREATEST(vouch_config_usage.max_key_count, COALESCE($3, 0)),
+                last_requested_at = NOW()",
+            config_name,
+            consumer,
+            key_count
+        )
+        .execute(&pool)
+        .await;
+        if let Err(e) = result {
+            warn!("Failed to record usage of config {}: {}", config_name, e);
+        }
+    });
+}
+
+/// Mark the proposers of requested keys as in use, for the inactive proposer cleanup.
+/// Refreshed at most hourly per key, as Vouch asks for the same keys every few minutes.
+fn record_key_usage(state: &AppState, keys: &[BlsPubkey]) {
+    if !state.config.vouch.track_usage || keys.is_empty() {
+        return;
+    }
+    let pool = state.pool.clone();
+    let keys = keys.to_vec();
+    tokio::spawn(async move {
+        let result = sqlx::query!(
+            "INSERT INTO vouch_proposer_usage (public_key)
+             SELECT public_key FROM vouch_proposers WHERE public_key = ANY($1)
+             ON CONFLICT (public_key) DO UPDATE SET last_requested_at = NOW()
+             WHERE vouch_proposer_usage.last_requested_at < NOW() - INTERVAL '1 hour'",
+            &keys as _
+        )
+        .execute(&pool)
+        .await;
+        if let Err(e) = result {
+            warn!("Failed to record usage of {} proposer keys: {}", keys.len(), e);
+        }
+    });
+}
+
+async fn build_execution_config_v1(
+    pool: &PgPool,
+    config_name: &str,
+    include_disabled: bool,
+) -> Result<ExecutionConfigV1Response, ApiError> {
+    let (default_config, relays_map, _) = resolve_default_config(pool, config_name).await?;
+    // v1 has no disabled flag, so disabled relays are left out (and listed on request)
+    let globally_disabled = load_disabled_relays(pool).await?;
+    let (mut default_relays, mut default_disabled): (Vec<String>, Vec<String>) = relays_map
+        .into_keys()
+        .partition(|url| !globally_disabled.contains(url));
+    default_relays.sort();
+    default_disabled.sort();
+
+    let groups = load_groups(pool).await?;
+    let proposers = sqlx::query_as!(
+        crate::models::VouchProposer,
+        r#"SELECT public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels as "labels: _", relay_sets, decommissioned_at, id, created_at, updated_at
+           FROM vouch_proposers"#
     )
-    .bind(&config_name)
-    .fetch_all(&state.pool)
+    .fetch_all(pool)
     .await?;
+    // Decommissioned proposers are known too, so their groups do not bring them back
+    let known: HashSet<String> = proposers.iter().map(|p| p.public_key.to_string()).collect();
+
+    let proposer_relays = sqlx::query!("SELECT proposer_public_key, url, disabled FROM vouch_proposer_relays")
+        .fetch_all(pool)
+        .await?;
+    let mut relays_by_proposer: HashMap<String, HashMap<String, bool>> = HashMap::new();
+    for relay in proposer_relays {
+        relays_by_proposer.entry(relay.proposer_public_key).or_default().insert(relay.url, relay.disabled);
+    }
+    let set_names: Vec<String> = proposers.iter().flat_map(|p| p.relay_sets.clone()).collect();
+    let sets = load_relay_sets(&mut *pool.acquire().await?, &set_names).await?;
+
+    // Served and disabled relays of an entry, resolved against the defaults
+    let entry_relays = |disabled_by_url: HashMap<String, bool>, reset_relays: bool| {
+        let (own_relays, own_disabled): (Vec<_>, Vec<_>) = disabled_by_url
+            .into_iter()
+            .partition(|(url, disabled)| !disabled && !globally_disabled.contains(url));
+        let mut own_relays: Vec<String> = own_relays.into_iter().map(|(url, _)| url).collect();
+        own_relays.sort();
+        // Without reset_relays the proposer's relays extend the defaults
+        let relays = if reset_relays {
+            own_relays
+        } else {
+            let mut relays = default_relays.clone();
+            relays.extend(own_relays.into_iter().filter(|url| !default_relays.contains(url)));
+            relays
+        };
+        let mut disabled: Vec<String> = own_disabled
+            .into_iter()
+            .map(|(url, _)| url)
+            .chain(if reset_relays { Vec::new() } else { default_disabled.clone() })
+            .filter(|url| !relays.contains(url))
+            .collect();
+        disabled.sort();
+        disabled.dedup();
+        (relays, disabled)
+    };
 
-    let relays_map: HashMap<String, RelayConfig> = default_relays
+    let mut proposer_config: HashMap<String, ProposerConfigV1> = proposers
         .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
+        .filter(|proposer| proposer.decommissioned_at.is_none())
+        .map(|proposer| {
+            let public_key = proposer.public_key.to_string();
+            let group = group_for(&groups, &public_key);
+            // Group and relay set URLs count as the proposer's own unless disabled by one of its relays
+            let mut disabled_by_url: HashMap<String, bool> = group
+                .map(|g| g.relays.clone())
+                .unwrap_or_default()
+                .into_keys()
+                .chain(expand_relay_sets(&proposer.relay_sets, &sets).into_keys())
+                .map(|url| (url, false))
+                .collect();
+            disabled_by_url.extend(relays_by_proposer.remove(&public_key).unwrap_or_default());
+            let group = group.map(|g| &g.group);
+            let (relays, disabled) = entry_relays(
+                disabled_by_url,
+                proposer.reset_relays || group.is_some_and(|g| g.reset_relays),
+            );
+            let config = ProposerConfigV1 {
+                fee_recipient: proposer
+                    .fee_recipient
+                    .or_else(|| group.and_then(|g| g.fee_recipient.clone()))
+                    .or(default_config.fee_recipient.clone()),
+                gas_limit: proposer
+                    .gas_limit
+                    .or_else(|| group.and_then(|g| g.gas_limit.clone()))
+                    .or(default_config.gas_limit.clone()),
+                builder: Some(builder_config_v1(
+                    relays,
+                    include_disabled.then_some(disabled),
+                    proposer
+                        .builder_enabled
+                        .or_else(|| group.and_then(|g| g.builder_enabled))
+                        .or(default_config.builder_enabled),
+                    default_config.grace.clone(),
+                )),
+            };
+            (public_key, config)
+        })
         .collect();
 
-    // 3. Load proposer-specific configs for requested keys
+    // Listed group members without a proposer entry; v1 has no patterns, so keys
+    // only a group pattern selects are left out
+    for group in &groups {
+        for key in group.keys.iter().filter(|key| !known.contains(*key)) {
+            if proposer_config.contains_key(key) || !group_for(&groups, key).is_some_and(|g| std::ptr::eq(g, group)) {
+                continue;
+            }
+            let (relays, disabled) = entry_relays(
+                group.relays.keys().map(|url| (url.clone(), false)).collect(),
+                group.group.reset_relays,
+            );
+            let config = ProposerConfigV1 {
+                fee_recipient: group.group.fee_recipient.clone().or(default_config.fee_recipient.clone()),
+                gas_limit: group.group.gas_limit.clone().or(default_config.gas_limit.clone()),
+                builder: Some(builder_config_v1(
+                    relays,
+                    include_disabled.then_some(disabled),
+                    group.group.builder_enabled.or(default_config.builder_enabled),
+                    default_config.grace.clone(),
+                )),
+            };
+            proposer_config.insert(key.clone(), config);
+        }
+    }
+
+    Ok(ExecutionConfigV1Response {
+        default_config: ProposerConfigV1 {
+            fee_recipient: default_config.fee_recipient,
+            gas_limit: default_config.gas_limit,
+            builder: Some(builder_config_v1(
+                default_relays,
+                include_disabled.then_some(default_disabled),
+                default_config.builder_enabled,
+                default_config.grace,
+            )),
+        },
+        proposer_config,
+    })
+}
+
+/// Builder is enabled when explicitly configured, otherwise whenever relays are present
+fn builder_config_v1(
+    relays: Vec<String>,
+    disabled_relays: Option<Vec<String>>,
+    enabled: Option<bool>,
+    grace: Option<String>,
+) -> BuilderConfigV1 {
+    BuilderConfigV1 {
+        enabled: enabled.unwrap_or(!relays.is_empty()),
+        relays,
+        disabled_relays,
+        grace,
+    }
+}
+
+/// Flag relays that are disabled globally, whatever the config says
+fn flag_disabled_relays(relays: &mut HashMap<String, RelayConfig>, disabled: &HashSet<String>) {
+    for (url, relay) in relays.iter_mut() {
+        if disabled.contains(url) {
+            relay.disabled = true;
+        }
+    }
+}
+
+/// Entry for a key that only a proposer group configures
+fn group_entry(key: String, group: &ResolvedGroup, globally_disabled: &HashSet<String>) -> ProposerEntry {
+    let mut relays = group.relays.clone();
+    flag_disabled_relays(&mut relays, globally_disabled);
+    let group_config = &group.group;
+    ProposerEntry {
+        proposer: key,
+        fee_recipient: group_config.fee_recipient.clone(),
+        gas_limit: group_config.gas_limit.clone(),
+        min_value: group_config.min_value.clone(),
+        builder_enabled: group_config.builder_enabled,
+        builder_boost_factor: group_config.builder_boost_factor.clone(),
+        reset_relays: group_config.reset_relays.then_some(true),
+        relays: if relays.is_empty() { None } else { Some(relays) },
+    }
+}
+
+/// Which config of a parent chain set each merged value: field name or relay URL to config name
+#[derive(Debug, Default)]
+struct DefaultOrigins {
+    fields: HashMap<&'static str, String>,
+    relays: HashMap<String, String>,
+}
+
+/// Where one proposer entry of a built execution config comes from
+#[derive(Debug)]
+enum EntryOrigin {
+    /// A proposer row on top of its group; `own_*` is what the row sets itself
+    Proposer {
+        group: Option<String>,
+        own_fields: HashSet<&'static str>,
+        own_relays: HashSet<String>,
+    },
+    /// A requested key that only a group configures
+    Group(String),
+    Pattern(String),
+}
+
+impl EntryOrigin {
+    /// Source of a value of the entry, `own` telling whether a proposer row set it
+    fn source(&self, key: &str, own: bool) -> (ConfigSourceKind, String) {
+        match self {
+            EntryOrigin::Proposer { group: Some(group), .. } if !own => (ConfigSourceKind::Group, group.clone()),
+            EntryOrigin::Proposer { .. } => (ConfigSourceKind::Proposer, key.to_string()),
+            EntryOrigin::Group(name) => (ConfigSourceKind::Group, name.clone()),
+            EntryOrigin::Pattern(name) => (ConfigSourceKind::Pattern, name.clone()),
+        }
+    }
+
+    fn sets_field(&self, field: &str) -> bool {
+        match self {
+            EntryOrigin::Proposer { own_fields, .. } => own_fields.contains(field),
+            _ => true,
+        }
+    }
+
+    fn sets_relay(&self, url: &str) -> bool {
+        match self {
+            EntryOrigin::Proposer { own_relays, .. } => own_relays.contains(url),
+            _ => true,
+        }
+    }
+}
+
+/// Names of the optional fields a proposer or pattern row sets
+fn set_fields(
+    fee_recipient: bool,
+    gas_limit: bool,
+    min_value: bool,
+    builder_enabled: bool,
+    builder_boost_factor: bool,
+    reset_relays: bool,
+) -> HashSet<&'static str> {
+    [
+        ("fee_recipient", fee_recipient),
+        ("gas_limit", gas_limit),
+        ("min_value", min_value),
+        ("builder_enabled", builder_enabled),
+        ("builder_boost_factor", builder_boost_factor),
+        ("reset_relays", reset_relays),
+    ]
+    .into_iter()
+    .filter_map(|(field, set)| set.then_some(field))
+    .collect()
+}
+
+/// An active default config merged with its parent chain, with the merged relays.
+/// Values and relays (by URL) set on a config override those of its parents,
+/// and a config's own relays override those of its relay sets.
+async fn resolve_default_config(
+    pool: &PgPool,
+    config_name: &str,
+) -> Result<(VouchDefaultConfig, HashMap<String, RelayConfig>, DefaultOrigins), ApiError> {
+    let mut conn = pool.acquire().await?;
+    let chain = load_config_chain(&mut conn, config_name).await?;
+    let names: Vec<String> = chain.iter().map(|c| c.name.clone()).collect();
+    let set_names: Vec<String> = chain.iter().flat_map(|c| c.relay_sets.clone()).collect();
+    let relay_sets: Vec<Vec<String>> = chain.iter().map(|c| c.relay_sets.clone()).collect();
+    let mut origins = DefaultOrigins::default();
+    for config in chain.iter().rev() {
+        let fields = [
+            ("fee_recipient", config.fee_recipient.is_some()),
+            ("gas_limit", config.gas_limit.is_some()),
+            ("min_value", config.min_value.is_some()),
+            ("builder_enabled", config.builder_enabled.is_some()),
+            ("builder_boost_factor", config.builder_boost_factor.is_some()),
+            ("grace", config.grace.is_some()),
+        ];
+        for (field, _) in fields.into_iter().filter(|(_, set)| *set) {
+            origins.fields.insert(field, config.name.clone());
+        }
+    }
+    // Only the requested config has to be active, its parents just supply values
+    let config = merge_config_chain(chain)
+        .filter(|config| config.active)
+        .ok_or_else(|| ApiError::NotFound(format!("Default config '{}' not found", config_name)))?;
+
+    let relays = sqlx::query_as!(
+        crate::models::VouchDefaultRelay,
+        r#"SELECT id, config_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value
+           FROM vouch_default_relays WHERE config_name = ANY($1)"#,
+        &names
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let sets = load_relay_sets(&mut conn, &set_names).await?;
+
+    let mut own_relays: HashMap<String, HashMap<String, RelayConfig>> = HashMap::new();
+    for relay in relays {
+        own_relays
+            .entry(relay.config_name.clone())
+            .or_default()
+            .insert(relay.url.clone(), relay.into());
+    }
+
+    // Apply the root first so each child replaces what it redefines
+    let mut relays_map: HashMap<String, RelayConfig> = HashMap::new();
+    for (name, relay_sets) in names.iter().zip(&relay_sets).rev() {
+        let config_relays = expand_relay_sets(relay_sets, &sets)
+            .into_iter()
+            .chain(own_relays.remove(name).unwrap_or_default());
+        for (url, relay) in config_relays {
+            origins.relays.insert(url.clone(), name.clone());
+            relays_map.insert(url, relay);
+        }
+    }
+
+    Ok((config, relays_map, origins))
+}
+
+/// Build the execution config for one default config, the requested keys and tags,
+/// with `min_value`s in ETH as Vouch reads them (they are stored in wei)
+async fn build_execution_config(
+    pool: &PgPool,
+    config_name: &str,
+    tags: Option<&str>,
+    tags_match: TagsMatch,
+    keys: &[BlsPubkey],
+) -> Result<ExecutionConfigResponse, ApiError> {
+    let (mut response, _, _) = build_traced_execution_config(pool, config_name, tags, tags_match, keys).await?;
+    response.display_min_values(ValueUnits::Eth);
+    Ok(response)
+}
+
+/// `build_execution_config`, also telling where the defaults and each proposer entry come from
+async fn build_traced_execution_config(
+    pool: &PgPool,
+    config_name: &str,
+    tags: Option<&str>,
+    tags_match: TagsMatch,
+    keys: &[BlsPubkey],
+) -> Result<(ExecutionConfigResponse, DefaultOrigins, Vec<EntryOrigin>), ApiError> {
+    // 1-2. Load default config and relays, merged with its parents
+    let (default_config, mut relays_map, default_origins) = resolve_default_config(pool, config_name).await?;
+    let globally_disabled = load_disabled_relays(pool).await?;
+    flag_disabled_relays(&mut relays_map, &globally_disabled);
+
+    // 3. Load proposer-specific configs for requested keys, each on top of its group
+    // (decommissioned ones get no entry)
     let mut proposers: Vec<ProposerEntry> = Vec::new();
+    let mut origins: Vec<EntryOrigin> = Vec::new();
 
     if !keys.is_empty() {
-        let proposer_configs = sqlx::query_as::<_, crate::models::VouchProposer>(
-            "SELECT public_key, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-             FROM vouch_proposers WHERE public_key = ANY($1)",
+        let groups = load_groups(pool).await?;
+        let proposer_configs = sqlx::query_as!(
+            crate::models::VouchProposer,
+            r#"SELECT public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels as "labels: _", relay_sets, decommissioned_at, id, created_at, updated_at
+               FROM vouch_proposers WHERE public_key = ANY($1)"#,
+            keys as _
         )
-        .bind(&keys)
-        .fetch_all(&state.pool)
+        .fetch_all(pool)
         .await?;
+        let mut seen: HashSet<String> =
+            proposer_configs.iter().map(|p| p.public_key.to_string()).collect();
+        let set_names: Vec<String> =
+            proposer_configs.iter().flat_map(|p| p.relay_sets.clone()).collect();
+        let sets = load_relay_sets(&mut *pool.acquire().await?, &set_names).await?;
 
         for proposer in proposer_configs {
+            if proposer.decommissioned_at.is_some() {
+                continue;
+            }
+            let group = group_for(&groups, &proposer.public_key.to_string());
             // Load proposer's relays (including disabled - Vouch handles disabled flag)
-            let proposer_relays = sqlx::query_as::<_, crate::models::VouchProposerRelay>(
-                "SELECT id, proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled
-                 FROM vouch_proposer_relays WHERE proposer_public_key = $1",
+            let proposer_relays = sqlx::query_as!(
+                crate::models::VouchProposerRelay,
+                r#"SELECT id, proposer_public_key as "proposer_public_key: _", url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, disabled
+                   FROM vouch_proposer_relays WHERE proposer_public_key = $1"#,
+                proposer.public_key as _
             )
-            .bind(&proposer.public_key)
-            .fetch_all(&state.pool)
+            .fetch_all(pool)
             .await?;
 
-            let proposer_relays_map: HashMap<String, RelayConfig> = proposer_relays
-                .into_iter()
-                .map(|r| {
-                    (
-                        r.url.clone(),
-                        RelayConfig {
-                            public_key: r.public_key,
-                            fee_recipient: r.fee_recipient,
-                            gas_limit: r.gas_limit,
-                            min_value: r.min_value,
-                            disabled: r.disabled,
-                        },
-                    )
-                })
-                .collect();
+            // The proposer's own relays override those of its relay sets, which override the group's
+            let mut proposer_relays_map = group.map(|g| g.relays.clone()).unwrap_or_default();
+            let own_relays_map = expand_relay_sets(&proposer.relay_sets, &sets);
+            let mut own_relays: HashSet<String> = own_relays_map.keys().cloned().collect();
+            own_relays.extend(proposer_relays.iter().map(|r| r.url.clone()));
+            proposer_relays_map.extend(own_relays_map);
+            proposer_relays_map.extend(proposer_relays.into_iter().map(|r| {
+                (
+                    r.url.clone(),
+                    RelayConfig {
+                        public_key: r.public_key,
+                        fee_recipient: r.fee_recipient,
+                        gas_limit: r.gas_limit,
+                        min_value: r.min_value,
+                        disabled: r.disabled,
+                    },
+                )
+            }));
+            flag_disabled_relays(&mut proposer_relays_map, &globally_disabled);
 
+            let group = group.map(|g| &g.group);
+            origins.push(EntryOrigin::Proposer {
+                group: group.map(|g| g.name.clone()),
+                own_fields: set_fields(
+                    proposer.fee_recipient.is_some(),
+                    proposer.gas_limit.is_some(),
+                    proposer.min_value.is_some(),
+                    proposer.builder_enabled.is_some(),
+                    proposer.builder_boost_factor.is_some(),
+                    proposer.reset_relays,
+                ),
+                own_relays,
+            });
             proposers.push(ProposerEntry {
                 proposer: proposer.public_key.to_string(),
-                fee_recipient: proposer.fee_recipient,
-                gas_limit: proposer.gas_limit,
-                min_value: proposer.min_value,
-                reset_relays: if proposer.reset_relays {
+                fee_recipient: proposer.fee_recipient.or_else(|| group.and_then(|g| g.fee_recipient.clone())),
+                gas_limit: proposer.gas_limit.or_else(|| group.and_then(|g| g.gas_limit.clone())),
+                min_value: proposer.min_value.or_else(|| group.and_then(|g| g.min_value.clone())),
+                builder_enabled: proposer.builder_enabled.or_else(|| group.and_then(|g| g.builder_enabled)),
+                builder_boost_factor: proposer
+                    .builder_boost_factor
+                    .or_else(|| group.and_then(|g| g.builder_boost_factor.clone())),
+                reset_relays: if proposer.reset_relays || group.is_some_and(|g| g.reset_relays) {
                     Some(true)
                 } else {
                     None
@@ -124,21 +1067,40 @@ pub async fn get_execution_config(
                 },
             });
         }
+
+        // Group members without a proposer entry of their own get the group's settings
+        for key in keys {
+            let key = key.to_string();
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            if let Some(group) = group_for(&groups, &key) {
+                origins.push(EntryOrigin::Group(group.group.name.clone()));
+                proposers.push(group_entry(key, group, &globally_disabled));
+            }
+        }
     }
 
-    // 4. Load pattern-based configs by tags (OR logic)
+    // 4. Load active pattern-based configs by tags (any tag by default, or all of them)
     // Patterns are sorted by the order of their first matching tag in the request
-    if let Some(tags_str) = &query.tags {
+    if let Some(tags_str) = tags {
         let tags: Vec<&str> = tags_str.split(',').map(|s| s.trim()).collect();
 
         if !tags.is_empty() {
-            let mut pattern_configs = sqlx::query_as::<_, crate::models::VouchProposerPattern>(
-                "SELECT name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-                 FROM vouch_proposer_patterns WHERE tags && $1",
-            )
-            .bind(&tags.iter().map(|s| s.to_string()).collect::<Vec<String>>())
-            .fetch_all(&state.pool)
-            .await?;
+            let tags_condition = match tags_match {
+                TagsMatch::Any => "tags && $1",
+                TagsMatch::All => "tags @> $1",
+            };
+            let pattern_sql = format!(
+                "SELECT name, pattern, tags, fee_recipient, gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, active, note, labels, relay_sets, id, created_at, updated_at
+                 FROM vouch_proposer_patterns WHERE active AND {} ORDER BY name",
+                tags_condition
+            );
+            let mut pattern_configs =
+                sqlx::query_as::<_, crate::models::VouchProposerPattern>(&pattern_sql)
+                    .bind(tags.iter().map(|s| s.to_string()).collect::<Vec<String>>())
+                    .fetch_all(pool)
+                    .await?;
 
             // Sort patterns by the position of their first matching tag in the request
             pattern_configs.sort_by_key(|p| {
@@ -148,27 +1110,33 @@ pub async fn get_execution_config(
                     .min()
                     .unwrap_or(usize::MAX)
             });
+            let set_names: Vec<String> =
+                pattern_configs.iter().flat_map(|p| p.relay_sets.clone()).collect();
+            let sets = load_relay_sets(&mut *pool.acquire().await?, &set_names).await?;
 
             for pattern in pattern_configs {
                 // Load pattern's relays (including disabled - Vouch handles disabled flag)
-                let pattern_relays = sqlx::query_as::<_, crate::models::VouchProposerPatternRelay>(
-                    "SELECT id, pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled
-                     FROM vouch_proposer_pattern_relays WHERE pattern_name = $1",
+                let pattern_relays = sqlx::query_as!(
+                    crate::models::VouchProposerPatternRelay,
+                    r#"SELECT id, pattern_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, disabled
+                       FROM vouch_proposer_pattern_relays WHERE pattern_name = $1"#,
+                    pattern.name
                 )
-                .bind(&pattern.name)
-                .fetch_all(&state.pool)
+                .fetch_all(pool)
                 .await?;
 
-                let pattern_relays_map: HashMap<String, RelayConfig> = pattern_relays
-                    .into_iter()
-                    .map(|r| (r.url.clone(), r.into()))
-                    .collect();
+                let mut pattern_relays_map = expand_relay_sets(&pattern.relay_sets, &sets);
+                pattern_relays_map.extend(pattern_relays.into_iter().map(|r| (r.url.clone(), r.into())));
+                flag_disabled_relays(&mut pattern_relays_map, &globally_disabled);
 
+                origins.push(EntryOrigin::Pattern(pattern.name.clone()));
                 proposers.push(ProposerEntry {
                     proposer: pattern.pattern,
                     fee_recipient: pattern.fee_recipient,
                     gas_limit: pattern.gas_limit,
                     min_value: pattern.min_value,
+                    builder_enabled: pattern.builder_enabled,
+                    builder_boost_factor: pattern.builder_boost_factor,
                     reset_relays: if pattern.reset_relays {
                         Some(true)
                     } else {
@@ -184,11 +1152,14 @@ pub async fn get_execution_config(
         }
     }
 
-    Ok(Json(ExecutionConfigResponse {
+    let response = ExecutionConfigResponse {
         version: 2,
         fee_recipient: default_config.fee_recipient,
         gas_limit: default_config.gas_limit,
         min_value: default_config.min_value,
+        builder_enabled: default_config.builder_enabled,
+        builder_boost_factor: default_config.builder_boost_factor,
+        grace: default_config.grace,
         relays: if relays_map.is_empty() {
             None
         } else {
@@ -199,5 +1170,6 @@ pub async fn get_execution_config(
         } else {
             Some(proposers)
         },
-    }))
+    };
+    Ok((response, default_origins, origins))
 }
@@ -0,0 +1,125 @@
+// handlers/vouch/import.rs - Import of Vouch static execution config files
+use crate::audit::{AuditAction, AuditChanges, Change, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::errors::ApiError;
+use crate::handlers::jobs::accepted;
+use crate::jobs;
+use crate::schema::{ImportExecutionConfigResponse, JobAccepted, JobKind};
+use crate::services::import::{self, ImportPlan};
+use crate::units::{MinValues, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_keys};
+use crate::AppState;
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQuery {
+    /// Default config to create or update from the file
+    pub name: String,
+    /// Validate and report without writing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Unit of bare `min_value` numbers in the file (default: eth, as Vouch writes them)
+    #[serde(default = "default_min_value_units")]
+    pub min_value_units: ValueUnits,
+}
+
+fn default_min_value_units() -> ValueUnits {
+    ValueUnits::Eth
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/import/execution-config",
+    params(ImportQuery),
+    request_body(content = Object, description = "Vouch execution config file, v1 (`default_config` / `proposer_config`) or v2 (`version: 2`)"),
+    responses(
+        (status = 200, description = "What the import created or updated (or would, with dry_run)", body = ImportExecutionConfigResponse),
+        (status = 202, description = "More proposers and patterns than api.async_job_threshold: imported by a background job", body = JobAccepted),
+        (status = 400, description = "Unsupported or invalid file")
+    ),
+    tag = "Vouch - Import",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, file))]
+pub async fn import_execution_config(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(query): Query<ImportQuery>,
+    Json(file): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    info!("Importing execution config into '{}' (dry run: {})", query.name, query.dry_run);
+
+    let mut plan = import::parse(&query.name, file)?;
+
+    // Same checks as the create endpoints, after giving bare amounts their unit
+    with_min_value_units(&mut plan.default_config, query.min_value_units);
+    plan.default_config.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut plan.default_config)?;
+    check_relay_keys(&state.config.validation, &plan.default_config)?;
+    for (_, proposer) in &mut plan.proposers {
+        with_min_value_units(proposer, query.min_value_units);
+        proposer.normalize_min_values()?;
+        check_gas_limits(&state.config.validation, &ctx, proposer)?;
+        check_relay_keys(&state.config.validation, proposer)?;
+    }
+    for pattern in &mut plan.patterns {
+        with_min_value_units(pattern, query.min_value_units);
+        pattern.normalize_min_values()?;
+        check_gas_limits(&state.config.validation, &ctx, pattern)?;
+        check_relay_keys(&state.config.validation, pattern)?;
+    }
+
+    if plan.proposers.len() + plan.patterns.len() > state.config.api.async_job_threshold {
+        let id = jobs::submit(
+            &state.pool,
+            &ctx,
+            JobKind::ImportExecutionConfig,
+            write_plan(state.clone(), ctx.clone(), query, plan),
+        )
+        .await?;
+        return Ok(accepted(id));
+    }
+    Ok(Json(write_plan(state, ctx, query, plan).await?).into_response())
+}
+
+/// Write a checked import plan and audit it
+async fn write_plan(
+    state: Arc<AppState>,
+    ctx: RequestContext,
+    query: ImportQuery,
+    plan: ImportPlan,
+) -> Result<ImportExecutionConfigResponse, ApiError> {
+    let response = import::write(&state.pool, &plan, query.dry_run).await?;
+
+    // Audit log: one event for the whole import
+    if !query.dry_run && state.config.audit_enabled {
+        let changes = AuditChanges {
+            key_count: Change::between(&None, &Some(response.proposers.len() as i64)),
+            relays_count: Change::between(&None, &Some(response.default_config.relays as i64)),
+            ..Default::default()
+        };
+        audit_log!(ctx, AuditAction::Import, ResourceType::VouchDefaultConfig, &query.name, changes);
+    }
+
+    Ok(response)
+}
+
+/// Give bare `min_value` numbers the file's unit so they parse like API input
+fn with_min_value_units(req: &mut impl MinValues, units: ValueUnits) {
+    req.visit_min_values(&mut |value| {
+        if let Some(v) = value.as_mut() {
+            let trimmed = v.trim();
+            if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                *v = format!("{} {}", trimmed, units.name());
+            }
+        }
+    });
+}
@@ -1,19 +1,184 @@
 // handlers/vouch/mod.rs - Vouch routes
+use crate::config::VouchConfig;
 use crate::AppState;
-use axum::{routing::{get, post}, Router};
+use crate::handlers::deprecation;
+use axum::{middleware, routing::{get, post, put}, Router};
 use std::sync::Arc;
+use utoipa::OpenApi;
 
 pub mod default_configs;
 pub mod execution_config;
+pub mod import;
+pub mod proposer_groups;
 pub mod proposer_patterns;
 pub mod proposers;
+pub mod relay_sets;
+pub mod tags;
+
+/// OpenAPI paths and schemas for the Vouch routes
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        // Public
+        execution_config::get_execution_config,
+        execution_config::get_execution_configs_batch,
+        execution_config::get_execution_config_v1,
+        // Proposers
+        proposers::list_proposers,
+        proposers::count_proposers,
+        proposers::cleanup_proposers,
+        proposers::head_proposer,
+        proposers::get_proposer,
+        proposers::create_or_update_proposer,
+        proposers::delete_proposer,
+        proposers::disable_proposer_relay,
+        proposers::enable_proposer_relay,
+        proposers::decommission_proposer,
+        proposers::reactivate_proposer,
+        proposers::get_proposer_registrations,
+        // Default Configs
+        default_configs::list_default_configs,
+        default_configs::get_default_config,
+        default_configs::head_default_config,
+        default_configs::create_default_config,
+        default_configs::update_default_config,
+        default_configs::delete_default_config,
+        default_configs::get_default_config_dependents,
+        default_configs::list_default_config_relays,
+        default_configs::put_default_config_relay,
+        default_configs::delete_default_config_relay,
+        default_configs::get_default_config_usage,
+        execution_config::export_execution_config,
+        execution_config::explain_execution_config,
+        // Proposer Patterns
+        proposer_patterns::list_proposer_patterns,
+        proposer_patterns::get_proposer_pattern,
+        proposer_patterns::head_proposer_pattern,
+        proposer_patterns::get_proposer_pattern_matches,
+        proposer_patterns::get_proposer_pattern_statistics,
+        proposer_patterns::put_proposer_pattern_relay,
+        proposer_patterns::delete_proposer_pattern_relay,
+        proposer_patterns::create_proposer_pattern,
+        proposer_patterns::update_proposer_pattern,
+        proposer_patterns::delete_proposer_pattern,
+        proposer_patterns::get_proposer_pattern_dependents,
+        proposer_patterns::export_proposer_patterns,
+        proposer_patterns::import_proposer_patterns,
+        // Relay Sets
+        relay_sets::list_relay_sets,
+        relay_sets::get_relay_set,
+        relay_sets::head_relay_set,
+        relay_sets::create_relay_set,
+        relay_sets::update_relay_set,
+        relay_sets::delete_relay_set,
+        // Tags
+        tags::list_tags,
+        tags::rename_tag,
+        // Proposer Groups
+        proposer_groups::list_proposer_groups,
+        proposer_groups::get_proposer_group,
+        proposer_groups::head_proposer_group,
+        proposer_groups::create_proposer_group,
+        proposer_groups::update_proposer_group,
+        proposer_groups::delete_proposer_group,
+        // Import
+        import::import_execution_config,
+    ),
+    components(schemas(
+        // Common
+        crate::schema::RelayConfig,
+        crate::schema::ProposerRelayConfig,
+        crate::schema::PaginatedResponse<crate::schema::ProposerListItem>,
+        crate::schema::CountResponse,
+        crate::schema::DeletionPreview,
+        crate::schema::DependentRows,
+        crate::schema::DependentReference,
+        crate::units::ValueUnits,
+        proposers::MissingField,
+        crate::schema::PaginatedResponse<crate::schema::DefaultConfigListItem>,
+        crate::schema::PaginatedResponse<crate::schema::ProposerPatternListItem>,
+        crate::schema::PaginatedResponse<crate::schema::RelaySetResponse>,
+        crate::schema::PaginatedResponse<crate::schema::ProposerGroupListItem>,
+        // Proposers
+        crate::schema::ProposerResponse,
+        crate::schema::ProposerListItem,
+        crate::schema::CreateOrUpdateProposerRequest,
+        crate::schema::ProposerRegistrationsResponse,
+        crate::schema::ProposerCleanupResponse,
+        crate::config::CleanupAction,
+        crate::schema::IntendedRegistration,
+        crate::schema::RelayRegistration,
+        // Default Configs
+        crate::schema::DefaultConfigResponse,
+        crate::schema::DefaultConfigListItem,
+        crate::schema::CreateDefaultConfigRequest,
+        crate::schema::UpdateDefaultConfigRequest,
+        crate::schema::ConfigUsageResponse,
+        crate::schema::ConsumerUsage,
+        // Proposer Patterns
+        crate::schema::ProposerPatternResponse,
+        crate::schema::ProposerPatternListItem,
+        crate::schema::CreateProposerPatternRequest,
+        crate::schema::UpdateProposerPatternRequest,
+        crate::schema::PatternMatchesResponse,
+        crate::schema::PatternMatchStatistics,
+        crate::schema::PatternMatchCount,
+        crate::schema::ImportProposerPatternsResponse,
+        proposer_patterns::ConflictStrategy,
+        // Relay Sets
+        crate::schema::RelaySetResponse,
+        crate::schema::CreateRelaySetRequest,
+        crate::schema::UpdateRelaySetRequest,
+        // Tags
+        crate::schema::TagUsage,
+        crate::schema::RenameTagRequest,
+        crate::schema::RenameTagResponse,
+        // Proposer Groups
+        crate::schema::ProposerGroupResponse,
+        crate::schema::ProposerGroupListItem,
+        crate::schema::CreateProposerGroupRequest,
+        crate::schema::UpdateProposerGroupRequest,
+        // Import
+        crate::schema::ImportExecutionConfigResponse,
+        crate::schema::ImportedResource,
+        crate::schema::ImportAction,
+        // Execution Config
+        crate::schema::ExecutionConfigResponse,
+        crate::schema::BatchExecutionConfigRequest,
+        crate::schema::ProposerEntry,
+        crate::schema::ExecutionConfigExplanation,
+        crate::schema::KeyExplanation,
+        crate::schema::ConfigSourceKind,
+        crate::schema::ExecutionConfigV1Response,
+        crate::schema::ProposerConfigV1,
+        crate::schema::BuilderConfigV1,
+        execution_config::TagsMatch,
+        execution_config::ExportFormat,
+        crate::handlers::export_destination::ExportDestination,
+        crate::schema::StoredExport,
+    ))
+)]
+pub struct VouchApi;
 
 /// Public routes for Vouch (no authentication)
-pub fn public_routes() -> Router<Arc<AppState>> {
-    Router::new().route(
-        "/v2/execution-config/{config}",
-        post(execution_config::get_execution_config),
-    )
+pub fn public_routes(config: &VouchConfig) -> Router<Arc<AppState>> {
+    let mut v1 = get(execution_config::get_execution_config_v1);
+    if let Some(since) = config.v1_deprecated_at {
+        v1 = v1.route_layer(middleware::from_fn_with_state(
+            execution_config::v1_deprecation(since),
+            deprecation::deprecated,
+        ));
+    }
+    Router::new()
+        .route(
+            "/v2/execution-config/{config}",
+            post(execution_config::get_execution_config),
+        )
+        .route(
+            "/v2/execution-config:batch",
+            post(execution_config::get_execution_configs_batch),
+        )
+        .route("/v1/execution-config/{config}", v1)
 }
 
 /// Admin routes for Vouch (authentication required)
@@ -21,12 +186,35 @@ pub fn admin_routes() -> Router<Arc<AppState>> {
     Router::new()
         // Proposers
         .route("/proposers", get(proposers::list_proposers))
+        .route("/proposers/count", get(proposers::count_proposers))
+        .route("/proposers/cleanup", post(proposers::cleanup_proposers))
         .route(
             "/proposers/{public_key}",
             get(proposers::get_proposer)
+                .head(proposers::head_proposer)
                 .put(proposers::create_or_update_proposer)
                 .delete(proposers::delete_proposer),
         )
+        .route(
+            "/proposers/{public_key}/decommission",
+            post(proposers::decommission_proposer),
+        )
+        .route(
+            "/proposers/{public_key}/reactivate",
+            post(proposers::reactivate_proposer),
+        )
+        .route(
+            "/proposers/{public_key}/registrations",
+            get(proposers::get_proposer_registrations),
+        )
+        .route(
+            "/proposers/{public_key}/relays/{url}/disable",
+            post(proposers::disable_proposer_relay),
+        )
+        .route(
+            "/proposers/{public_key}/relays/{url}/enable",
+            post(proposers::enable_proposer_relay),
+        )
         // Default Configs
         .route(
             "/configs/default",
@@ -35,19 +223,103 @@ pub fn admin_routes() -> Router<Arc<AppState>> {
         .route(
             "/configs/default/{name}",
             get(default_configs::get_default_config)
+                .head(default_configs::head_default_config)
                 .put(default_configs::update_default_config)
                 .delete(default_configs::delete_default_config),
         )
+        .route(
+            "/configs/default/{name}/dependents",
+            get(default_configs::get_default_config_dependents),
+        )
+        .route(
+            "/configs/default/{name}/usage",
+            get(default_configs::get_default_config_usage),
+        )
+        .route(
+            "/configs/default/{name}/relays",
+            get(default_configs::list_default_config_relays),
+        )
+        .route(
+            "/configs/default/{name}/relays/{url}",
+            put(default_configs::put_default_config_relay)
+                .delete(default_configs::delete_default_config_relay),
+        )
+        .route(
+            "/configs/default/{name}/export",
+            get(execution_config::export_execution_config),
+        )
+        .route(
+            "/execution-config/{config}/explain",
+            post(execution_config::explain_execution_config),
+        )
         // Proposer Patterns
         .route(
             "/proposer-patterns",
             get(proposer_patterns::list_proposer_patterns)
                 .post(proposer_patterns::create_proposer_pattern),
         )
+        .route(
+            "/proposer-patterns/export",
+            get(proposer_patterns::export_proposer_patterns),
+        )
+        .route(
+            "/proposer-patterns/import",
+            post(proposer_patterns::import_proposer_patterns),
+        )
+        .route(
+            "/proposer-patterns/statistics",
+            get(proposer_patterns::get_proposer_pattern_statistics),
+        )
         .route(
             "/proposer-patterns/{name}",
             get(proposer_patterns::get_proposer_pattern)
+                .head(proposer_patterns::head_proposer_pattern)
                 .put(proposer_patterns::update_proposer_pattern)
                 .delete(proposer_patterns::delete_proposer_pattern),
         )
+        .route(
+            "/proposer-patterns/{name}/dependents",
+            get(proposer_patterns::get_proposer_pattern_dependents),
+        )
+        .route(
+            "/proposer-patterns/{name}/matches",
+            get(proposer_patterns::get_proposer_pattern_matches),
+        )
+        .route(
+            "/proposer-patterns/{name}/relays/{url}",
+            put(proposer_patterns::put_proposer_pattern_relay)
+                .delete(proposer_patterns::delete_proposer_pattern_relay),
+        )
+        // Relay Sets
+        .route(
+            "/relay-sets",
+            get(relay_sets::list_relay_sets).post(relay_sets::create_relay_set),
+        )
+        .route(
+            "/relay-sets/{name}",
+            get(relay_sets::get_relay_set)
+                .head(relay_sets::head_relay_set)
+                .put(relay_sets::update_relay_set)
+                .delete(relay_sets::delete_relay_set),
+        )
+        // Tags
+        .route("/tags", get(tags::list_tags))
+        .route("/tags/{tag}/rename", post(tags::rename_tag))
+        // Proposer Groups
+        .route(
+            "/proposer-groups",
+            get(proposer_groups::list_proposer_groups).post(proposer_groups::create_proposer_group),
+        )
+        .route(
+            "/proposer-groups/{name}",
+            get(proposer_groups::get_proposer_group)
+                .head(proposer_groups::head_proposer_group)
+                .put(proposer_groups::update_proposer_group)
+                .delete(proposer_groups::delete_proposer_group),
+        )
+        // Import
+        .route(
+            "/import/execution-config",
+            post(import::import_execution_config),
+        )
 }
@@ -0,0 +1,237 @@
+// handlers/vouch/proposer_groups.rs - Proposer Group CRUD handlers
+use crate::addresses::BlsPubkey;
+use crate::audit::{AuditAction, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::errors::ApiError;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::schema::{
+    CreateProposerGroupRequest, PaginatedResponse, ProposerGroupListItem, ProposerGroupResponse,
+    UpdateProposerGroupRequest,
+};
+use crate::services::groups::{self, GroupFilters};
+use crate::services::PageRequest;
+use crate::units::{MinValues, UnitsQuery, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_keys};
+use crate::AppState;
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ProposerGroupFilters {
+    /// Filter by name (prefix match)
+    pub name: Option<String>,
+    /// Only groups listing this key (pattern members are not considered)
+    pub key: Option<BlsPubkey>,
+    /// Sort column: name, priority, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-groups",
+    params(ProposerGroupFilters, UnitsQuery),
+    responses(
+        (status = 200, description = "List of proposer groups", body = PaginatedResponse<ProposerGroupListItem>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, uri))]
+pub async fn list_proposer_groups(
+    State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
+    Query(filters): Query<ProposerGroupFilters>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Page<ProposerGroupListItem>, ApiError> {
+    info!("Listing proposer groups with filters: {:?}", filters);
+    check_limit(filters.limit, state.config.api.max_page_size)?;
+
+    let group_filters = GroupFilters {
+        name: filters.name,
+        key: filters.key,
+    };
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
+        limit: filters.limit,
+        offset: filters.offset,
+    };
+    let mut response = groups::list(&state.pool, &group_filters, page).await?;
+    response.data.display_min_values(units);
+
+    Ok(Page::new(uri, response))
+}
+
+#[utoipa::path(
+    head,
+    path = "/api/admin/vouch/proposer-groups/{name}",
+    params(
+        ("name" = String, Path, description = "Proposer group name")
+    ),
+    responses(
+        (status = 200, description = "Proposer group exists"),
+        (status = 404, description = "Proposer group not found")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_proposer_group(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    groups::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-groups/{name}",
+    params(
+        ("name" = String, Path, description = "Proposer group name"),
+        UnitsQuery
+    ),
+    responses(
+        (status = 200, description = "Proposer group details", body = ProposerGroupResponse),
+        (status = 404, description = "Proposer group not found")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_group(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Json<ProposerGroupResponse>, ApiError> {
+    info!("Getting proposer group: {}", name);
+
+    let mut response = groups::get(&state.pool, &name).await?;
+    response.display_min_values(units);
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposer-groups",
+    request_body = CreateProposerGroupRequest,
+    responses(
+        (status = 201, description = "Proposer group created", body = ProposerGroupResponse),
+        (status = 400, description = "Invalid pattern or unknown relay set"),
+        (status = 409, description = "Proposer group already exists")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn create_proposer_group(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(mut req): Json<CreateProposerGroupRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
+    info!("Creating proposer group: {}", req.name);
+
+    let (mut response, changes) = groups::create(&state.pool, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Create, ResourceType::VouchProposerGroup, &req.name, changes);
+    }
+
+    response.display_min_values(ValueUnits::Eth);
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/vouch/proposer-groups/{name}",
+    params(
+        ("name" = String, Path, description = "Proposer group name")
+    ),
+    request_body = UpdateProposerGroupRequest,
+    responses(
+        (status = 200, description = "Proposer group updated", body = ProposerGroupResponse),
+        (status = 400, description = "Invalid pattern or unknown relay set"),
+        (status = 404, description = "Proposer group not found")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn update_proposer_group(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(mut req): Json<UpdateProposerGroupRequest>,
+) -> Result<Json<ProposerGroupResponse>, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
+    info!("Updating proposer group: {}", name);
+
+    let (mut response, changes) = groups::update(&state.pool, &name, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchProposerGroup, &name, changes);
+    }
+
+    response.display_min_values(ValueUnits::Eth);
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/vouch/proposer-groups/{name}",
+    params(
+        ("name" = String, Path, description = "Proposer group name")
+    ),
+    responses(
+        (status = 204, description = "Proposer group deleted"),
+        (status = 404, description = "Proposer group not found")
+    ),
+    tag = "Vouch - Proposer Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_proposer_group(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Deleting proposer group: {}", name);
+
+    let changes = groups::delete(&state.pool, &name).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposerGroup, &name, changes);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -2,38 +2,66 @@
 use crate::audit::{AuditAction, AuditChanges, RequestContext, ResourceType};
 use crate::audit_log;
 use crate::errors::ApiError;
+use crate::handlers::export_destination::{store_export, ExportDestination, ExportDestinationQuery};
+use crate::handlers::jobs::accepted;
+use crate::handlers::labels::{label_filters, validate_labels};
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::handlers::streaming::JsonStream;
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::jobs;
+use crate::models::Labels;
 use crate::schema::{
-    CreateProposerPatternRequest, PaginatedResponse, ProposerPatternListItem,
-    ProposerPatternResponse, ProposerRelayConfig, UpdateProposerPatternRequest,
+    CreateProposerPatternRequest, DeletionPreview, ImportAction, ImportProposerPatternsResponse,
+    ImportedResource, JobAccepted, JobKind, PaginatedResponse, PatternMatchStatistics, PatternMatchesResponse, ProposerPatternListItem,
+    ProposerPatternResponse, ProposerRelayConfig, StoredExport, UpdateProposerPatternRequest,
 };
+use crate::services::patterns::{self, audit_snapshot, PatternFilters};
+use crate::services::relay_sets::ensure_relay_sets_exist;
+use crate::services::PageRequest;
+use crate::units::{MinValues, UnitsQuery, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_key, check_relay_keys};
 use crate::AppState;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{OriginalUri, Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{info, instrument};
-use utoipa::IntoParams;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct ProposerPatternFilters {
     pub name: Option<String>,
     pub pattern: Option<String>,
     pub tag: Option<String>,
+    /// Exclude patterns carrying this tag
+    pub exclude_tag: Option<String>,
     pub fee_recipient: Option<String>,
+    /// Exclude patterns with this fee recipient (those without one are kept)
+    pub fee_recipient_ne: Option<String>,
     pub gas_limit: Option<String>,
     pub min_value: Option<String>,
     pub reset_relays: Option<bool>,
+    pub active: Option<bool>,
     /// Filter by relay URL (prefix match)
     pub relay_url: Option<String>,
     /// Filter by relay min_value (exact match)
     pub relay_min_value: Option<String>,
     /// Filter by relay disabled status
     pub relay_disabled: Option<bool>,
+    /// Set from `label.<key>` query parameters by the handler
+    #[serde(skip)]
+    #[param(ignore)]
+    pub labels: Labels,
+    /// Sort column: name, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
@@ -47,108 +75,92 @@ fn default_limit() -> i64 {
 #[utoipa::path(
     get,
     path = "/api/admin/vouch/proposer-patterns",
-    params(ProposerPatternFilters),
+    params(
+        ProposerPatternFilters,
+        TimeRangeFilters,
+        UnitsQuery,
+        ("label.<key>" = Option<String>, Query, description = "Filter by label value, e.g. `label.team=alpha` (repeatable, all must match)")
+    ),
     responses(
-        (status = 200, description = "List of proposer patterns", body = PaginatedResponse<ProposerPatternListItem>)
+        (status = 200, description = "List of proposer patterns", body = PaginatedResponse<ProposerPatternListItem>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
     ),
     tag = "Vouch - Proposer Patterns",
     security(("bearer_auth" = []))
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, uri))]
 pub async fn list_proposer_patterns(
     State(state): State<Arc<AppState>>,
-    Query(filters): Query<ProposerPatternFilters>,
-) -> Result<Json<PaginatedResponse<ProposerPatternListItem>>, ApiError> {
+    OriginalUri(uri): OriginalUri,
+    Query(mut filters): Query<ProposerPatternFilters>,
+    Query(time_range): Query<TimeRangeFilters>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Page<ProposerPatternListItem>, ApiError> {
+    filters.labels = label_filters(&params)?;
     info!("Listing proposer patterns with filters: {:?}", filters);
-
-    let mut conditions = Vec::new();
-
-    if let Some(ref name) = filters.name {
-        conditions.push(format!("p.name LIKE '{}%'", name.replace('\'', "''")));
-    }
-    if let Some(ref pattern) = filters.pattern {
-        conditions.push(format!("p.pattern LIKE '%{}%'", pattern.replace('\'', "''")));
-    }
-    if let Some(ref tag) = filters.tag {
-        conditions.push(format!("'{}' = ANY(p.tags)", tag.replace('\'', "''")));
-    }
-    if let Some(ref fr) = filters.fee_recipient {
-        conditions.push(format!("p.fee_recipient = '{}'", fr.replace('\'', "''")));
-    }
-    if let Some(ref gl) = filters.gas_limit {
-        conditions.push(format!("p.gas_limit = '{}'", gl.replace('\'', "''")));
-    }
-    if let Some(ref mv) = filters.min_value {
-        conditions.push(format!("p.min_value = '{}'", mv.replace('\'', "''")));
-    }
-    if let Some(rr) = filters.reset_relays {
-        conditions.push(format!(
-            "p.reset_relays = {}",
-            if rr { "true" } else { "false" }
-        ));
-    }
-    // Relay filters using EXISTS subquery
-    if let Some(ref relay_url) = filters.relay_url {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.url LIKE '{}%')",
-            relay_url.replace('\'', "''")
-        ));
-    }
-    if let Some(ref relay_min_value) = filters.relay_min_value {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.min_value = '{}')",
-            relay_min_value.replace('\'', "''")
-        ));
-    }
-    if let Some(relay_disabled) = filters.relay_disabled {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.disabled = {})",
-            if relay_disabled { "true" } else { "false" }
-        ));
-    }
-
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
+    check_limit(filters.limit, state.config.api.max_page_size)?;
+
+    let pattern_filters = PatternFilters {
+        name: filters.name,
+        pattern: filters.pattern,
+        tag: filters.tag,
+        exclude_tag: filters.exclude_tag,
+        fee_recipient: filters.fee_recipient,
+        fee_recipient_ne: filters.fee_recipient_ne,
+        gas_limit: filters.gas_limit,
+        min_value: filters.min_value,
+        reset_relays: filters.reset_relays,
+        active: filters.active,
+        relay_url: filters.relay_url,
+        relay_min_value: filters.relay_min_value,
+        relay_disabled: filters.relay_disabled,
+        labels: filters.labels,
+        time_range,
     };
-
-    let count_sql = format!(
-        "SELECT COUNT(*) as count FROM vouch_proposer_patterns p {}",
-        where_clause
-    );
-    let total: i64 = sqlx::query_scalar(&count_sql)
-        .fetch_one(&state.pool)
-        .await?;
-
-    let data_sql = format!(
-        "SELECT p.name, p.pattern, p.tags, p.fee_recipient, p.gas_limit, p.min_value, p.reset_relays, p.created_at, p.updated_at
-         FROM vouch_proposer_patterns p {}
-         ORDER BY p.name ASC
-         LIMIT {} OFFSET {}",
-        where_clause, filters.limit, filters.offset
-    );
-
-    let patterns = sqlx::query_as::<_, crate::models::VouchProposerPattern>(&data_sql)
-        .fetch_all(&state.pool)
-        .await?;
-
-    let data: Vec<ProposerPatternListItem> = patterns.into_iter().map(Into::into).collect();
-
-    Ok(Json(PaginatedResponse {
-        data,
-        total,
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
         limit: filters.limit,
         offset: filters.offset,
-    }))
+    };
+    let mut response = patterns::list(&state.pool, &pattern_filters, page).await?;
+    response.data.display_min_values(units);
+
+    Ok(Page::new(uri, response))
 }
 
 #[utoipa::path(
-    get,
+    head,
     path = "/api/admin/vouch/proposer-patterns/{name}",
     params(
         ("name" = String, Path, description = "Pattern name")
     ),
+    responses(
+        (status = 200, description = "Pattern exists"),
+        (status = 404, description = "Pattern not found")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_proposer_pattern(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    patterns::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/{name}",
+    params(
+        ("name" = String, Path, description = "Pattern name"),
+        UnitsQuery
+    ),
     responses(
         (status = 200, description = "Proposer pattern details", body = ProposerPatternResponse),
         (status = 404, description = "Pattern not found")
@@ -160,47 +172,70 @@ pub async fn list_proposer_patterns(
 pub async fn get_proposer_pattern(
     State(state): State<Arc<AppState>>,
     Path(name): Path<String>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
 ) -> Result<Json<ProposerPatternResponse>, ApiError> {
     info!("Getting proposer pattern: {}", name);
 
-    let pattern = sqlx::query_as::<_, crate::models::VouchProposerPattern>(
-        "SELECT name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-         FROM vouch_proposer_patterns WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_optional(&state.pool)
-    .await?
-    .ok_or_else(|| ApiError::NotFound(format!("Proposer pattern '{}' not found", name)))?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchProposerPatternRelay>(
-        "SELECT id, pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled
-         FROM vouch_proposer_pattern_relays WHERE pattern_name = $1",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, ProposerRelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    Ok(Json(ProposerPatternResponse {
-        name: pattern.name,
-        pattern: pattern.pattern,
-        tags: pattern.tags,
-        fee_recipient: pattern.fee_recipient,
-        gas_limit: pattern.gas_limit,
-        min_value: pattern.min_value,
-        reset_relays: pattern.reset_relays,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: pattern.created_at,
-        updated_at: pattern.updated_at,
-    }))
+    let mut response = patterns::get(&state.pool, &name).await?;
+    response.display_min_values(units);
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PatternMatchesQuery {
+    /// Keys to test: `proposers` (default) or `mux:<name>` for a Commit-Boost mux
+    pub source: Option<String>,
+    /// Maximum number of matching keys to return (default: 10)
+    #[serde(default = "default_sample_size")]
+    pub sample: usize,
+}
+
+fn default_sample_size() -> usize {
+    10
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/{name}/matches",
+    params(
+        ("name" = String, Path, description = "Pattern name"),
+        PatternMatchesQuery
+    ),
+    responses(
+        (status = 200, description = "Keys matched by the pattern", body = PatternMatchesResponse),
+        (status = 400, description = "Invalid source or the stored pattern is not a valid regex"),
+        (status = 404, description = "Pattern or mux not found")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_pattern_matches(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(query): Query<PatternMatchesQuery>,
+) -> Result<Json<PatternMatchesResponse>, ApiError> {
+    info!("Evaluating proposer pattern: {}", name);
+
+    Ok(Json(patterns::matches(&state.pool, &name, query.source, query.sample).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/statistics",
+    responses(
+        (status = 200, description = "Proposers matched per pattern", body = PatternMatchStatistics)
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_pattern_statistics(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<PatternMatchStatistics>, ApiError> {
+    info!("Getting proposer pattern statistics");
+
+    Ok(Json(patterns::statistics(&state.pool).await?))
 }
 
 #[utoipa::path(
@@ -209,6 +244,7 @@ pub async fn get_proposer_pattern(
     request_body = CreateProposerPatternRequest,
     responses(
         (status = 201, description = "Pattern created", body = ProposerPatternResponse),
+        (status = 400, description = "Unknown relay set"),
         (status = 409, description = "Pattern already exists")
     ),
     tag = "Vouch - Proposer Patterns",
@@ -218,116 +254,23 @@ pub async fn get_proposer_pattern(
 pub async fn create_proposer_pattern(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
-    Json(req): Json<CreateProposerPatternRequest>,
+    Json(mut req): Json<CreateProposerPatternRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
     info!("Creating proposer pattern: {}", req.name);
 
-    let mut tx = state.pool.begin().await?;
+    validate_labels(&req.labels)?;
 
-    // Check if pattern already exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM vouch_proposer_patterns WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing > 0 {
-        return Err(ApiError::InvalidData(format!(
-            "Pattern '{}' already exists",
-            req.name
-        )));
-    }
-
-    sqlx::query(
-        "INSERT INTO vouch_proposer_patterns (name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)",
-    )
-    .bind(&req.name)
-    .bind(&req.pattern)
-    .bind(&req.tags)
-    .bind(&req.fee_recipient)
-    .bind(&req.gas_limit)
-    .bind(&req.min_value)
-    .bind(req.reset_relays)
-    .execute(&mut *tx)
-    .await?;
-
-    if let Some(relays) = &req.relays {
-        for (url, relay) in relays {
-            sqlx::query(
-                "INSERT INTO vouch_proposer_pattern_relays
-                 (pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
-            )
-            .bind(&req.name)
-            .bind(url)
-            .bind(&relay.public_key)
-            .bind(&relay.fee_recipient)
-            .bind(&relay.gas_limit)
-            .bind(&relay.min_value)
-            .bind(relay.disabled)
-            .execute(&mut *tx)
-            .await?;
-        }
-    }
-
-    tx.commit().await?;
+    let (mut response, changes) = patterns::create(&state.pool, &req).await?;
+    response.display_min_values(ValueUnits::Eth);
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            pattern: Some(req.pattern.clone()),
-            tags: Some(req.tags.clone()),
-            fee_recipient: req.fee_recipient.as_ref().map(|a| a.to_string()),
-            min_value: req.min_value.clone(),
-            gas_limit: req.gas_limit.clone(),
-            reset_relays: Some(req.reset_relays),
-            relays_count: req.relays.as_ref().map(|r| r.len()),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Create, ResourceType::VouchProposerPattern, &req.name, changes);
     }
 
-    // Fetch created pattern
-    let pattern = sqlx::query_as::<_, crate::models::VouchProposerPattern>(
-        "SELECT name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-         FROM vouch_proposer_patterns WHERE name = $1",
-    )
-    .bind(&req.name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchProposerPatternRelay>(
-        "SELECT id, pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled
-         FROM vouch_proposer_pattern_relays WHERE pattern_name = $1",
-    )
-    .bind(&req.name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, ProposerRelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    let response = ProposerPatternResponse {
-        name: pattern.name,
-        pattern: pattern.pattern,
-        tags: pattern.tags,
-        fee_recipient: pattern.fee_recipient,
-        gas_limit: pattern.gas_limit,
-        min_value: pattern.min_value,
-        reset_relays: pattern.reset_relays,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: pattern.created_at,
-        updated_at: pattern.updated_at,
-    };
-
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -340,6 +283,7 @@ pub async fn create_proposer_pattern(
     request_body = UpdateProposerPatternRequest,
     responses(
         (status = 200, description = "Pattern updated", body = ProposerPatternResponse),
+        (status = 400, description = "Unknown relay set"),
         (status = 404, description = "Pattern not found")
     ),
     tag = "Vouch - Proposer Patterns",
@@ -350,203 +294,317 @@ pub async fn update_proposer_pattern(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
     Path(name): Path<String>,
-    Json(req): Json<UpdateProposerPatternRequest>,
+    Json(mut req): Json<UpdateProposerPatternRequest>,
 ) -> Result<Json<ProposerPatternResponse>, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
     info!("Updating proposer pattern: {}", name);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if pattern exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM vouch_proposer_patterns WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    if existing == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Proposer pattern '{}' not found",
-            name
-        )));
+    if let Some(labels) = &req.labels {
+        validate_labels(labels)?;
     }
 
-    // Build update query dynamically
-    let mut set_clauses = Vec::new();
-    let mut param_index = 2;
+    let (mut response, changes) = patterns::update(&state.pool, &name, &req).await?;
+    response.display_min_values(ValueUnits::Eth);
 
-    if req.pattern.is_some() {
-        set_clauses.push(format!("pattern = ${}", param_index));
-        param_index += 1;
-    }
-    if req.tags.is_some() {
-        set_clauses.push(format!("tags = ${}", param_index));
-        param_index += 1;
-    }
-    if req.fee_recipient.is_some() {
-        set_clauses.push(format!("fee_recipient = ${}", param_index));
-        param_index += 1;
-    }
-    if req.gas_limit.is_some() {
-        set_clauses.push(format!("gas_limit = ${}", param_index));
-        param_index += 1;
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchProposerPattern, &name, changes);
     }
-    if req.min_value.is_some() {
-        set_clauses.push(format!("min_value = ${}", param_index));
-        param_index += 1;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/vouch/proposer-patterns/{name}",
+    params(
+        ("name" = String, Path, description = "Pattern name")
+    ),
+    responses(
+        (status = 204, description = "Pattern deleted"),
+        (status = 404, description = "Pattern not found")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_proposer_pattern(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Deleting proposer pattern: {}", name);
+
+    let changes = patterns::delete(&state.pool, &name).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposerPattern, &name, changes);
     }
-    if req.reset_relays.is_some() {
-        set_clauses.push(format!("reset_relays = ${}", param_index));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/{name}/dependents",
+    params(
+        ("name" = String, Path, description = "Pattern name")
+    ),
+    responses(
+        (status = 200, description = "What deleting the pattern would remove", body = DeletionPreview),
+        (status = 404, description = "Pattern not found")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_pattern_dependents(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<DeletionPreview>, ApiError> {
+    info!("Previewing deletion of proposer pattern: {}", name);
+
+    Ok(Json(patterns::dependents(&state.pool, &name).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposer-patterns/export",
+    params(ExportDestinationQuery),
+    responses(
+        (status = 200, description = "Every pattern with its relays, in the import format", body = Vec<CreateProposerPatternRequest>),
+        (status = 201, description = "Written to the export_storage bucket", body = StoredExport),
+        (status = 400, description = "destination=storage without export_storage"),
+        (status = 502, description = "The export_storage bucket rejected the file")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn export_proposer_patterns(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExportDestinationQuery>,
+) -> Result<Response, ApiError> {
+    info!("Exporting proposer patterns");
+
+    let patterns = patterns::export(&state.pool).await?;
+    if query.destination == ExportDestination::Storage {
+        return store_export(&state.config, "proposer-patterns", &patterns, false).await;
     }
+    Ok(JsonStream(patterns).into_response())
+}
 
-    if !set_clauses.is_empty() {
-        let update_sql = format!(
-            "UPDATE vouch_proposer_patterns SET {} WHERE name = $1",
-            set_clauses.join(", ")
-        );
+/// What a bulk import does with a pattern whose name already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictStrategy {
+    /// Keep the existing pattern
+    Skip,
+    /// Replace the existing pattern and its relays
+    Overwrite,
+    /// Reject the whole import with 409
+    #[default]
+    Fail,
+}
 
-        let mut query = sqlx::query(&update_sql).bind(&name);
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportPatternsQuery {
+    /// What to do with patterns that already exist (default: fail)
+    #[serde(default)]
+    #[param(inline)]
+    pub on_conflict: ConflictStrategy,
+    /// Validate and report without writing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
 
-        if let Some(ref p) = req.pattern {
-            query = query.bind(p);
-        }
-        if let Some(ref t) = req.tags {
-            query = query.bind(t);
-        }
-        if let Some(ref fr) = req.fee_recipient {
-            query = query.bind(fr);
-        }
-        if let Some(ref gl) = req.gas_limit {
-            query = query.bind(gl);
-        }
-        if let Some(ref mv) = req.min_value {
-            query = query.bind(mv);
-        }
-        if let Some(rr) = req.reset_relays {
-            query = query.bind(rr);
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposer-patterns/import",
+    params(ImportPatternsQuery),
+    request_body = Vec<CreateProposerPatternRequest>,
+    responses(
+        (status = 200, description = "What the import created, updated or skipped (or would, with dry_run)", body = ImportProposerPatternsResponse),
+        (status = 202, description = "More patterns than api.async_job_threshold: imported by a background job", body = JobAccepted),
+        (status = 400, description = "Invalid pattern, duplicate name or unknown relay set"),
+        (status = 409, description = "A pattern already exists and on_conflict is fail")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx, patterns))]
+pub async fn import_proposer_patterns(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(query): Query<ImportPatternsQuery>,
+    Json(mut patterns): Json<Vec<CreateProposerPatternRequest>>,
+) -> Result<Response, ApiError> {
+    info!(
+        "Importing {} proposer patterns (on conflict: {:?}, dry run: {})",
+        patterns.len(),
+        query.on_conflict,
+        query.dry_run
+    );
+
+    // Same checks as the create endpoint
+    let mut names = HashSet::new();
+    for pattern in &mut patterns {
+        if !names.insert(pattern.name.clone()) {
+            return Err(ApiError::InvalidData(format!("Pattern '{}' is listed twice", pattern.name)));
         }
+        pattern.normalize_min_values()?;
+        check_gas_limits(&state.config.validation, &ctx, pattern)?;
+        check_relay_keys(&state.config.validation, pattern)?;
+        validate_labels(&pattern.labels)?;
+    }
 
-        query.execute(&mut *tx).await?;
+    if patterns.len() > state.config.api.async_job_threshold {
+        let id = jobs::submit(
+            &state.pool,
+            &ctx,
+            JobKind::ImportProposerPatterns,
+            write_imported_patterns(state.clone(), ctx.clone(), query, patterns),
+        )
+        .await?;
+        return Ok(accepted(id));
     }
+    Ok(Json(write_imported_patterns(state, ctx, query, patterns).await?).into_response())
+}
+
+/// Write the checked patterns of an import, in one transaction
+async fn write_imported_patterns(
+    state: Arc<AppState>,
+    ctx: RequestContext,
+    query: ImportPatternsQuery,
+    patterns: Vec<CreateProposerPatternRequest>,
+) -> Result<ImportProposerPatternsResponse, ApiError> {
+    let mut tx = state.pool.begin().await?;
+    let relay_sets: Vec<String> = patterns.iter().flat_map(|p| p.relay_sets.clone()).collect();
+    ensure_relay_sets_exist(&mut tx, &relay_sets).await?;
+
+    let mut imported = Vec::with_capacity(patterns.len());
+    let mut events = Vec::new();
+    for pattern in &patterns {
+        let before = audit_snapshot(&mut tx, &pattern.name).await?;
+        let action = match (&before, query.on_conflict) {
+            (None, _) => ImportAction::Created,
+            (Some(_), ConflictStrategy::Overwrite) => ImportAction::Updated,
+            (Some(_), ConflictStrategy::Skip) => {
+                imported.push(ImportedResource {
+                    id: pattern.name.clone(),
+                    action: ImportAction::Skipped,
+                    relays: 0,
+                });
+                continue;
+            }
+            (Some(_), ConflictStrategy::Fail) => {
+                return Err(ApiError::Conflict(format!("Pattern '{}' already exists", pattern.name)));
+            }
+        };
 
-    // Handle relays if provided
-    if let Some(relays) = &req.relays {
-        sqlx::query("DELETE FROM vouch_proposer_pattern_relays WHERE pattern_name = $1")
-            .bind(&name)
-            .execute(&mut *tx)
-            .await?;
-
-        for (url, relay) in relays {
-            sqlx::query(
-                "INSERT INTO vouch_proposer_pattern_relays
-                 (pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
-            )
-            .bind(&name)
-            .bind(url)
-            .bind(&relay.public_key)
-            .bind(&relay.fee_recipient)
-            .bind(&relay.gas_limit)
-            .bind(&relay.min_value)
-            .bind(relay.disabled)
-            .execute(&mut *tx)
-            .await?;
+        patterns::replace(&mut tx, pattern).await?;
+        let after = audit_snapshot(&mut tx, &pattern.name).await?.unwrap_or_default();
+        events.push((action, pattern.name.clone(), AuditChanges::diff(&before.unwrap_or_default(), &after)));
+        imported.push(ImportedResource {
+            id: pattern.name.clone(),
+            action,
+            relays: pattern.relays.as_ref().map_or(0, HashMap::len),
+        });
+    }
+
+    // A dry run goes through every write so constraint errors show up, then rolls back
+    if query.dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+
+        // Audit log: one event per written pattern
+        if state.config.audit_enabled {
+            for (action, name, changes) in events {
+                let action = match action {
+                    ImportAction::Created => AuditAction::Create,
+                    _ => AuditAction::Update,
+                };
+                audit_log!(ctx, action, ResourceType::VouchProposerPattern, &name, changes);
+            }
         }
     }
 
-    tx.commit().await?;
+    Ok(ImportProposerPatternsResponse {
+        dry_run: query.dry_run,
+        patterns: imported,
+    })
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/vouch/proposer-patterns/{name}/relays/{url}",
+    params(
+        ("name" = String, Path, description = "Pattern name"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    request_body = ProposerRelayConfig,
+    responses(
+        (status = 200, description = "Relay added or replaced", body = ProposerPatternResponse),
+        (status = 400, description = "Invalid relay settings"),
+        (status = 404, description = "Pattern not found")
+    ),
+    tag = "Vouch - Proposer Patterns",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn put_proposer_pattern_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path((name, url)): Path<(String, String)>,
+    Json(mut relay): Json<ProposerRelayConfig>,
+) -> Result<Json<ProposerPatternResponse>, ApiError> {
+    relay.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut relay)?;
+    check_relay_key(&state.config.validation, &url, &relay.public_key)?;
+    info!("Setting relay {} for proposer pattern: {}", url, name);
+
+    let (mut response, changes) = patterns::put_relay(&state.pool, &name, &url, &relay).await?;
+    response.display_min_values(ValueUnits::Eth);
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            pattern: req.pattern.clone(),
-            tags: req.tags.clone(),
-            fee_recipient: req.fee_recipient.as_ref().map(|a| a.to_string()),
-            min_value: req.min_value.clone(),
-            gas_limit: req.gas_limit.clone(),
-            reset_relays: req.reset_relays,
-            relays_count: req.relays.as_ref().map(|r| r.len()),
-            ..Default::default()
-        };
         audit_log!(ctx, AuditAction::Update, ResourceType::VouchProposerPattern, &name, changes);
     }
 
-    // Fetch updated pattern
-    let pattern = sqlx::query_as::<_, crate::models::VouchProposerPattern>(
-        "SELECT name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-         FROM vouch_proposer_patterns WHERE name = $1",
-    )
-    .bind(&name)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchProposerPatternRelay>(
-        "SELECT id, pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled
-         FROM vouch_proposer_pattern_relays WHERE pattern_name = $1",
-    )
-    .bind(&name)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, ProposerRelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    Ok(Json(ProposerPatternResponse {
-        name: pattern.name,
-        pattern: pattern.pattern,
-        tags: pattern.tags,
-        fee_recipient: pattern.fee_recipient,
-        gas_limit: pattern.gas_limit,
-        min_value: pattern.min_value,
-        reset_relays: pattern.reset_relays,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: pattern.created_at,
-        updated_at: pattern.updated_at,
-    }))
+    Ok(Json(response))
 }
 
 #[utoipa::path(
     delete,
-    path = "/api/admin/vouch/proposer-patterns/{name}",
+    path = "/api/admin/vouch/proposer-patterns/{name}/relays/{url}",
     params(
-        ("name" = String, Path, description = "Pattern name")
+        ("name" = String, Path, description = "Pattern name"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
     ),
     responses(
-        (status = 204, description = "Pattern deleted"),
-        (status = 404, description = "Pattern not found")
+        (status = 204, description = "Relay removed from the pattern"),
+        (status = 404, description = "Pattern or relay not found")
     ),
     tag = "Vouch - Proposer Patterns",
     security(("bearer_auth" = []))
 )]
 #[instrument(skip(state, ctx))]
-pub async fn delete_proposer_pattern(
+pub async fn delete_proposer_pattern_relay(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
-    Path(name): Path<String>,
+    Path((name, url)): Path<(String, String)>,
 ) -> Result<impl IntoResponse, ApiError> {
-    info!("Deleting proposer pattern: {}", name);
-
-    let result = sqlx::query("DELETE FROM vouch_proposer_patterns WHERE name = $1")
-        .bind(&name)
-        .execute(&state.pool)
-        .await?;
+    info!("Removing relay {} from proposer pattern: {}", url, name);
 
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Proposer pattern '{}' not found",
-            name
-        )));
-    }
+    let changes = patterns::delete_relay(&state.pool, &name, &url).await?;
 
     // Audit log
     if state.config.audit_enabled {
-        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposerPattern, &name);
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchProposerPattern, &name, changes);
     }
 
     Ok(StatusCode::NO_CONTENT)
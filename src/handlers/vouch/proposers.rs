@@ -1,28 +1,45 @@
 // handlers/vouch/proposers.rs - Proposer CRUD handlers
-use crate::audit::{AuditAction, AuditChanges, RequestContext, ResourceType};
+use crate::addresses::BlsPubkey;
+use crate::audit::{AuditAction, RequestContext, ResourceType};
 use crate::audit_log;
+use crate::config::CleanupAction;
 use crate::errors::ApiError;
+use crate::handlers::extract::ApiPath;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::proposer_cleanup::run_cleanup;
 use crate::schema::{
-    CreateOrUpdateProposerRequest, PaginatedResponse, ProposerListItem, ProposerRelayConfig,
-    ProposerResponse,
+    CountResponse, CreateOrUpdateProposerRequest, IntendedRegistration, PaginatedResponse, ProposerCleanupResponse,
+    ProposerListItem, ProposerRegistrationsResponse, ProposerResponse, RelayRegistration,
 };
+use crate::services::configs::{load_config_chain, merge_config_chain};
+use crate::services::proposers;
+use crate::services::PageRequest;
+use crate::units::{MinValues, UnitsQuery, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_keys};
 use crate::AppState;
+use crate::handlers::labels::{label_filters, validate_labels};
+use crate::models::Labels;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{OriginalUri, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
 use serde::Deserialize;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{info, instrument};
 use utoipa::IntoParams;
 
+pub use crate::services::proposers::{MissingField, ProposerCountFilters};
+
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct ProposerFilters {
     pub public_key: Option<String>,
     pub fee_recipient: Option<String>,
+    /// Exclude proposers with this fee recipient (those without their own one are kept)
+    pub fee_recipient_ne: Option<String>,
     pub gas_limit: Option<String>,
     pub min_value: Option<String>,
     pub reset_relays: Option<bool>,
@@ -32,12 +49,56 @@ pub struct ProposerFilters {
     pub relay_min_value: Option<String>,
     /// Filter by relay disabled status
     pub relay_disabled: Option<bool>,
+    /// Filter by decommissioned status
+    pub decommissioned: Option<bool>,
+    /// Only proposers without their own value for this field, so they rely on the default
+    pub missing: Option<MissingField>,
+    /// Set from `label.<key>` query parameters by the handler
+    #[serde(skip)]
+    #[param(ignore)]
+    pub labels: Labels,
+    /// Set from the `TimeRangeFilters` query parameters by the handler
+    #[serde(skip)]
+    #[param(ignore)]
+    pub time_range: TimeRangeFilters,
+    /// Comma-separated extra data to embed in each item (supported: `relays`)
+    pub include: Option<String>,
+    /// Sort column: public_key, created_at, updated_at (default: created_at desc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
     pub offset: i64,
 }
 
+impl ProposerFilters {
+    fn count_filters(&self) -> ProposerCountFilters {
+        ProposerCountFilters {
+            public_key: self.public_key.clone(),
+            fee_recipient: self.fee_recipient.clone(),
+            fee_recipient_ne: self.fee_recipient_ne.clone(),
+            gas_limit: self.gas_limit.clone(),
+            min_value: self.min_value.clone(),
+            reset_relays: self.reset_relays,
+            relay_url: self.relay_url.clone(),
+            relay_min_value: self.relay_min_value.clone(),
+            relay_disabled: self.relay_disabled,
+            decommissioned: self.decommissioned,
+            missing: self.missing,
+            labels: self.labels.clone(),
+            time_range: self.time_range.clone(),
+        }
+    }
+
+    fn include_relays(&self) -> bool {
+        self.include
+            .as_deref()
+            .is_some_and(|include| include.split(',').any(|part| part.trim() == "relays"))
+    }
+}
+
 fn default_limit() -> i64 {
     100
 }
@@ -45,140 +106,169 @@ fn default_limit() -> i64 {
 #[utoipa::path(
     get,
     path = "/api/admin/vouch/proposers",
-    params(ProposerFilters),
+    params(
+        ProposerFilters,
+        TimeRangeFilters,
+        UnitsQuery,
+        ("label.<key>" = Option<String>, Query, description = "Filter by label value, e.g. `label.team=alpha` (repeatable, all must match)")
+    ),
     responses(
-        (status = 200, description = "List of proposers", body = PaginatedResponse<ProposerListItem>)
+        (status = 200, description = "List of proposers", body = PaginatedResponse<ProposerListItem>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
     ),
     tag = "Vouch - Proposers",
     security(("bearer_auth" = []))
 )]
-#[instrument(skip(state))]
+#[instrument(skip(state, uri))]
 pub async fn list_proposers(
     State(state): State<Arc<AppState>>,
-    Query(filters): Query<ProposerFilters>,
-) -> Result<Json<PaginatedResponse<ProposerListItem>>, ApiError> {
+    OriginalUri(uri): OriginalUri,
+    Query(mut filters): Query<ProposerFilters>,
+    Query(time_range): Query<TimeRangeFilters>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Page<ProposerListItem>, ApiError> {
+    filters.labels = label_filters(&params)?;
+    filters.time_range = time_range;
     info!("Listing proposers with filters: {:?}", filters);
+    check_limit(filters.limit, state.config.api.max_page_size)?;
 
-    // Build dynamic query based on filters
-    let mut conditions = Vec::new();
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
+        limit: filters.limit,
+        offset: filters.offset,
+    };
+    let mut response = proposers::list(&state.pool, &filters.count_filters(), page, filters.include_relays()).await?;
+    response.data.display_min_values(units);
 
-    if let Some(ref pk) = filters.public_key {
-        conditions.push(format!("p.public_key LIKE '{}%'", pk.replace('\'', "''")));
-    }
-    if let Some(ref fr) = filters.fee_recipient {
-        conditions.push(format!("p.fee_recipient = '{}'", fr.replace('\'', "''")));
-    }
-    if let Some(ref gl) = filters.gas_limit {
-        conditions.push(format!("p.gas_limit = '{}'", gl.replace('\'', "''")));
-    }
-    if let Some(ref mv) = filters.min_value {
-        conditions.push(format!("p.min_value = '{}'", mv.replace('\'', "''")));
-    }
-    if let Some(rr) = filters.reset_relays {
-        conditions.push(format!(
-            "p.reset_relays = {}",
-            if rr { "true" } else { "false" }
-        ));
-    }
-    // Relay filters using EXISTS subquery
-    if let Some(ref relay_url) = filters.relay_url {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.url LIKE '{}%')",
-            relay_url.replace('\'', "''")
-        ));
-    }
-    if let Some(ref relay_min_value) = filters.relay_min_value {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.min_value = '{}')",
-            relay_min_value.replace('\'', "''")
-        ));
-    }
-    if let Some(relay_disabled) = filters.relay_disabled {
-        conditions.push(format!(
-            "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.disabled = {})",
-            if relay_disabled { "true" } else { "false" }
-        ));
-    }
+    Ok(Page::new(uri, response))
+}
 
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
-    };
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposers/count",
+    params(
+        ProposerCountFilters,
+        TimeRangeFilters,
+        ("label.<key>" = Option<String>, Query, description = "Filter by label value, e.g. `label.team=alpha` (repeatable, all must match)")
+    ),
+    responses(
+        (status = 200, description = "Number of proposers matching the filters", body = CountResponse)
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn count_proposers(
+    State(state): State<Arc<AppState>>,
+    Query(mut filters): Query<ProposerCountFilters>,
+    Query(time_range): Query<TimeRangeFilters>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Result<Json<CountResponse>, ApiError> {
+    filters.labels = label_filters(&params)?;
+    filters.time_range = time_range;
+    let count = proposers::count(&state.pool, &filters).await?;
+
+    Ok(Json(CountResponse { count }))
+}
 
-    // Count query
-    let count_sql = format!("SELECT COUNT(*) as count FROM vouch_proposers p {}", where_clause);
-    let total: i64 = sqlx::query_scalar(&count_sql)
-        .fetch_one(&state.pool)
-        .await?;
-
-    // Data query
-    let data_sql = format!(
-        "SELECT p.public_key, p.fee_recipient, p.gas_limit, p.min_value, p.reset_relays, p.created_at, p.updated_at
-         FROM vouch_proposers p {}
-         ORDER BY p.created_at DESC
-         LIMIT {} OFFSET {}",
-        where_clause, filters.limit, filters.offset
-    );
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ProposerCleanupQuery {
+    /// Days without an execution-config request before a proposer counts as inactive
+    pub inactive_days: u32,
+    /// decommission (default) or delete
+    #[serde(default)]
+    #[param(inline)]
+    pub action: CleanupAction,
+    /// Report the inactive proposers without changing anything (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
 
-    let proposers = sqlx::query_as::<_, crate::models::VouchProposer>(&data_sql)
-        .fetch_all(&state.pool)
-        .await?;
-
-    // Fetch relays for all proposers in the result
-    let pubkeys: Vec<String> = proposers.iter().map(|p| p.public_key.to_string()).collect();
-    let relays_map = if !pubkeys.is_empty() {
-        let placeholders: Vec<String> = pubkeys.iter().enumerate()
-            .map(|(i, _)| format!("${}", i + 1))
-            .collect();
-        let relays_sql = format!(
-            "SELECT id, proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled
-             FROM vouch_proposer_relays WHERE proposer_public_key IN ({})",
-            placeholders.join(", ")
-        );
-        let mut query = sqlx::query_as::<_, crate::models::VouchProposerRelay>(&relays_sql);
-        for pk in &pubkeys {
-            query = query.bind(pk);
-        }
-        let all_relays = query.fetch_all(&state.pool).await?;
-
-        // Group relays by proposer_public_key
-        let mut map: HashMap<String, HashMap<String, ProposerRelayConfig>> = HashMap::new();
-        for relay in all_relays {
-            map.entry(relay.proposer_public_key.to_string())
-                .or_default()
-                .insert(relay.url.clone(), relay.into());
-        }
-        map
-    } else {
-        HashMap::new()
-    };
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposers/cleanup",
+    params(ProposerCleanupQuery),
+    responses(
+        (status = 200, description = "Inactive proposers and what was done to them", body = ProposerCleanupResponse),
+        (status = 400, description = "inactive_days is 0, or vouch.track_usage is off")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn cleanup_proposers(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Query(query): Query<ProposerCleanupQuery>,
+) -> Result<Json<ProposerCleanupResponse>, ApiError> {
+    info!(
+        "Cleaning up proposers inactive for {} days ({:?}, dry run: {})",
+        query.inactive_days, query.action, query.dry_run
+    );
+    if !state.config.vouch.track_usage {
+        return Err(ApiError::InvalidData(
+            "Proposer cleanup needs vouch.track_usage to see which proposers are requested".to_string(),
+        ));
+    }
+    if query.inactive_days == 0 {
+        return Err(ApiError::InvalidData("inactive_days must be greater than 0".to_string()));
+    }
 
-    let data: Vec<ProposerListItem> = proposers
-        .into_iter()
-        .map(|p| {
-            let relays = relays_map.get(&p.public_key.to_string()).cloned();
-            let mut item: ProposerListItem = p.into();
-            item.relays = relays;
-            item
-        })
-        .collect();
+    let outcome = run_cleanup(
+        &state.pool,
+        &ctx,
+        state.config.audit_enabled,
+        query.inactive_days,
+        query.action,
+        query.dry_run,
+    )
+    .await?;
 
-    Ok(Json(PaginatedResponse {
-        data,
-        total,
-        limit: filters.limit,
-        offset: filters.offset,
+    Ok(Json(ProposerCleanupResponse {
+        action: query.action,
+        dry_run: query.dry_run,
+        cutoff: outcome.cutoff,
+        count: outcome.public_keys.len(),
+        public_keys: outcome.public_keys,
     }))
 }
 
 #[utoipa::path(
-    get,
+    head,
     path = "/api/admin/vouch/proposers/{public_key}",
     params(
         ("public_key" = String, Path, description = "Proposer public key")
     ),
     responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Proposer exists"),
+        (status = 404, description = "Proposer not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_proposer(
+    State(state): State<Arc<AppState>>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+) -> Result<StatusCode, ApiError> {
+    proposers::ensure_exists(&state.pool, &public_key.to_string()).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposers/{public_key}",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key"),
+        UnitsQuery
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
         (status = 200, description = "Proposer details", body = ProposerResponse),
         (status = 404, description = "Proposer not found")
     ),
@@ -188,45 +278,97 @@ pub async fn list_proposers(
 #[instrument(skip(state))]
 pub async fn get_proposer(
     State(state): State<Arc<AppState>>,
-    Path(public_key): Path<String>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
 ) -> Result<Json<ProposerResponse>, ApiError> {
+    let public_key = public_key.to_string();
     info!("Getting proposer: {}", public_key);
 
-    let proposer = sqlx::query_as::<_, crate::models::VouchProposer>(
-        "SELECT public_key, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-         FROM vouch_proposers WHERE public_key = $1",
+    let mut response = proposers::get(&state.pool, &public_key).await?;
+    response.display_min_values(units);
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RegistrationsQuery {
+    /// Default config supplying intended values the proposer does not override
+    pub config: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/proposers/{public_key}/registrations",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key"),
+        RegistrationsQuery
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Intended vs observed registrations", body = ProposerRegistrationsResponse),
+        (status = 404, description = "Proposer or default config not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_proposer_registrations(
+    State(state): State<Arc<AppState>>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+    Query(query): Query<RegistrationsQuery>,
+) -> Result<Json<ProposerRegistrationsResponse>, ApiError> {
+    let public_key = public_key.to_string();
+    info!("Getting registrations for proposer: {}", public_key);
+
+    let proposer = sqlx::query_as!(
+        crate::models::VouchProposer,
+        r#"SELECT public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels as "labels: _", relay_sets, decommissioned_at, id, created_at, updated_at
+           FROM vouch_proposers WHERE public_key = $1"#,
+        public_key
     )
-    .bind(&public_key)
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| ApiError::NotFound(format!("Proposer '{}' not found", public_key)))?;
 
-    let relays = sqlx::query_as::<_, crate::models::VouchProposerRelay>(
-        "SELECT id, proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled
-         FROM vouch_proposer_relays WHERE proposer_public_key = $1",
+    let mut intended = IntendedRegistration {
+        fee_recipient: proposer.fee_recipient,
+        gas_limit: proposer.gas_limit,
+    };
+    if let Some(config) = &query.config {
+        let mut conn = state.pool.acquire().await?;
+        let chain = load_config_chain(&mut conn, config).await?;
+        let default_config = merge_config_chain(chain)
+            .ok_or_else(|| ApiError::NotFound(format!("Default config '{}' not found", config)))?;
+        intended.fee_recipient = intended.fee_recipient.or(default_config.fee_recipient);
+        intended.gas_limit = intended.gas_limit.or(default_config.gas_limit);
+    }
+
+    let rows = sqlx::query_as!(
+        crate::models::VouchValidatorRegistration,
+        r#"SELECT public_key as "public_key: _", relay_url, fee_recipient as "fee_recipient: _", gas_limit, registered_at, checked_at
+           FROM vouch_validator_registrations WHERE public_key = $1
+           ORDER BY relay_url"#,
+        public_key
     )
-    .bind(&public_key)
     .fetch_all(&state.pool)
     .await?;
 
-    let relays_map: HashMap<String, ProposerRelayConfig> = relays
+    let registrations = rows
         .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
+        .map(|r| RelayRegistration {
+            fee_recipient_matches: intended.fee_recipient.as_ref().map(|f| *f == r.fee_recipient),
+            gas_limit_matches: intended.gas_limit.as_ref().map(|g| *g == r.gas_limit),
+            relay_url: r.relay_url,
+            fee_recipient: r.fee_recipient,
+            gas_limit: r.gas_limit,
+            registered_at: r.registered_at,
+            checked_at: r.checked_at,
+        })
         .collect();
 
-    Ok(Json(ProposerResponse {
+    Ok(Json(ProposerRegistrationsResponse {
         public_key: proposer.public_key,
-        fee_recipient: proposer.fee_recipient,
-        gas_limit: proposer.gas_limit,
-        min_value: proposer.min_value,
-        reset_relays: proposer.reset_relays,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: proposer.created_at,
-        updated_at: proposer.updated_at,
+        intended,
+        registrations,
     }))
 }
 
@@ -238,6 +380,7 @@ pub async fn get_proposer(
     ),
     request_body = CreateOrUpdateProposerRequest,
     responses(
+        (status = 400, description = "Malformed public key or unknown relay set"),
         (status = 200, description = "Proposer updated", body = ProposerResponse),
         (status = 201, description = "Proposer created", body = ProposerResponse)
     ),
@@ -248,128 +391,26 @@ pub async fn get_proposer(
 pub async fn create_or_update_proposer(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
-    Path(public_key): Path<String>,
-    Json(req): Json<CreateOrUpdateProposerRequest>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+    Json(mut req): Json<CreateOrUpdateProposerRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
+    let public_key = public_key.to_string();
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
     info!("Creating/updating proposer: {}", public_key);
 
-    let mut tx = state.pool.begin().await?;
-
-    // Check if proposer exists
-    let existing = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM vouch_proposers WHERE public_key = $1",
-    )
-    .bind(&public_key)
-    .fetch_one(&mut *tx)
-    .await?;
-
-    let is_new = existing == 0;
-
-    if is_new {
-        sqlx::query(
-            "INSERT INTO vouch_proposers (public_key, fee_recipient, gas_limit, min_value, reset_relays)
-             VALUES ($1, $2, $3, $4, $5)",
-        )
-        .bind(&public_key)
-        .bind(&req.fee_recipient)
-        .bind(&req.gas_limit)
-        .bind(&req.min_value)
-        .bind(req.reset_relays)
-        .execute(&mut *tx)
-        .await?;
-    } else {
-        sqlx::query(
-            "UPDATE vouch_proposers
-             SET fee_recipient = $2, gas_limit = $3, min_value = $4, reset_relays = $5
-             WHERE public_key = $1",
-        )
-        .bind(&public_key)
-        .bind(&req.fee_recipient)
-        .bind(&req.gas_limit)
-        .bind(&req.min_value)
-        .bind(req.reset_relays)
-        .execute(&mut *tx)
-        .await?;
-    }
-
-    // Handle relays - delete existing and insert new
-    sqlx::query("DELETE FROM vouch_proposer_relays WHERE proposer_public_key = $1")
-        .bind(&public_key)
-        .execute(&mut *tx)
-        .await?;
-
-    if let Some(relays) = &req.relays {
-        for (url, relay) in relays {
-            sqlx::query(
-                "INSERT INTO vouch_proposer_relays
-                 (proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
-            )
-            .bind(&public_key)
-            .bind(url)
-            .bind(&relay.public_key)
-            .bind(&relay.fee_recipient)
-            .bind(&relay.gas_limit)
-            .bind(&relay.min_value)
-            .bind(relay.disabled)
-            .execute(&mut *tx)
-            .await?;
-        }
-    }
+    validate_labels(&req.labels)?;
 
-    tx.commit().await?;
+    let (mut response, is_new, changes) = proposers::put(&state.pool, &public_key, &req).await?;
+    response.display_min_values(ValueUnits::Eth);
 
     // Audit log
     if state.config.audit_enabled {
-        let changes = AuditChanges {
-            fee_recipient: req.fee_recipient.as_ref().map(|a| a.to_string()),
-            min_value: req.min_value.clone(),
-            gas_limit: req.gas_limit.clone(),
-            reset_relays: Some(req.reset_relays),
-            relays_count: req.relays.as_ref().map(|r| r.len()),
-            ..Default::default()
-        };
         let action = if is_new { AuditAction::Create } else { AuditAction::Update };
         audit_log!(ctx, action, ResourceType::VouchProposer, &public_key, changes);
     }
 
-    // Fetch the result
-    let proposer = sqlx::query_as::<_, crate::models::VouchProposer>(
-        "SELECT public_key, fee_recipient, gas_limit, min_value, reset_relays, created_at, updated_at
-         FROM vouch_proposers WHERE public_key = $1",
-    )
-    .bind(&public_key)
-    .fetch_one(&state.pool)
-    .await?;
-
-    let relays = sqlx::query_as::<_, crate::models::VouchProposerRelay>(
-        "SELECT id, proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled
-         FROM vouch_proposer_relays WHERE proposer_public_key = $1",
-    )
-    .bind(&public_key)
-    .fetch_all(&state.pool)
-    .await?;
-
-    let relays_map: HashMap<String, ProposerRelayConfig> = relays
-        .into_iter()
-        .map(|r| (r.url.clone(), r.into()))
-        .collect();
-
-    let response = ProposerResponse {
-        public_key: proposer.public_key,
-        fee_recipient: proposer.fee_recipient,
-        gas_limit: proposer.gas_limit,
-        min_value: proposer.min_value,
-        reset_relays: proposer.reset_relays,
-        relays: if relays_map.is_empty() {
-            None
-        } else {
-            Some(relays_map)
-        },
-        created_at: proposer.created_at,
-        updated_at: proposer.updated_at,
-    };
-
     if is_new {
         Ok((StatusCode::CREATED, Json(response)))
     } else {
@@ -384,6 +425,7 @@ pub async fn create_or_update_proposer(
         ("public_key" = String, Path, description = "Proposer public key")
     ),
     responses(
+        (status = 400, description = "Malformed public key"),
         (status = 204, description = "Proposer deleted"),
         (status = 404, description = "Proposer not found")
     ),
@@ -394,26 +436,166 @@ pub async fn create_or_update_proposer(
 pub async fn delete_proposer(
     State(state): State<Arc<AppState>>,
     ctx: RequestContext,
-    Path(public_key): Path<String>,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
 ) -> Result<impl IntoResponse, ApiError> {
+    let public_key = public_key.to_string();
     info!("Deleting proposer: {}", public_key);
 
-    let result = sqlx::query("DELETE FROM vouch_proposers WHERE public_key = $1")
-        .bind(&public_key)
-        .execute(&state.pool)
-        .await?;
+    let changes = proposers::delete(&state.pool, &public_key).await?;
 
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound(format!(
-            "Proposer '{}' not found",
-            public_key
-        )));
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposer, &public_key, changes);
     }
 
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Flip the `disabled` flag on a single proposer relay, leaving other fields untouched
+async fn set_proposer_relay_disabled(
+    state: &AppState,
+    ctx: &RequestContext,
+    public_key: &str,
+    url: &str,
+    disabled: bool,
+) -> Result<ProposerResponse, ApiError> {
+    let (mut response, changes) = proposers::set_relay_disabled(&state.pool, public_key, url, disabled).await?;
+    response.display_min_values(ValueUnits::Eth);
+
     // Audit log
     if state.config.audit_enabled {
-        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposer, &public_key);
+        let action = if disabled { AuditAction::DisableRelay } else { AuditAction::EnableRelay };
+        audit_log!(ctx, action, ResourceType::VouchProposer, public_key, changes);
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposers/{public_key}/relays/{url}/disable",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Relay disabled", body = ProposerResponse),
+        (status = 404, description = "Proposer or relay not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn disable_proposer_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    ApiPath((public_key, url)): ApiPath<(BlsPubkey, String)>,
+) -> Result<Json<ProposerResponse>, ApiError> {
+    let public_key = public_key.to_string();
+    info!("Disabling relay {} for proposer: {}", url, public_key);
+
+    let response = set_proposer_relay_disabled(&state, &ctx, &public_key, &url, true).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposers/{public_key}/relays/{url}/enable",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key"),
+        ("url" = String, Path, description = "Relay URL (percent-encoded)")
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Relay enabled", body = ProposerResponse),
+        (status = 404, description = "Proposer or relay not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn enable_proposer_relay(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    ApiPath((public_key, url)): ApiPath<(BlsPubkey, String)>,
+) -> Result<Json<ProposerResponse>, ApiError> {
+    let public_key = public_key.to_string();
+    info!("Enabling relay {} for proposer: {}", url, public_key);
+
+    let response = set_proposer_relay_disabled(&state, &ctx, &public_key, &url, false).await?;
+    Ok(Json(response))
+}
+
+/// Set or clear `decommissioned_at`; repeating the current state is a no-op
+async fn set_proposer_decommissioned(
+    state: &AppState,
+    ctx: &RequestContext,
+    public_key: &str,
+    decommissioned: bool,
+) -> Result<ProposerResponse, ApiError> {
+    let (mut response, changes) = proposers::set_decommissioned(&state.pool, public_key, decommissioned).await?;
+    response.display_min_values(ValueUnits::Eth);
+
+    // Audit log
+    if let (true, Some(changes)) = (state.config.audit_enabled, changes) {
+        let action = if decommissioned { AuditAction::Decommission } else { AuditAction::Reactivate };
+        audit_log!(ctx, action, ResourceType::VouchProposer, public_key, changes);
+    }
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposers/{public_key}/decommission",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key")
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Proposer decommissioned", body = ProposerResponse),
+        (status = 404, description = "Proposer not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn decommission_proposer(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+) -> Result<Json<ProposerResponse>, ApiError> {
+    let public_key = public_key.to_string();
+    info!("Decommissioning proposer: {}", public_key);
+
+    let response = set_proposer_decommissioned(&state, &ctx, &public_key, true).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/proposers/{public_key}/reactivate",
+    params(
+        ("public_key" = String, Path, description = "Proposer public key")
+    ),
+    responses(
+        (status = 400, description = "Malformed public key"),
+        (status = 200, description = "Proposer reactivated", body = ProposerResponse),
+        (status = 404, description = "Proposer not found")
+    ),
+    tag = "Vouch - Proposers",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn reactivate_proposer(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    ApiPath(public_key): ApiPath<BlsPubkey>,
+) -> Result<Json<ProposerResponse>, ApiError> {
+    let public_key = public_key.to_string();
+    info!("Reactivating proposer: {}", public_key);
+
+    let response = set_proposer_decommissioned(&state, &ctx, &public_key, false).await?;
+    Ok(Json(response))
 }
@@ -0,0 +1,232 @@
+// handlers/vouch/relay_sets.rs - Relay Set CRUD handlers
+use crate::audit::{AuditAction, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::errors::ApiError;
+use crate::handlers::pagination::{check_limit, Page};
+use crate::handlers::sort::SortOrder;
+use crate::schema::{CreateRelaySetRequest, PaginatedResponse, RelaySetResponse, UpdateRelaySetRequest};
+use crate::services::relay_sets::{self, SetFilters};
+use crate::services::PageRequest;
+use crate::units::{MinValues, UnitsQuery, ValueUnits};
+use crate::validation::{check_gas_limits, check_relay_keys};
+use crate::AppState;
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RelaySetFilters {
+    /// Filter by name (prefix match)
+    pub name: Option<String>,
+    /// Filter by relay URL (prefix match)
+    pub relay_url: Option<String>,
+    /// Sort column: name, created_at, updated_at (default: name asc)
+    pub sort: Option<String>,
+    /// Sort direction: asc or desc
+    pub order: Option<SortOrder>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/relay-sets",
+    params(RelaySetFilters, UnitsQuery),
+    responses(
+        (status = 200, description = "List of relay sets", body = PaginatedResponse<RelaySetResponse>,
+            headers(("link" = String, description = "Next and previous pages, RFC 5988"))),
+        (status = 400, description = "Invalid sort column"),
+        (status = 422, description = "limit exceeds api.max_page_size")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, uri))]
+pub async fn list_relay_sets(
+    State(state): State<Arc<AppState>>,
+    OriginalUri(uri): OriginalUri,
+    Query(filters): Query<RelaySetFilters>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Page<RelaySetResponse>, ApiError> {
+    info!("Listing relay sets with filters: {:?}", filters);
+    check_limit(filters.limit, state.config.api.max_page_size)?;
+
+    let set_filters = SetFilters {
+        name: filters.name,
+        relay_url: filters.relay_url,
+    };
+    let page = PageRequest {
+        sort: filters.sort.as_deref(),
+        order: filters.order,
+        limit: filters.limit,
+        offset: filters.offset,
+    };
+    let mut response = relay_sets::list(&state.pool, &set_filters, page).await?;
+    response.data.display_min_values(units);
+
+    Ok(Page::new(uri, response))
+}
+
+#[utoipa::path(
+    head,
+    path = "/api/admin/vouch/relay-sets/{name}",
+    params(
+        ("name" = String, Path, description = "Relay set name")
+    ),
+    responses(
+        (status = 200, description = "Relay set exists"),
+        (status = 404, description = "Relay set not found")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn head_relay_set(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    relay_sets::ensure_exists(&state.pool, &name).await?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/relay-sets/{name}",
+    params(
+        ("name" = String, Path, description = "Relay set name"),
+        UnitsQuery
+    ),
+    responses(
+        (status = 200, description = "Relay set details", body = RelaySetResponse),
+        (status = 404, description = "Relay set not found")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_relay_set(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(UnitsQuery { units }): Query<UnitsQuery>,
+) -> Result<Json<RelaySetResponse>, ApiError> {
+    info!("Getting relay set: {}", name);
+
+    let mut response = relay_sets::get(&state.pool, &name).await?;
+    response.display_min_values(units);
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/relay-sets",
+    request_body = CreateRelaySetRequest,
+    responses(
+        (status = 201, description = "Relay set created", body = RelaySetResponse),
+        (status = 409, description = "Relay set already exists")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn create_relay_set(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Json(mut req): Json<CreateRelaySetRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
+    info!("Creating relay set: {}", req.name);
+
+    let (mut response, changes) = relay_sets::create(&state.pool, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Create, ResourceType::VouchRelaySet, &req.name, changes);
+    }
+
+    response.display_min_values(ValueUnits::Eth);
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/vouch/relay-sets/{name}",
+    params(
+        ("name" = String, Path, description = "Relay set name")
+    ),
+    request_body = UpdateRelaySetRequest,
+    responses(
+        (status = 200, description = "Relay set updated", body = RelaySetResponse),
+        (status = 404, description = "Relay set not found")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn update_relay_set(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+    Json(mut req): Json<UpdateRelaySetRequest>,
+) -> Result<Json<RelaySetResponse>, ApiError> {
+    req.normalize_min_values()?;
+    check_gas_limits(&state.config.validation, &ctx, &mut req)?;
+    check_relay_keys(&state.config.validation, &req)?;
+    info!("Updating relay set: {}", name);
+
+    let (mut response, changes) = relay_sets::update(&state.pool, &name, &req).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Update, ResourceType::VouchRelaySet, &name, changes);
+    }
+
+    response.display_min_values(ValueUnits::Eth);
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/vouch/relay-sets/{name}",
+    params(
+        ("name" = String, Path, description = "Relay set name")
+    ),
+    responses(
+        (status = 204, description = "Relay set deleted"),
+        (status = 404, description = "Relay set not found"),
+        (status = 409, description = "Relay set is referenced by configs, proposers, patterns or groups")
+    ),
+    tag = "Vouch - Relay Sets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn delete_relay_set(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    info!("Deleting relay set: {}", name);
+
+    let changes = relay_sets::delete(&state.pool, &name).await?;
+
+    // Audit log
+    if state.config.audit_enabled {
+        audit_log!(ctx, AuditAction::Delete, ResourceType::VouchRelaySet, &name, changes);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,65 @@
+// handlers/vouch/tags.rs - Tags across proposer patterns
+use crate::audit::{AuditAction, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::errors::ApiError;
+use crate::schema::{RenameTagRequest, RenameTagResponse, TagUsage};
+use crate::services::patterns;
+use crate::AppState;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/vouch/tags",
+    responses(
+        (status = 200, description = "Every tag in use, sorted by name", body = Vec<TagUsage>)
+    ),
+    tag = "Vouch - Tags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_tags(State(state): State<Arc<AppState>>) -> Result<Json<Vec<TagUsage>>, ApiError> {
+    info!("Listing tags");
+
+    Ok(Json(patterns::tags(&state.pool).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/vouch/tags/{tag}/rename",
+    params(
+        ("tag" = String, Path, description = "Current tag")
+    ),
+    request_body = RenameTagRequest,
+    responses(
+        (status = 200, description = "Tag renamed on every pattern carrying it", body = RenameTagResponse),
+        (status = 400, description = "Invalid new tag"),
+        (status = 404, description = "No pattern carries the tag")
+    ),
+    tag = "Vouch - Tags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, ctx))]
+pub async fn rename_tag(
+    State(state): State<Arc<AppState>>,
+    ctx: RequestContext,
+    Path(tag): Path<String>,
+    Json(req): Json<RenameTagRequest>,
+) -> Result<Json<RenameTagResponse>, ApiError> {
+    info!("Renaming tag {} to {}", tag, req.to);
+
+    let (response, changes) = patterns::rename_tag(&state.pool, &tag, &req.to).await?;
+
+    // Audit log: one event per changed pattern
+    if state.config.audit_enabled {
+        for (name, changes) in changes {
+            audit_log!(ctx, AuditAction::Update, ResourceType::VouchProposerPattern, &name, changes);
+        }
+    }
+
+    Ok(Json(response))
+}
@@ -0,0 +1,125 @@
+//! Background jobs
+//!
+//! Requests too large to answer within a request's lifetime are accepted with
+//! `202 Accepted` and a job ID instead. The work runs on the replica that
+//! accepted it, and its outcome is kept in the `jobs` table for
+//! `GET /api/admin/jobs/{id}`.
+
+use std::future::Future;
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::types::Json;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::audit::RequestContext;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::schema::{JobKind, JobResponse, JobStatus};
+
+/// How often a running job shows it is still alive
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+/// A running job without a heartbeat for this long died with its replica
+const STALE_AFTER_SECS: f64 = 60.0;
+
+/// Record a job and start `work` in the background; returns the job ID
+///
+/// `work` gets what its synchronous endpoint would: its `Ok` value becomes the
+/// job's result, its error the job's problem details.
+pub async fn submit<F, T>(pool: &PgPool, ctx: &RequestContext, kind: JobKind, work: F) -> Result<Uuid, ApiError>
+where
+    F: Future<Output = Result<T, ApiError>> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        "INSERT INTO jobs (id, kind, actor) VALUES ($1, $2, $3)",
+        id,
+        kind as _,
+        ctx.actor.token_name
+    )
+    .execute(pool)
+    .await?;
+    info!(job_id = %id, ?kind, "Job submitted");
+
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        // A task of its own, so a panic fails the job instead of leaving it running
+        let mut work = tokio::spawn(work);
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        heartbeat.tick().await;
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut work => break outcome,
+                _ = heartbeat.tick() => {
+                    if let Err(e) = sqlx::query!("UPDATE jobs SET heartbeat_at = NOW() WHERE id = $1", id)
+                        .execute(&pool)
+                        .await
+                    {
+                        warn!(job_id = %id, "Job heartbeat failed: {}", e);
+                    }
+                }
+            }
+        };
+
+        let outcome = outcome
+            .unwrap_or_else(|e| Err(ApiError::InternalError(format!("Job panicked: {}", e))))
+            .and_then(|value| Ok(serde_json::to_value(value)?));
+        let (status, result, error) = match outcome {
+            Ok(value) => (JobStatus::Succeeded, Some(value), None),
+            Err(e) => (JobStatus::Failed, None, Some(Json(e.problem()))),
+        };
+        info!(job_id = %id, ?status, "Job finished");
+        if let Err(e) = sqlx::query!(
+            "UPDATE jobs SET status = $2, result = $3, error = $4, finished_at = NOW() WHERE id = $1",
+            id,
+            status as _,
+            result,
+            error as _
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!(job_id = %id, "Failed to record job outcome: {}", e);
+        }
+    });
+
+    Ok(id)
+}
+
+/// Look a job up, failing it first if its replica stopped while it ran
+pub async fn get(pool: &PgPool, id: Uuid) -> Result<Option<JobResponse>, sqlx::Error> {
+    let interrupted =
+        ApiError::ServiceUnavailable("Job was interrupted by its replica stopping; submit it again".to_string())
+            .problem();
+    sqlx::query!(
+        "UPDATE jobs SET status = 'failed', error = $2, finished_at = NOW()
+         WHERE id = $1 AND status = 'running' AND heartbeat_at < NOW() - make_interval(secs => $3)",
+        id,
+        Json(interrupted) as _,
+        STALE_AFTER_SECS
+    )
+    .execute(pool)
+    .await?;
+
+    let job = sqlx::query!(
+        r#"SELECT id, kind AS "kind: JobKind", status AS "status: JobStatus", actor, result,
+                  error AS "error: Json<ProblemDetails>", created_at, finished_at
+           FROM jobs WHERE id = $1"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job.map(|job| JobResponse {
+        id: job.id,
+        kind: job.kind,
+        status: job.status,
+        actor: job.actor,
+        result: job.result,
+        error: job.error.map(|error| error.0),
+        created_at: job.created_at,
+        finished_at: job.finished_at,
+    }))
+}
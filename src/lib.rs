@@ -4,15 +4,31 @@ use sqlx::PgPool;
 pub mod addresses;
 pub mod audit;
 pub mod auth;
+pub mod backups;
+pub mod build_info;
 pub mod config;
+pub mod consistency;
+pub mod cron;
 pub mod errors;
 pub mod handlers;
+pub mod jobs;
+pub mod logging;
+pub mod metrics;
 pub mod models;
+pub mod object_storage;
 pub mod openapi;
+pub mod patch;
+pub mod pattern_matches;
+pub mod proposer_cleanup;
+pub mod registrations;
+pub mod response_cache;
 pub mod schema;
+pub mod services;
+pub mod units;
+pub mod validation;
 
 pub use config::AppConfig;
-pub use handlers::create_router;
+pub use handlers::{create_admin_router, create_public_router, create_router};
 
 #[derive(Debug)]
 pub struct AppState {
@@ -20,9 +36,102 @@ pub struct AppState {
     pub config: AppConfig,
 }
 
-/// Run database migrations
-pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+/// Bind a unix domain socket listener, replacing a socket file left by a previous run
+#[cfg(unix)]
+pub fn bind_unix_socket(path: &str) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    tokio::net::UnixListener::bind(path)
+}
+
+/// Run database migrations. `locking` serializes replicas starting at once with a
+/// session-level advisory lock, which PgBouncer transaction pooling cannot hold.
+pub async fn run_migrations(pool: &PgPool, locking: bool) -> Result<(), sqlx::Error> {
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_locking(locking);
+    migrator.run(pool).await?;
     tracing::info!("Migrations completed successfully");
     Ok(())
 }
+
+/// Row of sqlx's `_sqlx_migrations` bookkeeping table
+#[derive(sqlx::FromRow)]
+struct AppliedMigration {
+    version: i64,
+    description: String,
+    installed_on: chrono::DateTime<chrono::Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+/// Migrations embedded in this binary merged with those recorded in the database, by version
+pub async fn list_migrations(pool: &PgPool) -> Result<Vec<schema::MigrationInfo>, sqlx::Error> {
+    use schema::{MigrationInfo, MigrationState};
+
+    // A database that never ran a migration has no bookkeeping table yet (undefined_table)
+    let recorded: Vec<AppliedMigration> = sqlx::query_as(
+        "SELECT version, description, installed_on, success, checksum FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await
+    .or_else(|e| match e.as_database_error().and_then(|db| db.code()) {
+        Some(code) if code == "42P01" => Ok(Vec::new()),
+        _ => Err(e),
+    })?;
+
+    let migrator = sqlx::migrate!("./migrations");
+    let embedded: Vec<_> = migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .collect();
+    let mut migrations: Vec<MigrationInfo> = embedded
+        .iter()
+        .map(|m| {
+            let record = recorded.iter().find(|r| r.version == m.version);
+            MigrationInfo {
+                version: m.version,
+                description: m.description.to_string(),
+                state: match record {
+                    Some(r) if r.success => MigrationState::Applied,
+                    Some(_) => MigrationState::Failed,
+                    None => MigrationState::Pending,
+                },
+                installed_on: record.map(|r| r.installed_on),
+                checksum_mismatch: record.is_some_and(|r| *r.checksum != *m.checksum),
+            }
+        })
+        .collect();
+    migrations.extend(
+        recorded
+            .into_iter()
+            .filter(|r| !embedded.iter().any(|m| m.version == r.version))
+            .map(|r| MigrationInfo {
+                version: r.version,
+                description: r.description,
+                state: MigrationState::Unknown,
+                installed_on: Some(r.installed_on),
+                checksum_mismatch: false,
+            }),
+    );
+    migrations.sort_by_key(|m| m.version);
+    Ok(migrations)
+}
+
+/// Number of applied migrations and the versions of embedded migrations still to run
+pub async fn migration_status(pool: &PgPool) -> Result<(usize, Vec<i64>), sqlx::Error> {
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success ORDER BY version")
+            .fetch_all(pool)
+            .await?;
+    let pending = sqlx::migrate!("./migrations")
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .map(|m| m.version)
+        .collect();
+    Ok((applied.len(), pending))
+}
@@ -0,0 +1,44 @@
+//! Tracing setup with a log filter that can be replaced at runtime
+
+use std::sync::OnceLock;
+use tracing_subscriber::{
+    fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry,
+};
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the global subscriber; call once at startup.
+/// `log_format` is `json` or `text`.
+pub fn init_tracing(log_level: &str, log_format: &str) {
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(log_level));
+    let subscriber = tracing_subscriber::registry().with(filter);
+
+    if log_format == "json" {
+        subscriber.with(fmt::layer().json()).init();
+    } else {
+        subscriber.with(fmt::layer()).init();
+    }
+    let _ = FILTER_HANDLE.set(handle);
+}
+
+/// Directives of the active filter, or `None` when `init_tracing` was not called
+pub fn log_level() -> Option<String> {
+    FILTER_HANDLE
+        .get()
+        .and_then(|handle| handle.with_current(ToString::to_string).ok())
+}
+
+/// Replace the active filter and return the directives it replaced
+pub fn set_log_level(filter: EnvFilter) -> Result<String, String> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "Tracing was not initialized with a reloadable filter".to_string())?;
+    let mut previous = String::new();
+    handle
+        .modify(|current| {
+            previous = current.to_string();
+            *current = filter;
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(previous)
+}
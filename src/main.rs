@@ -1,40 +1,76 @@
 // main.rs
-use fee_manager::{config, create_router, run_migrations, AppState};
+use fee_manager::{
+    config, create_admin_router, create_public_router, create_router, run_migrations, AppState,
+};
+use fee_manager::schema::MigrationState;
 use sqlx::postgres::PgPoolOptions;
+use std::future::IntoFuture;
 use std::sync::Arc;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+const USAGE: &str = "Usage: fee-manager [check-config [--check-db] | migrate [--dry-run]]";
 
 #[tokio::main]
 async fn main() {
-    // Load configuration
-    let config = config::load_config().expect("Failed to load configuration");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => {}
+        Some("check-config") => {
+            let check_db = match args.get(1).map(String::as_str) {
+                None => false,
+                Some("--check-db") => true,
+                Some(_) => exit_with(USAGE),
+            };
+            check_config(check_db).await;
+            return;
+        }
+        Some("migrate") => {
+            let dry_run = match args.get(1).map(String::as_str) {
+                None => false,
+                Some("--dry-run") => true,
+                Some(_) => exit_with(USAGE),
+            };
+            migrate(dry_run).await;
+            return;
+        }
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            return;
+        }
+        Some(_) => exit_with(USAGE),
+    }
 
-    // Initialize tracing with conditional JSON format
-    let subscriber = tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(&config.log_level));
+    // Load and validate configuration
+    let config = load_valid_config();
 
-    if config.log_format == "json" {
-        subscriber
-            .with(tracing_subscriber::fmt::layer().json())
-            .init();
-    } else {
-        subscriber.with(tracing_subscriber::fmt::layer()).init();
-    }
+    // Initialize tracing with conditional JSON format
+    fee_manager::logging::init_tracing(&config.log_level, &config.log_format);
 
     // Initialize audit writer if audit is enabled
     if config.audit_enabled {
-        fee_manager::audit::init_audit_writer(&config.audit_output);
+        fee_manager::audit::init_audit_writer(
+            &config.audit_output,
+            &config.audit_rotation,
+            &config.audit_queue,
+            config.audit_sink.as_ref(),
+            config
+                .audit_database
+                .then(|| config.database.connect_options().expect("Invalid database URL")),
+        );
     }
 
+    // Initialize token hashing scheme
+    fee_manager::auth::hashing::init_token_hasher(&config.auth)
+        .expect("Invalid auth configuration");
+
     // Create database connection pool
     let pool = PgPoolOptions::new()
         .max_connections(5)
-        .connect(&config.database.database_url())
+        .connect_with(config.database.connect_options().expect("Invalid database URL"))
         .await
         .expect("Failed to create pool");
 
     // Run migrations
-    if let Err(e) = run_migrations(&pool).await {
+    if let Err(e) = run_migrations(&pool, !config.database.pgbouncer_mode).await {
         panic!("Error running migrations: {}", e);
     }
 
@@ -54,18 +90,201 @@ async fn main() {
         }
     }
 
+    // Look for damage left by manual database edits if enabled
+    if config.consistency_check.on_startup {
+        fee_manager::consistency::run_on_startup(&pool, &config.consistency_check).await;
+    }
+
+    // Start validator registration tracking if enabled
+    if config.vouch.registrations.enabled {
+        fee_manager::registrations::spawn(pool.clone(), &config.vouch.registrations)
+            .expect("Failed to start validator registration sync");
+    }
+
+    // Start inactive proposer cleanup if enabled
+    if config.vouch.cleanup.enabled {
+        fee_manager::proposer_cleanup::spawn(pool.clone(), &config.vouch.cleanup, config.audit_enabled);
+    }
+
+    // Keep the proposer pattern matches current if enabled
+    if config.vouch.pattern_matches.enabled {
+        fee_manager::pattern_matches::spawn(pool.clone(), &config.vouch.pattern_matches);
+    }
+
+    // Delete stored audit events past their retention if configured
+    if config.audit_retention_days > 0 {
+        fee_manager::audit::spawn_retention(pool.clone(), config.audit_retention_days);
+    }
+
+    // Take scheduled backups if enabled (validate made sure export_storage is set)
+    if let (true, Some(storage)) = (config.backups.enabled, &config.export_storage) {
+        fee_manager::backups::spawn(pool.clone(), &config.backups, storage);
+    }
+
     // Create shared state
     let state = Arc::new(AppState {
         pool,
         config: config.clone(),
     });
 
-    // Build our application with routes
-    let app = create_router(state);
+    // Build our application with routes; admin routes move to their own listener if configured
+    let (app, admin_app) = match &config.listen.admin {
+        Some(_) => (
+            create_public_router(state.clone()),
+            Some(create_admin_router(state)),
+        ),
+        None => (create_router(state), None),
+    };
+
+    // Run it on every configured socket until a shutdown signal arrives
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(());
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        let _ = shutdown_tx.send(());
+    });
+    let mut servers = tokio::task::JoinSet::new();
+
+    if config.listen.tcp {
+        let addr = &config.address();
+        tracing::info!("Listening on {}", addr);
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        servers.spawn(
+            axum::serve(
+                listener,
+                app.clone()
+                    .into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown_requested(shutdown_rx.clone()))
+            .into_future(),
+        );
+    }
+
+    if let (Some(admin), Some(admin_app)) = (&config.listen.admin, admin_app) {
+        let addr = &admin.address();
+        tracing::info!("Admin API listening on {}", addr);
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        servers.spawn(
+            axum::serve(
+                listener,
+                admin_app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown_requested(shutdown_rx.clone()))
+            .into_future(),
+        );
+    }
+
+    #[cfg(unix)]
+    if let Some(path) = &config.listen.unix_socket_path {
+        tracing::info!("Listening on unix:{}", path);
+        let listener =
+            fee_manager::bind_unix_socket(path).expect("Failed to bind unix socket");
+        // No peer address here, so auth lockout only tracks token prefixes
+        servers.spawn(
+            axum::serve(listener, app.clone().into_make_service())
+                .with_graceful_shutdown(shutdown_requested(shutdown_rx.clone()))
+                .into_future(),
+        );
+    }
+
+    while let Some(result) = servers.join_next().await {
+        result.expect("Server task panicked").expect("Server error");
+    }
+
+    #[cfg(unix)]
+    if let Some(path) = &config.listen.unix_socket_path {
+        let _ = std::fs::remove_file(path);
+    }
+
+    // Don't lose audit events still waiting in the queue
+    fee_manager::audit::shutdown_audit_writer().await;
+}
+
+async fn shutdown_requested(mut shutdown: tokio::sync::watch::Receiver<()>) {
+    let _ = shutdown.changed().await;
+}
+
+/// Resolve on Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutting down");
+}
+
+/// Load config.yaml and environment overrides, exiting with readable errors on failure
+fn load_valid_config() -> config::AppConfig {
+    let config = match config::load_config() {
+        Ok(config) => config,
+        Err(e) => exit_with(&format!("Failed to load configuration: {}", e)),
+    };
+    if let Err(errors) = config.validate() {
+        exit_with(&errors.to_string());
+    }
+    config
+}
+
+/// `fee-manager check-config`: validate the configuration and optionally the database connection
+async fn check_config(check_db: bool) {
+    let config = load_valid_config();
+    if check_db {
+        if let Err(e) = config::check_database(&config.database).await {
+            exit_with(&format!("Cannot connect to database: {}", e));
+        }
+        println!("Database connection OK");
+    }
+    println!("Configuration OK");
+}
+
+/// `fee-manager migrate`: run pending migrations and exit, or with `--dry-run` only list them
+async fn migrate(dry_run: bool) {
+    let config = load_valid_config();
+    let options = match config.database.connect_options() {
+        Ok(options) => options,
+        Err(e) => exit_with(&format!("Invalid database URL: {}", e)),
+    };
+    let pool = match PgPoolOptions::new().max_connections(1).connect_with(options).await {
+        Ok(pool) => pool,
+        Err(e) => exit_with(&format!("Cannot connect to database: {}", e)),
+    };
+    let migrations = match fee_manager::list_migrations(&pool).await {
+        Ok(migrations) => migrations,
+        Err(e) => exit_with(&format!("Cannot read migration status: {}", e)),
+    };
+
+    for m in &migrations {
+        let state = serde_json::to_value(m.state).unwrap_or_default();
+        let mismatch = if m.checksum_mismatch { " (checksum mismatch)" } else { "" };
+        println!("{:<8} {} {}{}", state.as_str().unwrap_or("?"), m.version, m.description, mismatch);
+    }
+    let pending = migrations.iter().filter(|m| m.state == MigrationState::Pending).count();
+    if dry_run {
+        println!("{} pending migration(s) would run", pending);
+        return;
+    }
+    if let Err(e) = run_migrations(&pool, !config.database.pgbouncer_mode).await {
+        exit_with(&format!("Error running migrations: {}", e));
+    }
+    println!("Applied {} migration(s)", pending);
+}
 
-    // Run it
-    let addr = &config.address();
-    tracing::info!("Listening on {}", addr);
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
 }
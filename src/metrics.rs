@@ -0,0 +1,178 @@
+//! Prometheus metrics for alerting on configs that stopped changing or being polled
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+use sqlx::PgPool;
+
+use crate::audit::PublicRead;
+use crate::handlers::concurrency::public_load;
+
+/// Served public reads per endpoint and config, since startup
+static PUBLIC_REQUESTS: Mutex<BTreeMap<(&'static str, String), u64>> = Mutex::new(BTreeMap::new());
+
+/// Count one served read of a public endpoint. Only call it once the config was
+/// found, so unknown names cannot grow the label set.
+pub fn count_public_request(read: PublicRead, config: &str) {
+    if let Ok(mut requests) = PUBLIC_REQUESTS.lock() {
+        *requests.entry((read.as_str(), config.to_string())).or_insert(0) += 1;
+    }
+}
+
+/// Requests to routes marked deprecated, per route template, since startup
+static DEPRECATED_REQUESTS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+/// Count one request to a deprecated route (see `handlers::deprecation`)
+pub fn count_deprecated_request(route: &str) {
+    if let Ok(mut requests) = DEPRECATED_REQUESTS.lock() {
+        *requests.entry(route.to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Render every metric in the Prometheus text format
+pub async fn render(pool: &PgPool) -> Result<String, sqlx::Error> {
+    let updates: Vec<(String, String, f64)> = sqlx::query_as(
+        "SELECT 'vouch_default_config', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM vouch_default_configs
+         UNION ALL
+         SELECT 'vouch_proposer_pattern', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM vouch_proposer_patterns
+         UNION ALL
+         SELECT 'vouch_proposer_group', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM vouch_proposer_groups
+         UNION ALL
+         SELECT 'vouch_relay_set', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM vouch_relay_sets
+         UNION ALL
+         SELECT 'commit_boost_mux', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM commit_boost_mux_configs
+         UNION ALL
+         SELECT 'commit_boost_pbs_config', name, EXTRACT(EPOCH FROM updated_at)::FLOAT8 FROM commit_boost_pbs_configs
+         ORDER BY 1, 2",
+    )
+    .fetch_all(pool)
+    .await?;
+    // Written by every replica, unlike the request counter
+    let polls: Vec<(String, f64)> = sqlx::query_as(
+        "SELECT config_name, EXTRACT(EPOCH FROM MAX(last_requested_at))::FLOAT8
+         FROM vouch_config_usage GROUP BY config_name ORDER BY config_name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut out = String::new();
+    header(
+        &mut out,
+        "fee_manager_config_last_update_seconds",
+        "gauge",
+        "Unix time of the last change to a config",
+    );
+    for (resource_type, name, seconds) in updates {
+        let _ = writeln!(
+            out,
+            "fee_manager_config_last_update_seconds{{resource_type=\"{}\",name=\"{}\"}} {}",
+            resource_type,
+            escape(&name),
+            seconds
+        );
+    }
+
+    header(
+        &mut out,
+        "fee_manager_config_last_request_seconds",
+        "gauge",
+        "Unix time a default config was last fetched through any replica (needs vouch.track_usage)",
+    );
+    for (name, seconds) in polls {
+        let _ = writeln!(
+            out,
+            "fee_manager_config_last_request_seconds{{name=\"{}\"}} {}",
+            escape(&name),
+            seconds
+        );
+    }
+
+    header(
+        &mut out,
+        "fee_manager_public_requests_total",
+        "counter",
+        "Reads of the public endpoints served by this replica, per config",
+    );
+    let requests = PUBLIC_REQUESTS
+        .lock()
+        .map(|requests| requests.clone())
+        .unwrap_or_default();
+    for ((endpoint, config), count) in requests {
+        let _ = writeln!(
+            out,
+            "fee_manager_public_requests_total{{endpoint=\"{}\",config=\"{}\"}} {}",
+            endpoint,
+            escape(&config),
+            count
+        );
+    }
+
+    header(
+        &mut out,
+        "fee_manager_deprecated_requests_total",
+        "counter",
+        "Requests to deprecated routes served by this replica, per route",
+    );
+    let deprecated = DEPRECATED_REQUESTS
+        .lock()
+        .map(|requests| requests.clone())
+        .unwrap_or_default();
+    for (route, count) in deprecated {
+        let _ = writeln!(
+            out,
+            "fee_manager_deprecated_requests_total{{route=\"{}\"}} {}",
+            escape(&route),
+            count
+        );
+    }
+
+    let load = public_load();
+    header(
+        &mut out,
+        "fee_manager_public_in_flight_requests",
+        "gauge",
+        "Public requests being served (counted when public_concurrency is enabled)",
+    );
+    let _ = writeln!(out, "fee_manager_public_in_flight_requests {}", load.in_flight);
+    header(
+        &mut out,
+        "fee_manager_public_queued_requests",
+        "gauge",
+        "Public requests waiting for a public_concurrency slot",
+    );
+    let _ = writeln!(out, "fee_manager_public_queued_requests {}", load.queued);
+    header(
+        &mut out,
+        "fee_manager_public_shed_requests_total",
+        "counter",
+        "Public requests rejected with 503 because the server was overloaded",
+    );
+    let _ = writeln!(out, "fee_manager_public_shed_requests_total {}", load.shed);
+
+    Ok(out)
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value as the text format requires
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_label_values() {
+        assert_eq!(escape("main"), "main");
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
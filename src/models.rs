@@ -2,7 +2,13 @@
 use crate::addresses::{BlsPubkey, EthAddress};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Free-form key/value metadata on proposers and patterns
+pub type Labels = BTreeMap<String, String>;
 
 // ============================================================================
 // Vouch - Default Configs
@@ -11,10 +17,19 @@ use sqlx::FromRow;
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct VouchDefaultConfig {
     pub name: String,
+    /// Stable id for external tooling, unlike the natural key it never changes
+    pub id: Uuid,
     pub fee_recipient: Option<EthAddress>,
     pub gas_limit: Option<String>,
     pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub grace: Option<String>,
     pub active: bool,
+    /// Config this one inherits unset values and relays from
+    pub parent: Option<String>,
+    /// Relay sets expanded beneath this config's own relays
+    pub relay_sets: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -37,10 +52,17 @@ pub struct VouchDefaultRelay {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct VouchProposer {
     pub public_key: BlsPubkey,
+    pub id: Uuid,
     pub fee_recipient: Option<EthAddress>,
     pub gas_limit: Option<String>,
     pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    pub note: Option<String>,
+    pub labels: Json<Labels>,
+    pub relay_sets: Vec<String>,
+    pub decommissioned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -64,12 +86,19 @@ pub struct VouchProposerRelay {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct VouchProposerPattern {
     pub name: String,
+    pub id: Uuid,
     pub pattern: String,
     pub tags: Vec<String>,
     pub fee_recipient: Option<EthAddress>,
     pub gas_limit: Option<String>,
     pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    pub active: bool,
+    pub note: Option<String>,
+    pub labels: Json<Labels>,
+    pub relay_sets: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -86,6 +115,94 @@ pub struct VouchProposerPatternRelay {
     pub disabled: bool,
 }
 
+// ============================================================================
+// Vouch - Relay Sets
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchRelaySet {
+    pub name: String,
+    pub id: Uuid,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchRelaySetRelay {
+    pub id: i32,
+    pub set_name: String,
+    pub url: String,
+    pub public_key: BlsPubkey,
+    pub fee_recipient: Option<EthAddress>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+}
+
+// ============================================================================
+// Vouch - Proposer Groups
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchProposerGroup {
+    pub name: String,
+    pub id: Uuid,
+    /// Regex selecting members in addition to the listed keys
+    pub pattern: Option<String>,
+    /// Decides between groups sharing a key, highest first
+    pub priority: i32,
+    pub fee_recipient: Option<EthAddress>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub builder_enabled: Option<bool>,
+    pub builder_boost_factor: Option<String>,
+    pub reset_relays: bool,
+    pub note: Option<String>,
+    pub relay_sets: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchProposerGroupRelay {
+    pub id: i32,
+    pub group_name: String,
+    pub url: String,
+    pub public_key: BlsPubkey,
+    pub fee_recipient: Option<EthAddress>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+}
+
+// ============================================================================
+// Vouch - Validator Registrations
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchValidatorRegistration {
+    pub public_key: BlsPubkey,
+    pub relay_url: String,
+    pub fee_recipient: EthAddress,
+    pub gas_limit: String,
+    pub registered_at: DateTime<Utc>,
+    pub checked_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Vouch - Config Usage
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VouchConfigUsage {
+    pub config_name: String,
+    pub consumer: String,
+    pub request_count: i64,
+    pub last_key_count: i32,
+    pub max_key_count: i32,
+    pub first_requested_at: DateTime<Utc>,
+    pub last_requested_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // Commit-Boost - Mux Configs
 // ============================================================================
@@ -93,6 +210,7 @@ pub struct VouchProposerPatternRelay {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct CommitBoostMuxConfig {
     pub name: String,
+    pub id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -104,3 +222,54 @@ pub struct CommitBoostMuxKey {
     pub mux_name: String,
     pub public_key: BlsPubkey,
 }
+
+// ============================================================================
+// Commit-Boost - PBS Configs
+// ============================================================================
+
+/// `[pbs]` section of a PBS config, rendered as stored
+pub type PbsSettings = BTreeMap<String, serde_json::Value>;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CommitBoostPbsConfig {
+    pub name: String,
+    pub id: Uuid,
+    pub chain: String,
+    pub pbs: Json<PbsSettings>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CommitBoostPbsRelay {
+    pub id: i32,
+    pub config_name: String,
+    pub relay_id: String,
+    pub url: String,
+    pub public_key: BlsPubkey,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CommitBoostPbsMux {
+    pub id: i32,
+    pub config_name: String,
+    pub mux_id: String,
+    /// Mux config supplying the keys (exclusive with `registry`)
+    pub mux_name: Option<String>,
+    /// `lido` or `ssv`
+    pub registry: Option<String>,
+    pub node_operator_id: Option<i64>,
+    pub relay_ids: Vec<String>,
+}
+
+// ============================================================================
+// Relays
+// ============================================================================
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DisabledRelay {
+    pub url: String,
+    pub disabled_at: DateTime<Utc>,
+}
@@ -1,6 +1,14 @@
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{Content, Ref, RefOr, Response};
 use utoipa::{Modify, OpenApi};
 
+use crate::auth::handlers::AuthApi;
+use crate::errors::PROBLEM_JSON;
+use crate::handlers::api_version::{ADMIN_API_PREFIX, UNVERSIONED_ADMIN_PREFIX};
+use crate::handlers::{
+    access_tokens::AccessTokensApi, audit_history::AuditHistoryApi, backups::BackupsApi, commit_boost::CommitBoostApi, consistency::ConsistencyApi, jobs::JobsApi, log_level::LogLevelApi, migrations::MigrationsApi, relays::RelaysApi, search::SearchApi, vouch::VouchApi, HealthApi,
+};
+
 #[derive(OpenApi)]
 #[openapi(
     info(
@@ -13,87 +21,12 @@ use utoipa::{Modify, OpenApi};
             ("server_url" = (default = "http://localhost:3000", description = "API Server URL"))
         )),
     ),
-    modifiers(&SecurityAddon),
-    paths(
-        // Health
-        crate::handlers::get_ready,
-        crate::handlers::get_health,
-        // Auth
-        crate::auth::handlers::list_tokens,
-        crate::auth::handlers::create_token,
-        crate::auth::handlers::delete_token,
-        // Vouch - Public
-        crate::handlers::vouch::execution_config::get_execution_config,
-        // Vouch - Proposers
-        crate::handlers::vouch::proposers::list_proposers,
-        crate::handlers::vouch::proposers::get_proposer,
-        crate::handlers::vouch::proposers::create_or_update_proposer,
-        crate::handlers::vouch::proposers::delete_proposer,
-        // Vouch - Default Configs
-        crate::handlers::vouch::default_configs::list_default_configs,
-        crate::handlers::vouch::default_configs::get_default_config,
-        crate::handlers::vouch::default_configs::create_default_config,
-        crate::handlers::vouch::default_configs::update_default_config,
-        crate::handlers::vouch::default_configs::delete_default_config,
-        // Vouch - Proposer Patterns
-        crate::handlers::vouch::proposer_patterns::list_proposer_patterns,
-        crate::handlers::vouch::proposer_patterns::get_proposer_pattern,
-        crate::handlers::vouch::proposer_patterns::create_proposer_pattern,
-        crate::handlers::vouch::proposer_patterns::update_proposer_pattern,
-        crate::handlers::vouch::proposer_patterns::delete_proposer_pattern,
-        // Commit-Boost - Public
-        crate::handlers::commit_boost::mux::get_mux_keys_public,
-        // Commit-Boost - Mux Admin
-        crate::handlers::commit_boost::mux::list_mux_configs,
-        crate::handlers::commit_boost::mux::get_mux_config,
-        crate::handlers::commit_boost::mux::create_mux_config,
-        crate::handlers::commit_boost::mux::update_mux_config,
-        crate::handlers::commit_boost::mux::delete_mux_config,
-        crate::handlers::commit_boost::mux::add_mux_keys,
-        crate::handlers::commit_boost::mux::remove_mux_keys,
-    ),
-    components(
-        schemas(
-            crate::handlers::HealthResponse,
-            crate::errors::ErrorResponse,
-            crate::errors::ErrorDetail,
-            // Common
-            crate::schema::RelayConfig,
-            crate::schema::ProposerRelayConfig,
-            crate::schema::PaginatedResponse<crate::schema::ProposerListItem>,
-            crate::schema::PaginatedResponse<crate::schema::DefaultConfigListItem>,
-            crate::schema::PaginatedResponse<crate::schema::ProposerPatternListItem>,
-            crate::schema::PaginatedResponse<crate::schema::MuxConfigListItem>,
-            // Vouch - Proposers
-            crate::schema::ProposerResponse,
-            crate::schema::ProposerListItem,
-            crate::schema::CreateOrUpdateProposerRequest,
-            // Vouch - Default Configs
-            crate::schema::DefaultConfigResponse,
-            crate::schema::DefaultConfigListItem,
-            crate::schema::CreateDefaultConfigRequest,
-            crate::schema::UpdateDefaultConfigRequest,
-            // Vouch - Proposer Patterns
-            crate::schema::ProposerPatternResponse,
-            crate::schema::ProposerPatternListItem,
-            crate::schema::CreateProposerPatternRequest,
-            crate::schema::UpdateProposerPatternRequest,
-            // Vouch - Execution Config
-            crate::schema::ExecutionConfigResponse,
-            crate::schema::ProposerEntry,
-            // Commit-Boost - Mux
-            crate::schema::MuxConfigResponse,
-            crate::schema::MuxConfigListItem,
-            crate::schema::CreateMuxConfigRequest,
-            crate::schema::UpdateMuxConfigRequest,
-            crate::schema::MuxKeysRequest,
-            crate::schema::MuxKeysResponse,
-            // Auth
-            crate::auth::TokenInfo,
-            crate::auth::handlers::CreateTokenRequest,
-            crate::auth::handlers::CreateTokenResponse,
-        )
-    ),
+    modifiers(&RouteApis, &VersionedPaths, &SecurityAddon, &ProblemResponses),
+    components(schemas(
+        crate::errors::ProblemDetails,
+        crate::errors::ErrorCode,
+        crate::handlers::sort::SortOrder,
+    )),
     tags(
         (name = "Health", description = "Service health endpoints"),
         (name = "Auth", description = "API token management"),
@@ -101,12 +34,100 @@ use utoipa::{Modify, OpenApi};
         (name = "Vouch - Proposers", description = "Admin endpoints for managing proposer configurations"),
         (name = "Vouch - Default Configs", description = "Admin endpoints for managing default configurations"),
         (name = "Vouch - Proposer Patterns", description = "Admin endpoints for managing proposer patterns"),
+        (name = "Vouch - Relay Sets", description = "Admin endpoints for managing reusable relay sets"),
+        (name = "Vouch - Tags", description = "Admin endpoints for the tags of proposer patterns"),
+        (name = "Vouch - Proposer Groups", description = "Admin endpoints for managing settings shared by groups of proposers"),
+        (name = "Vouch - Import", description = "Admin endpoint for importing Vouch execution config files"),
         (name = "Commit-Boost - Public", description = "Public Commit-Boost endpoints"),
         (name = "Commit-Boost - Mux", description = "Admin endpoints for managing mux configurations"),
+        (name = "Commit-Boost - PBS", description = "Admin endpoints for managing complete PBS configurations"),
+        (name = "Search", description = "Admin search across all resources"),
+        (name = "Relays", description = "Admin switches for relays across all configs"),
+        (name = "Logging", description = "Admin endpoint for changing the log level at runtime"),
+        (name = "Migrations", description = "Admin endpoint for the database schema state"),
+        (name = "Audit", description = "Admin endpoints for the stored audit history of a resource"),
+        (name = "Jobs", description = "Admin endpoint for the status of background jobs"),
+        (name = "Backups", description = "Admin endpoint for the status of scheduled backups"),
+        (name = "Consistency", description = "Admin endpoint checking the database for damage left by manual edits"),
     )
 )]
 pub struct ApiDoc;
 
+/// Merges the paths and schemas declared next to each router module
+struct RouteApis;
+
+impl Modify for RouteApis {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.merge(HealthApi::openapi());
+        openapi.merge(AuthApi::openapi());
+        openapi.merge(VouchApi::openapi());
+        openapi.merge(CommitBoostApi::openapi());
+        openapi.merge(SearchApi::openapi());
+        openapi.merge(RelaysApi::openapi());
+        openapi.merge(LogLevelApi::openapi());
+        openapi.merge(MigrationsApi::openapi());
+        openapi.merge(AuditHistoryApi::openapi());
+        openapi.merge(AccessTokensApi::openapi());
+        openapi.merge(JobsApi::openapi());
+        openapi.merge(BackupsApi::openapi());
+        openapi.merge(ConsistencyApi::openapi());
+    }
+}
+
+/// Documents the admin operations under the versioned prefix; handlers are
+/// annotated with the unversioned paths, which stay served as deprecated aliases
+struct VersionedPaths;
+
+impl Modify for VersionedPaths {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let paths = std::mem::take(&mut openapi.paths.paths);
+        openapi.paths.paths = paths
+            .into_iter()
+            .map(|(path, item)| match path.strip_prefix(UNVERSIONED_ADMIN_PREFIX) {
+                Some(rest) => (format!("{}{}", ADMIN_API_PREFIX, rest), item),
+                None => (path, item),
+            })
+            .collect();
+    }
+}
+
+/// Documents every 4xx/5xx response as an `application/problem+json` body, adding
+/// the 401 of authenticated operations and the 500 shared by all of them
+struct ProblemResponses;
+
+impl Modify for ProblemResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                let responses = &mut operation.responses.responses;
+                if operation.security.as_ref().is_some_and(|s| !s.is_empty()) {
+                    responses
+                        .entry("401".to_string())
+                        .or_insert_with(|| Response::new("Missing or invalid API token").into());
+                }
+                responses
+                    .entry("500".to_string())
+                    .or_insert_with(|| Response::new("Internal server error").into());
+                for (status, response) in responses.iter_mut() {
+                    let is_error = status.starts_with('4') || status.starts_with('5');
+                    if let (true, RefOr::T(response)) = (is_error, response) {
+                        response.content.entry(PROBLEM_JSON.to_string()).or_insert_with(|| {
+                            Content::new(Some(Ref::from_schema_name("ProblemDetails")))
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Security scheme for Bearer token authentication
 struct SecurityAddon;
 
@@ -0,0 +1,98 @@
+//! Fields of partial updates that tell "not provided" from an explicit `null`
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A field of a partial update: missing leaves the stored value alone, `null`
+/// clears it and any other value replaces it.
+///
+/// Declare it with `#[serde(default, skip_serializing_if = "Patch::is_missing")]`
+/// so a missing field deserializes as missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch<T>(Option<Option<T>>);
+
+impl<T> Default for Patch<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Patch<T> {
+    pub fn missing() -> Self {
+        Self(None)
+    }
+
+    pub fn null() -> Self {
+        Self(Some(None))
+    }
+
+    pub fn is_missing(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// The new column value: `None` when missing, `Some(None)` when cleared
+    pub fn update(&self) -> Option<Option<&T>> {
+        self.0.as_ref().map(Option::as_ref)
+    }
+
+    /// The value being set, if any
+    pub fn value(&self) -> Option<&T> {
+        self.0.as_ref().and_then(Option::as_ref)
+    }
+
+    /// The provided value, `None` inside when cleared; `None` when missing
+    pub fn provided_mut(&mut self) -> Option<&mut Option<T>> {
+        self.0.as_mut()
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    /// `Some` sets the value, `None` clears it
+    fn from(value: Option<T>) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(|value| Self(Some(value)))
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value() {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Request {
+        #[serde(default, skip_serializing_if = "Patch::is_missing")]
+        note: Patch<String>,
+    }
+
+    #[test]
+    fn missing_null_and_value_are_distinct() {
+        let parse = |json: &str| serde_json::from_str::<Request>(json).unwrap().note;
+        assert_eq!(parse("{}"), Patch::missing());
+        assert_eq!(parse(r#"{"note": null}"#), Patch::null());
+        assert_eq!(parse(r#"{"note": "x"}"#), Patch::from(Some("x".to_string())));
+
+        assert_eq!(parse("{}").update(), None);
+        assert_eq!(parse(r#"{"note": null}"#).update(), Some(None));
+    }
+
+    #[test]
+    fn serializes_back_to_the_same_shape() {
+        for json in ["{}", r#"{"note":null}"#, r#"{"note":"x"}"#] {
+            let request: Request = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&request).unwrap(), json);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Proposer pattern matches
+//!
+//! Keeps `vouch_proposer_pattern_matches` in step with the patterns and
+//! proposers, so match statistics do not evaluate every regex against every
+//! key on each request. The matches are recomputed whenever the 'vouch'
+//! config version moved past the one they were computed at; readers call
+//! [`refresh`] first, which is a single lookup while they are current.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::config::PatternMatchesConfig;
+
+/// Outcome of one refresh
+#[derive(Debug, Clone)]
+pub struct RefreshOutcome {
+    /// When the current matches were computed
+    pub refreshed_at: DateTime<Utc>,
+    /// Whether this call recomputed them
+    pub recomputed: bool,
+    /// Number of (pattern, proposer) matches, when recomputed
+    pub matches: usize,
+}
+
+/// Recompute the matches unless they are current
+///
+/// Concurrent callers wait for the one recomputing. Patterns whose regex does
+/// not compile match nothing.
+pub async fn refresh(pool: &PgPool) -> Result<RefreshOutcome, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let state = sqlx::query!("SELECT version, refreshed_at FROM vouch_pattern_match_state FOR UPDATE")
+        .fetch_one(&mut *tx)
+        .await?;
+    // Read before the data: a change committed in between leaves the matches
+    // marked stale, never the other way round
+    let version: i64 = sqlx::query_scalar!("SELECT version FROM config_versions WHERE scope = 'vouch'")
+        .fetch_one(&mut *tx)
+        .await?;
+    if let (true, Some(refreshed_at)) = (state.version == version, state.refreshed_at) {
+        return Ok(RefreshOutcome { refreshed_at, recomputed: false, matches: 0 });
+    }
+
+    let patterns = sqlx::query!("SELECT name, pattern FROM vouch_proposer_patterns")
+        .fetch_all(&mut *tx)
+        .await?;
+    let keys: Vec<String> = sqlx::query_scalar!("SELECT public_key FROM vouch_proposers")
+        .fetch_all(&mut *tx)
+        .await?;
+
+    let patterns: Vec<(String, String)> = patterns.into_iter().map(|p| (p.name, p.pattern)).collect();
+    let (pattern_names, public_keys) = tokio::task::spawn_blocking(move || evaluate(&patterns, &keys))
+        .await
+        .expect("pattern evaluation panicked");
+
+    sqlx::query!("DELETE FROM vouch_proposer_pattern_matches")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO vouch_proposer_pattern_matches (pattern_name, public_key)
+         SELECT * FROM UNNEST($1::text[], $2::text[])",
+        &pattern_names,
+        &public_keys
+    )
+    .execute(&mut *tx)
+    .await?;
+    let refreshed_at: DateTime<Utc> = sqlx::query_scalar!(
+        r#"UPDATE vouch_pattern_match_state SET version = $1, refreshed_at = NOW() RETURNING refreshed_at AS "refreshed_at!""#,
+        version
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(RefreshOutcome {
+        refreshed_at,
+        recomputed: true,
+        matches: pattern_names.len(),
+    })
+}
+
+/// Match every key against every pattern, as parallel name/key columns
+fn evaluate(patterns: &[(String, String)], keys: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut pattern_names = Vec::new();
+    let mut public_keys = Vec::new();
+    for (name, pattern) in patterns {
+        let regex = match regex::Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                warn!("Proposer pattern '{}' is not a valid regex, skipping: {}", name, e);
+                continue;
+            }
+        };
+        for key in keys.iter().filter(|key| regex.is_match(key)) {
+            pattern_names.push(name.clone());
+            public_keys.push(key.clone());
+        }
+    }
+    (pattern_names, public_keys)
+}
+
+/// Refresh the matches every `interval_secs` in the background
+pub fn spawn(pool: PgPool, config: &PatternMatchesConfig) {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+            match refresh(&pool).await {
+                Ok(outcome) if outcome.recomputed => {
+                    info!(matches = outcome.matches, "Proposer pattern matches refreshed")
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Proposer pattern match refresh failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_patterns_match_nothing() {
+        let patterns = vec![
+            ("a".to_string(), "^0xa".to_string()),
+            ("broken".to_string(), "(".to_string()),
+            ("all".to_string(), ".*".to_string()),
+        ];
+        let keys = vec!["0xa1".to_string(), "0xb2".to_string()];
+
+        let (names, matched) = evaluate(&patterns, &keys);
+        assert_eq!(names, vec!["a", "all", "all"]);
+        assert_eq!(matched, vec!["0xa1", "0xa1", "0xb2"]);
+    }
+}
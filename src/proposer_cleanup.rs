@@ -0,0 +1,114 @@
+//! Inactive proposer cleanup
+//!
+//! Decommissions or deletes proposers that no execution-config request has
+//! asked for in a number of days, so entries of churned customers do not pile
+//! up. Proposers never requested count from their creation.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::addresses::BlsPubkey;
+use crate::audit::{ActorInfo, AuditAction, AuditChanges, Change, RequestContext, ResourceType};
+use crate::audit_log;
+use crate::config::{CleanupAction, ProposerCleanupConfig};
+
+/// Actor name of the background job in audit events
+const JOB_ACTOR: &str = "proposer-cleanup";
+
+/// Proposers not requested since `$1`; decommissioned ones only matter for delete
+const INACTIVE_CONDITION: &str =
+    "COALESCE((SELECT u.last_requested_at FROM vouch_proposer_usage u WHERE u.public_key = p.public_key), p.created_at) < $1
+     AND ($2 OR p.decommissioned_at IS NULL)";
+
+/// Outcome of one cleanup run
+#[derive(Debug, Clone)]
+pub struct CleanupOutcome {
+    /// Proposers not requested since this time were affected
+    pub cutoff: DateTime<Utc>,
+    pub public_keys: Vec<BlsPubkey>,
+}
+
+/// Find the inactive proposers and, unless `dry_run`, apply `action` to them
+pub async fn run_cleanup(
+    pool: &PgPool,
+    ctx: &RequestContext,
+    audit_enabled: bool,
+    inactive_days: u32,
+    action: CleanupAction,
+    dry_run: bool,
+) -> Result<CleanupOutcome, sqlx::Error> {
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(inactive_days));
+    let include_decommissioned = action == CleanupAction::Delete;
+
+    let statement = match (dry_run, action) {
+        (true, _) => format!("SELECT p.public_key FROM vouch_proposers p WHERE {}", INACTIVE_CONDITION),
+        (false, CleanupAction::Decommission) => format!(
+            "UPDATE vouch_proposers p SET decommissioned_at = NOW() WHERE {} RETURNING p.public_key",
+            INACTIVE_CONDITION
+        ),
+        (false, CleanupAction::Delete) => format!(
+            "DELETE FROM vouch_proposers p WHERE {} RETURNING p.public_key",
+            INACTIVE_CONDITION
+        ),
+    };
+    let public_keys: Vec<BlsPubkey> = sqlx::query_scalar(&format!(
+        "WITH affected AS ({}) SELECT public_key FROM affected ORDER BY public_key",
+        statement
+    ))
+    .bind(cutoff)
+    .bind(include_decommissioned)
+    .fetch_all(pool)
+    .await?;
+
+    if audit_enabled && !dry_run {
+        for public_key in &public_keys {
+            match action {
+                CleanupAction::Decommission => {
+                    let changes = AuditChanges {
+                        decommissioned: Change::between(&Some(false), &Some(true)),
+                        ..Default::default()
+                    };
+                    audit_log!(ctx, AuditAction::Decommission, ResourceType::VouchProposer, public_key.to_string(), changes);
+                }
+                CleanupAction::Delete => {
+                    audit_log!(ctx, AuditAction::Delete, ResourceType::VouchProposer, public_key.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(CleanupOutcome { cutoff, public_keys })
+}
+
+/// Run the cleanup every `interval_secs` in the background
+pub fn spawn(pool: PgPool, config: &ProposerCleanupConfig, audit_enabled: bool) {
+    let config = config.clone();
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+            let ctx = RequestContext {
+                request_id: Uuid::new_v4(),
+                actor: ActorInfo {
+                    token_name: JOB_ACTOR.to_string(),
+                    ..ActorInfo::default()
+                },
+                client_ip: None,
+                override_requested: false,
+            };
+            match run_cleanup(&pool, &ctx, audit_enabled, config.inactive_days, config.action, false).await {
+                Ok(outcome) => info!(
+                    action = ?config.action,
+                    proposers = outcome.public_keys.len(),
+                    cutoff = %outcome.cutoff,
+                    "Proposer cleanup completed"
+                ),
+                Err(e) => warn!("Proposer cleanup failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,219 @@
+//! Validator registration tracking
+//!
+//! Periodically asks relays which fee recipient and gas limit they last saw
+//! registered for each proposer, so operators can check that the intended
+//! configuration actually reaches the relays.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sqlx::PgPool;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::addresses::{BlsPubkey, EthAddress};
+use crate::config::RegistrationsConfig;
+
+/// Relay data API path for the latest registration of a validator
+const REGISTRATION_PATH: &str = "relay/v1/data/validator_registration";
+
+#[derive(Debug, Deserialize)]
+struct SignedValidatorRegistration {
+    message: ValidatorRegistration,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatorRegistration {
+    fee_recipient: EthAddress,
+    gas_limit: String,
+    /// Unix seconds, as a decimal string
+    timestamp: String,
+}
+
+/// Outcome of one ingestion run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub relays: usize,
+    pub checked: usize,
+    pub recorded: usize,
+    pub failed_relays: usize,
+}
+
+/// Queries relays for validator registrations and stores the latest seen values
+pub struct RegistrationSync {
+    http: reqwest::Client,
+    relays: Vec<String>,
+}
+
+impl RegistrationSync {
+    pub fn new(config: &RegistrationsConfig) -> Result<Self, reqwest::Error> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()?;
+        Ok(Self {
+            http,
+            relays: config.relays.clone(),
+        })
+    }
+
+    /// Check every proposer against every relay once
+    pub async fn run_once(&self, pool: &PgPool) -> Result<SyncSummary, sqlx::Error> {
+        let relays = if self.relays.is_empty() {
+            known_relays(pool).await?
+        } else {
+            self.relays.clone()
+        };
+        let keys: Vec<BlsPubkey> =
+            sqlx::query_scalar("SELECT public_key FROM vouch_proposers ORDER BY public_key")
+                .fetch_all(pool)
+                .await?;
+
+        let mut summary = SyncSummary {
+            relays: relays.len(),
+            ..SyncSummary::default()
+        };
+        for relay in &relays {
+            let Some(endpoint) = registration_endpoint(relay) else {
+                warn!("Skipping relay with invalid URL: {}", relay);
+                summary.failed_relays += 1;
+                continue;
+            };
+            for key in &keys {
+                summary.checked += 1;
+                match self.fetch(&endpoint, key).await {
+                    Ok(Some(registration)) => {
+                        store(pool, key, relay, &registration).await?;
+                        summary.recorded += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        // An unreachable relay would fail for every key; move on to the next one
+                        warn!("Failed to query relay {}: {}", relay, e);
+                        summary.failed_relays += 1;
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    async fn fetch(
+        &self,
+        endpoint: &Url,
+        key: &BlsPubkey,
+    ) -> Result<Option<ValidatorRegistration>, reqwest::Error> {
+        let mut url = endpoint.clone();
+        url.query_pairs_mut().append_pair("pubkey", &key.to_string());
+
+        let response = self.http.get(url).send().await?;
+        // Relays answer 400 or 404 for validators that never registered with them
+        if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST) {
+            return Ok(None);
+        }
+        let signed: SignedValidatorRegistration = response.error_for_status()?.json().await?;
+        Ok(Some(signed.message))
+    }
+}
+
+/// Relay URLs referenced by any default config, proposer, pattern or relay set
+async fn known_relays(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT url FROM vouch_default_relays
+         UNION SELECT url FROM vouch_proposer_relays
+         UNION SELECT url FROM vouch_proposer_pattern_relays
+         UNION SELECT url FROM vouch_relay_set_relays
+         ORDER BY 1",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Data API endpoint for a relay URL, dropping the relay public key Vouch keeps in the user info
+fn registration_endpoint(relay: &str) -> Option<Url> {
+    let mut url = Url::parse(relay).ok()?;
+    url.set_username("").ok()?;
+    url.set_password(None).ok()?;
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url.join(REGISTRATION_PATH).ok()
+}
+
+async fn store(
+    pool: &PgPool,
+    key: &BlsPubkey,
+    relay: &str,
+    registration: &ValidatorRegistration,
+) -> Result<(), sqlx::Error> {
+    let registered_at = registration
+        .timestamp
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+        .unwrap_or_else(Utc::now);
+
+    sqlx::query(
+        "INSERT INTO vouch_validator_registrations
+             (public_key, relay_url, fee_recipient, gas_limit, registered_at, checked_at)
+         VALUES ($1, $2, $3, $4, $5, NOW())
+         ON CONFLICT (public_key, relay_url) DO UPDATE
+         SET fee_recipient = EXCLUDED.fee_recipient,
+             gas_limit = EXCLUDED.gas_limit,
+             registered_at = EXCLUDED.registered_at,
+             checked_at = EXCLUDED.checked_at",
+    )
+    .bind(key)
+    .bind(relay)
+    .bind(&registration.fee_recipient)
+    .bind(&registration.gas_limit)
+    .bind(registered_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Run the ingestion job every `interval_secs` in the background
+pub fn spawn(pool: PgPool, config: &RegistrationsConfig) -> Result<(), reqwest::Error> {
+    let sync = RegistrationSync::new(config)?;
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_secs));
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+            match sync.run_once(&pool).await {
+                Ok(summary) => info!(
+                    relays = summary.relays,
+                    checked = summary.checked,
+                    recorded = summary.recorded,
+                    failed_relays = summary.failed_relays,
+                    "Validator registration sync completed"
+                ),
+                Err(e) => warn!("Validator registration sync failed: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_strips_relay_public_key() {
+        let endpoint = registration_endpoint("https://0xac6e77@relay.example.com").unwrap();
+        assert_eq!(
+            endpoint.as_str(),
+            "https://relay.example.com/relay/v1/data/validator_registration"
+        );
+
+        let endpoint = registration_endpoint("https://relay.example.com/prefix").unwrap();
+        assert_eq!(
+            endpoint.as_str(),
+            "https://relay.example.com/prefix/relay/v1/data/validator_registration"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Versioned responses for the public endpoints
+//!
+//! Every write to the tables behind the public endpoints bumps a counter in
+//! `config_versions` (statement triggers, see the migration). Each request
+//! reads the counter once: it keys this replica's in-memory response cache and
+//! is part of the ETag, so all replicas agree on when a response changed
+//! without talking to each other.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::errors::ApiError;
+
+static RESPONSE_CACHE: OnceLock<ResponseCache> = OnceLock::new();
+
+/// Group of tables sharing one version counter
+#[derive(Debug, Clone, Copy)]
+pub enum VersionScope {
+    Vouch,
+    CommitBoost,
+}
+
+impl VersionScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            VersionScope::Vouch => "vouch",
+            VersionScope::CommitBoost => "commit_boost",
+        }
+    }
+}
+
+/// Current value of a scope's version counter
+pub async fn current_version(pool: &PgPool, scope: VersionScope) -> Result<i64, ApiError> {
+    let version = sqlx::query_scalar("SELECT version FROM config_versions WHERE scope = $1")
+        .bind(scope.as_str())
+        .fetch_one(pool)
+        .await?;
+    Ok(version)
+}
+
+type CachedValue = Arc<dyn Any + Send + Sync>;
+
+/// Built responses of this replica, valid while their version is current
+struct ResponseCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<String, (i64, CachedValue)>>,
+}
+
+impl ResponseCache {
+    fn get(&self, key: &str, version: i64) -> Option<CachedValue> {
+        let entries = self.entries.lock().ok()?;
+        match entries.get(key) {
+            Some((cached_version, value)) if *cached_version == version => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, key: String, version: i64, value: CachedValue) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            // Responses built for older versions can never be served again
+            entries.retain(|_, (cached_version, _)| *cached_version >= version);
+            if entries.len() >= self.max_entries {
+                entries.clear();
+            }
+        }
+        entries.insert(key, (version, value));
+    }
+}
+
+fn response_cache(max_entries: usize) -> &'static ResponseCache {
+    RESPONSE_CACHE.get_or_init(|| ResponseCache {
+        max_entries,
+        entries: Mutex::new(HashMap::new()),
+    })
+}
+
+/// A public request pinned to the version it is answered from
+pub struct VersionedRequest {
+    key: String,
+    version: i64,
+    etag: String,
+    cache: &'static ResponseCache,
+}
+
+impl VersionedRequest {
+    /// `fingerprint` must capture everything the response depends on besides the data
+    pub async fn new(
+        pool: &PgPool,
+        scope: VersionScope,
+        fingerprint: &str,
+        max_entries: usize,
+    ) -> Result<Self, ApiError> {
+        let version = current_version(pool, scope).await?;
+        let digest = hex::encode(Sha256::digest(fingerprint.as_bytes()));
+        Ok(Self {
+            etag: format!("\"{}-{}-{}\"", scope.as_str(), version, &digest[..16]),
+            key: format!("{}:{}", scope.as_str(), digest),
+            version,
+            cache: response_cache(max_entries),
+        })
+    }
+
+    /// Whether the client's `If-None-Match` already names this response
+    pub fn not_modified(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|tag| tag.trim())
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == self.etag)
+    }
+
+    /// The response built for this version by an earlier request, if still cached
+    pub fn cached<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.cache
+            .get(&self.key, self.version)
+            .and_then(|value| value.downcast::<T>().ok())
+    }
+
+    pub fn store<T: Send + Sync + 'static>(&self, value: T) -> Arc<T> {
+        let value = Arc::new(value);
+        self.cache.insert(self.key.clone(), self.version, value.clone());
+        value
+    }
+
+    /// `304 Not Modified` carrying the ETag
+    pub fn not_modified_response(&self) -> Response {
+        (StatusCode::NOT_MODIFIED, self.etag_header()).into_response()
+    }
+
+    /// Headers to send with a full response
+    pub fn etag_header(&self) -> [(header::HeaderName, HeaderValue); 1] {
+        let value = HeaderValue::from_str(&self.etag).expect("ETag is ASCII");
+        [(header::ETAG, value)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(etag: &str) -> VersionedRequest {
+        VersionedRequest {
+            key: "vouch:test".to_string(),
+            version: 1,
+            etag: etag.to_string(),
+            cache: response_cache(16),
+        }
+    }
+
+    #[test]
+    fn if_none_match_accepts_lists_and_weak_tags() {
+        let req = request("\"vouch-7-abc\"");
+        let mut headers = HeaderMap::new();
+        assert!(!req.not_modified(&headers));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"other\", W/\"vouch-7-abc\""));
+        assert!(req.not_modified(&headers));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"vouch-6-abc\""));
+        assert!(!req.not_modified(&headers));
+    }
+
+    #[test]
+    fn cache_drops_entries_from_older_versions() {
+        let cache = ResponseCache {
+            max_entries: 2,
+            entries: Mutex::new(HashMap::new()),
+        };
+        cache.insert("a".to_string(), 1, Arc::new(1u32));
+        cache.insert("b".to_string(), 1, Arc::new(2u32));
+        assert!(cache.get("a", 1).is_some());
+        assert!(cache.get("a", 2).is_none());
+
+        cache.insert("c".to_string(), 2, Arc::new(3u32));
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries.contains_key("c"));
+    }
+}
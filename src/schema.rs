@@ -1,13 +1,19 @@
 // schema.rs - API request/response types
 use crate::addresses::{BlsPubkey, EthAddress};
+use crate::config::CleanupAction;
+use crate::errors::ProblemDetails;
 use crate::models::{
-    VouchDefaultConfig, VouchDefaultRelay, VouchProposer, VouchProposerPattern,
-    VouchProposerPatternRelay, VouchProposerRelay,
+    CommitBoostPbsMux, CommitBoostPbsRelay, DisabledRelay, Labels, PbsSettings, VouchConfigUsage, VouchDefaultConfig, VouchDefaultRelay, VouchProposer, VouchProposerPattern,
+    VouchProposerGroupRelay, VouchProposerPatternRelay, VouchProposerRelay, VouchRelaySetRelay,
 };
+use crate::patch::Patch;
+use crate::units::MinValues;
+use crate::validation::{GasLimits, RelayKeys};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 // ============================================================================
 // Common Types
@@ -24,8 +30,10 @@ pub struct RelayConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
     /// Only serialized when true
     #[serde(default, skip_serializing_if = "is_false")]
@@ -38,8 +46,10 @@ pub struct ProposerRelayConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
     /// Only serialized when true
     #[serde(default, skip_serializing_if = "is_false")]
@@ -52,8 +62,70 @@ pub struct PaginatedResponse<T> {
     pub total: i64,
     pub limit: i64,
     pub offset: i64,
+    /// More rows follow this page (the `Link` header then carries `rel="next"`)
+    pub has_more: bool,
 }
 
+impl<T> PaginatedResponse<T> {
+    pub fn new(data: Vec<T>, total: i64, limit: i64, offset: i64) -> Self {
+        let has_more = offset + (data.len() as i64) < total;
+        Self {
+            data,
+            total,
+            limit,
+            offset,
+            has_more,
+        }
+    }
+}
+
+/// Number of resources matching the given filters
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CountResponse {
+    pub count: i64,
+}
+
+/// Child rows of one kind that a delete removes together with the resource
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependentRows {
+    /// "relays", "keys", "usage" or "access_tokens"
+    pub kind: String,
+    pub count: i64,
+}
+
+/// Resource that refers to the one being deleted
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependentReference {
+    /// Same names as audit events, e.g. "vouch_default_config"
+    pub resource_type: String,
+    pub name: String,
+}
+
+/// What deleting a resource would remove, and what would stop it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeletionPreview {
+    pub name: String,
+    /// Child rows deleted along with the resource
+    pub removes: Vec<DependentRows>,
+    /// References that make the delete fail with 409 until they are gone
+    pub blocked_by: Vec<DependentReference>,
+}
+
+impl DeletionPreview {
+    pub fn new(name: String, removes: &[(&str, i64)], blocked_by: Vec<DependentReference>) -> Self {
+        Self {
+            name,
+            removes: removes
+                .iter()
+                .map(|(kind, count)| DependentRows {
+                    kind: kind.to_string(),
+                    count: *count,
+                })
+                .collect(),
+            blocked_by,
+        }
+    }
+}
 
 // ============================================================================
 // Vouch - Default Configs API
@@ -61,15 +133,37 @@ pub struct PaginatedResponse<T> {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DefaultConfigResponse {
+    #[schema(example = "main")]
     pub name: String,
+    pub id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    /// Grace period in milliseconds to wait for builder bids
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "1000")]
+    pub grace: Option<String>,
     pub active: bool,
+    /// Config this one inherits unset values and relays from
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -77,46 +171,141 @@ pub struct DefaultConfigResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DefaultConfigListItem {
+    #[schema(example = "main")]
     pub name: String,
+    pub id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    /// Grace period in milliseconds to wait for builder bids
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "1000")]
+    pub grace: Option<String>,
     pub active: bool,
+    /// Config this one inherits unset values and relays from
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Execution-config fetches of one default config
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigUsageResponse {
+    #[schema(example = "main")]
+    pub name: String,
+    /// Requests across all consumers
+    pub request_count: i64,
+    /// Most recent request from any consumer, absent if the config was never fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_requested_at: Option<DateTime<Utc>>,
+    /// Consumers ordered by request count, busiest first
+    pub consumers: Vec<ConsumerUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsumerUsage {
+    /// Signing consumer name, or client IP for unsigned requests
+    pub consumer: String,
+    pub request_count: i64,
+    /// Keys sent with the latest request (proposers returned for v1)
+    pub last_key_count: i32,
+    pub max_key_count: i32,
+    pub first_requested_at: DateTime<Utc>,
+    pub last_requested_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateDefaultConfigRequest {
+    #[schema(example = "main")]
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    /// Grace period in milliseconds to wait for builder bids
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "1000")]
+    pub grace: Option<String>,
     #[serde(default = "default_true")]
     pub active: bool,
+    /// Config to inherit unset values and relays from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[serde(default)]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
 }
 
+/// Omitted fields are left unchanged; `null` clears the nullable ones
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateDefaultConfigRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_recipient: Option<EthAddress>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>)]
+    pub fee_recipient: Patch<EthAddress>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "30000000")]
+    pub gas_limit: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "100000000000000000")]
+    pub min_value: Patch<String>,
+    /// Whether block building via relays is enabled
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<bool>)]
+    pub builder_enabled: Patch<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "90")]
+    pub builder_boost_factor: Patch<String>,
+    /// Grace period in milliseconds to wait for builder bids
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "1000")]
+    pub grace: Patch<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub active: Option<bool>,
+    /// Config to inherit unset values and relays from
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>)]
+    pub parent: Patch<String>,
+    /// Replaces the referenced relay sets when set
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
 }
 
@@ -131,15 +320,40 @@ fn default_true() -> bool {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProposerResponse {
     pub public_key: BlsPubkey,
+    pub id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    /// Free-form note, e.g. who operates the validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
+    /// Decommissioned proposers are kept for history but left out of execution configs
+    pub decommissioned: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decommissioned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -147,15 +361,43 @@ pub struct ProposerResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProposerListItem {
     pub public_key: BlsPubkey,
+    pub id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    /// Free-form note, e.g. who operates the validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    /// Number of relays configured for the proposer
+    pub relay_count: i64,
+    /// Relay configs, only populated with `?include=relays`
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
+    /// Decommissioned proposers are kept for history but left out of execution configs
+    pub decommissioned: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decommissioned_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -165,12 +407,34 @@ pub struct CreateOrUpdateProposerRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     #[serde(default)]
     pub reset_relays: bool,
+    /// Free-form note, e.g. who operates the validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[serde(default)]
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[serde(default)]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
 }
 
@@ -180,17 +444,43 @@ pub struct CreateOrUpdateProposerRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProposerPatternResponse {
+    #[schema(example = "lido-operators")]
     pub name: String,
+    pub id: Uuid,
+    #[schema(example = "^0xa[0-9a-f]{95}$")]
     pub pattern: String,
+    #[schema(example = json!(["lido", "mainnet"]))]
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    /// Inactive patterns are left out of execution configs
+    pub active: bool,
+    /// Free-form note, e.g. who operates the validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -198,189 +488,1395 @@ pub struct ProposerPatternResponse {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProposerPatternListItem {
+    #[schema(example = "lido-operators")]
     pub name: String,
+    pub id: Uuid,
+    #[schema(example = "^0xa[0-9a-f]{95}$")]
     pub pattern: String,
+    #[schema(example = json!(["lido", "mainnet"]))]
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     pub reset_relays: bool,
+    pub active: bool,
+    /// Free-form note, e.g. who operates the validator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateProposerPatternRequest {
+    #[schema(example = "lido-operators")]
     pub name: String,
+    #[schema(example = "^0xa[0-9a-f]{95}$")]
     pub pattern: String,
     #[serde(default)]
+    #[schema(example = json!(["lido", "mainnet"]))]
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_recipient: Option<EthAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
     pub gas_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
     pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
     #[serde(default)]
     pub reset_relays: bool,
+    #[serde(default = "default_true")]
+    pub active: bool,
+    /// Free-form note, e.g. who operates the validator
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Key/value metadata, filterable with `?label.<key>=<value>`
+    #[serde(default)]
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Labels,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[serde(default)]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
 }
 
+/// Omitted fields are left unchanged; `null` clears the nullable ones
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateProposerPatternRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "^0xa[0-9a-f]{95}$")]
     pub pattern: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["lido", "mainnet"]))]
     pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>)]
+    pub fee_recipient: Patch<EthAddress>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "30000000")]
+    pub gas_limit: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "100000000000000000")]
+    pub min_value: Patch<String>,
+    /// Whether block building via relays is enabled
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<bool>)]
+    pub builder_enabled: Patch<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "90")]
+    pub builder_boost_factor: Patch<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_recipient: Option<EthAddress>,
+    pub reset_relays: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
+    pub active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "Operated by team alpha")]
+    pub note: Patch<String>,
+    /// Replaces all labels when set
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<String>,
+    #[schema(example = json!({"operator": "alpha", "region": "eu"}))]
+    pub labels: Option<Labels>,
+    /// Replaces the referenced relay sets when set
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub reset_relays: Option<bool>,
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, ProposerRelayConfig>>,
 }
 
+/// Keys from the database that a pattern's regex matches
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PatternMatchesResponse {
+    #[schema(example = "lido-operators")]
+    pub name: String,
+    #[schema(example = "^0xa[0-9a-f]{95}$")]
+    pub pattern: String,
+    /// Where the keys came from: `proposers` or `mux:<name>`
+    pub source: String,
+    /// Number of keys checked
+    pub total: i64,
+    /// Number of keys the pattern matches
+    pub count: i64,
+    /// First matching keys in key order
+    pub sample: Vec<String>,
+    /// When the proposer matches were computed; absent for mux sources, which are matched on request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
+
+/// Proposers matched by each pattern, from the pattern match table
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PatternMatchStatistics {
+    /// When the matches were computed
+    pub refreshed_at: DateTime<Utc>,
+    /// Number of proposers
+    pub proposers: i64,
+    /// Proposers no active pattern matches
+    pub unmatched: i64,
+    pub patterns: Vec<PatternMatchCount>,
+}
+
+/// How many proposers one pattern matches
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PatternMatchCount {
+    #[schema(example = "lido-operators")]
+    pub name: String,
+    pub active: bool,
+    /// Number of proposers the pattern matches
+    pub matches: i64,
+}
+
 // ============================================================================
-// Vouch - Execution Config (Public Endpoint)
+// Vouch - Tags API
 // ============================================================================
 
+/// A tag and how many proposer patterns carry it
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct ExecutionConfigResponse {
-    pub version: u8,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_recipient: Option<EthAddress>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
+pub struct TagUsage {
+    pub tag: String,
+    pub patterns: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RenameTagRequest {
+    /// New tag name; merged into the existing tag if a pattern already has it
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RenameTagResponse {
+    pub from: String,
+    pub to: String,
+    /// Names of the patterns whose tags changed
+    pub patterns: Vec<String>,
+}
+
+// ============================================================================
+// Vouch - Relay Sets API
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RelaySetResponse {
+    #[schema(example = "flashbots")]
+    pub name: String,
+    pub id: Uuid,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<String>,
+    #[schema(example = "Flashbots mainnet relays")]
+    pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub proposers: Option<Vec<ProposerEntry>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
-/// Entry in proposers array - can be either a specific validator key or a regex pattern
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct ProposerEntry {
-    /// Validator public key (BlsPubkey) or regex pattern (String)
-    pub proposer: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_recipient: Option<EthAddress>,
+pub struct CreateRelaySetRequest {
+    #[schema(example = "flashbots")]
+    pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
+    #[schema(example = "Flashbots mainnet relays")]
+    pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<String>,
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
+    pub relays: Option<HashMap<String, RelayConfig>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRelaySetRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub reset_relays: Option<bool>,
+    #[schema(example = "Flashbots mainnet relays")]
+    pub description: Option<String>,
+    /// Replaces all relays of the set when set
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
     pub relays: Option<HashMap<String, RelayConfig>>,
 }
 
 // ============================================================================
-// Commit-Boost - Mux API
+// Vouch - Proposer Groups API
 // ============================================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct MuxConfigResponse {
+pub struct ProposerGroupResponse {
+    #[schema(example = "pool-a")]
     pub name: String,
+    pub id: Uuid,
+    /// Listed member keys
     pub keys: Vec<BlsPubkey>,
+    /// Regex selecting members in addition to `keys`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "^0x8[0-9a-f]{95}$")]
+    pub pattern: Option<String>,
+    /// Decides between groups sharing a key, highest first
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
+    pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    pub reset_relays: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    /// Relay sets expanded beneath `relays`, later sets overriding earlier ones by URL
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
+    pub relays: Option<HashMap<String, RelayConfig>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Proposer group without its keys and relays, as listed
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct MuxConfigListItem {
+pub struct ProposerGroupListItem {
+    #[schema(example = "pool-a")]
     pub name: String,
+    pub id: Uuid,
     pub key_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "^0x8[0-9a-f]{95}$")]
+    pub pattern: Option<String>,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
+    pub min_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    pub reset_relays: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct CreateMuxConfigRequest {
+pub struct CreateProposerGroupRequest {
+    #[schema(example = "pool-a")]
     pub name: String,
     #[serde(default)]
     pub keys: Vec<BlsPubkey>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct UpdateMuxConfigRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "^0x8[0-9a-f]{95}$")]
+    pub pattern: Option<String>,
     #[serde(default)]
-    pub keys: Vec<BlsPubkey>,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "30000000")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "100000000000000000")]
+    pub min_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "90")]
+    pub builder_boost_factor: Option<String>,
+    #[serde(default)]
+    pub reset_relays: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Operated by team alpha")]
+    pub note: Option<String>,
+    #[serde(default)]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
+    pub relays: Option<HashMap<String, RelayConfig>>,
 }
 
+/// Omitted fields are left unchanged; `null` clears the nullable ones
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct MuxKeysRequest {
-    pub keys: Vec<BlsPubkey>,
+pub struct UpdateProposerGroupRequest {
+    /// Replaces all listed keys when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<Vec<BlsPubkey>>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "^0x8[0-9a-f]{95}$")]
+    pub pattern: Patch<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>)]
+    pub fee_recipient: Patch<EthAddress>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "30000000")]
+    pub gas_limit: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "100000000000000000")]
+    pub min_value: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<bool>)]
+    pub builder_enabled: Patch<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "90")]
+    pub builder_boost_factor: Patch<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_relays: Option<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_missing")]
+    #[schema(value_type = Option<String>, example = "Operated by team alpha")]
+    pub note: Patch<String>,
+    /// Replaces the referenced relay sets when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(["flashbots"]))]
+    pub relay_sets: Option<Vec<String>>,
+    /// Replaces all relays of the group when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!({"https://relay.example.com": {"public_key": "0xac6e77dfe25ecd6110b8e780608cce0dab71fdd5ebea22a16c0205200f2f8e2e3ad3b71d3499c54ad14d6c21b41a37ae"}}))]
+    pub relays: Option<HashMap<String, RelayConfig>>,
 }
 
+/// Proposers found inactive by a cleanup run
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-pub struct MuxKeysResponse {
-    pub added: Option<i64>,
-    pub removed: Option<i64>,
-    pub total_keys: i64,
+pub struct ProposerCleanupResponse {
+    pub action: CleanupAction,
+    pub dry_run: bool,
+    /// Proposers not requested since this time count as inactive
+    pub cutoff: DateTime<Utc>,
+    pub count: usize,
+    /// Proposers the action was applied to (for a dry run, would be applied to)
+    pub public_keys: Vec<BlsPubkey>,
 }
 
 // ============================================================================
-// Conversions
+// Vouch - Validator Registrations API
 // ============================================================================
 
-impl From<VouchDefaultConfig> for DefaultConfigListItem {
-    fn from(config: VouchDefaultConfig) -> Self {
-        Self {
-            name: config.name,
-            fee_recipient: config.fee_recipient,
-            gas_limit: config.gas_limit,
-            min_value: config.min_value,
-            active: config.active,
-            relays: None, // Populated separately by handler
-            created_at: config.created_at,
-            updated_at: config.updated_at,
-        }
-    }
+/// Intended registration values compared with what relays observed
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IntendedRegistration {
+    /// Proposer fee recipient, falling back to the `config` default config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    /// Proposer gas limit, falling back to the `config` default config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
 }
 
-impl From<VouchProposer> for ProposerListItem {
-    fn from(proposer: VouchProposer) -> Self {
-        Self {
-            public_key: proposer.public_key,
-            fee_recipient: proposer.fee_recipient,
-            gas_limit: proposer.gas_limit,
-            min_value: proposer.min_value,
-            reset_relays: proposer.reset_relays,
-            relays: None, // Populated separately by handler
-            created_at: proposer.created_at,
-            updated_at: proposer.updated_at,
-        }
-    }
+/// Latest registration a relay returned for the proposer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RelayRegistration {
+    pub relay_url: String,
+    pub fee_recipient: EthAddress,
+    pub gas_limit: String,
+    /// Timestamp of the signed registration
+    pub registered_at: DateTime<Utc>,
+    /// When the relay was last queried
+    pub checked_at: DateTime<Utc>,
+    /// Whether the observed fee recipient equals the intended one (absent if none is intended)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient_matches: Option<bool>,
+    /// Whether the observed gas limit equals the intended one (absent if none is intended)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit_matches: Option<bool>,
 }
 
-impl From<VouchProposerPattern> for ProposerPatternListItem {
-    fn from(pattern: VouchProposerPattern) -> Self {
-        Self {
-            name: pattern.name,
-            pattern: pattern.pattern,
-            tags: pattern.tags,
-            fee_recipient: pattern.fee_recipient,
-            gas_limit: pattern.gas_limit,
-            min_value: pattern.min_value,
-            reset_relays: pattern.reset_relays,
-            created_at: pattern.created_at,
-            updated_at: pattern.updated_at,
-        }
-    }
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProposerRegistrationsResponse {
+    pub public_key: BlsPubkey,
+    pub intended: IntendedRegistration,
+    pub registrations: Vec<RelayRegistration>,
 }
 
-impl From<VouchDefaultRelay> for RelayConfig {
-    fn from(relay: VouchDefaultRelay) -> Self {
+// ============================================================================
+// Vouch - Execution Config (Public Endpoint)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionConfigResponse {
+    pub version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<String>,
+    /// Grace period in milliseconds to wait for builder bids
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relays: Option<HashMap<String, RelayConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposers: Option<Vec<ProposerEntry>>,
+}
+
+/// Request body for fetching several execution configs in one call
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchExecutionConfigRequest {
+    /// Default config names to build execution configs for
+    pub configs: Vec<String>,
+    /// Validator public keys to include proposer-specific configs for
+    #[serde(default)]
+    pub keys: Vec<BlsPubkey>,
+}
+
+/// Legacy v1 execution config: default config plus a per-proposer map
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionConfigV1Response {
+    pub default_config: ProposerConfigV1,
+    /// Proposer configs keyed by validator public key
+    pub proposer_config: HashMap<String, ProposerConfigV1>,
+}
+
+/// v1 proposer (or default) config
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProposerConfigV1 {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder: Option<BuilderConfigV1>,
+}
+
+/// v1 builder config: relays are plain URLs, disabled relays are left out
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BuilderConfigV1 {
+    pub enabled: bool,
+    pub relays: Vec<String>,
+    /// Configured relays left out of `relays` because they are disabled
+    /// (extension, only with `?include_disabled=true`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_relays: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace: Option<String>,
+}
+
+/// Entry in proposers array - can be either a specific validator key or a regex pattern
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProposerEntry {
+    /// Validator public key (BlsPubkey) or regex pattern (String)
+    pub proposer: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<EthAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<String>,
+    /// Whether block building via relays is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<bool>,
+    /// Boost factor applied to builder bids when comparing against local blocks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_relays: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relays: Option<HashMap<String, RelayConfig>>,
+}
+
+/// Layer of the configuration an explained value comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSourceKind {
+    Default,
+    Group,
+    Proposer,
+    Pattern,
+}
+
+/// A value of the merged execution config with the resource that set it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplainedValue<T> {
+    pub value: T,
+    pub source: ConfigSourceKind,
+    /// Default config, group or pattern name; the public key for proposers
+    pub name: String,
+}
+
+/// What Vouch ends up with for one key
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeyExplanation {
+    pub public_key: BlsPubkey,
+    /// `proposer` of the entry Vouch applies to the key (the first matching one)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<ExplainedValue<EthAddress>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<ExplainedValue<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<ExplainedValue<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_enabled: Option<ExplainedValue<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<ExplainedValue<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grace: Option<ExplainedValue<String>>,
+    /// The entry drops the default config's relays
+    pub reset_relays: bool,
+    pub relays: HashMap<String, ExplainedValue<RelayConfig>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionConfigExplanation {
+    pub config: String,
+    /// One entry per submitted key, in request order
+    pub keys: Vec<KeyExplanation>,
+}
+
+// ============================================================================
+// Vouch - Execution Config Import
+// ============================================================================
+
+/// Whether an imported resource was new or replaced an existing one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Created,
+    Updated,
+    /// Already existed and was left alone (`on_conflict=skip`)
+    Skipped,
+}
+
+/// A default config, proposer or pattern written by an import
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportedResource {
+    /// Config name, validator public key or pattern name
+    pub id: String,
+    pub action: ImportAction,
+    /// Number of relays imported for this resource
+    pub relays: usize,
+}
+
+/// Result of importing a Vouch execution config file
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportExecutionConfigResponse {
+    /// Nothing was written when true
+    pub dry_run: bool,
+    /// Execution config version of the imported file (1 or 2)
+    pub version: u8,
+    pub default_config: ImportedResource,
+    pub proposers: Vec<ImportedResource>,
+    pub patterns: Vec<ImportedResource>,
+}
+
+/// Result of a bulk proposer pattern import
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportProposerPatternsResponse {
+    /// Nothing was written when true
+    pub dry_run: bool,
+    /// One entry per pattern in the request, in request order
+    pub patterns: Vec<ImportedResource>,
+}
+
+// ============================================================================
+// Relays API
+// ============================================================================
+
+/// A relay URL switched off across all configs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DisabledRelayResponse {
+    pub url: String,
+    pub disabled_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Log Level API
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct LogLevelRequest {
+    /// `EnvFilter` directives, e.g. `debug` or `info,fee_manager=debug,sqlx=warn`
+    #[schema(example = "info,fee_manager=debug")]
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LogLevelResponse {
+    /// Active `EnvFilter` directives
+    pub level: String,
+}
+
+// ============================================================================
+// Migrations API
+// ============================================================================
+
+/// Where a migration stands in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    /// Embedded in this binary, not run yet
+    Pending,
+    /// Started but did not complete; needs manual repair
+    Failed,
+    /// Applied by a newer binary that this one does not know
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigrationInfo {
+    #[schema(example = 20260130000001i64)]
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+    /// When the database ran it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_on: Option<DateTime<Utc>>,
+    /// The file embedded in this binary differs from the one that was applied
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub checksum_mismatch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigrationsResponse {
+    pub applied: usize,
+    pub pending: usize,
+    /// Every migration known to this binary or the database, by version
+    pub migrations: Vec<MigrationInfo>,
+}
+
+// ============================================================================
+// Export Storage
+// ============================================================================
+
+/// Where an export was written with `?destination=storage`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredExport {
+    #[schema(example = "fee-manager-backups")]
+    pub bucket: String,
+    #[schema(example = "prod/proposer-patterns/20250110T120000Z.json")]
+    pub key: String,
+    /// Object size in bytes
+    pub size: u64,
+}
+
+// ============================================================================
+// Backups API
+// ============================================================================
+
+/// Contents of a backup object, and the body of a restore
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupSnapshot {
+    pub taken_at: DateTime<Utc>,
+    /// Latest migration applied when the snapshot was taken; a snapshot only
+    /// restores into the same schema
+    pub schema_version: i64,
+    /// Every row of every configuration table, by table, as read in one transaction
+    #[schema(value_type = Object)]
+    pub tables: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+/// Outcome of restoring a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupRestoreResponse {
+    /// When the restored snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// Rows written, by table
+    pub tables: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupRetention {
+    /// Newest snapshots kept (0: any number)
+    pub keep_last: u32,
+    /// Days after which snapshots are deleted (0: never)
+    pub max_age_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupResponse {
+    pub id: Uuid,
+    /// Run of the schedule this backup was taken for
+    pub scheduled_for: DateTime<Utc>,
+    pub status: JobStatus,
+    /// Object in the export_storage bucket, once succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "prod/backups/20250110T030000Z.json")]
+    pub key: Option<String>,
+    /// Object size in bytes, once succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupsResponse {
+    /// Whether this replica runs the schedule (`backups.enabled`)
+    pub enabled: bool,
+    #[schema(example = "0 3 * * *")]
+    pub schedule: String,
+    /// Next run of the schedule, when enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub retention: BackupRetention,
+    /// Retained snapshots and failed runs, newest first
+    pub backups: Vec<BackupResponse>,
+}
+
+// ============================================================================
+// Consistency Check API
+// ============================================================================
+
+/// Kind of damage a consistency check finds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyIssue {
+    /// Relay row whose config, proposer, pattern, group or relay set is gone
+    OrphanedRelay,
+    /// Mux key whose mux config is gone
+    MissingMux,
+    /// Reference to a relay set that does not exist
+    MissingRelaySet,
+    /// Public key or address that is malformed or not lowercase
+    InvalidHex,
+    /// Relay listed again under a URL differing only in case or a trailing slash
+    DuplicateUrl,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsistencyFinding {
+    pub issue: ConsistencyIssue,
+    #[schema(example = "vouch_default_relays")]
+    pub table: String,
+    /// Key of the row: `id` for relays and mux keys, else the name or public key
+    #[schema(example = "42")]
+    pub row: String,
+    #[schema(example = "fee_recipient '0xABCD...' is not lowercase")]
+    pub detail: String,
+    /// Whether `fix` can repair it; the rest need an operator
+    pub fixable: bool,
+    /// Whether this run repaired it
+    pub fixed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsistencyReport {
+    /// Whether fixable findings were repaired
+    pub fix: bool,
+    pub count: usize,
+    pub fixed: usize,
+    pub findings: Vec<ConsistencyFinding>,
+}
+
+// ============================================================================
+// Jobs API
+// ============================================================================
+
+/// Operation a background job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum JobKind {
+    ImportProposerPatterns,
+    ImportExecutionConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    /// Ended with an error, or was interrupted by its replica stopping
+    Failed,
+}
+
+/// Body of a `202 Accepted` answer; poll `url` for the outcome
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobAccepted {
+    pub id: Uuid,
+    #[schema(example = "/api/v1/admin/jobs/6f1c2d4e-8a3b-4c5d-9e6f-7a8b9c0d1e2f")]
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobResponse {
+    pub id: Uuid,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Token that submitted the job
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// What the synchronous endpoint would have answered, once succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// Problem details the synchronous endpoint would have answered, once failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Audit History API
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditHistoryResponse {
+    /// e.g. `vouch_proposer`
+    pub resource_type: String,
+    pub resource_id: String,
+    /// A page of the stored audit events of the resource, oldest first
+    #[schema(value_type = Vec<Object>)]
+    pub events: Vec<serde_json::Value>,
+    /// Stored events of the resource across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    /// More events follow this page
+    pub has_more: bool,
+}
+
+// ============================================================================
+// Commit-Boost - Mux API
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxConfigResponse {
+    #[schema(example = "lido-mux")]
+    pub name: String,
+    pub id: Uuid,
+    /// Keys in the requested page (all keys, up to the cap, without `limit`)
+    pub keys: Vec<BlsPubkey>,
+    /// Total number of keys in the mux config
+    pub total_keys: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxConfigListItem {
+    #[schema(example = "lido-mux")]
+    pub name: String,
+    pub id: Uuid,
+    pub key_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateMuxConfigRequest {
+    #[schema(example = "lido-mux")]
+    pub name: String,
+    #[serde(default)]
+    pub keys: Vec<BlsPubkey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateMuxConfigRequest {
+    #[serde(default)]
+    pub keys: Vec<BlsPubkey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeysRequest {
+    pub keys: Vec<BlsPubkey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeysResponse {
+    pub added: Option<i64>,
+    pub removed: Option<i64>,
+    pub total_keys: i64,
+}
+
+/// How a mux config's keys differ from a desired key list
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeysDiffResponse {
+    /// Desired keys missing from the mux config, in request order
+    pub to_add: Vec<BlsPubkey>,
+    /// Keys of the mux config not in the desired list, in mux order
+    pub to_remove: Vec<BlsPubkey>,
+    /// Desired keys already in the mux config, in request order
+    pub unchanged: Vec<BlsPubkey>,
+    /// The diff was written to the mux config (`apply=true`)
+    pub applied: bool,
+    /// Key count after the request, the current one when not applied
+    pub total_keys: i64,
+}
+
+/// A key that belongs to more than one mux config
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateMuxKey {
+    pub public_key: BlsPubkey,
+    /// Mux configs containing the key, sorted by name
+    pub muxes: Vec<String>,
+}
+
+/// Copy or move keys from one mux config to another
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeysTransferRequest {
+    /// Name of the mux config receiving the keys
+    pub target: String,
+    /// Keys to transfer; all keys of the source mux when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<Vec<BlsPubkey>>,
+}
+
+/// What a copy/move changed in the source and target mux configs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeysTransferResponse {
+    pub source: String,
+    pub target: String,
+    /// Keys newly added to the target
+    pub added: Vec<BlsPubkey>,
+    /// Keys that were already in the target
+    pub already_present: Vec<BlsPubkey>,
+    /// Requested keys that are not in the source (nothing done for them)
+    pub not_found: Vec<BlsPubkey>,
+    /// Keys removed from the source (move only)
+    pub removed: i64,
+    pub source_total_keys: i64,
+    pub target_total_keys: i64,
+}
+
+/// Salted key hashes of a mux config, for consumers that only check membership
+/// (`Accept: application/vnd.fee-manager.key-hashes+json`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeyHashesResponse {
+    /// Always `sha256`: each hash is SHA-256 over the salt bytes followed by the 48 key bytes
+    pub algorithm: String,
+    /// Hex salt, fixed per mux config
+    pub salt: String,
+    /// Hex hashes of every key in the mux config, sorted
+    pub hashes: Vec<String>,
+}
+
+/// Bloom filter over the salted key hashes of a mux config
+/// (`Accept: application/vnd.fee-manager.key-bloom+json`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MuxKeyBloomResponse {
+    /// Always `sha256`, as for the key hashes
+    pub algorithm: String,
+    /// Hex salt, fixed per mux config
+    pub salt: String,
+    /// Filter size in bits
+    pub bits: u64,
+    /// Number of bit positions per key: `(h1 + i * h2) mod bits` for `i` in `0..hashes`,
+    /// with `h1`/`h2` the first two big-endian u64 of the key hash
+    pub hashes: u32,
+    /// Hex filter bytes; bit `p` is `filter[p / 8] & (1 << (p % 8))`
+    pub filter: String,
+    /// Expected false positive rate for the number of keys in the filter
+    pub false_positive_rate: f64,
+}
+
+// ============================================================================
+// Commit-Boost - PBS API
+// ============================================================================
+
+/// Registry a mux loads its validator keys from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MuxRegistry {
+    Lido,
+    Ssv,
+}
+
+impl MuxRegistry {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MuxRegistry::Lido => "lido",
+            MuxRegistry::Ssv => "ssv",
+        }
+    }
+}
+
+/// A relay of a PBS config
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PbsRelay {
+    /// Relay id in cb-config.toml, also used by muxes to pick the relay
+    pub id: String,
+    /// Relay URL without the public key
+    pub url: String,
+    pub public_key: BlsPubkey,
+}
+
+impl From<CommitBoostPbsRelay> for PbsRelay {
+    fn from(r: CommitBoostPbsRelay) -> Self {
+        Self {
+            id: r.relay_id,
+            url: r.url,
+            public_key: r.public_key,
+        }
+    }
+}
+
+/// A mux of a PBS config, keyed by a mux config or by a registry
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PbsMux {
+    /// Mux id in cb-config.toml
+    pub id: String,
+    /// Mux config supplying the validator keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mux: Option<String>,
+    /// Registry supplying the validator keys, with `node_operator_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<MuxRegistry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_operator_id: Option<i64>,
+    /// Ids of the config's relays this mux uses
+    #[serde(default)]
+    pub relays: Vec<String>,
+}
+
+impl From<CommitBoostPbsMux> for PbsMux {
+    fn from(m: CommitBoostPbsMux) -> Self {
+        Self {
+            id: m.mux_id,
+            mux: m.mux_name,
+            registry: match m.registry.as_deref() {
+                Some("lido") => Some(MuxRegistry::Lido),
+                Some("ssv") => Some(MuxRegistry::Ssv),
+                _ => None,
+            },
+            node_operator_id: m.node_operator_id,
+            relays: m.relay_ids,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PbsConfigResponse {
+    pub name: String,
+    pub id: Uuid,
+    /// Commit-Boost chain (e.g. `Mainnet`, `Hoodi`)
+    pub chain: String,
+    /// `[pbs]` settings, rendered as given
+    #[schema(value_type = Object)]
+    pub pbs: PbsSettings,
+    pub relays: Vec<PbsRelay>,
+    pub muxes: Vec<PbsMux>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatePbsConfigRequest {
+    pub name: String,
+    pub chain: String,
+    /// `[pbs]` settings (strings, numbers or booleans)
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub pbs: PbsSettings,
+    #[serde(default)]
+    pub relays: Vec<PbsRelay>,
+    #[serde(default)]
+    pub muxes: Vec<PbsMux>,
+}
+
+/// Replaces the whole PBS config
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdatePbsConfigRequest {
+    pub chain: String,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub pbs: PbsSettings,
+    #[serde(default)]
+    pub relays: Vec<PbsRelay>,
+    #[serde(default)]
+    pub muxes: Vec<PbsMux>,
+}
+
+// ============================================================================
+// Search API
+// ============================================================================
+
+/// Resource type a search hit belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResourceType {
+    VouchProposer,
+    VouchDefaultConfig,
+    VouchProposerPattern,
+    VouchProposerGroup,
+    VouchRelaySet,
+    CommitBoostMux,
+    CommitBoostPbsConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchHit {
+    pub resource_type: SearchResourceType,
+    /// Identifier of the owning resource (public key or name)
+    pub resource_id: String,
+    /// Field that matched (e.g. `public_key`, `fee_recipient`, `relay_url`)
+    pub field: String,
+    /// Matched value
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Resource found by its stable id
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResourceLookupResponse {
+    pub id: Uuid,
+    pub resource_type: SearchResourceType,
+    /// Natural key of the resource (public key or name), as used in its admin URL
+    pub resource_id: String,
+}
+
+// ============================================================================
+// Field Visitors
+// ============================================================================
+
+/// Implement a visitor trait over one field of types that have it directly
+/// and, with `with relays`, in each entry of their `relays` map; `(relays)`
+/// visits the relays of types without the field of their own and `(patch field)`
+/// visits a `Patch` field when it is provided
+macro_rules! impl_field_visitor {
+    ($trait:ident::$method:ident(relays): $($ty:ty),* $(,)?) => {$(
+        impl $trait for $ty {
+            fn $method(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+                for relay in self.relays.iter_mut().flat_map(|relays| relays.values_mut()) {
+                    relay.$method(f);
+                }
+            }
+        }
+    )*};
+    ($trait:ident::$method:ident($field:ident): $($ty:ty),* $(,)?) => {$(
+        impl $trait for $ty {
+            fn $method(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+                f(&mut self.$field);
+            }
+        }
+    )*};
+    ($trait:ident::$method:ident(patch $field:ident) with relays: $($ty:ty),* $(,)?) => {$(
+        impl $trait for $ty {
+            fn $method(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+                if let Some(value) = self.$field.provided_mut() {
+                    f(value);
+                }
+                for relay in self.relays.iter_mut().flat_map(|relays| relays.values_mut()) {
+                    relay.$method(f);
+                }
+            }
+        }
+    )*};
+    ($trait:ident::$method:ident($field:ident) with relays: $($ty:ty),* $(,)?) => {$(
+        impl $trait for $ty {
+            fn $method(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+                f(&mut self.$field);
+                for relay in self.relays.iter_mut().flat_map(|relays| relays.values_mut()) {
+                    relay.$method(f);
+                }
+            }
+        }
+    )*};
+}
+
+impl_field_visitor!(MinValues::visit_min_values(min_value): RelayConfig, ProposerRelayConfig, ProposerPatternListItem, ProposerGroupListItem);
+impl_field_visitor!(MinValues::visit_min_values(relays): RelaySetResponse, CreateRelaySetRequest, UpdateRelaySetRequest);
+impl_field_visitor!(MinValues::visit_min_values(min_value) with relays:
+    DefaultConfigResponse,
+    DefaultConfigListItem,
+    CreateDefaultConfigRequest,
+    ProposerResponse,
+    ProposerListItem,
+    CreateOrUpdateProposerRequest,
+    ProposerPatternResponse,
+    CreateProposerPatternRequest,
+    ProposerGroupResponse,
+    CreateProposerGroupRequest,
+    ProposerEntry,
+);
+impl_field_visitor!(MinValues::visit_min_values(patch min_value) with relays:
+    UpdateDefaultConfigRequest,
+    UpdateProposerPatternRequest,
+    UpdateProposerGroupRequest,
+);
+
+impl_field_visitor!(GasLimits::visit_gas_limits(gas_limit): RelayConfig, ProposerRelayConfig);
+impl_field_visitor!(GasLimits::visit_gas_limits(relays): CreateRelaySetRequest, UpdateRelaySetRequest);
+impl_field_visitor!(GasLimits::visit_gas_limits(gas_limit) with relays:
+    CreateDefaultConfigRequest,
+    CreateOrUpdateProposerRequest,
+    CreateProposerPatternRequest,
+    CreateProposerGroupRequest,
+);
+impl_field_visitor!(GasLimits::visit_gas_limits(patch gas_limit) with relays:
+    UpdateDefaultConfigRequest,
+    UpdateProposerPatternRequest,
+    UpdateProposerGroupRequest,
+);
+
+/// Implement `RelayKeys` for types with a `relays` map
+macro_rules! impl_relay_keys {
+    ($($ty:ty),* $(,)?) => {$(
+        impl RelayKeys for $ty {
+            fn visit_relay_keys(&self, f: &mut dyn FnMut(&str, &BlsPubkey)) {
+                for (url, relay) in self.relays.iter().flatten() {
+                    f(url, &relay.public_key);
+                }
+            }
+        }
+    )*};
+}
+
+impl_relay_keys!(
+    CreateDefaultConfigRequest,
+    UpdateDefaultConfigRequest,
+    CreateOrUpdateProposerRequest,
+    CreateProposerPatternRequest,
+    UpdateProposerPatternRequest,
+    CreateProposerGroupRequest,
+    UpdateProposerGroupRequest,
+    CreateRelaySetRequest,
+    UpdateRelaySetRequest,
+);
+
+impl MinValues for ExecutionConfigResponse {
+    fn visit_min_values(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+        f(&mut self.min_value);
+        for relay in self.relays.iter_mut().flat_map(|relays| relays.values_mut()) {
+            relay.visit_min_values(f);
+        }
+        for proposer in self.proposers.iter_mut().flatten() {
+            proposer.visit_min_values(f);
+        }
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<VouchDefaultConfig> for DefaultConfigListItem {
+    fn from(config: VouchDefaultConfig) -> Self {
+        Self {
+            name: config.name,
+            id: config.id,
+            fee_recipient: config.fee_recipient,
+            gas_limit: config.gas_limit,
+            min_value: config.min_value,
+            builder_enabled: config.builder_enabled,
+            builder_boost_factor: config.builder_boost_factor,
+            grace: config.grace,
+            active: config.active,
+            parent: config.parent,
+            relay_sets: config.relay_sets,
+            relays: None, // Populated separately by handler
+            created_at: config.created_at,
+            updated_at: config.updated_at,
+        }
+    }
+}
+
+impl From<VouchProposer> for ProposerListItem {
+    fn from(proposer: VouchProposer) -> Self {
+        Self {
+            public_key: proposer.public_key,
+            id: proposer.id,
+            fee_recipient: proposer.fee_recipient,
+            gas_limit: proposer.gas_limit,
+            min_value: proposer.min_value,
+            builder_enabled: proposer.builder_enabled,
+            builder_boost_factor: proposer.builder_boost_factor,
+            reset_relays: proposer.reset_relays,
+            note: proposer.note,
+            labels: proposer.labels.0,
+            relay_sets: proposer.relay_sets,
+            relay_count: 0, // Populated separately by handler
+            relays: None,   // Populated separately by handler
+            decommissioned: proposer.decommissioned_at.is_some(),
+            decommissioned_at: proposer.decommissioned_at,
+            created_at: proposer.created_at,
+            updated_at: proposer.updated_at,
+        }
+    }
+}
+
+impl From<VouchProposerPattern> for ProposerPatternListItem {
+    fn from(pattern: VouchProposerPattern) -> Self {
+        Self {
+            name: pattern.name,
+            id: pattern.id,
+            pattern: pattern.pattern,
+            tags: pattern.tags,
+            fee_recipient: pattern.fee_recipient,
+            gas_limit: pattern.gas_limit,
+            min_value: pattern.min_value,
+            builder_enabled: pattern.builder_enabled,
+            builder_boost_factor: pattern.builder_boost_factor,
+            reset_relays: pattern.reset_relays,
+            active: pattern.active,
+            note: pattern.note,
+            labels: pattern.labels.0,
+            relay_sets: pattern.relay_sets,
+            created_at: pattern.created_at,
+            updated_at: pattern.updated_at,
+        }
+    }
+}
+
+impl From<VouchDefaultRelay> for RelayConfig {
+    fn from(relay: VouchDefaultRelay) -> Self {
         Self {
             public_key: relay.public_key,
             fee_recipient: relay.fee_recipient,
@@ -391,6 +1887,30 @@ impl From<VouchDefaultRelay> for RelayConfig {
     }
 }
 
+impl From<VouchProposerGroupRelay> for RelayConfig {
+    fn from(relay: VouchProposerGroupRelay) -> Self {
+        Self {
+            public_key: relay.public_key,
+            fee_recipient: relay.fee_recipient,
+            gas_limit: relay.gas_limit,
+            min_value: relay.min_value,
+            disabled: false, // Disabled per proposer by an inline relay of the same URL
+        }
+    }
+}
+
+impl From<VouchRelaySetRelay> for RelayConfig {
+    fn from(relay: VouchRelaySetRelay) -> Self {
+        Self {
+            public_key: relay.public_key,
+            fee_recipient: relay.fee_recipient,
+            gas_limit: relay.gas_limit,
+            min_value: relay.min_value,
+            disabled: false, // Disabled per proposer by an inline relay of the same URL
+        }
+    }
+}
+
 impl From<VouchProposerRelay> for ProposerRelayConfig {
     fn from(relay: VouchProposerRelay) -> Self {
         Self {
@@ -426,3 +1946,25 @@ impl From<VouchProposerPatternRelay> for RelayConfig {
         }
     }
 }
+
+impl From<VouchConfigUsage> for ConsumerUsage {
+    fn from(usage: VouchConfigUsage) -> Self {
+        Self {
+            consumer: usage.consumer,
+            request_count: usage.request_count,
+            last_key_count: usage.last_key_count,
+            max_key_count: usage.max_key_count,
+            first_requested_at: usage.first_requested_at,
+            last_requested_at: usage.last_requested_at,
+        }
+    }
+}
+
+impl From<DisabledRelay> for DisabledRelayResponse {
+    fn from(relay: DisabledRelay) -> Self {
+        Self {
+            url: relay.url,
+            disabled_at: relay.disabled_at,
+        }
+    }
+}
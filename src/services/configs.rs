@@ -0,0 +1,1150 @@
+// services/configs.rs - Default config lookups, listing and writes
+use super::{placeholders, relay_map, PageRequest, RelayRemoval};
+use super::relay_sets::ensure_relay_sets_exist;
+use crate::addresses::normalize_hex;
+use crate::audit::{AuditChanges, AuditValues, ResourceType};
+use crate::errors::ApiError;
+use crate::handlers::partial_update::PartialUpdate;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::models::{VouchConfigUsage, VouchDefaultConfig, VouchDefaultRelay};
+use crate::schema::{
+    ConfigUsageResponse, ConsumerUsage, CreateDefaultConfigRequest, DefaultConfigListItem, DefaultConfigResponse,
+    DeletionPreview, DependentReference, PaginatedResponse, RelayConfig, UpdateDefaultConfigRequest,
+};
+use crate::units::normalize_min_value_filter;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Filters of the default config list
+#[derive(Debug, Default)]
+pub struct ConfigFilters {
+    /// Name prefix
+    pub name: Option<String>,
+    pub fee_recipient: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub active: Option<bool>,
+    /// Relay URL prefix
+    pub relay_url: Option<String>,
+    /// Relay min_value (exact match)
+    pub relay_min_value: Option<String>,
+    pub time_range: TimeRangeFilters,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "c.name"),
+        ("created_at", "c.created_at"),
+        ("updated_at", "c.updated_at"),
+    ],
+    default: ("c.name", SortOrder::Asc),
+    tiebreak: "c.name",
+};
+
+/// Outcome of deleting a default config
+#[derive(Debug)]
+pub enum ConfigDeletion {
+    NotFound,
+    /// Other configs inherit from it, nothing was deleted
+    HasChildren(Vec<String>),
+    /// Deleted, with its audited values
+    Deleted(Box<AuditValues>),
+}
+
+/// What depends on a default config
+#[derive(Debug, Default)]
+pub struct ConfigDependents {
+    /// Rows deleted along with the config
+    pub relays: i64,
+    pub usage: i64,
+    pub access_tokens: i64,
+    /// Configs inheriting from it, which block its delete
+    pub children: Vec<String>,
+}
+
+/// Storage of default configs and their relays
+pub trait ConfigRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    /// Number of configs matching the filters
+    fn count(&self, filters: &ConfigFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the configs matching the filters
+    fn list(
+        &self,
+        filters: &ConfigFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<VouchDefaultConfig>, ApiError>> + Send;
+
+    /// Own relays of the given configs
+    fn relays_of(&self, names: &[String]) -> impl Future<Output = Result<Vec<VouchDefaultRelay>, ApiError>> + Send;
+
+    /// The config with its own relays
+    fn find(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<(VouchDefaultConfig, Vec<VouchDefaultRelay>)>, ApiError>> + Send;
+
+    /// Insert the config with its relays, returning its audited values; `None`
+    /// when a config of that name exists
+    fn insert(
+        &self,
+        req: &CreateDefaultConfigRequest,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Apply the fields the request sets, returning the audited values before and after
+    fn update(
+        &self,
+        name: &str,
+        req: &UpdateDefaultConfigRequest,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Add or replace one relay, leaving the others alone; returns the audited
+    /// values before and after
+    fn put_relay(
+        &self,
+        name: &str,
+        url: &str,
+        relay: &RelayConfig,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    fn delete_relay(&self, name: &str, url: &str) -> impl Future<Output = Result<RelayRemoval, ApiError>> + Send;
+
+    /// Delete the config unless it is the parent of others
+    fn delete(&self, name: &str) -> impl Future<Output = Result<ConfigDeletion, ApiError>> + Send;
+
+    /// Execution-config fetches of the config per consumer, most requests
+    /// first; `None` if it does not exist
+    fn usage(&self, name: &str) -> impl Future<Output = Result<Option<Vec<VouchConfigUsage>>, ApiError>> + Send;
+
+    /// `None` if the config does not exist
+    fn dependents(&self, name: &str) -> impl Future<Output = Result<Option<ConfigDependents>, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Default config '{}' not found", name))
+}
+
+pub async fn ensure_exists(repo: &impl ConfigRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// The config with its own relays (not those of parents or relay sets), min values in wei
+pub async fn get(repo: &impl ConfigRepository, name: &str) -> Result<DefaultConfigResponse, ApiError> {
+    let (config, relays) = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(DefaultConfigResponse {
+        name: config.name,
+        id: config.id,
+        fee_recipient: config.fee_recipient,
+        gas_limit: config.gas_limit,
+        min_value: config.min_value,
+        builder_enabled: config.builder_enabled,
+        builder_boost_factor: config.builder_boost_factor,
+        grace: config.grace,
+        active: config.active,
+        parent: config.parent,
+        relay_sets: config.relay_sets,
+        relays: relay_map(relays, |r| r.url.clone()),
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+    })
+}
+
+/// One page of the configs matching the filters with their own relays, min values in wei
+pub async fn list(
+    repo: &impl ConfigRepository,
+    filters: &ConfigFilters,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<DefaultConfigListItem>, ApiError> {
+    let configs = repo.list(filters, page).await?;
+    let total = repo.count(filters).await?;
+
+    let names: Vec<String> = configs.iter().map(|c| c.name.clone()).collect();
+    let mut relays: HashMap<String, HashMap<String, RelayConfig>> = HashMap::new();
+    if !names.is_empty() {
+        for relay in repo.relays_of(&names).await? {
+            relays
+                .entry(relay.config_name.clone())
+                .or_default()
+                .insert(relay.url.clone(), relay.into());
+        }
+    }
+
+    let data = configs
+        .into_iter()
+        .map(|c| {
+            let relays = relays.remove(&c.name);
+            let mut item: DefaultConfigListItem = c.into();
+            item.relays = relays;
+            item
+        })
+        .collect();
+
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a config with its relays, returning it and the changes to audit
+pub async fn create(
+    repo: &impl ConfigRepository,
+    req: &CreateDefaultConfigRequest,
+) -> Result<(DefaultConfigResponse, AuditChanges), ApiError> {
+    let after = repo
+        .insert(req)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Config '{}' already exists", req.name)))?;
+    let changes = AuditChanges::diff(&AuditValues::default(), &after);
+    Ok((get(repo, &req.name).await?, changes))
+}
+
+/// Update the fields of a config the request sets, returning it and the changes to audit
+pub async fn update(
+    repo: &impl ConfigRepository,
+    name: &str,
+    req: &UpdateDefaultConfigRequest,
+) -> Result<(DefaultConfigResponse, AuditChanges), ApiError> {
+    let (before, after) = repo.update(name, req).await?.ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Own relays of a config keyed by URL, min values in wei
+pub async fn relays(repo: &impl ConfigRepository, name: &str) -> Result<HashMap<String, RelayConfig>, ApiError> {
+    let (_, relays) = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(relays.into_iter().map(|r| (r.url.clone(), r.into())).collect())
+}
+
+/// Add or replace one relay of a config, returning all its relays and the changes to audit
+pub async fn put_relay(
+    repo: &impl ConfigRepository,
+    name: &str,
+    url: &str,
+    relay: &RelayConfig,
+) -> Result<(HashMap<String, RelayConfig>, AuditChanges), ApiError> {
+    let (before, after) = repo.put_relay(name, url, relay).await?.ok_or_else(|| not_found(name))?;
+    let changes = AuditChanges {
+        relay_url: Some(url.to_string()),
+        ..AuditChanges::diff(&before, &after)
+    };
+    Ok((relays(repo, name).await?, changes))
+}
+
+/// Remove one relay of a config, returning the changes to audit
+pub async fn delete_relay(repo: &impl ConfigRepository, name: &str, url: &str) -> Result<AuditChanges, ApiError> {
+    match repo.delete_relay(name, url).await? {
+        RelayRemoval::NotFound => Err(not_found(name)),
+        RelayRemoval::RelayNotFound => Err(ApiError::NotFound(format!(
+            "Relay '{}' not found for default config '{}'",
+            url, name
+        ))),
+        RelayRemoval::Removed(values) => {
+            let (before, after) = *values;
+            Ok(AuditChanges {
+                relay_url: Some(url.to_string()),
+                ..AuditChanges::diff(&before, &after)
+            })
+        }
+    }
+}
+
+/// Delete a config with its relays, returning the changes to audit
+pub async fn delete(repo: &impl ConfigRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    match repo.delete(name).await? {
+        ConfigDeletion::NotFound => Err(not_found(name)),
+        ConfigDeletion::HasChildren(children) => Err(ApiError::Conflict(format!(
+            "Default config '{}' is the parent of: {}",
+            name,
+            children.join(", ")
+        ))),
+        ConfigDeletion::Deleted(before) => Ok(AuditChanges::diff(&before, &AuditValues::default())),
+    }
+}
+
+/// Execution-config fetches of a config, in total and per consumer
+pub async fn usage(repo: &impl ConfigRepository, name: &str) -> Result<ConfigUsageResponse, ApiError> {
+    let consumers: Vec<ConsumerUsage> = repo
+        .usage(name)
+        .await?
+        .ok_or_else(|| not_found(name))?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    Ok(ConfigUsageResponse {
+        name: name.to_string(),
+        request_count: consumers.iter().map(|c| c.request_count).sum(),
+        last_requested_at: consumers.iter().map(|c| c.last_requested_at).max(),
+        consumers,
+    })
+}
+
+/// What deleting a config removes, and the children that block it like in [`delete`]
+pub async fn dependents(repo: &impl ConfigRepository, name: &str) -> Result<DeletionPreview, ApiError> {
+    let dependents = repo.dependents(name).await?.ok_or_else(|| not_found(name))?;
+    let blocked_by = dependents
+        .children
+        .into_iter()
+        .map(|child| DependentReference {
+            resource_type: ResourceType::VouchDefaultConfig.as_str().to_string(),
+            name: child,
+        })
+        .collect();
+    Ok(DeletionPreview::new(
+        name.to_string(),
+        &[
+            ("relays", dependents.relays),
+            ("usage", dependents.usage),
+            ("access_tokens", dependents.access_tokens),
+        ],
+        blocked_by,
+    ))
+}
+
+/// Longest parent chain followed, guarding against cycles
+const MAX_CHAIN_DEPTH: i32 = 16;
+
+/// A config followed by its parent, grandparent and so on
+pub(crate) async fn load_config_chain(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Vec<VouchDefaultConfig>, ApiError> {
+    let chain = sqlx::query_as!(
+        VouchDefaultConfig,
+        r#"WITH RECURSIVE chain AS (
+               SELECT c.*, 0 AS depth FROM vouch_default_configs c WHERE c.name = $1
+               UNION ALL
+               SELECT p.*, chain.depth + 1 FROM vouch_default_configs p
+               JOIN chain ON p.name = chain.parent
+               WHERE chain.depth < $2
+           )
+           SELECT name as "name!", fee_recipient as "fee_recipient: _", gas_limit, min_value, grace, builder_enabled, builder_boost_factor,
+                  active as "active!", parent, relay_sets as "relay_sets!", id as "id!", created_at as "created_at!", updated_at as "updated_at!"
+           FROM chain ORDER BY depth"#,
+        name,
+        MAX_CHAIN_DEPTH
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(chain)
+}
+
+/// Collapse a chain from `load_config_chain` into one config, child values winning
+pub(crate) fn merge_config_chain(
+    chain: Vec<VouchDefaultConfig>,
+) -> Option<VouchDefaultConfig> {
+    chain.into_iter().reduce(|child, parent| VouchDefaultConfig {
+        fee_recipient: child.fee_recipient.or(parent.fee_recipient),
+        gas_limit: child.gas_limit.or(parent.gas_limit),
+        min_value: child.min_value.or(parent.min_value),
+        builder_enabled: child.builder_enabled.or(parent.builder_enabled),
+        builder_boost_factor: child.builder_boost_factor.or(parent.builder_boost_factor),
+        grace: child.grace.or(parent.grace),
+        ..child
+    })
+}
+
+/// `parent` must exist and must not lead back to `name`
+pub(crate) async fn ensure_valid_parent(
+    conn: &mut PgConnection,
+    name: &str,
+    parent: &str,
+) -> Result<(), ApiError> {
+    let chain = load_config_chain(conn, parent).await?;
+    if chain.is_empty() {
+        return Err(ApiError::InvalidData(format!(
+            "Parent config '{}' not found",
+            parent
+        )));
+    }
+    if chain.iter().any(|c| c.name == name) {
+        return Err(ApiError::InvalidData(format!(
+            "Parent '{}' would make '{}' its own ancestor",
+            parent, name
+        )));
+    }
+    if chain.len() as i32 > MAX_CHAIN_DEPTH {
+        return Err(ApiError::InvalidData(format!(
+            "Parent chain is deeper than {} configs",
+            MAX_CHAIN_DEPTH
+        )));
+    }
+    Ok(())
+}
+
+/// Audited values of a config and its relay count, locking the config row
+pub(crate) async fn audit_snapshot(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let config = sqlx::query_as!(
+        VouchDefaultConfig,
+        r#"SELECT name, fee_recipient as "fee_recipient: _", gas_limit, min_value, grace, builder_enabled, builder_boost_factor, active, parent, relay_sets, id, created_at, updated_at
+           FROM vouch_default_configs WHERE name = $1 FOR UPDATE"#,
+        name
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(config) = config else {
+        return Ok(None);
+    };
+
+    let relays_count: i64 = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM vouch_default_relays WHERE config_name = $1"#,
+        name
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(AuditValues {
+        relays_count: Some(relays_count),
+        ..AuditValues::from(&config)
+    }))
+}
+
+impl ConfigFilters {
+    /// SQL WHERE clause over `vouch_default_configs c` (empty when no filter is set)
+    fn where_clause(&self) -> String {
+        let mut conditions = Vec::new();
+
+        if let Some(ref name) = self.name {
+            conditions.push(format!("c.name LIKE '{}%'", name.replace('\'', "''")));
+        }
+        if let Some(ref fr) = self.fee_recipient {
+            let fr = normalize_hex(fr);
+            conditions.push(format!("c.fee_recipient = '{}'", fr.replace('\'', "''")));
+        }
+        if let Some(ref gl) = self.gas_limit {
+            conditions.push(format!("c.gas_limit = '{}'", gl.replace('\'', "''")));
+        }
+        if let Some(ref mv) = self.min_value {
+            let mv = normalize_min_value_filter(mv);
+            conditions.push(format!("c.min_value = '{}'", mv.replace('\'', "''")));
+        }
+        if let Some(active) = self.active {
+            conditions.push(format!("c.active = {}", if active { "true" } else { "false" }));
+        }
+        // Relay filters using EXISTS subquery
+        if let Some(ref relay_url) = self.relay_url {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_default_relays r WHERE r.config_name = c.name AND r.url LIKE '{}%')",
+                relay_url.replace('\'', "''")
+            ));
+        }
+        if let Some(ref relay_min_value) = self.relay_min_value {
+            let relay_min_value = normalize_min_value_filter(relay_min_value);
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_default_relays r WHERE r.config_name = c.name AND r.min_value = '{}')",
+                relay_min_value.replace('\'', "''")
+            ));
+        }
+
+        conditions.extend(self.time_range.conditions("c"));
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+/// Replace the relays of a config with the given ones
+async fn replace_relays(
+    conn: &mut PgConnection,
+    name: &str,
+    relays: &HashMap<String, RelayConfig>,
+) -> Result<(), ApiError> {
+    sqlx::query!("DELETE FROM vouch_default_relays WHERE config_name = $1", name)
+        .execute(&mut *conn)
+        .await?;
+
+    for (url, relay) in relays {
+        sqlx::query!(
+            "INSERT INTO vouch_default_relays
+             (config_name, url, public_key, fee_recipient, gas_limit, min_value)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+impl ConfigRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM vouch_default_configs WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, filters: &ConfigFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM vouch_default_configs c {}", filters.where_clause());
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, filters: &ConfigFilters, page: PageRequest<'_>) -> Result<Vec<VouchDefaultConfig>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT c.name, c.fee_recipient, c.gas_limit, c.min_value, c.grace, c.builder_enabled, c.builder_boost_factor, c.active, c.parent, c.relay_sets, c.id, c.created_at, c.updated_at
+             FROM vouch_default_configs c {}
+             {}
+             LIMIT {} OFFSET {}",
+            filters.where_clause(), order_by, page.limit, page.offset
+        );
+        Ok(sqlx::query_as::<_, VouchDefaultConfig>(&data_sql).fetch_all(self).await?)
+    }
+
+    async fn relays_of(&self, names: &[String]) -> Result<Vec<VouchDefaultRelay>, ApiError> {
+        let relays_sql = format!(
+            "SELECT id, config_name, url, public_key, fee_recipient, gas_limit, min_value
+             FROM vouch_default_relays WHERE config_name IN ({})",
+            placeholders(names.len())
+        );
+        let mut query = sqlx::query_as::<_, VouchDefaultRelay>(&relays_sql);
+        for name in names {
+            query = query.bind(name);
+        }
+        Ok(query.fetch_all(self).await?)
+    }
+
+    async fn find(&self, name: &str) -> Result<Option<(VouchDefaultConfig, Vec<VouchDefaultRelay>)>, ApiError> {
+        let config = sqlx::query_as!(
+            VouchDefaultConfig,
+            r#"SELECT name, fee_recipient as "fee_recipient: _", gas_limit, min_value, grace, builder_enabled, builder_boost_factor, active, parent, relay_sets, id, created_at, updated_at
+               FROM vouch_default_configs WHERE name = $1"#,
+            name
+        )
+        .fetch_optional(self)
+        .await?;
+        let Some(config) = config else {
+            return Ok(None);
+        };
+
+        let relays = sqlx::query_as!(
+            VouchDefaultRelay,
+            r#"SELECT id, config_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value
+               FROM vouch_default_relays WHERE config_name = $1"#,
+            name
+        )
+        .fetch_all(self)
+        .await?;
+
+        Ok(Some((config, relays)))
+    }
+
+    async fn insert(&self, req: &CreateDefaultConfigRequest) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        if let Some(parent) = &req.parent {
+            ensure_valid_parent(&mut tx, &req.name, parent).await?;
+        }
+        ensure_relay_sets_exist(&mut tx, &req.relay_sets).await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO vouch_default_configs (name, fee_recipient, gas_limit, min_value, active, grace, builder_enabled, builder_boost_factor, parent, relay_sets)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (name) DO NOTHING",
+            req.name,
+            req.fee_recipient as _,
+            req.gas_limit,
+            req.min_value,
+            req.active,
+            req.grace,
+            req.builder_enabled,
+            req.builder_boost_factor,
+            req.parent,
+            &req.relay_sets
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The unique name constraint decides races between concurrent creates
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        if let Some(relays) = &req.relays {
+            replace_relays(&mut tx, &req.name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, &req.name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some(after))
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        req: &UpdateDefaultConfigRequest,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        // Read current values for the audit trail; also checks the config exists
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        if let Some(parent) = req.parent.value() {
+            ensure_valid_parent(&mut tx, name, parent).await?;
+        }
+        if let Some(relay_sets) = &req.relay_sets {
+            ensure_relay_sets_exist(&mut tx, relay_sets).await?;
+        }
+
+        let mut update = PartialUpdate::new("vouch_default_configs");
+        update
+            .set("fee_recipient", req.fee_recipient.update())
+            .set("gas_limit", req.gas_limit.update())
+            .set("min_value", req.min_value.update())
+            .set("active", req.active)
+            .set("grace", req.grace.update())
+            .set("builder_enabled", req.builder_enabled.update())
+            .set("builder_boost_factor", req.builder_boost_factor.update())
+            .set("parent", req.parent.update())
+            .set("relay_sets", req.relay_sets.as_deref());
+        update.execute(&mut tx, "name", name).await?;
+
+        if let Some(relays) = &req.relays {
+            replace_relays(&mut tx, name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn put_relay(
+        &self,
+        name: &str,
+        url: &str,
+        relay: &RelayConfig,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "INSERT INTO vouch_default_relays
+             (config_name, url, public_key, fee_recipient, gas_limit, min_value)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (config_name, url) DO UPDATE SET
+                 public_key = EXCLUDED.public_key,
+                 fee_recipient = EXCLUDED.fee_recipient,
+                 gas_limit = EXCLUDED.gas_limit,
+                 min_value = EXCLUDED.min_value",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn delete_relay(&self, name: &str, url: &str) -> Result<RelayRemoval, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(RelayRemoval::NotFound);
+        };
+
+        let deleted = sqlx::query!(
+            "DELETE FROM vouch_default_relays WHERE config_name = $1 AND url = $2",
+            name,
+            url
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted == 0 {
+            return Ok(RelayRemoval::RelayNotFound);
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(RelayRemoval::Removed(Box::new((before, after))))
+    }
+
+    async fn delete(&self, name: &str) -> Result<ConfigDeletion, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(ConfigDeletion::NotFound);
+        };
+
+        let children = sqlx::query_scalar!(
+            "SELECT name FROM vouch_default_configs WHERE parent = $1 ORDER BY name",
+            name
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if !children.is_empty() {
+            return Ok(ConfigDeletion::HasChildren(children));
+        }
+
+        sqlx::query!("DELETE FROM vouch_default_configs WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(ConfigDeletion::Deleted(Box::new(before)))
+    }
+
+    async fn usage(&self, name: &str) -> Result<Option<Vec<VouchConfigUsage>>, ApiError> {
+        if !self.exists(name).await? {
+            return Ok(None);
+        }
+        let usage = sqlx::query_as!(
+            VouchConfigUsage,
+            "SELECT config_name, consumer, request_count, last_key_count, max_key_count, first_requested_at, last_requested_at
+             FROM vouch_config_usage WHERE config_name = $1
+             ORDER BY request_count DESC, consumer",
+            name
+        )
+        .fetch_all(self)
+        .await?;
+        Ok(Some(usage))
+    }
+
+    async fn dependents(&self, name: &str) -> Result<Option<ConfigDependents>, ApiError> {
+        let counts = sqlx::query!(
+            r#"SELECT (SELECT COUNT(*) FROM vouch_default_relays r WHERE r.config_name = c.name) AS "relays!",
+                      (SELECT COUNT(*) FROM vouch_config_usage u WHERE u.config_name = c.name) AS "usage!",
+                      (SELECT COUNT(*) FROM public_access_tokens t
+                       WHERE t.resource_type = 'vouch_default_config' AND t.resource_name = c.name) AS "access_tokens!"
+               FROM vouch_default_configs c WHERE c.name = $1"#,
+            name
+        )
+        .fetch_optional(self)
+        .await?;
+        let Some(counts) = counts else {
+            return Ok(None);
+        };
+        let children = sqlx::query_scalar!(
+            "SELECT name FROM vouch_default_configs WHERE parent = $1 ORDER BY name",
+            name
+        )
+        .fetch_all(self)
+        .await?;
+        Ok(Some(ConfigDependents {
+            relays: counts.relays,
+            usage: counts.usage,
+            access_tokens: counts.access_tokens,
+            children,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory configs with their relays, keyed by name, and their usage
+    #[derive(Default)]
+    struct MockRepository {
+        configs: Mutex<HashMap<String, (VouchDefaultConfig, Vec<VouchDefaultRelay>)>>,
+        usage: Vec<VouchConfigUsage>,
+    }
+
+    impl MockRepository {
+        fn add(&self, name: &str, parent: Option<&str>) {
+            let config = VouchDefaultConfig {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                fee_recipient: None,
+                gas_limit: Some("30000000".to_string()),
+                min_value: None,
+                builder_enabled: None,
+                builder_boost_factor: None,
+                grace: None,
+                active: true,
+                parent: parent.map(str::to_string),
+                relay_sets: Vec::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            self.configs.lock().unwrap().insert(name.to_string(), (config, Vec::new()));
+        }
+
+        fn snapshot(&self, name: &str) -> Option<AuditValues> {
+            self.configs.lock().unwrap().get(name).map(|(config, relays)| AuditValues {
+                relays_count: Some(relays.len() as i64),
+                ..AuditValues::from(config)
+            })
+        }
+
+        fn set_relay(&self, name: &str, url: &str, relay: &RelayConfig) {
+            let mut configs = self.configs.lock().unwrap();
+            let (_, relays) = configs.get_mut(name).unwrap();
+            relays.retain(|r| r.url != url);
+            relays.push(VouchDefaultRelay {
+                id: 1,
+                config_name: name.to_string(),
+                url: url.to_string(),
+                public_key: relay.public_key.clone(),
+                fee_recipient: relay.fee_recipient.clone(),
+                gas_limit: relay.gas_limit.clone(),
+                min_value: relay.min_value.clone(),
+            });
+        }
+    }
+
+    fn relay() -> RelayConfig {
+        RelayConfig {
+            public_key: TEST_KEY.parse().unwrap(),
+            fee_recipient: None,
+            gas_limit: None,
+            min_value: Some("50000000000000000".to_string()),
+            disabled: false,
+        }
+    }
+
+    impl ConfigRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.configs.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self, filters: &ConfigFilters) -> Result<i64, ApiError> {
+            let all = PageRequest {
+                sort: None,
+                order: None,
+                limit: i64::MAX,
+                offset: 0,
+            };
+            Ok(self.list(filters, all).await?.len() as i64)
+        }
+
+        /// Applies only the name prefix filter, sorted by name
+        async fn list(&self, filters: &ConfigFilters, page: PageRequest<'_>) -> Result<Vec<VouchDefaultConfig>, ApiError> {
+            let mut configs: Vec<VouchDefaultConfig> = self
+                .configs
+                .lock()
+                .unwrap()
+                .values()
+                .map(|(config, _)| config.clone())
+                .filter(|c| filters.name.as_deref().is_none_or(|prefix| c.name.starts_with(prefix)))
+                .collect();
+            configs.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(configs.into_iter().skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn relays_of(&self, names: &[String]) -> Result<Vec<VouchDefaultRelay>, ApiError> {
+            let configs = self.configs.lock().unwrap();
+            Ok(names.iter().filter_map(|name| configs.get(name)).flat_map(|(_, relays)| relays.clone()).collect())
+        }
+
+        async fn find(&self, name: &str) -> Result<Option<(VouchDefaultConfig, Vec<VouchDefaultRelay>)>, ApiError> {
+            Ok(self.configs.lock().unwrap().get(name).cloned())
+        }
+
+        async fn insert(&self, req: &CreateDefaultConfigRequest) -> Result<Option<AuditValues>, ApiError> {
+            if self.exists(&req.name).await? {
+                return Ok(None);
+            }
+            self.add(&req.name, req.parent.as_deref());
+            for (url, relay) in req.relays.iter().flatten() {
+                self.set_relay(&req.name, url, relay);
+            }
+            Ok(self.snapshot(&req.name))
+        }
+
+        async fn update(
+            &self,
+            name: &str,
+            req: &UpdateDefaultConfigRequest,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            if let Some((config, relays)) = self.configs.lock().unwrap().get_mut(name) {
+                if let Some(gas_limit) = req.gas_limit.update() {
+                    config.gas_limit = gas_limit.cloned();
+                }
+                if req.relays.is_some() {
+                    relays.clear();
+                }
+            }
+            for (url, relay) in req.relays.iter().flatten() {
+                self.set_relay(name, url, relay);
+            }
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn put_relay(
+            &self,
+            name: &str,
+            url: &str,
+            relay: &RelayConfig,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            self.set_relay(name, url, relay);
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn delete_relay(&self, name: &str, url: &str) -> Result<RelayRemoval, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(RelayRemoval::NotFound);
+            };
+            {
+                let mut configs = self.configs.lock().unwrap();
+                let (_, relays) = configs.get_mut(name).unwrap();
+                let count = relays.len();
+                relays.retain(|r| r.url != url);
+                if relays.len() == count {
+                    return Ok(RelayRemoval::RelayNotFound);
+                }
+            }
+            Ok(RelayRemoval::Removed(Box::new((before, self.snapshot(name).unwrap()))))
+        }
+
+        async fn delete(&self, name: &str) -> Result<ConfigDeletion, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(ConfigDeletion::NotFound);
+            };
+            let mut configs = self.configs.lock().unwrap();
+            let mut children: Vec<String> = configs
+                .values()
+                .filter(|(c, _)| c.parent.as_deref() == Some(name))
+                .map(|(c, _)| c.name.clone())
+                .collect();
+            if !children.is_empty() {
+                children.sort();
+                return Ok(ConfigDeletion::HasChildren(children));
+            }
+            configs.remove(name);
+            Ok(ConfigDeletion::Deleted(Box::new(before)))
+        }
+
+        async fn usage(&self, name: &str) -> Result<Option<Vec<VouchConfigUsage>>, ApiError> {
+            if !self.exists(name).await? {
+                return Ok(None);
+            }
+            let mut usage: Vec<VouchConfigUsage> =
+                self.usage.iter().filter(|u| u.config_name == name).cloned().collect();
+            usage.sort_by(|a, b| b.request_count.cmp(&a.request_count).then(a.consumer.cmp(&b.consumer)));
+            Ok(Some(usage))
+        }
+
+        async fn dependents(&self, name: &str) -> Result<Option<ConfigDependents>, ApiError> {
+            let configs = self.configs.lock().unwrap();
+            let Some((_, relays)) = configs.get(name) else {
+                return Ok(None);
+            };
+            let mut children: Vec<String> = configs
+                .values()
+                .filter(|(c, _)| c.parent.as_deref() == Some(name))
+                .map(|(c, _)| c.name.clone())
+                .collect();
+            children.sort();
+            Ok(Some(ConfigDependents {
+                relays: relays.len() as i64,
+                usage: self.usage.iter().filter(|u| u.config_name == name).count() as i64,
+                access_tokens: 0,
+                children,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_config() {
+        let repo = MockRepository::default();
+        repo.add("main", None);
+
+        let response = get(&repo, "main").await.unwrap();
+        assert_eq!(response.gas_limit.as_deref(), Some("30000000"));
+        assert!(response.relays.is_none());
+        assert!(matches!(get(&repo, "missing").await, Err(ApiError::NotFound(_))));
+        assert!(ensure_exists(&repo, "main").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_parent_conflicts() {
+        let repo = MockRepository::default();
+        repo.add("base", None);
+        repo.add("child-b", Some("base"));
+        repo.add("child-a", Some("base"));
+
+        let result = delete(&repo, "base").await;
+        assert!(matches!(result, Err(ApiError::Conflict(message)) if message.ends_with("child-a, child-b")));
+
+        let changes = delete(&repo, "child-a").await.unwrap();
+        assert_eq!(changes.gas_limit.unwrap().from.as_deref(), Some("30000000"));
+        assert!(matches!(delete(&repo, "child-a").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_configs_with_relays() {
+        let repo = MockRepository::default();
+        for name in ["main-b", "main-a", "other"] {
+            repo.add(name, None);
+        }
+        repo.set_relay("main-a", "https://relay.example.com", &relay());
+
+        let filters = ConfigFilters {
+            name: Some("main".to_string()),
+            ..Default::default()
+        };
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 1,
+            offset: 0,
+        };
+        let response = list(&repo, &filters, page).await.unwrap();
+        assert_eq!((response.total, response.data.len(), response.has_more), (2, 1, true));
+        assert_eq!(response.data[0].name, "main-a");
+        assert!(response.data[0].relays.as_ref().unwrap().contains_key("https://relay.example.com"));
+
+        let response = list(&repo, &filters, PageRequest { offset: 1, ..page }).await.unwrap();
+        assert_eq!(response.data[0].name, "main-b");
+        assert!(response.data[0].relays.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_existing_config_conflicts() {
+        let repo = MockRepository::default();
+        let req = CreateDefaultConfigRequest {
+            name: "main".to_string(),
+            fee_recipient: None,
+            gas_limit: Some("30000000".to_string()),
+            min_value: None,
+            builder_enabled: None,
+            builder_boost_factor: None,
+            grace: None,
+            active: true,
+            parent: None,
+            relay_sets: Vec::new(),
+            relays: Some(HashMap::from([("https://relay.example.com".to_string(), relay())])),
+        };
+
+        let (response, changes) = create(&repo, &req).await.unwrap();
+        assert_eq!(response.relays.unwrap().len(), 1);
+        assert_eq!(changes.relays_count.unwrap().to, Some(1));
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_only_touches_set_fields() {
+        let repo = MockRepository::default();
+        repo.add("main", None);
+        repo.set_relay("main", "https://relay.example.com", &relay());
+
+        let req: UpdateDefaultConfigRequest = serde_json::from_str(r#"{"gas_limit": "36000000"}"#).unwrap();
+        let (response, changes) = update(&repo, "main", &req).await.unwrap();
+        assert_eq!(response.gas_limit.as_deref(), Some("36000000"));
+        assert_eq!(response.relays.unwrap().len(), 1);
+        assert!(changes.gas_limit.is_some() && changes.relays_count.is_none());
+
+        assert!(matches!(update(&repo, "missing", &req).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_put_and_delete_relay() {
+        let repo = MockRepository::default();
+        repo.add("main", None);
+
+        let (all, changes) = put_relay(&repo, "main", "https://relay.example.com", &relay()).await.unwrap();
+        assert!(all.contains_key("https://relay.example.com"));
+        assert_eq!(changes.relay_url.as_deref(), Some("https://relay.example.com"));
+        assert!(matches!(
+            put_relay(&repo, "missing", "https://relay.example.com", &relay()).await,
+            Err(ApiError::NotFound(_))
+        ));
+
+        let changes = delete_relay(&repo, "main", "https://relay.example.com").await.unwrap();
+        assert_eq!(changes.relays_count.unwrap().to, Some(0));
+        let missing = delete_relay(&repo, "main", "https://relay.example.com").await;
+        assert!(matches!(missing, Err(ApiError::NotFound(message)) if message.starts_with("Relay")));
+        assert!(relays(&repo, "main").await.unwrap().is_empty());
+    }
+
+    fn usage(config: &str, consumer: &str, request_count: i64) -> VouchConfigUsage {
+        VouchConfigUsage {
+            config_name: config.to_string(),
+            consumer: consumer.to_string(),
+            request_count,
+            last_key_count: 10,
+            max_key_count: 10,
+            first_requested_at: Utc::now(),
+            last_requested_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_usage_sums_consumers() {
+        let mut repo = MockRepository::default();
+        repo.add("main", None);
+        repo.add("idle", None);
+        repo.usage = vec![usage("main", "vouch-1", 2), usage("main", "vouch-2", 5), usage("other", "vouch-1", 1)];
+
+        let response = super::usage(&repo, "main").await.unwrap();
+        assert_eq!(response.request_count, 7);
+        let consumers: Vec<&str> = response.consumers.iter().map(|c| c.consumer.as_str()).collect();
+        assert_eq!(consumers, ["vouch-2", "vouch-1"]);
+
+        let response = super::usage(&repo, "idle").await.unwrap();
+        assert_eq!(response.request_count, 0);
+        assert!(response.last_requested_at.is_none());
+        assert!(matches!(super::usage(&repo, "missing").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dependents_lists_children() {
+        let mut repo = MockRepository::default();
+        repo.add("base", None);
+        repo.add("b", Some("base"));
+        repo.add("a", Some("base"));
+        repo.set_relay("base", "https://relay.example.com", &relay());
+        repo.usage = vec![usage("base", "vouch-1", 1)];
+
+        let preview = dependents(&repo, "base").await.unwrap();
+        let removes: Vec<(&str, i64)> = preview.removes.iter().map(|r| (r.kind.as_str(), r.count)).collect();
+        assert_eq!(removes, [("relays", 1), ("usage", 1), ("access_tokens", 0)]);
+        let blocked_by: Vec<&str> = preview.blocked_by.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(blocked_by, ["a", "b"]);
+
+        assert!(dependents(&repo, "a").await.unwrap().blocked_by.is_empty());
+        assert!(matches!(dependents(&repo, "missing").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_merge_config_chain_prefers_children() {
+        let repo = MockRepository::default();
+        repo.add("child", Some("parent"));
+        repo.add("parent", None);
+        let configs = repo.configs.lock().unwrap();
+        let mut child = configs["child"].0.clone();
+        child.gas_limit = None;
+        child.grace = Some("2s".to_string());
+        let mut parent = configs["parent"].0.clone();
+        parent.grace = Some("5s".to_string());
+
+        let merged = merge_config_chain(vec![child, parent]).unwrap();
+        assert_eq!(merged.name, "child");
+        assert_eq!(merged.gas_limit.as_deref(), Some("30000000"));
+        assert_eq!(merged.grace.as_deref(), Some("2s"));
+        assert!(merge_config_chain(Vec::new()).is_none());
+    }
+}
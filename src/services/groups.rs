@@ -0,0 +1,734 @@
+// services/groups.rs - Proposer group lookups, listing, writes and membership
+use super::relay_sets::{ensure_relay_sets_exist, expand_relay_sets, load_relay_sets};
+use super::PageRequest;
+use crate::addresses::BlsPubkey;
+use crate::audit::{AuditChanges, AuditValues};
+use crate::errors::ApiError;
+use crate::handlers::partial_update::PartialUpdate;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::models::{VouchProposerGroup, VouchProposerGroupRelay};
+use crate::schema::{
+    CreateProposerGroupRequest, PaginatedResponse, ProposerGroupListItem, ProposerGroupResponse, RelayConfig,
+    UpdateProposerGroupRequest,
+};
+use regex::Regex;
+use sqlx::{PgConnection, PgPool};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+const GROUP_COLUMNS: &str = "name, id, pattern, priority, fee_recipient, gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, relay_sets, created_at, updated_at";
+
+/// Filters of the proposer group list
+#[derive(Debug, Default)]
+pub struct GroupFilters {
+    /// Name prefix
+    pub name: Option<String>,
+    /// Groups listing this key (pattern members are not considered)
+    pub key: Option<BlsPubkey>,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "g.name"),
+        ("priority", "g.priority"),
+        ("created_at", "g.created_at"),
+        ("updated_at", "g.updated_at"),
+    ],
+    default: ("g.name", SortOrder::Asc),
+    tiebreak: "g.name",
+};
+
+/// A group with its listed keys, in key order, and its own relays
+#[derive(Debug, Clone)]
+pub struct StoredGroup {
+    pub group: VouchProposerGroup,
+    pub keys: Vec<BlsPubkey>,
+    pub relays: Vec<VouchProposerGroupRelay>,
+}
+
+/// Storage of proposer groups, their keys and relays
+pub trait GroupRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    /// Number of groups matching the filters
+    fn count(&self, filters: &GroupFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the groups matching the filters
+    fn list(
+        &self,
+        filters: &GroupFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<VouchProposerGroup>, ApiError>> + Send;
+
+    /// Number of listed keys per named group, absent for groups without keys
+    fn key_counts(&self, names: &[String]) -> impl Future<Output = Result<HashMap<String, i64>, ApiError>> + Send;
+
+    fn find(&self, name: &str) -> impl Future<Output = Result<Option<StoredGroup>, ApiError>> + Send;
+
+    /// Insert the group with its keys and relays, returning its audited values;
+    /// `None` when a group of that name exists
+    fn insert(
+        &self,
+        req: &CreateProposerGroupRequest,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Apply the fields the request sets, returning the audited values before and after
+    fn update(
+        &self,
+        name: &str,
+        req: &UpdateProposerGroupRequest,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Delete the group, returning its audited values
+    fn delete(&self, name: &str) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Proposer group '{}' not found", name))
+}
+
+/// Group patterns are matched here rather than by Vouch, so they must compile
+fn validate_pattern(pattern: Option<&str>) -> Result<(), ApiError> {
+    if let Some(pattern) = pattern {
+        Regex::new(pattern)
+            .map_err(|e| ApiError::InvalidData(format!("Invalid pattern '{}': {}", pattern, e)))?;
+    }
+    Ok(())
+}
+
+pub async fn ensure_exists(repo: &impl GroupRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// The group with its keys and relays, min values in wei
+pub async fn get(repo: &impl GroupRepository, name: &str) -> Result<ProposerGroupResponse, ApiError> {
+    let StoredGroup { group, keys, relays } = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    let relays: HashMap<String, RelayConfig> = relays.into_iter().map(|r| (r.url.clone(), r.into())).collect();
+    Ok(ProposerGroupResponse {
+        name: group.name,
+        id: group.id,
+        keys,
+        pattern: group.pattern,
+        priority: group.priority,
+        fee_recipient: group.fee_recipient,
+        gas_limit: group.gas_limit,
+        min_value: group.min_value,
+        builder_enabled: group.builder_enabled,
+        builder_boost_factor: group.builder_boost_factor,
+        reset_relays: group.reset_relays,
+        note: group.note,
+        relay_sets: group.relay_sets,
+        relays: if relays.is_empty() { None } else { Some(relays) },
+        created_at: group.created_at,
+        updated_at: group.updated_at,
+    })
+}
+
+/// One page of the groups matching the filters with their key counts, min values in wei
+pub async fn list(
+    repo: &impl GroupRepository,
+    filters: &GroupFilters,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<ProposerGroupListItem>, ApiError> {
+    let total = repo.count(filters).await?;
+    let groups = repo.list(filters, page).await?;
+    let names: Vec<String> = groups.iter().map(|g| g.name.clone()).collect();
+    let key_counts = repo.key_counts(&names).await?;
+
+    let data = groups
+        .into_iter()
+        .map(|group| ProposerGroupListItem {
+            key_count: key_counts.get(&group.name).copied().unwrap_or(0),
+            name: group.name,
+            id: group.id,
+            pattern: group.pattern,
+            priority: group.priority,
+            fee_recipient: group.fee_recipient,
+            gas_limit: group.gas_limit,
+            min_value: group.min_value,
+            builder_enabled: group.builder_enabled,
+            builder_boost_factor: group.builder_boost_factor,
+            reset_relays: group.reset_relays,
+            note: group.note,
+            relay_sets: group.relay_sets,
+            created_at: group.created_at,
+            updated_at: group.updated_at,
+        })
+        .collect();
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a group with its keys and relays, returning it and the changes to audit
+pub async fn create(
+    repo: &impl GroupRepository,
+    req: &CreateProposerGroupRequest,
+) -> Result<(ProposerGroupResponse, AuditChanges), ApiError> {
+    validate_pattern(req.pattern.as_deref())?;
+    let after = repo
+        .insert(req)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Proposer group '{}' already exists", req.name)))?;
+    let changes = AuditChanges::diff(&AuditValues::default(), &after);
+    Ok((get(repo, &req.name).await?, changes))
+}
+
+/// Update the fields of a group the request sets, returning it and the changes to audit
+pub async fn update(
+    repo: &impl GroupRepository,
+    name: &str,
+    req: &UpdateProposerGroupRequest,
+) -> Result<(ProposerGroupResponse, AuditChanges), ApiError> {
+    validate_pattern(req.pattern.value().map(String::as_str))?;
+    let (before, after) = repo.update(name, req).await?.ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Delete a group with its keys and relays, returning the changes to audit
+pub async fn delete(repo: &impl GroupRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    let before = repo.delete(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(AuditChanges::diff(&before, &AuditValues::default()))
+}
+
+/// A group ready to match keys, with its relay sets expanded beneath its own relays
+pub(crate) struct ResolvedGroup {
+    pub(crate) group: VouchProposerGroup,
+    /// Listed member keys
+    pub(crate) keys: HashSet<String>,
+    regex: Option<Regex>,
+    pub(crate) relays: HashMap<String, RelayConfig>,
+}
+
+impl ResolvedGroup {
+    fn contains(&self, key: &str) -> bool {
+        self.keys.contains(key) || self.regex.as_ref().is_some_and(|re| re.is_match(key))
+    }
+}
+
+/// Every group, highest priority first, so the first one containing a key applies to it
+pub(crate) async fn load_groups(pool: &PgPool) -> Result<Vec<ResolvedGroup>, ApiError> {
+    let groups = sqlx::query_as::<_, VouchProposerGroup>(&format!(
+        "SELECT {} FROM vouch_proposer_groups ORDER BY priority DESC, name",
+        GROUP_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+    if groups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut keys: HashMap<String, HashSet<String>> = HashMap::new();
+    let rows = sqlx::query!("SELECT group_name, public_key FROM vouch_proposer_group_keys")
+        .fetch_all(pool)
+        .await?;
+    for row in rows {
+        keys.entry(row.group_name).or_default().insert(row.public_key);
+    }
+    let mut relays = load_group_relays(pool).await?;
+    let set_names: Vec<String> = groups.iter().flat_map(|g| g.relay_sets.clone()).collect();
+    let sets = load_relay_sets(&mut *pool.acquire().await?, &set_names).await?;
+
+    Ok(groups
+        .into_iter()
+        .map(|group| {
+            let mut group_relays = expand_relay_sets(&group.relay_sets, &sets);
+            group_relays.extend(relays.remove(&group.name).unwrap_or_default());
+            ResolvedGroup {
+                keys: keys.remove(&group.name).unwrap_or_default(),
+                // Checked on write
+                regex: group.pattern.as_deref().and_then(|p| Regex::new(p).ok()),
+                relays: group_relays,
+                group,
+            }
+        })
+        .collect())
+}
+
+/// The group whose settings apply to `key`, if any
+pub(crate) fn group_for<'a>(groups: &'a [ResolvedGroup], key: &str) -> Option<&'a ResolvedGroup> {
+    groups.iter().find(|group| group.contains(key))
+}
+
+/// Relays of every group, keyed by group name and relay URL
+async fn load_group_relays(
+    pool: &PgPool,
+) -> Result<HashMap<String, HashMap<String, RelayConfig>>, ApiError> {
+    let relays = sqlx::query_as!(
+        VouchProposerGroupRelay,
+        r#"SELECT id, group_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value
+           FROM vouch_proposer_group_relays"#
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut groups: HashMap<String, HashMap<String, RelayConfig>> = HashMap::new();
+    for relay in relays {
+        groups
+            .entry(relay.group_name.clone())
+            .or_default()
+            .insert(relay.url.clone(), relay.into());
+    }
+    Ok(groups)
+}
+
+async fn insert_keys(
+    conn: &mut PgConnection,
+    name: &str,
+    keys: &[BlsPubkey],
+) -> Result<(), ApiError> {
+    sqlx::query!(
+        "INSERT INTO vouch_proposer_group_keys (group_name, public_key)
+         SELECT $1, UNNEST($2::TEXT[])
+         ON CONFLICT DO NOTHING",
+        name,
+        keys as _
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_relays(
+    conn: &mut PgConnection,
+    name: &str,
+    relays: &HashMap<String, RelayConfig>,
+) -> Result<(), ApiError> {
+    for (url, relay) in relays {
+        sqlx::query!(
+            "INSERT INTO vouch_proposer_group_relays
+             (group_name, url, public_key, fee_recipient, gas_limit, min_value)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Audited values of a group (with its key and relay counts), locking the group row
+async fn audit_snapshot(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let group = sqlx::query_as::<_, VouchProposerGroup>(&format!(
+        "SELECT {} FROM vouch_proposer_groups WHERE name = $1 FOR UPDATE",
+        GROUP_COLUMNS
+    ))
+    .bind(name)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(group) = group else {
+        return Ok(None);
+    };
+
+    let counts = sqlx::query!(
+        r#"SELECT (SELECT COUNT(*) FROM vouch_proposer_group_keys WHERE group_name = $1) AS "key_count!",
+                  (SELECT COUNT(*) FROM vouch_proposer_group_relays WHERE group_name = $1) AS "relays_count!""#,
+        name
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(AuditValues {
+        key_count: Some(counts.key_count),
+        relays_count: Some(counts.relays_count),
+        ..AuditValues::from(&group)
+    }))
+}
+
+impl GroupFilters {
+    /// SQL WHERE clause over `vouch_proposer_groups g` (empty when no filter is set)
+    fn where_clause(&self) -> String {
+        let mut conditions = Vec::new();
+        if let Some(ref name) = self.name {
+            conditions.push(format!("g.name LIKE '{}%'", name.replace('\'', "''")));
+        }
+        if let Some(ref key) = self.key {
+            // A normalized key is plain hex
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_group_keys k WHERE k.group_name = g.name AND k.public_key = '{}')",
+                key
+            ));
+        }
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+impl GroupRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM vouch_proposer_groups WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, filters: &GroupFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM vouch_proposer_groups g {}", filters.where_clause());
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, filters: &GroupFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposerGroup>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT {} FROM vouch_proposer_groups g {} {} LIMIT {} OFFSET {}",
+            GROUP_COLUMNS,
+            filters.where_clause(),
+            order_by,
+            page.limit,
+            page.offset
+        );
+        Ok(sqlx::query_as::<_, VouchProposerGroup>(&data_sql).fetch_all(self).await?)
+    }
+
+    async fn key_counts(&self, names: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+        Ok(sqlx::query!(
+            r#"SELECT group_name, COUNT(*) AS "count!" FROM vouch_proposer_group_keys
+               WHERE group_name = ANY($1) GROUP BY group_name"#,
+            names
+        )
+        .fetch_all(self)
+        .await?
+        .into_iter()
+        .map(|row| (row.group_name, row.count))
+        .collect())
+    }
+
+    async fn find(&self, name: &str) -> Result<Option<StoredGroup>, ApiError> {
+        let group = sqlx::query_as::<_, VouchProposerGroup>(&format!(
+            "SELECT {} FROM vouch_proposer_groups WHERE name = $1",
+            GROUP_COLUMNS
+        ))
+        .bind(name)
+        .fetch_optional(self)
+        .await?;
+        let Some(group) = group else {
+            return Ok(None);
+        };
+
+        let keys: Vec<BlsPubkey> = sqlx::query_scalar!(
+            r#"SELECT public_key as "public_key: BlsPubkey" FROM vouch_proposer_group_keys WHERE group_name = $1 ORDER BY public_key"#,
+            name
+        )
+        .fetch_all(self)
+        .await?;
+        let relays = sqlx::query_as!(
+            VouchProposerGroupRelay,
+            r#"SELECT id, group_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value
+               FROM vouch_proposer_group_relays WHERE group_name = $1"#,
+            name
+        )
+        .fetch_all(self)
+        .await?;
+
+        Ok(Some(StoredGroup { group, keys, relays }))
+    }
+
+    async fn insert(&self, req: &CreateProposerGroupRequest) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        ensure_relay_sets_exist(&mut tx, &req.relay_sets).await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO vouch_proposer_groups (name, pattern, priority, fee_recipient, gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, relay_sets)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (name) DO NOTHING",
+            req.name,
+            req.pattern,
+            req.priority,
+            req.fee_recipient as _,
+            req.gas_limit,
+            req.min_value,
+            req.builder_enabled,
+            req.builder_boost_factor,
+            req.reset_relays,
+            req.note,
+            &req.relay_sets
+        )
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        insert_keys(&mut tx, &req.name, &req.keys).await?;
+        if let Some(relays) = &req.relays {
+            insert_relays(&mut tx, &req.name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, &req.name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some(after))
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        req: &UpdateProposerGroupRequest,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        if let Some(relay_sets) = &req.relay_sets {
+            ensure_relay_sets_exist(&mut tx, relay_sets).await?;
+        }
+
+        let mut update = PartialUpdate::new("vouch_proposer_groups");
+        update
+            .set("pattern", req.pattern.update())
+            .set("priority", req.priority)
+            .set("fee_recipient", req.fee_recipient.update())
+            .set("gas_limit", req.gas_limit.update())
+            .set("min_value", req.min_value.update())
+            .set("builder_enabled", req.builder_enabled.update())
+            .set("builder_boost_factor", req.builder_boost_factor.update())
+            .set("reset_relays", req.reset_relays)
+            .set("note", req.note.update())
+            .set("relay_sets", req.relay_sets.as_deref());
+        update.execute(&mut tx, "name", name).await?;
+
+        if let Some(keys) = &req.keys {
+            sqlx::query!("DELETE FROM vouch_proposer_group_keys WHERE group_name = $1", name)
+                .execute(&mut *tx)
+                .await?;
+            insert_keys(&mut tx, name, keys).await?;
+        }
+        if let Some(relays) = &req.relays {
+            sqlx::query!("DELETE FROM vouch_proposer_group_relays WHERE group_name = $1", name)
+                .execute(&mut *tx)
+                .await?;
+            insert_relays(&mut tx, name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query!("DELETE FROM vouch_proposer_groups WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(before))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory groups keyed by name
+    #[derive(Default)]
+    struct MockRepository {
+        groups: Mutex<HashMap<String, StoredGroup>>,
+    }
+
+    impl MockRepository {
+        fn add(&self, name: &str, keys: &[&str]) {
+            let group = VouchProposerGroup {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                pattern: None,
+                priority: 0,
+                fee_recipient: None,
+                gas_limit: None,
+                min_value: None,
+                builder_enabled: None,
+                builder_boost_factor: None,
+                reset_relays: false,
+                note: None,
+                relay_sets: Vec::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let keys = keys.iter().map(|key| key.parse().unwrap()).collect();
+            let stored = StoredGroup {
+                group,
+                keys,
+                relays: Vec::new(),
+            };
+            self.groups.lock().unwrap().insert(name.to_string(), stored);
+        }
+
+        fn snapshot(&self, name: &str) -> Option<AuditValues> {
+            self.groups.lock().unwrap().get(name).map(|stored| AuditValues {
+                key_count: Some(stored.keys.len() as i64),
+                relays_count: Some(stored.relays.len() as i64),
+                ..AuditValues::from(&stored.group)
+            })
+        }
+
+        /// Groups passing the `name` filter (the only one the mock applies), by name
+        fn matching(&self, filters: &GroupFilters) -> Vec<VouchProposerGroup> {
+            let mut matching: Vec<VouchProposerGroup> = self
+                .groups
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|s| filters.name.as_ref().is_none_or(|name| s.group.name.starts_with(name.as_str())))
+                .map(|s| s.group.clone())
+                .collect();
+            matching.sort_by(|a, b| a.name.cmp(&b.name));
+            matching
+        }
+    }
+
+    impl GroupRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.groups.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self, filters: &GroupFilters) -> Result<i64, ApiError> {
+            Ok(self.matching(filters).len() as i64)
+        }
+
+        async fn list(&self, filters: &GroupFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposerGroup>, ApiError> {
+            let matching = self.matching(filters).into_iter();
+            Ok(matching.skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn key_counts(&self, names: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+            let groups = self.groups.lock().unwrap();
+            Ok(names
+                .iter()
+                .filter_map(|name| groups.get(name))
+                .filter(|stored| !stored.keys.is_empty())
+                .map(|stored| (stored.group.name.clone(), stored.keys.len() as i64))
+                .collect())
+        }
+
+        async fn find(&self, name: &str) -> Result<Option<StoredGroup>, ApiError> {
+            Ok(self.groups.lock().unwrap().get(name).cloned())
+        }
+
+        async fn insert(&self, req: &CreateProposerGroupRequest) -> Result<Option<AuditValues>, ApiError> {
+            if self.exists(&req.name).await? {
+                return Ok(None);
+            }
+            self.add(&req.name, &[]);
+            if let Some(stored) = self.groups.lock().unwrap().get_mut(&req.name) {
+                stored.group.pattern = req.pattern.clone();
+                stored.group.priority = req.priority;
+                stored.keys = req.keys.clone();
+            }
+            Ok(self.snapshot(&req.name))
+        }
+
+        async fn update(
+            &self,
+            name: &str,
+            req: &UpdateProposerGroupRequest,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            if let Some(stored) = self.groups.lock().unwrap().get_mut(name) {
+                if let Some(priority) = req.priority {
+                    stored.group.priority = priority;
+                }
+                if let Some(keys) = &req.keys {
+                    stored.keys = keys.clone();
+                }
+            }
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+            let before = self.snapshot(name);
+            self.groups.lock().unwrap().remove(name);
+            Ok(before)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_delete_group() {
+        let repo = MockRepository::default();
+        let req: CreateProposerGroupRequest = serde_json::from_value(serde_json::json!({
+            "name": "alpha",
+            "keys": [TEST_KEY],
+            "priority": 5,
+        }))
+        .unwrap();
+
+        let (response, changes) = create(&repo, &req).await.unwrap();
+        assert_eq!(response.keys.len(), 1);
+        assert!(response.relays.is_none());
+        assert_eq!(changes.key_count.unwrap().to, Some(1));
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::Conflict(_))));
+
+        let update_req: UpdateProposerGroupRequest = serde_json::from_str(r#"{"priority": 9, "keys": []}"#).unwrap();
+        let (response, changes) = update(&repo, "alpha", &update_req).await.unwrap();
+        assert_eq!(response.priority, 9);
+        assert!(response.keys.is_empty());
+        assert_eq!(changes.key_count.unwrap().to, Some(0));
+        assert!(matches!(update(&repo, "other", &update_req).await, Err(ApiError::NotFound(_))));
+
+        delete(&repo, "alpha").await.unwrap();
+        assert!(matches!(ensure_exists(&repo, "alpha").await, Err(ApiError::NotFound(_))));
+        assert!(matches!(delete(&repo, "alpha").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_patterns_are_refused_before_storage() {
+        let repo = MockRepository::default();
+        let req: CreateProposerGroupRequest = serde_json::from_value(serde_json::json!({
+            "name": "alpha",
+            "pattern": "^0x8(",
+        }))
+        .unwrap();
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::InvalidData(_))));
+        assert!(!repo.exists("alpha").await.unwrap());
+
+        repo.add("alpha", &[]);
+        let update_req: UpdateProposerGroupRequest = serde_json::from_str(r#"{"pattern": "[a-"}"#).unwrap();
+        assert!(matches!(update(&repo, "alpha", &update_req).await, Err(ApiError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_groups_with_key_counts() {
+        let repo = MockRepository::default();
+        repo.add("team_b", &[TEST_KEY]);
+        repo.add("team_a", &[]);
+        repo.add("other", &[TEST_KEY]);
+        let filters = GroupFilters {
+            name: Some("team_".to_string()),
+            ..Default::default()
+        };
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 10,
+            offset: 0,
+        };
+
+        let response = list(&repo, &filters, page).await.unwrap();
+        let counts: Vec<(&str, i64)> = response.data.iter().map(|g| (g.name.as_str(), g.key_count)).collect();
+        assert_eq!(counts, [("team_a", 0), ("team_b", 1)]);
+        assert_eq!(response.total, 2);
+    }
+}
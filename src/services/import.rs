@@ -0,0 +1,574 @@
+// services/import.rs - Parsing and writing of Vouch static execution config files
+use crate::addresses::BlsPubkey;
+use crate::errors::ApiError;
+use crate::schema::{
+    BuilderConfigV1, CreateDefaultConfigRequest, CreateOrUpdateProposerRequest, CreateProposerPatternRequest,
+    ExecutionConfigResponse, ExecutionConfigV1Response, ImportAction, ImportExecutionConfigResponse,
+    ImportedResource, ProposerConfigV1, ProposerEntry, ProposerRelayConfig, RelayConfig,
+};
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Everything a file turns into, as the matching create requests
+#[derive(Debug)]
+pub struct ImportPlan {
+    pub version: u8,
+    pub default_config: CreateDefaultConfigRequest,
+    pub proposers: Vec<(BlsPubkey, CreateOrUpdateProposerRequest)>,
+    pub patterns: Vec<CreateProposerPatternRequest>,
+}
+
+/// What writing a plan created or updated
+#[derive(Debug)]
+pub struct ImportedResources {
+    pub default_config: ImportedResource,
+    pub proposers: Vec<ImportedResource>,
+    pub patterns: Vec<ImportedResource>,
+}
+
+/// Storage an import writes to
+pub trait ImportRepository: Sync {
+    /// Upsert everything in the plan in one transaction, rolled back after
+    /// every write when `dry_run` is set
+    fn write(
+        &self,
+        plan: &ImportPlan,
+        dry_run: bool,
+    ) -> impl Future<Output = Result<ImportedResources, ApiError>> + Send;
+}
+
+/// Write a checked import plan
+pub async fn write(
+    repo: &impl ImportRepository,
+    plan: &ImportPlan,
+    dry_run: bool,
+) -> Result<ImportExecutionConfigResponse, ApiError> {
+    let written = repo.write(plan, dry_run).await?;
+    Ok(ImportExecutionConfigResponse {
+        dry_run,
+        version: plan.version,
+        default_config: written.default_config,
+        proposers: written.proposers,
+        patterns: written.patterns,
+    })
+}
+
+/// Parse a v1 or v2 file into the resources it describes
+pub fn parse(name: &str, file: serde_json::Value) -> Result<ImportPlan, ApiError> {
+    let invalid = |e: serde_json::Error| ApiError::InvalidData(format!("Invalid execution config: {}", e));
+
+    if file.get("default_config").is_some() {
+        let v1: ExecutionConfigV1Response = serde_json::from_value(file).map_err(invalid)?;
+        return plan_v1(name, v1);
+    }
+    match file.get("version").and_then(|v| v.as_u64()) {
+        Some(2) => {
+            let v2: ExecutionConfigResponse = serde_json::from_value(file).map_err(invalid)?;
+            Ok(plan_v2(name, v2))
+        }
+        _ => Err(ApiError::InvalidData(
+            "Unsupported execution config: expected `version: 2` or a v1 file with `default_config`".to_string(),
+        )),
+    }
+}
+
+fn plan_v2(name: &str, config: ExecutionConfigResponse) -> ImportPlan {
+    let mut proposers = Vec::new();
+    let mut patterns = Vec::new();
+    for entry in config.proposers.unwrap_or_default() {
+        match entry.proposer.parse::<BlsPubkey>() {
+            Ok(public_key) => proposers.push((public_key, proposer_request(entry))),
+            // Anything else is an account regex, kept as a pattern tagged with the config name
+            Err(_) => {
+                let index = patterns.len() + 1;
+                patterns.push(pattern_request(name, index, entry));
+            }
+        }
+    }
+
+    ImportPlan {
+        version: 2,
+        default_config: CreateDefaultConfigRequest {
+            name: name.to_string(),
+            fee_recipient: config.fee_recipient,
+            gas_limit: config.gas_limit,
+            min_value: config.min_value,
+            builder_enabled: config.builder_enabled,
+            builder_boost_factor: config.builder_boost_factor,
+            grace: config.grace,
+            active: true,
+            parent: None,
+            relay_sets: Vec::new(),
+            relays: config.relays,
+        },
+        proposers,
+        patterns,
+    }
+}
+
+fn plan_v1(name: &str, config: ExecutionConfigV1Response) -> Result<ImportPlan, ApiError> {
+    let default = config.default_config;
+    let builder = builder_v1(default.builder.as_ref())?;
+
+    let mut proposers = Vec::new();
+    for (key, proposer) in config.proposer_config {
+        let public_key: BlsPubkey = key
+            .parse()
+            .map_err(|_| ApiError::InvalidData(format!("Invalid proposer public key '{}'", key)))?;
+        proposers.push((public_key, proposer_request_v1(proposer)?));
+    }
+    proposers.sort_by_key(|(public_key, _)| public_key.to_string());
+
+    Ok(ImportPlan {
+        version: 1,
+        default_config: CreateDefaultConfigRequest {
+            name: name.to_string(),
+            fee_recipient: default.fee_recipient,
+            gas_limit: default.gas_limit,
+            min_value: None,
+            builder_enabled: builder.enabled,
+            builder_boost_factor: None,
+            grace: builder.grace,
+            active: true,
+            parent: None,
+            relay_sets: Vec::new(),
+            relays: (!builder.relays.is_empty()).then_some(builder.relays),
+        },
+        proposers,
+        patterns: Vec::new(),
+    })
+}
+
+/// Builder settings of a v1 config
+#[derive(Default)]
+struct BuilderV1 {
+    enabled: Option<bool>,
+    grace: Option<String>,
+    relays: HashMap<String, RelayConfig>,
+}
+
+/// v1 builder settings; relay public keys come from the URL's user part
+fn builder_v1(builder: Option<&BuilderConfigV1>) -> Result<BuilderV1, ApiError> {
+    let Some(builder) = builder else {
+        return Ok(BuilderV1::default());
+    };
+
+    let mut relays = HashMap::new();
+    for relay_url in &builder.relays {
+        let mut url = url::Url::parse(relay_url)
+            .map_err(|e| ApiError::InvalidData(format!("Invalid relay URL '{}': {}", relay_url, e)))?;
+        let public_key: BlsPubkey = url.username().parse().map_err(|_| {
+            ApiError::InvalidData(format!(
+                "v1 relay URL '{}' must carry the relay public key as user (https://0x...@host)",
+                relay_url
+            ))
+        })?;
+        let _ = url.set_username("");
+        // Url adds a root path; keep the URL as the file wrote it
+        let url = match url.path() {
+            "/" if !relay_url.ends_with('/') => url.as_str().trim_end_matches('/').to_string(),
+            _ => url.to_string(),
+        };
+        relays.insert(
+            url,
+            RelayConfig {
+                public_key,
+                fee_recipient: None,
+                gas_limit: None,
+                min_value: None,
+                disabled: false,
+            },
+        );
+    }
+
+    Ok(BuilderV1 {
+        enabled: Some(builder.enabled),
+        grace: builder.grace.clone(),
+        relays,
+    })
+}
+
+fn proposer_relays(relays: Option<HashMap<String, RelayConfig>>) -> Option<HashMap<String, ProposerRelayConfig>> {
+    relays.map(|relays| {
+        relays
+            .into_iter()
+            .map(|(url, r)| {
+                (
+                    url,
+                    ProposerRelayConfig {
+                        public_key: r.public_key,
+                        fee_recipient: r.fee_recipient,
+                        gas_limit: r.gas_limit,
+                        min_value: r.min_value,
+                        disabled: r.disabled,
+                    },
+                )
+            })
+            .collect()
+    })
+}
+
+fn proposer_request(entry: ProposerEntry) -> CreateOrUpdateProposerRequest {
+    CreateOrUpdateProposerRequest {
+        fee_recipient: entry.fee_recipient,
+        gas_limit: entry.gas_limit,
+        min_value: entry.min_value,
+        builder_enabled: entry.builder_enabled,
+        builder_boost_factor: entry.builder_boost_factor,
+        reset_relays: entry.reset_relays.unwrap_or(false),
+        note: None,
+        labels: Default::default(),
+        relay_sets: Vec::new(),
+        relays: proposer_relays(entry.relays),
+    }
+}
+
+fn proposer_request_v1(proposer: ProposerConfigV1) -> Result<CreateOrUpdateProposerRequest, ApiError> {
+    let has_builder = proposer.builder.is_some();
+    let builder = builder_v1(proposer.builder.as_ref())?;
+    Ok(CreateOrUpdateProposerRequest {
+        fee_recipient: proposer.fee_recipient,
+        gas_limit: proposer.gas_limit,
+        min_value: None,
+        builder_enabled: builder.enabled,
+        builder_boost_factor: None,
+        // A v1 builder block lists every relay of the proposer
+        reset_relays: has_builder,
+        note: None,
+        labels: Default::default(),
+        relay_sets: Vec::new(),
+        relays: proposer_relays((!builder.relays.is_empty()).then_some(builder.relays)),
+    })
+}
+
+fn pattern_request(name: &str, index: usize, entry: ProposerEntry) -> CreateProposerPatternRequest {
+    CreateProposerPatternRequest {
+        name: format!("{}-{}", name, index),
+        pattern: entry.proposer,
+        tags: vec![name.to_string()],
+        active: true,
+        fee_recipient: entry.fee_recipient,
+        gas_limit: entry.gas_limit,
+        min_value: entry.min_value,
+        builder_enabled: entry.builder_enabled,
+        builder_boost_factor: entry.builder_boost_factor,
+        reset_relays: entry.reset_relays.unwrap_or(false),
+        note: None,
+        labels: Default::default(),
+        relay_sets: Vec::new(),
+        relays: proposer_relays(entry.relays),
+    }
+}
+
+fn action(inserted: bool) -> ImportAction {
+    if inserted {
+        ImportAction::Created
+    } else {
+        ImportAction::Updated
+    }
+}
+
+/// Imported values replace the stored ones; parent, active and relay sets of an existing config stay
+async fn upsert_default_config(
+    conn: &mut PgConnection,
+    req: &CreateDefaultConfigRequest,
+) -> Result<ImportedResource, ApiError> {
+    let inserted: bool = sqlx::query_scalar!(
+        r#"INSERT INTO vouch_default_configs (name, fee_recipient, gas_limit, min_value, active, grace, builder_enabled, builder_boost_factor)
+           VALUES ($1, $2, $3, $4, TRUE, $5, $6, $7)
+           ON CONFLICT (name) DO UPDATE
+           SET fee_recipient = EXCLUDED.fee_recipient, gas_limit = EXCLUDED.gas_limit,
+               min_value = EXCLUDED.min_value, grace = EXCLUDED.grace,
+               builder_enabled = EXCLUDED.builder_enabled, builder_boost_factor = EXCLUDED.builder_boost_factor
+           RETURNING (xmax = 0) AS "inserted!""#,
+        req.name,
+        req.fee_recipient as _,
+        req.gas_limit,
+        req.min_value,
+        req.grace,
+        req.builder_enabled,
+        req.builder_boost_factor
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM vouch_default_relays WHERE config_name = $1", req.name)
+        .execute(&mut *conn)
+        .await?;
+    let relays = req.relays.clone().unwrap_or_default();
+    for (url, relay) in &relays {
+        sqlx::query!(
+            "INSERT INTO vouch_default_relays (config_name, url, public_key, fee_recipient, gas_limit, min_value)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            req.name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(ImportedResource {
+        id: req.name.clone(),
+        action: action(inserted),
+        relays: relays.len(),
+    })
+}
+
+/// Imported values replace the stored ones; note, labels and relay sets of an existing proposer stay
+async fn upsert_proposer(
+    conn: &mut PgConnection,
+    public_key: &BlsPubkey,
+    req: &CreateOrUpdateProposerRequest,
+) -> Result<ImportedResource, ApiError> {
+    let public_key = public_key.to_string();
+    let inserted: bool = sqlx::query_scalar!(
+        r#"INSERT INTO vouch_proposers
+               (public_key, fee_recipient, gas_limit, min_value, reset_relays, builder_enabled, builder_boost_factor)
+           VALUES ($1, $2, $3, $4, $5, $6, $7)
+           ON CONFLICT (public_key) DO UPDATE
+           SET fee_recipient = EXCLUDED.fee_recipient, gas_limit = EXCLUDED.gas_limit,
+               min_value = EXCLUDED.min_value, reset_relays = EXCLUDED.reset_relays,
+               builder_enabled = EXCLUDED.builder_enabled, builder_boost_factor = EXCLUDED.builder_boost_factor
+           RETURNING (xmax = 0) AS "inserted!""#,
+        public_key,
+        req.fee_recipient as _,
+        req.gas_limit,
+        req.min_value,
+        req.reset_relays,
+        req.builder_enabled,
+        req.builder_boost_factor
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM vouch_proposer_relays WHERE proposer_public_key = $1", public_key)
+        .execute(&mut *conn)
+        .await?;
+    let relays = req.relays.clone().unwrap_or_default();
+    for (url, relay) in &relays {
+        sqlx::query!(
+            "INSERT INTO vouch_proposer_relays
+             (proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            public_key,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value,
+            relay.disabled
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(ImportedResource {
+        id: public_key,
+        action: action(inserted),
+        relays: relays.len(),
+    })
+}
+
+/// Imported values replace the stored ones; note, labels and relay sets of an existing pattern stay
+async fn upsert_pattern(
+    conn: &mut PgConnection,
+    req: &CreateProposerPatternRequest,
+) -> Result<ImportedResource, ApiError> {
+    let inserted: bool = sqlx::query_scalar!(
+        r#"INSERT INTO vouch_proposer_patterns
+               (name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, builder_enabled, builder_boost_factor)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           ON CONFLICT (name) DO UPDATE
+           SET pattern = EXCLUDED.pattern, tags = EXCLUDED.tags,
+               fee_recipient = EXCLUDED.fee_recipient, gas_limit = EXCLUDED.gas_limit,
+               min_value = EXCLUDED.min_value, reset_relays = EXCLUDED.reset_relays,
+               builder_enabled = EXCLUDED.builder_enabled, builder_boost_factor = EXCLUDED.builder_boost_factor
+           RETURNING (xmax = 0) AS "inserted!""#,
+        req.name,
+        req.pattern,
+        &req.tags,
+        req.fee_recipient as _,
+        req.gas_limit,
+        req.min_value,
+        req.reset_relays,
+        req.builder_enabled,
+        req.builder_boost_factor
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!("DELETE FROM vouch_proposer_pattern_relays WHERE pattern_name = $1", req.name)
+        .execute(&mut *conn)
+        .await?;
+    let relays = req.relays.clone().unwrap_or_default();
+    for (url, relay) in &relays {
+        sqlx::query!(
+            "INSERT INTO vouch_proposer_pattern_relays
+             (pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            req.name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value,
+            relay.disabled
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(ImportedResource {
+        id: req.name.clone(),
+        action: action(inserted),
+        relays: relays.len(),
+    })
+}
+
+impl ImportRepository for PgPool {
+    async fn write(&self, plan: &ImportPlan, dry_run: bool) -> Result<ImportedResources, ApiError> {
+        let mut tx = self.begin().await?;
+
+        let default_config = upsert_default_config(&mut tx, &plan.default_config).await?;
+        let mut proposers = Vec::with_capacity(plan.proposers.len());
+        for (public_key, proposer) in &plan.proposers {
+            proposers.push(upsert_proposer(&mut tx, public_key, proposer).await?);
+        }
+        let mut patterns = Vec::with_capacity(plan.patterns.len());
+        for pattern in &plan.patterns {
+            patterns.push(upsert_pattern(&mut tx, pattern).await?);
+        }
+
+        // A dry run goes through every write so constraint errors show up, then rolls back
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+
+        Ok(ImportedResources {
+            default_config,
+            proposers,
+            patterns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use serde_json::json;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// Ids of the stored resources, kept only by writes that are not dry runs
+    #[derive(Default)]
+    struct MockRepository {
+        stored: Mutex<HashSet<String>>,
+    }
+
+    impl MockRepository {
+        fn upsert(&self, stored: &mut HashSet<String>, id: String, relays: usize) -> ImportedResource {
+            let inserted = !self.stored.lock().unwrap().contains(&id) && stored.insert(id.clone());
+            ImportedResource {
+                id,
+                action: action(inserted),
+                relays,
+            }
+        }
+    }
+
+    impl ImportRepository for MockRepository {
+        async fn write(&self, plan: &ImportPlan, dry_run: bool) -> Result<ImportedResources, ApiError> {
+            let mut stored = HashSet::new();
+            let config = &plan.default_config;
+            let default_config = self.upsert(&mut stored, config.name.clone(), config.relays.iter().flatten().count());
+            let proposers = plan
+                .proposers
+                .iter()
+                .map(|(key, p)| self.upsert(&mut stored, key.to_string(), p.relays.iter().flatten().count()))
+                .collect();
+            let patterns = plan
+                .patterns
+                .iter()
+                .map(|p| self.upsert(&mut stored, p.name.clone(), p.relays.iter().flatten().count()))
+                .collect();
+            if !dry_run {
+                self.stored.lock().unwrap().extend(stored);
+            }
+            Ok(ImportedResources {
+                default_config,
+                proposers,
+                patterns,
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_v2_splits_keys_and_patterns() {
+        let file = json!({
+            "version": 2,
+            "fee_recipient": "0x0000000000000000000000000000000000000001",
+            "relays": { "https://relay.example.com": { "public_key": TEST_KEY } },
+            "proposers": [
+                { "proposer": TEST_KEY, "gas_limit": "30000000" },
+                { "proposer": "^0x8.*$", "reset_relays": true }
+            ]
+        });
+
+        let plan = parse("main", file).unwrap();
+        assert_eq!(plan.version, 2);
+        assert_eq!(plan.default_config.relays.unwrap().len(), 1);
+        assert_eq!(plan.proposers.len(), 1);
+        assert_eq!(plan.proposers[0].1.gas_limit.as_deref(), Some("30000000"));
+        assert_eq!(plan.patterns[0].name, "main-1");
+        assert_eq!(plan.patterns[0].tags, ["main"]);
+        assert!(plan.patterns[0].reset_relays);
+    }
+
+    #[test]
+    fn test_parse_v1_takes_relay_keys_from_urls() {
+        let relay = format!("https://{}@relay.example.com", TEST_KEY);
+        let file = json!({
+            "default_config": { "builder": { "enabled": true, "relays": [relay] } },
+            "proposer_config": { TEST_KEY: { "builder": { "enabled": false, "relays": [] } } }
+        });
+
+        let plan = parse("main", file).unwrap();
+        assert_eq!(plan.version, 1);
+        let relays = plan.default_config.relays.unwrap();
+        assert!(relays.contains_key("https://relay.example.com"));
+        let (_, proposer) = &plan.proposers[0];
+        assert_eq!(proposer.builder_enabled, Some(false));
+        assert!(proposer.reset_relays);
+
+        let keyless = json!({
+            "default_config": { "builder": { "enabled": true, "relays": ["https://relay.example.com"] } },
+            "proposer_config": {}
+        });
+        assert!(matches!(parse("main", keyless), Err(ApiError::InvalidData(_))));
+        assert!(matches!(parse("main", json!({ "version": 3 })), Err(ApiError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_write_reports_created_then_updated() {
+        let repo = MockRepository::default();
+        let file = json!({ "version": 2, "proposers": [{ "proposer": TEST_KEY }] });
+
+        let dry_run = write(&repo, &parse("main", file.clone()).unwrap(), true).await.unwrap();
+        assert!(dry_run.dry_run);
+        assert_eq!(dry_run.default_config.action, ImportAction::Created);
+
+        let first = write(&repo, &parse("main", file.clone()).unwrap(), false).await.unwrap();
+        assert_eq!(first.proposers[0].action, ImportAction::Created);
+        let second = write(&repo, &parse("main", file).unwrap(), false).await.unwrap();
+        assert_eq!(second.default_config.action, ImportAction::Updated);
+        assert_eq!(second.proposers[0].action, ImportAction::Updated);
+    }
+}
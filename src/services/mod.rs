@@ -0,0 +1,55 @@
+// services/mod.rs - Storage-independent operations shared by the API surfaces
+//
+// Each module pairs a repository trait, implemented for `PgPool`, with the
+// operations built on it: not-found and conflict handling, validation and the
+// assembly of API responses. Handlers stay adapters that extract the request,
+// call a service and write the audit log, so other surfaces can reuse the same
+// operations and unit tests can run them against an in-memory repository.
+
+pub mod configs;
+pub mod groups;
+pub mod import;
+pub mod mux;
+pub mod patterns;
+pub mod pbs;
+pub mod proposers;
+pub mod relay_sets;
+pub mod tokens;
+
+use crate::audit::AuditValues;
+use crate::handlers::sort::SortOrder;
+use std::collections::HashMap;
+
+/// Sorting and paging of a list request
+#[derive(Debug, Clone, Copy)]
+pub struct PageRequest<'a> {
+    pub sort: Option<&'a str>,
+    pub order: Option<SortOrder>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Outcome of removing one relay from a resource
+#[derive(Debug)]
+pub enum RelayRemoval {
+    /// The resource does not exist
+    NotFound,
+    /// The resource has no relay with that URL
+    RelayNotFound,
+    /// Removed, with the audited values of the resource before and after
+    Removed(Box<(AuditValues, AuditValues)>),
+}
+
+/// `$1, $2, ..` for binding `count` values to an IN list
+fn placeholders(count: usize) -> String {
+    (1..=count).map(|i| format!("${}", i)).collect::<Vec<_>>().join(", ")
+}
+
+/// Relays keyed by URL, `None` when there are none (the API omits empty relay maps)
+fn relay_map<R, C: From<R>>(relays: Vec<R>, url: impl Fn(&R) -> String) -> Option<HashMap<String, C>> {
+    let relays: HashMap<String, C> = relays.into_iter().map(|r| (url(&r), C::from(r))).collect();
+    (!relays.is_empty()).then_some(relays)
+}
+
+#[cfg(test)]
+const TEST_KEY: &str = "0x8021cef5a095e4a4476d5a56d0e63e7ddb5e66ba5349fe1bc341f94f0f07a0dbfd19755b9820022e6b9153e28fce90e8";
@@ -0,0 +1,1366 @@
+// services/mux.rs - Mux config lookups, listing and key writes
+use super::{placeholders, PageRequest};
+use crate::addresses::BlsPubkey;
+use crate::audit::{AuditChanges, AuditValues, Change, ResourceType};
+use crate::errors::ApiError;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::models::CommitBoostMuxConfig;
+use crate::schema::{
+    CreateMuxConfigRequest, DeletionPreview, DependentReference, DuplicateMuxKey, MuxConfigListItem, MuxConfigResponse, MuxKeysDiffResponse, MuxKeysResponse,
+    MuxKeysTransferRequest, MuxKeysTransferResponse, PaginatedResponse,
+};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "name"),
+        ("created_at", "created_at"),
+        ("updated_at", "updated_at"),
+    ],
+    default: ("name", SortOrder::Asc),
+    tiebreak: "name",
+};
+
+/// Outcome of deleting a mux config
+#[derive(Debug)]
+pub enum MuxDeletion {
+    NotFound,
+    /// PBS configs take keys from it, nothing was deleted
+    InUse(Vec<String>),
+    /// Deleted, with its audited values
+    Deleted(Box<AuditValues>),
+}
+
+/// What depends on a mux config
+#[derive(Debug, Default)]
+pub struct MuxDependents {
+    /// Rows deleted along with the mux config
+    pub keys: i64,
+    pub access_tokens: i64,
+    /// PBS configs taking keys from it, which block its delete
+    pub pbs_configs: Vec<String>,
+}
+
+/// Storage of mux configs and their keys
+pub trait MuxRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    fn count(&self, time_range: &TimeRangeFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    fn list(
+        &self,
+        time_range: &TimeRangeFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<CommitBoostMuxConfig>, ApiError>> + Send;
+
+    /// Key count of each of the given mux configs
+    fn key_counts(&self, names: &[String]) -> impl Future<Output = Result<HashMap<String, i64>, ApiError>> + Send;
+
+    fn find(&self, name: &str) -> impl Future<Output = Result<Option<CommitBoostMuxConfig>, ApiError>> + Send;
+
+    /// One page of the keys in insertion order (all of them without `limit`),
+    /// and the total key count
+    fn keys(
+        &self,
+        name: &str,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> impl Future<Output = Result<(Vec<BlsPubkey>, i64), ApiError>> + Send;
+
+    /// Create the mux config with its keys, `None` if the name is taken
+    fn insert(
+        &self,
+        req: &CreateMuxConfigRequest,
+        strict_unique_keys: bool,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Replace every key of the mux config, returning its audited values
+    /// before and after (`None` if it does not exist)
+    fn replace_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Add the keys the mux config lacks, returning how many were added and
+    /// its audited values before and after
+    fn add_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> impl Future<Output = Result<Option<(i64, AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Remove the keys, returning how many were removed and the audited values
+    /// before and after
+    fn remove_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+    ) -> impl Future<Output = Result<Option<(i64, AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Copy the keys (all of the source's without `keys`) from `source` to
+    /// `target`, removing them from the source when `remove_from_source` is
+    /// set. Returns the outcome and the source and target key counts before.
+    fn transfer(
+        &self,
+        source: &str,
+        target: &str,
+        keys: Option<Vec<BlsPubkey>>,
+        remove_from_source: bool,
+        strict_unique_keys: bool,
+    ) -> impl Future<Output = Result<(MuxKeysTransferResponse, i64, i64), ApiError>> + Send;
+
+    /// Reconcile the keys to `desired`, returning the diff and the audited
+    /// values before (`None` if the mux config does not exist)
+    fn sync_keys(
+        &self,
+        name: &str,
+        desired: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> impl Future<Output = Result<Option<(MuxKeysDiffResponse, AuditValues)>, ApiError>> + Send;
+
+    /// Delete the mux config unless a PBS config uses it
+    fn delete(&self, name: &str) -> impl Future<Output = Result<MuxDeletion, ApiError>> + Send;
+
+    /// `None` if the mux config does not exist
+    fn dependents(&self, name: &str) -> impl Future<Output = Result<Option<MuxDependents>, ApiError>> + Send;
+
+    /// Number of keys that belong to more than one mux config
+    fn duplicate_count(&self) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One page of the keys in more than one mux config, in key order
+    fn duplicates(&self, limit: i64, offset: i64) -> impl Future<Output = Result<Vec<DuplicateMuxKey>, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Mux config '{}' not found", name))
+}
+
+pub async fn ensure_exists(repo: &impl MuxRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// The mux config with one page of its keys
+pub async fn get(repo: &impl MuxRepository, name: &str, limit: Option<i64>, offset: i64) -> Result<MuxConfigResponse, ApiError> {
+    let config = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    let (keys, total_keys) = repo.keys(name, limit, offset).await?;
+    Ok(MuxConfigResponse {
+        name: config.name,
+        id: config.id,
+        keys,
+        total_keys,
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+    })
+}
+
+/// One page of the mux configs, with their key counts
+pub async fn list(
+    repo: &impl MuxRepository,
+    time_range: &TimeRangeFilters,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<MuxConfigListItem>, ApiError> {
+    let configs = repo.list(time_range, page).await?;
+    let total = repo.count(time_range).await?;
+
+    let names: Vec<String> = configs.iter().map(|c| c.name.clone()).collect();
+    let counts = if names.is_empty() {
+        HashMap::new()
+    } else {
+        repo.key_counts(&names).await?
+    };
+    let data = configs
+        .into_iter()
+        .map(|config| MuxConfigListItem {
+            key_count: counts.get(&config.name).copied().unwrap_or(0),
+            name: config.name,
+            id: config.id,
+            created_at: config.created_at,
+            updated_at: config.updated_at,
+        })
+        .collect();
+
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a mux config with its keys, returning it and the changes to audit
+pub async fn create(
+    repo: &impl MuxRepository,
+    req: &CreateMuxConfigRequest,
+    strict_unique_keys: bool,
+) -> Result<(MuxConfigListItem, AuditChanges), ApiError> {
+    let after = repo
+        .insert(req, strict_unique_keys)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Mux config '{}' already exists", req.name)))?;
+    let config = repo.find(&req.name).await?.ok_or_else(|| not_found(&req.name))?;
+
+    let response = MuxConfigListItem {
+        name: config.name,
+        id: config.id,
+        key_count: after.key_count.unwrap_or_default(),
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+    };
+    Ok((response, AuditChanges::diff(&AuditValues::default(), &after)))
+}
+
+/// Replace every key of a mux config, returning it and the changes to audit
+pub async fn update(
+    repo: &impl MuxRepository,
+    name: &str,
+    keys: &[BlsPubkey],
+    strict_unique_keys: bool,
+) -> Result<(MuxConfigResponse, AuditChanges), ApiError> {
+    let (before, after) = repo
+        .replace_keys(name, keys, strict_unique_keys)
+        .await?
+        .ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name, None, 0).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Add keys to a mux config, skipping those it already has
+pub async fn add_keys(
+    repo: &impl MuxRepository,
+    name: &str,
+    keys: &[BlsPubkey],
+    strict_unique_keys: bool,
+) -> Result<(MuxKeysResponse, AuditChanges), ApiError> {
+    let (added, before, after) = repo
+        .add_keys(name, keys, strict_unique_keys)
+        .await?
+        .ok_or_else(|| not_found(name))?;
+    let response = MuxKeysResponse {
+        added: Some(added),
+        removed: None,
+        total_keys: after.key_count.unwrap_or_default(),
+    };
+    Ok((response, AuditChanges::diff(&before, &after)))
+}
+
+/// Remove keys from a mux config, ignoring those it does not have
+pub async fn remove_keys(
+    repo: &impl MuxRepository,
+    name: &str,
+    keys: &[BlsPubkey],
+) -> Result<(MuxKeysResponse, AuditChanges), ApiError> {
+    let (removed, before, after) = repo.remove_keys(name, keys).await?.ok_or_else(|| not_found(name))?;
+    let response = MuxKeysResponse {
+        added: None,
+        removed: Some(removed),
+        total_keys: after.key_count.unwrap_or_default(),
+    };
+    Ok((response, AuditChanges::diff(&before, &after)))
+}
+
+/// Copy keys from `source` to `req.target`, removing them from the source
+/// when `remove_from_source` is set
+pub async fn transfer(
+    repo: &impl MuxRepository,
+    source: &str,
+    req: MuxKeysTransferRequest,
+    remove_from_source: bool,
+    strict_unique_keys: bool,
+) -> Result<(MuxKeysTransferResponse, AuditChanges), ApiError> {
+    if source == req.target {
+        return Err(ApiError::InvalidData(
+            "Source and target mux config must differ".to_string(),
+        ));
+    }
+
+    let (response, source_keys_before, target_keys_before) = repo
+        .transfer(source, &req.target, req.keys, remove_from_source, strict_unique_keys)
+        .await?;
+    let changes = AuditChanges {
+        key_count: Change::between(&Some(source_keys_before), &Some(response.source_total_keys)),
+        target_key_count: Change::between(&Some(target_keys_before), &Some(response.target_total_keys)),
+        target: Some(response.target.clone()),
+        ..Default::default()
+    };
+    Ok((response, changes))
+}
+
+/// How the keys of a mux config differ from `desired`, reconciling them with
+/// `apply`. The changes to audit are only returned when keys were written.
+pub async fn diff(
+    repo: &impl MuxRepository,
+    name: &str,
+    desired: &[BlsPubkey],
+    apply: bool,
+    strict_unique_keys: bool,
+) -> Result<(MuxKeysDiffResponse, Option<AuditChanges>), ApiError> {
+    if !apply {
+        ensure_exists(repo, name).await?;
+        let (current, _) = repo.keys(name, None, 0).await?;
+        return Ok((key_diff(&current, desired), None));
+    }
+
+    let (response, before) = repo
+        .sync_keys(name, desired, strict_unique_keys)
+        .await?
+        .ok_or_else(|| not_found(name))?;
+    // A sync that finds nothing to do is not a change worth auditing
+    let changed = !response.to_add.is_empty() || !response.to_remove.is_empty();
+    let changes = changed.then(|| {
+        let after = AuditValues {
+            key_count: Some(response.total_keys),
+            ..Default::default()
+        };
+        AuditChanges::diff(&before, &after)
+    });
+    Ok((response, changes))
+}
+
+/// Keys to add, remove and keep to turn `current` into `desired` (not applied)
+fn key_diff(current: &[BlsPubkey], desired: &[BlsPubkey]) -> MuxKeysDiffResponse {
+    let current_set: HashSet<&BlsPubkey> = current.iter().collect();
+    let mut desired_set: HashSet<&BlsPubkey> = HashSet::new();
+    let mut to_add = Vec::new();
+    let mut unchanged = Vec::new();
+    for key in desired {
+        if !desired_set.insert(key) {
+            continue;
+        }
+        if current_set.contains(key) {
+            unchanged.push(key.clone());
+        } else {
+            to_add.push(key.clone());
+        }
+    }
+    let to_remove: Vec<BlsPubkey> = current
+        .iter()
+        .filter(|key| !desired_set.contains(key))
+        .cloned()
+        .collect();
+
+    MuxKeysDiffResponse {
+        to_add,
+        to_remove,
+        unchanged,
+        applied: false,
+        total_keys: current.len() as i64,
+    }
+}
+
+/// Delete a mux config with its keys, returning the changes to audit
+pub async fn delete(repo: &impl MuxRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    match repo.delete(name).await? {
+        MuxDeletion::NotFound => Err(not_found(name)),
+        MuxDeletion::InUse(pbs_configs) => Err(ApiError::Conflict(format!(
+            "Mux config '{}' is used by PBS config(s): {}",
+            name,
+            pbs_configs.join(", ")
+        ))),
+        MuxDeletion::Deleted(before) => Ok(AuditChanges::diff(&before, &AuditValues::default())),
+    }
+}
+
+pub(crate) async fn count_mux_keys(conn: &mut PgConnection, name: &str) -> Result<i64, ApiError> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM commit_boost_mux_keys WHERE mux_name = $1"#,
+        name
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(count)
+}
+
+/// One page of the keys of an existing mux config and the total key count
+pub async fn key_page(
+    repo: &impl MuxRepository,
+    name: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<BlsPubkey>, i64), ApiError> {
+    ensure_exists(repo, name).await?;
+    repo.keys(name, Some(limit), offset).await
+}
+
+/// Every key of a mux config and the salt (its id) of their membership digests
+pub async fn digest_keys(repo: &impl MuxRepository, name: &str) -> Result<(Vec<u8>, Vec<BlsPubkey>), ApiError> {
+    let config = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    let (keys, _) = repo.keys(name, None, 0).await?;
+    Ok((config.id.as_bytes().to_vec(), keys))
+}
+
+/// What deleting a mux config removes, and the PBS configs that block it like in [`delete`]
+pub async fn dependents(repo: &impl MuxRepository, name: &str) -> Result<DeletionPreview, ApiError> {
+    let dependents = repo.dependents(name).await?.ok_or_else(|| not_found(name))?;
+    let blocked_by = dependents
+        .pbs_configs
+        .into_iter()
+        .map(|config| DependentReference {
+            resource_type: ResourceType::CommitBoostPbsConfig.as_str().to_string(),
+            name: config,
+        })
+        .collect();
+    Ok(DeletionPreview::new(
+        name.to_string(),
+        &[("keys", dependents.keys), ("access_tokens", dependents.access_tokens)],
+        blocked_by,
+    ))
+}
+
+/// One page of the keys that belong to more than one mux config
+pub async fn duplicates(
+    repo: &impl MuxRepository,
+    limit: i64,
+    offset: i64,
+) -> Result<PaginatedResponse<DuplicateMuxKey>, ApiError> {
+    let data = repo.duplicates(limit, offset).await?;
+    let total = repo.duplicate_count().await?;
+    Ok(PaginatedResponse::new(data, total, limit, offset))
+}
+
+/// Audited key count of a mux config, locking the config row
+pub(crate) async fn audit_snapshot(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let found: Option<String> =
+        sqlx::query_scalar!("SELECT name FROM commit_boost_mux_configs WHERE name = $1 FOR UPDATE", name)
+            .fetch_optional(&mut *conn)
+            .await?;
+    if found.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(AuditValues {
+        key_count: Some(count_mux_keys(conn, name).await?),
+        ..Default::default()
+    }))
+}
+
+/// PBS configs whose muxes take keys from the given mux config
+async fn pbs_configs_using_mux(
+    conn: &mut PgConnection,
+    mux_name: &str,
+) -> Result<Vec<String>, ApiError> {
+    Ok(sqlx::query_scalar!(
+        "SELECT DISTINCT config_name FROM commit_boost_pbs_muxes WHERE mux_name = $1 ORDER BY config_name",
+        mux_name
+    )
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+/// SQL WHERE clause over `commit_boost_mux_configs m` (empty when no filter is set)
+fn where_clause(time_range: &TimeRangeFilters) -> String {
+    let conditions = time_range.conditions("m");
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+/// With `strict_unique_keys`, fail if any of `keys` already belongs to a mux
+/// config other than those in `allowed`
+async fn ensure_keys_unique(
+    conn: &mut PgConnection,
+    strict_unique_keys: bool,
+    keys: &[BlsPubkey],
+    allowed: &[&str],
+) -> Result<(), ApiError> {
+    if !strict_unique_keys || keys.is_empty() {
+        return Ok(());
+    }
+
+    // Serialize strict checks so two requests can't add the same key to different muxes
+    sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('commit_boost_mux_keys'))")
+        .execute(&mut *conn)
+        .await?;
+
+    let conflicts = sqlx::query!(
+        r#"SELECT public_key as "public_key: BlsPubkey", mux_name FROM commit_boost_mux_keys
+           WHERE public_key = ANY($1) AND NOT (mux_name = ANY($2))
+           ORDER BY public_key, mux_name"#,
+        keys as _,
+        allowed as _
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    let listed: Vec<String> = conflicts
+        .iter()
+        .take(10)
+        .map(|row| format!("{} (in '{}')", row.public_key, row.mux_name))
+        .collect();
+    Err(ApiError::Conflict(format!(
+        "{} key(s) already belong to another mux config: {}{}",
+        conflicts.len(),
+        listed.join(", "),
+        if conflicts.len() > listed.len() { ", ..." } else { "" }
+    )))
+}
+
+/// Every key of a mux config in insertion order
+async fn all_keys(conn: &mut PgConnection, name: &str) -> Result<Vec<BlsPubkey>, ApiError> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT public_key as "public_key: BlsPubkey" FROM commit_boost_mux_keys WHERE mux_name = $1 ORDER BY id"#,
+        name
+    )
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+impl MuxRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM commit_boost_mux_configs WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, time_range: &TimeRangeFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM commit_boost_mux_configs m {}", where_clause(time_range));
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, time_range: &TimeRangeFilters, page: PageRequest<'_>) -> Result<Vec<CommitBoostMuxConfig>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT name, id, created_at, updated_at
+             FROM commit_boost_mux_configs m
+             {}
+             {}
+             LIMIT $1 OFFSET $2",
+            where_clause(time_range), order_by
+        );
+        Ok(sqlx::query_as::<_, CommitBoostMuxConfig>(&data_sql)
+            .bind(page.limit)
+            .bind(page.offset)
+            .fetch_all(self)
+            .await?)
+    }
+
+    async fn key_counts(&self, names: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+        let counts_sql = format!(
+            "SELECT mux_name, COUNT(*) FROM commit_boost_mux_keys WHERE mux_name IN ({}) GROUP BY mux_name",
+            placeholders(names.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&counts_sql);
+        for name in names {
+            query = query.bind(name);
+        }
+        Ok(query.fetch_all(self).await?.into_iter().collect())
+    }
+
+    async fn find(&self, name: &str) -> Result<Option<CommitBoostMuxConfig>, ApiError> {
+        Ok(sqlx::query_as!(
+            CommitBoostMuxConfig,
+            "SELECT name, id, created_at, updated_at FROM commit_boost_mux_configs WHERE name = $1",
+            name
+        )
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn keys(&self, name: &str, limit: Option<i64>, offset: i64) -> Result<(Vec<BlsPubkey>, i64), ApiError> {
+        let total = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM commit_boost_mux_keys WHERE mux_name = $1"#,
+            name
+        )
+        .fetch_one(self)
+        .await?;
+
+        let keys = sqlx::query_scalar!(
+            r#"SELECT public_key as "public_key: BlsPubkey" FROM commit_boost_mux_keys WHERE mux_name = $1
+               ORDER BY id LIMIT $2 OFFSET $3"#,
+            name,
+            limit,
+            offset
+        )
+        .fetch_all(self)
+        .await?;
+
+        Ok((keys, total))
+    }
+
+    async fn insert(&self, req: &CreateMuxConfigRequest, strict_unique_keys: bool) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        ensure_keys_unique(&mut tx, strict_unique_keys, &req.keys, &[req.name.as_str()]).await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO commit_boost_mux_configs (name) VALUES ($1) ON CONFLICT (name) DO NOTHING",
+            req.name
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The unique name constraint decides races between concurrent creates
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        for key in &req.keys {
+            sqlx::query!(
+                "INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)",
+                req.name,
+                key as _
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let after = audit_snapshot(&mut tx, &req.name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some(after))
+    }
+
+    async fn replace_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        ensure_keys_unique(&mut tx, strict_unique_keys, keys, &[name]).await?;
+
+        sqlx::query!("DELETE FROM commit_boost_mux_keys WHERE mux_name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        for key in keys {
+            sqlx::query!("INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)", name, key as _)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn add_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> Result<Option<(i64, AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        ensure_keys_unique(&mut tx, strict_unique_keys, keys, &[name]).await?;
+
+        let mut added = 0i64;
+        for key in keys {
+            // Use ON CONFLICT to avoid duplicates
+            let result = sqlx::query!(
+                "INSERT INTO commit_boost_mux_keys (mux_name, public_key) VALUES ($1, $2)
+                 ON CONFLICT (mux_name, public_key) DO NOTHING",
+                name,
+                key as _
+            )
+            .execute(&mut *tx)
+            .await?;
+            added += result.rows_affected() as i64;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((added, before, after)))
+    }
+
+    async fn remove_keys(
+        &self,
+        name: &str,
+        keys: &[BlsPubkey],
+    ) -> Result<Option<(i64, AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        let removed = sqlx::query!(
+            "DELETE FROM commit_boost_mux_keys WHERE mux_name = $1 AND public_key = ANY($2)",
+            name,
+            keys as _
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() as i64;
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((removed, before, after)))
+    }
+
+    async fn transfer(
+        &self,
+        source: &str,
+        target: &str,
+        keys: Option<Vec<BlsPubkey>>,
+        remove_from_source: bool,
+        strict_unique_keys: bool,
+    ) -> Result<(MuxKeysTransferResponse, i64, i64), ApiError> {
+        let mut tx = self.begin().await?;
+
+        // Lock both configs in a fixed order so concurrent transfers can't deadlock
+        let found: Vec<String> = sqlx::query_scalar!(
+            "SELECT name FROM commit_boost_mux_configs WHERE name = ANY($1) ORDER BY name FOR UPDATE",
+            [source, target] as _
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        for name in [source, target] {
+            if !found.iter().any(|f| f == name) {
+                return Err(not_found(name));
+            }
+        }
+        let source_keys_before = count_mux_keys(&mut tx, source).await?;
+        let target_keys_before = count_mux_keys(&mut tx, target).await?;
+
+        let (keys, not_found) = match keys {
+            Some(requested) => {
+                let present: Vec<BlsPubkey> = sqlx::query_scalar!(
+                    r#"SELECT public_key as "public_key: BlsPubkey" FROM commit_boost_mux_keys
+                       WHERE mux_name = $1 AND public_key = ANY($2) ORDER BY id"#,
+                    source,
+                    requested as _
+                )
+                .fetch_all(&mut *tx)
+                .await?;
+                let mut not_found: Vec<BlsPubkey> = Vec::new();
+                for key in requested {
+                    if !present.contains(&key) && !not_found.contains(&key) {
+                        not_found.push(key);
+                    }
+                }
+                (present, not_found)
+            }
+            None => (all_keys(&mut tx, source).await?, Vec::new()),
+        };
+
+        // A copy leaves the keys in the source, so in strict mode only a move passes
+        let allowed = if remove_from_source {
+            vec![source, target]
+        } else {
+            vec![target]
+        };
+        ensure_keys_unique(&mut tx, strict_unique_keys, &keys, &allowed).await?;
+
+        // Keep the source order in the target
+        let added: Vec<BlsPubkey> = sqlx::query_scalar!(
+            r#"INSERT INTO commit_boost_mux_keys (mux_name, public_key)
+               SELECT $1, k FROM UNNEST($2::text[]) WITH ORDINALITY AS t(k, i) ORDER BY i
+               ON CONFLICT (mux_name, public_key) DO NOTHING
+               RETURNING public_key as "public_key: BlsPubkey""#,
+            target,
+            &keys as _
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let already_present: Vec<BlsPubkey> =
+            keys.iter().filter(|k| !added.contains(k)).cloned().collect();
+
+        let mut removed = 0i64;
+        if remove_from_source {
+            removed = sqlx::query!(
+                "DELETE FROM commit_boost_mux_keys WHERE mux_name = $1 AND public_key = ANY($2)",
+                source,
+                &keys as _
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i64;
+        }
+
+        let source_total_keys = count_mux_keys(&mut tx, source).await?;
+        let target_total_keys = count_mux_keys(&mut tx, target).await?;
+
+        tx.commit().await?;
+
+        let response = MuxKeysTransferResponse {
+            source: source.to_string(),
+            target: target.to_string(),
+            added,
+            already_present,
+            not_found,
+            removed,
+            source_total_keys,
+            target_total_keys,
+        };
+        Ok((response, source_keys_before, target_keys_before))
+    }
+
+    async fn sync_keys(
+        &self,
+        name: &str,
+        desired: &[BlsPubkey],
+        strict_unique_keys: bool,
+    ) -> Result<Option<(MuxKeysDiffResponse, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        let mut diff = key_diff(&all_keys(&mut tx, name).await?, desired);
+        diff.applied = true;
+        if !diff.to_add.is_empty() || !diff.to_remove.is_empty() {
+            ensure_keys_unique(&mut tx, strict_unique_keys, &diff.to_add, &[name]).await?;
+
+            sqlx::query!(
+                "DELETE FROM commit_boost_mux_keys WHERE mux_name = $1 AND public_key = ANY($2)",
+                name,
+                &diff.to_remove as _
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!(
+                "INSERT INTO commit_boost_mux_keys (mux_name, public_key)
+                 SELECT $1, k FROM UNNEST($2::text[]) WITH ORDINALITY AS t(k, i) ORDER BY i",
+                name,
+                &diff.to_add as _
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            diff.total_keys = count_mux_keys(&mut tx, name).await?;
+        }
+        tx.commit().await?;
+        Ok(Some((diff, before)))
+    }
+
+    async fn delete(&self, name: &str) -> Result<MuxDeletion, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(MuxDeletion::NotFound);
+        };
+
+        let pbs_configs = pbs_configs_using_mux(&mut tx, name).await?;
+        if !pbs_configs.is_empty() {
+            return Ok(MuxDeletion::InUse(pbs_configs));
+        }
+
+        sqlx::query!("DELETE FROM commit_boost_mux_configs WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(MuxDeletion::Deleted(Box::new(before)))
+    }
+
+    async fn dependents(&self, name: &str) -> Result<Option<MuxDependents>, ApiError> {
+        let mut conn = self.acquire().await?;
+        let counts = sqlx::query!(
+            r#"SELECT (SELECT COUNT(*) FROM commit_boost_mux_keys k WHERE k.mux_name = m.name) AS "keys!",
+                      (SELECT COUNT(*) FROM public_access_tokens t
+                       WHERE t.resource_type = 'commit_boost_mux' AND t.resource_name = m.name) AS "access_tokens!"
+               FROM commit_boost_mux_configs m WHERE m.name = $1"#,
+            name
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+        let Some(counts) = counts else {
+            return Ok(None);
+        };
+        Ok(Some(MuxDependents {
+            keys: counts.keys,
+            access_tokens: counts.access_tokens,
+            pbs_configs: pbs_configs_using_mux(&mut conn, name).await?,
+        }))
+    }
+
+    async fn duplicate_count(&self) -> Result<i64, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM (
+                   SELECT public_key FROM commit_boost_mux_keys
+                   GROUP BY public_key HAVING COUNT(*) > 1
+               ) d"#
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn duplicates(&self, limit: i64, offset: i64) -> Result<Vec<DuplicateMuxKey>, ApiError> {
+        Ok(sqlx::query_as!(
+            DuplicateMuxKey,
+            r#"SELECT public_key as "public_key: BlsPubkey", array_agg(mux_name ORDER BY mux_name) AS "muxes!"
+               FROM commit_boost_mux_keys
+               GROUP BY public_key HAVING COUNT(*) > 1
+               ORDER BY public_key
+               LIMIT $1 OFFSET $2"#,
+            limit,
+            offset
+        )
+        .fetch_all(self)
+        .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory mux configs with their keys, and the PBS configs using each
+    #[derive(Default)]
+    struct MockRepository {
+        muxes: Mutex<HashMap<String, (CommitBoostMuxConfig, Vec<BlsPubkey>)>>,
+        used_by: HashMap<String, Vec<String>>,
+    }
+
+    /// A distinct key per `i`
+    fn key(i: u8) -> BlsPubkey {
+        format!("0x{:096x}", i).parse().unwrap()
+    }
+
+    fn keys(range: std::ops::Range<u8>) -> Vec<BlsPubkey> {
+        range.map(key).collect()
+    }
+
+    fn key_count(count: usize) -> AuditValues {
+        AuditValues {
+            key_count: Some(count as i64),
+            ..Default::default()
+        }
+    }
+
+    impl MockRepository {
+        fn add(&self, name: &str, keys: Vec<BlsPubkey>) {
+            let config = CommitBoostMuxConfig {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            self.muxes.lock().unwrap().insert(name.to_string(), (config, keys));
+        }
+
+        fn key_list(&self, name: &str) -> Vec<BlsPubkey> {
+            self.muxes.lock().unwrap().get(name).map(|(_, keys)| keys.clone()).unwrap_or_default()
+        }
+
+        /// The strict uniqueness check: keys of `keys` held by muxes outside `allowed`
+        fn check_unique(&self, strict_unique_keys: bool, keys: &[BlsPubkey], allowed: &[&str]) -> Result<(), ApiError> {
+            let muxes = self.muxes.lock().unwrap();
+            let conflict = muxes
+                .iter()
+                .filter(|(name, _)| !allowed.contains(&name.as_str()))
+                .any(|(_, (_, held))| keys.iter().any(|key| held.contains(key)));
+            if strict_unique_keys && conflict {
+                return Err(ApiError::Conflict("key(s) already belong to another mux config".to_string()));
+            }
+            Ok(())
+        }
+
+        /// Apply `change` to the keys of `name`, with its key counts before and after
+        fn change_keys(
+            &self,
+            name: &str,
+            change: impl FnOnce(&mut Vec<BlsPubkey>),
+        ) -> Option<(AuditValues, AuditValues)> {
+            let mut muxes = self.muxes.lock().unwrap();
+            let (_, keys) = muxes.get_mut(name)?;
+            let before = key_count(keys.len());
+            change(keys);
+            Some((before, key_count(keys.len())))
+        }
+    }
+
+    impl MuxRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.muxes.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self, _time_range: &TimeRangeFilters) -> Result<i64, ApiError> {
+            Ok(self.muxes.lock().unwrap().len() as i64)
+        }
+
+        async fn list(&self, _time_range: &TimeRangeFilters, page: PageRequest<'_>) -> Result<Vec<CommitBoostMuxConfig>, ApiError> {
+            let mut configs: Vec<CommitBoostMuxConfig> =
+                self.muxes.lock().unwrap().values().map(|(config, _)| config.clone()).collect();
+            configs.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(configs.into_iter().skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn key_counts(&self, names: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+            let muxes = self.muxes.lock().unwrap();
+            Ok(names
+                .iter()
+                .filter_map(|name| muxes.get(name).map(|(_, keys)| (name.clone(), keys.len() as i64)))
+                .filter(|(_, count)| *count > 0)
+                .collect())
+        }
+
+        async fn find(&self, name: &str) -> Result<Option<CommitBoostMuxConfig>, ApiError> {
+            Ok(self.muxes.lock().unwrap().get(name).map(|(config, _)| config.clone()))
+        }
+
+        async fn keys(&self, name: &str, limit: Option<i64>, offset: i64) -> Result<(Vec<BlsPubkey>, i64), ApiError> {
+            let keys = self.key_list(name);
+            let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+            let page = keys.iter().skip(offset as usize).take(limit).cloned().collect();
+            Ok((page, keys.len() as i64))
+        }
+
+        async fn insert(&self, req: &CreateMuxConfigRequest, strict_unique_keys: bool) -> Result<Option<AuditValues>, ApiError> {
+            self.check_unique(strict_unique_keys, &req.keys, &[req.name.as_str()])?;
+            if self.exists(&req.name).await? {
+                return Ok(None);
+            }
+            self.add(&req.name, req.keys.clone());
+            Ok(Some(key_count(req.keys.len())))
+        }
+
+        async fn replace_keys(
+            &self,
+            name: &str,
+            keys: &[BlsPubkey],
+            strict_unique_keys: bool,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            self.check_unique(strict_unique_keys, keys, &[name])?;
+            Ok(self.change_keys(name, |current| *current = keys.to_vec()))
+        }
+
+        async fn add_keys(
+            &self,
+            name: &str,
+            keys: &[BlsPubkey],
+            strict_unique_keys: bool,
+        ) -> Result<Option<(i64, AuditValues, AuditValues)>, ApiError> {
+            self.check_unique(strict_unique_keys, keys, &[name])?;
+            let mut added = 0;
+            let counts = self.change_keys(name, |current| {
+                for key in keys {
+                    if !current.contains(key) {
+                        current.push(key.clone());
+                        added += 1;
+                    }
+                }
+            });
+            Ok(counts.map(|(before, after)| (added, before, after)))
+        }
+
+        async fn remove_keys(
+            &self,
+            name: &str,
+            keys: &[BlsPubkey],
+        ) -> Result<Option<(i64, AuditValues, AuditValues)>, ApiError> {
+            let mut removed = 0;
+            let counts = self.change_keys(name, |current| {
+                let count = current.len();
+                current.retain(|key| !keys.contains(key));
+                removed = (count - current.len()) as i64;
+            });
+            Ok(counts.map(|(before, after)| (removed, before, after)))
+        }
+
+        async fn transfer(
+            &self,
+            source: &str,
+            target: &str,
+            keys: Option<Vec<BlsPubkey>>,
+            remove_from_source: bool,
+            strict_unique_keys: bool,
+        ) -> Result<(MuxKeysTransferResponse, i64, i64), ApiError> {
+            for name in [source, target] {
+                ensure_exists(self, name).await?;
+            }
+            let source_keys = self.key_list(source);
+            let (source_keys_before, target_keys_before) =
+                (source_keys.len() as i64, self.key_list(target).len() as i64);
+
+            let (keys, not_found) = match keys {
+                Some(requested) => requested.into_iter().partition(|key| source_keys.contains(key)),
+                None => (source_keys, Vec::new()),
+            };
+            let allowed = if remove_from_source { vec![source, target] } else { vec![target] };
+            self.check_unique(strict_unique_keys, &keys, &allowed)?;
+
+            let mut added = Vec::new();
+            self.change_keys(target, |current| {
+                for key in &keys {
+                    if !current.contains(key) {
+                        current.push(key.clone());
+                        added.push(key.clone());
+                    }
+                }
+            });
+            let mut removed = 0;
+            if remove_from_source {
+                removed = self.remove_keys(source, &keys).await?.map_or(0, |(removed, _, _)| removed);
+            }
+
+            let response = MuxKeysTransferResponse {
+                source: source.to_string(),
+                target: target.to_string(),
+                already_present: keys.iter().filter(|k| !added.contains(k)).cloned().collect(),
+                added,
+                not_found,
+                removed,
+                source_total_keys: self.key_list(source).len() as i64,
+                target_total_keys: self.key_list(target).len() as i64,
+            };
+            Ok((response, source_keys_before, target_keys_before))
+        }
+
+        async fn sync_keys(
+            &self,
+            name: &str,
+            desired: &[BlsPubkey],
+            strict_unique_keys: bool,
+        ) -> Result<Option<(MuxKeysDiffResponse, AuditValues)>, ApiError> {
+            if !self.exists(name).await? {
+                return Ok(None);
+            }
+            let mut diff = key_diff(&self.key_list(name), desired);
+            self.check_unique(strict_unique_keys, &diff.to_add, &[name])?;
+            let (before, after) = self
+                .change_keys(name, |current| {
+                    current.retain(|key| !diff.to_remove.contains(key));
+                    current.extend(diff.to_add.iter().cloned());
+                })
+                .unwrap();
+            diff.applied = true;
+            diff.total_keys = after.key_count.unwrap();
+            Ok(Some((diff, before)))
+        }
+
+        async fn delete(&self, name: &str) -> Result<MuxDeletion, ApiError> {
+            let mut muxes = self.muxes.lock().unwrap();
+            let Some((_, keys)) = muxes.get(name) else {
+                return Ok(MuxDeletion::NotFound);
+            };
+            if let Some(pbs_configs) = self.used_by.get(name) {
+                return Ok(MuxDeletion::InUse(pbs_configs.clone()));
+            }
+            let before = key_count(keys.len());
+            muxes.remove(name);
+            Ok(MuxDeletion::Deleted(Box::new(before)))
+        }
+
+        async fn dependents(&self, name: &str) -> Result<Option<MuxDependents>, ApiError> {
+            Ok(self.muxes.lock().unwrap().get(name).map(|(_, keys)| MuxDependents {
+                keys: keys.len() as i64,
+                access_tokens: 0,
+                pbs_configs: self.used_by.get(name).cloned().unwrap_or_default(),
+            }))
+        }
+
+        async fn duplicate_count(&self) -> Result<i64, ApiError> {
+            Ok(self.duplicates(i64::MAX, 0).await?.len() as i64)
+        }
+
+        async fn duplicates(&self, limit: i64, offset: i64) -> Result<Vec<DuplicateMuxKey>, ApiError> {
+            let mut holders: HashMap<BlsPubkey, Vec<String>> = HashMap::new();
+            for (name, (_, keys)) in self.muxes.lock().unwrap().iter() {
+                for key in keys {
+                    holders.entry(key.clone()).or_default().push(name.clone());
+                }
+            }
+            let mut duplicates: Vec<DuplicateMuxKey> = holders
+                .into_iter()
+                .filter(|(_, muxes)| muxes.len() > 1)
+                .map(|(public_key, mut muxes)| {
+                    muxes.sort();
+                    DuplicateMuxKey { public_key, muxes }
+                })
+                .collect();
+            duplicates.sort_by_key(|d| d.public_key.0);
+            Ok(duplicates.into_iter().skip(offset as usize).take(limit as usize).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_pages_keys() {
+        let repo = MockRepository::default();
+        repo.add("lido", keys(0..3));
+
+        let response = get(&repo, "lido", Some(2), 0).await.unwrap();
+        assert_eq!((response.keys.len(), response.total_keys), (2, 3));
+        let response = get(&repo, "lido", Some(2), 2).await.unwrap();
+        assert_eq!((response.keys.len(), response.total_keys), (1, 3));
+        assert!(matches!(get(&repo, "other", Some(2), 0).await, Err(ApiError::NotFound(_))));
+
+        // No limit returns every key
+        let response = get(&repo, "lido", None, 0).await.unwrap();
+        assert_eq!((response.keys.len(), response.total_keys), (3, 3));
+    }
+
+    #[tokio::test]
+    async fn test_list_counts_keys() {
+        let repo = MockRepository::default();
+        repo.add("b", keys(0..2));
+        repo.add("a", Vec::new());
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 10,
+            offset: 0,
+        };
+
+        let response = list(&repo, &TimeRangeFilters::default(), page).await.unwrap();
+        let counts: Vec<(&str, i64)> = response.data.iter().map(|m| (m.name.as_str(), m.key_count)).collect();
+        assert_eq!(counts, [("a", 0), ("b", 2)]);
+        assert_eq!(response.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_key_writes() {
+        let repo = MockRepository::default();
+        let req = CreateMuxConfigRequest {
+            name: "lido".to_string(),
+            keys: keys(0..2),
+        };
+
+        let (response, changes) = create(&repo, &req, false).await.unwrap();
+        assert_eq!(response.key_count, 2);
+        assert_eq!(changes.key_count.unwrap().to, Some(2));
+        assert!(matches!(create(&repo, &req, false).await, Err(ApiError::Conflict(_))));
+
+        let (response, _) = add_keys(&repo, "lido", &keys(1..4), false).await.unwrap();
+        assert_eq!((response.added, response.total_keys), (Some(2), 4));
+
+        let (response, changes) = remove_keys(&repo, "lido", &keys(3..5)).await.unwrap();
+        assert_eq!((response.removed, response.total_keys), (Some(1), 3));
+        assert_eq!(changes.key_count.unwrap().from, Some(4));
+
+        let (response, _) = update(&repo, "lido", &keys(7..8), false).await.unwrap();
+        assert_eq!(response.keys, keys(7..8));
+        assert!(matches!(add_keys(&repo, "other", &keys(0..1), false).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_strict_unique_keys() {
+        let repo = MockRepository::default();
+        repo.add("lido", keys(0..2));
+        repo.add("spare", Vec::new());
+
+        assert!(add_keys(&repo, "spare", &keys(1..3), false).await.is_ok());
+        let result = add_keys(&repo, "spare", &keys(0..1), true).await;
+        assert!(matches!(result, Err(ApiError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_keys() {
+        let repo = MockRepository::default();
+        repo.add("lido", keys(0..3));
+        repo.add("spare", keys(2..3));
+
+        let req = MuxKeysTransferRequest {
+            target: "lido".to_string(),
+            keys: None,
+        };
+        assert!(matches!(transfer(&repo, "lido", req, false, false).await, Err(ApiError::InvalidData(_))));
+
+        let req = MuxKeysTransferRequest {
+            target: "spare".to_string(),
+            keys: Some(keys(1..4)),
+        };
+        let (response, changes) = transfer(&repo, "lido", req, true, false).await.unwrap();
+        assert_eq!(response.added, keys(1..2));
+        assert_eq!(response.already_present, keys(2..3));
+        assert_eq!(response.not_found, keys(3..4));
+        assert_eq!((response.removed, response.source_total_keys, response.target_total_keys), (2, 1, 2));
+        assert_eq!(changes.key_count.unwrap().from, Some(3));
+        assert_eq!(changes.target.as_deref(), Some("spare"));
+
+        let req = MuxKeysTransferRequest {
+            target: "other".to_string(),
+            keys: None,
+        };
+        assert!(matches!(transfer(&repo, "lido", req, false, false).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_diff_previews_or_applies() {
+        let repo = MockRepository::default();
+        repo.add("lido", keys(0..3));
+        let desired = [key(2), key(5), key(1), key(5)];
+
+        let (response, changes) = diff(&repo, "lido", &desired, false, false).await.unwrap();
+        assert_eq!(response.to_add, keys(5..6));
+        assert_eq!(response.to_remove, keys(0..1));
+        assert_eq!(response.unchanged, [key(2), key(1)]);
+        assert!(!response.applied && changes.is_none());
+        assert_eq!(repo.key_list("lido").len(), 3);
+
+        let (response, changes) = diff(&repo, "lido", &desired, true, false).await.unwrap();
+        assert!(response.applied);
+        assert_eq!(response.total_keys, 3);
+        assert!(changes.is_some());
+
+        // Nothing left to do is not audited
+        let (_, changes) = diff(&repo, "lido", &desired, true, false).await.unwrap();
+        assert!(changes.is_none());
+        assert!(matches!(diff(&repo, "other", &desired, false, false).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_mux_in_use_conflicts() {
+        let repo = MockRepository {
+            used_by: HashMap::from([("lido".to_string(), vec!["mainnet".to_string()])]),
+            ..Default::default()
+        };
+        repo.add("lido", keys(0..1));
+        repo.add("spare", keys(0..2));
+
+        let result = delete(&repo, "lido").await;
+        assert!(matches!(result, Err(ApiError::Conflict(message)) if message.ends_with("mainnet")));
+        assert!(repo.exists("lido").await.unwrap());
+
+        let changes = delete(&repo, "spare").await.unwrap();
+        assert_eq!(changes.key_count.unwrap().from, Some(2));
+        assert!(matches!(ensure_exists(&repo, "spare").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_key_page_and_digest_keys() {
+        let repo = MockRepository::default();
+        repo.add("lido", keys(0..3));
+
+        assert_eq!(key_page(&repo, "lido", 2, 1).await.unwrap(), (keys(1..3), 3));
+        assert!(matches!(key_page(&repo, "other", 2, 0).await, Err(ApiError::NotFound(_))));
+
+        let (salt, all) = digest_keys(&repo, "lido").await.unwrap();
+        let id = repo.find("lido").await.unwrap().unwrap().id;
+        assert_eq!(salt, id.as_bytes());
+        assert_eq!(all, keys(0..3));
+        assert!(matches!(digest_keys(&repo, "other").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dependents_and_duplicates() {
+        let repo = MockRepository {
+            used_by: HashMap::from([("lido".to_string(), vec!["mainnet".to_string()])]),
+            ..Default::default()
+        };
+        repo.add("lido", keys(0..3));
+        repo.add("spare", keys(1..4));
+        repo.add("third", keys(2..3));
+
+        let preview = dependents(&repo, "lido").await.unwrap();
+        assert_eq!(preview.removes[0].count, 3);
+        assert_eq!(preview.blocked_by[0].name, "mainnet");
+        assert!(dependents(&repo, "spare").await.unwrap().blocked_by.is_empty());
+        assert!(matches!(dependents(&repo, "other").await, Err(ApiError::NotFound(_))));
+
+        let page = duplicates(&repo, 1, 1).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.data[0].public_key, key(2));
+        assert_eq!(page.data[0].muxes, ["lido", "spare", "third"]);
+    }
+}
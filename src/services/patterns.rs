@@ -0,0 +1,1376 @@
+// services/patterns.rs - Proposer pattern lookups, listing and writes
+use super::{relay_map, PageRequest, RelayRemoval};
+use super::relay_sets::ensure_relay_sets_exist;
+use crate::addresses::normalize_hex;
+use crate::audit::{AuditChanges, AuditValues, Change};
+use crate::errors::ApiError;
+use crate::handlers::labels::labels_condition;
+use crate::handlers::partial_update::PartialUpdate;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::models::{Labels, VouchProposerPattern, VouchProposerPatternRelay};
+use crate::pattern_matches;
+use crate::schema::{
+    CreateProposerPatternRequest, DeletionPreview, PaginatedResponse, PatternMatchCount, PatternMatchStatistics,
+    PatternMatchesResponse, ProposerPatternListItem, ProposerPatternResponse, ProposerRelayConfig,
+    RenameTagResponse, TagUsage, UpdateProposerPatternRequest,
+};
+use crate::units::{normalize_min_value_filter, MinValues, ValueUnits};
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Filters of the proposer pattern list
+#[derive(Debug, Default)]
+pub struct PatternFilters {
+    /// Name prefix
+    pub name: Option<String>,
+    /// Substring of the pattern
+    pub pattern: Option<String>,
+    pub tag: Option<String>,
+    pub exclude_tag: Option<String>,
+    pub fee_recipient: Option<String>,
+    pub fee_recipient_ne: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub reset_relays: Option<bool>,
+    pub active: Option<bool>,
+    /// Relay URL prefix
+    pub relay_url: Option<String>,
+    /// Relay min_value (exact match)
+    pub relay_min_value: Option<String>,
+    pub relay_disabled: Option<bool>,
+    pub labels: Labels,
+    pub time_range: TimeRangeFilters,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "p.name"),
+        ("created_at", "p.created_at"),
+        ("updated_at", "p.updated_at"),
+    ],
+    default: ("p.name", SortOrder::Asc),
+    tiebreak: "p.name",
+};
+
+/// Tags of one pattern before and after a rename
+#[derive(Debug)]
+pub struct RenamedTags {
+    pub name: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// Storage of proposer patterns and their relays
+pub trait PatternRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    /// Number of patterns matching the filters
+    fn count(&self, filters: &PatternFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the patterns matching the filters
+    fn list(
+        &self,
+        filters: &PatternFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<VouchProposerPattern>, ApiError>> + Send;
+
+    /// The pattern with its relays
+    fn find(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<(VouchProposerPattern, Vec<VouchProposerPatternRelay>)>, ApiError>> + Send;
+
+    /// Insert the pattern with its relays, returning its audited values; `None`
+    /// when a pattern of that name exists
+    fn insert(
+        &self,
+        req: &CreateProposerPatternRequest,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Apply the fields the request sets, returning the audited values before and after
+    fn update(
+        &self,
+        name: &str,
+        req: &UpdateProposerPatternRequest,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Add or replace one relay, leaving the others alone; returns the audited
+    /// values before and after
+    fn put_relay(
+        &self,
+        name: &str,
+        url: &str,
+        relay: &ProposerRelayConfig,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    fn delete_relay(&self, name: &str, url: &str) -> impl Future<Output = Result<RelayRemoval, ApiError>> + Send;
+
+    /// Delete the pattern, returning its audited values
+    fn delete(&self, name: &str) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Number of relays of the pattern, `None` if it does not exist
+    fn relay_count(&self, name: &str) -> impl Future<Output = Result<Option<i64>, ApiError>> + Send;
+
+    /// Every pattern sorted by name, and every pattern relay
+    fn all(
+        &self,
+    ) -> impl Future<Output = Result<(Vec<VouchProposerPattern>, Vec<VouchProposerPatternRelay>), ApiError>> + Send;
+
+    /// Recompute the stored proposer matches if they are stale, returning
+    /// when the current ones were computed
+    fn refresh_matches(&self) -> impl Future<Output = Result<DateTime<Utc>, ApiError>> + Send;
+
+    fn proposer_count(&self) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// Stored proposer matches of the pattern: their number and the first
+    /// `sample` keys in key order
+    fn stored_matches(
+        &self,
+        name: &str,
+        sample: i64,
+    ) -> impl Future<Output = Result<(i64, Vec<String>), ApiError>> + Send;
+
+    /// Stored proposer matches of every pattern, sorted by name
+    fn match_counts(&self) -> impl Future<Output = Result<Vec<PatternMatchCount>, ApiError>> + Send;
+
+    /// Number of proposers no active pattern matches
+    fn unmatched_count(&self) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// Every key of a mux config in key order, `None` if it does not exist
+    fn mux_keys(&self, mux_name: &str) -> impl Future<Output = Result<Option<Vec<String>>, ApiError>> + Send;
+
+    /// Every tag with the number of patterns carrying it, sorted by tag
+    fn tags(&self) -> impl Future<Output = Result<Vec<TagUsage>, ApiError>> + Send;
+
+    /// Replace tag `from` with `to` on every pattern carrying it, all or none
+    fn rename_tag(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> impl Future<Output = Result<Vec<RenamedTags>, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Proposer pattern '{}' not found", name))
+}
+
+pub async fn ensure_exists(repo: &impl PatternRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// The pattern with its relays, min values in wei
+pub async fn get(repo: &impl PatternRepository, name: &str) -> Result<ProposerPatternResponse, ApiError> {
+    let (pattern, relays) = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(ProposerPatternResponse {
+        name: pattern.name,
+        id: pattern.id,
+        pattern: pattern.pattern,
+        tags: pattern.tags,
+        fee_recipient: pattern.fee_recipient,
+        gas_limit: pattern.gas_limit,
+        min_value: pattern.min_value,
+        builder_enabled: pattern.builder_enabled,
+        builder_boost_factor: pattern.builder_boost_factor,
+        reset_relays: pattern.reset_relays,
+        active: pattern.active,
+        note: pattern.note,
+        labels: pattern.labels.0,
+        relay_sets: pattern.relay_sets,
+        relays: relay_map(relays, |r| r.url.clone()),
+        created_at: pattern.created_at,
+        updated_at: pattern.updated_at,
+    })
+}
+
+/// One page of the patterns matching the filters, min values in wei
+pub async fn list(
+    repo: &impl PatternRepository,
+    filters: &PatternFilters,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<ProposerPatternListItem>, ApiError> {
+    let patterns = repo.list(filters, page).await?;
+    let total = repo.count(filters).await?;
+    let data = patterns.into_iter().map(Into::into).collect();
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a pattern with its relays, returning it and the changes to audit
+pub async fn create(
+    repo: &impl PatternRepository,
+    req: &CreateProposerPatternRequest,
+) -> Result<(ProposerPatternResponse, AuditChanges), ApiError> {
+    let after = repo
+        .insert(req)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Pattern '{}' already exists", req.name)))?;
+    let changes = AuditChanges::diff(&AuditValues::default(), &after);
+    Ok((get(repo, &req.name).await?, changes))
+}
+
+/// Update the fields of a pattern the request sets, returning it and the changes to audit
+pub async fn update(
+    repo: &impl PatternRepository,
+    name: &str,
+    req: &UpdateProposerPatternRequest,
+) -> Result<(ProposerPatternResponse, AuditChanges), ApiError> {
+    let (before, after) = repo.update(name, req).await?.ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Add or replace one relay of a pattern, returning the pattern and the changes to audit
+pub async fn put_relay(
+    repo: &impl PatternRepository,
+    name: &str,
+    url: &str,
+    relay: &ProposerRelayConfig,
+) -> Result<(ProposerPatternResponse, AuditChanges), ApiError> {
+    let (before, after) = repo.put_relay(name, url, relay).await?.ok_or_else(|| not_found(name))?;
+    let changes = AuditChanges {
+        relay_url: Some(url.to_string()),
+        ..AuditChanges::diff(&before, &after)
+    };
+    Ok((get(repo, name).await?, changes))
+}
+
+/// Remove one relay of a pattern, returning the changes to audit
+pub async fn delete_relay(repo: &impl PatternRepository, name: &str, url: &str) -> Result<AuditChanges, ApiError> {
+    match repo.delete_relay(name, url).await? {
+        RelayRemoval::NotFound => Err(not_found(name)),
+        RelayRemoval::RelayNotFound => Err(ApiError::NotFound(format!(
+            "Relay '{}' not found for proposer pattern '{}'",
+            url, name
+        ))),
+        RelayRemoval::Removed(values) => {
+            let (before, after) = *values;
+            Ok(AuditChanges {
+                relay_url: Some(url.to_string()),
+                ..AuditChanges::diff(&before, &after)
+            })
+        }
+    }
+}
+
+/// Delete a pattern with its relays, returning the changes to audit
+pub async fn delete(repo: &impl PatternRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    let before = repo.delete(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(AuditChanges::diff(&before, &AuditValues::default()))
+}
+
+/// What deleting a pattern removes; nothing refers to a pattern, so its
+/// delete is never blocked
+pub async fn dependents(repo: &impl PatternRepository, name: &str) -> Result<DeletionPreview, ApiError> {
+    let relays = repo.relay_count(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(DeletionPreview::new(name.to_string(), &[("relays", relays)], Vec::new()))
+}
+
+/// Every pattern with its relays, sorted by name, in the import format
+/// (`min_value`s in ETH, like API input)
+pub async fn export(repo: &impl PatternRepository) -> Result<Vec<CreateProposerPatternRequest>, ApiError> {
+    let (patterns, relays) = repo.all().await?;
+    let mut relays_by_pattern: HashMap<String, HashMap<String, ProposerRelayConfig>> = HashMap::new();
+    for relay in relays {
+        relays_by_pattern
+            .entry(relay.pattern_name.clone())
+            .or_default()
+            .insert(relay.url.clone(), relay.into());
+    }
+
+    let mut exported: Vec<CreateProposerPatternRequest> = patterns
+        .into_iter()
+        .map(|pattern| CreateProposerPatternRequest {
+            relays: relays_by_pattern.remove(&pattern.name),
+            name: pattern.name,
+            pattern: pattern.pattern,
+            tags: pattern.tags,
+            fee_recipient: pattern.fee_recipient,
+            gas_limit: pattern.gas_limit,
+            min_value: pattern.min_value,
+            builder_enabled: pattern.builder_enabled,
+            builder_boost_factor: pattern.builder_boost_factor,
+            reset_relays: pattern.reset_relays,
+            active: pattern.active,
+            note: pattern.note,
+            labels: pattern.labels.0,
+            relay_sets: pattern.relay_sets,
+        })
+        .collect();
+    exported.display_min_values(ValueUnits::Eth);
+    Ok(exported)
+}
+
+/// Evaluate a pattern against `proposers` (the stored matches, refreshed
+/// first if stale) or `mux:<name>` (the mux keys, matched here), returning
+/// the match count and up to `sample` matching keys
+pub async fn matches(
+    repo: &impl PatternRepository,
+    name: &str,
+    source: Option<String>,
+    sample: usize,
+) -> Result<PatternMatchesResponse, ApiError> {
+    let pattern = repo.find(name).await?.ok_or_else(|| not_found(name))?.0.pattern;
+    let regex = regex::Regex::new(&pattern).map_err(|e| {
+        ApiError::InvalidData(format!("Pattern '{}' is not a valid regex: {}", name, e))
+    })?;
+
+    let source = source.unwrap_or_else(|| "proposers".to_string());
+    let mux_name = match source.split_once(':') {
+        None if source == "proposers" => None,
+        Some(("mux", mux_name)) => Some(mux_name),
+        _ => {
+            return Err(ApiError::InvalidData(format!(
+                "Invalid source '{}': expected 'proposers' or 'mux:<name>'",
+                source
+            )))
+        }
+    };
+
+    let Some(mux_name) = mux_name else {
+        let refreshed_at = repo.refresh_matches().await?;
+        let total = repo.proposer_count().await?;
+        let (count, sample) = repo.stored_matches(name, sample as i64).await?;
+        return Ok(PatternMatchesResponse {
+            name: name.to_string(),
+            pattern,
+            source,
+            total,
+            count,
+            sample,
+            refreshed_at: Some(refreshed_at),
+        });
+    };
+
+    let keys = repo
+        .mux_keys(mux_name)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Mux config '{}' not found", mux_name)))?;
+    let mut count = 0;
+    let mut matched = Vec::new();
+    for key in &keys {
+        if regex.is_match(key) {
+            count += 1;
+            if matched.len() < sample {
+                matched.push(key.clone());
+            }
+        }
+    }
+
+    Ok(PatternMatchesResponse {
+        name: name.to_string(),
+        pattern,
+        source,
+        total: keys.len() as i64,
+        count,
+        sample: matched,
+        refreshed_at: None,
+    })
+}
+
+/// Proposers matched per pattern, refreshing the stored matches first if stale
+pub async fn statistics(repo: &impl PatternRepository) -> Result<PatternMatchStatistics, ApiError> {
+    let refreshed_at = repo.refresh_matches().await?;
+    Ok(PatternMatchStatistics {
+        refreshed_at,
+        proposers: repo.proposer_count().await?,
+        unmatched: repo.unmatched_count().await?,
+        patterns: repo.match_counts().await?,
+    })
+}
+
+/// Every tag in use; only proposer patterns carry tags
+pub async fn tags(repo: &impl PatternRepository) -> Result<Vec<TagUsage>, ApiError> {
+    repo.tags().await
+}
+
+/// Rename a tag on every pattern carrying it, returning the renamed patterns
+/// and the changes to audit for each
+pub async fn rename_tag(
+    repo: &impl PatternRepository,
+    from: &str,
+    to: &str,
+) -> Result<(RenameTagResponse, Vec<(String, AuditChanges)>), ApiError> {
+    let to = to.trim();
+    // Execution configs take tags as a comma-separated list
+    if to.is_empty() || to.contains(',') {
+        return Err(ApiError::InvalidData(format!(
+            "Tag '{}' must be non-empty and must not contain commas",
+            to
+        )));
+    }
+    if to == from {
+        return Err(ApiError::InvalidData(format!("Tag '{}' is already named that way", from)));
+    }
+
+    let renamed = repo.rename_tag(from, to).await?;
+    if renamed.is_empty() {
+        return Err(ApiError::NotFound(format!("Tag '{}' not found", from)));
+    }
+
+    let mut changes: Vec<(String, AuditChanges)> = renamed
+        .into_iter()
+        .map(|renamed| {
+            let changes = AuditChanges {
+                tags: Change::between(&Some(renamed.before), &Some(renamed.after)),
+                ..Default::default()
+            };
+            (renamed.name, changes)
+        })
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    let response = RenameTagResponse {
+        from: from.to_string(),
+        to: to.to_string(),
+        patterns: changes.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    Ok((response, changes))
+}
+
+/// Audited values of a pattern and its relay count, locking the pattern row
+pub(crate) async fn audit_snapshot(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let pattern = sqlx::query_as!(
+        VouchProposerPattern,
+        r#"SELECT name, pattern, tags, fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, active, note, labels as "labels: _", relay_sets, id, created_at, updated_at
+           FROM vouch_proposer_patterns WHERE name = $1 FOR UPDATE"#,
+        name
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(pattern) = pattern else {
+        return Ok(None);
+    };
+
+    let relays_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM vouch_proposer_pattern_relays WHERE pattern_name = $1"#,
+        name
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(AuditValues {
+        relays_count: Some(relays_count),
+        ..AuditValues::from(&pattern)
+    }))
+}
+
+/// Create a pattern, or replace every field and relay of an existing one
+pub(crate) async fn replace(conn: &mut PgConnection, req: &CreateProposerPatternRequest) -> Result<(), ApiError> {
+    sqlx::query!(
+        "INSERT INTO vouch_proposer_patterns (name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, builder_enabled, builder_boost_factor, note, labels, relay_sets, active)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+         ON CONFLICT (name) DO UPDATE
+         SET pattern = EXCLUDED.pattern, tags = EXCLUDED.tags,
+             fee_recipient = EXCLUDED.fee_recipient, gas_limit = EXCLUDED.gas_limit,
+             min_value = EXCLUDED.min_value, reset_relays = EXCLUDED.reset_relays,
+             builder_enabled = EXCLUDED.builder_enabled, builder_boost_factor = EXCLUDED.builder_boost_factor,
+             note = EXCLUDED.note, labels = EXCLUDED.labels, relay_sets = EXCLUDED.relay_sets,
+             active = EXCLUDED.active",
+        req.name,
+        req.pattern,
+        &req.tags,
+        req.fee_recipient as _,
+        req.gas_limit,
+        req.min_value,
+        req.reset_relays,
+        req.builder_enabled,
+        req.builder_boost_factor,
+        req.note,
+        sqlx::types::Json(&req.labels) as _,
+        &req.relay_sets,
+        req.active
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    replace_relays(conn, &req.name, req.relays.as_ref()).await
+}
+
+/// Replace the relays of a pattern with the given ones
+async fn replace_relays(
+    conn: &mut PgConnection,
+    name: &str,
+    relays: Option<&HashMap<String, ProposerRelayConfig>>,
+) -> Result<(), ApiError> {
+    sqlx::query!("DELETE FROM vouch_proposer_pattern_relays WHERE pattern_name = $1", name)
+        .execute(&mut *conn)
+        .await?;
+
+    for (url, relay) in relays.into_iter().flatten() {
+        sqlx::query!(
+            "INSERT INTO vouch_proposer_pattern_relays
+             (pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value,
+            relay.disabled
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+impl PatternFilters {
+    /// SQL WHERE clause over `vouch_proposer_patterns p` (empty when no filter is set)
+    fn where_clause(&self) -> String {
+        let mut conditions = Vec::new();
+
+        if let Some(ref name) = self.name {
+            conditions.push(format!("p.name LIKE '{}%'", name.replace('\'', "''")));
+        }
+        if let Some(ref pattern) = self.pattern {
+            conditions.push(format!("p.pattern LIKE '%{}%'", pattern.replace('\'', "''")));
+        }
+        if let Some(ref tag) = self.tag {
+            conditions.push(format!("'{}' = ANY(p.tags)", tag.replace('\'', "''")));
+        }
+        if let Some(ref tag) = self.exclude_tag {
+            conditions.push(format!("NOT ('{}' = ANY(p.tags))", tag.replace('\'', "''")));
+        }
+        if let Some(ref fr) = self.fee_recipient {
+            let fr = normalize_hex(fr);
+            conditions.push(format!("p.fee_recipient = '{}'", fr.replace('\'', "''")));
+        }
+        if let Some(ref fr) = self.fee_recipient_ne {
+            let fr = normalize_hex(fr);
+            conditions.push(format!("p.fee_recipient IS DISTINCT FROM '{}'", fr.replace('\'', "''")));
+        }
+        if let Some(ref gl) = self.gas_limit {
+            conditions.push(format!("p.gas_limit = '{}'", gl.replace('\'', "''")));
+        }
+        if let Some(ref mv) = self.min_value {
+            let mv = normalize_min_value_filter(mv);
+            conditions.push(format!("p.min_value = '{}'", mv.replace('\'', "''")));
+        }
+        if let Some(rr) = self.reset_relays {
+            conditions.push(format!(
+                "p.reset_relays = {}",
+                if rr { "true" } else { "false" }
+            ));
+        }
+        if let Some(active) = self.active {
+            conditions.push(format!("p.active = {}", if active { "true" } else { "false" }));
+        }
+        // Relay filters using EXISTS subquery
+        if let Some(ref relay_url) = self.relay_url {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.url LIKE '{}%')",
+                relay_url.replace('\'', "''")
+            ));
+        }
+        if let Some(ref relay_min_value) = self.relay_min_value {
+            let relay_min_value = normalize_min_value_filter(relay_min_value);
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.min_value = '{}')",
+                relay_min_value.replace('\'', "''")
+            ));
+        }
+        if let Some(relay_disabled) = self.relay_disabled {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name AND r.disabled = {})",
+                if relay_disabled { "true" } else { "false" }
+            ));
+        }
+
+        if let Some(condition) = labels_condition("p.labels", &self.labels) {
+            conditions.push(condition);
+        }
+
+        conditions.extend(self.time_range.conditions("p"));
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+impl PatternRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM vouch_proposer_patterns WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, filters: &PatternFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM vouch_proposer_patterns p {}", filters.where_clause());
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, filters: &PatternFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposerPattern>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT p.name, p.pattern, p.tags, p.fee_recipient, p.gas_limit, p.min_value, p.builder_enabled, p.builder_boost_factor, p.reset_relays, p.active, p.note, p.labels, p.relay_sets, p.id, p.created_at, p.updated_at
+             FROM vouch_proposer_patterns p {}
+             {}
+             LIMIT {} OFFSET {}",
+            filters.where_clause(), order_by, page.limit, page.offset
+        );
+        Ok(sqlx::query_as::<_, VouchProposerPattern>(&data_sql).fetch_all(self).await?)
+    }
+
+    async fn find(
+        &self,
+        name: &str,
+    ) -> Result<Option<(VouchProposerPattern, Vec<VouchProposerPatternRelay>)>, ApiError> {
+        let pattern = sqlx::query_as!(
+            VouchProposerPattern,
+            r#"SELECT name, pattern, tags, fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, active, note, labels as "labels: _", relay_sets, id, created_at, updated_at
+               FROM vouch_proposer_patterns WHERE name = $1"#,
+            name
+        )
+        .fetch_optional(self)
+        .await?;
+        let Some(pattern) = pattern else {
+            return Ok(None);
+        };
+
+        let relays = sqlx::query_as!(
+            VouchProposerPatternRelay,
+            r#"SELECT id, pattern_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, disabled
+               FROM vouch_proposer_pattern_relays WHERE pattern_name = $1"#,
+            name
+        )
+        .fetch_all(self)
+        .await?;
+
+        Ok(Some((pattern, relays)))
+    }
+
+    async fn insert(&self, req: &CreateProposerPatternRequest) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        ensure_relay_sets_exist(&mut tx, &req.relay_sets).await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO vouch_proposer_patterns (name, pattern, tags, fee_recipient, gas_limit, min_value, reset_relays, builder_enabled, builder_boost_factor, note, labels, relay_sets, active)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             ON CONFLICT (name) DO NOTHING",
+            req.name,
+            req.pattern,
+            &req.tags,
+            req.fee_recipient as _,
+            req.gas_limit,
+            req.min_value,
+            req.reset_relays,
+            req.builder_enabled,
+            req.builder_boost_factor,
+            req.note,
+            sqlx::types::Json(&req.labels) as _,
+            &req.relay_sets,
+            req.active
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The unique name constraint decides races between concurrent creates
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        if req.relays.is_some() {
+            replace_relays(&mut tx, &req.name, req.relays.as_ref()).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, &req.name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some(after))
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        req: &UpdateProposerPatternRequest,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        if let Some(relay_sets) = &req.relay_sets {
+            ensure_relay_sets_exist(&mut tx, relay_sets).await?;
+        }
+
+        let mut update = PartialUpdate::new("vouch_proposer_patterns");
+        update
+            .set("pattern", req.pattern.as_deref())
+            .set("tags", req.tags.as_deref())
+            .set("fee_recipient", req.fee_recipient.update())
+            .set("gas_limit", req.gas_limit.update())
+            .set("min_value", req.min_value.update())
+            .set("reset_relays", req.reset_relays)
+            .set("active", req.active)
+            .set("builder_enabled", req.builder_enabled.update())
+            .set("builder_boost_factor", req.builder_boost_factor.update())
+            .set("note", req.note.update())
+            .set("labels", req.labels.as_ref().map(sqlx::types::Json))
+            .set("relay_sets", req.relay_sets.as_deref());
+        update.execute(&mut tx, "name", name).await?;
+
+        if req.relays.is_some() {
+            replace_relays(&mut tx, name, req.relays.as_ref()).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn put_relay(
+        &self,
+        name: &str,
+        url: &str,
+        relay: &ProposerRelayConfig,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        // Other relays of the pattern are left alone
+        sqlx::query!(
+            "INSERT INTO vouch_proposer_pattern_relays
+             (pattern_name, url, public_key, fee_recipient, gas_limit, min_value, disabled)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT (pattern_name, url) DO UPDATE SET
+                 public_key = EXCLUDED.public_key,
+                 fee_recipient = EXCLUDED.fee_recipient,
+                 gas_limit = EXCLUDED.gas_limit,
+                 min_value = EXCLUDED.min_value,
+                 disabled = EXCLUDED.disabled",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value,
+            relay.disabled
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn delete_relay(&self, name: &str, url: &str) -> Result<RelayRemoval, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(RelayRemoval::NotFound);
+        };
+
+        let deleted = sqlx::query!(
+            "DELETE FROM vouch_proposer_pattern_relays WHERE pattern_name = $1 AND url = $2",
+            name,
+            url
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted == 0 {
+            return Ok(RelayRemoval::RelayNotFound);
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(RelayRemoval::Removed(Box::new((before, after))))
+    }
+
+    async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query!("DELETE FROM vouch_proposer_patterns WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(before))
+    }
+
+    async fn relay_count(&self, name: &str) -> Result<Option<i64>, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT (SELECT COUNT(*) FROM vouch_proposer_pattern_relays r WHERE r.pattern_name = p.name) AS "relays!"
+               FROM vouch_proposer_patterns p WHERE p.name = $1"#,
+            name
+        )
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn all(&self) -> Result<(Vec<VouchProposerPattern>, Vec<VouchProposerPatternRelay>), ApiError> {
+        let patterns = sqlx::query_as!(
+            VouchProposerPattern,
+            r#"SELECT name, pattern, tags, fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, active, note, labels as "labels: _", relay_sets, id, created_at, updated_at
+               FROM vouch_proposer_patterns ORDER BY name"#
+        )
+        .fetch_all(self)
+        .await?;
+        let relays = sqlx::query_as!(
+            VouchProposerPatternRelay,
+            r#"SELECT id, pattern_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, disabled
+               FROM vouch_proposer_pattern_relays"#
+        )
+        .fetch_all(self)
+        .await?;
+        Ok((patterns, relays))
+    }
+
+    async fn refresh_matches(&self) -> Result<DateTime<Utc>, ApiError> {
+        Ok(pattern_matches::refresh(self).await?.refreshed_at)
+    }
+
+    async fn proposer_count(&self) -> Result<i64, ApiError> {
+        Ok(sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM vouch_proposers"#)
+            .fetch_one(self)
+            .await?)
+    }
+
+    async fn stored_matches(&self, name: &str, sample: i64) -> Result<(i64, Vec<String>), ApiError> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM vouch_proposer_pattern_matches WHERE pattern_name = $1"#,
+            name
+        )
+        .fetch_one(self)
+        .await?;
+        let sample = sqlx::query_scalar!(
+            "SELECT public_key FROM vouch_proposer_pattern_matches WHERE pattern_name = $1 ORDER BY public_key LIMIT $2",
+            name,
+            sample
+        )
+        .fetch_all(self)
+        .await?;
+        Ok((count, sample))
+    }
+
+    async fn match_counts(&self) -> Result<Vec<PatternMatchCount>, ApiError> {
+        Ok(sqlx::query_as!(
+            PatternMatchCount,
+            r#"SELECT p.name, p.active, COUNT(m.public_key) AS "matches!"
+               FROM vouch_proposer_patterns p
+               LEFT JOIN vouch_proposer_pattern_matches m ON m.pattern_name = p.name
+               GROUP BY p.name, p.active
+               ORDER BY p.name"#
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn unmatched_count(&self) -> Result<i64, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM vouch_proposers v
+               WHERE NOT EXISTS (
+                   SELECT 1 FROM vouch_proposer_pattern_matches m
+                   JOIN vouch_proposer_patterns p ON p.name = m.pattern_name AND p.active
+                   WHERE m.public_key = v.public_key
+               )"#
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn mux_keys(&self, mux_name: &str) -> Result<Option<Vec<String>>, ApiError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM commit_boost_mux_configs WHERE name = $1) AS "exists!""#,
+            mux_name
+        )
+        .fetch_one(self)
+        .await?;
+        if !exists {
+            return Ok(None);
+        }
+        let keys = sqlx::query_scalar!(
+            "SELECT public_key FROM commit_boost_mux_keys WHERE mux_name = $1 ORDER BY public_key",
+            mux_name
+        )
+        .fetch_all(self)
+        .await?;
+        Ok(Some(keys))
+    }
+
+    async fn tags(&self) -> Result<Vec<TagUsage>, ApiError> {
+        Ok(sqlx::query_as!(
+            TagUsage,
+            r#"SELECT tag AS "tag!", COUNT(*) AS "patterns!" FROM vouch_proposer_patterns, unnest(tags) AS tag
+               GROUP BY tag ORDER BY tag"#
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn rename_tag(&self, from: &str, to: &str) -> Result<Vec<RenamedTags>, ApiError> {
+        // One statement, so every pattern changes or none; a pattern that already
+        // has the new tag keeps it once, at its first position
+        let renamed = sqlx::query!(
+            r#"WITH old AS (
+                   SELECT name, tags FROM vouch_proposer_patterns WHERE $1 = ANY(tags) FOR UPDATE
+               )
+               UPDATE vouch_proposer_patterns p
+               SET tags = ARRAY(
+                   SELECT t FROM unnest(array_replace(p.tags, $1, $2)) WITH ORDINALITY AS u(t, i)
+                   GROUP BY t ORDER BY MIN(i)
+               )
+               FROM old WHERE p.name = old.name
+               RETURNING p.name, old.tags AS before, p.tags AS after"#,
+            from,
+            to
+        )
+        .fetch_all(self)
+        .await?;
+        Ok(renamed
+            .into_iter()
+            .map(|row| RenamedTags {
+                name: row.name,
+                before: row.before,
+                after: row.after,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory patterns keyed by name, with the keys they are matched against
+    #[derive(Default)]
+    struct MockRepository {
+        patterns: Mutex<HashMap<String, (VouchProposerPattern, Vec<VouchProposerPatternRelay>)>>,
+        proposers: Vec<String>,
+        muxes: HashMap<String, Vec<String>>,
+    }
+
+    fn relay(url: &str, name: &str, config: &ProposerRelayConfig) -> VouchProposerPatternRelay {
+        VouchProposerPatternRelay {
+            id: 1,
+            pattern_name: name.to_string(),
+            url: url.to_string(),
+            public_key: config.public_key.clone(),
+            fee_recipient: config.fee_recipient.clone(),
+            gas_limit: config.gas_limit.clone(),
+            min_value: config.min_value.clone(),
+            disabled: config.disabled,
+        }
+    }
+
+    fn relay_config() -> ProposerRelayConfig {
+        ProposerRelayConfig {
+            public_key: TEST_KEY.parse().unwrap(),
+            fee_recipient: None,
+            gas_limit: None,
+            min_value: None,
+            disabled: false,
+        }
+    }
+
+    impl MockRepository {
+        fn with_pattern(name: &str, relay_urls: &[&str]) -> Self {
+            let repo = Self::default();
+            repo.add(name, relay_urls);
+            repo
+        }
+
+        fn add(&self, name: &str, relay_urls: &[&str]) {
+            let pattern = VouchProposerPattern {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                pattern: "^0x80".to_string(),
+                tags: vec!["lido".to_string()],
+                fee_recipient: None,
+                gas_limit: None,
+                min_value: None,
+                builder_enabled: None,
+                builder_boost_factor: None,
+                reset_relays: false,
+                active: true,
+                note: None,
+                labels: Default::default(),
+                relay_sets: Vec::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let relays = relay_urls.iter().map(|url| relay(url, name, &relay_config())).collect();
+            self.patterns.lock().unwrap().insert(name.to_string(), (pattern, relays));
+        }
+
+        fn snapshot(&self, name: &str) -> Option<AuditValues> {
+            self.patterns.lock().unwrap().get(name).map(|(pattern, relays)| AuditValues {
+                relays_count: Some(relays.len() as i64),
+                ..AuditValues::from(pattern)
+            })
+        }
+
+        /// Proposers the pattern matches, in key order
+        fn matched(&self, pattern: &VouchProposerPattern) -> Vec<String> {
+            let regex = regex::Regex::new(&pattern.pattern).unwrap();
+            let mut matched: Vec<String> = self.proposers.iter().filter(|key| regex.is_match(key)).cloned().collect();
+            matched.sort();
+            matched
+        }
+
+        /// Patterns passing the `tag` filter (the only one the mock applies), by name
+        fn matching(&self, filters: &PatternFilters) -> Vec<VouchProposerPattern> {
+            let mut matching: Vec<VouchProposerPattern> = self
+                .patterns
+                .lock()
+                .unwrap()
+                .values()
+                .map(|(pattern, _)| pattern.clone())
+                .filter(|p| filters.tag.as_ref().is_none_or(|tag| p.tags.contains(tag)))
+                .collect();
+            matching.sort_by(|a, b| a.name.cmp(&b.name));
+            matching
+        }
+    }
+
+    impl PatternRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.patterns.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self, filters: &PatternFilters) -> Result<i64, ApiError> {
+            Ok(self.matching(filters).len() as i64)
+        }
+
+        async fn list(&self, filters: &PatternFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposerPattern>, ApiError> {
+            let matching = self.matching(filters).into_iter();
+            Ok(matching.skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn find(
+            &self,
+            name: &str,
+        ) -> Result<Option<(VouchProposerPattern, Vec<VouchProposerPatternRelay>)>, ApiError> {
+            Ok(self.patterns.lock().unwrap().get(name).cloned())
+        }
+
+        async fn insert(&self, req: &CreateProposerPatternRequest) -> Result<Option<AuditValues>, ApiError> {
+            if self.exists(&req.name).await? {
+                return Ok(None);
+            }
+            self.add(&req.name, &[]);
+            if let Some((pattern, relays)) = self.patterns.lock().unwrap().get_mut(&req.name) {
+                pattern.pattern = req.pattern.clone();
+                pattern.tags = req.tags.clone();
+                relays.extend(req.relays.iter().flatten().map(|(url, config)| relay(url, &req.name, config)));
+            }
+            Ok(self.snapshot(&req.name))
+        }
+
+        async fn update(
+            &self,
+            name: &str,
+            req: &UpdateProposerPatternRequest,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            if let Some((pattern, relays)) = self.patterns.lock().unwrap().get_mut(name) {
+                if let Some(active) = req.active {
+                    pattern.active = active;
+                }
+                if let Some(new_relays) = &req.relays {
+                    *relays = new_relays.iter().map(|(url, config)| relay(url, name, config)).collect();
+                }
+            }
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn put_relay(
+            &self,
+            name: &str,
+            url: &str,
+            config: &ProposerRelayConfig,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            if let Some((_, relays)) = self.patterns.lock().unwrap().get_mut(name) {
+                relays.retain(|r| r.url != url);
+                relays.push(relay(url, name, config));
+            }
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn delete_relay(&self, name: &str, url: &str) -> Result<RelayRemoval, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(RelayRemoval::NotFound);
+            };
+            {
+                let mut patterns = self.patterns.lock().unwrap();
+                let (_, relays) = patterns.get_mut(name).unwrap();
+                let count = relays.len();
+                relays.retain(|r| r.url != url);
+                if relays.len() == count {
+                    return Ok(RelayRemoval::RelayNotFound);
+                }
+            }
+            Ok(RelayRemoval::Removed(Box::new((before, self.snapshot(name).unwrap()))))
+        }
+
+        async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+            let before = self.snapshot(name);
+            self.patterns.lock().unwrap().remove(name);
+            Ok(before)
+        }
+
+        async fn relay_count(&self, name: &str) -> Result<Option<i64>, ApiError> {
+            Ok(self.patterns.lock().unwrap().get(name).map(|(_, relays)| relays.len() as i64))
+        }
+
+        async fn all(&self) -> Result<(Vec<VouchProposerPattern>, Vec<VouchProposerPatternRelay>), ApiError> {
+            let patterns = self.matching(&PatternFilters::default());
+            let relays = self.patterns.lock().unwrap().values().flat_map(|(_, relays)| relays.clone()).collect();
+            Ok((patterns, relays))
+        }
+
+        async fn refresh_matches(&self) -> Result<DateTime<Utc>, ApiError> {
+            Ok(Utc::now())
+        }
+
+        async fn proposer_count(&self) -> Result<i64, ApiError> {
+            Ok(self.proposers.len() as i64)
+        }
+
+        async fn stored_matches(&self, name: &str, sample: i64) -> Result<(i64, Vec<String>), ApiError> {
+            let (pattern, _) = self.patterns.lock().unwrap().get(name).cloned().unwrap();
+            let matched = self.matched(&pattern);
+            Ok((matched.len() as i64, matched.into_iter().take(sample as usize).collect()))
+        }
+
+        async fn match_counts(&self) -> Result<Vec<PatternMatchCount>, ApiError> {
+            let counts = self
+                .matching(&PatternFilters::default())
+                .iter()
+                .map(|pattern| PatternMatchCount {
+                    name: pattern.name.clone(),
+                    active: pattern.active,
+                    matches: self.matched(pattern).len() as i64,
+                })
+                .collect();
+            Ok(counts)
+        }
+
+        async fn unmatched_count(&self) -> Result<i64, ApiError> {
+            let active: Vec<VouchProposerPattern> = self
+                .matching(&PatternFilters::default())
+                .into_iter()
+                .filter(|pattern| pattern.active)
+                .collect();
+            let unmatched = self
+                .proposers
+                .iter()
+                .filter(|key| !active.iter().any(|pattern| self.matched(pattern).contains(key)))
+                .count();
+            Ok(unmatched as i64)
+        }
+
+        async fn mux_keys(&self, mux_name: &str) -> Result<Option<Vec<String>>, ApiError> {
+            Ok(self.muxes.get(mux_name).cloned())
+        }
+
+        async fn tags(&self) -> Result<Vec<TagUsage>, ApiError> {
+            let mut counts: HashMap<String, i64> = HashMap::new();
+            for pattern in self.matching(&PatternFilters::default()) {
+                for tag in pattern.tags {
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+            let mut tags: Vec<TagUsage> = counts.into_iter().map(|(tag, patterns)| TagUsage { tag, patterns }).collect();
+            tags.sort_by(|a, b| a.tag.cmp(&b.tag));
+            Ok(tags)
+        }
+
+        async fn rename_tag(&self, from: &str, to: &str) -> Result<Vec<RenamedTags>, ApiError> {
+            let mut renamed = Vec::new();
+            for (pattern, _) in self.patterns.lock().unwrap().values_mut() {
+                if !pattern.tags.iter().any(|tag| tag == from) {
+                    continue;
+                }
+                let before = pattern.tags.clone();
+                let mut after: Vec<String> = Vec::new();
+                for tag in &before {
+                    let tag = if tag == from { to.to_string() } else { tag.clone() };
+                    if !after.contains(&tag) {
+                        after.push(tag);
+                    }
+                }
+                pattern.tags = after.clone();
+                renamed.push(RenamedTags {
+                    name: pattern.name.clone(),
+                    before,
+                    after,
+                });
+            }
+            Ok(renamed)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_pattern() {
+        let repo = MockRepository::with_pattern("lido", &["https://relay.example.com"]);
+        let response = get(&repo, "lido").await.unwrap();
+        assert_eq!(response.pattern, "^0x80");
+        assert_eq!(response.tags, vec!["lido"]);
+        assert_eq!(response.relays.unwrap().len(), 1);
+
+        assert!(ensure_exists(&repo, "lido").await.is_ok());
+        assert!(matches!(ensure_exists(&repo, "other").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_pattern() {
+        let repo = MockRepository::with_pattern("lido", &["https://relay.example.com"]);
+        let changes = delete(&repo, "lido").await.unwrap();
+        assert_eq!(changes.relays_count.unwrap().from, Some(1));
+        assert!(matches!(delete(&repo, "lido").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_patterns() {
+        let repo = MockRepository::default();
+        for name in ["c", "a", "b"] {
+            repo.add(name, &[]);
+        }
+        let filters = PatternFilters {
+            tag: Some("lido".to_string()),
+            ..Default::default()
+        };
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 2,
+            offset: 1,
+        };
+
+        let response = list(&repo, &filters, page).await.unwrap();
+        let names: Vec<&str> = response.data.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["b", "c"]);
+        assert_eq!(response.total, 3);
+        assert!(!response.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_relays() {
+        let repo = MockRepository::default();
+        let req: CreateProposerPatternRequest = serde_json::from_value(serde_json::json!({
+            "name": "lido",
+            "pattern": "^0x80",
+            "tags": ["lido"],
+        }))
+        .unwrap();
+
+        let (response, changes) = create(&repo, &req).await.unwrap();
+        assert!(response.relays.is_none());
+        assert_eq!(changes.relays_count.unwrap().to, Some(0));
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::Conflict(_))));
+
+        let update_req: UpdateProposerPatternRequest = serde_json::from_str(r#"{"active": false}"#).unwrap();
+        let (response, changes) = update(&repo, "lido", &update_req).await.unwrap();
+        assert!(!response.active);
+        assert!(changes.active.is_some());
+        assert!(matches!(update(&repo, "other", &update_req).await, Err(ApiError::NotFound(_))));
+
+        let (response, changes) = put_relay(&repo, "lido", "https://relay.example.com", &relay_config())
+            .await
+            .unwrap();
+        assert_eq!(response.relays.unwrap().len(), 1);
+        assert_eq!(changes.relay_url.as_deref(), Some("https://relay.example.com"));
+
+        delete_relay(&repo, "lido", "https://relay.example.com").await.unwrap();
+        let missing = delete_relay(&repo, "lido", "https://relay.example.com").await;
+        assert!(matches!(missing, Err(ApiError::NotFound(message)) if message.starts_with("Relay")));
+    }
+
+    #[tokio::test]
+    async fn test_matches_against_proposers_and_mux_keys() {
+        let mut repo = MockRepository::with_pattern("lido", &[]);
+        repo.proposers = vec!["0x80aa".to_string(), "0x9000".to_string(), "0x80bb".to_string()];
+        repo.muxes.insert("mux".to_string(), vec!["0x8001".to_string(), "0x7001".to_string()]);
+
+        let response = matches(&repo, "lido", None, 1).await.unwrap();
+        assert_eq!(response.source, "proposers");
+        assert_eq!((response.total, response.count), (3, 2));
+        assert_eq!(response.sample, ["0x80aa"]);
+        assert!(response.refreshed_at.is_some());
+
+        let response = matches(&repo, "lido", Some("mux:mux".to_string()), 10).await.unwrap();
+        assert_eq!((response.total, response.count), (2, 1));
+        assert_eq!(response.sample, ["0x8001"]);
+        assert!(response.refreshed_at.is_none());
+
+        let missing = matches(&repo, "lido", Some("mux:other".to_string()), 10).await;
+        assert!(matches!(missing, Err(ApiError::NotFound(message)) if message.starts_with("Mux")));
+        let invalid = matches(&repo, "lido", Some("keys".to_string()), 10).await;
+        assert!(matches!(invalid, Err(ApiError::InvalidData(_))));
+        assert!(matches!(matches(&repo, "other", None, 10).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_statistics_dependents_and_export() {
+        let mut repo = MockRepository::with_pattern("lido", &["https://relay.example.com"]);
+        repo.add("inactive", &[]);
+        repo.patterns.lock().unwrap().get_mut("inactive").unwrap().0.active = false;
+        repo.proposers = vec!["0x80aa".to_string(), "0x9000".to_string()];
+
+        let statistics = statistics(&repo).await.unwrap();
+        assert_eq!((statistics.proposers, statistics.unmatched), (2, 1));
+        let counts: Vec<(&str, i64)> = statistics.patterns.iter().map(|p| (p.name.as_str(), p.matches)).collect();
+        assert_eq!(counts, [("inactive", 1), ("lido", 1)]);
+
+        let preview = dependents(&repo, "lido").await.unwrap();
+        assert_eq!(preview.removes[0].count, 1);
+        assert!(preview.blocked_by.is_empty());
+        assert!(matches!(dependents(&repo, "other").await, Err(ApiError::NotFound(_))));
+
+        let exported = export(&repo).await.unwrap();
+        let names: Vec<&str> = exported.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["inactive", "lido"]);
+        assert!(exported[0].relays.is_none());
+        assert_eq!(exported[1].relays.as_ref().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rename_tag_merges_and_validates() {
+        let repo = MockRepository::default();
+        repo.add("b", &[]);
+        repo.add("a", &[]);
+        repo.patterns.lock().unwrap().get_mut("a").unwrap().0.tags = vec!["lido".to_string(), "rocketpool".to_string()];
+
+        let counts: Vec<(String, i64)> = tags(&repo).await.unwrap().into_iter().map(|t| (t.tag, t.patterns)).collect();
+        assert_eq!(counts, [("lido".to_string(), 2), ("rocketpool".to_string(), 1)]);
+
+        let (response, changes) = rename_tag(&repo, "rocketpool", " lido ").await.unwrap();
+        assert_eq!(response.to, "lido");
+        assert_eq!(response.patterns, ["a"]);
+        assert_eq!(changes[0].1.tags.as_ref().unwrap().to, Some(vec!["lido".to_string()]));
+
+        let (response, _) = rename_tag(&repo, "lido", "csm").await.unwrap();
+        assert_eq!(response.patterns, ["a", "b"]);
+
+        assert!(matches!(rename_tag(&repo, "lido", "csm").await, Err(ApiError::NotFound(_))));
+        assert!(matches!(rename_tag(&repo, "csm", "a,b").await, Err(ApiError::InvalidData(_))));
+        assert!(matches!(rename_tag(&repo, "csm", "csm").await, Err(ApiError::InvalidData(_))));
+    }
+}
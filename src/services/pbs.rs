@@ -0,0 +1,740 @@
+// services/pbs.rs - PBS config lookups, writes and cb-config.toml rendering
+use super::PageRequest;
+use crate::addresses::BlsPubkey;
+use crate::audit::{AuditChanges, AuditValues};
+use crate::errors::ApiError;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::models::{CommitBoostPbsConfig, CommitBoostPbsMux, CommitBoostPbsRelay, PbsSettings};
+use crate::schema::{
+    CreatePbsConfigRequest, PaginatedResponse, PbsConfigResponse, PbsMux, PbsRelay, UpdatePbsConfigRequest,
+};
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashSet;
+use std::future::Future;
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "name"),
+        ("chain", "chain"),
+        ("created_at", "created_at"),
+        ("updated_at", "updated_at"),
+    ],
+    default: ("name", SortOrder::Asc),
+    tiebreak: "name",
+};
+
+/// A PBS config with its relays and muxes, both in insertion order
+#[derive(Debug, Clone)]
+pub struct StoredPbsConfig {
+    pub config: CommitBoostPbsConfig,
+    pub relays: Vec<CommitBoostPbsRelay>,
+    pub muxes: Vec<CommitBoostPbsMux>,
+}
+
+/// Storage of PBS configs, their relays and muxes
+pub trait PbsRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    fn count(&self) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the configs
+    fn list(&self, page: PageRequest<'_>) -> impl Future<Output = Result<Vec<StoredPbsConfig>, ApiError>> + Send;
+
+    fn find(&self, name: &str) -> impl Future<Output = Result<Option<StoredPbsConfig>, ApiError>> + Send;
+
+    /// Keys of a mux config in insertion order
+    fn mux_keys(&self, mux_name: &str) -> impl Future<Output = Result<Vec<BlsPubkey>, ApiError>> + Send;
+
+    /// Insert the config with its relays and muxes, returning its audited
+    /// values; `None` when a config of that name exists. Fails when a mux
+    /// refers to an unknown mux config.
+    fn insert(
+        &self,
+        req: &CreatePbsConfigRequest,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Replace the whole config, returning the audited values before and after
+    fn replace(
+        &self,
+        name: &str,
+        req: &UpdatePbsConfigRequest,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Delete the config, returning its audited values
+    fn delete(&self, name: &str) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("PBS config '{}' not found", name))
+}
+
+fn response(stored: StoredPbsConfig) -> PbsConfigResponse {
+    let StoredPbsConfig { config, relays, muxes } = stored;
+    PbsConfigResponse {
+        name: config.name,
+        id: config.id,
+        chain: config.chain,
+        pbs: config.pbs.0,
+        relays: relays.into_iter().map(PbsRelay::from).collect(),
+        muxes: muxes.into_iter().map(PbsMux::from).collect(),
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+    }
+}
+
+fn audit_values(stored: &StoredPbsConfig) -> AuditValues {
+    AuditValues {
+        chain: Some(stored.config.chain.clone()),
+        relays_count: Some(stored.relays.len() as i64),
+        muxes: Some(stored.muxes.iter().map(|m| m.mux_id.clone()).collect()),
+        ..Default::default()
+    }
+}
+
+pub async fn ensure_exists(repo: &impl PbsRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+/// The config with its relays and muxes
+pub async fn get(repo: &impl PbsRepository, name: &str) -> Result<PbsConfigResponse, ApiError> {
+    Ok(response(repo.find(name).await?.ok_or_else(|| not_found(name))?))
+}
+
+/// One page of the configs with their relays and muxes
+pub async fn list(
+    repo: &impl PbsRepository,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<PbsConfigResponse>, ApiError> {
+    let total = repo.count().await?;
+    let data = repo.list(page).await?.into_iter().map(response).collect();
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a config, returning it and the changes to audit
+pub async fn create(
+    repo: &impl PbsRepository,
+    req: &CreatePbsConfigRequest,
+) -> Result<(PbsConfigResponse, AuditChanges), ApiError> {
+    validate(&req.chain, &req.pbs, &req.relays, &req.muxes)?;
+    let after = repo
+        .insert(req)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("PBS config '{}' already exists", req.name)))?;
+    let changes = AuditChanges::diff(&AuditValues::default(), &after);
+    Ok((get(repo, &req.name).await?, changes))
+}
+
+/// Replace a config, returning it and the changes to audit
+pub async fn update(
+    repo: &impl PbsRepository,
+    name: &str,
+    req: &UpdatePbsConfigRequest,
+) -> Result<(PbsConfigResponse, AuditChanges), ApiError> {
+    validate(&req.chain, &req.pbs, &req.relays, &req.muxes)?;
+    let (before, after) = repo.replace(name, req).await?.ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Delete a config with its relays and muxes, returning the changes to audit
+pub async fn delete(repo: &impl PbsRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    let before = repo.delete(name).await?.ok_or_else(|| not_found(name))?;
+    Ok(AuditChanges::diff(&before, &AuditValues::default()))
+}
+
+/// Mux configs whose keys the PBS config inlines, sorted by name
+pub async fn referenced_muxes(repo: &impl PbsRepository, name: &str) -> Result<Vec<String>, ApiError> {
+    let Some(stored) = repo.find(name).await? else {
+        return Ok(Vec::new());
+    };
+    let mut names: Vec<String> = stored.muxes.into_iter().filter_map(|m| m.mux_name).collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// cb-config.toml layout; the `toml` serializer orders values before tables
+#[derive(Serialize)]
+struct CbConfig<'a> {
+    chain: &'a str,
+    pbs: &'a PbsSettings,
+    relays: Vec<CbRelay>,
+    mux: Vec<CbMux>,
+}
+
+#[derive(Serialize)]
+struct CbRelay {
+    id: String,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct CbMux {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validator_pubkeys: Option<Vec<BlsPubkey>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loader: Option<CbLoader>,
+    relays: Vec<CbRelay>,
+}
+
+#[derive(Serialize)]
+struct CbLoader {
+    registry: String,
+    node_operator_id: i64,
+}
+
+/// Commit-Boost expects the relay public key as the URL's user part
+fn relay_url_with_key(relay: &PbsRelay) -> Result<String, ApiError> {
+    let mut url = url::Url::parse(&relay.url)
+        .map_err(|e| ApiError::InternalError(format!("Stored relay URL '{}' is invalid: {}", relay.url, e)))?;
+    url.set_username(&relay.public_key.to_string())
+        .map_err(|_| ApiError::InternalError(format!("Relay URL '{}' cannot carry a public key", relay.url)))?;
+    Ok(url.to_string())
+}
+
+/// Render a PBS config as cb-config.toml, along with the number of validator keys it lists.
+/// Globally disabled relays are left out, also from the muxes.
+pub async fn render(
+    repo: &impl PbsRepository,
+    name: &str,
+    globally_disabled: &HashSet<String>,
+) -> Result<(String, usize), ApiError> {
+    let config = get(repo, name).await?;
+
+    let relays = config
+        .relays
+        .iter()
+        .filter(|relay| !globally_disabled.contains(&relay.url))
+        .map(|relay| {
+            Ok((
+                relay.id.as_str(),
+                CbRelay {
+                    id: relay.id.clone(),
+                    url: relay_url_with_key(relay)?,
+                },
+            ))
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    let mut key_count = 0;
+    let mut muxes = Vec::with_capacity(config.muxes.len());
+    for mux in &config.muxes {
+        let validator_pubkeys = match &mux.mux {
+            Some(mux_name) => {
+                let keys = repo.mux_keys(mux_name).await?;
+                key_count += keys.len();
+                Some(keys)
+            }
+            None => None,
+        };
+        let loader = mux.registry.zip(mux.node_operator_id).map(|(registry, node_operator_id)| CbLoader {
+            registry: registry.as_str().to_string(),
+            node_operator_id,
+        });
+        let mux_relays = relays
+            .iter()
+            .filter(|(id, _)| mux.relays.iter().any(|r| r == id))
+            .map(|(_, relay)| CbRelay {
+                id: relay.id.clone(),
+                url: relay.url.clone(),
+            })
+            .collect();
+        muxes.push(CbMux {
+            id: mux.id.clone(),
+            validator_pubkeys,
+            loader,
+            relays: mux_relays,
+        });
+    }
+
+    let document = CbConfig {
+        chain: &config.chain,
+        pbs: &config.pbs,
+        relays: relays.into_iter().map(|(_, relay)| relay).collect(),
+        mux: muxes,
+    };
+    let toml = toml::to_string(&document)
+        .map_err(|e| ApiError::InternalError(format!("Failed to render PBS config '{}': {}", name, e)))?;
+
+    Ok((toml, key_count))
+}
+
+/// Reject configs Commit-Boost could not load: non-scalar settings, bad relay
+/// URLs, duplicate ids, muxes without exactly one key source or with unknown
+/// relays. Whether the referenced mux configs exist is checked on write.
+fn validate(chain: &str, pbs: &PbsSettings, relays: &[PbsRelay], muxes: &[PbsMux]) -> Result<(), ApiError> {
+    if chain.trim().is_empty() {
+        return Err(ApiError::InvalidData("chain must not be empty".to_string()));
+    }
+
+    for (key, value) in pbs {
+        if !(value.is_string() || value.is_number() || value.is_boolean()) {
+            return Err(ApiError::InvalidData(format!(
+                "pbs.{} must be a string, number or boolean",
+                key
+            )));
+        }
+    }
+
+    let mut relay_ids = HashSet::new();
+    for relay in relays {
+        if !relay_ids.insert(relay.id.as_str()) {
+            return Err(ApiError::InvalidData(format!("Duplicate relay id '{}'", relay.id)));
+        }
+        let url = url::Url::parse(&relay.url)
+            .map_err(|e| ApiError::InvalidData(format!("Invalid relay URL '{}': {}", relay.url, e)))?;
+        if !matches!(url.scheme(), "http" | "https") || !url.username().is_empty() {
+            return Err(ApiError::InvalidData(format!(
+                "Relay URL '{}' must be http(s) without credentials; the public key is added on render",
+                relay.url
+            )));
+        }
+    }
+
+    let mut mux_ids = HashSet::new();
+    for mux in muxes {
+        if !mux_ids.insert(mux.id.as_str()) {
+            return Err(ApiError::InvalidData(format!("Duplicate mux id '{}'", mux.id)));
+        }
+        match (&mux.mux, mux.registry, mux.node_operator_id) {
+            (Some(_), None, None) | (None, Some(_), Some(_)) => {}
+            _ => {
+                return Err(ApiError::InvalidData(format!(
+                    "Mux '{}' needs either `mux` or both `registry` and `node_operator_id`",
+                    mux.id
+                )))
+            }
+        }
+        if let Some(unknown) = mux.relays.iter().find(|id| !relay_ids.contains(id.as_str())) {
+            return Err(ApiError::InvalidData(format!(
+                "Mux '{}' uses unknown relay id '{}'",
+                mux.id, unknown
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Every mux config the muxes take keys from must exist
+async fn ensure_mux_configs_exist(conn: &mut PgConnection, muxes: &[PbsMux]) -> Result<(), ApiError> {
+    let mux_names: Vec<String> = muxes.iter().filter_map(|m| m.mux.clone()).collect();
+    if mux_names.is_empty() {
+        return Ok(());
+    }
+    let existing: Vec<String> =
+        sqlx::query_scalar!("SELECT name FROM commit_boost_mux_configs WHERE name = ANY($1)", &mux_names)
+            .fetch_all(&mut *conn)
+            .await?;
+    let missing: Vec<&str> = mux_names
+        .iter()
+        .filter(|name| !existing.contains(name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(ApiError::InvalidData(format!(
+            "Unknown mux config(s): {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// PBS config with its relays and muxes
+async fn load(conn: &mut PgConnection, name: &str) -> Result<Option<StoredPbsConfig>, ApiError> {
+    let config = sqlx::query_as!(
+        CommitBoostPbsConfig,
+        r#"SELECT name, chain, pbs as "pbs: _", id, created_at, updated_at FROM commit_boost_pbs_configs WHERE name = $1"#,
+        name
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(config) = config else {
+        return Ok(None);
+    };
+
+    let relays = sqlx::query_as!(
+        CommitBoostPbsRelay,
+        r#"SELECT id, config_name, relay_id, url, public_key as "public_key: _"
+           FROM commit_boost_pbs_relays WHERE config_name = $1 ORDER BY id"#,
+        name
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let muxes = sqlx::query_as!(
+        CommitBoostPbsMux,
+        "SELECT id, config_name, mux_id, mux_name, registry, node_operator_id, relay_ids
+         FROM commit_boost_pbs_muxes WHERE config_name = $1 ORDER BY id",
+        name
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(Some(StoredPbsConfig { config, relays, muxes }))
+}
+
+async fn insert_relays_and_muxes(
+    conn: &mut PgConnection,
+    name: &str,
+    relays: &[PbsRelay],
+    muxes: &[PbsMux],
+) -> Result<(), ApiError> {
+    for relay in relays {
+        sqlx::query!(
+            "INSERT INTO commit_boost_pbs_relays (config_name, relay_id, url, public_key)
+             VALUES ($1, $2, $3, $4)",
+            name,
+            relay.id,
+            relay.url,
+            relay.public_key as _
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    for mux in muxes {
+        sqlx::query!(
+            "INSERT INTO commit_boost_pbs_muxes
+                 (config_name, mux_id, mux_name, registry, node_operator_id, relay_ids)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            name,
+            mux.id,
+            mux.mux,
+            mux.registry.map(|r| r.as_str()),
+            mux.node_operator_id,
+            &mux.relays
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+impl PbsRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM commit_boost_pbs_configs WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self) -> Result<i64, ApiError> {
+        Ok(sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM commit_boost_pbs_configs"#)
+            .fetch_one(self)
+            .await?)
+    }
+
+    async fn list(&self, page: PageRequest<'_>) -> Result<Vec<StoredPbsConfig>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT name FROM commit_boost_pbs_configs {} LIMIT $1 OFFSET $2",
+            order_by
+        );
+        let names: Vec<String> = sqlx::query_scalar(&data_sql)
+            .bind(page.limit)
+            .bind(page.offset)
+            .fetch_all(self)
+            .await?;
+
+        let mut conn = self.acquire().await?;
+        let mut configs = Vec::with_capacity(names.len());
+        for name in names {
+            // Deleted since the page was read
+            if let Some(stored) = load(&mut conn, &name).await? {
+                configs.push(stored);
+            }
+        }
+        Ok(configs)
+    }
+
+    async fn find(&self, name: &str) -> Result<Option<StoredPbsConfig>, ApiError> {
+        load(&mut *self.acquire().await?, name).await
+    }
+
+    async fn mux_keys(&self, mux_name: &str) -> Result<Vec<BlsPubkey>, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT public_key as "public_key: BlsPubkey" FROM commit_boost_mux_keys WHERE mux_name = $1 ORDER BY id"#,
+            mux_name
+        )
+        .fetch_all(self)
+        .await?)
+    }
+
+    async fn insert(&self, req: &CreatePbsConfigRequest) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        ensure_mux_configs_exist(&mut tx, &req.muxes).await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO commit_boost_pbs_configs (name, chain, pbs) VALUES ($1, $2, $3)
+             ON CONFLICT (name) DO NOTHING",
+            req.name,
+            req.chain,
+            sqlx::types::Json(&req.pbs) as _
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The unique name constraint decides races between concurrent creates
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        insert_relays_and_muxes(&mut tx, &req.name, &req.relays, &req.muxes).await?;
+
+        let after = load(&mut tx, &req.name).await?.as_ref().map(audit_values);
+        tx.commit().await?;
+        Ok(after)
+    }
+
+    async fn replace(
+        &self,
+        name: &str,
+        req: &UpdatePbsConfigRequest,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        let found: Option<String> =
+            sqlx::query_scalar!("SELECT name FROM commit_boost_pbs_configs WHERE name = $1 FOR UPDATE", name)
+                .fetch_optional(&mut *tx)
+                .await?;
+        if found.is_none() {
+            return Ok(None);
+        }
+        let before = load(&mut tx, name).await?.as_ref().map(audit_values).unwrap_or_default();
+
+        ensure_mux_configs_exist(&mut tx, &req.muxes).await?;
+
+        sqlx::query!(
+            "UPDATE commit_boost_pbs_configs SET chain = $2, pbs = $3 WHERE name = $1",
+            name,
+            req.chain,
+            sqlx::types::Json(&req.pbs) as _
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Replace all relays and muxes
+        sqlx::query!("DELETE FROM commit_boost_pbs_muxes WHERE config_name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM commit_boost_pbs_relays WHERE config_name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+        insert_relays_and_muxes(&mut tx, name, &req.relays, &req.muxes).await?;
+
+        let after = load(&mut tx, name).await?.as_ref().map(audit_values).unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        let Some(before) = load(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query!("DELETE FROM commit_boost_pbs_configs WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(audit_values(&before)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory PBS configs keyed by name, with the keys of mux configs
+    #[derive(Default)]
+    struct MockRepository {
+        configs: Mutex<HashMap<String, StoredPbsConfig>>,
+        mux_keys: HashMap<String, Vec<BlsPubkey>>,
+    }
+
+    impl MockRepository {
+        fn store(&self, name: &str, chain: &str, pbs: &PbsSettings, relays: &[PbsRelay], muxes: &[PbsMux]) {
+            let config = CommitBoostPbsConfig {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                chain: chain.to_string(),
+                pbs: sqlx::types::Json(pbs.clone()),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let relays = relays
+                .iter()
+                .enumerate()
+                .map(|(i, relay)| CommitBoostPbsRelay {
+                    id: i as i32,
+                    config_name: name.to_string(),
+                    relay_id: relay.id.clone(),
+                    url: relay.url.clone(),
+                    public_key: relay.public_key.clone(),
+                })
+                .collect();
+            let muxes = muxes
+                .iter()
+                .enumerate()
+                .map(|(i, mux)| CommitBoostPbsMux {
+                    id: i as i32,
+                    config_name: name.to_string(),
+                    mux_id: mux.id.clone(),
+                    mux_name: mux.mux.clone(),
+                    registry: mux.registry.map(|r| r.as_str().to_string()),
+                    node_operator_id: mux.node_operator_id,
+                    relay_ids: mux.relays.clone(),
+                })
+                .collect();
+            let stored = StoredPbsConfig { config, relays, muxes };
+            self.configs.lock().unwrap().insert(name.to_string(), stored);
+        }
+
+        fn snapshot(&self, name: &str) -> Option<AuditValues> {
+            self.configs.lock().unwrap().get(name).map(audit_values)
+        }
+
+        fn check_muxes(&self, muxes: &[PbsMux]) -> Result<(), ApiError> {
+            match muxes.iter().filter_map(|m| m.mux.as_ref()).find(|n| !self.mux_keys.contains_key(*n)) {
+                Some(unknown) => Err(ApiError::InvalidData(format!("Unknown mux config(s): {}", unknown))),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl PbsRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.configs.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self) -> Result<i64, ApiError> {
+            Ok(self.configs.lock().unwrap().len() as i64)
+        }
+
+        async fn list(&self, page: PageRequest<'_>) -> Result<Vec<StoredPbsConfig>, ApiError> {
+            let mut configs: Vec<StoredPbsConfig> = self.configs.lock().unwrap().values().cloned().collect();
+            configs.sort_by(|a, b| a.config.name.cmp(&b.config.name));
+            Ok(configs.into_iter().skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn find(&self, name: &str) -> Result<Option<StoredPbsConfig>, ApiError> {
+            Ok(self.configs.lock().unwrap().get(name).cloned())
+        }
+
+        async fn mux_keys(&self, mux_name: &str) -> Result<Vec<BlsPubkey>, ApiError> {
+            Ok(self.mux_keys.get(mux_name).cloned().unwrap_or_default())
+        }
+
+        async fn insert(&self, req: &CreatePbsConfigRequest) -> Result<Option<AuditValues>, ApiError> {
+            self.check_muxes(&req.muxes)?;
+            if self.exists(&req.name).await? {
+                return Ok(None);
+            }
+            self.store(&req.name, &req.chain, &req.pbs, &req.relays, &req.muxes);
+            Ok(self.snapshot(&req.name))
+        }
+
+        async fn replace(
+            &self,
+            name: &str,
+            req: &UpdatePbsConfigRequest,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            self.check_muxes(&req.muxes)?;
+            self.store(name, &req.chain, &req.pbs, &req.relays, &req.muxes);
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn delete(&self, name: &str) -> Result<Option<AuditValues>, ApiError> {
+            let before = self.snapshot(name);
+            self.configs.lock().unwrap().remove(name);
+            Ok(before)
+        }
+    }
+
+    fn create_request(muxes: serde_json::Value) -> CreatePbsConfigRequest {
+        serde_json::from_value(serde_json::json!({
+            "name": "main",
+            "chain": "Mainnet",
+            "pbs": { "port": 18550 },
+            "relays": [
+                { "id": "one", "url": "https://one.example.com", "public_key": TEST_KEY },
+                { "id": "two", "url": "https://two.example.com", "public_key": TEST_KEY }
+            ],
+            "muxes": muxes
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_replace_and_delete_config() {
+        let mut repo = MockRepository::default();
+        repo.mux_keys.insert("lido".to_string(), vec![TEST_KEY.parse().unwrap()]);
+        let req = create_request(serde_json::json!([{ "id": "m", "mux": "lido", "relays": ["one"] }]));
+
+        let (response, changes) = create(&repo, &req).await.unwrap();
+        assert_eq!(response.relays.len(), 2);
+        assert_eq!(changes.muxes.unwrap().to, Some(vec!["m".to_string()]));
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::Conflict(_))));
+        assert_eq!(referenced_muxes(&repo, "main").await.unwrap(), ["lido"]);
+
+        let update_req: UpdatePbsConfigRequest =
+            serde_json::from_value(serde_json::json!({ "chain": "Holesky" })).unwrap();
+        let (response, changes) = update(&repo, "main", &update_req).await.unwrap();
+        assert!(response.relays.is_empty());
+        assert_eq!(changes.chain.unwrap().to.as_deref(), Some("Holesky"));
+        assert!(matches!(update(&repo, "other", &update_req).await, Err(ApiError::NotFound(_))));
+
+        delete(&repo, "main").await.unwrap();
+        assert!(matches!(ensure_exists(&repo, "main").await, Err(ApiError::NotFound(_))));
+        assert!(referenced_muxes(&repo, "main").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_configs_are_refused() {
+        let repo = MockRepository::default();
+        let cases = [
+            serde_json::json!([{ "id": "m", "mux": "lido", "registry": "lido", "node_operator_id": 1 }]),
+            serde_json::json!([{ "id": "m", "registry": "lido", "node_operator_id": 1, "relays": ["three"] }]),
+            serde_json::json!([{ "id": "m", "mux": "unknown" }]),
+        ];
+        for muxes in cases {
+            let refused = create(&repo, &create_request(muxes)).await;
+            assert!(matches!(refused, Err(ApiError::InvalidData(_))), "{:?}", refused);
+        }
+        assert!(!repo.exists("main").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_render_inlines_mux_keys_and_skips_disabled_relays() {
+        let mut repo = MockRepository::default();
+        repo.mux_keys.insert("lido".to_string(), vec![TEST_KEY.parse().unwrap()]);
+        let req = create_request(serde_json::json!([{ "id": "m", "mux": "lido", "relays": ["one", "two"] }]));
+        create(&repo, &req).await.unwrap();
+
+        let disabled = HashSet::from(["https://two.example.com".to_string()]);
+        let (toml, key_count) = render(&repo, "main", &disabled).await.unwrap();
+        assert_eq!(key_count, 1);
+        assert!(toml.contains("chain = \"Mainnet\""));
+        assert!(toml.contains(&format!("https://{}@one.example.com", TEST_KEY)));
+        assert!(!toml.contains("two.example.com"));
+        assert!(matches!(render(&repo, "other", &disabled).await, Err(ApiError::NotFound(_))));
+    }
+}
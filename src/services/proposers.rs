@@ -0,0 +1,890 @@
+// services/proposers.rs - Proposer lookups, listing and writes
+use super::{placeholders, relay_map, PageRequest};
+use super::relay_sets::ensure_relay_sets_exist;
+use crate::addresses::normalize_hex;
+use crate::audit::{AuditChanges, AuditValues, Change};
+use crate::errors::ApiError;
+use crate::handlers::labels::labels_condition;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::handlers::time_range::TimeRangeFilters;
+use crate::models::{Labels, VouchProposer, VouchProposerRelay};
+use crate::schema::{
+    CreateOrUpdateProposerRequest, PaginatedResponse, ProposerListItem, ProposerRelayConfig, ProposerResponse,
+};
+use crate::units::normalize_min_value_filter;
+use serde::Deserialize;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::future::Future;
+use utoipa::{IntoParams, ToSchema};
+
+/// Proposer field that `?missing=` looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingField {
+    FeeRecipient,
+    GasLimit,
+    Relays,
+}
+
+/// Filters accepted by the proposer count endpoint (the list filters without paging)
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ProposerCountFilters {
+    pub public_key: Option<String>,
+    pub fee_recipient: Option<String>,
+    /// Exclude proposers with this fee recipient (those without their own one are kept)
+    pub fee_recipient_ne: Option<String>,
+    pub gas_limit: Option<String>,
+    pub min_value: Option<String>,
+    pub reset_relays: Option<bool>,
+    /// Filter by relay URL (prefix match)
+    pub relay_url: Option<String>,
+    /// Filter by relay min_value (exact match)
+    pub relay_min_value: Option<String>,
+    /// Filter by relay disabled status
+    pub relay_disabled: Option<bool>,
+    /// Filter by decommissioned status
+    pub decommissioned: Option<bool>,
+    /// Only proposers without their own value for this field, so they rely on the default
+    pub missing: Option<MissingField>,
+    /// Set from `label.<key>` query parameters by the handler
+    #[serde(skip)]
+    #[param(ignore)]
+    pub labels: Labels,
+    /// Set from the `TimeRangeFilters` query parameters by the handler
+    #[serde(skip)]
+    #[param(ignore)]
+    pub time_range: TimeRangeFilters,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("public_key", "p.public_key"),
+        ("created_at", "p.created_at"),
+        ("updated_at", "p.updated_at"),
+    ],
+    default: ("p.created_at", SortOrder::Desc),
+    tiebreak: "p.public_key",
+};
+
+/// Storage of proposers and their relays
+pub trait ProposerRepository: Sync {
+    fn exists(&self, public_key: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    /// Number of proposers matching the filters
+    fn count(&self, filters: &ProposerCountFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the proposers matching the filters
+    fn list(
+        &self,
+        filters: &ProposerCountFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<VouchProposer>, ApiError>> + Send;
+
+    /// Relays of the given proposers
+    fn relays_of(
+        &self,
+        public_keys: &[String],
+    ) -> impl Future<Output = Result<Vec<VouchProposerRelay>, ApiError>> + Send;
+
+    /// Relay counts of the given proposers, leaving out those without relays
+    fn relay_counts(
+        &self,
+        public_keys: &[String],
+    ) -> impl Future<Output = Result<HashMap<String, i64>, ApiError>> + Send;
+
+    /// The proposer with its relays
+    fn find(
+        &self,
+        public_key: &str,
+    ) -> impl Future<Output = Result<Option<(VouchProposer, Vec<VouchProposerRelay>)>, ApiError>> + Send;
+
+    /// Replace the proposer and its relays, creating it when missing. Returns
+    /// whether it was created and its audited values before and after.
+    fn upsert(
+        &self,
+        public_key: &str,
+        req: &CreateOrUpdateProposerRequest,
+    ) -> impl Future<Output = Result<(bool, AuditValues, AuditValues), ApiError>> + Send;
+
+    /// Delete the proposer, returning its audited values
+    fn delete(&self, public_key: &str) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Set or clear `decommissioned_at`, returning the audited values before and after
+    fn set_decommissioned(
+        &self,
+        public_key: &str,
+        decommissioned: bool,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Set the `disabled` flag of one relay, returning its previous value
+    fn set_relay_disabled(
+        &self,
+        public_key: &str,
+        url: &str,
+        disabled: bool,
+    ) -> impl Future<Output = Result<Option<bool>, ApiError>> + Send;
+}
+
+fn not_found(public_key: &str) -> ApiError {
+    ApiError::NotFound(format!("Proposer '{}' not found", public_key))
+}
+
+pub async fn ensure_exists(repo: &impl ProposerRepository, public_key: &str) -> Result<(), ApiError> {
+    if !repo.exists(public_key).await? {
+        return Err(not_found(public_key));
+    }
+    Ok(())
+}
+
+/// The proposer with its relays, min values in wei
+pub async fn get(repo: &impl ProposerRepository, public_key: &str) -> Result<ProposerResponse, ApiError> {
+    let (proposer, relays) = repo.find(public_key).await?.ok_or_else(|| not_found(public_key))?;
+    Ok(ProposerResponse {
+        public_key: proposer.public_key,
+        id: proposer.id,
+        fee_recipient: proposer.fee_recipient,
+        gas_limit: proposer.gas_limit,
+        min_value: proposer.min_value,
+        builder_enabled: proposer.builder_enabled,
+        builder_boost_factor: proposer.builder_boost_factor,
+        reset_relays: proposer.reset_relays,
+        note: proposer.note,
+        labels: proposer.labels.0,
+        relay_sets: proposer.relay_sets,
+        relays: relay_map(relays, |r| r.url.clone()),
+        decommissioned: proposer.decommissioned_at.is_some(),
+        decommissioned_at: proposer.decommissioned_at,
+        created_at: proposer.created_at,
+        updated_at: proposer.updated_at,
+    })
+}
+
+pub async fn count(repo: &impl ProposerRepository, filters: &ProposerCountFilters) -> Result<i64, ApiError> {
+    repo.count(filters).await
+}
+
+/// One page of the proposers matching the filters with their relay counts, and
+/// their relays too with `include_relays`; min values in wei
+pub async fn list(
+    repo: &impl ProposerRepository,
+    filters: &ProposerCountFilters,
+    page: PageRequest<'_>,
+    include_relays: bool,
+) -> Result<PaginatedResponse<ProposerListItem>, ApiError> {
+    let proposers = repo.list(filters, page).await?;
+    let total = repo.count(filters).await?;
+
+    let public_keys: Vec<String> = proposers.iter().map(|p| p.public_key.to_string()).collect();
+    let mut relays: HashMap<String, HashMap<String, ProposerRelayConfig>> = HashMap::new();
+    let counts = if public_keys.is_empty() {
+        HashMap::new()
+    } else if include_relays {
+        for relay in repo.relays_of(&public_keys).await? {
+            relays
+                .entry(relay.proposer_public_key.to_string())
+                .or_default()
+                .insert(relay.url.clone(), relay.into());
+        }
+        relays.iter().map(|(pk, relays)| (pk.clone(), relays.len() as i64)).collect()
+    } else {
+        repo.relay_counts(&public_keys).await?
+    };
+
+    let data = proposers
+        .into_iter()
+        .map(|p| {
+            let pk = p.public_key.to_string();
+            let mut item: ProposerListItem = p.into();
+            item.relay_count = counts.get(&pk).copied().unwrap_or(0);
+            if include_relays {
+                item.relays = Some(relays.remove(&pk).unwrap_or_default());
+            }
+            item
+        })
+        .collect();
+
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create or replace a proposer with its relays. Returns the proposer with min
+/// values in wei, whether it was created and the changes to audit.
+pub async fn put(
+    repo: &impl ProposerRepository,
+    public_key: &str,
+    req: &CreateOrUpdateProposerRequest,
+) -> Result<(ProposerResponse, bool, AuditChanges), ApiError> {
+    let (created, before, after) = repo.upsert(public_key, req).await?;
+    Ok((get(repo, public_key).await?, created, AuditChanges::diff(&before, &after)))
+}
+
+/// Delete a proposer with its relays, returning the changes to audit
+pub async fn delete(repo: &impl ProposerRepository, public_key: &str) -> Result<AuditChanges, ApiError> {
+    let before = repo.delete(public_key).await?.ok_or_else(|| not_found(public_key))?;
+    Ok(AuditChanges::diff(&before, &AuditValues::default()))
+}
+
+/// Decommission or reactivate a proposer. Repeating the current state keeps the
+/// original timestamp and returns no changes to audit.
+pub async fn set_decommissioned(
+    repo: &impl ProposerRepository,
+    public_key: &str,
+    decommissioned: bool,
+) -> Result<(ProposerResponse, Option<AuditChanges>), ApiError> {
+    let (before, after) = repo
+        .set_decommissioned(public_key, decommissioned)
+        .await?
+        .ok_or_else(|| not_found(public_key))?;
+    let changes = (before != after).then(|| AuditChanges::diff(&before, &after));
+    Ok((get(repo, public_key).await?, changes))
+}
+
+/// Disable or enable one relay of a proposer, leaving its other fields untouched
+pub async fn set_relay_disabled(
+    repo: &impl ProposerRepository,
+    public_key: &str,
+    url: &str,
+    disabled: bool,
+) -> Result<(ProposerResponse, AuditChanges), ApiError> {
+    let was_disabled = repo
+        .set_relay_disabled(public_key, url, disabled)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Relay '{}' not found for proposer '{}'", url, public_key))
+        })?;
+    let changes = AuditChanges {
+        disabled: Change::between(&Some(was_disabled), &Some(disabled)),
+        relay_url: Some(url.to_string()),
+        ..Default::default()
+    };
+    Ok((get(repo, public_key).await?, changes))
+}
+
+/// Audited values of a proposer and its relay count, locking the proposer row
+pub(crate) async fn audit_snapshot(
+    conn: &mut PgConnection,
+    public_key: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let proposer = sqlx::query_as!(
+        VouchProposer,
+        r#"SELECT public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels as "labels: _", relay_sets, decommissioned_at, id, created_at, updated_at
+           FROM vouch_proposers WHERE public_key = $1 FOR UPDATE"#,
+        public_key
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(proposer) = proposer else {
+        return Ok(None);
+    };
+
+    let relays_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM vouch_proposer_relays WHERE proposer_public_key = $1"#,
+        public_key
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(AuditValues {
+        relays_count: Some(relays_count),
+        ..AuditValues::from(&proposer)
+    }))
+}
+
+impl ProposerCountFilters {
+    /// SQL WHERE clause over `vouch_proposers p` (empty when no filter is set)
+    fn where_clause(&self) -> String {
+        let mut conditions = Vec::new();
+
+        if let Some(ref pk) = self.public_key {
+            let pk = normalize_hex(pk);
+            conditions.push(format!("p.public_key LIKE '{}%'", pk.replace('\'', "''")));
+        }
+        if let Some(ref fr) = self.fee_recipient {
+            let fr = normalize_hex(fr);
+            conditions.push(format!("p.fee_recipient = '{}'", fr.replace('\'', "''")));
+        }
+        if let Some(ref fr) = self.fee_recipient_ne {
+            let fr = normalize_hex(fr);
+            conditions.push(format!("p.fee_recipient IS DISTINCT FROM '{}'", fr.replace('\'', "''")));
+        }
+        if let Some(ref gl) = self.gas_limit {
+            conditions.push(format!("p.gas_limit = '{}'", gl.replace('\'', "''")));
+        }
+        if let Some(ref mv) = self.min_value {
+            let mv = normalize_min_value_filter(mv);
+            conditions.push(format!("p.min_value = '{}'", mv.replace('\'', "''")));
+        }
+        if let Some(rr) = self.reset_relays {
+            conditions.push(format!(
+                "p.reset_relays = {}",
+                if rr { "true" } else { "false" }
+            ));
+        }
+        if let Some(decommissioned) = self.decommissioned {
+            conditions.push(format!(
+                "p.decommissioned_at IS {}NULL",
+                if decommissioned { "NOT " } else { "" }
+            ));
+        }
+        match self.missing {
+            Some(MissingField::FeeRecipient) => conditions.push("p.fee_recipient IS NULL".to_string()),
+            Some(MissingField::GasLimit) => conditions.push("p.gas_limit IS NULL".to_string()),
+            // Relay sets count as the proposer's own relays
+            Some(MissingField::Relays) => conditions.push(
+                "cardinality(p.relay_sets) = 0 AND NOT EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key)".to_string(),
+            ),
+            None => {}
+        }
+        // Relay filters using EXISTS subquery
+        if let Some(ref relay_url) = self.relay_url {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.url LIKE '{}%')",
+                relay_url.replace('\'', "''")
+            ));
+        }
+        if let Some(ref relay_min_value) = self.relay_min_value {
+            let relay_min_value = normalize_min_value_filter(relay_min_value);
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.min_value = '{}')",
+                relay_min_value.replace('\'', "''")
+            ));
+        }
+        if let Some(relay_disabled) = self.relay_disabled {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_proposer_relays r WHERE r.proposer_public_key = p.public_key AND r.disabled = {})",
+                if relay_disabled { "true" } else { "false" }
+            ));
+        }
+
+        if let Some(condition) = labels_condition("p.labels", &self.labels) {
+            conditions.push(condition);
+        }
+        conditions.extend(self.time_range.conditions("p"));
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+impl ProposerRepository for PgPool {
+    async fn exists(&self, public_key: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM vouch_proposers WHERE public_key = $1) AS "exists!""#,
+            public_key
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, filters: &ProposerCountFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM vouch_proposers p {}", filters.where_clause());
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, filters: &ProposerCountFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposer>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT p.public_key, p.fee_recipient, p.gas_limit, p.min_value, p.builder_enabled, p.builder_boost_factor, p.reset_relays, p.note, p.labels, p.relay_sets, p.decommissioned_at, p.id, p.created_at, p.updated_at
+             FROM vouch_proposers p {}
+             {}
+             LIMIT {} OFFSET {}",
+            filters.where_clause(), order_by, page.limit, page.offset
+        );
+        Ok(sqlx::query_as::<_, VouchProposer>(&data_sql).fetch_all(self).await?)
+    }
+
+    async fn relays_of(&self, public_keys: &[String]) -> Result<Vec<VouchProposerRelay>, ApiError> {
+        let relays_sql = format!(
+            "SELECT id, proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled
+             FROM vouch_proposer_relays WHERE proposer_public_key IN ({})",
+            placeholders(public_keys.len())
+        );
+        let mut query = sqlx::query_as::<_, VouchProposerRelay>(&relays_sql);
+        for pk in public_keys {
+            query = query.bind(pk);
+        }
+        Ok(query.fetch_all(self).await?)
+    }
+
+    async fn relay_counts(&self, public_keys: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+        let counts_sql = format!(
+            "SELECT proposer_public_key, COUNT(*) FROM vouch_proposer_relays
+             WHERE proposer_public_key IN ({}) GROUP BY proposer_public_key",
+            placeholders(public_keys.len())
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&counts_sql);
+        for pk in public_keys {
+            query = query.bind(pk);
+        }
+        Ok(query.fetch_all(self).await?.into_iter().collect())
+    }
+
+    async fn find(&self, public_key: &str) -> Result<Option<(VouchProposer, Vec<VouchProposerRelay>)>, ApiError> {
+        let proposer = sqlx::query_as!(
+            VouchProposer,
+            r#"SELECT public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value, builder_enabled, builder_boost_factor, reset_relays, note, labels as "labels: _", relay_sets, decommissioned_at, id, created_at, updated_at
+               FROM vouch_proposers WHERE public_key = $1"#,
+            public_key
+        )
+        .fetch_optional(self)
+        .await?;
+        let Some(proposer) = proposer else {
+            return Ok(None);
+        };
+
+        let relays = sqlx::query_as!(
+            VouchProposerRelay,
+            r#"SELECT id, proposer_public_key as "proposer_public_key: _", url, public_key as "public_key: _",
+                      fee_recipient as "fee_recipient: _", gas_limit, min_value, disabled
+               FROM vouch_proposer_relays WHERE proposer_public_key = $1"#,
+            public_key
+        )
+        .fetch_all(self)
+        .await?;
+
+        Ok(Some((proposer, relays)))
+    }
+
+    async fn upsert(
+        &self,
+        public_key: &str,
+        req: &CreateOrUpdateProposerRequest,
+    ) -> Result<(bool, AuditValues, AuditValues), ApiError> {
+        let mut tx = self.begin().await?;
+        ensure_relay_sets_exist(&mut tx, &req.relay_sets).await?;
+
+        // Read current values for the audit trail
+        let before = audit_snapshot(&mut tx, public_key).await?;
+
+        // Single-statement upsert: concurrent PUTs for the same key serialize on the
+        // row lock instead of racing between a read and an INSERT. `xmax = 0` holds
+        // only for a freshly inserted row, which tells create from update.
+        let is_new = sqlx::query_scalar!(
+            r#"INSERT INTO vouch_proposers
+                 (public_key, fee_recipient, gas_limit, min_value, reset_relays, builder_enabled, builder_boost_factor, note, labels, relay_sets)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (public_key) DO UPDATE
+             SET fee_recipient = EXCLUDED.fee_recipient, gas_limit = EXCLUDED.gas_limit,
+                 min_value = EXCLUDED.min_value, reset_relays = EXCLUDED.reset_relays,
+                 builder_enabled = EXCLUDED.builder_enabled, builder_boost_factor = EXCLUDED.builder_boost_factor,
+                 note = EXCLUDED.note, labels = EXCLUDED.labels, relay_sets = EXCLUDED.relay_sets
+             RETURNING (xmax = 0) AS "inserted!""#,
+            public_key,
+            req.fee_recipient as _,
+            req.gas_limit,
+            req.min_value,
+            req.reset_relays,
+            req.builder_enabled,
+            req.builder_boost_factor,
+            req.note,
+            sqlx::types::Json(&req.labels) as _,
+            &req.relay_sets
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Handle relays - delete existing and insert new
+        sqlx::query!("DELETE FROM vouch_proposer_relays WHERE proposer_public_key = $1", public_key)
+            .execute(&mut *tx)
+            .await?;
+
+        if let Some(relays) = &req.relays {
+            for (url, relay) in relays {
+                sqlx::query!(
+                    "INSERT INTO vouch_proposer_relays
+                     (proposer_public_key, url, public_key, fee_recipient, gas_limit, min_value, disabled)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                    public_key,
+                    url,
+                    relay.public_key as _,
+                    relay.fee_recipient as _,
+                    relay.gas_limit,
+                    relay.min_value,
+                    relay.disabled
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        let after = audit_snapshot(&mut tx, public_key).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok((is_new, before.unwrap_or_default(), after))
+    }
+
+    async fn delete(&self, public_key: &str) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, public_key).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query!("DELETE FROM vouch_proposers WHERE public_key = $1", public_key)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(before))
+    }
+
+    async fn set_decommissioned(
+        &self,
+        public_key: &str,
+        decommissioned: bool,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, public_key).await? else {
+            return Ok(None);
+        };
+
+        // Keep the original timestamp when decommissioning twice
+        sqlx::query!(
+            "UPDATE vouch_proposers
+             SET decommissioned_at = CASE WHEN $2 THEN COALESCE(decommissioned_at, NOW()) END
+             WHERE public_key = $1",
+            public_key,
+            decommissioned
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let after = audit_snapshot(&mut tx, public_key).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn set_relay_disabled(&self, public_key: &str, url: &str, disabled: bool) -> Result<Option<bool>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        let was_disabled = sqlx::query_scalar!(
+            "SELECT disabled FROM vouch_proposer_relays
+             WHERE proposer_public_key = $1 AND url = $2 FOR UPDATE",
+            public_key,
+            url
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if was_disabled.is_none() {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "UPDATE vouch_proposer_relays SET disabled = $3
+             WHERE proposer_public_key = $1 AND url = $2",
+            public_key,
+            url,
+            disabled
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(was_disabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::{DateTime, Utc};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory proposers keyed by public key
+    #[derive(Default)]
+    struct MockRepository {
+        proposers: Mutex<HashMap<String, (VouchProposer, Vec<VouchProposerRelay>)>>,
+    }
+
+    impl MockRepository {
+        fn with_proposer(relay_urls: &[&str]) -> Self {
+            let public_key = TEST_KEY.parse().unwrap();
+            let proposer = VouchProposer {
+                public_key,
+                id: Uuid::nil(),
+                fee_recipient: None,
+                gas_limit: Some("30000000".to_string()),
+                min_value: None,
+                builder_enabled: None,
+                builder_boost_factor: None,
+                reset_relays: false,
+                note: None,
+                labels: Default::default(),
+                relay_sets: Vec::new(),
+                decommissioned_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let relays = relay_urls
+                .iter()
+                .map(|url| VouchProposerRelay {
+                    id: 1,
+                    proposer_public_key: TEST_KEY.parse().unwrap(),
+                    url: url.to_string(),
+                    public_key: TEST_KEY.parse().unwrap(),
+                    fee_recipient: None,
+                    gas_limit: None,
+                    min_value: None,
+                    disabled: false,
+                })
+                .collect();
+            let repo = Self::default();
+            repo.proposers.lock().unwrap().insert(TEST_KEY.to_string(), (proposer, relays));
+            repo
+        }
+
+        fn snapshot(&self, public_key: &str) -> Option<AuditValues> {
+            self.proposers.lock().unwrap().get(public_key).map(|(proposer, relays)| AuditValues {
+                relays_count: Some(relays.len() as i64),
+                ..AuditValues::from(proposer)
+            })
+        }
+
+        fn decommissioned_at(&self) -> Option<DateTime<Utc>> {
+            self.proposers.lock().unwrap()[TEST_KEY].0.decommissioned_at
+        }
+
+        /// Proposers passing the `decommissioned` filter (the only one the mock applies), by key
+        fn matching(&self, filters: &ProposerCountFilters) -> Vec<VouchProposer> {
+            let mut matching: Vec<VouchProposer> = self
+                .proposers
+                .lock()
+                .unwrap()
+                .values()
+                .map(|(proposer, _)| proposer.clone())
+                .filter(|p| filters.decommissioned.is_none_or(|d| d == p.decommissioned_at.is_some()))
+                .collect();
+            matching.sort_by_key(|p| p.public_key.to_string());
+            matching
+        }
+    }
+
+    impl ProposerRepository for MockRepository {
+        async fn exists(&self, public_key: &str) -> Result<bool, ApiError> {
+            Ok(self.proposers.lock().unwrap().contains_key(public_key))
+        }
+
+        async fn count(&self, filters: &ProposerCountFilters) -> Result<i64, ApiError> {
+            Ok(self.matching(filters).len() as i64)
+        }
+
+        async fn list(&self, filters: &ProposerCountFilters, page: PageRequest<'_>) -> Result<Vec<VouchProposer>, ApiError> {
+            let matching = self.matching(filters).into_iter();
+            Ok(matching.skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn relays_of(&self, public_keys: &[String]) -> Result<Vec<VouchProposerRelay>, ApiError> {
+            let proposers = self.proposers.lock().unwrap();
+            Ok(public_keys
+                .iter()
+                .filter_map(|pk| proposers.get(pk))
+                .flat_map(|(_, relays)| relays.clone())
+                .collect())
+        }
+
+        async fn relay_counts(&self, public_keys: &[String]) -> Result<HashMap<String, i64>, ApiError> {
+            let proposers = self.proposers.lock().unwrap();
+            Ok(public_keys
+                .iter()
+                .filter_map(|pk| proposers.get(pk).map(|(_, relays)| (pk.clone(), relays.len() as i64)))
+                .filter(|(_, count)| *count > 0)
+                .collect())
+        }
+
+        async fn find(&self, public_key: &str) -> Result<Option<(VouchProposer, Vec<VouchProposerRelay>)>, ApiError> {
+            Ok(self.proposers.lock().unwrap().get(public_key).cloned())
+        }
+
+        async fn upsert(
+            &self,
+            public_key: &str,
+            req: &CreateOrUpdateProposerRequest,
+        ) -> Result<(bool, AuditValues, AuditValues), ApiError> {
+            let before = self.snapshot(public_key);
+            let proposer = VouchProposer {
+                public_key: public_key.parse().unwrap(),
+                id: Uuid::nil(),
+                fee_recipient: req.fee_recipient.clone(),
+                gas_limit: req.gas_limit.clone(),
+                min_value: req.min_value.clone(),
+                builder_enabled: req.builder_enabled,
+                builder_boost_factor: req.builder_boost_factor.clone(),
+                reset_relays: req.reset_relays,
+                note: req.note.clone(),
+                labels: sqlx::types::Json(req.labels.clone()),
+                relay_sets: req.relay_sets.clone(),
+                decommissioned_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let relays = req
+                .relays
+                .iter()
+                .flatten()
+                .map(|(url, relay)| VouchProposerRelay {
+                    id: 1,
+                    proposer_public_key: proposer.public_key.clone(),
+                    url: url.clone(),
+                    public_key: relay.public_key.clone(),
+                    fee_recipient: relay.fee_recipient.clone(),
+                    gas_limit: relay.gas_limit.clone(),
+                    min_value: relay.min_value.clone(),
+                    disabled: relay.disabled,
+                })
+                .collect();
+            self.proposers.lock().unwrap().insert(public_key.to_string(), (proposer, relays));
+            Ok((before.is_none(), before.unwrap_or_default(), self.snapshot(public_key).unwrap()))
+        }
+
+        async fn delete(&self, public_key: &str) -> Result<Option<AuditValues>, ApiError> {
+            let before = self.snapshot(public_key);
+            self.proposers.lock().unwrap().remove(public_key);
+            Ok(before)
+        }
+
+        async fn set_decommissioned(
+            &self,
+            public_key: &str,
+            decommissioned: bool,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(public_key) else {
+                return Ok(None);
+            };
+            if let Some((proposer, _)) = self.proposers.lock().unwrap().get_mut(public_key) {
+                proposer.decommissioned_at = match decommissioned {
+                    true => proposer.decommissioned_at.or_else(|| Some(Utc::now())),
+                    false => None,
+                };
+            }
+            Ok(Some((before, self.snapshot(public_key).unwrap())))
+        }
+
+        async fn set_relay_disabled(&self, public_key: &str, url: &str, disabled: bool) -> Result<Option<bool>, ApiError> {
+            let mut proposers = self.proposers.lock().unwrap();
+            let relay = proposers
+                .get_mut(public_key)
+                .and_then(|(_, relays)| relays.iter_mut().find(|r| r.url == url));
+            Ok(relay.map(|relay| std::mem::replace(&mut relay.disabled, disabled)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_builds_response_with_relays() {
+        let repo = MockRepository::with_proposer(&["https://relay.example.com"]);
+        let response = get(&repo, TEST_KEY).await.unwrap();
+        assert_eq!(response.gas_limit.as_deref(), Some("30000000"));
+        assert!(response.relays.unwrap().contains_key("https://relay.example.com"));
+        assert!(!response.decommissioned);
+
+        let repo = MockRepository::with_proposer(&[]);
+        assert!(get(&repo, TEST_KEY).await.unwrap().relays.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_proposer_is_not_found() {
+        let repo = MockRepository::default();
+        assert!(matches!(ensure_exists(&repo, TEST_KEY).await, Err(ApiError::NotFound(_))));
+        assert!(matches!(get(&repo, TEST_KEY).await, Err(ApiError::NotFound(_))));
+        assert!(matches!(delete(&repo, TEST_KEY).await, Err(ApiError::NotFound(_))));
+        assert!(matches!(set_decommissioned(&repo, TEST_KEY, true).await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_returns_removed_values() {
+        let repo = MockRepository::with_proposer(&["https://relay.example.com"]);
+        let changes = delete(&repo, TEST_KEY).await.unwrap();
+        assert_eq!(changes.relays_count.unwrap().from, Some(1));
+        assert!(!repo.exists(TEST_KEY).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_decommission_has_no_changes() {
+        let repo = MockRepository::with_proposer(&[]);
+        let (response, changes) = set_decommissioned(&repo, TEST_KEY, true).await.unwrap();
+        assert!(response.decommissioned && changes.is_some());
+        let decommissioned_at = repo.decommissioned_at();
+
+        let (_, changes) = set_decommissioned(&repo, TEST_KEY, true).await.unwrap();
+        assert!(changes.is_none());
+        assert_eq!(repo.decommissioned_at(), decommissioned_at);
+
+        let (response, changes) = set_decommissioned(&repo, TEST_KEY, false).await.unwrap();
+        assert!(!response.decommissioned && changes.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_set_relay_disabled() {
+        let repo = MockRepository::with_proposer(&["https://relay.example.com"]);
+        let (response, changes) = set_relay_disabled(&repo, TEST_KEY, "https://relay.example.com", true)
+            .await
+            .unwrap();
+        assert!(response.relays.unwrap()["https://relay.example.com"].disabled);
+        assert_eq!(changes.relay_url.as_deref(), Some("https://relay.example.com"));
+        assert!(changes.disabled.is_some());
+
+        let missing = set_relay_disabled(&repo, TEST_KEY, "https://other.example.com", true).await;
+        assert!(matches!(missing, Err(ApiError::NotFound(message)) if message.contains("other.example.com")));
+    }
+
+    #[tokio::test]
+    async fn test_list_counts_or_embeds_relays() {
+        let repo = MockRepository::with_proposer(&["https://a.example.com", "https://b.example.com"]);
+        let filters = ProposerCountFilters::default();
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 10,
+            offset: 0,
+        };
+
+        let response = list(&repo, &filters, page, false).await.unwrap();
+        assert_eq!((response.total, response.data.len()), (1, 1));
+        assert_eq!(response.data[0].relay_count, 2);
+        assert!(response.data[0].relays.is_none());
+
+        let response = list(&repo, &filters, page, true).await.unwrap();
+        assert_eq!(response.data[0].relay_count, 2);
+        assert_eq!(response.data[0].relays.as_ref().unwrap().len(), 2);
+
+        let decommissioned = ProposerCountFilters {
+            decommissioned: Some(true),
+            ..Default::default()
+        };
+        let response = list(&repo, &decommissioned, page, true).await.unwrap();
+        assert!(response.data.is_empty() && !response.has_more);
+        assert_eq!(count(&repo, &decommissioned).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_put_creates_then_updates() {
+        let repo = MockRepository::default();
+        let mut req = CreateOrUpdateProposerRequest {
+            fee_recipient: None,
+            gas_limit: Some("30000000".to_string()),
+            min_value: None,
+            builder_enabled: None,
+            builder_boost_factor: None,
+            reset_relays: false,
+            note: None,
+            labels: Default::default(),
+            relay_sets: Vec::new(),
+            relays: None,
+        };
+
+        let (response, created, changes) = put(&repo, TEST_KEY, &req).await.unwrap();
+        assert!(created);
+        assert_eq!(response.gas_limit.as_deref(), Some("30000000"));
+        assert_eq!(changes.gas_limit.unwrap().to.as_deref(), Some("30000000"));
+
+        req.gas_limit = Some("36000000".to_string());
+        let (_, created, changes) = put(&repo, TEST_KEY, &req).await.unwrap();
+        assert!(!created);
+        let gas_limit = changes.gas_limit.unwrap();
+        assert_eq!(gas_limit.from.as_deref(), Some("30000000"));
+        assert_eq!(gas_limit.to.as_deref(), Some("36000000"));
+    }
+}
@@ -0,0 +1,633 @@
+// services/relay_sets.rs - Relay set lookups, listing, writes and expansion
+use super::PageRequest;
+use crate::audit::{AuditChanges, AuditValues};
+use crate::errors::ApiError;
+use crate::handlers::sort::{SortColumns, SortOrder};
+use crate::models::{VouchRelaySet, VouchRelaySetRelay};
+use crate::schema::{CreateRelaySetRequest, PaginatedResponse, RelayConfig, RelaySetResponse, UpdateRelaySetRequest};
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Filters of the relay set list
+#[derive(Debug, Default)]
+pub struct SetFilters {
+    /// Name prefix
+    pub name: Option<String>,
+    /// URL prefix of any relay of the set
+    pub relay_url: Option<String>,
+}
+
+const SORT_COLUMNS: SortColumns = SortColumns {
+    columns: &[
+        ("name", "s.name"),
+        ("created_at", "s.created_at"),
+        ("updated_at", "s.updated_at"),
+    ],
+    default: ("s.name", SortOrder::Asc),
+    tiebreak: "s.name",
+};
+
+/// Relays of relay sets, keyed by set name and relay URL
+pub type RelaySetRelays = HashMap<String, HashMap<String, RelayConfig>>;
+
+/// Outcome of deleting a relay set
+#[derive(Debug)]
+pub enum RelaySetDeletion {
+    NotFound,
+    /// Configs, proposers, patterns or groups reference it, nothing was deleted
+    InUse(Vec<String>),
+    /// Deleted, with its audited values
+    Deleted(Box<AuditValues>),
+}
+
+/// Storage of relay sets and their relays
+pub trait RelaySetRepository: Sync {
+    fn exists(&self, name: &str) -> impl Future<Output = Result<bool, ApiError>> + Send;
+
+    /// Number of sets matching the filters
+    fn count(&self, filters: &SetFilters) -> impl Future<Output = Result<i64, ApiError>> + Send;
+
+    /// One sorted page of the sets matching the filters
+    fn list(
+        &self,
+        filters: &SetFilters,
+        page: PageRequest<'_>,
+    ) -> impl Future<Output = Result<Vec<VouchRelaySet>, ApiError>> + Send;
+
+    /// Relays of the named sets
+    fn relays(&self, names: &[String]) -> impl Future<Output = Result<RelaySetRelays, ApiError>> + Send;
+
+    fn find(&self, name: &str) -> impl Future<Output = Result<Option<VouchRelaySet>, ApiError>> + Send;
+
+    /// Insert the set with its relays, returning its audited values; `None`
+    /// when a set of that name exists
+    fn insert(
+        &self,
+        req: &CreateRelaySetRequest,
+    ) -> impl Future<Output = Result<Option<AuditValues>, ApiError>> + Send;
+
+    /// Apply the fields the request sets, returning the audited values before and after
+    fn update(
+        &self,
+        name: &str,
+        req: &UpdateRelaySetRequest,
+    ) -> impl Future<Output = Result<Option<(AuditValues, AuditValues)>, ApiError>> + Send;
+
+    /// Delete the set unless something references it
+    fn delete(&self, name: &str) -> impl Future<Output = Result<RelaySetDeletion, ApiError>> + Send;
+}
+
+fn not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Relay set '{}' not found", name))
+}
+
+pub async fn ensure_exists(repo: &impl RelaySetRepository, name: &str) -> Result<(), ApiError> {
+    if !repo.exists(name).await? {
+        return Err(not_found(name));
+    }
+    Ok(())
+}
+
+fn response(set: VouchRelaySet, relays: Option<HashMap<String, RelayConfig>>) -> RelaySetResponse {
+    RelaySetResponse {
+        name: set.name,
+        id: set.id,
+        description: set.description,
+        relays: relays.filter(|relays| !relays.is_empty()),
+        created_at: set.created_at,
+        updated_at: set.updated_at,
+    }
+}
+
+/// The set with its relays, min values in wei
+pub async fn get(repo: &impl RelaySetRepository, name: &str) -> Result<RelaySetResponse, ApiError> {
+    let set = repo.find(name).await?.ok_or_else(|| not_found(name))?;
+    let mut relays = repo.relays(&[name.to_string()]).await?;
+    Ok(response(set, relays.remove(name)))
+}
+
+/// One page of the sets matching the filters with their relays, min values in wei
+pub async fn list(
+    repo: &impl RelaySetRepository,
+    filters: &SetFilters,
+    page: PageRequest<'_>,
+) -> Result<PaginatedResponse<RelaySetResponse>, ApiError> {
+    let total = repo.count(filters).await?;
+    let sets = repo.list(filters, page).await?;
+    let names: Vec<String> = sets.iter().map(|s| s.name.clone()).collect();
+    let mut relays = repo.relays(&names).await?;
+
+    let data = sets
+        .into_iter()
+        .map(|set| {
+            let set_relays = relays.remove(&set.name);
+            response(set, set_relays)
+        })
+        .collect();
+    Ok(PaginatedResponse::new(data, total, page.limit, page.offset))
+}
+
+/// Create a set with its relays, returning it and the changes to audit
+pub async fn create(
+    repo: &impl RelaySetRepository,
+    req: &CreateRelaySetRequest,
+) -> Result<(RelaySetResponse, AuditChanges), ApiError> {
+    let after = repo
+        .insert(req)
+        .await?
+        .ok_or_else(|| ApiError::Conflict(format!("Relay set '{}' already exists", req.name)))?;
+    let changes = AuditChanges::diff(&AuditValues::default(), &after);
+    Ok((get(repo, &req.name).await?, changes))
+}
+
+/// Update the description or replace the relays of a set, returning it and the changes to audit
+pub async fn update(
+    repo: &impl RelaySetRepository,
+    name: &str,
+    req: &UpdateRelaySetRequest,
+) -> Result<(RelaySetResponse, AuditChanges), ApiError> {
+    let (before, after) = repo.update(name, req).await?.ok_or_else(|| not_found(name))?;
+    Ok((get(repo, name).await?, AuditChanges::diff(&before, &after)))
+}
+
+/// Delete a set with its relays, returning the changes to audit
+pub async fn delete(repo: &impl RelaySetRepository, name: &str) -> Result<AuditChanges, ApiError> {
+    match repo.delete(name).await? {
+        RelaySetDeletion::NotFound => Err(not_found(name)),
+        RelaySetDeletion::InUse(users) => Err(ApiError::Conflict(format!(
+            "Relay set '{}' is referenced by: {}",
+            name,
+            users.join(", ")
+        ))),
+        RelaySetDeletion::Deleted(before) => Ok(AuditChanges::diff(&before, &AuditValues::default())),
+    }
+}
+
+/// Every name in `names` must be an existing relay set
+pub(crate) async fn ensure_relay_sets_exist(
+    conn: &mut PgConnection,
+    names: &[String],
+) -> Result<(), ApiError> {
+    if names.is_empty() {
+        return Ok(());
+    }
+    let existing: Vec<String> =
+        sqlx::query_scalar!("SELECT name FROM vouch_relay_sets WHERE name = ANY($1) FOR SHARE", names)
+            .fetch_all(&mut *conn)
+            .await?;
+    let missing: Vec<&str> = names
+        .iter()
+        .filter(|name| !existing.contains(name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(ApiError::InvalidData(format!(
+            "Unknown relay set(s): {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Relays of the named sets, keyed by set name and relay URL
+pub(crate) async fn load_relay_sets(
+    conn: &mut PgConnection,
+    names: &[String],
+) -> Result<RelaySetRelays, ApiError> {
+    let mut sets = RelaySetRelays::new();
+    if names.is_empty() {
+        return Ok(sets);
+    }
+    let relays = sqlx::query_as!(
+        VouchRelaySetRelay,
+        r#"SELECT id, set_name, url, public_key as "public_key: _", fee_recipient as "fee_recipient: _", gas_limit, min_value
+           FROM vouch_relay_set_relays WHERE set_name = ANY($1)"#,
+        names
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    for relay in relays {
+        sets.entry(relay.set_name.clone())
+            .or_default()
+            .insert(relay.url.clone(), relay.into());
+    }
+    Ok(sets)
+}
+
+/// Merge the relays of the referenced sets in order, later sets winning by URL
+pub(crate) fn expand_relay_sets(names: &[String], sets: &RelaySetRelays) -> HashMap<String, RelayConfig> {
+    names
+        .iter()
+        .filter_map(|name| sets.get(name))
+        .flat_map(|relays| relays.iter().map(|(url, relay)| (url.clone(), relay.clone())))
+        .collect()
+}
+
+async fn insert_relays(
+    conn: &mut PgConnection,
+    name: &str,
+    relays: &HashMap<String, RelayConfig>,
+) -> Result<(), ApiError> {
+    for (url, relay) in relays {
+        sqlx::query!(
+            "INSERT INTO vouch_relay_set_relays
+             (set_name, url, public_key, fee_recipient, gas_limit, min_value)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            name,
+            url,
+            relay.public_key as _,
+            relay.fee_recipient as _,
+            relay.gas_limit,
+            relay.min_value
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Audited values of a relay set (its relay count), locking the set row
+async fn audit_snapshot(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<AuditValues>, ApiError> {
+    let exists: Option<String> =
+        sqlx::query_scalar!("SELECT name FROM vouch_relay_sets WHERE name = $1 FOR UPDATE", name)
+            .fetch_optional(&mut *conn)
+            .await?;
+    if exists.is_none() {
+        return Ok(None);
+    }
+
+    let relays_count: i64 = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM vouch_relay_set_relays WHERE set_name = $1"#,
+        name
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(AuditValues {
+        relays_count: Some(relays_count),
+        ..Default::default()
+    }))
+}
+
+impl SetFilters {
+    /// SQL WHERE clause over `vouch_relay_sets s` (empty when no filter is set)
+    fn where_clause(&self) -> String {
+        let mut conditions = Vec::new();
+        if let Some(ref name) = self.name {
+            conditions.push(format!("s.name LIKE '{}%'", name.replace('\'', "''")));
+        }
+        if let Some(ref relay_url) = self.relay_url {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM vouch_relay_set_relays r WHERE r.set_name = s.name AND r.url LIKE '{}%')",
+                relay_url.replace('\'', "''")
+            ));
+        }
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+impl RelaySetRepository for PgPool {
+    async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+        Ok(sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM vouch_relay_sets WHERE name = $1) AS "exists!""#,
+            name
+        )
+        .fetch_one(self)
+        .await?)
+    }
+
+    async fn count(&self, filters: &SetFilters) -> Result<i64, ApiError> {
+        let count_sql = format!("SELECT COUNT(*) FROM vouch_relay_sets s {}", filters.where_clause());
+        Ok(sqlx::query_scalar(&count_sql).fetch_one(self).await?)
+    }
+
+    async fn list(&self, filters: &SetFilters, page: PageRequest<'_>) -> Result<Vec<VouchRelaySet>, ApiError> {
+        let order_by = SORT_COLUMNS.order_by(page.sort, page.order)?;
+        let data_sql = format!(
+            "SELECT s.name, s.description, s.id, s.created_at, s.updated_at
+             FROM vouch_relay_sets s {}
+             {}
+             LIMIT {} OFFSET {}",
+            filters.where_clause(), order_by, page.limit, page.offset
+        );
+        Ok(sqlx::query_as::<_, VouchRelaySet>(&data_sql).fetch_all(self).await?)
+    }
+
+    async fn relays(&self, names: &[String]) -> Result<RelaySetRelays, ApiError> {
+        let mut conn = self.acquire().await?;
+        load_relay_sets(&mut conn, names).await
+    }
+
+    async fn find(&self, name: &str) -> Result<Option<VouchRelaySet>, ApiError> {
+        Ok(sqlx::query_as!(
+            VouchRelaySet,
+            "SELECT name, description, id, created_at, updated_at FROM vouch_relay_sets WHERE name = $1",
+            name
+        )
+        .fetch_optional(self)
+        .await?)
+    }
+
+    async fn insert(&self, req: &CreateRelaySetRequest) -> Result<Option<AuditValues>, ApiError> {
+        let mut tx = self.begin().await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO vouch_relay_sets (name, description) VALUES ($1, $2)
+             ON CONFLICT (name) DO NOTHING",
+            req.name,
+            req.description
+        )
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        if let Some(relays) = &req.relays {
+            insert_relays(&mut tx, &req.name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, &req.name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some(after))
+    }
+
+    async fn update(
+        &self,
+        name: &str,
+        req: &UpdateRelaySetRequest,
+    ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(None);
+        };
+
+        if let Some(description) = &req.description {
+            sqlx::query!("UPDATE vouch_relay_sets SET description = $2 WHERE name = $1", name, description)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        if let Some(relays) = &req.relays {
+            sqlx::query!("DELETE FROM vouch_relay_set_relays WHERE set_name = $1", name)
+                .execute(&mut *tx)
+                .await?;
+            insert_relays(&mut tx, name, relays).await?;
+        }
+
+        let after = audit_snapshot(&mut tx, name).await?.unwrap_or_default();
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn delete(&self, name: &str) -> Result<RelaySetDeletion, ApiError> {
+        let mut tx = self.begin().await?;
+        let Some(before) = audit_snapshot(&mut tx, name).await? else {
+            return Ok(RelaySetDeletion::NotFound);
+        };
+
+        let users: Vec<String> = sqlx::query_scalar!(
+            r#"SELECT 'default config ' || name AS "user!" FROM vouch_default_configs WHERE $1 = ANY(relay_sets)
+               UNION ALL
+               SELECT 'proposer ' || public_key FROM vouch_proposers WHERE $1 = ANY(relay_sets)
+               UNION ALL
+               SELECT 'proposer pattern ' || name FROM vouch_proposer_patterns WHERE $1 = ANY(relay_sets)
+               UNION ALL
+               SELECT 'proposer group ' || name FROM vouch_proposer_groups WHERE $1 = ANY(relay_sets)
+               ORDER BY 1"#,
+            name
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if !users.is_empty() {
+            return Ok(RelaySetDeletion::InUse(users));
+        }
+
+        sqlx::query!("DELETE FROM vouch_relay_sets WHERE name = $1", name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(RelaySetDeletion::Deleted(Box::new(before)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::TEST_KEY;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// In-memory sets keyed by name, with what references them
+    #[derive(Default)]
+    struct MockRepository {
+        sets: Mutex<HashMap<String, VouchRelaySet>>,
+        relays: Mutex<RelaySetRelays>,
+        users: HashMap<String, Vec<String>>,
+    }
+
+    fn relay_config() -> RelayConfig {
+        RelayConfig {
+            public_key: TEST_KEY.parse().unwrap(),
+            fee_recipient: None,
+            gas_limit: None,
+            min_value: None,
+            disabled: false,
+        }
+    }
+
+    impl MockRepository {
+        fn add(&self, name: &str, relay_urls: &[&str]) {
+            let set = VouchRelaySet {
+                name: name.to_string(),
+                id: Uuid::new_v4(),
+                description: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            let relays = relay_urls.iter().map(|url| (url.to_string(), relay_config())).collect();
+            self.sets.lock().unwrap().insert(name.to_string(), set);
+            self.relays.lock().unwrap().insert(name.to_string(), relays);
+        }
+
+        fn snapshot(&self, name: &str) -> Option<AuditValues> {
+            self.relays.lock().unwrap().get(name).map(|relays| AuditValues {
+                relays_count: Some(relays.len() as i64),
+                ..Default::default()
+            })
+        }
+
+        /// Sets passing the `name` filter (the only one the mock applies), by name
+        fn matching(&self, filters: &SetFilters) -> Vec<VouchRelaySet> {
+            let mut matching: Vec<VouchRelaySet> = self
+                .sets
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|s| filters.name.as_ref().is_none_or(|name| s.name.starts_with(name.as_str())))
+                .cloned()
+                .collect();
+            matching.sort_by(|a, b| a.name.cmp(&b.name));
+            matching
+        }
+    }
+
+    impl RelaySetRepository for MockRepository {
+        async fn exists(&self, name: &str) -> Result<bool, ApiError> {
+            Ok(self.sets.lock().unwrap().contains_key(name))
+        }
+
+        async fn count(&self, filters: &SetFilters) -> Result<i64, ApiError> {
+            Ok(self.matching(filters).len() as i64)
+        }
+
+        async fn list(&self, filters: &SetFilters, page: PageRequest<'_>) -> Result<Vec<VouchRelaySet>, ApiError> {
+            let matching = self.matching(filters).into_iter();
+            Ok(matching.skip(page.offset as usize).take(page.limit as usize).collect())
+        }
+
+        async fn relays(&self, names: &[String]) -> Result<RelaySetRelays, ApiError> {
+            let relays = self.relays.lock().unwrap();
+            Ok(names
+                .iter()
+                .filter_map(|name| relays.get(name).map(|relays| (name.clone(), relays.clone())))
+                .collect())
+        }
+
+        async fn find(&self, name: &str) -> Result<Option<VouchRelaySet>, ApiError> {
+            Ok(self.sets.lock().unwrap().get(name).cloned())
+        }
+
+        async fn insert(&self, req: &CreateRelaySetRequest) -> Result<Option<AuditValues>, ApiError> {
+            if self.sets.lock().unwrap().contains_key(&req.name) {
+                return Ok(None);
+            }
+            self.add(&req.name, &[]);
+            if let Some(relays) = &req.relays {
+                self.relays.lock().unwrap().insert(req.name.clone(), relays.clone());
+            }
+            Ok(self.snapshot(&req.name))
+        }
+
+        async fn update(
+            &self,
+            name: &str,
+            req: &UpdateRelaySetRequest,
+        ) -> Result<Option<(AuditValues, AuditValues)>, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(None);
+            };
+            if let Some(description) = &req.description {
+                self.sets.lock().unwrap().get_mut(name).unwrap().description = Some(description.clone());
+            }
+            if let Some(relays) = &req.relays {
+                self.relays.lock().unwrap().insert(name.to_string(), relays.clone());
+            }
+            Ok(Some((before, self.snapshot(name).unwrap())))
+        }
+
+        async fn delete(&self, name: &str) -> Result<RelaySetDeletion, ApiError> {
+            let Some(before) = self.snapshot(name) else {
+                return Ok(RelaySetDeletion::NotFound);
+            };
+            if let Some(users) = self.users.get(name) {
+                return Ok(RelaySetDeletion::InUse(users.clone()));
+            }
+            self.sets.lock().unwrap().remove(name);
+            self.relays.lock().unwrap().remove(name);
+            Ok(RelaySetDeletion::Deleted(Box::new(before)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_get_and_update_relay_set() {
+        let repo = MockRepository::default();
+        let req: CreateRelaySetRequest = serde_json::from_value(serde_json::json!({
+            "name": "flashbots",
+            "relays": { "https://relay.example.com": { "public_key": TEST_KEY } }
+        }))
+        .unwrap();
+
+        let (response, changes) = create(&repo, &req).await.unwrap();
+        assert_eq!(response.relays.unwrap().len(), 1);
+        assert_eq!(changes.relays_count.unwrap().to, Some(1));
+        assert!(matches!(create(&repo, &req).await, Err(ApiError::Conflict(_))));
+
+        let update_req: UpdateRelaySetRequest =
+            serde_json::from_str(r#"{"description": "Flashbots", "relays": {}}"#).unwrap();
+        let (response, changes) = update(&repo, "flashbots", &update_req).await.unwrap();
+        assert_eq!(response.description.as_deref(), Some("Flashbots"));
+        assert!(response.relays.is_none());
+        assert_eq!(changes.relays_count.unwrap().to, Some(0));
+        assert!(matches!(update(&repo, "other", &update_req).await, Err(ApiError::NotFound(_))));
+
+        assert!(ensure_exists(&repo, "flashbots").await.is_ok());
+        assert!(matches!(get(&repo, "other").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_relay_sets() {
+        let repo = MockRepository::default();
+        for name in ["set_c", "set_a", "other", "set_b"] {
+            repo.add(name, &["https://relay.example.com"]);
+        }
+        let filters = SetFilters {
+            name: Some("set_".to_string()),
+            ..Default::default()
+        };
+        let page = PageRequest {
+            sort: None,
+            order: None,
+            limit: 2,
+            offset: 1,
+        };
+
+        let response = list(&repo, &filters, page).await.unwrap();
+        let names: Vec<&str> = response.data.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["set_b", "set_c"]);
+        assert!(response.data.iter().all(|s| s.relays.is_some()));
+        assert_eq!(response.total, 3);
+    }
+
+    #[tokio::test]
+    async fn test_delete_refuses_referenced_sets() {
+        let mut repo = MockRepository::default();
+        repo.add("used", &[]);
+        repo.add("unused", &["https://relay.example.com"]);
+        repo.users.insert("used".to_string(), vec!["proposer group lido".to_string()]);
+
+        let refused = delete(&repo, "used").await;
+        assert!(matches!(refused, Err(ApiError::Conflict(message)) if message.ends_with("proposer group lido")));
+        let changes = delete(&repo, "unused").await.unwrap();
+        assert_eq!(changes.relays_count.unwrap().from, Some(1));
+        assert!(matches!(delete(&repo, "unused").await, Err(ApiError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_expand_relay_sets_later_sets_win() {
+        let mut later = relay_config();
+        later.gas_limit = Some("30000000".to_string());
+        let sets: RelaySetRelays = HashMap::from([
+            ("a".to_string(), HashMap::from([("https://one".to_string(), relay_config())])),
+            (
+                "b".to_string(),
+                HashMap::from([("https://one".to_string(), later), ("https://two".to_string(), relay_config())]),
+            ),
+        ]);
+
+        let expanded = expand_relay_sets(&["a".to_string(), "b".to_string(), "missing".to_string()], &sets);
+        assert_eq!(expanded.len(), 2);
+        assert_eq!(expanded["https://one"].gas_limit.as_deref(), Some("30000000"));
+    }
+}
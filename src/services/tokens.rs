@@ -0,0 +1,284 @@
+// services/tokens.rs - API token management
+use crate::audit::{AuditChanges, Change};
+use crate::auth::handlers::{CreateTokenRequest, UpdateTokenRequest};
+use crate::auth::{service, AuthToken, ADMIN_SCOPE, KNOWN_SCOPES};
+use crate::errors::ApiError;
+use sqlx::PgPool;
+use std::future::Future;
+use uuid::Uuid;
+
+/// Storage of API tokens
+pub trait TokenRepository: Sync {
+    /// All tokens, ordered by a whitelisted ORDER BY clause
+    fn list(&self, order_by: &str) -> impl Future<Output = Result<Vec<AuthToken>, ApiError>> + Send;
+
+    fn get(&self, id: Uuid) -> impl Future<Output = Result<Option<AuthToken>, ApiError>> + Send;
+
+    /// Store a new token, returning it with its plaintext
+    fn create(
+        &self,
+        name: &str,
+        description: Option<&str>,
+        scopes: &[String],
+    ) -> impl Future<Output = Result<(AuthToken, String), ApiError>> + Send;
+
+    /// Update the given fields; `None` leaves a field unchanged
+    fn update(
+        &self,
+        id: Uuid,
+        request: &UpdateTokenRequest,
+        scopes: Option<&[String]>,
+    ) -> impl Future<Output = Result<Option<AuthToken>, ApiError>> + Send;
+
+    /// Whether a token was deleted
+    fn delete(&self, id: Uuid) -> impl Future<Output = Result<bool, ApiError>> + Send;
+}
+
+fn not_found(id: Uuid) -> ApiError {
+    ApiError::NotFound(format!("Token {} not found", id))
+}
+
+/// Known scopes from a request, deduplicated and always including `admin`
+pub fn token_scopes(requested: &[String]) -> Result<Vec<String>, ApiError> {
+    let mut scopes = vec![ADMIN_SCOPE.to_string()];
+    for scope in requested {
+        if !KNOWN_SCOPES.contains(&scope.as_str()) {
+            return Err(ApiError::InvalidData(format!(
+                "Unknown scope '{}', expected one of: {}",
+                scope,
+                KNOWN_SCOPES.join(", ")
+            )));
+        }
+        if !scopes.contains(scope) {
+            scopes.push(scope.clone());
+        }
+    }
+    Ok(scopes)
+}
+
+pub async fn get(repo: &impl TokenRepository, id: Uuid) -> Result<AuthToken, ApiError> {
+    repo.get(id).await?.ok_or_else(|| not_found(id))
+}
+
+/// Create a token, returning it with its plaintext and the changes to audit
+pub async fn create(
+    repo: &impl TokenRepository,
+    request: &CreateTokenRequest,
+) -> Result<(AuthToken, String, AuditChanges), ApiError> {
+    let scopes = token_scopes(&request.scopes)?;
+    let (token, plaintext) = repo.create(&request.name, request.description.as_deref(), &scopes).await?;
+    let changes = AuditChanges {
+        name: Change::set(Some(token.name.clone())),
+        active: Change::set(Some(token.active)),
+        scopes: Change::set(Some(token.scopes.clone())),
+        ..Default::default()
+    };
+    Ok((token, plaintext, changes))
+}
+
+/// Update a token on behalf of the token `caller`, which must not deactivate itself
+pub async fn update(
+    repo: &impl TokenRepository,
+    caller: Uuid,
+    id: Uuid,
+    request: &UpdateTokenRequest,
+) -> Result<(AuthToken, AuditChanges), ApiError> {
+    if request.name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::InvalidData("Token name must not be empty".to_string()));
+    }
+    // Deactivating the token in use would lock the caller out mid-session
+    if request.active == Some(false) && caller == id {
+        return Err(ApiError::InvalidData(
+            "Cannot deactivate the token used for this request".to_string(),
+        ));
+    }
+
+    let scopes = request.scopes.as_deref().map(token_scopes).transpose()?;
+    let before = get(repo, id).await?;
+    let token = repo
+        .update(id, request, scopes.as_deref())
+        .await?
+        .ok_or_else(|| not_found(id))?;
+
+    let changes = AuditChanges {
+        name: Change::between(&Some(before.name), &Some(token.name.clone())),
+        active: Change::between(&Some(before.active), &Some(token.active)),
+        scopes: Change::between(&Some(before.scopes), &Some(token.scopes.clone())),
+        ..Default::default()
+    };
+    Ok((token, changes))
+}
+
+pub async fn delete(repo: &impl TokenRepository, id: Uuid) -> Result<(), ApiError> {
+    if !repo.delete(id).await? {
+        return Err(not_found(id));
+    }
+    Ok(())
+}
+
+impl TokenRepository for PgPool {
+    async fn list(&self, order_by: &str) -> Result<Vec<AuthToken>, ApiError> {
+        service::list_tokens(self, order_by).await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<AuthToken>, ApiError> {
+        service::get_token(self, id).await
+    }
+
+    async fn create(&self, name: &str, description: Option<&str>, scopes: &[String]) -> Result<(AuthToken, String), ApiError> {
+        service::create_token(self, name, description, scopes).await
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        request: &UpdateTokenRequest,
+        scopes: Option<&[String]>,
+    ) -> Result<Option<AuthToken>, ApiError> {
+        service::update_token(
+            self,
+            id,
+            request.name.as_deref(),
+            request.description.as_deref(),
+            request.active,
+            scopes,
+        )
+        .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, ApiError> {
+        service::delete_token(self, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory tokens keyed by id
+    #[derive(Default)]
+    struct MockRepository {
+        tokens: Mutex<HashMap<Uuid, AuthToken>>,
+    }
+
+    impl TokenRepository for MockRepository {
+        async fn list(&self, _order_by: &str) -> Result<Vec<AuthToken>, ApiError> {
+            Ok(self.tokens.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn get(&self, id: Uuid) -> Result<Option<AuthToken>, ApiError> {
+            Ok(self.tokens.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn create(&self, name: &str, description: Option<&str>, scopes: &[String]) -> Result<(AuthToken, String), ApiError> {
+            let token = AuthToken {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+                description: description.map(str::to_string),
+                token_hash: "hash".to_string(),
+                created_at: Utc::now(),
+                last_used_at: None,
+                active: true,
+                scopes: scopes.to_vec(),
+            };
+            self.tokens.lock().unwrap().insert(token.id, token.clone());
+            Ok((token, "plaintext".to_string()))
+        }
+
+        async fn update(
+            &self,
+            id: Uuid,
+            request: &UpdateTokenRequest,
+            scopes: Option<&[String]>,
+        ) -> Result<Option<AuthToken>, ApiError> {
+            let mut tokens = self.tokens.lock().unwrap();
+            let Some(token) = tokens.get_mut(&id) else {
+                return Ok(None);
+            };
+            if let Some(name) = &request.name {
+                token.name = name.clone();
+            }
+            if let Some(active) = request.active {
+                token.active = active;
+            }
+            if let Some(scopes) = scopes {
+                token.scopes = scopes.to_vec();
+            }
+            Ok(Some(token.clone()))
+        }
+
+        async fn delete(&self, id: Uuid) -> Result<bool, ApiError> {
+            Ok(self.tokens.lock().unwrap().remove(&id).is_some())
+        }
+    }
+
+    fn create_request(scopes: &[&str]) -> CreateTokenRequest {
+        CreateTokenRequest {
+            name: "ci".to_string(),
+            description: None,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn update_request() -> UpdateTokenRequest {
+        UpdateTokenRequest {
+            name: None,
+            description: None,
+            active: None,
+            scopes: None,
+        }
+    }
+
+    #[test]
+    fn test_token_scopes_always_include_admin() {
+        assert_eq!(token_scopes(&[]).unwrap(), vec![ADMIN_SCOPE]);
+        let scopes = token_scopes(&["override".to_string(), "override".to_string()]).unwrap();
+        assert_eq!(scopes, vec![ADMIN_SCOPE, "override"]);
+        assert!(matches!(token_scopes(&["root".to_string()]), Err(ApiError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_scopes() {
+        let repo = MockRepository::default();
+        let (token, plaintext, changes) = create(&repo, &create_request(&["override"])).await.unwrap();
+        assert_eq!(token.scopes, vec![ADMIN_SCOPE, "override"]);
+        assert_eq!(plaintext, "plaintext");
+        assert!(changes.name.is_some());
+
+        assert!(create(&repo, &create_request(&["root"])).await.is_err());
+        assert_eq!(repo.list("").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_rules() {
+        let repo = MockRepository::default();
+        let (token, _, _) = create(&repo, &create_request(&[])).await.unwrap();
+        let caller = Uuid::new_v4();
+
+        let blank_name = UpdateTokenRequest { name: Some(" ".to_string()), ..update_request() };
+        assert!(matches!(update(&repo, caller, token.id, &blank_name).await, Err(ApiError::InvalidData(_))));
+
+        let deactivate = UpdateTokenRequest { active: Some(false), ..update_request() };
+        let own = update(&repo, token.id, token.id, &deactivate).await;
+        assert!(matches!(own, Err(ApiError::InvalidData(message)) if message.contains("used for this request")));
+
+        let (updated, changes) = update(&repo, caller, token.id, &deactivate).await.unwrap();
+        assert!(!updated.active);
+        assert!(changes.active.is_some() && changes.name.is_none());
+
+        let missing = update(&repo, caller, Uuid::new_v4(), &update_request()).await;
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_token() {
+        let repo = MockRepository::default();
+        let (token, _, _) = create(&repo, &create_request(&[])).await.unwrap();
+        assert!(delete(&repo, token.id).await.is_ok());
+        assert!(matches!(delete(&repo, token.id).await, Err(ApiError::NotFound(_))));
+        assert!(matches!(get(&repo, token.id).await, Err(ApiError::NotFound(_))));
+    }
+}
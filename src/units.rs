@@ -0,0 +1,194 @@
+// units.rs - Ether denominations for min_value
+use crate::errors::ApiError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+
+/// Denomination a `min_value` is written in; values are stored in wei and
+/// shown in ETH unless asked otherwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueUnits {
+    Wei,
+    Gwei,
+    #[default]
+    Eth,
+}
+
+impl ValueUnits {
+    /// Number of decimal places between this unit and wei
+    fn decimals(self) -> u32 {
+        match self {
+            ValueUnits::Wei => 0,
+            ValueUnits::Gwei => 9,
+            ValueUnits::Eth => 18,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ValueUnits::Wei => "wei",
+            ValueUnits::Gwei => "gwei",
+            ValueUnits::Eth => "eth",
+        }
+    }
+
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix.to_ascii_lowercase().as_str() {
+            "wei" => Some(ValueUnits::Wei),
+            "gwei" => Some(ValueUnits::Gwei),
+            // Vouch reads min_value in ETH, and the API took bare ETH amounts before units
+            "" | "eth" | "ether" => Some(ValueUnits::Eth),
+            _ => None,
+        }
+    }
+}
+
+/// `?units=` query parameter of admin reads
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct UnitsQuery {
+    /// Unit to display min_value in: eth (default), gwei or wei
+    #[serde(default)]
+    pub units: ValueUnits,
+}
+
+/// Parse an amount such as `"0.05 ETH"`, `"50000000 gwei"` or a bare ETH
+/// amount (`"0.05"`) into wei
+pub fn parse_wei(input: &str) -> Result<u128, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, suffix) = input.split_at(split);
+    let units = ValueUnits::from_suffix(suffix.trim())
+        .ok_or_else(|| format!("unknown unit '{}' (expected wei, gwei or eth)", suffix.trim()))?;
+
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err("expected a number".to_string());
+    }
+    let decimals = units.decimals() as usize;
+    if fraction.len() > decimals {
+        return Err(format!("at most {} decimal places allowed in {}", decimals, units.name()));
+    }
+
+    // Right-pad the fraction to the unit's precision so both halves are whole wei
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    if digits.contains('.') {
+        return Err("expected a number".to_string());
+    }
+    digits.parse::<u128>().map_err(|_| "value out of range".to_string())
+}
+
+/// Render a wei amount in the given unit, without trailing zeros
+pub fn format_wei(wei: u128, units: ValueUnits) -> String {
+    let scale = 10u128.pow(units.decimals());
+    let whole = wei / scale;
+    let fraction = wei % scale;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:0>width$}", fraction, width = units.decimals() as usize);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// A stored (wei) `min_value` in the given unit; values that predate
+/// normalization are left as they are
+pub fn min_value_in(value: Option<String>, units: ValueUnits) -> Option<String> {
+    match value.as_deref().map(str::parse::<u128>) {
+        Some(Ok(wei)) => Some(format_wei(wei, units)),
+        _ => value,
+    }
+}
+
+/// Canonical (wei) form of a `min_value` filter, or the raw value when it
+/// does not parse
+pub fn normalize_min_value_filter(value: &str) -> String {
+    parse_wei(value)
+        .map(|wei| wei.to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Types carrying `min_value` fields, directly or through their relays and proposers
+pub trait MinValues {
+    fn visit_min_values(&mut self, f: &mut dyn FnMut(&mut Option<String>));
+
+    /// Rewrite every `min_value` of a request to its canonical wei form
+    fn normalize_min_values(&mut self) -> Result<(), ApiError> {
+        let mut result = Ok(());
+        self.visit_min_values(&mut |value| {
+            if let (Some(v), Ok(())) = (value.as_mut(), &result) {
+                match parse_wei(v) {
+                    Ok(wei) => *v = wei.to_string(),
+                    Err(e) => result = Err(ApiError::InvalidData(format!("Invalid min_value '{}': {}", v, e))),
+                }
+            }
+        });
+        result
+    }
+
+    /// Render every stored (wei) `min_value` in the given unit
+    fn display_min_values(&mut self, units: ValueUnits) {
+        if units == ValueUnits::Wei {
+            return;
+        }
+        self.visit_min_values(&mut |value| *value = min_value_in(value.take(), units));
+    }
+}
+
+impl<T: MinValues> MinValues for Vec<T> {
+    fn visit_min_values(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+        for item in self {
+            item.visit_min_values(f);
+        }
+    }
+}
+
+impl<K, T: MinValues> MinValues for HashMap<K, T> {
+    fn visit_min_values(&mut self, f: &mut dyn FnMut(&mut Option<String>)) {
+        for item in self.values_mut() {
+            item.visit_min_values(f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_units() {
+        assert_eq!(parse_wei("50000000000000000 wei"), Ok(50_000_000_000_000_000));
+        assert_eq!(parse_wei("0.05"), Ok(50_000_000_000_000_000));
+        assert_eq!(parse_wei("1"), Ok(1_000_000_000_000_000_000));
+        assert_eq!(parse_wei("0.05 ETH"), Ok(50_000_000_000_000_000));
+        assert_eq!(parse_wei("0.05eth"), Ok(50_000_000_000_000_000));
+        assert_eq!(parse_wei("50000000 gwei"), Ok(50_000_000_000_000_000));
+        assert_eq!(parse_wei("1.5 Gwei"), Ok(1_500_000_000));
+        assert_eq!(parse_wei("2 ether"), Ok(2_000_000_000_000_000_000));
+        assert_eq!(parse_wei(".5 eth"), Ok(500_000_000_000_000_000));
+        assert_eq!(parse_wei("0 wei"), Ok(0));
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        assert!(parse_wei("").is_err());
+        assert!(parse_wei("eth").is_err());
+        assert!(parse_wei("0.0000000000000000001").is_err());
+        assert!(parse_wei("1.5 wei").is_err());
+        assert!(parse_wei("0.0000000001 gwei").is_err());
+        assert!(parse_wei("1.2.3 eth").is_err());
+        assert!(parse_wei("-1 eth").is_err());
+        assert!(parse_wei("1 finney").is_err());
+        assert!(parse_wei("1000000000000000000000 eth").is_err());
+    }
+
+    #[test]
+    fn format_units() {
+        assert_eq!(format_wei(50_000_000_000_000_000, ValueUnits::Eth), "0.05");
+        assert_eq!(format_wei(2_000_000_000_000_000_000, ValueUnits::Eth), "2");
+        assert_eq!(format_wei(1_500_000_000, ValueUnits::Gwei), "1.5");
+        assert_eq!(format_wei(42, ValueUnits::Wei), "42");
+        assert_eq!(format_wei(1, ValueUnits::Eth), "0.000000000000000001");
+    }
+}
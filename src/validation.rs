@@ -0,0 +1,108 @@
+// validation.rs - Policy bounds from the `validation` config section
+use crate::addresses::BlsPubkey;
+use crate::audit::RequestContext;
+use crate::auth::OVERRIDE_SCOPE;
+use crate::config::ValidationConfig;
+use crate::errors::ApiError;
+use tracing::warn;
+
+/// Header asking to bypass the policy bounds; honoured for the `override` scope only
+pub const OVERRIDE_HEADER: &str = "x-fee-manager-override";
+
+/// Types carrying `gas_limit` fields, directly or through their relays
+pub trait GasLimits {
+    fn visit_gas_limits(&mut self, f: &mut dyn FnMut(&mut Option<String>));
+}
+
+/// Types carrying relays keyed by URL
+pub trait RelayKeys {
+    fn visit_relay_keys(&self, f: &mut dyn FnMut(&str, &BlsPubkey));
+}
+
+/// Check every `gas_limit` of a write request: it must be an integer within
+/// the configured bounds, unless the request carries a valid override
+pub fn check_gas_limits<T: GasLimits>(
+    config: &ValidationConfig,
+    ctx: &RequestContext,
+    request: &mut T,
+) -> Result<(), ApiError> {
+    let mut result = Ok(());
+    request.visit_gas_limits(&mut |value| {
+        if let (Some(v), Ok(())) = (value.as_deref(), &result) {
+            result = check_gas_limit(config, ctx, v);
+        }
+    });
+    result
+}
+
+fn check_gas_limit(config: &ValidationConfig, ctx: &RequestContext, value: &str) -> Result<(), ApiError> {
+    let gas_limit: u64 = value
+        .parse()
+        .map_err(|_| ApiError::InvalidData(format!("Invalid gas_limit '{}': must be an integer", value)))?;
+
+    let too_low = config.gas_limit_min.is_some_and(|min| gas_limit < min);
+    let too_high = config.gas_limit_max.is_some_and(|max| gas_limit > max);
+    if !too_low && !too_high {
+        return Ok(());
+    }
+
+    let bounds = match (config.gas_limit_min, config.gas_limit_max) {
+        (Some(min), Some(max)) => format!("between {} and {}", min, max),
+        (Some(min), None) => format!("at least {}", min),
+        (None, Some(max)) => format!("at most {}", max),
+        (None, None) => unreachable!("out of bounds without bounds"),
+    };
+    if !ctx.override_requested {
+        return Err(ApiError::InvalidData(format!(
+            "gas_limit {} must be {}",
+            gas_limit, bounds
+        )));
+    }
+    if !ctx.actor.has_scope(OVERRIDE_SCOPE) {
+        return Err(ApiError::InvalidData(format!(
+            "gas_limit {} must be {}; overriding requires a token with the '{}' scope",
+            gas_limit, bounds, OVERRIDE_SCOPE
+        )));
+    }
+
+    warn!(
+        "{} overrides gas_limit bounds with {} (allowed: {})",
+        ctx.actor.token_name, gas_limit, bounds
+    );
+    Ok(())
+}
+
+/// Check every relay of a write request against the `relay_keys` registry
+pub fn check_relay_keys<T: RelayKeys>(config: &ValidationConfig, request: &T) -> Result<(), ApiError> {
+    let mut result = Ok(());
+    request.visit_relay_keys(&mut |url, public_key| {
+        if result.is_ok() {
+            result = check_relay_key(config, url, public_key);
+        }
+    });
+    result
+}
+
+/// A relay listed in `relay_keys` must use the public key listed for it, and
+/// with `require_known_relays` it must be listed at all
+pub fn check_relay_key(config: &ValidationConfig, url: &str, public_key: &BlsPubkey) -> Result<(), ApiError> {
+    let listed = config
+        .relay_keys
+        .iter()
+        .find(|relay| same_relay_url(&relay.url, url));
+    match listed {
+        Some(relay) if relay.public_key != *public_key => Err(ApiError::InvalidData(format!(
+            "Relay '{}' has public key {} in validation.relay_keys, not {}",
+            url, relay.public_key, public_key
+        ))),
+        None if config.require_known_relays => Err(ApiError::InvalidData(format!(
+            "Relay '{}' is not listed in validation.relay_keys",
+            url
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn same_relay_url(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/').eq_ignore_ascii_case(b.trim_end_matches('/'))
+}
@@ -0,0 +1,223 @@
+// tests/access_tokens_test.rs - Public access token tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+/// Names must not repeat across test runs, so a failed run cannot leave a clash behind
+fn unique_name(prefix: &str) -> String {
+    format!("test_access_{}_{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+async fn create_token(app: &TestApp, url: &str, name: &str) -> (String, String) {
+    let response = app
+        .client()
+        .post(url)
+        .json(&json!({ "name": name }))
+        .send()
+        .await
+        .expect("Failed to create access token");
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    (body["id"].as_str().unwrap().to_string(), body["token"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_access_tokens_guard_execution_config() {
+    let app = TestApp::get().await;
+    let config = unique_name("config");
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config, "active": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let public_url = format!("{}/vouch/v2/execution-config/{}", app.address, config);
+    let fetch = |token: Option<&str>| {
+        let mut request = app.client_unauthenticated().post(&public_url).json(&json!([]));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send()
+    };
+
+    // Public until the first token is attached
+    assert_eq!(fetch(None).await.unwrap().status(), 200);
+
+    let tokens_url = format!("{}/api/admin/vouch/configs/default/{}/access-tokens", app.address, config);
+    let (id, token) = create_token(app, &tokens_url, "vouch-1").await;
+    let (_, other_token) = create_token(app, &tokens_url, "vouch-2").await;
+
+    assert_eq!(fetch(None).await.unwrap().status(), 401);
+    assert_eq!(fetch(Some("not-a-token")).await.unwrap().status(), 401);
+    assert_eq!(fetch(Some(&token)).await.unwrap().status(), 200);
+    assert_eq!(fetch(Some(&other_token)).await.unwrap().status(), 200);
+
+    // The v1 and batch endpoints are guarded too
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/vouch/v1/execution-config/{}", app.address, config))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+    let response = app
+        .client_unauthenticated()
+        .post(format!("{}/vouch/v2/execution-config:batch", app.address))
+        .json(&json!({ "configs": [config], "keys": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // Listing shows the tokens without their secret
+    let body: Value = app.client().get(&tokens_url).send().await.unwrap().json().await.unwrap();
+    let listed = body.as_array().unwrap();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0]["name"], "vouch-1");
+    assert!(listed[0]["last_used_at"].is_string());
+    assert!(listed[0].get("token").is_none());
+
+    let response = app.client().delete(format!("{}/{}", tokens_url, id)).send().await.unwrap();
+    assert_eq!(response.status(), 204);
+    assert_eq!(fetch(Some(&token)).await.unwrap().status(), 401);
+    assert_eq!(fetch(Some(&other_token)).await.unwrap().status(), 200);
+
+    // Tokens go away with the config
+    let response = app
+        .client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, config))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    let response = app.client().get(&tokens_url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_access_tokens_guard_mux_keys() {
+    let app = TestApp::get().await;
+    let mux = unique_name("mux");
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux, "keys": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let tokens_url = format!("{}/api/admin/commit-boost/mux/{}/access-tokens", app.address, mux);
+    let (_, token) = create_token(app, &tokens_url, "commit-boost").await;
+
+    let public_url = format!("{}/commit-boost/v1/mux/{}", app.address, mux);
+    let response = app.client_unauthenticated().get(&public_url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    let response = app.client_unauthenticated().get(&public_url).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // A token only opens the resource it belongs to
+    let other = unique_name("other");
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": other, "keys": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let other_tokens_url = format!("{}/api/admin/commit-boost/mux/{}/access-tokens", app.address, other);
+    create_token(app, &other_tokens_url, "other").await;
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/commit-boost/v1/mux/{}", app.address, other))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    for name in [&mux, &other] {
+        let _ = app
+            .client()
+            .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+            .send()
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_access_tokens_guard_pbs_config_keys() {
+    let app = TestApp::get().await;
+    let mux = unique_name("pbs_mux");
+    let pbs = unique_name("pbs");
+    let validator_key = TestApp::test_bls_pubkey(&format!("ac{}", TestApp::unique_id()));
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux, "keys": [validator_key] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+        .json(&json!({
+            "name": pbs,
+            "chain": "Mainnet",
+            "muxes": [{ "id": "pool", "mux": mux }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let public_url = format!("{}/commit-boost/v1/pbs/{}", app.address, pbs);
+    let response = app.client_unauthenticated().get(&public_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.text().await.unwrap().contains(&validator_key));
+
+    // Once the mux is protected, the PBS config no longer hands out its keys
+    let tokens_url = format!("{}/api/admin/commit-boost/mux/{}/access-tokens", app.address, mux);
+    let (_, token) = create_token(app, &tokens_url, "commit-boost").await;
+    let response = app.client_unauthenticated().get(&public_url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    assert!(!response.text().await.unwrap().contains(&validator_key));
+
+    let response = app.client_unauthenticated().get(&public_url).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.text().await.unwrap().contains(&validator_key));
+
+    let _ = app
+        .client()
+        .delete(format!("{}/api/admin/commit-boost/pbs/{}", app.address, pbs))
+        .send()
+        .await;
+    let _ = app
+        .client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux))
+        .send()
+        .await;
+}
+
+#[tokio::test]
+async fn test_access_tokens_of_unknown_resource() {
+    let app = TestApp::get().await;
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/configs/default/{}/access-tokens",
+            app.address,
+            unique_name("missing")
+        ))
+        .json(&json!({ "name": "vouch" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
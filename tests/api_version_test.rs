@@ -0,0 +1,80 @@
+// tests/api_version_test.rs - Admin API version prefix and negotiation tests
+mod common;
+
+use common::TestApp;
+use serde_json::Value;
+
+const API_VERSION_HEADER: &str = "x-fee-manager-api-version";
+
+#[tokio::test]
+async fn test_versioned_prefix() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/v1/admin/whoami", app.address))
+        .header(API_VERSION_HEADER, "1")
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+    assert!(response.headers().get("deprecation").is_none());
+
+    // Without the header the prefix decides
+    let response = app
+        .client()
+        .get(format!("{}/api/v1/admin/whoami", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+}
+
+#[tokio::test]
+async fn test_unversioned_prefix_is_deprecated() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/whoami", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[API_VERSION_HEADER], "1");
+    assert_eq!(response.headers()["deprecation"], "@1770681600");
+    assert_eq!(
+        response.headers()["link"],
+        "</api/v1/admin/whoami>; rel=\"successor-version\""
+    );
+
+    // Unauthenticated clients are told too
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/api/admin/whoami", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 401);
+    assert!(response.headers().get("deprecation").is_some());
+}
+
+#[tokio::test]
+async fn test_unserved_version_is_not_acceptable() {
+    let app = TestApp::get().await;
+
+    for prefix in ["/api/v1/admin", "/api/admin"] {
+        let response = app
+            .client()
+            .get(format!("{}{}/whoami", app.address, prefix))
+            .header(API_VERSION_HEADER, "2")
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 406, "{}", prefix);
+        let problem: Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "NOT_ACCEPTABLE");
+    }
+}
@@ -0,0 +1,164 @@
+// tests/audit_history_test.rs - Stored audit history endpoint tests
+mod common;
+
+use common::TestApp;
+use fee_manager::config::{self, AuditQueueConfig, AuditRotationConfig};
+use serde_json::{json, Value};
+use std::sync::Once;
+use std::time::Duration;
+
+static AUDIT: Once = Once::new();
+
+/// This test binary starts the audit writer the way main does with
+/// `audit_database: true`, writing the file output to a temp file
+async fn app() -> &'static TestApp {
+    AUDIT.call_once(|| {
+        let config = config::load_config().expect("Failed to load test config");
+        let output = std::env::temp_dir().join("fee-manager-audit-history-test.log");
+        fee_manager::audit::init_audit_writer(
+            output.to_str().unwrap(),
+            &AuditRotationConfig::default(),
+            &AuditQueueConfig::default(),
+            None,
+            config.database.connect_options().ok(),
+        );
+    });
+    TestApp::get().await
+}
+
+/// Events are stored in the background, so wait until `count` of them arrived
+async fn wait_for_history(app: &TestApp, url: &str, count: usize) -> Value {
+    for _ in 0..100 {
+        let response = app.client().get(url).send().await.expect("Failed to get history");
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.expect("Failed to parse JSON");
+        if body["events"].as_array().unwrap().len() >= count {
+            return body;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("{} did not return {} events", url, count);
+}
+
+#[tokio::test]
+async fn test_proposer_audit_history() {
+    let app = app().await;
+    // Stored events outlive test runs, so the key must not repeat across them
+    let pubkey = TestApp::test_bls_pubkey(&format!("ad{}", uuid::Uuid::new_v4().simple()));
+
+    let proposer_url = format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey);
+    for gas_limit in ["30000000", "36000000"] {
+        let response = app
+            .client()
+            .put(&proposer_url)
+            .json(&json!({ "gas_limit": gas_limit }))
+            .send()
+            .await
+            .expect("Failed to put proposer");
+        assert!(response.status().is_success());
+    }
+    let response = app.client().delete(&proposer_url).send().await.unwrap();
+    assert_eq!(response.status(), 204);
+
+    // History outlives the proposer
+    let body = wait_for_history(app, &format!("{}/audit", proposer_url), 3).await;
+    assert_eq!(body["resource_type"], "vouch_proposer");
+    assert_eq!(body["resource_id"], pubkey.as_str());
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 3);
+    let actions: Vec<&str> = events.iter().map(|e| e["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, ["create", "update", "delete"]);
+    assert_eq!(events[1]["changes"]["gas_limit"], json!({ "from": "30000000", "to": "36000000" }));
+    assert_eq!(events[1]["actor"]["token_name"], "test-token");
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["has_more"], false);
+
+    // Paged like the list endpoints, bounded by api.max_page_size
+    let response = app
+        .client()
+        .get(format!("{}/audit?limit=1&offset=1", proposer_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["events"].as_array().unwrap().len(), 1);
+    assert_eq!(body["events"][0]["action"], "update");
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["limit"], 1);
+    assert_eq!(body["offset"], 1);
+    assert_eq!(body["has_more"], true);
+    let response = app
+        .client()
+        .get(format!("{}/audit?limit=1000000", proposer_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+}
+
+#[tokio::test]
+async fn test_audit_retention_sweep() {
+    app().await;
+    // The shared app's pool belongs to the runtime of the test that created it
+    let config = config::load_config().expect("Failed to load test config");
+    let pool = sqlx::PgPool::connect(&config.database.database_url())
+        .await
+        .expect("Failed to connect to database");
+    let resource_id = format!("retention-{}", uuid::Uuid::new_v4().simple());
+    for days in [40, 1] {
+        sqlx::query(
+            "INSERT INTO audit_events (event, created_at)
+             VALUES (jsonb_build_object('type', 'audit', 'resource_type', 'relay', 'resource_id', $1::text),
+                     NOW() - make_interval(days => $2))",
+        )
+        .bind(&resource_id)
+        .bind(days)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let deleted = fee_manager::audit::sweep(&pool, 30).await.unwrap();
+    assert!(deleted >= 1);
+    let left: Vec<i32> = sqlx::query_scalar(
+        "SELECT EXTRACT(DAY FROM NOW() - created_at)::INT FROM audit_events WHERE resource_id = $1",
+    )
+    .bind(&resource_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(left, vec![1]);
+
+    sqlx::query("DELETE FROM audit_events WHERE resource_id = $1")
+        .bind(&resource_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_audit_history_of_unknown_resource_is_empty() {
+    let app = app().await;
+    let url = format!(
+        "{}/api/admin/vouch/configs/default/never-existed-{}/audit",
+        app.address,
+        TestApp::unique_id()
+    );
+
+    let body = wait_for_history(app, &url, 0).await;
+    assert_eq!(body["resource_type"], "vouch_default_config");
+    assert_eq!(body["events"], json!([]));
+}
+
+#[tokio::test]
+async fn test_audit_history_requires_auth() {
+    let app = app().await;
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/api/admin/tokens/{}/audit", app.address, uuid::Uuid::new_v4()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
@@ -0,0 +1,208 @@
+// tests/backup_restore_test.rs - Backup snapshot and restore round trip
+//
+// A restore replaces every configuration table, so this binary holds a single
+// test and nothing else writes while it runs.
+mod common;
+
+use common::TestApp;
+use fee_manager::schema::BackupSnapshot;
+use serde_json::{json, Value};
+
+fn unique_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, TestApp::unique_id())
+}
+
+/// Rows of every table in a stable order, without the `updated_at` a restore
+/// moves forward
+fn comparable(snapshot: &BackupSnapshot) -> Vec<(String, Vec<String>)> {
+    snapshot
+        .tables
+        .iter()
+        .map(|(table, rows)| {
+            let mut rows: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    let mut row = row.clone();
+                    row.as_object_mut().unwrap().remove("updated_at");
+                    row.to_string()
+                })
+                .collect();
+            rows.sort();
+            (table.clone(), rows)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_snapshot_restores() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config = unique_name("restore_config");
+    let mux = unique_name("restore_mux");
+    let relay_set = unique_name("restore_set");
+    let proposer = TestApp::test_bls_pubkey(&format!("4e{}", id));
+    let retired = TestApp::test_bls_pubkey(&format!("4f{}", id));
+    let mux_key = TestApp::test_bls_pubkey(&format!("5a{}", id));
+    let relay_key = TestApp::test_bls_pubkey("c5");
+
+    // What a backup used to miss: inactive configs, raw proposer rows,
+    // decommissioned proposers, relay sets, muxes and their access tokens
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config,
+            "active": false,
+            "relays": { "https://restore.example.com": { "public_key": relay_key } }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    for key in [&proposer, &retired] {
+        let response = app
+            .client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .json(&json!({ "note": "rack 3", "labels": { "team": "restore" } }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+    }
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/{}/decommission", app.address, retired))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/relay-sets", app.address))
+        .json(&json!({ "name": relay_set, "relays": { "https://restore.example.com": { "public_key": relay_key } } }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux, "keys": [mux_key] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/access-tokens", app.address, mux))
+        .json(&json!({ "name": "restore" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let token = response.json::<Value>().await.unwrap()["token"].as_str().unwrap().to_string();
+
+    let snapshot = fee_manager::backups::snapshot(&app.pool).await.unwrap();
+    let proposers = &snapshot.tables["vouch_proposers"];
+    let row = proposers.iter().find(|row| row["public_key"] == retired.as_str()).unwrap();
+    assert_eq!(row["note"], "rack 3");
+    assert!(!row["decommissioned_at"].is_null());
+    assert!(snapshot.tables["vouch_default_configs"].iter().any(|row| row["name"] == config.as_str()));
+    assert!(snapshot.tables["commit_boost_mux_keys"].iter().any(|row| row["public_key"] == mux_key.as_str()));
+
+    // Change things after the snapshot
+    app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux))
+        .send()
+        .await
+        .unwrap();
+    app.client()
+        .delete(format!("{}/api/admin/vouch/relay-sets/{}", app.address, relay_set))
+        .send()
+        .await
+        .unwrap();
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, proposer))
+        .json(&json!({ "note": "moved" }))
+        .send()
+        .await
+        .unwrap();
+    let added = unique_name("restore_added");
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": added }))
+        .send()
+        .await
+        .unwrap();
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/backups/restore", app.address))
+        .json(&snapshot)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(
+        body["tables"]["commit_boost_mux_keys"],
+        snapshot.tables["commit_boost_mux_keys"].len()
+    );
+
+    // The tables read back as they were
+    let again = fee_manager::backups::snapshot(&app.pool).await.unwrap();
+    assert_eq!(comparable(&again), comparable(&snapshot));
+
+    // The mux is back with its keys, still guarded by its token
+    let public_url = format!("{}/commit-boost/v1/mux/{}", app.address, mux);
+    let response = app.client_unauthenticated().get(&public_url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    let response = app.client_unauthenticated().get(&public_url).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.text().await.unwrap().contains(&mux_key));
+
+    // New rows get ids past the restored ones
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, mux))
+        .json(&json!({ "keys": [TestApp::test_bls_pubkey(&format!("5b{}", id))] }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    // A snapshot of another schema is refused
+    let mut stale = snapshot.clone();
+    stale.schema_version -= 1;
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/backups/restore", app.address))
+        .json(&stale)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux))
+        .send()
+        .await
+        .unwrap();
+    app.client()
+        .delete(format!("{}/api/admin/vouch/relay-sets/{}", app.address, relay_set))
+        .send()
+        .await
+        .unwrap();
+    for key in [&proposer, &retired] {
+        app.client()
+            .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .send()
+            .await
+            .unwrap();
+    }
+    app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, config))
+        .send()
+        .await
+        .unwrap();
+}
@@ -1,7 +1,10 @@
 // tests/common/mod.rs - Test utilities and helpers
 #![allow(dead_code)]
 
-use fee_manager::{config, create_router, run_migrations, AppState};
+use fee_manager::auth::ADMIN_SCOPE;
+use fee_manager::{
+    config, create_admin_router, create_public_router, create_router, run_migrations, AppState,
+};
 use reqwest::{Client, header};
 use sqlx::PgPool;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -13,6 +16,8 @@ static TEST_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 pub struct TestApp {
     pub address: String,
+    /// Where `/api/admin` is served; same as `address` unless `listen.admin` is set
+    pub admin_address: String,
     pub pool: PgPool,
     pub auth_token: String,
 }
@@ -35,12 +40,13 @@ impl TestApp {
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
-                let address = Self::spawn_inner().await;
+                let config = config::load_config().expect("Failed to load test config");
+                let (address, _) = Self::spawn_inner(config).await;
                 // Create a simple client for health check in this runtime
                 let temp_client = Client::new();
                 // Wait for server to be ready
                 for _ in 0..50 {
-                    if temp_client.get(&format!("{}/health", address)).send().await.is_ok() {
+                    if temp_client.get(format!("{}/health", address)).send().await.is_ok() {
                         break;
                     }
                     tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
@@ -59,11 +65,16 @@ impl TestApp {
             .expect("Failed to connect to database for tests");
 
         // Create a test auth token
-        let (_, auth_token) = fee_manager::auth::service::create_token(&pool, "test-token", Some("Token for integration tests"))
+        let (_, auth_token) = fee_manager::auth::service::create_token(&pool, "test-token", Some("Token for integration tests"), &[ADMIN_SCOPE.to_string()])
             .await
             .expect("Failed to create test auth token");
 
-        TEST_APP.get_or_init(|| TestApp { address, pool, auth_token })
+        TEST_APP.get_or_init(|| TestApp {
+            admin_address: address.clone(),
+            address,
+            pool,
+            auth_token,
+        })
     }
 
     /// Create a new HTTP client with authentication for this test
@@ -91,17 +102,36 @@ impl TestApp {
         format!("{:08x}", id)
     }
 
-    async fn spawn_inner() -> String {
-        // Load config from environment
-        let config = config::load_config().expect("Failed to load test config");
+    /// Start a dedicated server with an adjusted config on the calling test's runtime.
+    /// Use this instead of `get()` for behaviour that depends on non-default settings.
+    pub async fn spawn_with_config(configure: impl FnOnce(&mut config::AppConfig)) -> TestApp {
+        let mut config = config::load_config().expect("Failed to load test config");
+        configure(&mut config);
+        let db_url = config.database.database_url();
+
+        let (address, admin_address) = Self::spawn_inner(config).await;
+
+        let pool = PgPool::connect(&db_url)
+            .await
+            .expect("Failed to connect to database for tests");
+        let (_, auth_token) = fee_manager::auth::service::create_token(&pool, "test-token", Some("Token for integration tests"), &[ADMIN_SCOPE.to_string()])
+            .await
+            .expect("Failed to create test auth token");
+
+        TestApp { address, admin_address, pool, auth_token }
+    }
+
+    /// Returns the main and admin base URLs
+    async fn spawn_inner(config: config::AppConfig) -> (String, String) {
+        let split_admin = config.listen.admin.is_some();
 
         // Connect to database (this pool is for the SERVER, not for tests)
-        let pool = PgPool::connect(&config.database.database_url())
+        let pool = PgPool::connect_with(config.database.connect_options().expect("Invalid database URL"))
             .await
             .expect("Failed to connect to database");
 
         // Run migrations
-        run_migrations(&pool)
+        run_migrations(&pool, !config.database.pgbouncer_mode)
             .await
             .expect("Failed to run migrations");
 
@@ -111,9 +141,18 @@ impl TestApp {
             config,
         });
 
-        // Create router
-        let app = create_router(state);
+        // Create router, with admin routes on a second random port if configured
+        if split_admin {
+            let address = Self::serve(create_public_router(state.clone())).await;
+            let admin_address = Self::serve(create_admin_router(state)).await;
+            (address, admin_address)
+        } else {
+            let address = Self::serve(create_router(state)).await;
+            (address.clone(), address)
+        }
+    }
 
+    async fn serve(app: axum::Router) -> String {
         // Bind to random port
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
             .await
@@ -124,7 +163,12 @@ impl TestApp {
 
         // Spawn server in background (within the dedicated runtime)
         tokio::spawn(async move {
-            axum::serve(listener, app).await.unwrap();
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .await
+            .unwrap();
         });
 
         address
@@ -163,6 +207,16 @@ impl TestApp {
             .await
             .ok();
 
+        sqlx::query("DELETE FROM vouch_relay_sets WHERE name LIKE 'test_%'")
+            .execute(&self.pool)
+            .await
+            .ok();
+
+        sqlx::query("DELETE FROM commit_boost_pbs_configs WHERE name LIKE 'test_%'")
+            .execute(&self.pool)
+            .await
+            .ok();
+
         sqlx::query("DELETE FROM commit_boost_mux_keys WHERE mux_name LIKE 'test_%'")
             .execute(&self.pool)
             .await
@@ -0,0 +1,93 @@
+// tests/concurrency_test.rs - Public endpoint concurrency limit and load shedding tests
+mod common;
+
+use common::TestApp;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+/// Start a public request whose body never arrives, so it holds its slot until dropped
+async fn hold_slot(app: &TestApp) -> TcpStream {
+    let host = app.address.trim_start_matches("http://");
+    let mut stream = TcpStream::connect(host).await.expect("Failed to connect");
+    let head = format!(
+        "POST /vouch/v2/execution-config/held HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n",
+        host
+    );
+    stream.write_all(head.as_bytes()).await.unwrap();
+    // Give the server time to admit it
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    stream
+}
+
+async fn public_request(app: &TestApp) -> reqwest::Response {
+    app.client_unauthenticated()
+        .get(format!("{}/commit-boost/v1/mux/unknown", app.address))
+        .send()
+        .await
+        .expect("Failed to send request")
+}
+
+#[tokio::test]
+async fn test_excess_public_requests_are_shed() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.public_concurrency.max_in_flight = 1;
+        c.public_concurrency.max_queued = 0;
+        c.metrics_enabled = true;
+    })
+    .await;
+
+    let held = hold_slot(&app).await;
+    let response = public_request(&app).await;
+    assert_eq!(response.status(), 503);
+    assert_eq!(response.headers()["retry-after"], "1");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "SERVICE_UNAVAILABLE");
+
+    // Health checks and the admin API are not limited
+    let response = app.client().get(format!("{}/api/admin/whoami", app.address)).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let metrics = app
+        .client_unauthenticated()
+        .get(format!("{}/metrics", app.address))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let shed = metrics
+        .lines()
+        .find_map(|line| line.strip_prefix("fee_manager_public_shed_requests_total "))
+        .expect("No shed counter");
+    assert!(shed.parse::<u64>().unwrap() >= 1, "{}", metrics);
+
+    // The slot frees up once the held request goes away
+    drop(held);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(public_request(&app).await.status(), 404);
+}
+
+#[tokio::test]
+async fn test_queued_public_requests_wait_for_a_slot() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.public_concurrency.max_in_flight = 1;
+        c.public_concurrency.max_queued = 1;
+        c.public_concurrency.queue_timeout_ms = 300;
+    })
+    .await;
+
+    // Queued until the timeout, then shed
+    let held = hold_slot(&app).await;
+    let started = Instant::now();
+    assert_eq!(public_request(&app).await.status(), 503);
+    assert!(started.elapsed() >= Duration::from_millis(300));
+
+    // Admitted as soon as the slot is released
+    let release = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(held);
+    };
+    let (response, _) = tokio::join!(public_request(&app), release);
+    assert_eq!(response.status(), 404);
+}
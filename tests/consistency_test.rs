@@ -0,0 +1,102 @@
+// tests/consistency_test.rs - Database consistency check tests
+mod common;
+
+use common::TestApp;
+use serde_json::Value;
+
+/// Findings of a check run about the given rows of a table
+fn findings_for<'a>(report: &'a Value, table: &str, rows: &[&str]) -> Vec<&'a Value> {
+    report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["table"] == table && rows.contains(&f["row"].as_str().unwrap()))
+        .collect()
+}
+
+async fn check(app: &TestApp, fix: bool) -> Value {
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/consistency-check?fix={}", app.address, fix))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_consistency_check_finds_and_fixes_manual_edits() {
+    let app = TestApp::get().await;
+    let name = format!("test_consistency_{}", TestApp::unique_id());
+    let fee_recipient = TestApp::test_eth_address("c0ffee");
+    let relay_key = TestApp::test_bls_pubkey("c0ffee");
+
+    // What a hand-written INSERT might leave: uppercase hex, a dropped relay set
+    // still referenced, and the same relay under two spellings
+    sqlx::query("INSERT INTO vouch_default_configs (name, fee_recipient, relay_sets) VALUES ($1, $2, $3)")
+        .bind(&name)
+        .bind(fee_recipient.to_uppercase().replacen("0X", "0x", 1))
+        .bind(vec![format!("{}_missing", name)])
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let mut relay_ids = Vec::new();
+    for url in ["https://relay.example.com", "https://Relay.example.com/"] {
+        let id: i32 = sqlx::query_scalar(
+            "INSERT INTO vouch_default_relays (config_name, url, public_key) VALUES ($1, $2, $3) RETURNING id",
+        )
+        .bind(&name)
+        .bind(url)
+        .bind(&relay_key)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+        relay_ids.push(id.to_string());
+    }
+
+    let report = check(app, false).await;
+    assert_eq!(report["fix"], false);
+    let config_findings = findings_for(&report, "vouch_default_configs", &[name.as_str()]);
+    let mut issues: Vec<&str> = config_findings.iter().map(|f| f["issue"].as_str().unwrap()).collect();
+    issues.sort();
+    assert_eq!(issues, ["invalid_hex", "missing_relay_set"]);
+    assert!(config_findings.iter().all(|f| f["fixable"] == true && f["fixed"] == false));
+    // The older spelling is the duplicate
+    let relay_findings = findings_for(&report, "vouch_default_relays", &[relay_ids[0].as_str(), relay_ids[1].as_str()]);
+    assert_eq!(relay_findings.len(), 1);
+    assert_eq!(relay_findings[0]["issue"], "duplicate_url");
+    assert_eq!(relay_findings[0]["row"], relay_ids[0].as_str());
+
+    let report = check(app, true).await;
+    assert_eq!(report["fix"], true);
+    let fixed = findings_for(&report, "vouch_default_configs", &[name.as_str()]);
+    assert_eq!(fixed.len(), 2);
+    assert!(fixed.iter().all(|f| f["fixed"] == true));
+
+    let (stored_fee_recipient, relay_sets): (String, Vec<String>) =
+        sqlx::query_as("SELECT fee_recipient, relay_sets FROM vouch_default_configs WHERE name = $1")
+            .bind(&name)
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(stored_fee_recipient, fee_recipient);
+    assert!(relay_sets.is_empty());
+    let urls: Vec<String> = sqlx::query_scalar("SELECT url FROM vouch_default_relays WHERE config_name = $1")
+        .bind(&name)
+        .fetch_all(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(urls, ["https://Relay.example.com/"]);
+
+    // Nothing left to report about these rows
+    let report = check(app, false).await;
+    assert!(findings_for(&report, "vouch_default_configs", &[name.as_str()]).is_empty());
+    assert!(findings_for(&report, "vouch_default_relays", &[relay_ids[0].as_str(), relay_ids[1].as_str()]).is_empty());
+
+    sqlx::query("DELETE FROM vouch_default_configs WHERE name = $1")
+        .bind(&name)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+}
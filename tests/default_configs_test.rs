@@ -2,6 +2,7 @@
 mod common;
 
 use common::TestApp;
+use fee_manager::config::TrustedProxies;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
@@ -24,6 +25,7 @@ struct DefaultConfigResponse {
     gas_limit: Option<String>,
     #[allow(dead_code)]
     min_value: Option<String>,
+    grace: Option<String>,
     active: bool,
     relays: Option<HashMap<String, RelayConfig>>,
     #[allow(dead_code)]
@@ -53,6 +55,7 @@ struct PaginatedResponse<T> {
     total: i64,
     limit: i64,
     offset: i64,
+    has_more: bool,
 }
 
 /// Helper to create a unique test config name
@@ -63,7 +66,7 @@ fn unique_config_name(prefix: &str) -> String {
 /// Helper to delete a config (cleanup)
 async fn delete_config(app: &TestApp, name: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await;
 }
@@ -79,7 +82,7 @@ async fn test_create_default_config() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
@@ -109,7 +112,7 @@ async fn test_create_default_config_with_relays() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
@@ -134,7 +137,7 @@ async fn test_create_default_config_with_relays() {
     // Fetch the config to verify relays
     let get_response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await
         .expect("Failed to get config");
@@ -147,6 +150,109 @@ async fn test_create_default_config_with_relays() {
     delete_config(app, &name).await;
 }
 
+/// Percent-encode a relay URL for use as a path segment
+fn encode_url(url: &str) -> String {
+    url::form_urlencoded::byte_serialize(url.as_bytes()).collect()
+}
+
+#[tokio::test]
+async fn test_default_config_relay_sub_resource() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("relay_sub");
+    let relay_key = TestApp::test_bls_pubkey("d2");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": name,
+            "relays": {
+                "https://relay1.example.com": { "public_key": relay_key },
+                "https://relay2.example.com": { "public_key": relay_key }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+
+    let relays_url = format!("{}/api/admin/vouch/configs/default/{}/relays", app.address, name);
+    let relay_url = |url: &str| format!("{}/{}", relays_url, encode_url(url));
+
+    // Replace one relay and add another, leaving the rest untouched
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": relay_key, "min_value": "0.3 eth" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let response = app
+        .client()
+        .put(relay_url("https://relay3.example.com"))
+        .json(&json!({ "public_key": relay_key, "gas_limit": "36000000" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let relays: HashMap<String, RelayConfig> = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(relays.len(), 3);
+    assert_eq!(relays["https://relay1.example.com"].min_value.as_deref(), Some("0.3"));
+    assert_eq!(relays["https://relay3.example.com"].gas_limit.as_deref(), Some("36000000"));
+
+    let response = app
+        .client()
+        .delete(relay_url("https://relay2.example.com"))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 204);
+    let response = app
+        .client()
+        .delete(relay_url("https://relay2.example.com"))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .client()
+        .get(format!("{}?units=gwei", relays_url))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let relays: HashMap<String, RelayConfig> = response.json().await.expect("Failed to parse JSON");
+    let mut urls: Vec<&String> = relays.keys().collect();
+    urls.sort();
+    assert_eq!(urls, ["https://relay1.example.com", "https://relay3.example.com"]);
+    assert_eq!(relays["https://relay1.example.com"].min_value.as_deref(), Some("300000000"));
+
+    // Invalid relay settings and unknown configs
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": relay_key, "gas_limit": "lots" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/configs/default/{}/relays",
+            app.address,
+            unique_config_name("missing")
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    delete_config(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_create_default_config_duplicate() {
     let app = TestApp::get().await;
@@ -155,7 +261,7 @@ async fn test_create_default_config_duplicate() {
     // Create first config
     let _ = app
         .client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "active": true
@@ -167,7 +273,7 @@ async fn test_create_default_config_duplicate() {
     // Try to create duplicate
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "active": true
@@ -176,7 +282,7 @@ async fn test_create_default_config_duplicate() {
         .await
         .expect("Failed to send request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 409);
 
     delete_config(app, &name).await;
 }
@@ -188,7 +294,7 @@ async fn test_get_default_config() {
 
     // Create config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "gas_limit": "32000000",
@@ -201,7 +307,7 @@ async fn test_get_default_config() {
     // Get config
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -222,7 +328,7 @@ async fn test_get_default_config_not_found() {
 
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -237,7 +343,7 @@ async fn test_update_default_config() {
 
     // Create config
     let create_resp = app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "gas_limit": "30000000",
@@ -252,7 +358,7 @@ async fn test_update_default_config() {
     // Update config
     let response = app
         .client()
-        .put(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .json(&json!({
             "gas_limit": "35000000",
             "active": false
@@ -270,6 +376,152 @@ async fn test_update_default_config() {
     delete_config(app, &name).await;
 }
 
+/// Regression test for partial updates: every combination of fields must land
+/// in the right columns and leave the others alone
+#[tokio::test]
+async fn test_update_default_config_every_field_combination() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("partial");
+    let fields: [(&str, [serde_json::Value; 2]); 7] = [
+        ("fee_recipient", [json!(TestApp::test_eth_address("e1")), json!(TestApp::test_eth_address("e2"))]),
+        ("gas_limit", [json!("30000000"), json!("36000000")]),
+        ("min_value", [json!("1000"), json!("2000")]),
+        ("active", [json!(true), json!(false)]),
+        ("grace", [json!("500"), json!("1000")]),
+        ("builder_enabled", [json!(true), json!(false)]),
+        ("builder_boost_factor", [json!("100"), json!("90")]),
+    ];
+
+    let mut expected = serde_json::Map::new();
+    for (field, values) in &fields {
+        expected.insert(field.to_string(), values[0].clone());
+    }
+    let mut create = expected.clone();
+    create.insert("name".to_string(), json!(name));
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&create)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+
+    let url = format!("{}/api/admin/vouch/configs/default/{}", app.address, name);
+    for mask in 1u32..(1 << fields.len()) {
+        // Flip each selected field to the value it does not have yet
+        let mut update = serde_json::Map::new();
+        for (i, (field, values)) in fields.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                let value = if expected[*field] == values[0] { &values[1] } else { &values[0] };
+                update.insert(field.to_string(), value.clone());
+                expected.insert(field.to_string(), value.clone());
+            }
+        }
+
+        let response = app
+            .client()
+            .put(&url)
+            .json(&update)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 200, "update {:?}", update);
+
+        let response = app.client().get(&url).send().await.expect("Failed to get config");
+        let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+        for (field, _) in &fields {
+            assert_eq!(body[*field], expected[*field], "{} after update {:?}", field, update);
+        }
+    }
+
+    delete_config(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_update_default_config_null_clears_field() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("clear");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": name,
+            "fee_recipient": TestApp::test_eth_address("c2"),
+            "gas_limit": "30000000",
+            "min_value": "1000",
+            "builder_enabled": true
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    // Explicit null clears, omitted fields stay as they were
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .json(&json!({ "min_value": null, "builder_enabled": null }))
+        .send()
+        .await
+        .expect("Failed to update config");
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert!(body["min_value"].is_null());
+    assert!(body["builder_enabled"].is_null());
+    assert_eq!(body["gas_limit"], "30000000");
+    assert_eq!(body["fee_recipient"], TestApp::test_eth_address("c2"));
+
+    delete_config(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_default_config_grace() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("grace");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": name, "grace": "1000" }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+    let body: DefaultConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.grace, Some("1000".to_string()));
+
+    // Updating other fields keeps grace
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .json(&json!({ "gas_limit": "35000000" }))
+        .send()
+        .await
+        .expect("Failed to update config");
+    let body: DefaultConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.grace, Some("1000".to_string()));
+
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .json(&json!({ "grace": "2500" }))
+        .send()
+        .await
+        .expect("Failed to update config");
+    let body: DefaultConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.grace, Some("2500".to_string()));
+
+    // Emitted in the public execution config
+    let response = app.client_unauthenticated()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, name))
+        .json(&json!([]))
+        .send()
+        .await
+        .expect("Failed to get execution config");
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["grace"], "2500");
+
+    delete_config(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_delete_default_config() {
     let app = TestApp::get().await;
@@ -277,7 +529,7 @@ async fn test_delete_default_config() {
 
     // Create config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name,
             "active": true
@@ -289,7 +541,7 @@ async fn test_delete_default_config() {
     // Delete config
     let response = app
         .client()
-        .delete(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -299,7 +551,7 @@ async fn test_delete_default_config() {
     // Verify deleted
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -307,6 +559,66 @@ async fn test_delete_default_config() {
     assert_eq!(response.status(), 404);
 }
 
+#[tokio::test]
+async fn test_default_config_dependents() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("dependents");
+    let child = unique_config_name("dependents_child");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": name,
+            "active": true,
+            "relays": {
+                "https://relay1.example.com": { "public_key": TestApp::test_bls_pubkey("de1") },
+                "https://relay2.example.com": { "public_key": TestApp::test_bls_pubkey("de2") }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": child, "active": true, "parent": name }))
+        .send()
+        .await
+        .expect("Failed to create child config");
+    assert_eq!(response.status(), 201);
+
+    let url = format!("{}/api/admin/vouch/configs/default/{}/dependents", app.address, name);
+    let body: serde_json::Value = app.client().get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["name"], name);
+    assert_eq!(
+        body["removes"],
+        json!([
+            { "kind": "relays", "count": 2 },
+            { "kind": "usage", "count": 0 },
+            { "kind": "access_tokens", "count": 0 }
+        ])
+    );
+    assert_eq!(body["blocked_by"], json!([{ "resource_type": "vouch_default_config", "name": child }]));
+
+    // The preview changes nothing, and the blocker is the same one delete reports
+    let response = app
+        .client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+    delete_config(app, &child).await;
+    let body: serde_json::Value = app.client().get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["blocked_by"], json!([]));
+
+    delete_config(app, &name).await;
+    let response = app.client().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
+
 // ============================================================================
 // Filtering Tests
 // ============================================================================
@@ -320,7 +632,7 @@ async fn test_list_default_configs() {
     // Create multiple configs
     for (i, name) in names.iter().enumerate() {
         let resp = app.client()
-            .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+            .post(format!("{}/api/admin/vouch/configs/default", app.address))
             .json(&json!({
                 "name": name,
                 "gas_limit": format!("{}0000000", 30 + i),
@@ -335,7 +647,7 @@ async fn test_list_default_configs() {
     // List all test configs
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_list_{}", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_list_{}", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -360,7 +672,7 @@ async fn test_filter_by_active() {
     // Create configs with different active states
     for (i, name) in names.iter().enumerate() {
         app.client()
-            .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+            .post(format!("{}/api/admin/vouch/configs/default", app.address))
             .json(&json!({ "name": name, "active": i != 1 }))  // 0,2 = true, 1 = false
             .send()
             .await
@@ -370,7 +682,7 @@ async fn test_filter_by_active() {
     // Filter active only
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_active_{}&active=true", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_active_{}&active=true", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -382,7 +694,7 @@ async fn test_filter_by_active() {
     // Filter inactive only
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_active_{}&active=false", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_active_{}&active=false", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -405,21 +717,21 @@ async fn test_filter_by_gas_limit() {
 
     // Create configs with different gas limits
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({ "name": &names[0], "gas_limit": "30000000", "active": true }))
         .send()
         .await
         .unwrap();
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({ "name": &names[1], "gas_limit": "35000000", "active": true }))
         .send()
         .await
         .unwrap();
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({ "name": &names[2], "gas_limit": "30000000", "active": true }))
         .send()
         .await
@@ -428,7 +740,7 @@ async fn test_filter_by_gas_limit() {
     // Filter by gas_limit
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_gas_{}&gas_limit=30000000", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_gas_{}&gas_limit=30000000", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -452,7 +764,7 @@ async fn test_pagination() {
     // Create 5 configs
     for name in &names {
         app.client()
-            .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+            .post(format!("{}/api/admin/vouch/configs/default", app.address))
             .json(&json!({ "name": name, "active": true }))
             .send()
             .await
@@ -462,27 +774,51 @@ async fn test_pagination() {
     // Test limit
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_page_{}&limit=2", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_page_{}&limit=2", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
 
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert_eq!(
+        link,
+        format!("</api/admin/vouch/configs/default?name=test_page_{}&limit=2&offset=2>; rel=\"next\"", prefix)
+    );
     let body: PaginatedResponse<DefaultConfigListItem> = response.json().await.unwrap();
     assert_eq!(body.data.len(), 2);
     assert_eq!(body.limit, 2);
     assert_eq!(body.total, 5);
+    assert!(body.has_more);
 
     // Test offset
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/configs/default?name=test_page_{}&limit=2&offset=2", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_page_{}&limit=2&offset=2", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
 
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert!(link.contains("offset=4>; rel=\"next\""));
+    assert!(link.contains("offset=0>; rel=\"prev\""));
     let body: PaginatedResponse<DefaultConfigListItem> = response.json().await.unwrap();
     assert_eq!(body.data.len(), 2);
     assert_eq!(body.offset, 2);
+    assert!(body.has_more);
+
+    // Last page links back only
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/configs/default?name=test_page_{}&limit=2&offset=4", app.address, prefix))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert!(!link.contains("rel=\"next\""));
+    let body: PaginatedResponse<DefaultConfigListItem> = response.json().await.unwrap();
+    assert_eq!(body.data.len(), 1);
+    assert!(!body.has_more);
 
     // Cleanup
     for name in &names {
@@ -490,6 +826,29 @@ async fn test_pagination() {
     }
 }
 
+#[tokio::test]
+async fn test_limit_above_max_page_size() {
+    let app = TestApp::spawn_with_config(|c| c.api.max_page_size = 10).await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/configs/default?limit=11", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 422);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/configs/default?limit=10", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+}
+
 // ============================================================================
 // Relay Filter Tests
 // ============================================================================
@@ -503,7 +862,7 @@ async fn test_filter_by_relay_url() {
 
     // Create config with relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name_with_relay,
             "active": true,
@@ -519,7 +878,7 @@ async fn test_filter_by_relay_url() {
 
     // Create config without relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name_without_relay,
             "active": true
@@ -531,7 +890,7 @@ async fn test_filter_by_relay_url() {
     // Filter by relay_url prefix
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/configs/default?name=test_relay_url_{}&relay_url=https://flashbots",
             app.address, prefix
         ))
@@ -558,7 +917,7 @@ async fn test_filter_by_relay_min_value() {
 
     // Create config with relay that has min_value
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name_with_min,
             "active": true,
@@ -575,7 +934,7 @@ async fn test_filter_by_relay_min_value() {
 
     // Create config with relay without min_value
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": name_without_min,
             "active": true,
@@ -592,7 +951,7 @@ async fn test_filter_by_relay_min_value() {
     // Filter by relay_min_value
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/configs/default?name=test_relay_min_{}&relay_min_value=50000000000000000",
             app.address, prefix
         ))
@@ -609,3 +968,272 @@ async fn test_filter_by_relay_min_value() {
     delete_config(app, &name_with_min).await;
     delete_config(app, &name_without_min).await;
 }
+
+#[tokio::test]
+async fn test_default_config_usage() {
+    let app = TestApp::get().await;
+    let name = unique_config_name("test_usage");
+    let id = TestApp::unique_id();
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": name, "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+
+    let usage_url = format!("{}/api/admin/vouch/configs/default/{}/usage", app.address, name);
+    let body: serde_json::Value = app.client().get(&usage_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["request_count"], 0);
+    assert!(body.get("last_requested_at").is_none());
+    assert_eq!(body["consumers"], json!([]));
+
+    let keys = [
+        TestApp::test_bls_pubkey(&format!("a1{}", id)),
+        TestApp::test_bls_pubkey(&format!("a2{}", id)),
+    ];
+    for keys in [&keys[..], &keys[..1]] {
+        let response = app
+            .client_unauthenticated()
+            .post(format!("{}/vouch/v2/execution-config/{}", app.address, name))
+            .json(&keys)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    // Usage is recorded in the background
+    let mut body = serde_json::Value::Null;
+    for _ in 0..50 {
+        body = app.client().get(&usage_url).send().await.unwrap().json().await.unwrap();
+        if body["request_count"] == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(body["request_count"], 2, "usage not recorded: {}", body);
+    assert!(body["last_requested_at"].is_string());
+    let consumers = body["consumers"].as_array().unwrap();
+    assert_eq!(consumers.len(), 1);
+    assert_eq!(consumers[0]["consumer"], "127.0.0.1");
+    assert_eq!(consumers[0]["max_key_count"], 2);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/configs/default/nonexistent_usage/usage", app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    delete_config(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_default_config_usage_behind_trusted_proxy() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.listen.trusted_proxies =
+            TrustedProxies::try_from(vec!["127.0.0.1".to_string()]).unwrap();
+    })
+    .await;
+    let name = format!("test_proxy_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": name, "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+
+    // The test client connects from 127.0.0.1, so its forwarded address is believed
+    let response = app
+        .client_unauthenticated()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, name))
+        .header("X-Forwarded-For", "6.6.6.6, 203.0.113.7")
+        .json(&json!([]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let usage_url = format!("{}/api/admin/vouch/configs/default/{}/usage", app.address, name);
+    let mut body = serde_json::Value::Null;
+    for _ in 0..50 {
+        body = app.client().get(&usage_url).send().await.unwrap().json().await.unwrap();
+        if body["request_count"] == 1 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(body["consumers"][0]["consumer"], "203.0.113.7", "{}", body);
+
+    delete_config(&app, &name).await;
+}
+
+// ============================================================================
+// Validation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_gas_limit_bounds() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.validation.gas_limit_min = Some(1_000_000);
+        c.validation.gas_limit_max = Some(60_000_000);
+    })
+    .await;
+    let name = unique_config_name("gas_bounds");
+    let url = format!("{}/api/admin/vouch/configs/default", app.address);
+    let config = |gas_limit: &str, relay_gas_limit: &str| {
+        json!({
+            "name": name,
+            "gas_limit": gas_limit,
+            "active": true,
+            "relays": { "https://relay1.example.com": { "public_key": TestApp::test_bls_pubkey("b0"), "gas_limit": relay_gas_limit } }
+        })
+    };
+
+    // Out of bounds or malformed, on the config itself or a relay override
+    for body in [
+        config("100000000", "30000000"),
+        config("30000000", "500000"),
+        config("30m", "30000000"),
+    ] {
+        let response = app.client().post(&url).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 400, "accepted {}", body);
+    }
+
+    let pubkey = TestApp::test_bls_pubkey(&TestApp::unique_id());
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "gas_limit": "70000000", "reset_relays": false }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    // The override header alone is not enough
+    let response = app
+        .client()
+        .post(&url)
+        .header("X-Fee-Manager-Override", "true")
+        .json(&config("100000000", "30000000"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["detail"].as_str().unwrap().contains("'override' scope"));
+
+    // A token with the override scope can opt out per request
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/tokens", app.address))
+        .json(&json!({ "name": format!("{}-override", name), "scopes": ["override"] }))
+        .send()
+        .await
+        .unwrap();
+    let token: serde_json::Value = response.json().await.unwrap();
+    let bearer = format!("Bearer {}", token["token"].as_str().unwrap());
+
+    let response = app
+        .client_unauthenticated()
+        .post(&url)
+        .header(reqwest::header::AUTHORIZATION, &bearer)
+        .json(&config("100000000", "30000000"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .client_unauthenticated()
+        .post(&url)
+        .header(reqwest::header::AUTHORIZATION, &bearer)
+        .header("X-Fee-Manager-Override", "true")
+        .json(&config("100000000", "30000000"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let body: DefaultConfigResponse = response.json().await.unwrap();
+    assert_eq!(body.gas_limit, Some("100000000".to_string()));
+
+    delete_config(&app, &name).await;
+    app.client()
+        .delete(format!("{}/api/admin/tokens/{}", app.address, token["id"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_relay_key_registry() {
+    let registered = TestApp::test_bls_pubkey("a1");
+    let app = TestApp::spawn_with_config(|c| {
+        c.validation.relay_keys = vec![fee_manager::config::RelayKeyConfig {
+            url: "https://relay1.example.com".to_string(),
+            public_key: registered.parse().unwrap(),
+        }];
+    })
+    .await;
+    let name = unique_config_name("relay_keys");
+    let url = format!("{}/api/admin/vouch/configs/default", app.address);
+    let config = |relay_url: &str, public_key: &str| {
+        json!({
+            "name": name,
+            "active": true,
+            "relays": { relay_url: { "public_key": public_key } }
+        })
+    };
+
+    // A listed relay with another key is rejected, however its URL is spelled
+    for relay_url in ["https://relay1.example.com", "https://RELAY1.example.com/"] {
+        let response = app
+            .client()
+            .post(&url)
+            .json(&config(relay_url, &TestApp::test_bls_pubkey("a2")))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["detail"].as_str().unwrap().contains("validation.relay_keys"), "{}", body);
+    }
+
+    // Unlisted relays pass unless require_known_relays is set
+    let response = app
+        .client()
+        .post(&url)
+        .json(&config("https://relay2.example.com", &TestApp::test_bls_pubkey("a2")))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    // The single-relay endpoint checks too
+    let relay_url = |relay: &str| format!("{}/{}/relays/{}", url, name, encode_url(relay));
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": TestApp::test_bls_pubkey("a2") }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": registered }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    delete_config(&app, &name).await;
+}
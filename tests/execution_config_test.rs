@@ -37,6 +37,29 @@ struct ExecutionConfigResponse {
     proposers: Option<Vec<ProposerEntry>>,
 }
 
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct BuilderConfigV1 {
+    enabled: bool,
+    relays: Vec<String>,
+    disabled_relays: Option<Vec<String>>,
+    grace: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ProposerConfigV1 {
+    fee_recipient: Option<String>,
+    gas_limit: Option<String>,
+    builder: Option<BuilderConfigV1>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecutionConfigV1Response {
+    default_config: ProposerConfigV1,
+    proposer_config: HashMap<String, ProposerConfigV1>,
+}
+
 /// Helper to create unique config name for this test
 fn unique_config_name(prefix: &str) -> String {
     format!("test_{}_{}", prefix, TestApp::unique_id())
@@ -45,7 +68,7 @@ fn unique_config_name(prefix: &str) -> String {
 /// Helper to delete a config
 async fn delete_config(app: &TestApp, name: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
         .send()
         .await;
 }
@@ -53,7 +76,7 @@ async fn delete_config(app: &TestApp, name: &str) {
 /// Helper to delete a proposer pattern
 async fn delete_pattern(app: &TestApp, name: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await;
 }
@@ -61,7 +84,7 @@ async fn delete_pattern(app: &TestApp, name: &str) {
 /// Helper to delete a proposer
 async fn delete_proposer(app: &TestApp, pubkey: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await;
 }
@@ -77,7 +100,7 @@ async fn test_get_execution_config_basic() {
 
     // Create a default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
@@ -97,7 +120,7 @@ async fn test_get_execution_config_basic() {
     // Get execution config
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([]))
         .send()
         .await
@@ -121,7 +144,7 @@ async fn test_get_execution_config_not_found() {
 
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([]))
         .send()
         .await
@@ -137,7 +160,7 @@ async fn test_get_execution_config_inactive() {
 
     // Create an inactive default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
@@ -150,7 +173,7 @@ async fn test_get_execution_config_inactive() {
     // Should return 404 for inactive config
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([]))
         .send()
         .await
@@ -173,7 +196,7 @@ async fn test_get_execution_config_with_proposer_keys() {
 
     // Create default config
     let create_resp = app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
@@ -188,7 +211,7 @@ async fn test_get_execution_config_with_proposer_keys() {
 
     // Create proposer with custom config
     let proposer_resp = app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "fee_recipient": "0x5e8422345238f34275888049021821e8e08caa1f",
             "gas_limit": "35000000",
@@ -203,7 +226,7 @@ async fn test_get_execution_config_with_proposer_keys() {
     // Get execution config with proposer key
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([pubkey]))
         .send()
         .await
@@ -229,6 +252,85 @@ async fn test_get_execution_config_with_proposer_keys() {
     delete_config(app, &config_name).await;
 }
 
+#[tokio::test]
+async fn test_get_execution_config_with_uploaded_keys() {
+    let app = TestApp::get().await;
+    let config_name = unique_config_name("exec_upload");
+    let id = TestApp::unique_id();
+    let pubkeys: Vec<String> = (1..=2)
+        .map(|i| TestApp::test_bls_pubkey(&format!("f11e{}{}", id, i)))
+        .collect();
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name, "active": true }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    for pubkey in &pubkeys {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(&json!({ "fee_recipient": "0x5e8422345238f34275888049021821e8e08caa1f" }))
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+    let url = format!("{}/vouch/v2/execution-config/{}", app.address, config_name);
+
+    // One key per line, comments and blank lines skipped
+    let response = app
+        .client()
+        .post(&url)
+        .header("content-type", "text/plain")
+        .body(format!("# validators\r\n{}\r\n\n{}", pubkeys[0], pubkeys[1]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: ExecutionConfigResponse = response.json().await.unwrap();
+    assert_eq!(body.proposers.unwrap().len(), 2);
+
+    // Keys spread over the files of a multipart upload
+    let boundary = "fee-manager-test-boundary";
+    let multipart = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"keys\"; filename=\"a.txt\"\r\n\
+         Content-Type: text/plain\r\n\r\n{}\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"keys\"; filename=\"b.txt\"\r\n\
+         Content-Type: text/plain\r\n\r\n{}\n\r\n--{b}--\r\n",
+        pubkeys[0],
+        pubkeys[1],
+        b = boundary
+    );
+    let response = app
+        .client()
+        .post(&url)
+        .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+        .body(multipart)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: ExecutionConfigResponse = response.json().await.unwrap();
+    assert_eq!(body.proposers.unwrap().len(), 2);
+
+    let response = app
+        .client()
+        .post(&url)
+        .header("content-type", "text/plain")
+        .body(format!("{}\nnot-a-key\n", pubkeys[0]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["detail"].as_str().unwrap().starts_with("Line 2:"), "{}", body);
+
+    for pubkey in &pubkeys {
+        delete_proposer(app, pubkey).await;
+    }
+    delete_config(app, &config_name).await;
+}
+
 #[tokio::test]
 async fn test_get_execution_config_unknown_keys() {
     let app = TestApp::get().await;
@@ -237,7 +339,7 @@ async fn test_get_execution_config_unknown_keys() {
 
     // Create default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
@@ -250,7 +352,7 @@ async fn test_get_execution_config_unknown_keys() {
     // Request with keys that don't have specific configs
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([unknown_key]))
         .send()
         .await
@@ -288,7 +390,7 @@ async fn test_get_execution_config_with_tags() {
 
     // Create default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
@@ -300,7 +402,7 @@ async fn test_get_execution_config_with_tags() {
 
     // Create proposer pattern with tags
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": pattern_name,
             "pattern": "^0xtest.*$",
@@ -315,7 +417,7 @@ async fn test_get_execution_config_with_tags() {
     // Get execution config with tags filter
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}?tags=lido", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}?tags=lido", app.address, config_name))
         .json(&json!([]))
         .send()
         .await
@@ -337,6 +439,66 @@ async fn test_get_execution_config_with_tags() {
     delete_config(app, &config_name).await;
 }
 
+#[tokio::test]
+async fn test_execution_config_skips_inactive_patterns() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_name = format!("test_exec_inactive_pattern_{}", id);
+    let pattern_name = format!("test_pattern_inactive_{}", id);
+    let tag = format!("paused{}", id);
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name, "active": true }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": pattern_name,
+            "pattern": "^0xpaused.*$",
+            "tags": [tag],
+            "active": false,
+            "relays": { "https://paused.example.com": { "public_key": relay_pubkey } }
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["active"], false);
+
+    let fetch = || async {
+        let response = app.client()
+            .post(format!("{}/vouch/v2/execution-config/{}?tags={}", app.address, config_name, tag))
+            .json(&json!([]))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 200);
+        let body: ExecutionConfigResponse = response.json().await.expect("Failed to parse JSON");
+        body.proposers.unwrap_or_default()
+    };
+    assert!(fetch().await.is_empty());
+
+    // Reactivating brings the pattern back with its relays
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, pattern_name))
+        .json(&json!({ "active": true }))
+        .send()
+        .await
+        .expect("Failed to update pattern");
+    assert_eq!(response.status(), 200);
+    let proposers = fetch().await;
+    assert_eq!(proposers.len(), 1);
+    assert!(proposers[0].relays.as_ref().unwrap().contains_key("https://paused.example.com"));
+
+    delete_pattern(app, &pattern_name).await;
+    delete_config(app, &config_name).await;
+}
+
 // ============================================================================
 // Tag Ordering Tests
 // ============================================================================
@@ -352,7 +514,7 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
 
     // Create default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
@@ -364,7 +526,7 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
 
     // Create proposer
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "fee_recipient": "0x1111111111111111111111111111111111111111"
         }))
@@ -374,7 +536,7 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
 
     // Create pattern with "dev" tag
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": pattern_dev,
             "pattern": "^dev/.*$",
@@ -387,7 +549,7 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
 
     // Create pattern with "lido" tag
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": pattern_lido,
             "pattern": "^lido/.*$",
@@ -401,7 +563,7 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
     // Request with tags=lido,dev - lido should come before dev
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}?tags=lido,dev", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}?tags=lido,dev", app.address, config_name))
         .json(&json!([pubkey]))
         .send()
         .await
@@ -441,6 +603,146 @@ async fn test_execution_config_patterns_after_proposers_in_tag_order() {
     delete_config(app, &config_name).await;
 }
 
+#[tokio::test]
+async fn test_execution_config_tags_match_all() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_name = unique_config_name("exec_tags_all");
+    let lido = format!("lido-{}", id);
+    let mainnet = format!("mainnet-{}", id);
+    let both = format!("test_pattern_both_{}", id);
+    let lido_only = format!("test_pattern_lido_{}", id);
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    for (name, pattern, tags) in [
+        (&both, "^Both/.*$", json!([lido, mainnet])),
+        (&lido_only, "^LidoOnly/.*$", json!([lido])),
+    ] {
+        app.client()
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .json(&json!({ "name": name, "pattern": pattern, "tags": tags }))
+            .send()
+            .await
+            .expect("Failed to create pattern");
+    }
+
+    let patterns = |tags_match: &'static str| {
+        let url = format!(
+            "{}/vouch/v2/execution-config/{}?tags={},{}&tags_match={}",
+            app.address, config_name, lido, mainnet, tags_match
+        );
+        async move {
+            let response = app
+                .client()
+                .post(url)
+                .json(&json!([]))
+                .send()
+                .await
+                .expect("Failed to send request");
+            assert_eq!(response.status(), 200);
+            let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+            let proposers = body["proposers"].as_array().cloned().unwrap_or_default();
+            proposers
+                .iter()
+                .map(|p| p["proposer"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>()
+        }
+    };
+
+    assert_eq!(patterns("any").await, vec!["^Both/.*$", "^LidoOnly/.*$"]);
+    assert_eq!(patterns("all").await, vec!["^Both/.*$"]);
+
+    delete_pattern(app, &both).await;
+    delete_pattern(app, &lido_only).await;
+    delete_config(app, &config_name).await;
+}
+
+#[tokio::test]
+async fn test_execution_config_merges_parent_chain() {
+    let app = TestApp::get().await;
+    let base = unique_config_name("exec_base");
+    let child = unique_config_name("exec_child");
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": base,
+            "fee_recipient": "0x1111111111111111111111111111111111111111",
+            "gas_limit": "30000000",
+            "relays": {
+                "https://a.example.com": { "public_key": relay_pubkey },
+                "https://b.example.com": { "public_key": relay_pubkey, "min_value": "0.1 eth" }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create base config");
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": child,
+            "parent": base,
+            "fee_recipient": "0x2222222222222222222222222222222222222222",
+            "relays": {
+                "https://b.example.com": { "public_key": relay_pubkey, "min_value": "0.5 eth" },
+                "https://c.example.com": { "public_key": relay_pubkey }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create child config");
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["parent"], base.as_str());
+
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, child))
+        .json(&json!([]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["fee_recipient"], "0x2222222222222222222222222222222222222222");
+    assert_eq!(body["gas_limit"], "30000000");
+    let relays = body["relays"].as_object().expect("Should have relays");
+    assert_eq!(relays.len(), 3);
+    assert_eq!(relays["https://b.example.com"]["min_value"], "0.5");
+
+    // The parent may not point back at its child, nor be deleted while in use
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, base))
+        .json(&json!({ "parent": child }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, base))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 409);
+
+    delete_config(app, &child).await;
+    delete_config(app, &base).await;
+}
+
 // ============================================================================
 // Multiple Proposers Test
 // ============================================================================
@@ -453,7 +755,7 @@ async fn test_get_execution_config_multiple_proposers() {
 
     // Create default config
     app.client()
-        .post(&format!("{}/api/admin/vouch/configs/default", app.address))
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
         .json(&json!({
             "name": config_name,
             "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
@@ -470,7 +772,7 @@ async fn test_get_execution_config_multiple_proposers() {
 
     for (i, pubkey) in [&pubkey1, &pubkey2, &pubkey3].iter().enumerate() {
         app.client()
-            .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
             .json(&json!({
                 "gas_limit": format!("{}0000000", 30 + i)
             }))
@@ -482,7 +784,7 @@ async fn test_get_execution_config_multiple_proposers() {
     // Get execution config with all three keys
     let response = app
         .client()
-        .post(&format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
         .json(&json!([pubkey1.clone(), pubkey2.clone(), pubkey3.clone()]))
         .send()
         .await
@@ -502,3 +804,671 @@ async fn test_get_execution_config_multiple_proposers() {
     }
     delete_config(app, &config_name).await;
 }
+
+// ============================================================================
+// Batch Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_execution_configs_batch() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_a = format!("test_exec_batch_a_{}", id);
+    let config_b = format!("test_exec_batch_b_{}", id);
+    let pubkey = TestApp::test_bls_pubkey(&format!("ba{}", id));
+
+    for (name, fee_recipient) in [
+        (&config_a, "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+        (&config_b, "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+    ] {
+        app.client()
+            .post(format!("{}/api/admin/vouch/configs/default", app.address))
+            .json(&json!({ "name": name, "fee_recipient": fee_recipient }))
+            .send()
+            .await
+            .expect("Failed to create config");
+    }
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "min_value": "0.5 eth" }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config:batch", app.address))
+        .json(&json!({ "configs": [config_a, config_b], "keys": [pubkey] }))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 200);
+
+    let body: HashMap<String, ExecutionConfigResponse> =
+        response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.len(), 2);
+    assert_eq!(
+        body[&config_a].fee_recipient,
+        Some("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string())
+    );
+    assert_eq!(
+        body[&config_b].fee_recipient,
+        Some("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string())
+    );
+    for config in body.values() {
+        let proposers = config.proposers.as_ref().unwrap();
+        assert_eq!(proposers.len(), 1);
+        assert_eq!(proposers[0].proposer, pubkey);
+    }
+
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &config_a).await;
+    delete_config(app, &config_b).await;
+}
+
+#[tokio::test]
+async fn test_get_execution_configs_batch_unknown_config() {
+    let app = TestApp::get().await;
+    let config_name = unique_config_name("exec_batch_known");
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config:batch", app.address))
+        .json(&json!({
+            "configs": [config_name, unique_config_name("exec_batch_missing")],
+            "keys": []
+        }))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 404);
+
+    delete_config(app, &config_name).await;
+}
+
+// ============================================================================
+// Builder Options Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execution_config_builder_options() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_name = unique_config_name("exec_builder");
+    let pattern_name = format!("test_pattern_builder_{}", id);
+    let tag = format!("builder-{}", id);
+    let pubkey = TestApp::test_bls_pubkey(&format!("b0{}01", id));
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "builder_enabled": true,
+            "builder_boost_factor": "90",
+            "relays": { "https://default.example.com": { "public_key": relay_pubkey } }
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "builder_enabled": false }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["builder_enabled"], false);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": pattern_name,
+            "pattern": "^Builder/.*$",
+            "tags": [tag],
+            "builder_boost_factor": "100"
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["builder_boost_factor"], "100");
+    assert!(body.get("builder_enabled").is_none());
+
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config/{}?tags={}", app.address, config_name, tag))
+        .json(&json!([pubkey]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["builder_enabled"], true);
+    assert_eq!(body["builder_boost_factor"], "90");
+    let proposers = body["proposers"].as_array().unwrap();
+    assert_eq!(proposers[0]["proposer"], pubkey.as_str());
+    assert_eq!(proposers[0]["builder_enabled"], false);
+    assert_eq!(proposers[1]["proposer"], "^Builder/.*$");
+    assert_eq!(proposers[1]["builder_boost_factor"], "100");
+
+    // v1 honours an explicit builder_enabled over the presence of relays
+    let response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/{}", app.address, config_name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: ExecutionConfigV1Response = response.json().await.expect("Failed to parse JSON");
+    let builder = body.proposer_config[&pubkey].builder.as_ref().unwrap();
+    assert_eq!(builder.relays, vec!["https://default.example.com"]);
+    assert!(!builder.enabled);
+
+    delete_pattern(app, &pattern_name).await;
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &config_name).await;
+}
+
+// ============================================================================
+// Legacy v1 Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_execution_config_v1() {
+    let app = TestApp::get().await;
+    let config_name = unique_config_name("exec_v1");
+    let id = TestApp::unique_id();
+    let merged = TestApp::test_bls_pubkey(&format!("e1{}01", id));
+    let reset = TestApp::test_bls_pubkey(&format!("e1{}02", id));
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "fee_recipient": "0xdef1def1def1def1def1def1def1def1def1def1",
+            "gas_limit": "30000000",
+            "grace": "1000",
+            "relays": { "https://default.example.com": { "public_key": relay_pubkey } }
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, merged))
+        .json(&json!({
+            "gas_limit": "35000000",
+            "relays": {
+                "https://extra.example.com": { "public_key": relay_pubkey },
+                "https://disabled.example.com": { "public_key": relay_pubkey, "disabled": true }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, reset))
+        .json(&json!({
+            "fee_recipient": "0x5e8422345238f34275888049021821e8e08caa1f",
+            "reset_relays": true
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    let response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/{}", app.address, config_name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: ExecutionConfigV1Response = response.json().await.expect("Failed to parse JSON");
+
+    let default_builder = body.default_config.builder.as_ref().unwrap();
+    assert_eq!(body.default_config.fee_recipient, Some("0xdef1def1def1def1def1def1def1def1def1def1".to_string()));
+    assert!(default_builder.enabled);
+    assert_eq!(default_builder.relays, vec!["https://default.example.com"]);
+    assert_eq!(default_builder.grace, Some("1000".to_string()));
+
+    // Proposer relays extend the defaults; unset fields fall back to the default config
+    let merged_config = &body.proposer_config[&merged];
+    assert_eq!(merged_config.fee_recipient, Some("0xdef1def1def1def1def1def1def1def1def1def1".to_string()));
+    assert_eq!(merged_config.gas_limit, Some("35000000".to_string()));
+    assert_eq!(
+        merged_config.builder.as_ref().unwrap().relays,
+        vec!["https://default.example.com", "https://extra.example.com"]
+    );
+
+    // reset_relays without own relays disables the builder
+    let reset_config = &body.proposer_config[&reset];
+    assert_eq!(reset_config.fee_recipient, Some("0x5e8422345238f34275888049021821e8e08caa1f".to_string()));
+    assert!(!reset_config.builder.as_ref().unwrap().enabled);
+    assert!(merged_config.builder.as_ref().unwrap().disabled_relays.is_none());
+
+    // On request, relays left out because they are disabled are listed
+    let body: ExecutionConfigV1Response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/{}?include_disabled=true", app.address, config_name))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    let merged_builder = body.proposer_config[&merged].builder.as_ref().unwrap();
+    assert_eq!(
+        merged_builder.relays,
+        vec!["https://default.example.com", "https://extra.example.com"]
+    );
+    assert_eq!(merged_builder.disabled_relays, Some(vec!["https://disabled.example.com".to_string()]));
+    assert_eq!(body.default_config.builder.as_ref().unwrap().disabled_relays, Some(vec![]));
+
+    delete_proposer(app, &merged).await;
+    delete_proposer(app, &reset).await;
+    delete_config(app, &config_name).await;
+}
+
+#[tokio::test]
+async fn test_get_execution_config_v1_not_found() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/nonexistent_v1_config", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_globally_disabled_relay() {
+    let app = TestApp::get().await;
+    let config_name = unique_config_name("exec_incident");
+    let id = TestApp::unique_id();
+    let pubkey = TestApp::test_bls_pubkey(&format!("1d{}", id));
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+    let incident = format!("https://incident-{}.example.com", id);
+    let encoded: String = url::form_urlencoded::byte_serialize(incident.as_bytes()).collect();
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "relays": {
+                incident.as_str(): { "public_key": relay_pubkey },
+                "https://healthy.example.com": { "public_key": relay_pubkey }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "relays": { incident.as_str(): { "public_key": relay_pubkey } } }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/relays/{}/disable", app.address, encoded))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["url"], incident.as_str());
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/relays/disabled", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body.as_array().unwrap().iter().any(|r| r["url"] == incident.as_str()));
+
+    // v2 flags the relay everywhere it appears
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .json(&json!([pubkey]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["relays"][incident.as_str()]["disabled"], true);
+    assert!(body["relays"]["https://healthy.example.com"].get("disabled").is_none());
+    assert_eq!(body["proposers"][0]["relays"][incident.as_str()]["disabled"], true);
+
+    // v1 has no flag and leaves it out
+    let response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/{}", app.address, config_name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: ExecutionConfigV1Response = response.json().await.unwrap();
+    assert_eq!(
+        body.default_config.builder.as_ref().unwrap().relays,
+        vec!["https://healthy.example.com"]
+    );
+    assert_eq!(
+        body.proposer_config[&pubkey].builder.as_ref().unwrap().relays,
+        vec!["https://healthy.example.com"]
+    );
+    let body: ExecutionConfigV1Response = app
+        .client()
+        .get(format!("{}/vouch/v1/execution-config/{}?include_disabled=true", app.address, config_name))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body.default_config.builder.as_ref().unwrap().disabled_relays, Some(vec![incident.clone()]));
+    assert_eq!(
+        body.proposer_config[&pubkey].builder.as_ref().unwrap().disabled_relays,
+        Some(vec![incident.clone()])
+    );
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/relays/{}/enable", app.address, encoded))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 204);
+
+    let response = app
+        .client()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .json(&json!([]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert!(body["relays"][incident.as_str()].get("disabled").is_none());
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/relays/{}/enable", app.address, encoded))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &config_name).await;
+}
+
+#[tokio::test]
+async fn test_execution_config_etag_shared_across_replicas() {
+    let app = TestApp::get().await;
+    // A second app on the same database stands in for another replica
+    let replica = TestApp::spawn_with_config(|_| {}).await;
+    let config_name = unique_config_name("exec_etag");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
+            "gas_limit": "30000000",
+            "active": true
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    let url = |base: &str| format!("{}/vouch/v2/execution-config/{}", base, config_name);
+
+    // Other tests write to the same tables concurrently, so retry until two
+    // requests land on the same version
+    let mut matched = None;
+    for _ in 0..10 {
+        let response = app.client().post(url(&app.address)).json(&json!([])).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        let etag = response.headers()["etag"].to_str().unwrap().to_string();
+
+        let response = replica
+            .client()
+            .post(url(&replica.address))
+            .header("If-None-Match", &etag)
+            .json(&json!([]))
+            .send()
+            .await
+            .unwrap();
+        if response.status() == 304 {
+            assert_eq!(response.headers()["etag"].to_str().unwrap(), etag);
+            matched = Some(etag);
+            break;
+        }
+        assert_eq!(response.status(), 200);
+    }
+    let etag = matched.expect("replicas never agreed on an ETag");
+
+    // A write on one replica changes the ETag served by the other
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/configs/default/{}", app.address, config_name))
+        .json(&json!({ "gas_limit": "35000000" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = replica
+        .client()
+        .post(url(&replica.address))
+        .header("If-None-Match", &etag)
+        .json(&json!([]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_ne!(response.headers()["etag"].to_str().unwrap(), etag);
+    let body: ExecutionConfigResponse = response.json().await.unwrap();
+    assert_eq!(body.gas_limit, Some("35000000".to_string()));
+
+    delete_config(app, &config_name).await;
+}
+
+// ============================================================================
+// Export Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_export_execution_config_file() {
+    let app = TestApp::get().await;
+    let config_name = unique_config_name("exec_export");
+    let pubkey = TestApp::test_bls_pubkey(&format!("exp{}", TestApp::unique_id()));
+    let retired = TestApp::test_bls_pubkey(&format!("expr{}", TestApp::unique_id()));
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "fee_recipient": "0xe4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4",
+            "active": true
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    for key in [&pubkey, &retired] {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .json(&json!({ "gas_limit": "36000000" }))
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+    app.client()
+        .post(format!("{}/api/admin/vouch/proposers/{}/decommission", app.address, retired))
+        .send()
+        .await
+        .expect("Failed to decommission proposer");
+
+    let response = app.client()
+        .get(format!(
+            "{}/api/admin/vouch/configs/default/{}/export?format=vouch-json",
+            app.address, config_name
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-disposition"],
+        format!("attachment; filename=\"{}-execution-config.json\"", config_name).as_str()
+    );
+
+    // Every active proposer is included without being asked for
+    let body: ExecutionConfigResponse = response.json().await.unwrap();
+    assert_eq!(body.version, 2);
+    assert_eq!(body.fee_recipient, Some("0xe4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4".to_string()));
+    let proposers = body.proposers.unwrap();
+    assert!(proposers.iter().any(|p| p.proposer == pubkey));
+    assert!(!proposers.iter().any(|p| p.proposer == retired));
+
+    let response = app.client()
+        .get(format!(
+            "{}/api/admin/vouch/configs/default/{}/export?format=yaml",
+            app.address, config_name
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    delete_proposer(app, &pubkey).await;
+    delete_proposer(app, &retired).await;
+    delete_config(app, &config_name).await;
+}
+
+// ============================================================================
+// Explain Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_explain_execution_config() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let base = unique_config_name("explain_base");
+    let child = unique_config_name("explain_child");
+    let pattern_name = format!("test_explain_pattern_{}", id);
+    let tag = format!("explain{}", id);
+    let proposer = TestApp::test_bls_pubkey(&format!("e1{}", id));
+    let matched = TestApp::test_bls_pubkey(&format!("e2{}", id));
+    let unknown = TestApp::test_bls_pubkey(&format!("e3{}", id));
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    for body in [
+        json!({
+            "name": base,
+            "gas_limit": "30000000",
+            "relays": { "https://base.example.com": { "public_key": relay_pubkey } }
+        }),
+        json!({ "name": child, "parent": base, "fee_recipient": "0x1111111111111111111111111111111111111111" }),
+    ] {
+        let response = app.client()
+            .post(format!("{}/api/admin/vouch/configs/default", app.address))
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to create config");
+        assert_eq!(response.status(), 201);
+    }
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, proposer))
+        .json(&json!({
+            "fee_recipient": "0x2222222222222222222222222222222222222222",
+            "relays": { "https://own.example.com": { "public_key": relay_pubkey } }
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": pattern_name,
+            "pattern": format!("^{}$", matched),
+            "tags": [tag],
+            "gas_limit": "36000000",
+            "reset_relays": true
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    assert_eq!(response.status(), 201);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/execution-config/{}/explain?tags={}", app.address, child, tag))
+        .json(&json!([proposer, matched, unknown]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let keys = body["keys"].as_array().unwrap();
+    assert_eq!(keys.len(), 3);
+
+    // Proposer value over the child default, parent default underneath, relays merged
+    let explained = &keys[0];
+    assert_eq!(explained["entry"], proposer.as_str());
+    assert_eq!(explained["fee_recipient"]["value"], "0x2222222222222222222222222222222222222222");
+    assert_eq!(explained["fee_recipient"]["source"], "proposer");
+    assert_eq!(explained["gas_limit"]["source"], "default");
+    assert_eq!(explained["gas_limit"]["name"], base.as_str());
+    assert_eq!(explained["relays"]["https://base.example.com"]["name"], base.as_str());
+    assert_eq!(explained["relays"]["https://own.example.com"]["source"], "proposer");
+
+    // The pattern resets the default relays
+    let explained = &keys[1];
+    assert_eq!(explained["entry"], format!("^{}$", matched).as_str());
+    assert_eq!(explained["gas_limit"]["source"], "pattern");
+    assert_eq!(explained["gas_limit"]["name"], pattern_name.as_str());
+    assert_eq!(explained["fee_recipient"]["name"], child.as_str());
+    assert_eq!(explained["reset_relays"], true);
+    assert!(explained["relays"].as_object().unwrap().is_empty());
+
+    // Keys no entry matches get the defaults
+    let explained = &keys[2];
+    assert!(explained.get("entry").is_none());
+    assert_eq!(explained["fee_recipient"]["source"], "default");
+    assert_eq!(explained["relays"].as_object().unwrap().len(), 1);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/execution-config/{}_missing/explain", app.address, child))
+        .json(&json!([]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    delete_pattern(app, &pattern_name).await;
+    delete_proposer(app, &proposer).await;
+    delete_config(app, &child).await;
+    delete_config(app, &base).await;
+}
@@ -0,0 +1,202 @@
+// tests/export_storage_test.rs - Exports written to an S3-compatible bucket
+mod common;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::put,
+    Router,
+};
+use common::TestApp;
+use fee_manager::config::{BackupRetentionConfig, ExportStorageConfig};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Objects a fake bucket received (path, authorization header, body) and the paths deleted from it
+#[derive(Clone, Default)]
+struct Bucket {
+    received: Arc<Mutex<Vec<(String, String, Bytes)>>>,
+    deleted: Arc<Mutex<Vec<String>>>,
+}
+
+/// Path-style S3 stand-in that records what it is sent and refuses the bucket `denied`
+async fn spawn_bucket() -> (String, Bucket) {
+    let bucket = Bucket::default();
+    let app = Router::new()
+        .route(
+            "/{bucket}/{*key}",
+            put(
+                |State(state): State<Bucket>, Path((bucket, key)): Path<(String, String)>, headers: HeaderMap, body: Bytes| async move {
+                    if bucket == "denied" {
+                        return StatusCode::FORBIDDEN;
+                    }
+                    let authorization = headers["authorization"].to_str().unwrap().to_string();
+                    state.received.lock().unwrap().push((format!("{}/{}", bucket, key), authorization, body));
+                    StatusCode::OK
+                },
+            )
+            .delete(|State(state): State<Bucket>, Path((bucket, key)): Path<(String, String)>| async move {
+                state.deleted.lock().unwrap().push(format!("{}/{}", bucket, key));
+                StatusCode::NO_CONTENT
+            }),
+        )
+        .with_state(bucket.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (address, bucket)
+}
+
+fn storage(endpoint: &str, bucket: &str) -> ExportStorageConfig {
+    ExportStorageConfig {
+        endpoint: endpoint.parse().unwrap(),
+        bucket: bucket.to_string(),
+        region: "us-east-1".to_string(),
+        prefix: "test/".to_string(),
+        access_key_id: "test-key".to_string(),
+        secret_access_key: Some("test-secret".to_string()),
+        secret_access_key_file: None,
+        path_style: true,
+        timeout_secs: 5,
+    }
+}
+
+#[tokio::test]
+async fn test_export_patterns_to_storage() {
+    let (endpoint, bucket) = spawn_bucket().await;
+    let config = storage(&endpoint, "backups");
+    let app = TestApp::spawn_with_config(move |c| c.export_storage = Some(config)).await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/export?destination=storage", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+    let stored: Value = response.json().await.unwrap();
+    assert_eq!(stored["bucket"], "backups");
+    let key = stored["key"].as_str().unwrap();
+    assert!(key.starts_with("test/proposer-patterns/") && key.ends_with(".json"), "{}", key);
+
+    let received = bucket.received.lock().unwrap();
+    let (path, authorization, body) = &received[0];
+    assert_eq!(path, &format!("backups/{}", key));
+    assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=test-key/"), "{}", authorization);
+    assert_eq!(stored["size"], body.len());
+    assert!(serde_json::from_slice::<Value>(body).unwrap().is_array());
+}
+
+#[tokio::test]
+async fn test_export_storage_errors() {
+    // Not configured
+    let app = TestApp::get().await;
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/export?destination=storage", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    // Refused by the bucket
+    let (endpoint, _) = spawn_bucket().await;
+    let config = storage(&endpoint, "denied");
+    let app = TestApp::spawn_with_config(move |c| c.export_storage = Some(config)).await;
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/export?destination=storage", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 502);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "UPSTREAM_FAILED");
+}
+
+/// A pool of the calling test's own; the shared app's pool belongs to the
+/// runtime of the test that created it
+async fn pool() -> sqlx::PgPool {
+    let config = fee_manager::config::load_config().expect("Failed to load test config");
+    sqlx::PgPool::connect(&config.database.database_url())
+        .await
+        .expect("Failed to connect to database")
+}
+
+/// A run of the schedule no other test run takes: a random minute in year 2000
+fn unique_run() -> DateTime<Utc> {
+    let minute = (Uuid::new_v4().as_u128() % (365 * 24 * 60)) as i64;
+    Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minute)
+}
+
+#[tokio::test]
+async fn test_scheduled_backups() {
+    let app = TestApp::get().await;
+    let pool = pool().await;
+    let (endpoint, bucket) = spawn_bucket().await;
+    let storage = storage(&endpoint, "backups");
+    let retention = BackupRetentionConfig { keep_last: 0, max_age_days: 0 };
+
+    let first_run = unique_run();
+    let first = fee_manager::backups::run(&pool, &storage, &retention, first_run)
+        .await
+        .unwrap()
+        .expect("run not claimed yet");
+    assert_eq!(first.key.as_deref(), Some(format!("test/backups/{}.json", first_run.format("%Y%m%dT%H%M%SZ")).as_str()));
+    {
+        let received = bucket.received.lock().unwrap();
+        let snapshot: Value = serde_json::from_slice(&received[0].2).unwrap();
+        assert!(snapshot["schema_version"].as_i64().unwrap() > 0);
+        assert!(snapshot["tables"]["vouch_proposers"].is_array());
+        assert!(snapshot["tables"]["commit_boost_mux_keys"].is_array());
+    }
+
+    // Another replica waking for the same run leaves it alone
+    let again = fee_manager::backups::run(&pool, &storage, &retention, first_run).await.unwrap();
+    assert!(again.is_none());
+
+    // Keeping only the newest snapshot deletes the first
+    let keep_one = BackupRetentionConfig { keep_last: 1, max_age_days: 0 };
+    let second_run = Utc::now() + Duration::days(365 * 100);
+    let second = fee_manager::backups::run(&pool, &storage, &keep_one, second_run)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(bucket.deleted.lock().unwrap().contains(&format!("backups/{}", first.key.unwrap())));
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/backups", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["enabled"], false);
+    assert_eq!(body["schedule"], "0 3 * * *");
+    assert!(body.get("next_run_at").is_none());
+    let backups = body["backups"].as_array().unwrap();
+    assert_eq!(backups[0]["id"], second.id.to_string());
+    assert_eq!(backups[0]["status"], "succeeded");
+    assert!(backups.iter().all(|backup| backup["id"] != first.id.to_string()));
+}
+
+#[tokio::test]
+async fn test_failed_backup_is_recorded() {
+    TestApp::get().await;
+    let pool = pool().await;
+    let (endpoint, _) = spawn_bucket().await;
+    let storage = storage(&endpoint, "denied");
+    let retention = BackupRetentionConfig { keep_last: 1, max_age_days: 0 };
+
+    let backup = fee_manager::backups::run(&pool, &storage, &retention, unique_run())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(backup.status, fee_manager::schema::JobStatus::Failed);
+    assert!(backup.key.is_none());
+    assert!(backup.error.unwrap().contains("403"));
+}
@@ -0,0 +1,153 @@
+// tests/graphql_test.rs - Read-only GraphQL API tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+const RELAY_KEY: &str = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+async fn graphql(app: &TestApp, query: &str, variables: Value) -> Value {
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/graphql", app.address))
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    response.json().await.expect("Failed to parse JSON")
+}
+
+#[tokio::test]
+async fn test_graphql_disabled_by_default() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/graphql", app.address))
+        .json(&json!({ "query": "{ muxes { name } }" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_graphql_requires_auth() {
+    let app = TestApp::spawn_with_config(|c| c.graphql_enabled = true).await;
+
+    let response = app
+        .client_unauthenticated()
+        .post(format!("{}/api/admin/graphql", app.address))
+        .json(&json!({ "query": "{ muxes { name } }" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn test_graphql_proposer_with_relays_patterns_and_muxes() {
+    let app = TestApp::spawn_with_config(|c| c.graphql_enabled = true).await;
+    let id = TestApp::unique_id();
+    let pubkey = TestApp::test_bls_pubkey(&format!("9a{}", id));
+    let pattern_name = format!("test_gql_{}", id);
+    let mux_name = format!("test_gql_{}", id);
+
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({
+            "reset_relays": false,
+            "labels": { "team": format!("gql{}", id) },
+            "relays": { "https://relay1.example.com": { "public_key": RELAY_KEY } }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": pattern_name,
+            "pattern": format!("^{}$", pubkey),
+            "tags": ["gql"],
+            "reset_relays": false,
+            "relays": { "https://relay2.example.com": { "public_key": RELAY_KEY } }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_name, "keys": [pubkey] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let body = graphql(
+        &app,
+        "query($team: String!) {
+            proposers(labels: [{ key: \"team\", value: $team }]) {
+                publicKey
+                labels { key value }
+                relays { url publicKey disabled }
+                matchingPatterns { name relays { url } }
+                muxes
+            }
+        }",
+        json!({ "team": format!("gql{}", id) }),
+    )
+    .await;
+    assert!(body.get("errors").is_none(), "unexpected errors: {}", body);
+
+    let proposers = body["data"]["proposers"].as_array().unwrap();
+    assert_eq!(proposers.len(), 1);
+    let proposer = &proposers[0];
+    assert_eq!(proposer["publicKey"], pubkey);
+    assert_eq!(proposer["relays"][0]["url"], "https://relay1.example.com");
+    assert_eq!(proposer["relays"][0]["disabled"], false);
+    assert_eq!(proposer["matchingPatterns"][0]["name"], pattern_name);
+    assert_eq!(
+        proposer["matchingPatterns"][0]["relays"][0]["url"],
+        "https://relay2.example.com"
+    );
+    assert_eq!(proposer["muxes"], json!([mux_name]));
+
+    let body = graphql(
+        &app,
+        "query($name: String!) { mux(name: $name) { keyCount keys } missing: mux(name: \"test_gql_none\") { name } }",
+        json!({ "name": mux_name }),
+    )
+    .await;
+    assert_eq!(body["data"]["mux"]["keyCount"], 1);
+    assert_eq!(body["data"]["mux"]["keys"], json!([pubkey]));
+    assert!(body["data"]["missing"].is_null());
+
+    // Out-of-range pages are reported as GraphQL errors
+    let body = graphql(&app, "{ proposers(limit: 0) { publicKey } }", json!({})).await;
+    assert!(body["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("limit"));
+
+    for path in [
+        format!("vouch/proposers/{}", pubkey),
+        format!("vouch/proposer-patterns/{}", pattern_name),
+        format!("commit-boost/mux/{}", mux_name),
+    ] {
+        app.client()
+            .delete(format!("{}/api/admin/{}", app.address, path))
+            .send()
+            .await
+            .unwrap();
+    }
+}
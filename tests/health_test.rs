@@ -3,6 +3,7 @@ mod common;
 
 use common::TestApp;
 use serde::Deserialize;
+use serde_json::Value;
 
 #[derive(Debug, Deserialize)]
 struct HealthResponse {
@@ -15,7 +16,7 @@ async fn test_health_endpoint() {
 
     let response = app
         .client()
-        .get(&format!("{}/health", app.address))
+        .get(format!("{}/health", app.address))
         .send()
         .await
         .expect("Failed to send request");
@@ -26,13 +27,62 @@ async fn test_health_endpoint() {
     assert_eq!(body.status, "healthy");
 }
 
+#[tokio::test]
+async fn test_health_verbose_requires_auth() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/health?verbose=true", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 401);
+
+    let response = app
+        .client()
+        .get(format!("{}/health?verbose=true", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["status"], "healthy");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body["uptime_secs"].is_u64());
+    assert!(body["pool"]["size"].as_u64().unwrap() >= 1);
+    assert!(body["pool"]["max_connections"].as_u64().unwrap() >= 1);
+    assert!(body["migrations"]["applied"].as_u64().unwrap() >= 1);
+    assert_eq!(body["migrations"]["pending"], serde_json::json!([]));
+    assert!(body["audit"]["enabled"].is_boolean());
+}
+
+#[tokio::test]
+async fn test_version_endpoint() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/version", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(body["built_at"].is_string());
+    assert!(body["features"].is_array());
+}
+
 #[tokio::test]
 async fn test_ready_endpoint() {
     let app = TestApp::get().await;
 
     let response = app
         .client()
-        .get(&format!("{}/ready", app.address))
+        .get(format!("{}/ready", app.address))
         .send()
         .await
         .expect("Failed to send request");
@@ -42,3 +92,96 @@ async fn test_ready_endpoint() {
     let body: HealthResponse = response.json().await.expect("Failed to parse JSON");
     assert_eq!(body.status, "ready");
 }
+
+#[tokio::test]
+async fn test_admin_routes_on_separate_listener() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.listen.admin = Some(fee_manager::config::AdminListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+        })
+    })
+    .await;
+    assert_ne!(app.address, app.admin_address);
+    let client = app.client();
+
+    // Public listener: Vouch endpoints and health, no admin API or Swagger
+    // An empty body is rejected by the handler, an unrouted path gives 404
+    let response = client
+        .post(format!("{}/vouch/v2/execution-config/test_split", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_ne!(response.status(), 404);
+    for path in [
+        "/api/v1/admin/whoami",
+        "/api/admin/whoami",
+        "/api-doc/openapi.json",
+        "/api-doc/audit-schema.json",
+    ] {
+        let response = client
+            .get(format!("{}{}", app.address, path))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 404, "{} on public listener", path);
+    }
+
+    // Admin listener: admin API, Swagger and health, no Vouch endpoints
+    for path in [
+        "/api/v1/admin/whoami",
+        "/api/admin/whoami",
+        "/api-doc/openapi.json",
+        "/api-doc/audit-schema.json",
+        "/health",
+    ] {
+        let response = client
+            .get(format!("{}{}", app.admin_address, path))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 200, "{} on admin listener", path);
+    }
+    let response = client
+        .post(format!("{}/vouch/v2/execution-config/test_split", app.admin_address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_pgbouncer_mode_serves_requests() {
+    let app = TestApp::spawn_with_config(|c| c.database.pgbouncer_mode = true).await;
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/configs/default", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_migrations_endpoint() {
+    let app = TestApp::get().await;
+    let url = format!("{}/api/admin/migrations", app.address);
+
+    let response = app.client().get(&url).send().await.expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    let migrations = body["migrations"].as_array().unwrap();
+    assert_eq!(body["pending"], 0);
+    assert_eq!(body["applied"].as_u64().unwrap() as usize, migrations.len());
+    let first = &migrations[0];
+    assert_eq!(first["state"], "applied");
+    assert!(first["version"].is_i64());
+    assert!(first["description"].is_string());
+    assert!(first["installed_on"].is_string());
+    assert!(first.get("checksum_mismatch").is_none());
+    let versions: Vec<i64> = migrations.iter().map(|m| m["version"].as_i64().unwrap()).collect();
+    assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let response = app.client_unauthenticated().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+}
@@ -0,0 +1,322 @@
+// tests/import_test.rs - Vouch execution config file import tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+/// Helper to create unique config name for this test
+fn unique_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, TestApp::unique_id())
+}
+
+/// Helper to delete a config
+async fn delete_config(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await;
+}
+
+/// Helper to delete a proposer
+async fn delete_proposer(app: &TestApp, pubkey: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await;
+}
+
+/// Helper to delete a proposer pattern
+async fn delete_pattern(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .send()
+        .await;
+}
+
+// ============================================================================
+// v2 Import Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_import_v2_execution_config() {
+    let app = TestApp::get().await;
+    let name = unique_name("import_v2");
+    let pubkey = TestApp::test_bls_pubkey(&format!("1a{}", TestApp::unique_id()));
+    let relay_key = TestApp::test_bls_pubkey("b1");
+    let fee_recipient = TestApp::test_eth_address("b1");
+
+    let file = json!({
+        "version": 2,
+        "fee_recipient": fee_recipient,
+        "gas_limit": "36000000",
+        "min_value": "0.05",
+        "grace": "1000",
+        "relays": {
+            "https://relay-a.example.com": { "public_key": relay_key }
+        },
+        "proposers": [
+            {
+                "proposer": pubkey,
+                "min_value": "0.1",
+                "reset_relays": true,
+                "relays": {
+                    "https://relay-b.example.com": { "public_key": relay_key }
+                }
+            },
+            {
+                "proposer": "^Wallet 1/.*$",
+                "builder_enabled": false
+            }
+        ]
+    });
+    let url = format!("{}/api/admin/vouch/import/execution-config?name={}", app.address, name);
+
+    // Dry run reports what would happen and writes nothing
+    let response = app.client()
+        .post(format!("{}&dry_run=true", url))
+        .json(&file)
+        .send()
+        .await
+        .expect("Failed to import execution config");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["version"], 2);
+    assert_eq!(body["default_config"]["action"], "created");
+    assert_eq!(body["default_config"]["relays"], 1);
+    assert_eq!(body["proposers"][0]["id"], pubkey);
+    assert_eq!(body["patterns"][0]["id"], format!("{}-1", name));
+
+    let response = app.client()
+        .head(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Real import
+    let response = app.client()
+        .post(&url)
+        .json(&file)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["dry_run"], false);
+    assert_eq!(body["proposers"][0]["action"], "created");
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["gas_limit"], "36000000");
+    assert_eq!(body["min_value"], "0.05");
+    assert_eq!(body["grace"], "1000");
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["min_value"], "0.1");
+    assert_eq!(body["reset_relays"], true);
+    assert!(body["relays"]["https://relay-b.example.com"].is_object());
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}-1", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["pattern"], "^Wallet 1/.*$");
+    assert_eq!(body["tags"], json!([name]));
+
+    // Importing again updates in place
+    let response = app.client()
+        .post(&url)
+        .json(&file)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["default_config"]["action"], "updated");
+    assert_eq!(body["proposers"][0]["action"], "updated");
+
+    delete_pattern(app, &format!("{}-1", name)).await;
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let app = TestApp::get().await;
+    let name = unique_name("round_trip");
+    let pubkey = TestApp::test_bls_pubkey(&format!("1c{}", TestApp::unique_id()));
+    let relay_key = TestApp::test_bls_pubkey("b3");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": name,
+            "min_value": "0.05",
+            "relays": {
+                "https://relay-a.example.com": { "public_key": relay_key, "min_value": "200000000 gwei" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "min_value": "1.5 eth" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    // The file carries ETH, as Vouch reads it
+    let mut file: Value = app.client()
+        .get(format!("{}/api/admin/vouch/configs/default/{}/export", app.address, name))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(file["min_value"], "0.05");
+    assert_eq!(file["relays"]["https://relay-a.example.com"]["min_value"], "0.2");
+    // Only this test's proposer, so others running alongside are left alone
+    let entry = file["proposers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["proposer"] == pubkey.as_str())
+        .cloned()
+        .expect("Proposer should be exported");
+    assert_eq!(entry["min_value"], "1.5");
+    file["proposers"] = json!([entry]);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/import/execution-config?name={}", app.address, name))
+        .json(&file)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Stored values are unchanged
+    let body: Value = app.client()
+        .get(format!("{}/api/admin/vouch/configs/default/{}?units=wei", app.address, name))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["min_value"], "50000000000000000");
+    assert_eq!(body["relays"]["https://relay-a.example.com"]["min_value"], "200000000000000000");
+    let body: Value = app.client()
+        .get(format!("{}/api/admin/vouch/proposers/{}?units=wei", app.address, pubkey))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["min_value"], "1500000000000000000");
+
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &name).await;
+}
+
+// ============================================================================
+// v1 Import Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_import_v1_execution_config() {
+    let app = TestApp::get().await;
+    let name = unique_name("import_v1");
+    let pubkey = TestApp::test_bls_pubkey(&format!("1b{}", TestApp::unique_id()));
+    let relay_key = TestApp::test_bls_pubkey("b2");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/import/execution-config?name={}", app.address, name))
+        .json(&json!({
+            "default_config": {
+                "gas_limit": "30000000",
+                "builder": {
+                    "enabled": true,
+                    "relays": [format!("https://{}@relay-a.example.com", relay_key)],
+                    "grace": "500"
+                }
+            },
+            "proposer_config": {
+                pubkey.clone(): { "builder": { "enabled": false, "relays": [] } }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["version"], 1);
+    assert_eq!(body["default_config"]["relays"], 1);
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["builder_enabled"], true);
+    assert_eq!(body["grace"], "500");
+    assert_eq!(body["relays"]["https://relay-a.example.com"]["public_key"], relay_key);
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["builder_enabled"], false);
+    assert_eq!(body["reset_relays"], true);
+
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_import_rejects_invalid_files() {
+    let app = TestApp::get().await;
+    let name = unique_name("import_invalid");
+
+    let invalid = [
+        json!({ "version": 3 }),
+        json!({ "version": 2, "min_value": "lots" }),
+        json!({ "default_config": { "builder": { "enabled": true, "relays": ["https://relay-a.example.com"] } },
+                "proposer_config": {} }),
+    ];
+    for file in invalid {
+        let response = app.client()
+            .post(format!("{}/api/admin/vouch/import/execution-config?name={}", app.address, name))
+            .json(&file)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "expected 400 for {}", file);
+    }
+
+    let response = app.client()
+        .head(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,101 @@
+// tests/jobs_test.rs - Background job tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Poll a job until it is no longer running
+async fn wait_for_job(app: &TestApp, url: &str) -> Value {
+    for _ in 0..100 {
+        let response = app
+            .client()
+            .get(format!("{}{}", app.address, url))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), 200);
+        let job: Value = response.json().await.unwrap();
+        if job["status"] != "running" {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Job {} did not finish", url);
+}
+
+#[tokio::test]
+async fn test_large_pattern_import_runs_as_job() {
+    let app = TestApp::spawn_with_config(|c| c.api.async_job_threshold = 1).await;
+    let id = TestApp::unique_id();
+    let names = [format!("test_pattern_job_{}_a", id), format!("test_pattern_job_{}_b", id)];
+    let patterns = json!([
+        { "name": names[0], "pattern": "^0xjoba" },
+        { "name": names[1], "pattern": "^0xjobb" }
+    ]);
+    let import_url = format!("{}/api/admin/vouch/proposer-patterns/import", app.address);
+
+    let response = app
+        .client()
+        .post(&import_url)
+        .json(&patterns)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 202);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let accepted: Value = response.json().await.unwrap();
+    assert_eq!(accepted["url"], location);
+
+    let job = wait_for_job(&app, &location).await;
+    assert_eq!(job["status"], "succeeded", "{}", job);
+    assert_eq!(job["kind"], "import_proposer_patterns");
+    assert_eq!(job["actor"], "test-token");
+    assert_eq!(job["result"]["patterns"].as_array().unwrap().len(), 2);
+    assert!(job["finished_at"].is_string());
+
+    // Existing patterns conflict; the job keeps the problem the 409 would have carried
+    let response = app
+        .client()
+        .post(&import_url)
+        .json(&patterns)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 202);
+    let accepted: Value = response.json().await.unwrap();
+    let job = wait_for_job(&app, accepted["url"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "failed");
+    assert_eq!(job["error"]["status"], 409);
+    assert!(job.get("result").is_none());
+
+    // Requests at or below the threshold are answered directly
+    let response = app
+        .client()
+        .post(format!("{}?on_conflict=skip", import_url))
+        .json(&json!([{ "name": names[0], "pattern": "^0xjoba" }]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    for name in &names {
+        app.client()
+            .delete(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+            .send()
+            .await
+            .expect("Failed to delete pattern");
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_job() {
+    let app = TestApp::get().await;
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/jobs/{}", app.address, uuid::Uuid::new_v4()))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+}
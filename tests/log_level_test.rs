@@ -0,0 +1,93 @@
+// tests/log_level_test.rs - Runtime log level endpoint tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+use std::sync::Once;
+
+static TRACING: Once = Once::new();
+
+/// This test binary installs the reloadable subscriber the way main does,
+/// at `error` to keep test output quiet
+async fn app() -> &'static TestApp {
+    TRACING.call_once(|| fee_manager::logging::init_tracing("error", "text"));
+    TestApp::get().await
+}
+
+#[tokio::test]
+async fn test_log_level_requires_logging_scope() {
+    let app = app().await;
+    let url = format!("{}/api/admin/log-level", app.address);
+
+    let response = app.client().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["level"].is_string());
+
+    // The test token only has the admin scope
+    let response = app
+        .client()
+        .put(&url)
+        .json(&json!({ "level": "debug" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 403);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "FORBIDDEN");
+
+    let response = app
+        .client_unauthenticated()
+        .put(&url)
+        .json(&json!({ "level": "debug" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn test_log_level_change() {
+    let app = app().await;
+    let url = format!("{}/api/admin/log-level", app.address);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/tokens", app.address))
+        .json(&json!({ "name": format!("test_logging_{}", TestApp::unique_id()), "scopes": ["logging"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let token: Value = response.json().await.unwrap();
+    let bearer = format!("Bearer {}", token["token"].as_str().unwrap());
+
+    for level in ["", "fee_manager=loud"] {
+        let response = app
+            .client_unauthenticated()
+            .put(&url)
+            .header(reqwest::header::AUTHORIZATION, &bearer)
+            .json(&json!({ "level": level }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "expected 400 for '{}'", level);
+    }
+
+    let response = app
+        .client_unauthenticated()
+        .put(&url)
+        .header(reqwest::header::AUTHORIZATION, &bearer)
+        .json(&json!({ "level": "error,sqlx=warn" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    let level = body["level"].as_str().unwrap().to_string();
+    assert!(level.contains("sqlx=warn"));
+
+    let response = app.client().get(&url).send().await.unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["level"], level);
+}
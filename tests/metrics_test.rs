@@ -0,0 +1,178 @@
+// tests/metrics_test.rs - Prometheus metrics endpoint tests
+mod common;
+
+use common::TestApp;
+use serde_json::json;
+
+/// Value of the first sample whose name and labels start with `prefix`
+fn sample(metrics: &str, prefix: &str) -> Option<f64> {
+    metrics
+        .lines()
+        .find(|line| line.starts_with(prefix))
+        .and_then(|line| line.rsplit(' ').next())
+        .map(|value| value.parse().expect("Invalid sample value"))
+}
+
+async fn get_metrics(app: &TestApp) -> String {
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/metrics", app.address))
+        .send()
+        .await
+        .expect("Failed to get metrics");
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    response.text().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_metrics_disabled_by_default() {
+    let app = TestApp::get().await;
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/metrics", app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_metrics_report_config_updates_and_public_requests() {
+    let app = TestApp::spawn_with_config(|c| c.metrics_enabled = true).await;
+    let name = format!("test_metrics_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let config_url = format!("{}/api/admin/vouch/configs/default/{}", app.address, name);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": name, "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    for _ in 0..2 {
+        let response = app
+            .client_unauthenticated()
+            .post(format!("{}/vouch/v2/execution-config/{}", app.address, name))
+            .json(&json!([]))
+            .send()
+            .await
+            .expect("Failed to get execution config");
+        assert_eq!(response.status(), 200);
+    }
+
+    let metrics = get_metrics(&app).await;
+    assert!(metrics.contains("# TYPE fee_manager_config_last_update_seconds gauge"));
+    let updated = sample(
+        &metrics,
+        &format!(
+            "fee_manager_config_last_update_seconds{{resource_type=\"vouch_default_config\",name=\"{}\"}}",
+            name
+        ),
+    )
+    .expect("No last update sample for the config");
+    assert_eq!(
+        sample(
+            &metrics,
+            &format!(
+                "fee_manager_public_requests_total{{endpoint=\"execution_config\",config=\"{}\"}}",
+                name
+            )
+        ),
+        Some(2.0)
+    );
+
+    // Changing only a relay still counts as an update of the config
+    let response = app
+        .client()
+        .put(format!("{}/relays/{}", config_url, "https%3A%2F%2Frelay.example.com"))
+        .json(&json!({ "public_key": TestApp::test_bls_pubkey("ae") }))
+        .send()
+        .await
+        .expect("Failed to put relay");
+    assert_eq!(response.status(), 200);
+
+    let metrics = get_metrics(&app).await;
+    let relay_updated = sample(
+        &metrics,
+        &format!(
+            "fee_manager_config_last_update_seconds{{resource_type=\"vouch_default_config\",name=\"{}\"}}",
+            name
+        ),
+    )
+    .unwrap();
+    assert!(relay_updated > updated, "{} <= {}", relay_updated, updated);
+
+    let _ = app.client().delete(&config_url).send().await;
+}
+
+#[tokio::test]
+async fn test_deprecated_route_headers_and_counter() {
+    let app = TestApp::spawn_with_config(|c| {
+        c.metrics_enabled = true;
+        c.vouch.v1_deprecated_at = Some("2026-02-01T00:00:00Z".parse().unwrap());
+    })
+    .await;
+    let name = format!("test_deprecated_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let counter = "fee_manager_deprecated_requests_total{route=\"/vouch/v1/execution-config/{config}\"}";
+
+    let response = app
+        .client()
+        .post(format!("{}/api/v1/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": name, "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    let before = sample(&get_metrics(&app).await, counter).unwrap_or(0.0);
+    for _ in 0..2 {
+        let response = app
+            .client_unauthenticated()
+            .get(format!("{}/vouch/v1/execution-config/{}", app.address, name))
+            .send()
+            .await
+            .expect("Failed to get execution config");
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["deprecation"], "@1769904000");
+        assert_eq!(
+            response.headers()["link"],
+            format!("</vouch/v2/execution-config/{}>; rel=\"successor-version\"", name)
+        );
+        assert!(response.headers().get("sunset").is_none());
+    }
+
+    // The v2 endpoint is not deprecated
+    let response = app
+        .client_unauthenticated()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, name))
+        .json(&json!([]))
+        .send()
+        .await
+        .expect("Failed to get execution config");
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().get("deprecation").is_none());
+
+    let after = sample(&get_metrics(&app).await, counter).expect("No sample for the deprecated route");
+    assert_eq!(after - before, 2.0);
+
+    // Without vouch.v1_deprecated_at the v1 endpoint is served as is
+    let default_app = TestApp::get().await;
+    let response = default_app
+        .client_unauthenticated()
+        .get(format!("{}/vouch/v1/execution-config/{}", default_app.address, name))
+        .send()
+        .await
+        .expect("Failed to get execution config");
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(response.headers().get("link").is_none());
+
+    let _ = app
+        .client()
+        .delete(format!("{}/api/v1/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await;
+}
@@ -3,13 +3,14 @@ mod common;
 
 use common::TestApp;
 use serde::Deserialize;
-use serde_json::json;
+use serde_json::{json, Value};
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct MuxConfigResponse {
     name: String,
     keys: Vec<String>,
+    total_keys: i64,
     created_at: String,
     updated_at: String,
 }
@@ -38,6 +39,7 @@ struct PaginatedResponse<T> {
     total: i64,
     limit: i64,
     offset: i64,
+    has_more: bool,
 }
 
 /// Helper to create unique mux name
@@ -48,7 +50,7 @@ fn unique_mux_name(prefix: &str) -> String {
 /// Helper to delete a mux config
 async fn delete_mux(app: &TestApp, name: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await;
 }
@@ -64,7 +66,7 @@ async fn test_create_mux_config() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": []
@@ -92,7 +94,7 @@ async fn test_create_mux_config_with_keys() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": [key1, key2]
@@ -117,7 +119,7 @@ async fn test_create_mux_config_duplicate() {
 
     // Create first config
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name
         }))
@@ -128,7 +130,7 @@ async fn test_create_mux_config_duplicate() {
     // Try to create duplicate
     let response = app
         .client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name
         }))
@@ -136,7 +138,15 @@ async fn test_create_mux_config_duplicate() {
         .await
         .expect("Failed to send request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 409);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let body: Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["status"], 409);
+    assert_eq!(body["code"], "CONFLICT");
+    assert!(body["detail"].as_str().unwrap().contains(&name));
 
     delete_mux(app, &name).await;
 }
@@ -151,7 +161,7 @@ async fn test_get_mux_config() {
 
     // Create config with keys
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": [key1, key2]
@@ -163,7 +173,7 @@ async fn test_get_mux_config() {
     // Get config
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -184,7 +194,7 @@ async fn test_get_mux_config_not_found() {
 
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -203,7 +213,7 @@ async fn test_update_mux_config() {
 
     // Create config
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": [key1.clone()]
@@ -215,7 +225,7 @@ async fn test_update_mux_config() {
     // Update config (replace keys)
     let response = app
         .client()
-        .put(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .put(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .json(&json!({
             "keys": [key2.clone(), key3.clone()]
         }))
@@ -241,7 +251,7 @@ async fn test_delete_mux_config() {
 
     // Create config
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name
         }))
@@ -252,7 +262,7 @@ async fn test_delete_mux_config() {
     // Delete config
     let response = app
         .client()
-        .delete(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -262,7 +272,7 @@ async fn test_delete_mux_config() {
     // Verify deleted
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -274,6 +284,42 @@ async fn test_delete_mux_config() {
 // Keys Management Tests
 // ============================================================================
 
+#[tokio::test]
+async fn test_add_mux_keys_malformed_key() {
+    let app = TestApp::get().await;
+    let name = unique_mux_name("badkeys");
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({
+            "name": name
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
+        .json(&json!({
+            "keys": ["0xnothex"]
+        }))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let body: Value = response.json().await.expect("Failed to parse response");
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+    assert!(body["detail"].as_str().unwrap().contains("0xnothex"));
+
+    delete_mux(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_add_mux_keys() {
     let app = TestApp::get().await;
@@ -282,7 +328,7 @@ async fn test_add_mux_keys() {
 
     // Create config
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name
         }))
@@ -296,7 +342,7 @@ async fn test_add_mux_keys() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
         .json(&json!({
             "keys": [key1.clone(), key2]
         }))
@@ -315,7 +361,7 @@ async fn test_add_mux_keys() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
         .json(&json!({
             "keys": [key1, key3]  // key1 is duplicate
         }))
@@ -343,7 +389,7 @@ async fn test_remove_mux_keys() {
 
     // Create config with keys
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": [key1.clone(), key2.clone(), key3.clone()]
@@ -355,7 +401,7 @@ async fn test_remove_mux_keys() {
     // Remove some keys
     let response = app
         .client()
-        .delete(&format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
+        .delete(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, name))
         .json(&json!({
             "keys": [key1, key2]
         }))
@@ -372,7 +418,7 @@ async fn test_remove_mux_keys() {
     // Verify remaining key
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -384,6 +430,278 @@ async fn test_remove_mux_keys() {
     delete_mux(app, &name).await;
 }
 
+// ============================================================================
+// Copy/Move Tests
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct MuxKeysTransferResponse {
+    source: String,
+    target: String,
+    added: Vec<String>,
+    already_present: Vec<String>,
+    not_found: Vec<String>,
+    removed: i64,
+    source_total_keys: i64,
+    target_total_keys: i64,
+}
+
+#[tokio::test]
+async fn test_copy_and_move_mux_keys() {
+    let app = TestApp::get().await;
+    let source = unique_mux_name("xfer_src");
+    let target = unique_mux_name("xfer_dst");
+    let id = TestApp::unique_id();
+    let key1 = TestApp::test_bls_pubkey(&format!("c1{}", id));
+    let key2 = TestApp::test_bls_pubkey(&format!("c2{}", id));
+    let key3 = TestApp::test_bls_pubkey(&format!("c3{}", id));
+    let unknown = TestApp::test_bls_pubkey(&format!("c4{}", id));
+
+    for (name, keys) in [(&source, vec![&key1, &key2, &key3]), (&target, vec![&key2])] {
+        app.client()
+            .post(format!("{}/api/admin/commit-boost/mux", app.address))
+            .json(&json!({ "name": name, "keys": keys }))
+            .send()
+            .await
+            .expect("Failed to create config");
+    }
+
+    // Copy a subset: key2 is already in the target, unknown is not in the source
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/copy", app.address, source))
+        .json(&json!({ "target": target, "keys": [key1, key2, unknown] }))
+        .send()
+        .await
+        .expect("Failed to copy keys");
+    assert_eq!(response.status(), 200);
+    let body: MuxKeysTransferResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.added, vec![key1.clone()]);
+    assert_eq!(body.already_present, vec![key2.clone()]);
+    assert_eq!(body.not_found, vec![unknown.clone()]);
+    assert_eq!(body.removed, 0);
+    assert_eq!(body.source_total_keys, 3);
+    assert_eq!(body.target_total_keys, 2);
+
+    // Move everything that is left
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/move", app.address, source))
+        .json(&json!({ "target": target }))
+        .send()
+        .await
+        .expect("Failed to move keys");
+    assert_eq!(response.status(), 200);
+    let body: MuxKeysTransferResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.added, vec![key3.clone()]);
+    assert_eq!(body.removed, 3);
+    assert_eq!(body.source_total_keys, 0);
+    assert_eq!(body.target_total_keys, 3);
+
+    let response = app.client()
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, target))
+        .send()
+        .await
+        .expect("Failed to get config");
+    let body: MuxConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.keys, vec![key2, key1, key3]);
+
+    // Unknown target and self-transfer are rejected
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/copy", app.address, target))
+        .json(&json!({ "target": unique_mux_name("xfer_missing") }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/move", app.address, target))
+        .json(&json!({ "target": target }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    delete_mux(app, &source).await;
+    delete_mux(app, &target).await;
+}
+
+#[tokio::test]
+async fn test_diff_mux_keys() {
+    let app = TestApp::get().await;
+    let name = unique_mux_name("diff");
+    let id = TestApp::unique_id();
+    let key1 = TestApp::test_bls_pubkey(&format!("d1{}", id));
+    let key2 = TestApp::test_bls_pubkey(&format!("d2{}", id));
+    let key3 = TestApp::test_bls_pubkey(&format!("d3{}", id));
+    let key4 = TestApp::test_bls_pubkey(&format!("d4{}", id));
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": name, "keys": [key1, key2, key3] }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    let diff_url = format!("{}/api/admin/commit-boost/mux/{}/keys/diff", app.address, name);
+    let desired = json!({ "keys": [key4, key2, key1, key4] });
+
+    // Preview leaves the keys alone
+    let response = app.client().post(&diff_url).json(&desired).send().await.expect("Failed to diff keys");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["to_add"], json!([key4]));
+    assert_eq!(body["to_remove"], json!([key3]));
+    assert_eq!(body["unchanged"], json!([key2, key1]));
+    assert_eq!(body["applied"], false);
+    assert_eq!(body["total_keys"], 3);
+
+    let get_url = format!("{}/api/admin/commit-boost/mux/{}", app.address, name);
+    let config: MuxConfigResponse = app.client().get(&get_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(config.keys, vec![key1.clone(), key2.clone(), key3.clone()]);
+
+    // Apply reconciles to the desired list, keeping the order of kept keys
+    let response = app.client()
+        .post(format!("{}?apply=true", diff_url))
+        .json(&desired)
+        .send()
+        .await
+        .expect("Failed to apply diff");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["applied"], true);
+    assert_eq!(body["total_keys"], 3);
+    let config: MuxConfigResponse = app.client().get(&get_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(config.keys, vec![key1.clone(), key2.clone(), key4.clone()]);
+
+    // Nothing left to do
+    let response = app.client().post(&diff_url).json(&desired).send().await.unwrap();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["to_add"], json!([]));
+    assert_eq!(body["to_remove"], json!([]));
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/diff", app.address, unique_mux_name("diff_missing")))
+        .json(&desired)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    delete_mux(app, &name).await;
+}
+
+// ============================================================================
+// Duplicate Key Tests
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct DuplicateMuxKey {
+    public_key: String,
+    muxes: Vec<String>,
+}
+
+#[tokio::test]
+async fn test_list_duplicate_keys() {
+    let app = TestApp::get().await;
+    let mux_a = unique_mux_name("dup_a");
+    let mux_b = unique_mux_name("dup_b");
+    let id = TestApp::unique_id();
+    let shared = TestApp::test_bls_pubkey(&format!("d1{}", id));
+    let single = TestApp::test_bls_pubkey(&format!("d2{}", id));
+
+    for (name, keys) in [(&mux_a, vec![&shared, &single]), (&mux_b, vec![&shared])] {
+        app.client()
+            .post(format!("{}/api/admin/commit-boost/mux", app.address))
+            .json(&json!({ "name": name, "keys": keys }))
+            .send()
+            .await
+            .expect("Failed to create config");
+    }
+
+    let response = app.client()
+        .get(format!("{}/api/admin/commit-boost/keys/duplicates?limit=1000", app.address))
+        .send()
+        .await
+        .expect("Failed to list duplicates");
+    assert_eq!(response.status(), 200);
+    let body: PaginatedResponse<DuplicateMuxKey> = response.json().await.expect("Failed to parse JSON");
+
+    let dup = body.data.iter().find(|d| d.public_key == shared).expect("shared key reported");
+    let mut expected = vec![mux_a.clone(), mux_b.clone()];
+    expected.sort();
+    assert_eq!(dup.muxes, expected);
+    assert!(!body.data.iter().any(|d| d.public_key == single));
+
+    delete_mux(app, &mux_a).await;
+    delete_mux(app, &mux_b).await;
+}
+
+#[tokio::test]
+async fn test_strict_unique_keys() {
+    let app = TestApp::spawn_with_config(|c| c.commit_boost.strict_unique_keys = true).await;
+    let mux_a = unique_mux_name("strict_a");
+    let mux_b = unique_mux_name("strict_b");
+    let id = TestApp::unique_id();
+    let key = TestApp::test_bls_pubkey(&format!("e1{}", id));
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_a, "keys": [key] }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_b }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    // Adding or copying a key owned by another mux is rejected
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys", app.address, mux_b))
+        .json(&json!({ "keys": [key] }))
+        .send()
+        .await
+        .expect("Failed to add keys");
+    assert_eq!(response.status(), 409);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/copy", app.address, mux_a))
+        .json(&json!({ "target": mux_b }))
+        .send()
+        .await
+        .expect("Failed to copy keys");
+    assert_eq!(response.status(), 409);
+
+    // Previewing is fine, reconciling to it is not
+    let diff_url = format!("{}/api/admin/commit-boost/mux/{}/keys/diff", app.address, mux_b);
+    let response = app.client().post(&diff_url).json(&json!({ "keys": [key] })).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let response = app.client()
+        .post(format!("{}?apply=true", diff_url))
+        .json(&json!({ "keys": [key] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    // Moving keeps the key unique
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/mux/{}/keys/move", app.address, mux_a))
+        .json(&json!({ "target": mux_b }))
+        .send()
+        .await
+        .expect("Failed to move keys");
+    assert_eq!(response.status(), 200);
+
+    delete_mux(&app, &mux_a).await;
+    delete_mux(&app, &mux_b).await;
+}
+
 // ============================================================================
 // Public Endpoint Tests
 // ============================================================================
@@ -399,7 +717,7 @@ async fn test_get_mux_keys_public() {
 
     // Create config with keys
     app.client()
-        .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
         .json(&json!({
             "name": name,
             "keys": [key1.clone(), key2.clone()]
@@ -411,7 +729,7 @@ async fn test_get_mux_keys_public() {
     // Get keys via public endpoint
     let response = app
         .client()
-        .get(&format!("{}/commit-boost/v1/mux/{}", app.address, name))
+        .get(format!("{}/commit-boost/v1/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -426,6 +744,201 @@ async fn test_get_mux_keys_public() {
     delete_mux(app, &name).await;
 }
 
+#[tokio::test]
+async fn test_mux_keys_paginated() {
+    let app = TestApp::get().await;
+    let name = unique_mux_name("keypage");
+    let id = TestApp::unique_id();
+    let keys: Vec<String> = (1..=5)
+        .map(|i| TestApp::test_bls_pubkey(&format!("a{}{}", i, id)))
+        .collect();
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": name, "keys": keys }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    // Admin detail: page of keys plus total
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/commit-boost/mux/{}?limit=2&offset=1", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: MuxConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.keys, keys[1..3]);
+    assert_eq!(body.total_keys, 5);
+
+    // Without limit up to api.keys_page_size keys are returned
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: MuxConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.keys, keys);
+    assert_eq!(body.total_keys, 5);
+
+    // Public endpoint: plain array, total in header
+    let response = app
+        .client()
+        .get(format!("{}/commit-boost/v1/mux/{}?limit=2&offset=4", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-total-count"], "5");
+    let body: Vec<String> = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body, keys[4..]);
+
+    // A limit beyond api.max_page_size is refused, not clamped
+    for url in [
+        format!("{}/api/admin/commit-boost/mux/{}?limit=1001", app.address, name),
+        format!("{}/commit-boost/v1/mux/{}?limit=1001", app.address, name),
+        format!("{}/commit-boost/v1/mux/{}?limit=-1", app.address, name),
+    ] {
+        let response = app.client().get(&url).send().await.expect("Failed to send request");
+        assert_eq!(response.status(), 422, "{}", url);
+    }
+
+    delete_mux(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_mux_keys_without_limit_are_capped() {
+    let app = TestApp::spawn_with_config(|c| c.api.keys_page_size = 2).await;
+    let name = unique_mux_name("keycap");
+    let id = TestApp::unique_id();
+    let keys: Vec<String> = (1..=5)
+        .map(|i| TestApp::test_bls_pubkey(&format!("f{}{}", i, id)))
+        .collect();
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": name, "keys": keys }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    assert_eq!(response.status(), 201);
+
+    // Without limit a page of api.keys_page_size keys, the total tells there are more
+    let response = app
+        .client()
+        .get(format!("{}/commit-boost/v1/mux/{}", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-total-count"], "5");
+    let body: Vec<String> = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body, keys[..2]);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    // Streamed like the public keys, so without a length up front
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert!(response.headers().get("content-length").is_none());
+    let body: MuxConfigResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.keys, keys[..2]);
+    assert_eq!(body.total_keys, 5);
+
+    // An explicit limit may go beyond the default
+    let response = app
+        .client()
+        .get(format!("{}/commit-boost/v1/mux/{}?limit=4&offset=1", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: Vec<String> = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body, keys[1..5]);
+
+    delete_mux(&app, &name).await;
+}
+
+#[tokio::test]
+async fn test_get_mux_keys_public_membership_representations() {
+    use sha2::{Digest, Sha256};
+
+    let app = TestApp::get().await;
+    let name = unique_mux_name("digest");
+    let id = TestApp::unique_id();
+    let keys: Vec<String> = (1..=3)
+        .map(|i| TestApp::test_bls_pubkey(&format!("d{}{}", i, id)))
+        .collect();
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": name, "keys": keys }))
+        .send()
+        .await
+        .expect("Failed to create config");
+    let url = format!("{}/commit-boost/v1/mux/{}?limit=1", app.address, name);
+
+    // Salted hashes of every key, regardless of paging
+    let response = app
+        .client()
+        .get(&url)
+        .header("Accept", "application/vnd.fee-manager.key-hashes+json")
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/vnd.fee-manager.key-hashes+json");
+    assert_eq!(response.headers()["x-total-count"], "3");
+    assert_eq!(response.headers()["vary"], "accept");
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["algorithm"], "sha256");
+    let salt = hex::decode(body["salt"].as_str().unwrap()).unwrap();
+    let hashes: Vec<&str> = body["hashes"].as_array().unwrap().iter().map(|h| h.as_str().unwrap()).collect();
+    assert_eq!(hashes.len(), 3);
+    for key in &keys {
+        let mut hasher = Sha256::new();
+        hasher.update(&salt);
+        hasher.update(hex::decode(key.trim_start_matches("0x")).unwrap());
+        assert!(hashes.contains(&hex::encode(hasher.finalize()).as_str()));
+    }
+
+    // Bloom filter sized for the key count
+    let response = app
+        .client()
+        .get(&url)
+        .header("Accept", "application/vnd.fee-manager.key-bloom+json")
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/vnd.fee-manager.key-bloom+json");
+    let body: Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(hex::decode(body["salt"].as_str().unwrap()).unwrap(), salt);
+    let bits = body["bits"].as_u64().unwrap();
+    assert_eq!(body["filter"].as_str().unwrap().len() as u64, bits / 4);
+    assert!(body["hashes"].as_u64().unwrap() >= 1);
+    assert_eq!(body["false_positive_rate"], 0.001);
+
+    // Plain JSON stays the default
+    let response = app
+        .client()
+        .get(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: Vec<String> = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body, keys[..1]);
+
+    delete_mux(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_get_mux_keys_public_not_found() {
     let app = TestApp::get().await;
@@ -433,7 +946,7 @@ async fn test_get_mux_keys_public_not_found() {
 
     let response = app
         .client()
-        .get(&format!("{}/commit-boost/v1/mux/{}", app.address, name))
+        .get(format!("{}/commit-boost/v1/mux/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -454,7 +967,7 @@ async fn test_list_mux_configs() {
     let names: Vec<String> = (1..=3).map(|i| format!("test_mux_list_{}_{}", id, i)).collect();
     for name in &names {
         app.client()
-            .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+            .post(format!("{}/api/admin/commit-boost/mux", app.address))
             .json(&json!({
                 "name": name
             }))
@@ -466,7 +979,7 @@ async fn test_list_mux_configs() {
     // List all configs
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux", app.address))
+        .get(format!("{}/api/admin/commit-boost/mux", app.address))
         .send()
         .await
         .expect("Failed to send request");
@@ -492,7 +1005,7 @@ async fn test_mux_pagination() {
     let names: Vec<String> = (1..=5).map(|i| format!("test_mux_page_{}_{}", id, i)).collect();
     for name in &names {
         app.client()
-            .post(&format!("{}/api/admin/commit-boost/mux", app.address))
+            .post(format!("{}/api/admin/commit-boost/mux", app.address))
             .json(&json!({
                 "name": name
             }))
@@ -504,7 +1017,7 @@ async fn test_mux_pagination() {
     // Test limit
     let response = app
         .client()
-        .get(&format!("{}/api/admin/commit-boost/mux?limit=2", app.address))
+        .get(format!("{}/api/admin/commit-boost/mux?limit=2", app.address))
         .send()
         .await
         .expect("Failed to send request");
@@ -518,3 +1031,50 @@ async fn test_mux_pagination() {
         delete_mux(app, name).await;
     }
 }
+
+#[tokio::test]
+async fn test_mux_sorting() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+
+    let names: Vec<String> = (1..=3).map(|i| format!("test_mux_sort_{}_{}", id, i)).collect();
+    for name in &names {
+        app.client()
+            .post(format!("{}/api/admin/commit-boost/mux", app.address))
+            .json(&json!({ "name": name }))
+            .send()
+            .await
+            .expect("Failed to create config");
+    }
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/commit-boost/mux?sort=name&order=desc&limit=1000", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: PaginatedResponse<MuxConfigListItem> = response.json().await.unwrap();
+    let sorted: Vec<&str> = body
+        .data
+        .iter()
+        .map(|c| c.name.as_str())
+        .filter(|n| n.starts_with(&format!("test_mux_sort_{}_", id)))
+        .collect();
+    let expected: Vec<&str> = names.iter().rev().map(String::as_str).collect();
+    assert_eq!(sorted, expected);
+
+    // Columns outside the whitelist are rejected
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/commit-boost/mux?sort=keys;DROP", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    for name in &names {
+        delete_mux(app, name).await;
+    }
+}
@@ -0,0 +1,250 @@
+// tests/openapi_test.rs - OpenAPI spec / router parity tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+const METHODS: [&str; 6] = ["get", "head", "post", "put", "delete", "patch"];
+
+async fn fetch_spec(app: &TestApp) -> Value {
+    app.client()
+        .get(format!("{}/api-doc/openapi.json", app.address))
+        .send()
+        .await
+        .expect("Failed to fetch spec")
+        .json()
+        .await
+        .expect("Failed to parse spec")
+}
+
+/// Every (method, path) operation documented in the spec
+fn spec_operations(spec: &Value) -> Vec<(String, String)> {
+    let mut operations = Vec::new();
+    for (path, item) in spec["paths"].as_object().expect("paths object") {
+        for method in METHODS {
+            if item.get(method).is_some() {
+                operations.push((method.to_string(), path.clone()));
+            }
+        }
+    }
+    operations
+}
+
+/// Count `#[utoipa::path(` annotations in the source tree
+fn count_annotated_handlers(dir: &Path) -> usize {
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir).expect("Failed to read src dir") {
+        let path = entry.expect("Failed to read entry").path();
+        if path.is_dir() {
+            count += count_annotated_handlers(&path);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            let source = std::fs::read_to_string(&path).expect("Failed to read source");
+            count += source.matches("#[utoipa::path(").count();
+        }
+    }
+    count
+}
+
+#[tokio::test]
+async fn test_every_annotated_handler_is_documented() {
+    let app = TestApp::get().await;
+    let spec = fetch_spec(app).await;
+
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    assert_eq!(spec_operations(&spec).len(), count_annotated_handlers(&src));
+}
+
+#[tokio::test]
+async fn test_every_documented_operation_is_routed() {
+    let app = TestApp::get().await;
+    let spec = fetch_spec(app).await;
+    let client = app.client();
+
+    for (method, path) in spec_operations(&spec) {
+        // Fill path parameters with placeholders; handlers may reject them, the router must not
+        let url = format!(
+            "{}{}",
+            app.address,
+            path.replace("{config}", "test_openapi")
+                .replace("{public_key}", "0xdead")
+                .replace("{url}", "test_openapi")
+                .replace("{name}", "test_openapi")
+                .replace("{id}", "00000000-0000-0000-0000-000000000000")
+        );
+        let request = match method.as_str() {
+            "get" => client.get(&url),
+            "head" => client.head(&url),
+            "post" => client.post(&url),
+            "put" => client.put(&url),
+            "delete" => client.delete(&url),
+            _ => client.patch(&url),
+        };
+        let response = request.send().await.expect("Failed to send request");
+        let status = response.status().as_u16();
+        let has_content_type = response.headers().contains_key("content-type");
+        let body = response.text().await.unwrap_or_default();
+
+        // Unmatched routes return an empty 404 or a 405, handlers always return a JSON body
+        // (for HEAD only its content type is sent)
+        assert_ne!(status, 405, "{} {} is not routed", method, path);
+        assert!(
+            status != 404 || !body.is_empty() || (method == "head" && has_content_type),
+            "{} {} is not routed",
+            method,
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_error_responses_are_problem_json() {
+    let app = TestApp::get().await;
+    let spec = fetch_spec(app).await;
+
+    for (path, item) in spec["paths"].as_object().expect("paths object") {
+        // HEAD responses never carry a body
+        for method in METHODS.into_iter().filter(|m| *m != "head") {
+            let Some(responses) = item.get(method).map(|op| &op["responses"]) else {
+                continue;
+            };
+            for (status, response) in responses.as_object().expect("responses object") {
+                if status.starts_with('4') || status.starts_with('5') {
+                    assert_eq!(
+                        response["content"]["application/problem+json"]["schema"]["$ref"],
+                        "#/components/schemas/ProblemDetails",
+                        "{} {} {} is not documented as problem+json",
+                        method,
+                        path,
+                        status
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Compiles a schema of the spec with its `#/components/schemas/...` references resolvable
+fn spec_validator(spec: &Value, schema: &Value) -> jsonschema::Validator {
+    jsonschema::meta::validate(schema).unwrap_or_else(|e| panic!("Invalid schema {}: {}", schema, e));
+    let root = json!({ "allOf": [schema], "components": { "schemas": spec["components"]["schemas"] } });
+    jsonschema::draft202012::new(&root).unwrap_or_else(|e| panic!("Failed to compile {}: {}", schema, e))
+}
+
+fn assert_example_valid(spec: &Value, schema: &Value, example: &Value, location: &str) {
+    let validator = spec_validator(spec, schema);
+    let errors: Vec<String> = validator.iter_errors(example).map(|e| e.to_string()).collect();
+    assert!(errors.is_empty(), "Example of {} is invalid: {:?}", location, errors);
+}
+
+/// Every `$ref` found anywhere below `value`
+fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match (key.as_str(), child) {
+                    ("$ref", Value::String(reference)) => refs.push(reference),
+                    _ => collect_refs(child, refs),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
+
+#[tokio::test]
+async fn test_spec_is_valid() {
+    let app = TestApp::get().await;
+    let spec = fetch_spec(app).await;
+
+    assert!(spec["openapi"].as_str().is_some_and(|v| v.starts_with("3.1.")));
+    assert!(spec["info"]["title"].is_string() && spec["info"]["version"].is_string());
+
+    let mut refs = Vec::new();
+    collect_refs(&spec, &mut refs);
+    for reference in refs {
+        let pointer = reference.strip_prefix('#').expect("Only local references are used");
+        assert!(spec.pointer(pointer).is_some(), "Unresolved reference {}", reference);
+    }
+
+    // Components are valid JSON Schema and their examples match them
+    for (name, schema) in spec["components"]["schemas"].as_object().expect("schemas object") {
+        spec_validator(&spec, schema);
+        if let Some(example) = schema.get("example") {
+            assert_example_valid(&spec, schema, example, name);
+        }
+        for (field, property) in schema["properties"].as_object().into_iter().flatten() {
+            if let Some(example) = property.get("example") {
+                assert_example_valid(&spec, property, example, &format!("{}.{}", name, field));
+            }
+        }
+    }
+
+    let tags: HashSet<&str> = spec["tags"].as_array().expect("tags array").iter().filter_map(|t| t["name"].as_str()).collect();
+    let schemes = &spec["components"]["securitySchemes"];
+    let mut operation_ids = HashSet::new();
+    for (path, item) in spec["paths"].as_object().expect("paths object") {
+        let path_params: Vec<&str> = path.split('{').skip(1).filter_map(|s| s.split('}').next()).collect();
+        for method in METHODS {
+            let Some(op) = item.get(method) else { continue };
+            let location = format!("{} {}", method, path);
+
+            let operation_id = op["operationId"].as_str().expect("operationId");
+            assert!(operation_ids.insert(operation_id), "Duplicate operationId {}", operation_id);
+            for tag in op["tags"].as_array().into_iter().flatten() {
+                assert!(tags.contains(tag.as_str().unwrap()), "{} uses undeclared tag {}", location, tag);
+            }
+
+            for name in &path_params {
+                let declared = op["parameters"].as_array().into_iter().flatten().any(|p| {
+                    p["in"] == "path" && p["name"] == *name && p["required"] == true
+                });
+                assert!(declared, "{} does not declare path parameter {}", location, name);
+            }
+
+            let responses = op["responses"].as_object().expect("responses object");
+            assert!(!responses.is_empty(), "{} has no responses", location);
+            let secured = op["security"].as_array().is_some_and(|s| !s.is_empty());
+            for requirement in op["security"].as_array().into_iter().flatten() {
+                for scheme in requirement.as_object().expect("security requirement").keys() {
+                    assert!(schemes.get(scheme).is_some(), "{} uses unknown scheme {}", location, scheme);
+                }
+            }
+            if secured && method != "head" {
+                assert!(responses.contains_key("401"), "{} does not document 401", location);
+            }
+
+            let bodies = op.get("requestBody").into_iter().chain(responses.values());
+            for content in bodies.filter_map(|body| body["content"].as_object()) {
+                for media in content.values() {
+                    if let Some(example) = media.get("example") {
+                        assert_example_valid(&spec, &media["schema"], example, &location);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_audit_schema_is_published() {
+    let app = TestApp::get().await;
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/api-doc/audit-schema.json", app.address))
+        .send()
+        .await
+        .expect("Failed to fetch audit schema");
+    assert_eq!(response.status(), 200);
+    let schema: Value = response.json().await.expect("Failed to parse audit schema");
+
+    assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+    assert_eq!(schema["oneOf"].as_array().unwrap().len(), 2);
+    let audit = &schema["$defs"]["AuditEvent"];
+    assert!(audit["required"].as_array().unwrap().contains(&Value::from("schema_version")));
+    assert_eq!(audit["properties"]["actor"]["$ref"], "#/$defs/ActorInfo");
+    assert!(schema["$defs"]["AuditChanges"].is_object());
+    assert_eq!(schema["$defs"]["AccessEvent"]["properties"]["type"]["const"], "access");
+}
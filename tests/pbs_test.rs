@@ -0,0 +1,304 @@
+// tests/pbs_test.rs - Commit-Boost PBS config CRUD and cb-config.toml rendering tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+/// Helper to create unique config name for this test
+fn unique_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, TestApp::unique_id())
+}
+
+/// Helper to delete a PBS config
+async fn delete_pbs_config(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/commit-boost/pbs/{}", app.address, name))
+        .send()
+        .await;
+}
+
+/// Helper to delete a mux config
+async fn delete_mux(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, name))
+        .send()
+        .await;
+}
+
+// ============================================================================
+// CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_pbs_config_crud() {
+    let app = TestApp::get().await;
+    let name = unique_name("pbs_crud");
+    let relay_key = TestApp::test_bls_pubkey("c1");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+        .json(&json!({
+            "name": name,
+            "chain": "Hoodi",
+            "pbs": { "port": 18550, "relay_check": true },
+            "relays": [
+                { "id": "relay-a", "url": "https://relay-a.example.com", "public_key": relay_key }
+            ],
+            "muxes": [
+                { "id": "lido-8", "registry": "lido", "node_operator_id": 8, "relays": ["relay-a"] }
+            ]
+        }))
+        .send()
+        .await
+        .expect("Failed to create PBS config");
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["chain"], "Hoodi");
+    assert_eq!(body["pbs"]["port"], 18550);
+    assert_eq!(body["muxes"][0]["registry"], "lido");
+
+    // Duplicate name
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+        .json(&json!({ "name": name, "chain": "Hoodi" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    // Update replaces everything
+    let response = app.client()
+        .put(format!("{}/api/admin/commit-boost/pbs/{}", app.address, name))
+        .json(&json!({ "chain": "Mainnet" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["chain"], "Mainnet");
+    assert_eq!(body["relays"], json!([]));
+    assert_eq!(body["muxes"], json!([]));
+
+    let response = app.client()
+        .delete(format!("{}/api/admin/commit-boost/pbs/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = app.client()
+        .head(format!("{}/api/admin/commit-boost/pbs/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_pbs_config_validation() {
+    let app = TestApp::get().await;
+    let name = unique_name("pbs_invalid");
+    let relay_key = TestApp::test_bls_pubkey("c2");
+    let relays = json!([
+        { "id": "relay-a", "url": "https://relay-a.example.com", "public_key": relay_key }
+    ]);
+
+    let invalid = [
+        json!({ "name": name, "chain": "Mainnet", "pbs": { "port": [1] } }),
+        json!({ "name": name, "chain": "Mainnet", "relays": relays,
+                "muxes": [{ "id": "m", "registry": "ssv", "node_operator_id": 1, "relays": ["relay-b"] }] }),
+        json!({ "name": name, "chain": "Mainnet",
+                "muxes": [{ "id": "m", "mux": unique_name("missing") }] }),
+        json!({ "name": name, "chain": "Mainnet", "muxes": [{ "id": "m", "registry": "lido" }] }),
+        json!({ "name": name, "chain": "Mainnet", "relays": [
+            { "id": "relay-a", "url": "ftp://relay-a.example.com", "public_key": relay_key }
+        ] }),
+    ];
+    for body in invalid {
+        let response = app.client()
+            .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "expected 400 for {}", body);
+    }
+}
+
+// ============================================================================
+// Rendering Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_pbs_config_renders_toml() {
+    let app = TestApp::get().await;
+    let name = unique_name("pbs_render");
+    let mux_name = unique_name("pbs_mux");
+    let relay_key = TestApp::test_bls_pubkey("c3");
+    let validator_key = TestApp::test_bls_pubkey(&format!("pbs{}", TestApp::unique_id()));
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_name, "keys": [validator_key] }))
+        .send()
+        .await
+        .unwrap();
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+        .json(&json!({
+            "name": name,
+            "chain": "Mainnet",
+            "pbs": { "port": 18550, "timeout_get_header_ms": 950 },
+            "relays": [
+                { "id": "relay-a", "url": "https://relay-a.example.com", "public_key": relay_key },
+                { "id": "relay-b", "url": "https://relay-b.example.com", "public_key": relay_key }
+            ],
+            "muxes": [
+                { "id": "pool", "mux": mux_name, "relays": ["relay-b"] },
+                { "id": "lido-8", "registry": "lido", "node_operator_id": 8, "relays": ["relay-a"] }
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app.client()
+        .get(format!("{}/commit-boost/v1/pbs/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/toml");
+    let text = response.text().await.unwrap();
+    let config: toml::Value = toml::from_str(&text).expect("Rendered config is not valid TOML");
+
+    assert_eq!(config["chain"].as_str(), Some("Mainnet"));
+    assert_eq!(config["pbs"]["port"].as_integer(), Some(18550));
+    let relays = config["relays"].as_array().unwrap();
+    assert_eq!(relays.len(), 2);
+    assert_eq!(
+        relays[0]["url"].as_str().unwrap(),
+        format!("https://{}@relay-a.example.com/", relay_key)
+    );
+
+    let muxes = config["mux"].as_array().unwrap();
+    assert_eq!(muxes[0]["id"].as_str(), Some("pool"));
+    assert_eq!(muxes[0]["validator_pubkeys"][0].as_str(), Some(validator_key.as_str()));
+    assert_eq!(muxes[0]["relays"][0]["id"].as_str(), Some("relay-b"));
+    assert_eq!(muxes[1]["loader"]["registry"].as_str(), Some("lido"));
+    assert_eq!(muxes[1]["loader"]["node_operator_id"].as_integer(), Some(8));
+
+    // A mux config used by a PBS config cannot be deleted, as its preview shows
+    let dependents_url = format!("{}/api/admin/commit-boost/mux/{}/dependents", app.address, mux_name);
+    let body: Value = app.client().get(&dependents_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["removes"], json!([{ "kind": "keys", "count": 1 }, { "kind": "access_tokens", "count": 0 }]));
+    assert_eq!(body["blocked_by"], json!([{ "resource_type": "commit_boost_pbs_config", "name": name }]));
+    let response = app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux_name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    delete_pbs_config(app, &name).await;
+    let body: Value = app.client().get(&dependents_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["blocked_by"], json!([]));
+    delete_mux(app, &mux_name).await;
+
+    let response = app.client()
+        .get(format!("{}/commit-boost/v1/pbs/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_pbs_config_leaves_out_disabled_relays() {
+    let app = TestApp::get().await;
+    let name = unique_name("pbs_incident");
+    let vouch_name = unique_name("pbs_incident_vouch");
+    let relay_key = TestApp::test_bls_pubkey("c4");
+    let incident = format!("https://incident-{}.example.com", TestApp::unique_id());
+    let encoded: String = url::form_urlencoded::byte_serialize(incident.as_bytes()).collect();
+
+    let response = app.client()
+        .post(format!("{}/api/admin/commit-boost/pbs", app.address))
+        .json(&json!({
+            "name": name,
+            "chain": "Mainnet",
+            "pbs": { "port": 18550 },
+            "relays": [
+                { "id": "incident", "url": incident, "public_key": relay_key },
+                { "id": "healthy", "url": "https://healthy.example.com", "public_key": relay_key }
+            ],
+            "muxes": [
+                { "id": "lido-8", "registry": "lido", "node_operator_id": 8, "relays": ["incident", "healthy"] }
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": vouch_name,
+            "relays": {
+                incident.as_str(): { "public_key": relay_key },
+                "https://healthy.example.com": { "public_key": relay_key }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let pbs_url = format!("{}/commit-boost/v1/pbs/{}", app.address, name);
+    let execution_url = format!("{}/vouch/v1/execution-config/{}", app.address, vouch_name);
+    let response = app.client().get(&pbs_url).send().await.unwrap();
+    let etag = response.headers()["etag"].clone();
+    let config: toml::Value = toml::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(config["relays"].as_array().unwrap().len(), 2);
+
+    let response = app.client()
+        .post(format!("{}/api/admin/relays/{}/disable", app.address, encoded))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Disabling bumps the Commit-Boost version, so the cached config is not served
+    let response = app.client().get(&pbs_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_ne!(response.headers()["etag"], etag);
+    let config: toml::Value = toml::from_str(&response.text().await.unwrap()).unwrap();
+    let relays = config["relays"].as_array().unwrap();
+    assert_eq!(relays.len(), 1);
+    assert_eq!(relays[0]["id"].as_str(), Some("healthy"));
+    let mux_relays = config["mux"][0]["relays"].as_array().unwrap();
+    assert_eq!(mux_relays.len(), 1);
+    assert_eq!(mux_relays[0]["id"].as_str(), Some("healthy"));
+
+    let body: Value = app.client().get(&execution_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["default_config"]["builder"]["relays"], json!(["https://healthy.example.com"]));
+
+    let response = app.client()
+        .post(format!("{}/api/admin/relays/{}/enable", app.address, encoded))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let config: toml::Value =
+        toml::from_str(&app.client().get(&pbs_url).send().await.unwrap().text().await.unwrap()).unwrap();
+    assert_eq!(config["relays"].as_array().unwrap().len(), 2);
+
+    delete_pbs_config(app, &name).await;
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, vouch_name))
+        .send()
+        .await;
+}
@@ -0,0 +1,239 @@
+// tests/proposer_groups_test.rs - Proposer group CRUD and execution config merge tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+fn unique_name(prefix: &str) -> String {
+    format!("test_group_{}_{}", prefix, TestApp::unique_id())
+}
+
+/// Keys must not repeat across test runs, or groups left behind by a failed run would match them
+fn unique_key(prefix: &str) -> String {
+    TestApp::test_bls_pubkey(&format!("{}{}", prefix, uuid::Uuid::new_v4().simple()))
+}
+
+async fn create_group(app: &TestApp, body: Value) -> reqwest::Response {
+    app.client()
+        .post(format!("{}/api/admin/vouch/proposer-groups", app.address))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to create group")
+}
+
+async fn delete_group(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/proposer-groups/{}", app.address, name))
+        .send()
+        .await;
+}
+
+async fn create_config(app: &TestApp, name: &str) {
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": name,
+            "active": true,
+            "fee_recipient": TestApp::test_eth_address("d0"),
+            "gas_limit": "30000000"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+}
+
+async fn delete_config(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await;
+}
+
+async fn delete_proposer(app: &TestApp, pubkey: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await;
+}
+
+/// Proposer entries of a v2 execution config, keyed by public key
+async fn execution_config(app: &TestApp, config: &str, keys: &[&str]) -> Value {
+    let response = app.client()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config))
+        .json(&json!(keys))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    body["proposers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| (entry["proposer"].as_str().unwrap().to_string(), entry.clone()))
+        .collect::<serde_json::Map<String, Value>>()
+        .into()
+}
+
+// ============================================================================
+// CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_proposer_group_crud() {
+    let app = TestApp::get().await;
+    let name = unique_name("crud");
+    let key = unique_key("c1");
+    let relay_key = TestApp::test_bls_pubkey("a1");
+
+    let response = create_group(app, json!({
+        "name": name,
+        "keys": [key],
+        "priority": 5,
+        "fee_recipient": TestApp::test_eth_address("c1"),
+        "relays": { "https://relay-a.example.com": { "public_key": relay_key } }
+    }))
+    .await;
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["name"], name.as_str());
+    assert_eq!(body["keys"], json!([key]));
+    assert_eq!(body["priority"], 5);
+    assert!(body["id"].is_string());
+
+    // Names are unique
+    let response = create_group(app, json!({ "name": name })).await;
+    assert_eq!(response.status(), 409);
+
+    let url = format!("{}/api/admin/vouch/proposer-groups/{}", app.address, name);
+    let response = app.client()
+        .put(&url)
+        .json(&json!({ "keys": [], "gas_limit": "36000000", "fee_recipient": null }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["keys"], json!([]));
+    assert_eq!(body["gas_limit"], "36000000");
+    assert!(body["fee_recipient"].is_null());
+    assert_eq!(body["relays"].as_object().unwrap().len(), 1);
+
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-groups?name={}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["key_count"], 0);
+
+    let response = app.client().delete(&url).send().await.unwrap();
+    assert_eq!(response.status(), 204);
+    let response = app.client().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_proposer_group_rejects_invalid_pattern() {
+    let app = TestApp::get().await;
+    let response = create_group(app, json!({ "name": unique_name("regex"), "pattern": "^0x(" })).await;
+    assert_eq!(response.status(), 400);
+}
+
+// ============================================================================
+// Execution Config Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execution_config_merges_group_settings() {
+    let app = TestApp::get().await;
+    let config = unique_name("exec");
+    let group = unique_name("members");
+    let member = unique_key("e1");
+    let overridden = unique_key("e2");
+    let outsider = unique_key("e3");
+    let relay_key = TestApp::test_bls_pubkey("a2");
+    create_config(app, &config).await;
+
+    let response = create_group(app, json!({
+        "name": group,
+        "keys": [member, overridden],
+        "fee_recipient": TestApp::test_eth_address("91"),
+        "gas_limit": "36000000",
+        "relays": { "https://relay-group.example.com": { "public_key": relay_key } }
+    }))
+    .await;
+    assert_eq!(response.status(), 201);
+
+    // The proposer's own values win over the group's
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, overridden))
+        .json(&json!({ "fee_recipient": TestApp::test_eth_address("92") }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let proposers = execution_config(app, &config, &[&member, &overridden, &outsider]).await;
+    assert_eq!(proposers[&member]["fee_recipient"], TestApp::test_eth_address("91"));
+    assert_eq!(proposers[&member]["gas_limit"], "36000000");
+    assert!(proposers[&member]["relays"]["https://relay-group.example.com"].is_object());
+    assert_eq!(proposers[&overridden]["fee_recipient"], TestApp::test_eth_address("92"));
+    assert_eq!(proposers[&overridden]["gas_limit"], "36000000");
+    assert!(proposers[&overridden]["relays"]["https://relay-group.example.com"].is_object());
+    assert!(proposers.get(&outsider).is_none());
+
+    delete_proposer(app, &overridden).await;
+    delete_group(app, &group).await;
+    delete_config(app, &config).await;
+}
+
+#[tokio::test]
+async fn test_execution_config_group_pattern_and_priority() {
+    let app = TestApp::get().await;
+    let config = unique_name("prio");
+    let low = unique_name("low");
+    let high = unique_name("high");
+    let run = uuid::Uuid::new_v4().simple().to_string();
+    let matched = TestApp::test_bls_pubkey(&format!("9a{}", run));
+    create_config(app, &config).await;
+
+    // Both groups select the key; the higher priority one applies
+    let response = create_group(app, json!({
+        "name": low,
+        "keys": [matched],
+        "priority": 1,
+        "fee_recipient": TestApp::test_eth_address("a1")
+    }))
+    .await;
+    assert_eq!(response.status(), 201);
+    let response = create_group(app, json!({
+        "name": high,
+        "pattern": format!("^0xdead9a{}", run),
+        "priority": 10,
+        "fee_recipient": TestApp::test_eth_address("a2")
+    }))
+    .await;
+    assert_eq!(response.status(), 201);
+
+    let proposers = execution_config(app, &config, &[&matched]).await;
+    assert_eq!(proposers[&matched]["fee_recipient"], TestApp::test_eth_address("a2"));
+
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposer-groups/{}", app.address, low))
+        .json(&json!({ "priority": 20 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let proposers = execution_config(app, &config, &[&matched]).await;
+    assert_eq!(proposers[&matched]["fee_recipient"], TestApp::test_eth_address("a1"));
+
+    delete_group(app, &low).await;
+    delete_group(app, &high).await;
+    delete_config(app, &config).await;
+}
@@ -54,6 +54,7 @@ struct PaginatedResponse<T> {
     total: i64,
     limit: i64,
     offset: i64,
+    has_more: bool,
 }
 
 /// Helper to create unique pattern name
@@ -64,7 +65,7 @@ fn unique_pattern_name(prefix: &str) -> String {
 /// Helper to delete a pattern
 async fn delete_pattern(app: &TestApp, name: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await;
 }
@@ -80,7 +81,7 @@ async fn test_create_proposer_pattern() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0x8[0-9a-f]{94}$",
@@ -111,7 +112,7 @@ async fn test_create_proposer_pattern_with_relays() {
 
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0x9[0-9a-f]{94}$",
@@ -140,6 +141,116 @@ async fn test_create_proposer_pattern_with_relays() {
     delete_pattern(app, &name).await;
 }
 
+/// Percent-encode a relay URL for use as a path segment
+fn encode_url(url: &str) -> String {
+    url::form_urlencoded::byte_serialize(url.as_bytes()).collect()
+}
+
+#[tokio::test]
+async fn test_proposer_pattern_relay_sub_resource() {
+    let app = TestApp::get().await;
+    let name = unique_pattern_name("relay_sub");
+    let relay_key = TestApp::test_bls_pubkey("d1");
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": name,
+            "pattern": "^0xa[0-9a-f]{94}$",
+            "relays": {
+                "https://relay1.example.com": { "public_key": relay_key },
+                "https://relay2.example.com": { "public_key": relay_key }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 201);
+
+    let relay_url = |url: &str| {
+        format!(
+            "{}/api/admin/vouch/proposer-patterns/{}/relays/{}",
+            app.address, name, encode_url(url)
+        )
+    };
+
+    // Replace one relay and add another, leaving the rest untouched
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": relay_key, "min_value": "0.2 eth", "gas_limit": "36000000" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let response = app
+        .client()
+        .put(relay_url("https://relay3.example.com"))
+        .json(&json!({ "public_key": relay_key }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: ProposerPatternResponse = response.json().await.expect("Failed to parse JSON");
+    let relays = body.relays.unwrap();
+    assert_eq!(relays.len(), 3);
+    assert_eq!(relays["https://relay1.example.com"].min_value.as_deref(), Some("0.2"));
+    assert_eq!(relays["https://relay1.example.com"].gas_limit.as_deref(), Some("36000000"));
+    assert_eq!(relays["https://relay2.example.com"].min_value, None);
+
+    let response = app
+        .client()
+        .delete(relay_url("https://relay2.example.com"))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 204);
+    let response = app
+        .client()
+        .delete(relay_url("https://relay2.example.com"))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: ProposerPatternResponse = response.json().await.expect("Failed to parse JSON");
+    let mut urls: Vec<String> = body.relays.unwrap().into_keys().collect();
+    urls.sort();
+    assert_eq!(urls, ["https://relay1.example.com", "https://relay3.example.com"]);
+
+    // Invalid relay settings and unknown patterns
+    let response = app
+        .client()
+        .put(relay_url("https://relay1.example.com"))
+        .json(&json!({ "public_key": relay_key, "min_value": "lots" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+    let response = app
+        .client()
+        .put(format!(
+            "{}/api/admin/vouch/proposer-patterns/{}/relays/{}",
+            app.address,
+            unique_pattern_name("missing"),
+            encode_url("https://relay1.example.com")
+        ))
+        .json(&json!({ "public_key": relay_key }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+
+    delete_pattern(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_create_proposer_pattern_duplicate() {
     let app = TestApp::get().await;
@@ -147,7 +258,7 @@ async fn test_create_proposer_pattern_duplicate() {
 
     // Create first pattern
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0x[0-9a-f]+$"
@@ -159,7 +270,7 @@ async fn test_create_proposer_pattern_duplicate() {
     // Try to create duplicate
     let response = app
         .client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0x[0-9a-f]+$"
@@ -168,7 +279,7 @@ async fn test_create_proposer_pattern_duplicate() {
         .await
         .expect("Failed to send request");
 
-    assert_eq!(response.status(), 400);
+    assert_eq!(response.status(), 409);
 
     delete_pattern(app, &name).await;
 }
@@ -180,7 +291,7 @@ async fn test_get_proposer_pattern() {
 
     // Create pattern
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0xa[0-9a-f]{94}$",
@@ -193,7 +304,7 @@ async fn test_get_proposer_pattern() {
     // Get pattern
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -215,7 +326,7 @@ async fn test_get_proposer_pattern_not_found() {
 
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -230,7 +341,7 @@ async fn test_update_proposer_pattern() {
 
     // Create pattern
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0xb[0-9a-f]{94}$",
@@ -244,7 +355,7 @@ async fn test_update_proposer_pattern() {
     // Update pattern
     let response = app
         .client()
-        .put(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .put(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .json(&json!({
             "pattern": "^0xc[0-9a-f]{94}$",
             "tags": ["solo", "home-staker"],
@@ -264,6 +375,44 @@ async fn test_update_proposer_pattern() {
     delete_pattern(app, &name).await;
 }
 
+#[tokio::test]
+async fn test_update_proposer_pattern_null_clears_field() {
+    let app = TestApp::get().await;
+    let name = unique_pattern_name("clear");
+    let fee_recipient = TestApp::test_eth_address("c1");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": name,
+            "pattern": "^0xd[0-9a-f]{94}$",
+            "fee_recipient": fee_recipient,
+            "min_value": "0.2 eth",
+            "note": "to be cleared"
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    assert_eq!(response.status(), 201);
+
+    // Explicit null clears, omitted fields stay as they were
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .json(&json!({ "fee_recipient": null, "note": null }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert!(body["fee_recipient"].is_null());
+    assert!(body["note"].is_null());
+    assert_eq!(body["min_value"], "0.2");
+    assert_eq!(body["pattern"], "^0xd[0-9a-f]{94}$");
+
+    delete_pattern(app, &name).await;
+}
+
 #[tokio::test]
 async fn test_delete_proposer_pattern() {
     let app = TestApp::get().await;
@@ -271,7 +420,7 @@ async fn test_delete_proposer_pattern() {
 
     // Create pattern
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name,
             "pattern": "^0x[0-9a-f]+$"
@@ -283,7 +432,7 @@ async fn test_delete_proposer_pattern() {
     // Delete pattern
     let response = app
         .client()
-        .delete(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .delete(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -293,7 +442,7 @@ async fn test_delete_proposer_pattern() {
     // Verify deleted
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
         .send()
         .await
         .expect("Failed to send request");
@@ -301,6 +450,36 @@ async fn test_delete_proposer_pattern() {
     assert_eq!(response.status(), 404);
 }
 
+#[tokio::test]
+async fn test_proposer_pattern_dependents() {
+    let app = TestApp::get().await;
+    let name = unique_pattern_name("dependents");
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": name,
+            "pattern": "^0x[0-9a-f]+$",
+            "relays": {
+                "https://relay1.example.com": { "public_key": TestApp::test_bls_pubkey("pd1") }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+
+    let url = format!("{}/api/admin/vouch/proposer-patterns/{}/dependents", app.address, name);
+    let response = app.client().get(&url).send().await.expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["removes"], json!([{ "kind": "relays", "count": 1 }]));
+    assert_eq!(body["blocked_by"], json!([]));
+
+    delete_pattern(app, &name).await;
+    let response = app.client().get(&url).send().await.expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+}
+
 // ============================================================================
 // Filtering Tests
 // ============================================================================
@@ -314,7 +493,7 @@ async fn test_list_proposer_patterns() {
     let names: Vec<String> = (1..=3).map(|i| format!("test_list_{}_{}", id, i)).collect();
     for (i, name) in names.iter().enumerate() {
         app.client()
-            .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
             .json(&json!({
                 "name": name,
                 "pattern": format!("^0x{}[0-9a-f]{{94}}$", i),
@@ -328,7 +507,7 @@ async fn test_list_proposer_patterns() {
     // List all test patterns
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_list_{}", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_list_{}", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -355,7 +534,7 @@ async fn test_filter_by_tag() {
     let name3 = format!("test_tag_{}_{}", id, 3);
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name1,
             "pattern": "^0x1[0-9a-f]{94}$",
@@ -366,7 +545,7 @@ async fn test_filter_by_tag() {
         .unwrap();
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name2,
             "pattern": "^0x2[0-9a-f]{94}$",
@@ -377,7 +556,7 @@ async fn test_filter_by_tag() {
         .unwrap();
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name3,
             "pattern": "^0x3[0-9a-f]{94}$",
@@ -390,7 +569,7 @@ async fn test_filter_by_tag() {
     // Filter by tag "lido"
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_tag_{}&tag=lido", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_tag_{}&tag=lido", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -402,7 +581,7 @@ async fn test_filter_by_tag() {
     // Filter by tag "decentralized"
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_tag_{}&tag=decentralized", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_tag_{}&tag=decentralized", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -426,7 +605,7 @@ async fn test_filter_by_pattern() {
     let name2 = format!("test_pat_{}_{}", id, 2);
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name1,
             "pattern": "^0x8[0-9a-f]{94}$"
@@ -436,7 +615,7 @@ async fn test_filter_by_pattern() {
         .unwrap();
 
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name2,
             "pattern": "^0x9[0-9a-f]{94}$"
@@ -448,7 +627,7 @@ async fn test_filter_by_pattern() {
     // Filter by pattern substring
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_pat_{}&pattern=0x8", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_pat_{}&pattern=0x8", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -471,7 +650,7 @@ async fn test_filter_by_reset_relays() {
     let names: Vec<String> = (1..=4).map(|i| format!("test_reset_{}_{}", id, i)).collect();
     for (i, name) in names.iter().enumerate() {
         app.client()
-            .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
             .json(&json!({
                 "name": name,
                 "pattern": format!("^0x{}[0-9a-f]{{94}}$", i),
@@ -485,7 +664,7 @@ async fn test_filter_by_reset_relays() {
     // Filter reset_relays = true
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_reset_{}&reset_relays=true", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_reset_{}&reset_relays=true", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -509,7 +688,7 @@ async fn test_proposer_patterns_pagination() {
     let names: Vec<String> = (1..=5).map(|i| format!("test_page_{}_{}", id, i)).collect();
     for (i, name) in names.iter().enumerate() {
         app.client()
-            .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
             .json(&json!({
                 "name": name,
                 "pattern": format!("^0x{}[0-9a-f]{{94}}$", i)
@@ -522,7 +701,7 @@ async fn test_proposer_patterns_pagination() {
     // Test limit
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_page_{}&limit=2", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_page_{}&limit=2", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -534,7 +713,7 @@ async fn test_proposer_patterns_pagination() {
     // Test offset
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposer-patterns?name=test_page_{}&limit=2&offset=3", app.address, id))
+        .get(format!("{}/api/admin/vouch/proposer-patterns?name=test_page_{}&limit=2&offset=3", app.address, id))
         .send()
         .await
         .expect("Failed to send request");
@@ -562,7 +741,7 @@ async fn test_filter_patterns_by_relay_url() {
 
     // Create pattern with relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_with_relay,
             "pattern": "^0xa[0-9a-f]{94}$",
@@ -578,7 +757,7 @@ async fn test_filter_patterns_by_relay_url() {
 
     // Create pattern without relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_without_relay,
             "pattern": "^0xb[0-9a-f]{94}$"
@@ -590,7 +769,7 @@ async fn test_filter_patterns_by_relay_url() {
     // Filter by relay_url prefix
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposer-patterns?name=test_relay_url_{}&relay_url=https://flashbots",
             app.address, id
         ))
@@ -617,7 +796,7 @@ async fn test_filter_patterns_by_relay_min_value() {
 
     // Create pattern with relay that has min_value
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_with_min,
             "pattern": "^0xc[0-9a-f]{94}$",
@@ -634,7 +813,7 @@ async fn test_filter_patterns_by_relay_min_value() {
 
     // Create pattern with relay without min_value
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_without_min,
             "pattern": "^0xd[0-9a-f]{94}$",
@@ -651,7 +830,7 @@ async fn test_filter_patterns_by_relay_min_value() {
     // Filter by relay_min_value
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposer-patterns?name=test_relay_min_{}&relay_min_value=77000000000000000",
             app.address, id
         ))
@@ -678,7 +857,7 @@ async fn test_filter_patterns_by_relay_disabled() {
 
     // Create pattern with disabled relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_disabled,
             "pattern": "^0xe[0-9a-f]{94}$",
@@ -695,7 +874,7 @@ async fn test_filter_patterns_by_relay_disabled() {
 
     // Create pattern with enabled relay
     app.client()
-        .post(&format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
         .json(&json!({
             "name": name_enabled,
             "pattern": "^0xf[0-9a-f]{94}$",
@@ -713,7 +892,7 @@ async fn test_filter_patterns_by_relay_disabled() {
     // Filter by relay_disabled=true
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposer-patterns?name=test_relay_dis_{}&relay_disabled=true",
             app.address, id
         ))
@@ -729,7 +908,7 @@ async fn test_filter_patterns_by_relay_disabled() {
     // Filter by relay_disabled=false
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposer-patterns?name=test_relay_dis_{}&relay_disabled=false",
             app.address, id
         ))
@@ -746,3 +925,434 @@ async fn test_filter_patterns_by_relay_disabled() {
     delete_pattern(app, &name_disabled).await;
     delete_pattern(app, &name_enabled).await;
 }
+
+#[tokio::test]
+async fn test_proposer_pattern_note_and_labels() {
+    let app = TestApp::get().await;
+    let name = unique_pattern_name("labels");
+    let team = format!("team-{}", TestApp::unique_id());
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({
+            "name": name,
+            "pattern": "^0xdead.*$",
+            "note": "Operator pool",
+            "labels": { "team": team }
+        }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["note"], "Operator pool");
+    assert_eq!(body["labels"]["team"], team);
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposer-patterns?label.team={}",
+            app.address, team
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["name"], name);
+
+    // Partial update replaces labels and keeps the note
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, name))
+        .json(&json!({ "labels": { "team": "moved" } }))
+        .send()
+        .await
+        .expect("Failed to update pattern");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["labels"], json!({ "team": "moved" }));
+    assert_eq!(body["note"], "Operator pool");
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposer-patterns?label.team={}",
+            app.address, team
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 0);
+
+    delete_pattern(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_proposer_pattern_matches() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let name = unique_pattern_name("matches");
+    let mux_name = format!("test_mux_matches_{}", id);
+    let matching: Vec<String> = (1..=3)
+        .map(|i| TestApp::test_bls_pubkey(&format!("3a{}{}", id, i)))
+        .collect();
+    let other = TestApp::test_bls_pubkey(&format!("3b{}", id));
+
+    for key in matching.iter().chain([&other]) {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .json(&json!({}))
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_name, "keys": [matching[0], other] }))
+        .send()
+        .await
+        .expect("Failed to create mux");
+    app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({ "name": name, "pattern": format!("^0xdead3a{}", id) }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposer-patterns/{}/matches?sample=2",
+            app.address, name
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["source"], "proposers");
+    assert_eq!(body["count"], 3);
+    assert_eq!(body["sample"], json!([matching[0], matching[1]]));
+    assert!(body["total"].as_i64().unwrap() >= 4);
+    assert!(body["refreshed_at"].is_string());
+
+    // Statistics follow proposer changes
+    app.client()
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, matching[2]))
+        .send()
+        .await
+        .expect("Failed to delete proposer");
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/statistics", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let stats = body["patterns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == name)
+        .expect("pattern missing from statistics");
+    assert_eq!(stats["matches"], 2);
+    assert_eq!(stats["active"], true);
+    assert!(body["proposers"].as_i64().unwrap() >= body["unmatched"].as_i64().unwrap());
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposer-patterns/{}/matches?source=mux:{}",
+            app.address, name, mux_name
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["sample"], json!([matching[0]]));
+    assert!(body.get("refreshed_at").is_none());
+
+    for (source, status) in [("mux:test_missing", 404), ("keys", 400)] {
+        let response = app
+            .client()
+            .get(format!(
+                "{}/api/admin/vouch/proposer-patterns/{}/matches?source={}",
+                app.address, name, source
+            ))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(response.status(), status, "source={}", source);
+    }
+
+    delete_pattern(app, &name).await;
+    app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux_name))
+        .send()
+        .await
+        .expect("Failed to delete mux");
+    for key in matching.iter().chain([&other]) {
+        app.client()
+            .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .send()
+            .await
+            .expect("Failed to delete proposer");
+    }
+}
+
+#[tokio::test]
+async fn test_filter_patterns_by_active() {
+    let app = TestApp::get().await;
+    let name = unique_pattern_name("inactive");
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+        .json(&json!({ "name": name, "pattern": "^0xinactive", "active": false }))
+        .send()
+        .await
+        .expect("Failed to create pattern");
+    assert_eq!(response.status(), 201);
+
+    for (active, listed) in [(false, true), (true, false)] {
+        let body: serde_json::Value = app.client()
+            .get(format!("{}/api/admin/vouch/proposer-patterns?name={}&active={}", app.address, name, active))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), usize::from(listed));
+    }
+
+    delete_pattern(app, &name).await;
+}
+
+#[tokio::test]
+async fn test_filter_patterns_by_negative_filters() {
+    let app = TestApp::get().await;
+    let prefix = unique_pattern_name("exclude");
+    let old_recipient = "0x1111111111111111111111111111111111111111";
+    let patterns = [
+        json!({ "name": format!("{}_a", prefix), "pattern": "^0xa", "tags": ["legacy"], "fee_recipient": old_recipient }),
+        json!({ "name": format!("{}_b", prefix), "pattern": "^0xb", "fee_recipient": "0x2222222222222222222222222222222222222222" }),
+        json!({ "name": format!("{}_c", prefix), "pattern": "^0xc" }),
+    ];
+    for pattern in &patterns {
+        let response = app.client()
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .json(pattern)
+            .send()
+            .await
+            .expect("Failed to create pattern");
+        assert_eq!(response.status(), 201);
+    }
+
+    for filter in ["exclude_tag=legacy".to_string(), format!("fee_recipient_ne={}", old_recipient)] {
+        let body: serde_json::Value = app.client()
+            .get(format!("{}/api/admin/vouch/proposer-patterns?name={}&{}", app.address, prefix, filter))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let names: Vec<&str> = body["data"].as_array().unwrap().iter().map(|p| p["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec![format!("{}_b", prefix), format!("{}_c", prefix)], "{}", filter);
+    }
+
+    for pattern in &patterns {
+        delete_pattern(app, pattern["name"].as_str().unwrap()).await;
+    }
+}
+
+#[tokio::test]
+async fn test_proposer_patterns_bulk_import_export() {
+    let app = TestApp::get().await;
+    let first = unique_pattern_name("bulk_a");
+    let second = unique_pattern_name("bulk_b");
+    let third = unique_pattern_name("bulk_c");
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+    let import = |query: &str, body: serde_json::Value| {
+        app.client()
+            .post(format!("{}/api/admin/vouch/proposer-patterns/import?{}", app.address, query))
+            .json(&body)
+            .send()
+    };
+    let actions = |body: &serde_json::Value| -> Vec<String> {
+        body["patterns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["action"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let response = import(
+        "",
+        json!([
+            {
+                "name": first,
+                "pattern": "^0xbulk1",
+                "tags": ["bulk"],
+                "gas_limit": "30000000",
+                "note": "operator 1",
+                "relays": { "https://relay1.example.com": { "public_key": relay_pubkey } }
+            },
+            { "name": second, "pattern": "^0xbulk2" }
+        ]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(actions(&body), ["created", "created"]);
+    assert_eq!(body["patterns"][0]["relays"], 1);
+
+    // The export can be fed back as it is
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/export", app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let exported: Vec<serde_json::Value> = response.json().await.unwrap();
+    let entry = exported.iter().find(|p| p["name"] == first.as_str()).expect("Pattern should be exported");
+    assert_eq!(entry["pattern"], "^0xbulk1");
+    assert_eq!(entry["note"], "operator 1");
+    assert!(entry["relays"]["https://relay1.example.com"].is_object());
+    assert!(exported.iter().any(|p| p["name"] == second.as_str()));
+
+    let changed = json!([
+        { "name": first, "pattern": "^0xbulk1", "gas_limit": "36000000" },
+        { "name": third, "pattern": "^0xbulk3" }
+    ]);
+
+    // An existing name fails the whole import by default
+    let response = import("", changed.clone()).await.unwrap();
+    assert_eq!(response.status(), 409);
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, third))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Dry runs report without writing
+    let response = import("on_conflict=overwrite&dry_run=true", changed.clone()).await.unwrap();
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(actions(&body), ["updated", "created"]);
+    let pattern: ProposerPatternResponse = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, first))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pattern.gas_limit.as_deref(), Some("30000000"));
+
+    let response = import("on_conflict=skip", changed.clone()).await.unwrap();
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(actions(&body), ["skipped", "created"]);
+
+    // Overwriting replaces every field and relay
+    let response = import("on_conflict=overwrite", changed).await.unwrap();
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(actions(&body), ["updated", "updated"]);
+    let pattern: serde_json::Value = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, first))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pattern["gas_limit"], "36000000");
+    assert!(pattern.get("note").is_none());
+    assert!(pattern.get("relays").is_none());
+
+    let response = import(
+        "on_conflict=overwrite",
+        json!([{ "name": first, "pattern": "a" }, { "name": first, "pattern": "b" }]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 400);
+
+    for name in [&first, &second, &third] {
+        delete_pattern(app, name).await;
+    }
+}
+
+#[tokio::test]
+async fn test_list_and_rename_tags() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let typo = format!("lidoo{}", id);
+    let fixed = format!("lido{}", id);
+    let first = unique_pattern_name("tags_a");
+    let second = unique_pattern_name("tags_b");
+
+    for (name, tags) in [(&first, vec![typo.clone()]), (&second, vec![fixed.clone(), "other".to_string(), typo.clone()])] {
+        let response = app.client()
+            .post(format!("{}/api/admin/vouch/proposer-patterns", app.address))
+            .json(&json!({ "name": name, "pattern": "^0xtags", "tags": tags }))
+            .send()
+            .await
+            .expect("Failed to create pattern");
+        assert_eq!(response.status(), 201);
+    }
+
+    let usage = || async {
+        let tags: Vec<serde_json::Value> = app.client()
+            .get(format!("{}/api/admin/vouch/tags", app.address))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let count = |tag: &str| tags.iter().find(|t| t["tag"] == tag).map(|t| t["patterns"].as_i64().unwrap());
+        (count(&typo), count(&fixed))
+    };
+    assert_eq!(usage().await, (Some(2), Some(1)));
+
+    let rename = |from: &str, to: &str| {
+        app.client()
+            .post(format!("{}/api/admin/vouch/tags/{}/rename", app.address, from))
+            .json(&json!({ "to": to }))
+            .send()
+    };
+    let response = rename(&typo, &fixed).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let mut expected = vec![first.clone(), second.clone()];
+    expected.sort();
+    assert_eq!(body["patterns"], json!(expected));
+    assert_eq!(usage().await, (None, Some(2)));
+
+    // A pattern that had both keeps the tag once, where it was first
+    let pattern: ProposerPatternResponse = app.client()
+        .get(format!("{}/api/admin/vouch/proposer-patterns/{}", app.address, second))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pattern.tags, [fixed.clone(), "other".to_string()]);
+
+    assert_eq!(rename(&typo, &fixed).await.unwrap().status(), 404);
+    assert_eq!(rename(&fixed, "a,b").await.unwrap().status(), 400);
+
+    delete_pattern(app, &first).await;
+    delete_pattern(app, &second).await;
+}
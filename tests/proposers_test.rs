@@ -41,6 +41,8 @@ struct ProposerListItem {
     gas_limit: Option<String>,
     min_value: Option<String>,
     reset_relays: bool,
+    relay_count: i64,
+    relays: Option<HashMap<String, ProposerRelayConfig>>,
     created_at: String,
     updated_at: String,
 }
@@ -52,12 +54,13 @@ struct PaginatedResponse<T> {
     total: i64,
     limit: i64,
     offset: i64,
+    has_more: bool,
 }
 
 /// Helper to delete a proposer
 async fn delete_proposer(app: &TestApp, pubkey: &str) {
     let _ = app.client()
-        .delete(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await;
 }
@@ -73,7 +76,7 @@ async fn test_create_proposer() {
 
     let response = app
         .client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
             "gas_limit": "30000000",
@@ -103,7 +106,7 @@ async fn test_create_proposer_with_relays() {
 
     let response = app
         .client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
             "reset_relays": true,
@@ -139,7 +142,7 @@ async fn test_get_proposer() {
 
     // Create proposer
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "gas_limit": "32000000"
         }))
@@ -150,7 +153,7 @@ async fn test_get_proposer() {
     // Get proposer
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await
         .expect("Failed to send request");
@@ -171,7 +174,7 @@ async fn test_get_proposer_not_found() {
 
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await
         .expect("Failed to send request");
@@ -186,7 +189,7 @@ async fn test_update_proposer() {
 
     // Create proposer
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "gas_limit": "30000000",
             "reset_relays": false
@@ -198,7 +201,7 @@ async fn test_update_proposer() {
     // Update proposer
     let response = app
         .client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({
             "gas_limit": "35000000",
             "reset_relays": true
@@ -216,6 +219,59 @@ async fn test_update_proposer() {
     delete_proposer(app, &pubkey).await;
 }
 
+#[tokio::test]
+async fn test_concurrent_put_proposer() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("cc{}", TestApp::unique_id()));
+
+    // Parallel PUTs for a new key must all succeed, with exactly one creating it
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let pubkey = pubkey.clone();
+            tokio::spawn(async move {
+                app.client()
+                    .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+                    .json(&json!({
+                        "gas_limit": format!("{}", 30000000 + i),
+                        "reset_relays": false,
+                        "relays": {
+                            "https://relay1.example.com": {
+                                "public_key": "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f"
+                            }
+                        }
+                    }))
+                    .send()
+                    .await
+                    .expect("Failed to send request")
+                    .status()
+            })
+        })
+        .collect();
+
+    let mut created = 0;
+    for handle in handles {
+        let status = handle.await.unwrap();
+        assert!(status == 200 || status == 201, "unexpected status {}", status);
+        if status == 201 {
+            created += 1;
+        }
+    }
+    assert_eq!(created, 1);
+
+    let body: ProposerResponse = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body.relays.map(|r| r.len()), Some(1));
+
+    delete_proposer(app, &pubkey).await;
+}
+
 #[tokio::test]
 async fn test_delete_proposer() {
     let app = TestApp::get().await;
@@ -223,7 +279,7 @@ async fn test_delete_proposer() {
 
     // Create proposer
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .json(&json!({}))
         .send()
         .await
@@ -232,7 +288,7 @@ async fn test_delete_proposer() {
     // Delete proposer
     let response = app
         .client()
-        .delete(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await
         .expect("Failed to send request");
@@ -242,7 +298,7 @@ async fn test_delete_proposer() {
     // Verify deleted
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
         .send()
         .await
         .expect("Failed to send request");
@@ -264,7 +320,7 @@ async fn test_list_proposers() {
     let pubkeys: Vec<String> = (1..=3).map(|i| TestApp::test_bls_pubkey(&format!("{}0{}", prefix, i))).collect();
     for (i, pubkey) in pubkeys.iter().enumerate() {
         app.client()
-            .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
             .json(&json!({
                 "gas_limit": format!("{}0000000", 30 + i),
                 "reset_relays": i % 2 == 0
@@ -277,7 +333,7 @@ async fn test_list_proposers() {
     // List all test proposers - use hex prefix for filtering
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdead{}", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -293,6 +349,117 @@ async fn test_list_proposers() {
     }
 }
 
+#[tokio::test]
+async fn test_list_proposers_include_relays() {
+    let app = TestApp::get().await;
+    let prefix = format!("ab{}", TestApp::unique_id());
+    let pubkey_with_relays = TestApp::test_bls_pubkey(&format!("{}01", prefix));
+    let pubkey_without_relays = TestApp::test_bls_pubkey(&format!("{}02", prefix));
+    let relay_pubkey = "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f";
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_with_relays))
+        .json(&json!({
+            "relays": {
+                "https://relay1.example.com": { "public_key": relay_pubkey },
+                "https://relay2.example.com": { "public_key": relay_pubkey }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer with relays");
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_without_relays))
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("Failed to create proposer without relays");
+
+    // Default: counts only
+    let body: PaginatedResponse<ProposerListItem> = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}", app.address, prefix))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    assert_eq!(body.data.len(), 2);
+    assert!(body.data.iter().all(|p| p.relays.is_none()));
+    let with = body.data.iter().find(|p| p.public_key == pubkey_with_relays).unwrap();
+    let without = body.data.iter().find(|p| p.public_key == pubkey_without_relays).unwrap();
+    assert_eq!(with.relay_count, 2);
+    assert_eq!(without.relay_count, 0);
+
+    // include=relays embeds relay configs
+    let body: PaginatedResponse<ProposerListItem> = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key=0xdead{}&include=relays",
+            app.address, prefix
+        ))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    let with = body.data.iter().find(|p| p.public_key == pubkey_with_relays).unwrap();
+    let without = body.data.iter().find(|p| p.public_key == pubkey_without_relays).unwrap();
+    assert_eq!(with.relay_count, 2);
+    assert_eq!(with.relays.as_ref().unwrap().len(), 2);
+    assert!(with.relays.as_ref().unwrap().contains_key("https://relay1.example.com"));
+    assert_eq!(without.relays.as_ref().unwrap().len(), 0);
+
+    delete_proposer(app, &pubkey_with_relays).await;
+    delete_proposer(app, &pubkey_without_relays).await;
+}
+
+#[tokio::test]
+async fn test_list_proposers_sorting() {
+    let app = TestApp::get().await;
+    let prefix = format!("ac{}", TestApp::unique_id());
+    let pubkeys: Vec<String> = (1..=3).map(|i| TestApp::test_bls_pubkey(&format!("{}0{}", prefix, i))).collect();
+    for pubkey in &pubkeys {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(&json!({}))
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+
+    for (order, expected) in [("asc", pubkeys.clone()), ("desc", pubkeys.iter().rev().cloned().collect())] {
+        let body: PaginatedResponse<ProposerListItem> = app
+            .client()
+            .get(format!(
+                "{}/api/admin/vouch/proposers?public_key=0xdead{}&sort=public_key&order={}",
+                app.address, prefix, order
+            ))
+            .send()
+            .await
+            .expect("Failed to send request")
+            .json()
+            .await
+            .expect("Failed to parse JSON");
+        let actual: Vec<String> = body.data.into_iter().map(|p| p.public_key).collect();
+        assert_eq!(actual, expected);
+    }
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers?sort=fee_recipient", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    for pubkey in &pubkeys {
+        delete_proposer(app, pubkey).await;
+    }
+}
+
 #[tokio::test]
 async fn test_filter_by_reset_relays() {
     let app = TestApp::get().await;
@@ -303,7 +470,7 @@ async fn test_filter_by_reset_relays() {
     let pubkeys: Vec<String> = (1..=4).map(|i| TestApp::test_bls_pubkey(&format!("{}0{}", prefix, i))).collect();
     for (i, pubkey) in pubkeys.iter().enumerate() {
         app.client()
-            .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
             .json(&json!({
                 "reset_relays": (i + 1) % 2 == 0  // 2,4 = true
             }))
@@ -315,7 +482,7 @@ async fn test_filter_by_reset_relays() {
     // Filter reset_relays = true
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&reset_relays=true", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&reset_relays=true", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -327,7 +494,7 @@ async fn test_filter_by_reset_relays() {
     // Filter reset_relays = false
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&reset_relays=false", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&reset_relays=false", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -354,7 +521,7 @@ async fn test_filter_by_public_key_prefix() {
 
     for pubkey in [&pubkey1, &pubkey2, &pubkey3] {
         app.client()
-            .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
             .json(&json!({}))
             .send()
             .await
@@ -364,7 +531,7 @@ async fn test_filter_by_public_key_prefix() {
     // Filter by prefix "cc" - should match cc1 and cc2
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdeadcc", app.address))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdeadcc", app.address))
         .send()
         .await
         .expect("Failed to send request");
@@ -380,6 +547,133 @@ async fn test_filter_by_public_key_prefix() {
     delete_proposer(app, &pubkey3).await;
 }
 
+#[tokio::test]
+async fn test_filter_proposers_by_time_range() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let prefix = format!("0xdeadfa7e{}", id);
+    let pubkey1 = TestApp::test_bls_pubkey(&format!("fa7e{}1", id));
+    let pubkey2 = TestApp::test_bls_pubkey(&format!("fa7e{}2", id));
+
+    let mut created = Vec::new();
+    for pubkey in [&pubkey1, &pubkey2] {
+        let proposer: ProposerResponse = app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(&json!({}))
+            .send()
+            .await
+            .expect("Failed to create proposer")
+            .json()
+            .await
+            .unwrap();
+        created.push(proposer);
+    }
+
+    let list = |query: String| {
+        let url = format!("{}/api/admin/vouch/proposers?public_key={}&{}", app.address, prefix, query);
+        async move {
+            let body: PaginatedResponse<ProposerListItem> =
+                app.client().get(url).send().await.unwrap().json().await.unwrap();
+            body.data.into_iter().map(|p| p.public_key).collect::<Vec<_>>()
+        }
+    };
+    let at = |ts: &str| url::form_urlencoded::byte_serialize(ts.as_bytes()).collect::<String>();
+
+    // Bounds are exclusive
+    assert_eq!(list(format!("created_after={}", at(&created[0].created_at))).await, vec![pubkey2.clone()]);
+    assert_eq!(list(format!("created_before={}", at(&created[1].created_at))).await, vec![pubkey1.clone()]);
+
+    // A relay-only change moves updated_at as well
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey1))
+        .json(&json!({
+            "relays": {
+                "https://relay1.example.com/": {
+                    "public_key": "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f"
+                }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to update proposer");
+    assert_eq!(list(format!("updated_after={}", at(&created[1].updated_at))).await, vec![pubkey1.clone()]);
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers/count?public_key={}&created_after={}",
+            app.address, prefix, at(&created[0].created_at)
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 1);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers?created_after=yesterday", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    delete_proposer(app, &pubkey1).await;
+    delete_proposer(app, &pubkey2).await;
+}
+
+#[tokio::test]
+async fn test_filter_proposers_by_fee_recipient_ne() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let prefix = format!("0xdeadf3e{}", id);
+    let old_recipient = "0x1111111111111111111111111111111111111111";
+    let proposers = [
+        (TestApp::test_bls_pubkey(&format!("f3e{}1", id)), json!({ "fee_recipient": old_recipient })),
+        (TestApp::test_bls_pubkey(&format!("f3e{}2", id)), json!({ "fee_recipient": "0x2222222222222222222222222222222222222222" })),
+        (TestApp::test_bls_pubkey(&format!("f3e{}3", id)), json!({})),
+    ];
+    for (pubkey, body) in &proposers {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+
+    let body: PaginatedResponse<ProposerListItem> = app.client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key={}&fee_recipient_ne={}&sort=public_key&order=asc",
+            app.address, prefix, old_recipient
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let keys: Vec<String> = body.data.into_iter().map(|p| p.public_key).collect();
+    assert_eq!(keys, vec![proposers[1].0.clone(), proposers[2].0.clone()]);
+
+    let body: serde_json::Value = app.client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers/count?public_key={}&fee_recipient_ne={}",
+            app.address, prefix, old_recipient
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["count"], 2);
+
+    for (pubkey, _) in &proposers {
+        delete_proposer(app, pubkey).await;
+    }
+}
+
 #[tokio::test]
 async fn test_proposers_pagination() {
     let app = TestApp::get().await;
@@ -390,7 +684,7 @@ async fn test_proposers_pagination() {
     let pubkeys: Vec<String> = (1..=5).map(|i| TestApp::test_bls_pubkey(&format!("{}0{}", prefix, i))).collect();
     for pubkey in &pubkeys {
         app.client()
-            .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
             .json(&json!({}))
             .send()
             .await
@@ -400,7 +694,7 @@ async fn test_proposers_pagination() {
     // Test limit
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&limit=2", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&limit=2", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -413,7 +707,7 @@ async fn test_proposers_pagination() {
     // Test offset
     let response = app
         .client()
-        .get(&format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&limit=2&offset=3", app.address, prefix))
+        .get(format!("{}/api/admin/vouch/proposers?public_key=0xdead{}&limit=2&offset=3", app.address, prefix))
         .send()
         .await
         .expect("Failed to send request");
@@ -443,7 +737,7 @@ async fn test_filter_proposers_by_relay_url() {
 
     // Create proposer with relay
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_with_relay))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_with_relay))
         .json(&json!({
             "relays": {
                 "https://flashbots.example.com": {
@@ -457,7 +751,7 @@ async fn test_filter_proposers_by_relay_url() {
 
     // Create proposer without relay
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_without_relay))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_without_relay))
         .json(&json!({}))
         .send()
         .await
@@ -466,7 +760,7 @@ async fn test_filter_proposers_by_relay_url() {
     // Filter by relay_url prefix
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposers?public_key=0xdead{}&relay_url=https://flashbots",
             app.address, prefix
         ))
@@ -495,7 +789,7 @@ async fn test_filter_proposers_by_relay_min_value() {
 
     // Create proposer with relay that has min_value
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_with_min))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_with_min))
         .json(&json!({
             "relays": {
                 "https://relay1.example.com": {
@@ -510,7 +804,7 @@ async fn test_filter_proposers_by_relay_min_value() {
 
     // Create proposer with relay without min_value
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_without_min))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_without_min))
         .json(&json!({
             "relays": {
                 "https://relay2.example.com": {
@@ -525,7 +819,7 @@ async fn test_filter_proposers_by_relay_min_value() {
     // Filter by relay_min_value
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposers?public_key=0xdead{}&relay_min_value=99000000000000000",
             app.address, prefix
         ))
@@ -554,7 +848,7 @@ async fn test_filter_proposers_by_relay_disabled() {
 
     // Create proposer with disabled relay
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_disabled))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_disabled))
         .json(&json!({
             "relays": {
                 "https://relay1.example.com": {
@@ -569,7 +863,7 @@ async fn test_filter_proposers_by_relay_disabled() {
 
     // Create proposer with enabled relay
     app.client()
-        .put(&format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_enabled))
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey_enabled))
         .json(&json!({
             "relays": {
                 "https://relay2.example.com": {
@@ -585,7 +879,7 @@ async fn test_filter_proposers_by_relay_disabled() {
     // Filter by relay_disabled=true
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposers?public_key=0xdead{}&relay_disabled=true",
             app.address, prefix
         ))
@@ -601,7 +895,7 @@ async fn test_filter_proposers_by_relay_disabled() {
     // Filter by relay_disabled=false
     let response = app
         .client()
-        .get(&format!(
+        .get(format!(
             "{}/api/admin/vouch/proposers?public_key=0xdead{}&relay_disabled=false",
             app.address, prefix
         ))
@@ -618,3 +912,859 @@ async fn test_filter_proposers_by_relay_disabled() {
     delete_proposer(app, &pubkey_disabled).await;
     delete_proposer(app, &pubkey_enabled).await;
 }
+
+// ============================================================================
+// Relay Toggle Tests
+// ============================================================================
+
+/// Percent-encode a relay URL for use as a path segment
+fn encode_url(url: &str) -> String {
+    url::form_urlencoded::byte_serialize(url.as_bytes()).collect()
+}
+
+#[tokio::test]
+async fn test_disable_and_enable_proposer_relay() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("tg{}", TestApp::unique_id()));
+    let relay_url = "https://relay1.example.com/";
+
+    let created: ProposerResponse = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({
+            "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
+            "relays": {
+                relay_url: {
+                    "public_key": "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f",
+                    "min_value": "0.1 eth"
+                }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer")
+        .json()
+        .await
+        .unwrap();
+
+    // Disable relay
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/proposers/{}/relays/{}/disable",
+            app.address, pubkey, encode_url(relay_url)
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 200);
+    let body: ProposerResponse = response.json().await.unwrap();
+    let relay = &body.relays.as_ref().unwrap()[relay_url];
+    assert!(relay.disabled);
+    assert_eq!(relay.min_value, Some("0.1".to_string()));
+    assert_eq!(body.fee_recipient, Some("0x1234567890abcdef1234567890abcdef12345678".to_string()));
+    // Only the relay row changed, but the proposer's updated_at follows it
+    assert_ne!(body.updated_at, created.updated_at);
+
+    // Enable relay again
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/proposers/{}/relays/{}/enable",
+            app.address, pubkey, encode_url(relay_url)
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 200);
+    let body: ProposerResponse = response.json().await.unwrap();
+    let relay = &body.relays.as_ref().unwrap()[relay_url];
+    assert!(!relay.disabled);
+    assert_eq!(relay.min_value, Some("0.1".to_string()));
+
+    delete_proposer(app, &pubkey).await;
+}
+
+#[tokio::test]
+async fn test_disable_proposer_relay_not_found() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("tn{}", TestApp::unique_id()));
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/proposers/{}/relays/{}/disable",
+            app.address, pubkey, encode_url("https://unknown.example.com")
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 404);
+
+    delete_proposer(app, &pubkey).await;
+}
+
+#[tokio::test]
+async fn test_count_and_head_proposers() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let fee_recipient = TestApp::test_eth_address(&format!("c0{}", id));
+    let pubkeys: Vec<String> = (1..=2)
+        .map(|i| TestApp::test_bls_pubkey(&format!("c{}{}", i, id)))
+        .collect();
+
+    for pubkey in &pubkeys {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(&json!({ "fee_recipient": fee_recipient }))
+            .send()
+            .await
+            .expect("Failed to create proposer");
+    }
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers/count?fee_recipient={}",
+            app.address, fee_recipient
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 2);
+
+    let response = app
+        .client()
+        .head(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[0]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    for pubkey in &pubkeys {
+        delete_proposer(app, pubkey).await;
+    }
+
+    let response = app
+        .client()
+        .head(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[0]))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+    assert!(response.bytes().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_proposer_note_and_labels() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let team = format!("team-{}", id);
+    let pubkeys: Vec<String> = (1..=2)
+        .map(|i| TestApp::test_bls_pubkey(&format!("1a{}{}", i, id)))
+        .collect();
+
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[0]))
+        .json(&json!({
+            "note": "Customer A, rack 3",
+            "labels": { "team": team, "customer": "acme" }
+        }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["note"], "Customer A, rack 3");
+    assert_eq!(body["labels"], json!({ "team": team, "customer": "acme" }));
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[1]))
+        .json(&json!({ "labels": { "team": team, "customer": "other" } }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    // All label filters must match
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?label.team={}&label.customer=acme",
+            app.address, team
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["public_key"], pubkeys[0]);
+    assert_eq!(body["data"][0]["labels"]["customer"], "acme");
+
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers/count?label.team={}",
+            app.address, team
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 2);
+
+    // A full update without labels clears them
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[1]))
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("Failed to update proposer");
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["labels"], json!({}));
+    assert!(body.get("note").is_none());
+
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkeys[1]))
+        .json(&json!({ "labels": { "bad key": "x" } }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    for pubkey in &pubkeys {
+        delete_proposer(app, pubkey).await;
+    }
+}
+
+/// Serve a relay data API that knows a single validator registration
+async fn spawn_mock_relay(pubkey: String, fee_recipient: String) -> String {
+    use axum::{extract::Query, http::StatusCode, routing::get, Json, Router};
+
+    let app = Router::new().route(
+        "/relay/v1/data/validator_registration",
+        get(move |Query(query): Query<HashMap<String, String>>| async move {
+            if query.get("pubkey") != Some(&pubkey) {
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Ok(Json(json!({
+                "message": {
+                    "fee_recipient": fee_recipient,
+                    "gas_limit": "36000000",
+                    "timestamp": "1700000000",
+                    "pubkey": pubkey,
+                },
+                "signature": "0x00",
+            })))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind");
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    address
+}
+
+#[tokio::test]
+async fn test_proposer_registrations() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let pubkey = TestApp::test_bls_pubkey(&format!("a1{}", id));
+    let fee_recipient = "0x1111111111111111111111111111111111111111";
+
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "fee_recipient": fee_recipient, "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+    assert_eq!(response.status(), 201);
+
+    // Nothing observed yet
+    let url = format!("{}/api/admin/vouch/proposers/{}/registrations", app.address, pubkey);
+    let body: serde_json::Value = app.client().get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["intended"]["fee_recipient"], fee_recipient);
+    assert_eq!(body["registrations"], json!([]));
+
+    // Run one ingestion pass against a mock relay
+    let relay = spawn_mock_relay(pubkey.clone(), fee_recipient.to_string()).await;
+    let mut config = fee_manager::config::load_config().expect("Failed to load test config");
+    config.vouch.registrations.relays = vec![relay.clone()];
+    let pool = sqlx::PgPool::connect(&config.database.database_url())
+        .await
+        .expect("Failed to connect to database");
+    let sync = fee_manager::registrations::RegistrationSync::new(&config.vouch.registrations).unwrap();
+    let summary = sync.run_once(&pool).await.expect("Sync failed");
+    assert!(summary.recorded >= 1);
+    assert_eq!(summary.failed_relays, 0);
+
+    let body: serde_json::Value = app.client().get(&url).send().await.unwrap().json().await.unwrap();
+    let registration = &body["registrations"][0];
+    assert_eq!(registration["relay_url"], relay);
+    assert_eq!(registration["fee_recipient"], fee_recipient);
+    assert_eq!(registration["gas_limit"], "36000000");
+    assert_eq!(registration["registered_at"], "2023-11-14T22:13:20Z");
+    assert_eq!(registration["fee_recipient_matches"], true);
+    assert_eq!(registration["gas_limit_matches"], false);
+
+    let response = app.client()
+        .get(format!("{}?config=missing_{}", url, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    delete_proposer(app, &pubkey).await;
+}
+
+/// Runs a cleanup of proposers inactive for 19000 days, returning the body and which of `keys` it affected
+async fn run_cleanup(app: &TestApp, keys: &[String], query: &str) -> (serde_json::Value, Vec<String>) {
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/cleanup?inactive_days=19000&{}", app.address, query))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    let affected = body["public_keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| k.as_str().unwrap().to_string())
+        .filter(|k| keys.contains(k))
+        .collect();
+    (body, affected)
+}
+
+async fn get_proposer(app: &TestApp, key: &str) -> reqwest::Response {
+    app.client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cleanup_inactive_proposers() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_name = format!("test_cleanup_{}", id);
+    let keys: Vec<String> = ["a", "b", "c"]
+        .iter()
+        .map(|k| TestApp::test_bls_pubkey(&format!("c1ea{}{}", k, id)))
+        .collect();
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name, "active": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    for key in &keys {
+        app.client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, key))
+            .json(&json!({ "gas_limit": "30000000", "reset_relays": false }))
+            .send()
+            .await
+            .unwrap();
+    }
+    // Old enough that no other test's proposers qualify
+    sqlx::query("UPDATE vouch_proposers SET created_at = NOW() - INTERVAL '20000 days' WHERE public_key = ANY($1)")
+        .bind(&keys)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // Requesting the first key marks it as in use
+    let response = app
+        .client_unauthenticated()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .json(&json!([keys[0]]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    for _ in 0..50 {
+        let recorded: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM vouch_proposer_usage WHERE public_key = $1)")
+                .bind(&keys[0])
+                .fetch_one(&app.pool)
+                .await
+                .unwrap();
+        if recorded {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    // A dry run only reports
+    let (body, affected) = run_cleanup(app, &keys, "dry_run=true").await;
+    assert_eq!(body["action"], "decommission");
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(affected, keys[1..]);
+    let proposer: serde_json::Value = get_proposer(app, &keys[1]).await.json().await.unwrap();
+    assert!(proposer.get("decommissioned").is_none() || proposer["decommissioned"] == false);
+
+    let (_, affected) = run_cleanup(app, &keys, "action=decommission").await;
+    assert_eq!(affected, keys[1..]);
+    let proposer: serde_json::Value = get_proposer(app, &keys[1]).await.json().await.unwrap();
+    assert_eq!(proposer["decommissioned"], true);
+
+    // Delete also takes the already decommissioned ones
+    let (body, affected) = run_cleanup(app, &keys, "action=delete").await;
+    assert_eq!(body["action"], "delete");
+    assert_eq!(affected, keys[1..]);
+    assert_eq!(get_proposer(app, &keys[1]).await.status(), 404);
+    assert_eq!(get_proposer(app, &keys[0]).await.status(), 200);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/cleanup?inactive_days=0", app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    delete_proposer(app, &keys[0]).await;
+    let _ = app
+        .client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, config_name))
+        .send()
+        .await;
+}
+
+#[tokio::test]
+async fn test_decommission_and_reactivate_proposer() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let pubkey = TestApp::test_bls_pubkey(&format!("dc{}", id));
+    let config_name = format!("test_decommission_{}", id);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({ "name": config_name, "active": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({
+            "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678",
+            "reset_relays": false
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let served_keys = || async {
+        let body: serde_json::Value = app
+            .client_unauthenticated()
+            .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+            .json(&json!([pubkey]))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        body["proposers"]
+            .as_array()
+            .map(|entries| entries.iter().map(|e| e["proposer"].clone()).collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    assert_eq!(served_keys().await, vec![json!(pubkey)]);
+
+    // Decommissioning twice keeps the first timestamp
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/{}/decommission", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["decommissioned"], true);
+    let decommissioned_at = body["decommissioned_at"].clone();
+    assert!(decommissioned_at.is_string());
+
+    let body: serde_json::Value = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/{}/decommission", app.address, pubkey))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["decommissioned_at"], decommissioned_at);
+
+    // Left out of execution configs, still listed and flagged
+    assert!(served_keys().await.is_empty());
+
+    let body: PaginatedResponse<serde_json::Value> = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key={}&decommissioned=true",
+            app.address, pubkey
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body.total, 1);
+    assert_eq!(body.data[0]["decommissioned"], true);
+    assert_eq!(body.data[0]["fee_recipient"], "0x1234567890abcdef1234567890abcdef12345678");
+
+    let body: PaginatedResponse<serde_json::Value> = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key={}&decommissioned=false",
+            app.address, pubkey
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body.total, 0);
+
+    let response = app
+        .client()
+        .post(format!("{}/api/admin/vouch/proposers/{}/reactivate", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["decommissioned"], false);
+    assert!(body.get("decommissioned_at").is_none());
+    assert_eq!(served_keys().await, vec![json!(pubkey)]);
+
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/proposers/{}/decommission",
+            app.address,
+            TestApp::test_bls_pubkey(&format!("dd{}", id))
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    delete_proposer(app, &pubkey).await;
+    app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, config_name))
+        .send()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_mixed_case_keys_are_normalized() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("ca{}", TestApp::unique_id()));
+    let upper_key = format!("0X{}", pubkey[2..].to_uppercase());
+
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, upper_key))
+        .json(&json!({
+            "fee_recipient": "0xABCDEF0123456789abcdef0123456789ABCDEF01",
+            "reset_relays": false
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let body: ProposerResponse = response.json().await.unwrap();
+    assert_eq!(body.public_key, pubkey);
+    assert_eq!(body.fee_recipient.as_deref(), Some("0xabcdef0123456789abcdef0123456789abcdef01"));
+
+    // Lookups and filters ignore case
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body: PaginatedResponse<ProposerListItem> = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key={}&fee_recipient=0xABCDEF0123456789ABCDEF0123456789ABCDEF01",
+            app.address, upper_key
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body.total, 1);
+    assert_eq!(body.data[0].public_key, pubkey);
+
+    delete_proposer(app, &upper_key).await;
+    let response = app
+        .client()
+        .head(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_error_response_reports_request_id() {
+    let app = TestApp::get().await;
+    let missing = TestApp::test_bls_pubkey("e404");
+
+    // A caller-supplied ID is echoed in the body and header
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/{}", app.address, missing))
+        .header("x-request-id", &request_id)
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers()["x-request-id"], request_id.as_str());
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["request_id"], request_id);
+
+    // Otherwise the generated ID is reported
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/0x1234", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status(), 400);
+    let header = response.headers()["x-request-id"].to_str().unwrap().to_string();
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["request_id"], header);
+}
+
+#[tokio::test]
+async fn test_malformed_public_key_is_rejected() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/0x1234", app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+    assert!(body["detail"].as_str().unwrap().contains("Invalid string length"));
+
+    // Relay sub-resources share the extractor
+    let response = app
+        .client()
+        .post(format!(
+            "{}/api/admin/vouch/proposers/not-a-key/relays/https%3A%2F%2Frelay.example/disable",
+            app.address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_min_value_units() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("mv{}", TestApp::unique_id()));
+    let relay_url = "https://relay1.example.com";
+
+    // Amounts with units are echoed back in ETH
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({
+            "min_value": "0.05 ETH",
+            "relays": {
+                relay_url: {
+                    "public_key": "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f",
+                    "min_value": "50000000 gwei"
+                }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(response.status() == 200 || response.status() == 201);
+    let body: ProposerResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.min_value, Some("0.05".to_string()));
+    assert_eq!(body.relays.as_ref().unwrap()[relay_url].min_value, Some("0.05".to_string()));
+
+    // Reads can display the stored wei
+    let body: ProposerResponse = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers/{}?units=wei", app.address, pubkey))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    assert_eq!(body.min_value, Some("50000000000000000".to_string()));
+    assert_eq!(
+        body.relays.as_ref().unwrap()[relay_url].min_value,
+        Some("50000000000000000".to_string())
+    );
+
+    // Filters accept units too
+    let body: PaginatedResponse<ProposerListItem> = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers?public_key={}&min_value=0.05%20eth&units=gwei",
+            app.address, pubkey
+        ))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    assert_eq!(body.total, 1);
+    assert_eq!(body.data[0].min_value, Some("50000000".to_string()));
+
+    // Bare amounts are ETH, as before units were accepted
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "min_value": "0.06" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    let body: ProposerResponse = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body.min_value, Some("0.06".to_string()));
+
+    // More decimals than wei can hold are rejected
+    let response = app
+        .client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({ "min_value": "0.0000000000000000001" }))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+    let body: serde_json::Value = response.json().await.expect("Failed to parse JSON");
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+    assert!(body["detail"].as_str().unwrap().contains("min_value"));
+
+    delete_proposer(app, &pubkey).await;
+}
+
+#[tokio::test]
+async fn test_list_proposers_missing_field() {
+    let app = TestApp::get().await;
+    let prefix = format!("ad{}", TestApp::unique_id());
+    let with_fee_recipient = TestApp::test_bls_pubkey(&format!("{}a", prefix));
+    let with_relays = TestApp::test_bls_pubkey(&format!("{}b", prefix));
+    let bare = TestApp::test_bls_pubkey(&format!("{}c", prefix));
+
+    for (pubkey, body) in [
+        (&with_fee_recipient, json!({ "fee_recipient": "0x1234567890abcdef1234567890abcdef12345678" })),
+        (&with_relays, json!({
+            "gas_limit": "36000000",
+            "relays": { "https://relay.example.com": { "public_key": TestApp::test_bls_pubkey("a1") } }
+        })),
+        (&bare, json!({ "note": "defaults only" })),
+    ] {
+        let response = app
+            .client()
+            .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(response.status().is_success());
+    }
+
+    let missing = |field: &'static str| {
+        let url = format!(
+            "{}/api/admin/vouch/proposers?public_key=0xdead{}&missing={}&sort=public_key&order=asc",
+            app.address, prefix, field
+        );
+        async move {
+            let body: PaginatedResponse<ProposerListItem> = app
+                .client()
+                .get(url)
+                .send()
+                .await
+                .expect("Failed to send request")
+                .json()
+                .await
+                .expect("Failed to parse JSON");
+            body.data.into_iter().map(|p| p.public_key).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(missing("fee_recipient").await, vec![with_relays.clone(), bare.clone()]);
+    assert_eq!(missing("gas_limit").await, vec![with_fee_recipient.clone(), bare.clone()]);
+    assert_eq!(missing("relays").await, vec![with_fee_recipient.clone(), bare.clone()]);
+
+    // The count endpoint takes the same filter
+    let body: serde_json::Value = app
+        .client()
+        .get(format!(
+            "{}/api/admin/vouch/proposers/count?public_key=0xdead{}&missing=fee_recipient",
+            app.address, prefix
+        ))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("Failed to parse JSON");
+    assert_eq!(body["count"], 2);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/vouch/proposers?missing=note", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 400);
+
+    for pubkey in [&with_fee_recipient, &with_relays, &bare] {
+        delete_proposer(app, pubkey).await;
+    }
+}
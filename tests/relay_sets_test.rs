@@ -0,0 +1,256 @@
+// tests/relay_sets_test.rs - Vouch relay set tests
+mod common;
+
+use common::TestApp;
+use serde_json::{json, Value};
+
+/// Helper to create unique relay set / config name for this test
+fn unique_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, TestApp::unique_id())
+}
+
+/// Helper to delete a relay set
+async fn delete_relay_set(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/relay-sets/{}", app.address, name))
+        .send()
+        .await;
+}
+
+/// Helper to delete a config
+async fn delete_config(app: &TestApp, name: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, name))
+        .send()
+        .await;
+}
+
+/// Helper to delete a proposer
+async fn delete_proposer(app: &TestApp, pubkey: &str) {
+    let _ = app.client()
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await;
+}
+
+// ============================================================================
+// CRUD Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_relay_set_crud() {
+    let app = TestApp::get().await;
+    let name = unique_name("set_crud");
+    let relay_key = TestApp::test_bls_pubkey("a1");
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/relay-sets", app.address))
+        .json(&json!({
+            "name": name,
+            "description": "Mainnet relays",
+            "relays": {
+                "https://relay-a.example.com": {
+                    "public_key": relay_key,
+                    "min_value": "0.1 eth"
+                }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create relay set");
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["name"], name);
+    assert_eq!(body["relays"]["https://relay-a.example.com"]["min_value"], "0.1");
+
+    // Duplicate name
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/relay-sets", app.address))
+        .json(&json!({ "name": name }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    // Listed by name prefix
+    let response = app.client()
+        .get(format!("{}/api/admin/vouch/relay-sets?name={}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+
+    // Update replaces relays
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/relay-sets/{}", app.address, name))
+        .json(&json!({
+            "relays": {
+                "https://relay-b.example.com": { "public_key": relay_key }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["description"], "Mainnet relays");
+    let relays = body["relays"].as_object().unwrap();
+    assert_eq!(relays.len(), 1);
+    assert!(relays.contains_key("https://relay-b.example.com"));
+
+    let response = app.client()
+        .delete(format!("{}/api/admin/vouch/relay-sets/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = app.client()
+        .head(format!("{}/api/admin/vouch/relay-sets/{}", app.address, name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_relay_set_references() {
+    let app = TestApp::get().await;
+    let set_name = unique_name("set_ref");
+    let config_name = unique_name("set_ref_cfg");
+
+    // Unknown set is rejected
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "active": true,
+            "relay_sets": [set_name]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/relay-sets", app.address))
+        .json(&json!({ "name": set_name }))
+        .send()
+        .await
+        .unwrap();
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "active": true,
+            "relay_sets": [set_name]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["relay_sets"], json!([set_name]));
+
+    // Referenced set cannot be deleted
+    let response = app.client()
+        .delete(format!("{}/api/admin/vouch/relay-sets/{}", app.address, set_name))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    delete_config(app, &config_name).await;
+    delete_relay_set(app, &set_name).await;
+}
+
+// ============================================================================
+// Execution Config Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execution_config_expands_relay_sets() {
+    let app = TestApp::get().await;
+    let base_set = unique_name("set_base");
+    let extra_set = unique_name("set_extra");
+    let config_name = unique_name("set_exec");
+    let pubkey = TestApp::test_bls_pubkey(&format!("rs{}", TestApp::unique_id()));
+    let relay_key = TestApp::test_bls_pubkey("a2");
+
+    for (name, relays) in [
+        (&base_set, json!({
+            "https://relay-a.example.com": { "public_key": relay_key, "gas_limit": "30000000" },
+            "https://relay-b.example.com": { "public_key": relay_key }
+        })),
+        (&extra_set, json!({
+            "https://relay-b.example.com": { "public_key": relay_key, "gas_limit": "36000000" },
+            "https://relay-c.example.com": { "public_key": relay_key }
+        })),
+    ] {
+        let response = app.client()
+            .post(format!("{}/api/admin/vouch/relay-sets", app.address))
+            .json(&json!({ "name": name, "relays": relays }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+    }
+
+    let response = app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "active": true,
+            "relay_sets": [base_set, extra_set],
+            "relays": {
+                "https://relay-a.example.com": { "public_key": relay_key, "gas_limit": "45000000" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({
+            "reset_relays": true,
+            "relay_sets": [extra_set],
+            "relays": {
+                "https://relay-c.example.com": { "public_key": relay_key, "disabled": true }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let response = app.client()
+        .post(format!("{}/vouch/v2/execution-config/{}", app.address, config_name))
+        .json(&json!([pubkey]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+
+    // Later sets override earlier ones, inline relays override sets
+    let relays = body["relays"].as_object().unwrap();
+    assert_eq!(relays.len(), 3);
+    assert_eq!(relays["https://relay-a.example.com"]["gas_limit"], "45000000");
+    assert_eq!(relays["https://relay-b.example.com"]["gas_limit"], "36000000");
+
+    // Inline disabled relay overrides the set relay for this proposer
+    let proposer_relays = body["proposers"][0]["relays"].as_object().unwrap();
+    assert_eq!(proposer_relays.len(), 2);
+    assert!(proposer_relays["https://relay-b.example.com"].get("disabled").is_none());
+    assert_eq!(proposer_relays["https://relay-c.example.com"]["disabled"], true);
+
+    delete_proposer(app, &pubkey).await;
+    delete_config(app, &config_name).await;
+    delete_relay_set(app, &base_set).await;
+    delete_relay_set(app, &extra_set).await;
+}
@@ -0,0 +1,242 @@
+// tests/search_test.rs - Cross-resource search tests
+mod common;
+
+use common::TestApp;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct SearchHit {
+    resource_type: String,
+    resource_id: String,
+    field: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct SearchResponse {
+    query: String,
+    hits: Vec<SearchHit>,
+}
+
+async fn search(app: &TestApp, q: &str) -> SearchResponse {
+    let response = app
+        .client()
+        .get(format!(
+            "{}/api/admin/search?q={}",
+            app.address,
+            url::form_urlencoded::byte_serialize(q.as_bytes()).collect::<String>()
+        ))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 200);
+    response.json().await.expect("Failed to parse JSON")
+}
+
+#[tokio::test]
+async fn test_search_pubkey_prefix_across_resources() {
+    let app = TestApp::get().await;
+    let prefix = format!("5e{}", TestApp::unique_id());
+    let pubkey = TestApp::test_bls_pubkey(&prefix);
+    let mux_name = format!("test_search_mux_{}", TestApp::unique_id());
+
+    app.client()
+        .put(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .json(&json!({}))
+        .send()
+        .await
+        .expect("Failed to create proposer");
+
+    app.client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_name, "keys": [pubkey] }))
+        .send()
+        .await
+        .expect("Failed to create mux");
+
+    let body = search(app, &format!("0xDEAD{}", prefix)).await;
+
+    assert!(body.hits.iter().any(|h| h.resource_type == "vouch_proposer"
+        && h.resource_id == pubkey
+        && h.field == "public_key"));
+    assert!(body.hits.iter().any(|h| h.resource_type == "commit_boost_mux"
+        && h.resource_id == mux_name
+        && h.field == "public_key"));
+
+    app.client()
+        .delete(format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey))
+        .send()
+        .await
+        .ok();
+    app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux_name))
+        .send()
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn test_search_names_and_relay_urls() {
+    let app = TestApp::get().await;
+    let id = TestApp::unique_id();
+    let config_name = format!("test_search_cfg_{}", id);
+    let relay_url = format!("https://search-{}.relay.example.com", id);
+
+    app.client()
+        .post(format!("{}/api/admin/vouch/configs/default", app.address))
+        .json(&json!({
+            "name": config_name,
+            "relays": {
+                relay_url.clone(): {
+                    "public_key": "0x8b5d2e73e2a3a55c6c87b8b6eb92e0149a125c852751db1422fa951e42a09b82c142c3ea98d0d9930b056a3bc9896b8f"
+                }
+            }
+        }))
+        .send()
+        .await
+        .expect("Failed to create config");
+
+    let body = search(app, &format!("search_cfg_{}", id)).await;
+    assert_eq!(body.hits.len(), 1);
+    assert_eq!(body.hits[0].resource_type, "vouch_default_config");
+    assert_eq!(body.hits[0].field, "name");
+
+    let body = search(app, &format!("search-{}.relay", id)).await;
+    assert_eq!(body.hits.len(), 1);
+    assert_eq!(body.hits[0].resource_id, config_name);
+    assert_eq!(body.hits[0].field, "relay_url");
+    assert_eq!(body.hits[0].value, relay_url);
+
+    app.client()
+        .delete(format!("{}/api/admin/vouch/configs/default/{}", app.address, config_name))
+        .send()
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn test_search_wildcards_are_literal() {
+    let app = TestApp::get().await;
+
+    let body = search(app, "%_%").await;
+    assert!(body.hits.iter().all(|h| h.value.contains("%_%")));
+}
+
+#[tokio::test]
+async fn test_search_empty_query() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/search?q=", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_search_requires_auth() {
+    let app = TestApp::get().await;
+
+    let response = app
+        .client_unauthenticated()
+        .get(format!("{}/api/admin/search?q=0x", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn test_resource_ids_are_stable_and_resolvable() {
+    let app = TestApp::get().await;
+    let pubkey = TestApp::test_bls_pubkey(&format!("1d{}", TestApp::unique_id()));
+    let mux_name = format!("test_resource_id_mux_{}", TestApp::unique_id());
+    let proposer_url = format!("{}/api/admin/vouch/proposers/{}", app.address, pubkey);
+
+    let created: serde_json::Value = app
+        .client()
+        .put(&proposer_url)
+        .json(&json!({ "gas_limit": "30000000" }))
+        .send()
+        .await
+        .expect("Failed to create proposer")
+        .json()
+        .await
+        .unwrap();
+    let proposer_id = created["id"].as_str().expect("No proposer id").to_string();
+
+    // Updates keep the id
+    let updated: serde_json::Value = app
+        .client()
+        .put(&proposer_url)
+        .json(&json!({ "gas_limit": "36000000" }))
+        .send()
+        .await
+        .expect("Failed to update proposer")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(updated["id"], proposer_id.as_str());
+
+    let mux: serde_json::Value = app
+        .client()
+        .post(format!("{}/api/admin/commit-boost/mux", app.address))
+        .json(&json!({ "name": mux_name, "keys": [pubkey] }))
+        .send()
+        .await
+        .expect("Failed to create mux")
+        .json()
+        .await
+        .unwrap();
+    let mux_id = mux["id"].as_str().expect("No mux id").to_string();
+    assert_ne!(mux_id, proposer_id);
+
+    for (id, resource_type, resource_id) in [
+        (&proposer_id, "vouch_proposer", &pubkey),
+        (&mux_id, "commit_boost_mux", &mux_name),
+    ] {
+        let response = app
+            .client()
+            .get(format!("{}/api/admin/resources/{}", app.address, id))
+            .send()
+            .await
+            .expect("Failed to look up resource");
+        assert_eq!(response.status(), 200);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["id"], id.as_str());
+        assert_eq!(body["resource_type"], resource_type);
+        assert_eq!(body["resource_id"], resource_id.as_str());
+    }
+
+    app.client().delete(&proposer_url).send().await.ok();
+    app.client()
+        .delete(format!("{}/api/admin/commit-boost/mux/{}", app.address, mux_name))
+        .send()
+        .await
+        .ok();
+
+    // Gone with the resource
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/resources/{}", app.address, proposer_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .client()
+        .get(format!("{}/api/admin/resources/not-a-uuid", app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
@@ -0,0 +1,58 @@
+// tests/ui_test.rs - Embedded admin UI tests
+mod common;
+
+use common::TestApp;
+
+#[tokio::test]
+async fn test_ui_serves_assets_without_auth() {
+    let app = TestApp::get().await;
+    let client = app.client_unauthenticated();
+
+    // /ui redirects to the app shell
+    let response = client
+        .get(format!("{}/ui", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert!(response.url().path().ends_with("/ui/"));
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    let body = response.text().await.unwrap();
+    assert!(body.contains("/ui/app.js"));
+
+    let response = client
+        .get(format!("{}/ui/app.js", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .contains("javascript"));
+}
+
+#[tokio::test]
+async fn test_ui_deep_links_fall_back_to_shell() {
+    let app = TestApp::get().await;
+    let client = app.client_unauthenticated();
+
+    let response = client
+        .get(format!("{}/ui/proposers/edit", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 200);
+    assert!(response.text().await.unwrap().contains("<title>Fee Manager</title>"));
+
+    // Missing files are not masked by the shell
+    let response = client
+        .get(format!("{}/ui/missing.js", app.address))
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,312 @@
+// Fee Manager admin UI: a thin client over the admin JSON API
+'use strict';
+
+const PAGE_SIZE = 50;
+const TOKEN_KEY = 'fee-manager-token';
+
+// Fields returned by the API that are not accepted in request bodies
+const READ_ONLY = ['created_at', 'updated_at', 'relay_count', 'key_count', 'total_keys', 'last_used_at', 'id'];
+
+const RESOURCES = {
+  configs: {
+    title: 'Default Configs',
+    path: '/api/v1/admin/vouch/configs/default',
+    key: 'name',
+    columns: ['name', 'fee_recipient', 'gas_limit', 'min_value', 'active', 'updated_at'],
+    create: 'POST',
+    keyInBody: true,
+    template: { fee_recipient: '', gas_limit: '30000000', min_value: '0.1', active: true, relays: {} },
+  },
+  proposers: {
+    title: 'Proposers',
+    path: '/api/v1/admin/vouch/proposers',
+    key: 'public_key',
+    columns: ['public_key', 'fee_recipient', 'gas_limit', 'reset_relays', 'relay_count', 'updated_at'],
+    create: 'PUT',
+    template: { fee_recipient: '', reset_relays: false, relays: {} },
+  },
+  patterns: {
+    title: 'Proposer Patterns',
+    path: '/api/v1/admin/vouch/proposer-patterns',
+    key: 'name',
+    columns: ['name', 'pattern', 'tags', 'fee_recipient', 'reset_relays', 'active', 'updated_at'],
+    create: 'POST',
+    keyInBody: true,
+    template: { pattern: '^Pool1/.*$', tags: [], fee_recipient: '', reset_relays: false, active: true, relays: {} },
+  },
+  muxes: {
+    title: 'Mux Configs',
+    path: '/api/v1/admin/commit-boost/mux',
+    key: 'name',
+    columns: ['name', 'key_count', 'updated_at'],
+    create: 'POST',
+    keyInBody: true,
+    template: { keys: [] },
+  },
+  tokens: {
+    title: 'Tokens',
+    path: '/api/v1/admin/tokens',
+    key: 'id',
+    columns: ['name', 'description', 'active', 'created_at', 'last_used_at'],
+    create: 'POST',
+    update: 'PATCH',
+    unpaginated: true,
+    template: { name: '', description: '' },
+  },
+};
+
+const $ = (id) => document.getElementById(id);
+
+class ApiError extends Error {
+  constructor(status, problem) {
+    super(problem && problem.detail ? problem.detail : `HTTP ${status}`);
+    this.status = status;
+    this.code = problem && problem.code;
+  }
+}
+
+async function api(method, path, body) {
+  const headers = { Accept: 'application/json' };
+  const token = sessionStorage.getItem(TOKEN_KEY);
+  if (token) headers.Authorization = `Bearer ${token}`;
+  if (body !== undefined) headers['Content-Type'] = 'application/json';
+
+  const response = await fetch(path, {
+    method,
+    headers,
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  const text = await response.text();
+  const data = text ? JSON.parse(text) : null;
+  if (!response.ok) {
+    if (response.status === 401) showLogin();
+    throw new ApiError(response.status, data);
+  }
+  return data;
+}
+
+function showError(message) {
+  const box = $('error');
+  box.textContent = message || '';
+  box.hidden = !message;
+}
+
+function showLogin() {
+  sessionStorage.removeItem(TOKEN_KEY);
+  $('login').hidden = false;
+  $('view').replaceChildren();
+  $('whoami').textContent = '';
+}
+
+async function checkSession() {
+  try {
+    const me = await api('GET', '/api/v1/admin/whoami');
+    $('whoami').textContent = me.name;
+  } catch (e) {
+    // 404 means auth is disabled on the server; anything else needs a token
+    if (e.status !== 404) return false;
+    $('whoami').textContent = 'auth disabled';
+  }
+  $('login').hidden = true;
+  return true;
+}
+
+function formatCell(value) {
+  if (value === undefined || value === null) return '';
+  if (Array.isArray(value)) return value.join(', ');
+  return String(value);
+}
+
+function button(label, onClick, className) {
+  const el = document.createElement('button');
+  el.type = 'button';
+  el.textContent = label;
+  if (className) el.className = className;
+  el.addEventListener('click', onClick);
+  return el;
+}
+
+function itemUrl(resource, key) {
+  return `${resource.path}/${encodeURIComponent(key)}`;
+}
+
+async function renderList(name, offset) {
+  const resource = RESOURCES[name];
+  const query = resource.unpaginated ? '' : `?limit=${PAGE_SIZE}&offset=${offset}`;
+  const result = await api('GET', resource.path + query);
+  const items = Array.isArray(result) ? result : result.data;
+
+  const toolbar = document.createElement('div');
+  toolbar.className = 'toolbar';
+  const heading = document.createElement('h2');
+  heading.textContent = resource.title;
+  toolbar.append(heading, button('Refresh', () => route()), button('New', () => openEditor(name, null)));
+
+  const table = document.createElement('table');
+  const head = table.createTHead().insertRow();
+  for (const column of resource.columns) {
+    const th = document.createElement('th');
+    th.textContent = column;
+    head.appendChild(th);
+  }
+  head.appendChild(document.createElement('th'));
+
+  const body = table.createTBody();
+  for (const item of items) {
+    const row = body.insertRow();
+    for (const column of resource.columns) {
+      const cell = row.insertCell();
+      cell.textContent = formatCell(item[column]);
+      if (column === 'public_key' || column === 'fee_recipient') cell.className = 'mono';
+    }
+    const actions = row.insertCell();
+    actions.className = 'actions';
+    actions.append(
+      button('Edit', () => openEditor(name, item)),
+      button('Delete', () => deleteItem(name, item), 'danger'),
+    );
+  }
+
+  const children = [toolbar, table];
+  if (!resource.unpaginated) {
+    const pager = document.createElement('div');
+    pager.className = 'pager';
+    const info = document.createElement('span');
+    info.textContent = `${result.total === 0 ? 0 : offset + 1}-${offset + items.length} of ${result.total}`;
+    const prev = button('Previous', () => renderPage(name, Math.max(0, offset - PAGE_SIZE)));
+    prev.disabled = offset === 0;
+    const next = button('Next', () => renderPage(name, offset + PAGE_SIZE));
+    next.disabled = !result.has_more;
+    pager.append(prev, info, next);
+    children.push(pager);
+  }
+  $('view').replaceChildren(...children);
+}
+
+async function renderPage(name, offset) {
+  showError('');
+  try {
+    await renderList(name, offset);
+  } catch (e) {
+    showError(e.message);
+  }
+}
+
+function editableBody(resource, item) {
+  const body = {};
+  for (const [field, value] of Object.entries(item)) {
+    if (READ_ONLY.includes(field) || field === resource.key) continue;
+    body[field] = value;
+  }
+  return body;
+}
+
+async function openEditor(name, item) {
+  const resource = RESOURCES[name];
+  const creating = item === null;
+  let body = resource.template;
+
+  showError('');
+  if (!creating) {
+    // Lists can omit nested fields (relays, keys), so edit the full resource
+    const detail = name === 'tokens' ? item : await api('GET', itemUrl(resource, item[resource.key]));
+    body = editableBody(resource, detail);
+  }
+
+  const keyInput = $('editor-key');
+  const showKey = creating ? resource.create === 'PUT' || resource.keyInBody : true;
+  $('editor-key-label').hidden = !showKey || name === 'tokens';
+  $('editor-key-name').textContent = resource.key;
+  keyInput.value = creating ? '' : item[resource.key];
+  keyInput.readOnly = !creating;
+  keyInput.required = creating && showKey && name !== 'tokens';
+
+  $('editor-title').textContent = `${creating ? 'New' : 'Edit'} ${resource.title.replace(/s$/, '')}`;
+  $('editor-body').value = JSON.stringify(body, null, 2);
+  $('editor-error').hidden = true;
+  $('editor').dataset.resource = name;
+  $('editor').dataset.creating = creating ? 'true' : '';
+  $('editor').showModal();
+}
+
+async function saveEditor(event) {
+  const dialog = $('editor');
+  if (event.submitter && event.submitter.value !== 'save') return;
+  event.preventDefault();
+
+  const name = dialog.dataset.resource;
+  const resource = RESOURCES[name];
+  const creating = dialog.dataset.creating === 'true';
+  const key = $('editor-key').value.trim();
+  const errorBox = $('editor-error');
+
+  let body;
+  try {
+    body = JSON.parse($('editor-body').value || '{}');
+  } catch (e) {
+    errorBox.textContent = `Invalid JSON: ${e.message}`;
+    errorBox.hidden = false;
+    return;
+  }
+
+  try {
+    let result;
+    if (creating && resource.create === 'POST') {
+      if (resource.keyInBody) body[resource.key] = key;
+      result = await api('POST', resource.path, body);
+    } else {
+      result = await api(creating ? resource.create : resource.update || 'PUT', itemUrl(resource, key), body);
+    }
+    dialog.close();
+    await route();
+    if (name === 'tokens' && result && result.token) {
+      const notice = document.createElement('div');
+      notice.className = 'notice';
+      notice.textContent = `New token (shown only once): ${result.token}`;
+      $('view').prepend(notice);
+    }
+  } catch (e) {
+    errorBox.textContent = e.code ? `${e.code}: ${e.message}` : e.message;
+    errorBox.hidden = false;
+  }
+}
+
+async function deleteItem(name, item) {
+  const resource = RESOURCES[name];
+  const key = item[resource.key];
+  const label = name === 'tokens' ? item.name : key;
+  if (!confirm(`Delete ${label}?`)) return;
+  try {
+    await api('DELETE', itemUrl(resource, key));
+    await route();
+  } catch (e) {
+    showError(e.message);
+  }
+}
+
+async function route() {
+  const name = location.hash.replace(/^#\//, '') || 'configs';
+  for (const link of document.querySelectorAll('#nav a')) {
+    link.classList.toggle('active', link.getAttribute('href') === `#/${name}`);
+  }
+  if (!RESOURCES[name]) {
+    showError(`Unknown page: ${name}`);
+    return;
+  }
+  if (!(await checkSession())) {
+    showLogin();
+    return;
+  }
+  await renderPage(name, 0);
+}
+
+$('login-form').addEventListener('submit', async (event) => {
+  event.preventDefault();
+  sessionStorage.setItem(TOKEN_KEY, $('token').value.trim());
+  $('token').value = '';
+  await route();
+});
+$('logout').addEventListener('click', showLogin);
+$('editor-form').addEventListener('submit', saveEditor);
+window.addEventListener('hashchange', route);
+route();